@@ -30,6 +30,7 @@ use manta_backend_dispatcher::{
   },
   types::{
     bos::session_template::BosSessionTemplate,
+    capability::{Capabilities, Capability},
     cfs::{
       cfs_configuration_details::LayerDetails,
       cfs_configuration_request::CfsConfigurationRequest,
@@ -38,6 +39,7 @@ use manta_backend_dispatcher::{
     },
     hsm::inventory::RedfishEndpointArray as FrontEndRedfishEndpointArray,
     ims::Image as FrontEndImage,
+    pcs::power_cap::types::PowerCapTaskInfo as FrontEndPowerCapTaskInfo,
     pcs::power_status::types::{
       //PowerStatus as FrontEndPowerStatus,
       PowerStatusAll as FrontEndPowerStatusAll,
@@ -64,9 +66,17 @@ use crate::{
     self, component::types::ComponentArrayPostArray, group::types::Member,
   },
   node::console,
-  pcs::{self},
+  pcs::{
+    self,
+    power_cap::types::{
+      PowerCapControl, PowerCapPatch, PowerCapPatchComponent,
+    },
+  },
 };
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Clone)]
 pub struct Csm {
   base_url: String,
@@ -80,6 +90,35 @@ impl Csm {
       root_cert: root_cert.to_vec(),
     }
   }
+
+  /// Creates a power transition and returns its ID without waiting for it to
+  /// complete
+  async fn create_power_transition(
+    &self,
+    auth_token: &str,
+    operation: &str,
+    nodes: &[String],
+  ) -> Result<String, Error> {
+    let transition = pcs::transitions::http_client::post(
+      &self.base_url,
+      auth_token,
+      &self.root_cert,
+      operation,
+      &nodes.to_vec(),
+    )
+    .await
+    .map_err(Error::from)?;
+
+    transition["transitionID"]
+      .as_str()
+      .map(str::to_string)
+      .ok_or_else(|| {
+        Error::Message(format!(
+          "PCS transition ID missing in response:\n{}",
+          transition
+        ))
+      })
+  }
 }
 
 impl GroupTrait for Csm {
@@ -87,10 +126,8 @@ impl GroupTrait for Csm {
     &self,
     auth_token: &str,
   ) -> Result<Vec<FrontEndGroup>, Error> {
-    let mut group_vec = self
-      .get_all_groups(auth_token)
-      .await
-      .map_err(|e| Error::Message(e.to_string()))?;
+    let mut group_vec =
+      self.get_all_groups(auth_token).await.map_err(Error::from)?;
 
     let available_groups_name =
       self.get_group_name_available(auth_token).await?;
@@ -110,7 +147,7 @@ impl GroupTrait for Csm {
       &self.root_cert,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn add_group(
@@ -125,7 +162,7 @@ impl GroupTrait for Csm {
       group.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     // let group: FrontEndGroup = group_csm.into();
     log::info!("Group created: {}", group_csm);
@@ -147,7 +184,7 @@ impl GroupTrait for Csm {
       hsm_group_name_vec,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_group_map_and_filter_by_group_vec(
@@ -162,7 +199,7 @@ impl GroupTrait for Csm {
       hsm_name_vec,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_group_map_and_filter_by_member_vec(
@@ -177,7 +214,7 @@ impl GroupTrait for Csm {
       member_vec,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_all_groups(
@@ -191,7 +228,7 @@ impl GroupTrait for Csm {
       &self.root_cert,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     // Convert all HSM groups from mesa to infra
     let hsm_group_vec = hsm_group_backend_vec
@@ -216,7 +253,7 @@ impl GroupTrait for Csm {
       None,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     // Error if more than one HSM group found
     if hsm_group_backend_vec.len() > 1 {
@@ -226,7 +263,14 @@ impl GroupTrait for Csm {
       )));
     }
 
-    let hsm_group_backend = hsm_group_backend_vec.first().unwrap().to_owned();
+    let hsm_group_backend =
+      hsm_group_backend_vec.first().cloned().ok_or_else(|| {
+        Error::NotFound {
+          service: "HSM".to_string(),
+          resource: "group".to_string(),
+          id: hsm_name.to_string(),
+        }
+      })?;
 
     let hsm_group: FrontEndGroup = hsm_group_backend.into();
 
@@ -247,7 +291,7 @@ impl GroupTrait for Csm {
       None,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     // Convert from HsmGroup (silla) to HsmGroup (infra)
     let mut hsm_group_vec = Vec::new();
@@ -271,7 +315,7 @@ impl GroupTrait for Csm {
       &label.to_string(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_hsm_map_and_filter_by_hsm_name_vec(
@@ -286,7 +330,7 @@ impl GroupTrait for Csm {
       hsm_name_vec,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn post_member(
@@ -307,7 +351,7 @@ impl GroupTrait for Csm {
       member,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn add_members_to_group(
@@ -327,7 +371,7 @@ impl GroupTrait for Csm {
         new_member,
       )
       .await
-      .map_err(|e| Error::Message(e.to_string()))?;
+      .map_err(Error::from)?;
     }
 
     Ok(sol)
@@ -347,7 +391,7 @@ impl GroupTrait for Csm {
       xname,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn update_group_members(
@@ -366,7 +410,7 @@ impl GroupTrait for Csm {
       members_to_add,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   // HSM/GROUP
@@ -387,7 +431,7 @@ impl GroupTrait for Csm {
       true,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -404,10 +448,9 @@ impl HardwareInventory for Csm {
       xname,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
     .and_then(|hw_inventory| {
-      serde_json::to_value(hw_inventory)
-        .map_err(|e| Error::Message(e.to_string()))
+      serde_json::to_value(hw_inventory).map_err(Error::from)
     })
   }
 
@@ -428,7 +471,7 @@ impl HardwareInventory for Csm {
       xname,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn post_inventory_hardware(
@@ -443,7 +486,7 @@ impl HardwareInventory for Csm {
       hw_inventory.into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -480,7 +523,7 @@ impl ComponentTrait for Csm {
     )
     .await
     .map(|c| c.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_node_metadata_available(
@@ -490,7 +533,7 @@ impl ComponentTrait for Csm {
     let xname_available_vec: Vec<String> = self
       .get_group_available(auth_token)
       .await
-      .map_err(|e| Error::Message(e.to_string()))?
+      .map_err(Error::from)?
       .iter()
       .flat_map(|group| group.get_members())
       .collect();
@@ -564,7 +607,7 @@ impl ComponentTrait for Csm {
     )
     .await
     .map(|c| c.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn post_nodes(
@@ -581,7 +624,7 @@ impl ComponentTrait for Csm {
       component_backend,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn delete_node(
@@ -590,13 +633,13 @@ impl ComponentTrait for Csm {
     id: &str,
   ) -> Result<Value, Error> {
     hsm::component::http_client::delete_one(
-      auth_token,
       &self.base_url,
+      auth_token,
       &self.root_cert,
       id,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -616,7 +659,7 @@ impl PCSTrait for Csm {
       &nodes.to_vec(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn power_off_sync(
@@ -635,7 +678,7 @@ impl PCSTrait for Csm {
       &nodes.to_vec(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn power_reset_sync(
@@ -658,7 +701,73 @@ impl PCSTrait for Csm {
       &nodes.to_vec(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
+  }
+
+  async fn power_on(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+  ) -> Result<String, Error> {
+    self.create_power_transition(auth_token, "on", nodes).await
+  }
+
+  async fn power_off(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    let operation = if force { "force-off" } else { "soft-off" };
+
+    self
+      .create_power_transition(auth_token, operation, nodes)
+      .await
+  }
+
+  async fn power_reset(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    let operation = if force {
+      "hard-restart"
+    } else {
+      "soft-restart"
+    };
+
+    self
+      .create_power_transition(auth_token, operation, nodes)
+      .await
+  }
+
+  async fn get_power_transition(
+    &self,
+    auth_token: &str,
+    transition_id: &str,
+  ) -> Result<Value, Error> {
+    pcs::transitions::http_client::get_by_id(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      transition_id,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_power_transitions(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Value>, Error> {
+    pcs::transitions::http_client::get(
+      &self.base_url,
+      auth_token,
+      &self.root_cert,
+    )
+    .await
+    .map_err(Error::from)
   }
 
   async fn power_status(
@@ -687,7 +796,54 @@ impl PCSTrait for Csm {
       println!("return value from async fn power_status : {:?}", status);
       status.into()
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
+  }
+
+  async fn power_cap_snapshot(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+  ) -> Result<FrontEndPowerCapTaskInfo, Error> {
+    pcs::power_cap::http_client::post_snapshot_block(
+      &self.base_url,
+      auth_token,
+      &self.root_cert,
+      nodes.iter().map(String::as_str).collect(),
+    )
+    .await
+    .map(|task| task.into())
+    .map_err(Error::from)
+  }
+
+  async fn power_cap_patch(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    control_name: &str,
+    watts: usize,
+  ) -> Result<FrontEndPowerCapTaskInfo, Error> {
+    let power_cap = PowerCapPatch {
+      components: nodes
+        .iter()
+        .map(|xname| PowerCapPatchComponent {
+          xname: xname.clone(),
+          controls: vec![PowerCapControl {
+            name: control_name.to_string(),
+            value: watts,
+          }],
+        })
+        .collect(),
+    };
+
+    pcs::power_cap::http_client::patch_block(
+      &self.base_url,
+      auth_token,
+      &self.root_cert,
+      power_cap,
+    )
+    .await
+    .map(|task| task.into())
+    .map_err(Error::from)
   }
 }
 
@@ -699,7 +855,7 @@ impl BootParametersTrait for Csm {
     let boot_parameter_vec =
       bss::http_client::get_all(auth_token, &self.base_url, &self.root_cert)
         .await
-        .map_err(|e| Error::Message(e.to_string()))?;
+        .map_err(Error::from)?;
 
     let boot_parameter_infra_vec = boot_parameter_vec
       .into_iter()
@@ -721,7 +877,7 @@ impl BootParametersTrait for Csm {
       nodes,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     let boot_parameter_infra_vec = boot_parameter_vec
       .into_iter()
@@ -742,7 +898,7 @@ impl BootParametersTrait for Csm {
       &self.root_cert,
       boot_parameters.clone().into(),
     )
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn update_bootparameters(
@@ -757,7 +913,7 @@ impl BootParametersTrait for Csm {
       &boot_parameter.clone().into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn delete_bootparameters(
@@ -765,9 +921,8 @@ impl BootParametersTrait for Csm {
     _auth_token: &str,
     _boot_parameters: &FrontEndBootParameters,
   ) -> Result<String, Error> {
-    Err(Error::Message(
-      "Delete boot parameters command not implemented for this backend"
-        .to_string(),
+    Err(Error::NotImplemented(
+      "Delete boot parameters command".to_string(),
     ))
   }
 }
@@ -777,9 +932,8 @@ impl RedfishEndpointTrait for Csm {
     &self,
     _auth_token: &str,
   ) -> Result<FrontEndRedfishEndpointArray, Error> {
-    Err(Error::Message(
-      "Get all redfish endpoint command not implemented for this backend"
-        .to_string(),
+    Err(Error::NotImplemented(
+      "Get all redfish endpoint command".to_string(),
     ))
   }
   async fn get_redfish_endpoints(
@@ -793,9 +947,8 @@ impl RedfishEndpointTrait for Csm {
     _ip_address: Option<&str>,
     _last_status: Option<&str>,
   ) -> Result<FrontEndRedfishEndpointArray, Error> {
-    Err(Error::Message(
-      "Get redfish endpoint command not implemented for this backend"
-        .to_string(),
+    Err(Error::NotImplemented(
+      "Get redfish endpoint command".to_string(),
     ))
   }
 
@@ -804,9 +957,8 @@ impl RedfishEndpointTrait for Csm {
     _auth_token: &str,
     _redfish_endpoint: &manta_backend_dispatcher::types::hsm::inventory::RedfishEndpointArray,
   ) -> Result<(), Error> {
-    Err(Error::Message(
-      "Add redfish endpoint command not implemented for this backend"
-        .to_string(),
+    Err(Error::NotImplemented(
+      "Add redfish endpoint command".to_string(),
     ))
   }
 
@@ -815,9 +967,8 @@ impl RedfishEndpointTrait for Csm {
     _auth_token: &str,
     _redfish_endpoint: &manta_backend_dispatcher::types::hsm::inventory::RedfishEndpoint,
   ) -> Result<(), Error> {
-    Err(Error::Message(
-      "Update redfish endpoint command not implemented for this backend"
-        .to_string(),
+    Err(Error::NotImplemented(
+      "Update redfish endpoint command".to_string(),
     ))
   }
 
@@ -826,9 +977,8 @@ impl RedfishEndpointTrait for Csm {
     _auth_token: &str,
     _id: &str,
   ) -> Result<Value, Error> {
-    Err(Error::Message(
-      "Delete redfish endpoint command not implemented for this backend"
-        .to_string(),
+    Err(Error::NotImplemented(
+      "Delete redfish endpoint command".to_string(),
     ))
   }
 }
@@ -839,6 +989,14 @@ impl BackendTrait for Csm {
     "in mesa backend".to_string()
   }
 
+  fn capabilities(&self) -> Capabilities {
+    // Redfish endpoints are not implemented for CSM yet
+    Capabilities::all()
+      .iter()
+      .filter(|capability| *capability != Capability::RedfishEndpoint)
+      .collect()
+  }
+
   async fn get_api_token(&self, site_name: &str) -> Result<String, Error> {
     // FIXME: this is not nice but authentication/authorization will potentially move out to an
     // external crate since this is type of logic is external to each site ...
@@ -856,7 +1014,7 @@ impl BackendTrait for Csm {
       site_name,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   /// Get list of xnames from NIDs
@@ -887,7 +1045,7 @@ impl BackendTrait for Csm {
         Some("true"),
       )
       .await
-      .map_err(|e| Error::Message(e.to_string()))?
+      .map_err(Error::from)?
       .components
       .unwrap_or_default();
 
@@ -979,7 +1137,7 @@ impl BackendTrait for Csm {
         Some("true"),
       )
       .await
-      .map_err(|e| Error::Message(e.to_string()))?;
+      .map_err(Error::from)?;
 
       // Get list of xnames from HSM components
       let xname_vec: Vec<String> = hsm_components
@@ -1081,7 +1239,7 @@ impl CfsTrait for Csm {
       None,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     crate::cfs::session::utils::filter_by_xname(
       auth_token,
@@ -1115,7 +1273,7 @@ impl CfsTrait for Csm {
   async fn get_cfs_health(&self) -> Result<(), Error> {
     crate::cfs::health::test_connectivity_to_backend(self.base_url.as_str())
       .await
-      .map_err(|e| Error::Message(e.to_string()))
+      .map_err(Error::from)
   }
 
   async fn post_session(
@@ -1133,7 +1291,7 @@ impl CfsTrait for Csm {
     )
     .await
     .map(|cfs_session| cfs_session.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   /// Fetch CFS sessions ref --> https://apidocs.svc.cscs.ch/paas/cfs/operation/get_sessions/
@@ -1177,7 +1335,7 @@ impl CfsTrait for Csm {
           .map(|cfs_session| cfs_session.into())
           .collect::<Vec<CfsSessionGetResponse>>()
       })
-      .map_err(|e| Error::Message(e.to_string()));
+      .map_err(Error::from);
 
     border_session_vec
   }
@@ -1221,7 +1379,7 @@ impl CfsTrait for Csm {
           true,
         )
         .await
-        .map_err(|e| Error::Message(e.to_string()))?;
+        .map_err(Error::from)?;
       }
     }
 
@@ -1367,7 +1525,7 @@ impl CfsTrait for Csm {
             repo_name_vec,
             local_git_commit_vec,
             playbook_file_name_opt,
-        ).await.map_err(|e| Error::Message(e.to_string()))?.into())
+        ).await.map_err(Error::from)?.into())
   }
 
   async fn get_configuration(
@@ -1385,7 +1543,7 @@ impl CfsTrait for Csm {
         configuration_name_opt.map(|elem| elem.as_str()),
       )
       .await
-      .map_err(|e| Error::Message(e.to_string()));
+      .map_err(Error::from);
 
     cfs_configuration_vec
       .map(|config_vec| config_vec.into_iter().map(|c| c.into()).collect())
@@ -1412,7 +1570,7 @@ impl CfsTrait for Csm {
     )
     .await
     .map(|config_vec| config_vec.into_iter().map(|c| c.into()).collect())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_configuration_layer_details(
@@ -1432,7 +1590,7 @@ impl CfsTrait for Csm {
     )
     .await
     .map(|layer_details| layer_details.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn put_configuration(
@@ -1452,7 +1610,7 @@ impl CfsTrait for Csm {
     )
     .await
     .map(|config| config.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   /// Fetch CFS sessions ref --> https://apidocs.svc.cscs.ch/paas/cfs/operation/get_sessions/
@@ -1483,7 +1641,7 @@ impl CfsTrait for Csm {
       is_succeded_opt,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     crate::cfs::session::utils::filter_by_xname(
       shasta_token,
@@ -1523,7 +1681,7 @@ impl CfsTrait for Csm {
       enabled,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   // Get all CFS sessions, IMS images and BOS sessiontemplates related to a CFS configuration
@@ -1567,7 +1725,7 @@ impl CfsTrait for Csm {
         }),
       )
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_cfs_components(
@@ -1597,7 +1755,7 @@ impl CfsTrait for Csm {
         .map(|component| component.into())
         .collect()
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1642,7 +1800,7 @@ impl SatTrait for Csm {
       dry_run,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1671,7 +1829,34 @@ impl ApplyHwClusterPin for Csm {
       delete_empty_parent_hsm_group,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
+  }
+
+  async fn apply_hw_cluster_unpin(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    target_hsm_group_name: &str,
+    parent_hsm_group_name: &str,
+    pattern: &str,
+    nodryrun: bool,
+    create_target_hsm_group: bool,
+    delete_empty_parent_hsm_group: bool,
+  ) -> Result<(), Error> {
+    crate::commands::apply_hw_cluster_unpin::command::exec(
+      shasta_token,
+      shasta_base_url,
+      shasta_root_cert,
+      target_hsm_group_name,
+      parent_hsm_group_name,
+      pattern,
+      nodryrun,
+      create_target_hsm_group,
+      delete_empty_parent_hsm_group,
+    )
+    .await
+    .map_err(Error::from)
   }
 }
 
@@ -1691,7 +1876,7 @@ impl ImsTrait for Csm {
     )
     .await
     .map(|image_vec| image_vec.into_iter().map(|image| image.into()).collect())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_all_images(
@@ -1707,7 +1892,7 @@ impl ImsTrait for Csm {
     )
     .await
     .map(|image_vec| image_vec.into_iter().map(|image| image.into()).collect())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   fn filter_images(
@@ -1736,7 +1921,7 @@ impl ImsTrait for Csm {
       image_id,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1779,7 +1964,7 @@ impl ApplySessionTrait for Csm {
       k8s, */
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1806,7 +1991,7 @@ impl MigrateRestoreTrait for Csm {
       image_dir,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1827,7 +2012,7 @@ impl MigrateBackupTrait for Csm {
       destination,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1856,7 +2041,7 @@ impl GetImagesAndDetailsTrait for Csm {
         .map(|(image, x, y, z)| (image.into(), x, y, z))
         .collect()
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1875,7 +2060,7 @@ impl ClusterSessionTrait for Csm {
       bos_session.into(),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -1900,7 +2085,7 @@ impl ClusterTemplateTrait for Csm {
         .map(|template| template.into())
         .collect::<Vec<BosSessionTemplate>>()
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn get_and_filter_templates(
@@ -1920,7 +2105,7 @@ impl ClusterTemplateTrait for Csm {
       bos_sessiontemplate_name_opt.map(|value| value.as_str()),
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))?;
+    .map_err(Error::from)?;
 
     bos::template::utils::filter(
       &mut bos_sessiontemplate_vec,
@@ -1955,7 +2140,7 @@ impl ClusterTemplateTrait for Csm {
         .map(|template| template.into())
         .collect::<Vec<BosSessionTemplate>>()
     })
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn put_template(
@@ -1975,7 +2160,7 @@ impl ClusterTemplateTrait for Csm {
     )
     .await
     .map(|bos_session_template| bos_session_template.into())
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn delete_template(
@@ -1992,7 +2177,7 @@ impl ClusterTemplateTrait for Csm {
       bos_template_id,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -2017,7 +2202,7 @@ impl CommandsTrait for Csm {
       assume_yes,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 
   async fn i_delete_data_related_to_cfs_configuration(
@@ -2044,7 +2229,7 @@ impl CommandsTrait for Csm {
       assume_yes,
     )
     .await
-    .map_err(|e| Error::Message(e.to_string()))
+    .map_err(Error::from)
   }
 }
 
@@ -2076,7 +2261,7 @@ impl ConsoleTrait for Csm {
       } => {
         fetch_shasta_k8s_secrets_from_vault(&base_url, shasta_token, &site_name)
           .await
-          .map_err(|e| Error::Message(e.to_string()))?
+          .map_err(Error::from)?
       }
     };
 
@@ -2087,7 +2272,7 @@ impl ConsoleTrait for Csm {
         shasta_k8s_secrets,
       )
       .await
-      .map_err(|e| Error::Message(e.to_string()))?;
+      .map_err(Error::from)?;
 
     let mut terminal_size_writer: Sender<TerminalSize> =
       attached.terminal_size().unwrap();
@@ -0,0 +1,470 @@
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{
+    bos::ClusterTemplateTrait,
+    bss::BootParametersTrait,
+    cfs::CfsTrait,
+    hsm::{
+      component::ComponentTrait, group::GroupTrait,
+      hardware_inventory::HardwareInventory,
+    },
+    ims::ImsTrait,
+    pcs::PCSTrait,
+  },
+  types::{
+    bos::session_template::BosSessionTemplate,
+    cfs::{
+      cfs_configuration_request::CfsConfigurationRequest,
+      session::CfsSessionPostRequest,
+    },
+    BootParameters, ComponentArrayPostArray, Group,
+  },
+};
+use serde_json::json;
+
+use crate::stand_in::{self, CsmStandIn, CsmStore, Fault};
+
+use super::Csm;
+
+fn store() -> CsmStore {
+  serde_json::from_value(json!({
+    "groups": [
+      { "label": "zinal", "description": "", "members": { "ids": ["x1000c0s0b0n0", "x1000c0s0b0n1"] } },
+      { "label": "nodes_free", "description": "", "members": { "ids": ["x1000c0s1b0n0"] } },
+    ],
+    "components": [
+      { "ID": "x1000c0s0b0n0", "Type": "Node", "State": "Ready", "NID": 1, "Role": "Compute", "Enabled": true },
+      { "ID": "x1000c0s0b0n1", "Type": "Node", "State": "Ready", "NID": 2, "Role": "Compute", "Enabled": true },
+      { "ID": "x1000c0s1b0n0", "Type": "Node", "State": "Off", "NID": 3, "Role": "Compute", "Enabled": true },
+    ],
+    "hardware_inventory": {
+      "x1000c0s0b0n0": {
+        "XName": "x1000c0s0b0n0",
+        "Format": "NestNodesOnly",
+        "Nodes": [{ "ID": "x1000c0s0b0n0", "Type": "Node", "Status": "Populated" }],
+      },
+    },
+    "boot_parameters": [
+      { "hosts": ["x1000c0s0b0n0"], "params": "console=ttyS0", "kernel": "s3://boot-images/1234/kernel", "initrd": "s3://boot-images/1234/initrd" },
+    ],
+    "ims_images": [
+      { "id": "1234", "name": "compute-image", "created": "2024-01-01T00:00:00Z" },
+    ],
+    "power_state": { "x1000c0s0b0n0": "on", "x1000c0s0b0n1": "on", "x1000c0s1b0n0": "off" },
+  }))
+  .unwrap()
+}
+
+async fn setup() -> (CsmStandIn, Csm, String) {
+  let stand_in = CsmStandIn::start(store()).await.unwrap();
+  let csm = Csm::new(stand_in.base_url(), &[]);
+  let auth_token = stand_in::auth_token(&["pa_admin"]);
+
+  (stand_in, csm, auth_token)
+}
+
+#[tokio::test]
+async fn test_get_all_groups() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let group_vec = csm.get_all_groups(&auth_token).await.unwrap();
+
+  assert_eq!(group_vec.len(), 2);
+  assert_eq!(
+    group_vec[0].get_members(),
+    vec!["x1000c0s0b0n0".to_string(), "x1000c0s0b0n1".to_string()]
+  );
+}
+
+#[tokio::test]
+async fn test_add_and_delete_group() {
+  let (stand_in, csm, auth_token) = setup().await;
+
+  let group: Group =
+    serde_json::from_value(json!({ "label": "davos", "description": "" }))
+      .unwrap();
+
+  csm.add_group(&auth_token, group).await.unwrap();
+
+  assert_eq!(
+    csm.get_group(&auth_token, "davos").await.unwrap().label,
+    "davos"
+  );
+
+  csm.delete_group(&auth_token, "davos").await.unwrap();
+
+  assert!(stand_in.store().find_group("davos").is_none());
+}
+
+#[tokio::test]
+async fn test_add_group_already_exists() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let group: Group =
+    serde_json::from_value(json!({ "label": "zinal", "description": "" }))
+      .unwrap();
+
+  let error = csm.add_group(&auth_token, group).await.unwrap_err();
+
+  assert!(
+    matches!(&error, Error::Conflict { service, .. } if service == "HSM"),
+    "{error:?}"
+  );
+  assert_eq!(error.http_status(), 409);
+}
+
+#[tokio::test]
+async fn test_get_group_not_found() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let error = csm.get_group(&auth_token, "davos").await.unwrap_err();
+
+  assert!(
+    matches!(&error, Error::NotFound { id, .. } if id == "davos"),
+    "{error:?}"
+  );
+}
+
+#[tokio::test]
+async fn test_delete_group_not_found() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let error = csm.delete_group(&auth_token, "davos").await.unwrap_err();
+
+  assert!(
+    matches!(&error, Error::NotFound { service, id, .. } if service == "HSM" && id == "davos"),
+    "{error:?}"
+  );
+  assert_eq!(error.http_status(), 404);
+}
+
+#[tokio::test]
+async fn test_migrate_group_members() {
+  let (stand_in, csm, auth_token) = setup().await;
+
+  csm
+    .migrate_group_members(
+      &auth_token,
+      "zinal",
+      "nodes_free",
+      vec!["x1000c0s1b0n0"],
+    )
+    .await
+    .unwrap();
+
+  assert!(stand_in
+    .store()
+    .get_group_members("zinal")
+    .unwrap()
+    .contains(&"x1000c0s1b0n0".to_string()));
+  assert!(stand_in
+    .store()
+    .get_group_members("nodes_free")
+    .unwrap()
+    .is_empty());
+}
+
+#[tokio::test]
+async fn test_post_and_delete_nodes() {
+  let (stand_in, csm, auth_token) = setup().await;
+
+  let component_vec: ComponentArrayPostArray = serde_json::from_value(json!({
+    "Components": [{ "ID": "x1000c0s2b0n0", "State": "Ready", "NID": 4 }],
+  }))
+  .unwrap();
+
+  csm.post_nodes(&auth_token, component_vec).await.unwrap();
+
+  assert_eq!(stand_in.store().components.len(), 4);
+
+  csm.delete_node(&auth_token, "x1000c0s2b0n0").await.unwrap();
+
+  assert_eq!(stand_in.store().components.len(), 3);
+}
+
+#[tokio::test]
+async fn test_get_inventory_hardware_query() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let hw_inventory = csm
+    .get_inventory_hardware_query(
+      &auth_token,
+      "x1000c0s0b0n0",
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(hw_inventory["Nodes"][0]["ID"], "x1000c0s0b0n0");
+}
+
+#[tokio::test]
+async fn test_power_status() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let power_status = csm
+    .power_status(
+      &auth_token,
+      &["x1000c0s0b0n0".to_string(), "x1000c0s1b0n0".to_string()],
+      Some("off"),
+      None,
+    )
+    .await
+    .unwrap();
+
+  let power_status = serde_json::to_value(power_status).unwrap();
+
+  assert_eq!(power_status["status"].as_array().unwrap().len(), 1);
+  assert_eq!(power_status["status"][0]["xname"], "x1000c0s1b0n0");
+}
+
+#[tokio::test]
+async fn test_power_on_sync() {
+  let (stand_in, csm, auth_token) = setup().await;
+
+  csm
+    .power_on_sync(&auth_token, &["x1000c0s1b0n0".to_string()])
+    .await
+    .unwrap();
+
+  assert_eq!(stand_in.store().power_state["x1000c0s1b0n0"], "on");
+}
+
+#[tokio::test]
+async fn test_update_bootparameters() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let mut boot_parameters = csm
+    .get_bootparameters(&auth_token, &["x1000c0s0b0n0".to_string()])
+    .await
+    .unwrap()
+    .remove(0);
+
+  boot_parameters.params = "console=ttyS0 quiet".to_string();
+
+  csm
+    .update_bootparameters(&auth_token, &boot_parameters)
+    .await
+    .unwrap();
+
+  let boot_parameters = csm
+    .get_bootparameters(&auth_token, &["x1000c0s0b0n0".to_string()])
+    .await
+    .unwrap();
+
+  assert_eq!(boot_parameters.len(), 1);
+  assert_eq!(boot_parameters[0].params, "console=ttyS0 quiet");
+}
+
+#[tokio::test]
+async fn test_get_all_bootparameters() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let boot_parameter_vec: Vec<BootParameters> =
+    csm.get_all_bootparameters(&auth_token).await.unwrap();
+
+  assert_eq!(
+    boot_parameter_vec[0].hosts,
+    vec!["x1000c0s0b0n0".to_string()]
+  );
+}
+
+#[tokio::test]
+async fn test_cfs_configuration_and_session() {
+  let (stand_in, csm, auth_token) = setup().await;
+  let base_url = stand_in.base_url().to_string();
+
+  let configuration: CfsConfigurationRequest = serde_json::from_value(json!({
+    "layers": [{
+      "name": "layer-1",
+      "clone_url": "https://vcs.cmn.stand-in/vcs/cray/repo.git",
+      "commit": "c0ffee",
+      "playbook": "site.yml",
+    }],
+  }))
+  .unwrap();
+
+  csm
+    .put_configuration(&auth_token, &base_url, &[], &configuration, "cfg-1")
+    .await
+    .unwrap();
+
+  let configuration_vec = csm
+    .get_configuration(&auth_token, &base_url, &[], Some(&"cfg-1".to_string()))
+    .await
+    .unwrap();
+
+  assert_eq!(configuration_vec[0].name, "cfg-1");
+
+  let session: CfsSessionPostRequest = serde_json::from_value(json!({
+    "name": "session-1",
+    "configuration_name": "cfg-1",
+    "ansible_limit": "x1000c0s0b0n0",
+    "debug_on_failure": false,
+  }))
+  .unwrap();
+
+  csm
+    .post_session(&auth_token, &base_url, &[], &session)
+    .await
+    .unwrap();
+
+  let session_vec = csm
+    .get_sessions(
+      &auth_token,
+      &base_url,
+      &[],
+      Some(&"session-1".to_string()),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(session_vec.len(), 1);
+  assert_eq!(session_vec[0].name.as_deref(), Some("session-1"));
+}
+
+#[tokio::test]
+async fn test_update_runtime_configuration() {
+  let (stand_in, csm, auth_token) = setup().await;
+  let base_url = stand_in.base_url().to_string();
+
+  csm
+    .update_runtime_configuration(
+      &auth_token,
+      &base_url,
+      &[],
+      vec!["x1000c0s0b0n0".to_string()],
+      "cfg-1",
+      true,
+    )
+    .await
+    .unwrap();
+
+  let component_vec = csm
+    .get_cfs_components(&auth_token, &base_url, &[], Some("cfg-1"), None, None)
+    .await
+    .unwrap();
+
+  assert_eq!(component_vec.len(), 1);
+  assert_eq!(component_vec[0].id.as_deref(), Some("x1000c0s0b0n0"));
+}
+
+#[tokio::test]
+async fn test_get_and_delete_image() {
+  let (stand_in, csm, auth_token) = setup().await;
+  let base_url = stand_in.base_url().to_string();
+
+  let image_vec = csm
+    .get_images(&auth_token, &base_url, &[], Some("1234"))
+    .await
+    .unwrap();
+
+  assert_eq!(image_vec[0].name, "compute-image");
+
+  csm
+    .delete_image(&auth_token, &base_url, &[], "1234")
+    .await
+    .unwrap();
+
+  // Image is soft deleted and then permanently deleted
+  assert!(stand_in.store().ims_images.is_empty());
+  assert!(stand_in.store().ims_deleted_images.is_empty());
+}
+
+#[tokio::test]
+async fn test_bos_sessiontemplate() {
+  let (stand_in, csm, auth_token) = setup().await;
+  let base_url = stand_in.base_url().to_string();
+
+  let template: BosSessionTemplate = serde_json::from_value(json!({
+    "boot_sets": {
+      "compute": {
+        "node_groups": ["zinal"],
+        "path": "s3://boot-images/1234/manifest.json",
+        "type": "s3",
+        "etag": "1234",
+      },
+    },
+    "enable_cfs": true,
+  }))
+  .unwrap();
+
+  csm
+    .put_template(&auth_token, &base_url, &[], &template, "template-1")
+    .await
+    .unwrap();
+
+  let template_vec = csm
+    .get_template(&auth_token, &base_url, &[], Some("template-1"))
+    .await
+    .unwrap();
+
+  assert_eq!(template_vec[0].name.as_deref(), Some("template-1"));
+
+  csm
+    .delete_template(&auth_token, &base_url, &[], "template-1")
+    .await
+    .unwrap();
+
+  assert!(stand_in.store().bos_sessiontemplates.is_empty());
+}
+
+#[tokio::test]
+async fn test_server_error() {
+  let (stand_in, csm, auth_token) = setup().await;
+
+  stand_in.inject_fault(Fault::new(Some("GET"), "/smd/hsm/v2/groups", 503));
+
+  let error = csm.get_all_groups(&auth_token).await.unwrap_err();
+
+  assert!(
+    matches!(&error, Error::Http { status: 503, service, .. } if service == "HSM"),
+    "{error:?}"
+  );
+
+  // Fault only applies once
+  assert!(csm.get_all_groups(&auth_token).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_invalid_token() {
+  let (stand_in, csm, auth_token) = setup().await;
+
+  stand_in.store().auth_token = Some(auth_token);
+
+  let other_auth_token = stand_in::auth_token(&["zinal"]);
+
+  let error = csm.get_all_groups(&other_auth_token).await.unwrap_err();
+
+  assert!(matches!(error, Error::Unauthorized { .. }), "{error:?}");
+  assert!(error.hint().is_some());
+  assert_eq!(stand_in.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_not_implemented() {
+  let (_stand_in, csm, auth_token) = setup().await;
+
+  let boot_parameters: BootParameters =
+    serde_json::from_value(json!({ "hosts": ["x1000c0s0b0n0"] })).unwrap();
+
+  let error = csm
+    .delete_bootparameters(&auth_token, &boot_parameters)
+    .await
+    .unwrap_err();
+
+  assert!(error.is_not_implemented());
+  assert_eq!(error.http_status(), 501);
+}
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use csm_rs::stand_in::{CsmStandIn, CsmStore};
+
+/// Runs the CSM stand-in server
+///
+/// Usage: csm-stand-in [FIXTURE_FILE] [LISTEN_ADDRESS]
+///
+/// LISTEN_ADDRESS defaults to '127.0.0.1:8080'. The server runs until Ctrl-C is pressed
+#[tokio::main]
+async fn main() {
+  let mut arg_iter = std::env::args().skip(1);
+
+  let store = match arg_iter.next().map(PathBuf::from) {
+    Some(fixture_path) => match CsmStore::from_file(&fixture_path) {
+      Ok(store) => store,
+      Err(e) => {
+        eprintln!("ERROR - {}", e);
+        std::process::exit(1);
+      }
+    },
+    None => CsmStore::default(),
+  };
+
+  let address = arg_iter.next().unwrap_or("127.0.0.1:8080".to_string());
+
+  let stand_in = match CsmStandIn::bind(&address, store).await {
+    Ok(stand_in) => stand_in,
+    Err(e) => {
+      eprintln!(
+        "ERROR - Could not start CSM stand-in server. Reason:\n{}",
+        e
+      );
+      std::process::exit(1);
+    }
+  };
+
+  println!("CSM stand-in listening on {}", stand_in.base_url());
+
+  let _ = tokio::signal::ctrl_c().await;
+}
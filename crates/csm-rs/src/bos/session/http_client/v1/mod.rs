@@ -65,11 +65,6 @@ pub async fn post(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
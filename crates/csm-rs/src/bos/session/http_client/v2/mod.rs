@@ -48,12 +48,7 @@ pub async fn post(
         .map_err(|error| Error::NetError(error))?,
     )
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -119,12 +114,7 @@ pub async fn get(
         .map_err(|error| Error::NetError(error))
     }
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -172,11 +162,6 @@ pub async fn delete(
   if response.status().is_success() {
     Ok(())
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
@@ -102,10 +102,6 @@ pub async fn post(
         .map_err(|error| Error::NetError(error))?,
     )
   } else {
-    let payload = response
-      .json()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
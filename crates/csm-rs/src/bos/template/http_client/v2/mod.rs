@@ -1,7 +1,5 @@
 pub mod types;
 
-use serde_json::Value;
-
 use crate::{
   bos::template::http_client::v2::types::BosSessionTemplate, error::Error,
 };
@@ -60,11 +58,7 @@ pub async fn get(
         .map_err(|error| Error::NetError(error))
     }
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -121,8 +115,7 @@ pub async fn put(
   if response.status().is_success() {
     response.json().await.map_err(Error::NetError)
   } else {
-    let payload = response.json::<Value>().await.map_err(Error::NetError)?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -51,11 +51,7 @@ pub async fn get(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -200,7 +196,7 @@ pub async fn put(
   if response.status().is_success() {
     Ok(response.json().await?)
   } else {
-    Err(Error::Message(response.text().await?))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -240,6 +236,6 @@ pub async fn patch(
   if response.status().is_success() {
     Ok(())
   } else {
-    Err(Error::Message(response.text().await?))
+    Err(Error::from_response(response).await)
   }
 }
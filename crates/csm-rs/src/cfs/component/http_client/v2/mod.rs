@@ -2,7 +2,6 @@ pub mod types;
 
 use std::{sync::Arc, time::Instant};
 
-use serde_json::Value;
 use tokio::sync::Semaphore;
 use types::{Component, ComponentVec};
 
@@ -47,11 +46,7 @@ pub async fn get(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -100,11 +95,7 @@ pub async fn get_single_component(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -317,11 +308,7 @@ pub async fn get_query(
       .map(|component_vec: ComponentVec| component_vec.components)
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -364,11 +351,7 @@ pub async fn put_component(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -428,10 +411,6 @@ pub async fn delete_single_component(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
@@ -45,11 +45,7 @@ pub async fn get_options(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -92,11 +88,7 @@ pub async fn get(
       .map(|component_vec| component_vec.components)
       .map_err(|e| Error::NetError(e))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -137,11 +129,7 @@ pub async fn get_single_by_id(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -270,11 +258,7 @@ pub async fn get_query(
       .map(|component_vec: ComponentVec| component_vec.components)
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -317,11 +301,7 @@ pub async fn patch_component(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -359,11 +339,7 @@ pub async fn patch_component_list(
   if response.status().is_success() {
     Ok(())
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -406,11 +382,7 @@ pub async fn put_component(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .json()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -470,10 +442,6 @@ pub async fn delete_single_component(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
@@ -1,7 +1,5 @@
 pub mod types;
 
-use serde_json::Value;
-
 use crate::{
   cfs::configuration::http_client::v2::types::{
     cfs_configuration_request::CfsConfigurationRequest,
@@ -69,12 +67,7 @@ pub async fn get(
         .map_err(|error| Error::NetError(error))
     }
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -137,12 +130,7 @@ pub async fn put(
         .map_err(|error| Error::NetError(error))?,
     )
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -182,11 +170,6 @@ pub async fn delete(
   if response.status().is_success() {
     Ok(())
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
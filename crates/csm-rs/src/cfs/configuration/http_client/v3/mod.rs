@@ -1,7 +1,5 @@
 pub mod types;
 
-use serde_json::Value;
-
 use crate::{
   cfs::configuration::http_client::v3::types::{
     cfs_configuration_request::CfsConfigurationRequest,
@@ -70,12 +68,7 @@ pub async fn get(
       Ok(payload.configurations)
     }
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -158,12 +151,7 @@ pub async fn put(
         .map_err(|error| Error::NetError(error))?,
     )
   } else {
-    let payload = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::Message(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -203,11 +191,6 @@ pub async fn delete(
   if response.status().is_success() {
     Ok(())
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
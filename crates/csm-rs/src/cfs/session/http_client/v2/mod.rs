@@ -1,7 +1,5 @@
 pub mod types;
 
-use serde_json::Value;
-
 use crate::error::Error;
 
 use super::v2::types::{CfsSessionGetResponse, CfsSessionPostRequest};
@@ -86,11 +84,7 @@ pub async fn get(
         .map_err(|error| Error::NetError(error))
     }
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -153,11 +147,7 @@ pub async fn post(
         .map_err(|error| Error::NetError(error))?,
     )
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -198,10 +188,6 @@ pub async fn delete(
   if response.status().is_success() {
     Ok(())
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
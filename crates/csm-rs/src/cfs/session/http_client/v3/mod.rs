@@ -1,7 +1,5 @@
 pub mod types;
 
-use serde_json::Value;
-
 use crate::{
   cfs::session::http_client::v3::types::{
     CfsSessionGetResponse, CfsSessionGetResponseList, CfsSessionPostRequest,
@@ -103,11 +101,7 @@ pub async fn get(
         .map_err(|error| Error::NetError(error))
     }
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -152,11 +146,7 @@ pub async fn post(
         .map_err(|error| Error::NetError(error))?,
     )
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -197,10 +187,6 @@ pub async fn delete(
   if response.status().is_success() {
     Ok(())
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
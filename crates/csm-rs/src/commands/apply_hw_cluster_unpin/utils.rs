@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use crate::{
+  commands::apply_hw_cluster_pin::utils::{
+    calculate_hsm_hw_component_summary,
+    calculate_hsm_node_scores_from_final_hsm,
+    calculate_hw_component_scarcity_scores, keep_iterating_final_hsm,
+  },
+  error::Error,
+};
+
+// Returns a tuple (target_hsm, parent_hsm) with 2 list of nodes and its hardware components.
+// The left tuple element are the nodes moved from the
+pub fn resolve_hw_description_to_xnames(
+  target_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  parent_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  user_defined_target_hsm_hw_component_count_hashmap: HashMap<String, usize>,
+) -> Result<
+  (
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, HashMap<String, usize>)>,
+  ),
+  Error,
+> {
+  let (
+    target_hsm_node_hw_component_count_vec,
+    parent_hsm_node_hw_component_count_vec,
+    _,
+    _,
+  ) = resolve_hw_description_to_xnames_with_scores(
+    target_hsm_node_hw_component_count_vec,
+    parent_hsm_node_hw_component_count_vec,
+    user_defined_target_hsm_hw_component_count_hashmap,
+  )?;
+
+  Ok((
+    target_hsm_node_hw_component_count_vec,
+    parent_hsm_node_hw_component_count_vec,
+  ))
+}
+
+/// Same as `resolve_hw_description_to_xnames` but also returns the nodes moved to the target
+/// HSM group in the order they were picked with the score they had at that moment, and the
+/// scarcity score of each hw component
+pub fn resolve_hw_description_to_xnames_with_scores(
+  target_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  parent_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  user_defined_target_hsm_hw_component_count_hashmap: HashMap<String, usize>,
+) -> Result<
+  (
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, f32)>,
+    HashMap<String, f32>,
+  ),
+  Error,
+> {
+  // *********************************************************************************************************
+  // CALCULATE 'COMBINED HSM' WITH TARGET HSM AND PARENT HSM ELEMENTS COMBINED
+  // NOTE: PARENT HSM may contain elements in TARGET HSM, we need to only add those xnames
+  // which are not part of PARENT HSM already
+
+  let mut combined_target_parent_hsm_node_hw_component_count_vec =
+    parent_hsm_node_hw_component_count_vec.clone();
+
+  for elem in &target_hsm_node_hw_component_count_vec {
+    if !parent_hsm_node_hw_component_count_vec
+      .iter()
+      .any(|(xname, _)| xname.eq(&elem.0))
+    {
+      combined_target_parent_hsm_node_hw_component_count_vec.push(elem.clone());
+    }
+  }
+
+  let combined_target_parent_hsm_hw_component_summary_hashmap =
+    calculate_hsm_hw_component_summary(
+      &combined_target_parent_hsm_node_hw_component_count_vec,
+    );
+
+  // *********************************************************************************************************
+  // CALCULATE HW COMPONENT TYPE SCORE BASED ON SCARCITY
+
+  // Get parent HSM group members
+  // Calculate nomarlized score for each hw component type in as much HSM groups as possible
+  // related to the stakeholders using these nodes
+  let hw_component_scarcity_scores_hashmap: HashMap<String, f32> =
+    calculate_hw_component_scarcity_scores(
+      &combined_target_parent_hsm_node_hw_component_count_vec,
+    );
+
+  // *********************************************************************************************************
+  // CALCULATE FINAL HSM SUMMARY COUNTERS AFTER REMOVING THE NODES THAT NEED TO GO TO TARGET
+  // HSM (SUBSTRACT USER INPUT SUMMARY FROM INITIAL COMBINED HSM SUMMARY)
+  let mut final_combined_target_parent_hsm_hw_component_summary =
+    user_defined_target_hsm_hw_component_count_hashmap.clone();
+
+  for (hw_component, qty) in
+    combined_target_parent_hsm_hw_component_summary_hashmap
+  {
+    final_combined_target_parent_hsm_hw_component_summary
+      .entry(hw_component)
+      .and_modify(|current_qty| *current_qty = qty - *current_qty);
+  }
+
+  // Calculate new target HSM group
+  let mut selected_node_score_vec: Vec<(String, f32)> = Vec::new();
+
+  let hw_component_counters_to_move_out_from_combined_hsm =
+    calculate_target_hsm_unpin(
+      &final_combined_target_parent_hsm_hw_component_summary,
+      &mut combined_target_parent_hsm_node_hw_component_count_vec,
+      &mut selected_node_score_vec,
+      &hw_component_scarcity_scores_hashmap,
+    )?;
+
+  let new_target_hsm_node_hw_component_count_vec =
+    hw_component_counters_to_move_out_from_combined_hsm;
+
+  Ok((
+    new_target_hsm_node_hw_component_count_vec,
+    combined_target_parent_hsm_node_hw_component_count_vec,
+    selected_node_score_vec,
+    hw_component_scarcity_scores_hashmap,
+  ))
+}
+
+/// Unpin means this function was defined to be used when user does not want to pin nodes in
+/// target HSM, meaning, the user does not care if the new nodes in target HSM group are new or
+/// existing nodes from original target HSM group. User cases for this:
+///  - minimize fragmentation
+///  - migrating cluster across different sites
+///  - operating deltas (eg adding or removing nodes to/from existing HSM group)
+pub fn get_best_candidate_in_hsm_unpin(
+  hsm_score_vec: &mut [(String, f32)],
+  hsm_hw_component_vec: &[(String, HashMap<String, usize>)],
+) -> Option<((String, f32), HashMap<String, usize>)> {
+  if hsm_score_vec.is_empty() || hsm_hw_component_vec.is_empty() {
+    return None;
+  }
+
+  hsm_score_vec.sort_by_key(|elem| elem.0.clone());
+  hsm_score_vec.sort_by(|b, a| a.1.total_cmp(&b.1));
+
+  // Get node with highest normalized score (best candidate)
+  let best_candidate: (String, f32) = hsm_score_vec.first()?.clone();
+
+  hsm_hw_component_vec
+    .iter()
+    .find(|(node, _)| node.eq(&best_candidate.0))
+    .map(|(_, best_candidate_counters)| {
+      (best_candidate, best_candidate_counters.clone())
+    })
+}
+
+/// Generates a list of tuples with xnames and the hardware summary for each node. This method
+/// merges both target and parent HSM groups into a single one, this is a good practice to get
+/// rid of fragmentation and also when calculating new target HSM group based on deltas.
+/// Returns a list of tuples, the first element is the xname and the last element is a hardware
+/// summary of the node
+pub fn calculate_target_hsm_unpin(
+  user_defined_hsm_hw_components_count_hashmap: &HashMap<String, usize>, // hw
+  // components summary the target hsm group should have according to user requests (this is
+  // equivalent to target_hsm_node_hw_component_count_vec minus
+  // hw_components_deltas_from_target_hsm_to_parent_hsm). Note hw componets needs to be grouped/filtered
+  // based on user input
+  combination_target_parent_hsm_node_hw_component_count_vec: &mut Vec<(
+    String,
+    HashMap<String, usize>,
+  )>, // list
+  // of hw component counters in target HSM group
+  selected_node_score_vec: &mut Vec<(String, f32)>, // nodes moved to target
+  // HSM group in the order they were picked, with the score they had at that moment
+  hw_component_scarcity_scores_hashmap: &HashMap<String, f32>, // hw
+                                                               // component type score for as much hsm groups related to the stakeholders using these
+                                                               // nodes
+) -> Result<Vec<(String, HashMap<String, usize>)>, Error> {
+  ////////////////////////////////
+  // Initialize
+
+  // Calculate hw component counters for the whole HSM group
+  let mut combination_target_parent_hsm_hw_component_summary_hashmap: HashMap<
+    String,
+    usize,
+  > = calculate_hsm_hw_component_summary(
+    combination_target_parent_hsm_node_hw_component_count_vec,
+  );
+
+  // Calculate initial scores
+  let mut combination_target_parent_hsm_node_score_tuple_vec: Vec<(
+    String,
+    f32,
+  )> = calculate_hsm_node_scores_from_final_hsm(
+    combination_target_parent_hsm_node_hw_component_count_vec,
+    &combination_target_parent_hsm_hw_component_summary_hashmap,
+    user_defined_hsm_hw_components_count_hashmap,
+    hw_component_scarcity_scores_hashmap,
+  );
+
+  let mut nodes_migrated_from_combination_target_parent_hsm: Vec<(
+    String,
+    HashMap<String, usize>,
+  )> = Vec::new();
+
+  // Get best candidate
+  let (mut best_candidate, mut best_candidate_counters) =
+    get_best_candidate_in_hsm_unpin(
+      &mut combination_target_parent_hsm_node_score_tuple_vec,
+      combination_target_parent_hsm_node_hw_component_count_vec,
+    )
+    .ok_or_else(|| {
+      Error::Message("ERROR - No best candidate found.".to_string())
+    })?;
+
+  // Check if we need to keep iterating
+  let mut work_to_do = keep_iterating_final_hsm(
+    user_defined_hsm_hw_components_count_hashmap,
+    &combination_target_parent_hsm_hw_component_summary_hashmap,
+  );
+
+  ////////////////////////////////
+  // Iterate
+
+  let mut iter = 0;
+
+  while work_to_do {
+    log::info!("----- ITERATION {} -----", iter);
+
+    log::info!(
+      "HSM group hw component counters: {:?}",
+      combination_target_parent_hsm_hw_component_summary_hashmap
+    );
+    log::info!(
+      "Final hw component counters the user wants: {:?}",
+      user_defined_hsm_hw_components_count_hashmap
+    );
+    log::info!(
+      "Best candidate is '{}' with score {} and hw component counters {:?}",
+      best_candidate.0,
+      best_candidate.1,
+      best_candidate_counters
+    );
+
+    ////////////////////////////////
+    // Apply changes - Migrate from target to parent HSM
+
+    // Add best candidate to list of nodes migrated
+    nodes_migrated_from_combination_target_parent_hsm
+      .push((best_candidate.0.clone(), best_candidate_counters.clone()));
+
+    selected_node_score_vec.push(best_candidate.clone());
+
+    // Remove best candidate from target HSM grour
+    combination_target_parent_hsm_node_hw_component_count_vec
+      .retain(|(node, _)| !node.eq(&best_candidate.0));
+
+    if combination_target_parent_hsm_node_hw_component_count_vec.is_empty() {
+      break;
+    }
+
+    // Calculate hw component couters for the whole HSM group
+    combination_target_parent_hsm_hw_component_summary_hashmap =
+      calculate_hsm_hw_component_summary(
+        combination_target_parent_hsm_node_hw_component_count_vec,
+      );
+
+    // Remove best candidate from scores
+    combination_target_parent_hsm_node_score_tuple_vec
+      .retain(|(node, _)| !node.eq(&best_candidate.0));
+
+    // Recalculate scores
+    let mut target_hsm_node_score_tuple_vec: Vec<(String, f32)> =
+      calculate_hsm_node_scores_from_final_hsm(
+        combination_target_parent_hsm_node_hw_component_count_vec,
+        &combination_target_parent_hsm_hw_component_summary_hashmap,
+        user_defined_hsm_hw_components_count_hashmap,
+        hw_component_scarcity_scores_hashmap,
+      );
+
+    // Get best candidate
+    (best_candidate, best_candidate_counters) =
+      get_best_candidate_in_hsm_unpin(
+        &mut target_hsm_node_score_tuple_vec,
+        combination_target_parent_hsm_node_hw_component_count_vec,
+      )
+      .ok_or_else(|| {
+        Error::Message("ERROR - No best candidate found.".to_string())
+      })?;
+
+    // Check if we need to keep iterating
+    work_to_do = keep_iterating_final_hsm(
+      user_defined_hsm_hw_components_count_hashmap,
+      &combination_target_parent_hsm_hw_component_summary_hashmap,
+    );
+
+    iter += 1;
+  }
+
+  log::info!("----- FINAL RESULT -----");
+
+  log::info!("No candidates found");
+
+  Ok(nodes_migrated_from_combination_target_parent_hsm)
+}
@@ -1,4 +1,5 @@
 pub mod apply_hw_cluster_pin;
+pub mod apply_hw_cluster_unpin;
 pub mod apply_sat_file;
 pub mod get_images_and_details;
 pub mod i_apply_session;
@@ -38,10 +38,6 @@ pub async fn process_get_http_request(
   // Error handleling. Check for errors from the CSM API processing the request
   match response.status().is_success() {
     true => response.json().await.map_err(|e| Error::NetError(e)), // Map error during marshalling
-    false => {
-      let e: Value = response.json().await.map_err(|e| Error::NetError(e))?; // Map error during
-
-      Err(Error::CsmError(e))
-    }
+    false => Err(Error::from_response(response).await),
   }
 }
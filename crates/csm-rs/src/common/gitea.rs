@@ -64,7 +64,7 @@ pub mod http_client {
     if response.status().is_success() {
       response.json().await.map_err(|e| Error::NetError(e))
     } else {
-      Err(Error::Message(response.text().await?))
+      Err(Error::from_response(response).await)
     }
     /*
     match resp_rslt {
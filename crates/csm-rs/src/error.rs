@@ -28,4 +28,176 @@ pub enum Error {
   ImageNotFound(String),
   #[error("CSM-RS > Group '{0}' not found")]
   GroupNotFound(String),
+  /// CSM service answered with an HTTP error status
+  #[error("CSM-RS > {service}: HTTP {status} '{path}'. {detail}")]
+  HttpError {
+    service: String,
+    status: u16,
+    /// Path of the request (eg: '/smd/hsm/v2/groups/zinal')
+    path: String,
+    /// Reason given by the service, either the 'detail' field of the problem
+    /// details payload or the response body
+    detail: String,
+  },
+}
+
+impl Error {
+  /// Builds an error from a CSM API response with an HTTP error status
+  pub async fn from_response(response: reqwest::Response) -> Self {
+    let status = response.status().as_u16();
+    let path = response.url().path().to_string();
+    let service = get_service_name(&path).to_string();
+
+    let detail = match response.text().await {
+      Ok(payload) => get_error_detail(&payload),
+      Err(e) => e.to_string(),
+    };
+
+    Error::HttpError {
+      service,
+      status,
+      path,
+      detail,
+    }
+  }
+}
+
+/// Returns the name of the CSM service serving an API path
+/// (eg: '/apis/smd/hsm/v2/groups' -> 'HSM')
+pub fn get_service_name(path: &str) -> &'static str {
+  path
+    .split('/')
+    .find_map(|segment| match segment {
+      "smd" | "hsm" => Some("HSM"),
+      "bss" | "boot" => Some("BSS"),
+      "cfs" => Some("CFS"),
+      "bos" => Some("BOS"),
+      "ims" => Some("IMS"),
+      "power-control" => Some("PCS"),
+      "capmc" => Some("CAPMC"),
+      "keycloak" => Some("Keycloak"),
+      "vault" => Some("Vault"),
+      "vcs" => Some("Gitea"),
+      _ => None,
+    })
+    .unwrap_or("CSM")
+}
+
+/// Returns the 'detail' of an RFC 7807 problem details payload (format used by
+/// most CSM services to report errors) or the payload itself otherwise
+fn get_error_detail(payload: &str) -> String {
+  serde_json::from_str::<Value>(payload)
+    .ok()
+    .and_then(|value| get_problem_detail(&value))
+    .unwrap_or_else(|| payload.trim().to_string())
+}
+
+fn get_problem_detail(value: &Value) -> Option<String> {
+  value
+    .get("detail")
+    .or_else(|| value.get("message"))
+    .and_then(Value::as_str)
+    .map(str::to_string)
+}
+
+/// Splits a request path into the resource type and id it refers to
+/// (eg: '/smd/hsm/v2/groups/zinal' -> ('groups', 'zinal'))
+fn get_resource_and_id(path: &str) -> (String, String) {
+  let mut segment_iter =
+    path.split('/').filter(|segment| !segment.is_empty()).rev();
+
+  let id = segment_iter.next().unwrap_or_default().to_string();
+  let resource = segment_iter.next().unwrap_or_default().to_string();
+
+  (resource, id)
+}
+
+impl From<Error> for manta_backend_dispatcher::error::Error {
+  fn from(error: Error) -> Self {
+    use manta_backend_dispatcher::error::Error as BackendError;
+
+    match error {
+      Error::Message(message) => BackendError::Message(message),
+      Error::IoError(e) => BackendError::IoError(e),
+      Error::SerdeError(e) => BackendError::SerdeError(e),
+      Error::NetError(e) => {
+        let path = e.url().map(|url| url.path().to_string());
+        let service = get_service_name(path.as_deref().unwrap_or_default());
+
+        if e.is_timeout() {
+          BackendError::Timeout {
+            service: service.to_string(),
+            detail: e.to_string(),
+          }
+        } else if let Some(status) = e.status() {
+          let (resource, id) =
+            get_resource_and_id(path.as_deref().unwrap_or_default());
+
+          BackendError::from_http_status(
+            service,
+            &resource,
+            &id,
+            status.as_u16(),
+            &e.to_string(),
+          )
+        } else {
+          BackendError::NetError(e)
+        }
+      }
+      Error::RequestError { response, payload } => {
+        match (response.status(), response.url()) {
+          (Some(status), Some(url)) => {
+            let (resource, id) = get_resource_and_id(url.path());
+
+            BackendError::from_http_status(
+              get_service_name(url.path()),
+              &resource,
+              &id,
+              status.as_u16(),
+              &get_error_detail(&payload),
+            )
+          }
+          _ => BackendError::RequestError { response, payload },
+        }
+      }
+      Error::CsmError(payload) => {
+        match payload.get("status").and_then(Value::as_u64) {
+          Some(status) => BackendError::from_http_status(
+            "CSM",
+            "",
+            "",
+            status as u16,
+            &get_problem_detail(&payload).unwrap_or(payload.to_string()),
+          ),
+          None => BackendError::CsmError(payload),
+        }
+      }
+      Error::ConsoleError(message) => BackendError::ConsoleError(message),
+      Error::K8sError(message) => {
+        BackendError::Message(format!("K8s: {}", message))
+      }
+      Error::ImageNotFound(id) => BackendError::NotFound {
+        service: "IMS".to_string(),
+        resource: "image".to_string(),
+        id,
+      },
+      Error::GroupNotFound(id) => BackendError::NotFound {
+        service: "HSM".to_string(),
+        resource: "group".to_string(),
+        id,
+      },
+      Error::HttpError {
+        service,
+        status,
+        path,
+        detail,
+      } => {
+        let (resource, id) = get_resource_and_id(&path);
+
+        BackendError::from_http_status(
+          &service, &resource, &id, status, &detail,
+        )
+      }
+    }
+  }
 }
@@ -145,21 +145,7 @@ pub async fn get(
     .await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        let error = Error::Message(error_payload);
-        return Err(error);
-
-        // return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        let error_payload = response.text().await?;
-        let error = Error::Message(error_payload);
-        return Err(error);
-        // return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -195,24 +181,7 @@ pub async fn get_one(
   let response = client.get(api_url).bearer_auth(auth_token).send().await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        /* let error_payload = response.text().await?;
-        let error = Error::RequestError {
-            response: e,
-            payload: error_payload,
-        };
-        return Err(error); */
-
-        return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        /* let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error); */
-        return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -253,24 +222,7 @@ pub async fn post(
     .await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        /* let error_payload = response.text().await?;
-        let error = Error::RequestError {
-            response: e,
-            payload: error_payload,
-        };
-        return Err(error); */
-
-        return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        /* let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error); */
-        return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   /* response
@@ -312,24 +264,7 @@ pub async fn post_query(
     .await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        /* let error_payload = response.text().await?;
-        let error = Error::RequestError {
-            response: e,
-            payload: error_payload,
-        };
-        return Err(error); */
-
-        return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        /* let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error); */
-        return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -370,24 +305,7 @@ pub async fn post_bynid_query(
     .await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        /* let error_payload = response.text().await?;
-        let error = Error::RequestError {
-            response: e,
-            payload: error_payload,
-        };
-        return Err(error); */
-
-        return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        /* let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error); */
-        return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -429,24 +347,7 @@ pub async fn put(
     .await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        /* let error_payload = response.text().await?;
-        let error = Error::RequestError {
-            response: e,
-            payload: error_payload,
-        };
-        return Err(error); */
-
-        return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        /* let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error); */
-        return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -486,24 +387,7 @@ pub async fn delete_one(
     .await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        /* let error_payload = response.text().await?;
-        let error = Error::RequestError {
-            response: e,
-            payload: error_payload,
-        };
-        return Err(error); */
-
-        return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        /* let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error); */
-        return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -541,24 +425,7 @@ pub async fn delete(
     .await?;
 
   if !response.status().is_success() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        /* let error_payload = response.text().await?;
-        let error = Error::RequestError {
-            response: e,
-            payload: error_payload,
-        };
-        return Err(error); */
-
-        return Err(Error::Message(response.text().await?));
-      }
-      _ => {
-        /* let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error); */
-        return Err(Error::CsmError(response.json::<Value>().await?));
-      }
-    }
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -50,12 +50,7 @@ pub async fn get(
             ))),
         }
   } else {
-    let e = response
-      .text()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::Message(e.to_string()))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -100,12 +95,7 @@ pub async fn get_query(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -140,22 +130,8 @@ pub async fn post(
     .send()
     .await?;
 
-  if let Err(e) = response.error_for_status_ref() {
-    match response.status() {
-      reqwest::StatusCode::UNAUTHORIZED => {
-        let error_payload = response.text().await?;
-        let error = Error::RequestError {
-          response: e,
-          payload: error_payload,
-        };
-        return Err(error);
-      }
-      _ => {
-        let error_payload = response.json::<Value>().await?;
-        let error = Error::CsmError(error_payload);
-        return Err(error);
-      }
-    }
+  if !response.status().is_success() {
+    return Err(Error::from_response(response).await);
   }
 
   response
@@ -244,6 +244,8 @@ impl Into<FrontEndArtifactSummary> for ArtifactSummary {
       xname: self.xname,
       r#type: self.r#type.into(),
       info: self.info.map(|v| v),
+      fru_id: None,
+      serial_number: None,
     }
   }
 }
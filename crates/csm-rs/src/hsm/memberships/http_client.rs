@@ -1,5 +1,3 @@
-use serde_json::Value;
-
 use crate::error::Error;
 
 use super::types::Membership;
@@ -42,11 +40,7 @@ pub async fn get_all(
         .unwrap(),
     )
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -90,10 +84,6 @@ pub async fn get_xname(
         .unwrap(),
     )
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
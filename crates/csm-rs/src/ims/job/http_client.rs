@@ -81,12 +81,7 @@ pub async fn post_customize(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -10,3 +10,5 @@ pub mod hsm;
 pub mod ims;
 pub mod node;
 pub mod pcs;
+#[cfg(any(test, feature = "test-support"))]
+pub mod stand_in;
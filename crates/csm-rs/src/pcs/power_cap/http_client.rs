@@ -1,8 +1,8 @@
-use serde_json::Value;
+use std::time;
 
 use crate::error::Error;
 
-use super::types::{PowerCapComponent, PowerCapTaskInfo};
+use super::types::{PowerCapPatch, PowerCapTaskInfo};
 
 pub async fn get(
   shasta_base_url: &str,
@@ -41,12 +41,7 @@ pub async fn get(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -89,12 +84,7 @@ pub async fn get_task_id(
       .await
       .map_err(|error| Error::NetError(error))
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -126,7 +116,7 @@ pub async fn post_snapshot(
     shasta_base_url.to_owned() + "/power-control/v1/power-cap/snapshot";
 
   let response = client
-    .put(api_url)
+    .post(api_url)
     .json(&serde_json::json!({
         "xnames": xname_vec
     }))
@@ -138,12 +128,7 @@ pub async fn post_snapshot(
   if response.status().is_success() {
     Ok(response.json().await.map_err(|e| Error::NetError(e))?)
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|e| Error::NetError(e))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -151,7 +136,7 @@ pub async fn patch(
   shasta_base_url: &str,
   shasta_token: &str,
   shasta_root_cert: &[u8],
-  power_cap: Vec<PowerCapComponent>,
+  power_cap: PowerCapPatch,
 ) -> Result<PowerCapTaskInfo, Error> {
   log::info!("Create PCS power cap:\n{:#?}", power_cap);
   log::debug!("Create PCS power cap:\n{:#?}", power_cap);
@@ -171,11 +156,10 @@ pub async fn patch(
     client_builder.build()?
   };
 
-  let api_url =
-    shasta_base_url.to_owned() + "/power-control/v1/power-cap/snapshot";
+  let api_url = shasta_base_url.to_owned() + "/power-control/v1/power-cap";
 
   let response = client
-    .put(api_url)
+    .patch(api_url)
     .json(&power_cap)
     .bearer_auth(shasta_token)
     .send()
@@ -185,11 +169,81 @@ pub async fn patch(
   if response.status().is_success() {
     Ok(response.json().await.map_err(|e| Error::NetError(e))?)
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|e| Error::NetError(e))?;
+    Err(Error::from_response(response).await)
+  }
+}
 
-    Err(Error::CsmError(payload))
+/// Creates a power cap snapshot task and waits for it to complete
+pub async fn post_snapshot_block(
+  shasta_base_url: &str,
+  shasta_token: &str,
+  shasta_root_cert: &[u8],
+  xname_vec: Vec<&str>,
+) -> Result<PowerCapTaskInfo, Error> {
+  let task =
+    post_snapshot(shasta_base_url, shasta_token, shasta_root_cert, xname_vec)
+      .await?;
+
+  wait_to_complete(shasta_base_url, shasta_token, shasta_root_cert, task).await
+}
+
+/// Creates a power cap patch task and waits for it to complete
+pub async fn patch_block(
+  shasta_base_url: &str,
+  shasta_token: &str,
+  shasta_root_cert: &[u8],
+  power_cap: PowerCapPatch,
+) -> Result<PowerCapTaskInfo, Error> {
+  let task =
+    patch(shasta_base_url, shasta_token, shasta_root_cert, power_cap).await?;
+
+  wait_to_complete(shasta_base_url, shasta_token, shasta_root_cert, task).await
+}
+
+pub async fn wait_to_complete(
+  shasta_base_url: &str,
+  shasta_token: &str,
+  shasta_root_cert: &[u8],
+  task: PowerCapTaskInfo,
+) -> Result<PowerCapTaskInfo, Error> {
+  let task_id = task.task_id.ok_or_else(|| {
+    Error::Message("PCS power cap task without task ID".to_string())
+  })?;
+
+  log::info!("PCS power cap task ID: {}", task_id);
+
+  let max_attempt = 300;
+
+  for attempt in 1..=max_attempt {
+    let task =
+      get_task_id(shasta_base_url, shasta_token, shasta_root_cert, &task_id)
+        .await?;
+
+    let task_status = task.task_status.clone().unwrap_or_default();
+
+    if let Some(task_counts) = &task.task_counts {
+      eprintln!(
+        "Power cap '{}' summary - status: {}, failed: {}, in-progress: {}, succeeded: {}, total: {}. Attempt {} of {}",
+        task.r#type.as_deref().unwrap_or_default(),
+        task_status,
+        task_counts.failed,
+        task_counts.in_progress,
+        task_counts.succeeded,
+        task_counts.total,
+        attempt,
+        max_attempt
+      );
+    }
+
+    if task_status == "completed" {
+      return Ok(task);
+    }
+
+    tokio::time::sleep(time::Duration::from_secs(3)).await;
   }
+
+  Err(Error::Message(format!(
+    "PCS power cap task '{}' did not complete after {} attempts",
+    task_id, max_attempt
+  )))
 }
@@ -1,3 +1,9 @@
+use manta_backend_dispatcher::types::pcs::power_cap::types::{
+  Limit as FrontEndLimit, PowerCapComponent as FrontEndPowerCapComponent,
+  PowerCapLimit as FrontEndPowerCapLimit,
+  PowerCapTaskInfo as FrontEndPowerCapTaskInfo,
+  TaskCounts as FrontEndTaskCounts,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -9,25 +15,50 @@ pub struct PowerCapTaskList {
 pub struct TaskCounts {
   pub total: usize,
   pub new: usize,
+  #[serde(rename = "in-progress")]
   pub in_progress: usize,
   pub failed: usize,
   pub succeeded: usize,
+  #[serde(rename = "un-supported")]
   pub un_supported: usize,
 }
 
+impl Into<FrontEndTaskCounts> for TaskCounts {
+  fn into(self) -> FrontEndTaskCounts {
+    FrontEndTaskCounts {
+      total: self.total,
+      new: self.new,
+      in_progress: self.in_progress,
+      failed: self.failed,
+      succeeded: self.succeeded,
+      un_supported: self.un_supported,
+    }
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Limit {
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "hostsLimitMax")]
-  pub hosts_limit_max: Option<usize>,
+  #[serde(rename = "hostLimitMax")]
+  pub host_limit_max: Option<usize>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "hostsLimitMin")]
-  pub hosts_limit_min: Option<usize>,
+  #[serde(rename = "hostLimitMin")]
+  pub host_limit_min: Option<usize>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "powerupPower")]
   pub powerup_power: Option<usize>,
 }
 
+impl Into<FrontEndLimit> for Limit {
+  fn into(self) -> FrontEndLimit {
+    FrontEndLimit {
+      host_limit_max: self.host_limit_max,
+      host_limit_min: self.host_limit_min,
+      powerup_power: self.powerup_power,
+    }
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PowerCapLimit {
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -36,11 +67,22 @@ pub struct PowerCapLimit {
   #[serde(rename = "currentValue")]
   pub current_value: Option<usize>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "mamximumValue")]
+  #[serde(rename = "maximumValue")]
   pub maximum_value: Option<usize>,
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "minimumValue")]
-  pub mnimum_value: Option<usize>,
+  pub minimum_value: Option<usize>,
+}
+
+impl Into<FrontEndPowerCapLimit> for PowerCapLimit {
+  fn into(self) -> FrontEndPowerCapLimit {
+    FrontEndPowerCapLimit {
+      name: self.name,
+      current_value: self.current_value,
+      maximum_value: self.maximum_value,
+      minimum_value: self.minimum_value,
+    }
+  }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,14 +94,27 @@ pub struct PowerCapComponent {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub limits: Option<Limit>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "power_cap_limits")]
-  pub power_cap_limits: Option<PowerCapLimit>,
+  #[serde(rename = "powerCapLimits")]
+  pub power_cap_limits: Option<Vec<PowerCapLimit>>,
+}
+
+impl Into<FrontEndPowerCapComponent> for PowerCapComponent {
+  fn into(self) -> FrontEndPowerCapComponent {
+    FrontEndPowerCapComponent {
+      xname: self.xname,
+      error: self.error,
+      limits: self.limits.map(Into::into),
+      power_cap_limits: self
+        .power_cap_limits
+        .map(|limit_vec| limit_vec.into_iter().map(Into::into).collect()),
+    }
+  }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PowerCapTaskInfo {
   #[serde(skip_serializing_if = "Option::is_none")]
-  #[serde(rename = "taskId")]
+  #[serde(rename = "taskID")]
   pub task_id: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub r#type: Option<String>, // TODO: convert to enum. Valid values are `snapshot` and `patch`
@@ -78,3 +133,37 @@ pub struct PowerCapTaskInfo {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub components: Option<Vec<PowerCapComponent>>,
 }
+
+impl Into<FrontEndPowerCapTaskInfo> for PowerCapTaskInfo {
+  fn into(self) -> FrontEndPowerCapTaskInfo {
+    FrontEndPowerCapTaskInfo {
+      task_id: self.task_id,
+      r#type: self.r#type,
+      task_create_time: self.task_create_time,
+      automatic_expiration_time: self.automatic_expiration_time,
+      task_status: self.task_status,
+      task_counts: self.task_counts.map(Into::into),
+      components: self.components.map(|component_vec| {
+        component_vec.into_iter().map(Into::into).collect()
+      }),
+    }
+  }
+}
+
+/// Request body to change power cap controls
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerCapPatch {
+  pub components: Vec<PowerCapPatchComponent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerCapPatchComponent {
+  pub xname: String,
+  pub controls: Vec<PowerCapControl>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerCapControl {
+  pub name: String,
+  pub value: usize,
+}
@@ -1,4 +1,4 @@
-use serde_json::{json, Value};
+use serde_json::json;
 
 use crate::error::Error;
 
@@ -58,11 +58,6 @@ pub async fn post(
     })
   } else {
     println!("Response is failure");
-    let payload = response.json::<Value>().await.map_err(|error| {
-      println!("{:?}", error);
-      Error::NetError(error)
-    })?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
@@ -49,12 +49,7 @@ pub async fn get(
     serde_json::from_value::<Vec<Value>>(resp_payload["transitions"].clone())
       .map_err(|error| Error::SerdeError(error))
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -101,12 +96,7 @@ pub async fn get_by_id(
 
     payload
   } else {
-    let payload = response
-      .json::<Value>()
-      .await
-      .map_err(|error| Error::NetError(error))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -169,9 +159,7 @@ pub async fn post(
   if response.status().is_success() {
     Ok(response.json::<Value>().await.unwrap())
   } else {
-    let payload = response.json().await.map_err(|e| Error::NetError(e))?;
-
-    Err(Error::CsmError(payload))
+    Err(Error::from_response(response).await)
   }
 }
 
@@ -0,0 +1,23 @@
+pub mod routes;
+pub mod server;
+pub mod store;
+
+pub use server::CsmStandIn;
+pub use store::{CsmStore, Fault, RecordedRequest};
+
+/// Builds an unsigned JWT token with the roles in `realm_access`, good enough for the functions
+/// in csm-rs reading the token claims (eg: 'pa_admin' role gives access to all HSM groups)
+pub fn auth_token(roles: &[&str]) -> String {
+  let header = serde_json::json!({ "alg": "none", "typ": "JWT" });
+  let claims = serde_json::json!({
+    "name": "Stand In",
+    "preferred_username": "stand-in",
+    "realm_access": { "roles": roles },
+  });
+
+  format!(
+    "{}.{}.",
+    base64::encode_config(header.to_string(), base64::URL_SAFE_NO_PAD),
+    base64::encode(claims.to_string())
+  )
+}
@@ -0,0 +1,1607 @@
+use serde_json::{json, Map, Value};
+
+use crate::stand_in::store::{problem_details, CsmStore};
+
+/// HTTP status code and payload. Responses without payload have an empty body
+pub type Reply = (u16, Option<Value>);
+
+/// Query parameters of a request
+pub struct Query(pub Vec<(String, String)>);
+
+impl Query {
+  pub fn get(&self, name: &str) -> Option<&str> {
+    self
+      .0
+      .iter()
+      .find(|(key, _)| key.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Returns all values of a query parameter, comma separated values are split
+  pub fn get_all(&self, name: &str) -> Vec<&str> {
+    self
+      .0
+      .iter()
+      .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+      .flat_map(|(_, value)| value.split(','))
+      .map(str::trim)
+      .filter(|value| !value.is_empty())
+      .collect()
+  }
+}
+
+fn ok(payload: Value) -> Reply {
+  (200, Some(payload))
+}
+
+fn created(payload: Value) -> Reply {
+  (201, Some(payload))
+}
+
+fn no_content() -> Reply {
+  (204, None)
+}
+
+fn error(status: u16, detail: &str) -> Reply {
+  (status, Some(problem_details(status, detail)))
+}
+
+fn not_found(resource: &str, id: &str) -> Reply {
+  error(404, &format!("{} '{}' not found", resource, id))
+}
+
+fn deleted(count: usize) -> Reply {
+  ok(json!({ "code": 0, "message": format!("deleted {} entry", count) }))
+}
+
+fn now() -> String {
+  chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn new_id() -> String {
+  uuid::Uuid::new_v4().to_string()
+}
+
+/// Returns 'true' if no value is requested or if the field matches any of the values
+/// requested. Comparison is case insensitive, same as HSM does
+fn matches_any(requested_vec: &[&str], field: &Value) -> bool {
+  requested_vec.is_empty()
+    || requested_vec.iter().any(|requested| match field {
+      Value::String(value) => value.eq_ignore_ascii_case(requested),
+      Value::Null => false,
+      value => requested.eq_ignore_ascii_case(&value.to_string()),
+    })
+}
+
+/// Shallow merge of 'patch' fields into 'target'
+fn merge(target: &mut Value, patch: &Value) {
+  if let (Some(target), Some(patch)) =
+    (target.as_object_mut(), patch.as_object())
+  {
+    for (key, value) in patch {
+      if !value.is_null() {
+        target.insert(key.clone(), value.clone());
+      }
+    }
+  }
+}
+
+fn convert_keys(value: Value, f: &dyn Fn(&str) -> String) -> Value {
+  match value {
+    Value::Object(object) => Value::Object(
+      object
+        .into_iter()
+        .map(|(key, value)| (f(&key), convert_keys(value, f)))
+        .collect::<Map<String, Value>>(),
+    ),
+    Value::Array(array) => Value::Array(
+      array
+        .into_iter()
+        .map(|value| convert_keys(value, f))
+        .collect(),
+    ),
+    value => value,
+  }
+}
+
+fn snake_to_camel(key: &str) -> String {
+  let mut camel = String::new();
+  let mut upper = false;
+
+  for c in key.chars() {
+    if c == '_' {
+      upper = true;
+    } else if upper {
+      camel.extend(c.to_uppercase());
+      upper = false;
+    } else {
+      camel.push(c);
+    }
+  }
+
+  camel
+}
+
+fn camel_to_snake(key: &str) -> String {
+  let mut snake = String::new();
+
+  for c in key.chars() {
+    if c.is_uppercase() {
+      snake.push('_');
+      snake.extend(c.to_lowercase());
+    } else {
+      snake.push(c);
+    }
+  }
+
+  snake
+}
+
+/// CFS v2 uses the same payloads as v3 with camelCase field names
+fn to_cfs_v2(value: Value) -> Value {
+  convert_keys(value, &snake_to_camel)
+}
+
+fn from_cfs_v2(value: Value) -> Value {
+  convert_keys(value, &camel_to_snake)
+}
+
+fn body_or_empty(body: Option<Value>) -> Value {
+  body.unwrap_or_else(|| json!({}))
+}
+
+fn string_vec(value: &Value) -> Vec<String> {
+  value
+    .as_array()
+    .into_iter()
+    .flatten()
+    .filter_map(Value::as_str)
+    .map(str::to_string)
+    .collect()
+}
+
+/// Dispatches a request to the handler of the service it belongs to
+pub fn route(
+  store: &mut CsmStore,
+  method: &str,
+  path: &[&str],
+  query: &Query,
+  body: Option<Value>,
+) -> Reply {
+  match path {
+    ["smd", "hsm", "v2", rest @ ..] => hsm(store, method, rest, query, body),
+    ["bss", "boot", "v1", "bootparameters"] => bss(store, method, query, body),
+    ["cfs", "healthz"] | ["cfs", "v2" | "v3", "healthz"] => {
+      ok(json!({ "db_status": "ok", "kafka_status": "ok" }))
+    }
+    ["cfs", version @ ("v2" | "v3"), rest @ ..] => {
+      let v2 = *version == "v2";
+      let body = if v2 { body.map(from_cfs_v2) } else { body };
+
+      let (status, payload) = cfs(store, method, rest, query, body, v2);
+
+      (status, if v2 { payload.map(to_cfs_v2) } else { payload })
+    }
+    ["bos", "v2", rest @ ..] => bos(store, method, rest, body),
+    ["ims", _, rest @ ..] => ims(store, method, rest, body),
+    ["power-control", "v1", rest @ ..] => pcs(store, method, rest, query, body),
+    ["capmc", "capmc", "v1", operation] => capmc(store, operation, body),
+    _ => error(404, &format!("{} /{} not found", method, path.join("/"))),
+  }
+}
+
+// HSM
+
+fn filter_components(store: &CsmStore, query: &Query) -> Vec<Value> {
+  let group_vec = query.get_all("group");
+  let group_member_vec: Vec<String> = group_vec
+    .iter()
+    .flat_map(|group| store.get_group_members(group).unwrap_or_default())
+    .collect();
+
+  let nid_start = query.get("nidstart").and_then(|nid| nid.parse().ok());
+  let nid_end = query.get("nidend").and_then(|nid| nid.parse().ok());
+
+  store
+    .components
+    .iter()
+    .filter(|component| {
+      let nid = component["NID"].as_u64();
+
+      matches_any(&query.get_all("id"), &component["ID"])
+        && matches_any(&query.get_all("type"), &component["Type"])
+        && matches_any(&query.get_all("state"), &component["State"])
+        && matches_any(&query.get_all("flag"), &component["Flag"])
+        && matches_any(&query.get_all("role"), &component["Role"])
+        && matches_any(&query.get_all("subrole"), &component["SubRole"])
+        && matches_any(&query.get_all("enabled"), &component["Enabled"])
+        && matches_any(
+          &query.get_all("softwarestatus"),
+          &component["SoftwareStatus"],
+        )
+        && matches_any(&query.get_all("subtype"), &component["Subtype"])
+        && matches_any(&query.get_all("arch"), &component["Arch"])
+        && matches_any(&query.get_all("class"), &component["Class"])
+        && matches_any(&query.get_all("nid"), &component["NID"])
+        && nid_start
+          .is_none_or(|start: u64| nid.is_some_and(|nid| nid >= start))
+        && nid_end.is_none_or(|end: u64| nid.is_some_and(|nid| nid <= end))
+        && (group_vec.is_empty()
+          || component["ID"]
+            .as_str()
+            .is_some_and(|xname| group_member_vec.iter().any(|m| m == xname)))
+    })
+    .cloned()
+    .collect()
+}
+
+fn nid_in_ranges(nid: u64, nid_range_vec: &[String]) -> bool {
+  nid_range_vec
+    .iter()
+    .any(|nid_range| match nid_range.split_once('-') {
+      Some((start, end)) => {
+        start.trim().parse().is_ok_and(|start: u64| nid >= start)
+          && end.trim().parse().is_ok_and(|end: u64| nid <= end)
+      }
+      None => nid_range.trim().parse() == Ok(nid),
+    })
+}
+
+fn hsm(
+  store: &mut CsmStore,
+  method: &str,
+  path: &[&str],
+  query: &Query,
+  body: Option<Value>,
+) -> Reply {
+  match (method, path) {
+    // Groups
+    ("GET", ["groups"]) => {
+      let label_vec = query.get_all("group");
+      let tag_vec = query.get_all("tag");
+
+      ok(Value::Array(
+        store
+          .groups
+          .iter()
+          .filter(|group| {
+            matches_any(&label_vec, &group["label"])
+              && (tag_vec.is_empty()
+                || string_vec(&group["tags"])
+                  .iter()
+                  .any(|tag| tag_vec.contains(&tag.as_str())))
+          })
+          .cloned()
+          .collect(),
+      ))
+    }
+    ("POST", ["groups"]) => {
+      let group = body_or_empty(body);
+
+      let Some(label) = group["label"].as_str().map(str::to_string) else {
+        return error(400, "Group label missing");
+      };
+
+      if store.find_group(&label).is_some() {
+        return error(409, &format!("Group '{}' already exists", label));
+      }
+
+      store.groups.push(group);
+
+      created(json!([{ "URI": format!("/hsm/v2/groups/{}", label) }]))
+    }
+    ("GET", ["groups", label]) => match store.find_group(label) {
+      Some(group) => ok(group.clone()),
+      None => not_found("Group", label),
+    },
+    ("PATCH", ["groups", label]) => match store.find_group_mut(label) {
+      Some(group) => {
+        merge(group, &body_or_empty(body));
+        no_content()
+      }
+      None => not_found("Group", label),
+    },
+    ("DELETE", ["groups", label]) => {
+      let group_count = store.groups.len();
+
+      store.groups.retain(|group| group["label"] != *label);
+
+      if store.groups.len() == group_count {
+        not_found("Group", label)
+      } else {
+        deleted(1)
+      }
+    }
+    ("GET", ["groups", label, "members"]) => {
+      match store.get_group_members(label) {
+        Some(member_vec) => ok(json!({ "ids": member_vec })),
+        None => not_found("Group", label),
+      }
+    }
+    ("POST", ["groups", label, "members"]) => {
+      let Some(xname) = body_or_empty(body)["id"].as_str().map(str::to_string)
+      else {
+        return error(400, "Member id missing");
+      };
+
+      let Some(mut member_vec) = store.get_group_members(label) else {
+        return not_found("Group", label);
+      };
+
+      if member_vec.contains(&xname) {
+        return error(
+          409,
+          &format!("Node '{}' already member of group '{}'", xname, label),
+        );
+      }
+
+      member_vec.push(xname.clone());
+
+      if let Some(group) = store.find_group_mut(label) {
+        group["members"] = json!({ "ids": member_vec });
+      }
+
+      created(json!([{
+        "URI": format!("/hsm/v2/groups/{}/members/{}", label, xname)
+      }]))
+    }
+    ("DELETE", ["groups", label, "members", xname]) => {
+      let Some(mut member_vec) = store.get_group_members(label) else {
+        return not_found("Group", label);
+      };
+
+      if !member_vec.iter().any(|member| member == xname) {
+        return not_found("Member", xname);
+      }
+
+      member_vec.retain(|member| member != xname);
+
+      if let Some(group) = store.find_group_mut(label) {
+        group["members"] = json!({ "ids": member_vec });
+      }
+
+      deleted(1)
+    }
+    // Memberships
+    ("GET", ["memberships"]) => ok(Value::Array(
+      filter_components(store, query)
+        .iter()
+        .filter_map(|component| component["ID"].as_str())
+        .map(|xname| {
+          json!({
+            "id": xname,
+            "groupLabels": store.get_node_groups(xname),
+            "partitionName": "",
+          })
+        })
+        .collect(),
+    )),
+    ("GET", ["memberships", xname]) => ok(json!({
+      "id": xname,
+      "groupLabels": store.get_node_groups(xname),
+      "partitionName": "",
+    })),
+    // Components
+    ("GET", ["State", "Components"]) => {
+      ok(json!({ "Components": filter_components(store, query) }))
+    }
+    ("POST", ["State", "Components"]) => {
+      let body = body_or_empty(body);
+
+      // Query components
+      if let Some(xname_vec) = body.get("ComponentIDs") {
+        let xname_vec = string_vec(xname_vec);
+
+        return ok(json!({
+          "Components": store
+            .components
+            .iter()
+            .filter(|component| {
+              component["ID"]
+                .as_str()
+                .is_some_and(|xname| xname_vec.iter().any(|x| x == xname))
+            })
+            .cloned()
+            .collect::<Vec<Value>>()
+        }));
+      }
+
+      // Create components
+      let force = body["Force"].as_bool().unwrap_or(false);
+
+      for component in
+        body["Components"].as_array().cloned().unwrap_or_default()
+      {
+        let position = store
+          .components
+          .iter()
+          .position(|existing| existing["ID"] == component["ID"]);
+
+        match position {
+          Some(_) if !force => {
+            return error(
+              409,
+              &format!("Component '{}' already exists", component["ID"]),
+            );
+          }
+          Some(position) => store.components[position] = component,
+          None => store.components.push(component),
+        }
+      }
+
+      no_content()
+    }
+    ("DELETE", ["State", "Components"]) => {
+      let component_count = store.components.len();
+      store.components.clear();
+      deleted(component_count)
+    }
+    ("POST", ["State", "Components", "ByNID", "Query"]) => {
+      let body = body_or_empty(body);
+      let nid_range_vec = string_vec(&body["NIDRanges"]);
+
+      ok(json!({
+        "Components": store
+          .components
+          .iter()
+          .filter(|component| {
+            component["NID"]
+              .as_u64()
+              .is_some_and(|nid| nid_in_ranges(nid, &nid_range_vec))
+          })
+          .cloned()
+          .collect::<Vec<Value>>()
+      }))
+    }
+    ("GET", ["State", "Components", xname]) => {
+      match store.components.iter().find(|c| c["ID"] == *xname) {
+        Some(component) => ok(component.clone()),
+        None => not_found("Component", xname),
+      }
+    }
+    ("PUT", ["State", "Components", xname]) => {
+      let body = body_or_empty(body);
+      let mut component = body.get("Component").cloned().unwrap_or(body);
+      component["ID"] = json!(xname);
+
+      match store.components.iter_mut().find(|c| c["ID"] == *xname) {
+        Some(existing) => *existing = component,
+        None => store.components.push(component),
+      }
+
+      no_content()
+    }
+    ("DELETE", ["State", "Components", xname]) => {
+      let component_count = store.components.len();
+
+      store
+        .components
+        .retain(|component| component["ID"] != *xname);
+
+      if store.components.len() == component_count {
+        not_found("Component", xname)
+      } else {
+        deleted(1)
+      }
+    }
+    // Hardware inventory
+    ("GET", ["Inventory", "Hardware"]) => {
+      let xname_vec = query.get_all("id");
+
+      ok(Value::Array(
+        store
+          .hardware_inventory
+          .iter()
+          .filter(|(xname, _)| {
+            xname_vec.is_empty() || xname_vec.contains(&xname.as_str())
+          })
+          .filter_map(|(_, hw_inventory)| hw_inventory.pointer("/Nodes/0"))
+          .cloned()
+          .collect(),
+      ))
+    }
+    ("POST", ["Inventory", "Hardware"]) => {
+      let body = body_or_empty(body);
+      let mut count = 0;
+
+      for hardware in body["Hardware"].as_array().into_iter().flatten() {
+        // Only nodes are stored, other components are part of the node inventory
+        if let (Some(xname), Some("Node")) =
+          (hardware["ID"].as_str(), hardware["Type"].as_str())
+        {
+          store.hardware_inventory.insert(
+            xname.to_string(),
+            json!({
+              "XName": xname,
+              "Format": "NestNodesOnly",
+              "Nodes": [hardware],
+            }),
+          );
+          count += 1;
+        }
+      }
+
+      ok(json!({
+        "code": 0,
+        "message": format!("Created or modified {} entries", count)
+      }))
+    }
+    ("GET", ["Inventory", "Hardware", "Query", xname]) => {
+      match store.hardware_inventory.get(*xname) {
+        Some(hw_inventory) => ok(hw_inventory.clone()),
+        None => not_found("Hardware inventory", xname),
+      }
+    }
+    ("GET", ["Inventory", "Hardware", xname]) => {
+      match store
+        .hardware_inventory
+        .get(*xname)
+        .and_then(|hw_inventory| hw_inventory.pointer("/Nodes/0"))
+      {
+        Some(hardware) => ok(hardware.clone()),
+        None => not_found("Hardware inventory", xname),
+      }
+    }
+    ("DELETE", ["Inventory", "Hardware", xname]) => {
+      match store.hardware_inventory.remove(*xname) {
+        Some(_) => deleted(1),
+        None => not_found("Hardware inventory", xname),
+      }
+    }
+    // Redfish endpoints
+    ("GET", ["Inventory", "RedfishEndpoints"]) => ok(json!({
+      "RedfishEndpoints": store
+        .redfish_endpoints
+        .iter()
+        .filter(|redfish_endpoint| {
+          matches_any(&query.get_all("id"), &redfish_endpoint["ID"])
+            && matches_any(&query.get_all("fqdn"), &redfish_endpoint["FQDN"])
+            && matches_any(&query.get_all("type"), &redfish_endpoint["Type"])
+            && matches_any(&query.get_all("uuid"), &redfish_endpoint["UUID"])
+            && matches_any(
+              &query.get_all("macaddr"),
+              &redfish_endpoint["MACAddr"],
+            )
+            && matches_any(
+              &query.get_all("ipaddress"),
+              &redfish_endpoint["IPAddress"],
+            )
+        })
+        .cloned()
+        .collect::<Vec<Value>>()
+    })),
+    ("POST", ["Inventory", "RedfishEndpoints"]) => {
+      let body = body_or_empty(body);
+
+      let redfish_endpoint_vec = match body.get("RedfishEndpoints") {
+        Some(redfish_endpoint_vec) => {
+          redfish_endpoint_vec.as_array().cloned().unwrap_or_default()
+        }
+        None => vec![body],
+      };
+
+      let mut uri_vec = Vec::new();
+
+      for redfish_endpoint in redfish_endpoint_vec {
+        let Some(id) = redfish_endpoint["ID"].as_str().map(str::to_string)
+        else {
+          return error(400, "Redfish endpoint ID missing");
+        };
+
+        if store
+          .redfish_endpoints
+          .iter()
+          .any(|r| r["ID"] == id.as_str())
+        {
+          return error(
+            409,
+            &format!("Redfish endpoint '{}' already exists", id),
+          );
+        }
+
+        uri_vec.push(
+          json!({ "URI": format!("/hsm/v2/Inventory/RedfishEndpoints/{}", id) }),
+        );
+        store.redfish_endpoints.push(redfish_endpoint);
+      }
+
+      created(Value::Array(uri_vec))
+    }
+    ("DELETE", ["Inventory", "RedfishEndpoints"]) => {
+      let redfish_endpoint_count = store.redfish_endpoints.len();
+      store.redfish_endpoints.clear();
+      deleted(redfish_endpoint_count)
+    }
+    ("GET", ["Inventory", "RedfishEndpoints", id]) => {
+      match store.redfish_endpoints.iter().find(|r| r["ID"] == *id) {
+        Some(redfish_endpoint) => ok(redfish_endpoint.clone()),
+        None => not_found("Redfish endpoint", id),
+      }
+    }
+    ("GET", ["Inventory", "RedfishEndpoint", "Query", xname]) => ok(json!({
+      "RedfishEndpoints": store
+        .redfish_endpoints
+        .iter()
+        .filter(|redfish_endpoint| redfish_endpoint["ID"] == *xname)
+        .cloned()
+        .collect::<Vec<Value>>()
+    })),
+    ("PUT" | "PATCH", ["Inventory", "RedfishEndpoints", id]) => {
+      let body = body_or_empty(body);
+
+      match store.redfish_endpoints.iter_mut().find(|r| r["ID"] == *id) {
+        Some(existing) if method == "PATCH" => {
+          merge(existing, &body);
+          ok(existing.clone())
+        }
+        Some(existing) => {
+          *existing = body;
+          existing["ID"] = json!(id);
+          ok(existing.clone())
+        }
+        None => not_found("Redfish endpoint", id),
+      }
+    }
+    ("DELETE", ["Inventory", "RedfishEndpoints", id]) => {
+      let redfish_endpoint_count = store.redfish_endpoints.len();
+
+      store.redfish_endpoints.retain(|r| r["ID"] != *id);
+
+      if store.redfish_endpoints.len() == redfish_endpoint_count {
+        not_found("Redfish endpoint", id)
+      } else {
+        deleted(1)
+      }
+    }
+    ("GET", ["Inventory", "EthernetInterfaces"]) => ok(json!([])),
+    ("GET", ["service", "values", "role"]) => ok(json!({
+      "Role": ["Compute", "Service", "System", "Application", "Storage", "Management"]
+    })),
+    _ => error(
+      404,
+      &format!("{} /smd/hsm/v2/{} not found", method, path.join("/")),
+    ),
+  }
+}
+
+// BSS
+
+fn bss(
+  store: &mut CsmStore,
+  method: &str,
+  query: &Query,
+  body: Option<Value>,
+) -> Reply {
+  let body = body_or_empty(body);
+  let host_vec = string_vec(&body["hosts"]);
+
+  let has_host = |boot_parameters: &Value, host_vec: &[&str]| {
+    string_vec(&boot_parameters["hosts"])
+      .iter()
+      .any(|host| host_vec.contains(&host.as_str()))
+  };
+
+  let host_ref_vec: Vec<&str> = host_vec.iter().map(String::as_str).collect();
+
+  match method {
+    "GET" => {
+      let name_vec = query.get_all("name");
+
+      ok(Value::Array(
+        store
+          .boot_parameters
+          .iter()
+          .filter(|boot_parameters| {
+            name_vec.is_empty() || has_host(boot_parameters, &name_vec)
+          })
+          .cloned()
+          .collect(),
+      ))
+    }
+    "POST" => {
+      if store
+        .boot_parameters
+        .iter()
+        .any(|boot_parameters| has_host(boot_parameters, &host_ref_vec))
+      {
+        return error(
+          409,
+          &format!(
+            "Boot parameters for hosts '{}' already exist",
+            host_vec.join(",")
+          ),
+        );
+      }
+
+      store.boot_parameters.push(body);
+
+      created(json!([]))
+    }
+    "PUT" => {
+      remove_bss_hosts(store, &host_vec);
+      store.boot_parameters.push(body);
+
+      ok(json!([]))
+    }
+    "PATCH" => {
+      let mut found = false;
+
+      for boot_parameters in store
+        .boot_parameters
+        .iter_mut()
+        .filter(|boot_parameters| has_host(boot_parameters, &host_ref_vec))
+      {
+        found = true;
+
+        for field in ["params", "kernel", "initrd", "cloud-init"] {
+          if !body[field].is_null() && body[field] != "" {
+            boot_parameters[field] = body[field].clone();
+          }
+        }
+      }
+
+      if found {
+        ok(json!([]))
+      } else {
+        not_found("Boot parameters for hosts", &host_vec.join(","))
+      }
+    }
+    "DELETE" => {
+      remove_bss_hosts(store, &host_vec);
+
+      ok(json!([]))
+    }
+    _ => error(405, "Method not allowed"),
+  }
+}
+
+/// Removes hosts from BSS boot parameters and deletes the boot parameters left without hosts
+fn remove_bss_hosts(store: &mut CsmStore, host_vec: &[String]) {
+  for boot_parameters in store.boot_parameters.iter_mut() {
+    let remaining_host_vec: Vec<String> = string_vec(&boot_parameters["hosts"])
+      .into_iter()
+      .filter(|host| !host_vec.contains(host))
+      .collect();
+
+    boot_parameters["hosts"] = json!(remaining_host_vec);
+  }
+
+  store.boot_parameters.retain(|boot_parameters| {
+    !string_vec(&boot_parameters["hosts"]).is_empty()
+  });
+}
+
+// CFS
+
+fn cfs(
+  store: &mut CsmStore,
+  method: &str,
+  path: &[&str],
+  query: &Query,
+  body: Option<Value>,
+  v2: bool,
+) -> Reply {
+  // CFS v3 wraps lists in an object, CFS v2 returns arrays
+  let list = |key: &str, item_vec: Vec<Value>| {
+    if v2 {
+      Value::Array(item_vec)
+    } else {
+      json!({ key: item_vec, "next": null })
+    }
+  };
+
+  match (method, path) {
+    ("GET", ["options"]) => ok(json!({
+      "default_ansible_config": "cfs-default-ansible-cfg",
+      "default_playbook": "site.yml",
+      "batch_size": 25,
+      "batch_window": 60,
+    })),
+    // Sessions
+    ("GET", ["sessions"]) => {
+      let name_contains_opt = query.get("name_contains");
+      let status_vec = query.get_all("status");
+      let succeeded_vec = query.get_all("succeeded");
+
+      let mut session_vec: Vec<Value> = store
+        .cfs_sessions
+        .iter()
+        .filter(|session| {
+          name_contains_opt.is_none_or(|name_contains| {
+            session["name"]
+              .as_str()
+              .is_some_and(|name| name.contains(name_contains))
+          }) && matches_any(
+            &status_vec,
+            &session["status"]["session"]["status"],
+          ) && matches_any(
+            &succeeded_vec,
+            &session["status"]["session"]["succeeded"],
+          )
+        })
+        .cloned()
+        .collect();
+
+      if let Some(limit) = query.get("limit").and_then(|l| l.parse().ok()) {
+        session_vec.truncate(limit);
+      }
+
+      ok(list("sessions", session_vec))
+    }
+    ("POST", ["sessions"]) => create_cfs_session(store, body_or_empty(body)),
+    ("GET", ["sessions", name]) => {
+      match store.cfs_sessions.iter().find(|s| s["name"] == *name) {
+        Some(session) => ok(session.clone()),
+        None => not_found("CFS session", name),
+      }
+    }
+    ("DELETE", ["sessions", name]) => {
+      let session_count = store.cfs_sessions.len();
+
+      store
+        .cfs_sessions
+        .retain(|session| session["name"] != *name);
+
+      if store.cfs_sessions.len() == session_count {
+        not_found("CFS session", name)
+      } else {
+        no_content()
+      }
+    }
+    // Configurations
+    ("GET", ["configurations"]) => {
+      let mut configuration_vec = store.cfs_configurations.clone();
+
+      if let Some(limit) = query.get("limit").and_then(|l| l.parse().ok()) {
+        configuration_vec.truncate(limit);
+      }
+
+      ok(list("configurations", configuration_vec))
+    }
+    ("GET", ["configurations", name]) => {
+      match store.cfs_configurations.iter().find(|c| c["name"] == *name) {
+        Some(configuration) => ok(configuration.clone()),
+        None => not_found("CFS configuration", name),
+      }
+    }
+    ("PUT", ["configurations", name]) => {
+      let body = body_or_empty(body);
+
+      let mut configuration = json!({
+        "name": name,
+        "last_updated": now(),
+        "layers": body["layers"].as_array().cloned().unwrap_or_default(),
+      });
+
+      if !body["additional_inventory"].is_null() {
+        configuration["additional_inventory"] =
+          body["additional_inventory"].clone();
+      }
+
+      store.cfs_configurations.retain(|c| c["name"] != *name);
+      store.cfs_configurations.push(configuration.clone());
+
+      ok(configuration)
+    }
+    ("DELETE", ["configurations", name]) => {
+      let configuration_count = store.cfs_configurations.len();
+
+      store.cfs_configurations.retain(|c| c["name"] != *name);
+
+      if store.cfs_configurations.len() == configuration_count {
+        not_found("CFS configuration", name)
+      } else {
+        no_content()
+      }
+    }
+    // Components
+    ("GET", ["components"]) => {
+      let id_vec = query.get_all("ids");
+      let status_vec = query.get_all("status");
+      let config_name_vec = query.get_all("config_name");
+      let enabled_vec = query.get_all("enabled");
+
+      let component_vec = store
+        .cfs_components
+        .iter()
+        .filter(|component| {
+          matches_any(&id_vec, &component["id"])
+            && matches_any(&status_vec, &component["configuration_status"])
+            && matches_any(&config_name_vec, &component["desired_config"])
+            && matches_any(&enabled_vec, &component["enabled"])
+        })
+        .cloned()
+        .collect();
+
+      ok(list("components", component_vec))
+    }
+    ("PATCH" | "PUT", ["components"]) => {
+      let body = body_or_empty(body);
+
+      // Either a list of components or a patch applied to components matching a filter
+      let patch_vec: Vec<Value> = match body {
+        Value::Array(component_vec) => component_vec,
+        body => {
+          let id_vec = body["filters"]["ids"]
+            .as_str()
+            .map(|ids| ids.split(',').map(str::to_string).collect())
+            .unwrap_or_else(|| {
+              store
+                .cfs_components
+                .iter()
+                .filter_map(|component| component["id"].as_str())
+                .map(str::to_string)
+                .collect::<Vec<String>>()
+            });
+
+          id_vec
+            .into_iter()
+            .map(|id| {
+              let mut patch = body["patch"].clone();
+              patch["id"] = json!(id);
+              patch
+            })
+            .collect()
+        }
+      };
+
+      let mut id_vec = Vec::new();
+
+      for patch in patch_vec {
+        id_vec.push(patch["id"].clone());
+        upsert_cfs_component(store, patch);
+      }
+
+      ok(json!({ "component_ids": id_vec }))
+    }
+    ("GET", ["components", id]) => {
+      match store.cfs_components.iter().find(|c| c["id"] == *id) {
+        Some(component) => ok(component.clone()),
+        None => not_found("CFS component", id),
+      }
+    }
+    ("PATCH" | "PUT", ["components", id]) => {
+      let mut patch = body_or_empty(body);
+      patch["id"] = json!(id);
+
+      ok(upsert_cfs_component(store, patch))
+    }
+    ("DELETE", ["components", id]) => {
+      let component_count = store.cfs_components.len();
+
+      store
+        .cfs_components
+        .retain(|component| component["id"] != *id);
+
+      if store.cfs_components.len() == component_count {
+        not_found("CFS component", id)
+      } else {
+        no_content()
+      }
+    }
+    _ => error(
+      404,
+      &format!("{} /cfs/{} not found", method, path.join("/")),
+    ),
+  }
+}
+
+fn upsert_cfs_component(store: &mut CsmStore, patch: Value) -> Value {
+  let position = store
+    .cfs_components
+    .iter()
+    .position(|component| component["id"] == patch["id"]);
+
+  let component = match position {
+    Some(position) => &mut store.cfs_components[position],
+    None => {
+      store.cfs_components.push(json!({
+        "id": patch["id"],
+        "state": [],
+        "desired_config": "",
+        "error_count": 0,
+        "enabled": true,
+        "configuration_status": "unconfigured",
+        "tags": {},
+      }));
+
+      store.cfs_components.last_mut().unwrap()
+    }
+  };
+
+  merge(component, &patch);
+
+  if !patch["desired_config"].is_null() {
+    // Configuration is applied straight away
+    component["configuration_status"] = json!("configured");
+    component["error_count"] = json!(0);
+  }
+
+  component.clone()
+}
+
+/// Creates a CFS session which finishes successfully straight away. Sessions building images
+/// create the resulting IMS images
+fn create_cfs_session(store: &mut CsmStore, request: Value) -> Reply {
+  let Some(name) = request["name"].as_str().map(str::to_string) else {
+    return error(400, "CFS session name missing");
+  };
+
+  if store
+    .cfs_sessions
+    .iter()
+    .any(|session| session["name"] == name)
+  {
+    return error(409, &format!("CFS session '{}' already exists", name));
+  }
+
+  let configuration_name = request["configuration_name"].clone();
+
+  if !store
+    .cfs_configurations
+    .iter()
+    .any(|configuration| configuration["name"] == configuration_name)
+  {
+    return error(
+      400,
+      &format!("CFS configuration '{}' not found", configuration_name),
+    );
+  }
+
+  let timestamp = now();
+
+  let mut artifact_vec = Vec::new();
+
+  if request["target"]["definition"] == "image" {
+    for image_map in request["target"]["image_map"]
+      .as_array()
+      .into_iter()
+      .flatten()
+    {
+      let image_id = new_id();
+
+      store.ims_images.push(json!({
+        "id": image_id,
+        "created": timestamp,
+        "name": image_map["result_name"],
+        "link": null,
+        "arch": "x86_64",
+      }));
+
+      artifact_vec.push(json!({
+        "image_id": image_map["source_id"],
+        "result_id": image_id,
+        "type": "ims_customized_image",
+      }));
+    }
+  }
+
+  let session = json!({
+    "name": name,
+    "configuration": {
+      "name": configuration_name,
+      "limit": request["configuration_limit"].as_str().unwrap_or_default(),
+    },
+    "ansible": {
+      "config": request["ansible_config"].as_str().unwrap_or("cfs-default-ansible-cfg"),
+      "limit": request["ansible_limit"].as_str().unwrap_or_default(),
+      "verbosity": request["ansible_verbosity"].as_u64().unwrap_or(0),
+      "passthrough": request["ansible_passthrough"].as_str().unwrap_or_default(),
+    },
+    "target": if request["target"].is_null() {
+      json!({ "definition": "dynamic", "groups": [], "image_map": [] })
+    } else {
+      request["target"].clone()
+    },
+    "status": {
+      "artifacts": artifact_vec,
+      "session": {
+        "job": format!("cfs-{}", new_id()),
+        "ims_job": null,
+        "completion_time": timestamp,
+        "start_time": timestamp,
+        "status": "complete",
+        "succeeded": "true",
+      },
+    },
+    "tags": request["tags"].as_object().cloned().unwrap_or_default(),
+    "debug_on_failure": request["debug_on_failure"].as_bool().unwrap_or(false),
+    "logs": format!("ara.cmn.stand-in/?label={}", name),
+  });
+
+  store.cfs_sessions.push(session.clone());
+
+  created(session)
+}
+
+// BOS
+
+fn bos(
+  store: &mut CsmStore,
+  method: &str,
+  path: &[&str],
+  body: Option<Value>,
+) -> Reply {
+  match (method, path) {
+    ("GET", ["healthz"]) => ok(json!({ "dbStatus": "ok", "apiStatus": "ok" })),
+    // Session templates
+    ("GET", ["sessiontemplates"]) => {
+      ok(Value::Array(store.bos_sessiontemplates.clone()))
+    }
+    ("GET", ["sessiontemplates", name]) => {
+      match store
+        .bos_sessiontemplates
+        .iter()
+        .find(|t| t["name"] == *name)
+      {
+        Some(template) => ok(template.clone()),
+        None => not_found("BOS sessiontemplate", name),
+      }
+    }
+    ("PUT", ["sessiontemplates", name]) => {
+      let mut template = body_or_empty(body);
+      template["name"] = json!(name);
+
+      store.bos_sessiontemplates.retain(|t| t["name"] != *name);
+      store.bos_sessiontemplates.push(template.clone());
+
+      ok(template)
+    }
+    ("DELETE", ["sessiontemplates", name]) => {
+      let template_count = store.bos_sessiontemplates.len();
+
+      store.bos_sessiontemplates.retain(|t| t["name"] != *name);
+
+      if store.bos_sessiontemplates.len() == template_count {
+        not_found("BOS sessiontemplate", name)
+      } else {
+        no_content()
+      }
+    }
+    // Sessions
+    ("GET", ["sessions"]) => ok(Value::Array(store.bos_sessions.clone())),
+    ("POST", ["sessions"]) => create_bos_session(store, body_or_empty(body)),
+    ("GET", ["sessions", id]) => {
+      match store.bos_sessions.iter().find(|s| s["name"] == *id) {
+        Some(session) => ok(session.clone()),
+        None => not_found("BOS session", id),
+      }
+    }
+    ("DELETE", ["sessions", id]) => {
+      let session_count = store.bos_sessions.len();
+
+      store.bos_sessions.retain(|session| session["name"] != *id);
+
+      if store.bos_sessions.len() == session_count {
+        not_found("BOS session", id)
+      } else {
+        no_content()
+      }
+    }
+    _ => error(
+      404,
+      &format!("{} /bos/v2/{} not found", method, path.join("/")),
+    ),
+  }
+}
+
+/// Creates a BOS session which finishes straight away. Nodes targeted by the session are
+/// powered on (boot/reboot) or off (shutdown)
+fn create_bos_session(store: &mut CsmStore, request: Value) -> Reply {
+  let template_name = request["template_name"].clone();
+
+  let Some(template) = store
+    .bos_sessiontemplates
+    .iter()
+    .find(|template| template["name"] == template_name)
+    .cloned()
+  else {
+    return error(
+      400,
+      &format!("BOS sessiontemplate '{}' not found", template_name),
+    );
+  };
+
+  let mut xname_vec: Vec<String> = Vec::new();
+
+  for boot_set in template["boot_sets"].as_object().into_iter().flatten() {
+    xname_vec.extend(string_vec(&boot_set.1["node_list"]));
+
+    for group in string_vec(&boot_set.1["node_groups"]) {
+      xname_vec.extend(store.get_group_members(&group).unwrap_or_default());
+    }
+  }
+
+  if let Some(limit) = request["limit"].as_str().filter(|l| !l.is_empty()) {
+    let limit_vec: Vec<&str> = limit.split(',').map(str::trim).collect();
+    xname_vec.retain(|xname| limit_vec.contains(&xname.as_str()));
+  }
+
+  xname_vec.sort();
+  xname_vec.dedup();
+
+  let operation = request["operation"].as_str().unwrap_or("boot");
+  let power_state = if operation == "shutdown" { "off" } else { "on" };
+
+  for xname in &xname_vec {
+    store
+      .power_state
+      .insert(xname.clone(), power_state.to_string());
+  }
+
+  let timestamp = now();
+
+  let session = json!({
+    "name": request["name"].as_str().map(str::to_string).unwrap_or_else(new_id),
+    "tenant": "",
+    "operation": operation,
+    "template_name": template_name,
+    "limit": request["limit"].as_str().unwrap_or_default(),
+    "stage": request["stage"].as_bool().unwrap_or(false),
+    "components": xname_vec.join(","),
+    "include_disabled": request["include_disabled"].as_bool().unwrap_or(false),
+    "status": {
+      "start_time": timestamp,
+      "end_time": timestamp,
+      "status": "complete",
+      "error": null,
+    },
+  });
+
+  store.bos_sessions.push(session.clone());
+
+  created(session)
+}
+
+// IMS
+
+fn ims(
+  store: &mut CsmStore,
+  method: &str,
+  path: &[&str],
+  body: Option<Value>,
+) -> Reply {
+  match (method, path) {
+    ("GET", ["images"]) => ok(Value::Array(store.ims_images.clone())),
+    ("POST", ["images"]) => {
+      let mut image = body_or_empty(body);
+      image["id"] = json!(new_id());
+      image["created"] = json!(now());
+
+      store.ims_images.push(image.clone());
+
+      created(image)
+    }
+    ("GET", ["images", id]) => {
+      match store.ims_images.iter().find(|image| image["id"] == *id) {
+        Some(image) => ok(image.clone()),
+        None => not_found("Image", id),
+      }
+    }
+    ("PATCH", ["images", id]) => {
+      match store.ims_images.iter_mut().find(|image| image["id"] == *id) {
+        Some(image) => {
+          merge(image, &body_or_empty(body));
+          ok(image.clone())
+        }
+        None => not_found("Image", id),
+      }
+    }
+    ("DELETE", ["images", id]) => {
+      match store.ims_images.iter().position(|image| image["id"] == *id) {
+        Some(position) => {
+          let image = store.ims_images.remove(position);
+          store.ims_deleted_images.push(image);
+          no_content()
+        }
+        None => not_found("Image", id),
+      }
+    }
+    ("GET", ["deleted", "images"]) => {
+      ok(Value::Array(store.ims_deleted_images.clone()))
+    }
+    ("DELETE", ["deleted", "images", id]) => {
+      let image_count = store.ims_deleted_images.len();
+
+      store.ims_deleted_images.retain(|image| image["id"] != *id);
+
+      if store.ims_deleted_images.len() == image_count {
+        not_found("Deleted image", id)
+      } else {
+        no_content()
+      }
+    }
+    ("GET", ["jobs"]) => ok(Value::Array(store.ims_jobs.clone())),
+    ("GET", ["jobs", id]) => {
+      match store.ims_jobs.iter().find(|job| job["id"] == *id) {
+        Some(job) => ok(job.clone()),
+        None => not_found("IMS job", id),
+      }
+    }
+    ("GET", ["public-keys" | "recipes"]) => ok(json!([])),
+    _ => error(
+      404,
+      &format!("{} /ims/{} not found", method, path.join("/")),
+    ),
+  }
+}
+
+// PCS
+
+/// Runs a power transition on the nodes and returns the PCS transition
+fn create_power_transition(
+  store: &mut CsmStore,
+  operation: &str,
+  xname_vec: &[String],
+) -> Value {
+  let power_state = if operation.to_lowercase().ends_with("off") {
+    "off"
+  } else {
+    "on"
+  };
+
+  let task_vec: Vec<Value> = xname_vec
+    .iter()
+    .map(|xname| {
+      if store.components.iter().any(|c| c["ID"] == xname.as_str()) {
+        store
+          .power_state
+          .insert(xname.clone(), power_state.to_string());
+
+        json!({
+          "xname": xname,
+          "taskStatus": "succeeded",
+          "taskStatusDescription": format!("Transition confirmed, {}", power_state),
+          "error": "",
+        })
+      } else {
+        json!({
+          "xname": xname,
+          "taskStatus": "failed",
+          "taskStatusDescription": "Missing xname",
+          "error": "Xname not found in HSM",
+        })
+      }
+    })
+    .collect();
+
+  let failed = task_vec
+    .iter()
+    .filter(|task| task["taskStatus"] == "failed")
+    .count();
+
+  let transition = json!({
+    "transitionID": new_id(),
+    "operation": operation,
+    "createTime": now(),
+    "automaticExpirationTime": now(),
+    "transitionStatus": "completed",
+    "taskCounts": {
+      "total": task_vec.len(),
+      "new": 0,
+      "in-progress": 0,
+      "failed": failed,
+      "succeeded": task_vec.len() - failed,
+      "un-supported": 0,
+    },
+    "tasks": task_vec,
+  });
+
+  store.power_transitions.push(transition.clone());
+
+  transition
+}
+
+fn power_status(
+  store: &CsmStore,
+  xname_vec: &[String],
+  power_state_filter: &str,
+  management_state_filter: &str,
+) -> Value {
+  let xname_vec: Vec<String> = if xname_vec.is_empty() {
+    store
+      .components
+      .iter()
+      .filter_map(|component| component["ID"].as_str())
+      .map(str::to_string)
+      .collect()
+  } else {
+    xname_vec.to_vec()
+  };
+
+  let status_vec: Vec<Value> = xname_vec
+    .iter()
+    .map(|xname| {
+      let power_state = store
+        .power_state
+        .get(xname)
+        .map(String::as_str)
+        .unwrap_or("undefined");
+
+      let supported_power_transition_vec = match power_state {
+        "on" => json!(["Soft-Off", "Off", "Soft-Restart", "Hard-Restart"]),
+        "off" => json!(["On"]),
+        _ => json!([]),
+      };
+
+      json!({
+        "xname": xname,
+        "powerState": power_state,
+        "managementState": "available",
+        "error": null,
+        "supportedPowerTransitions": supported_power_transition_vec,
+        "lastUpdated": now(),
+      })
+    })
+    .filter(|status| {
+      (power_state_filter.is_empty()
+        || status["powerState"] == power_state_filter)
+        && (management_state_filter.is_empty()
+          || status["managementState"] == management_state_filter)
+    })
+    .collect();
+
+  json!({ "status": status_vec })
+}
+
+fn pcs(
+  store: &mut CsmStore,
+  method: &str,
+  path: &[&str],
+  query: &Query,
+  body: Option<Value>,
+) -> Reply {
+  let body = body_or_empty(body);
+
+  match (method, path) {
+    ("POST", ["transitions"]) => {
+      let Some(operation) = body["operation"].as_str() else {
+        return error(400, "Transition operation missing");
+      };
+
+      let xname_vec: Vec<String> = body["location"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|location| location["xname"].as_str())
+        .map(str::to_string)
+        .collect();
+
+      let transition = create_power_transition(store, operation, &xname_vec);
+
+      ok(json!({
+        "transitionID": transition["transitionID"],
+        "operation": transition["operation"],
+      }))
+    }
+    ("GET", ["transitions"]) => ok(json!({
+      "transitions": store
+        .power_transitions
+        .iter()
+        .map(|transition| {
+          let mut transition = transition.clone();
+          if let Some(t) = transition.as_object_mut() {
+            t.remove("tasks");
+          }
+          transition
+        })
+        .collect::<Vec<Value>>()
+    })),
+    ("GET", ["transitions", id]) => {
+      match store
+        .power_transitions
+        .iter()
+        .find(|transition| transition["transitionID"] == *id)
+      {
+        Some(transition) => ok(transition.clone()),
+        None => not_found("Transition", id),
+      }
+    }
+    ("DELETE", ["transitions", id]) => {
+      if store
+        .power_transitions
+        .iter()
+        .any(|transition| transition["transitionID"] == *id)
+      {
+        ok(json!({ "abortStatus": "Accepted" }))
+      } else {
+        not_found("Transition", id)
+      }
+    }
+    ("GET", ["power-status"]) => {
+      let xname_vec: Vec<String> = query
+        .get_all("xname")
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+      ok(power_status(
+        store,
+        &xname_vec,
+        query.get("powerStateFilter").unwrap_or_default(),
+        query.get("managementStateFilter").unwrap_or_default(),
+      ))
+    }
+    ("POST", ["power-status"]) => ok(power_status(
+      store,
+      &string_vec(&body["xname"]),
+      body["powerStateFilter"].as_str().unwrap_or_default(),
+      body["managementStateFilter"].as_str().unwrap_or_default(),
+    )),
+    ("GET", ["power-cap"]) => ok(json!({ "tasks": [] })),
+    ("POST", ["power-cap", "snapshot"]) | ("PATCH", ["power-cap"]) => {
+      ok(json!({ "taskID": new_id() }))
+    }
+    ("GET", ["power-cap", id]) => ok(json!({
+      "taskID": id,
+      "type": "snapshot",
+      "taskCreateTime": now(),
+      "automaticExpirationTime": now(),
+      "taskStatus": "completed",
+      "components": [],
+    })),
+    _ => error(
+      404,
+      &format!("{} /power-control/v1/{} not found", method, path.join("/")),
+    ),
+  }
+}
+
+// CAPMC
+
+fn capmc(store: &mut CsmStore, operation: &str, body: Option<Value>) -> Reply {
+  let body = body_or_empty(body);
+  let xname_vec = string_vec(&body["xnames"]);
+
+  match operation {
+    "xname_on" | "xname_off" | "xname_reinit" => {
+      let pcs_operation = match operation {
+        "xname_on" => "On",
+        "xname_off" if body["force"].as_bool().unwrap_or(false) => "Force-Off",
+        "xname_off" => "Soft-Off",
+        _ => "Soft-Restart",
+      };
+
+      let transition =
+        create_power_transition(store, pcs_operation, &xname_vec);
+
+      let failed_xname_vec: Vec<Value> = transition["tasks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|task| task["taskStatus"] == "failed")
+        .map(|task| {
+          json!({ "xname": task["xname"], "e": -1, "err_msg": "Missing xname" })
+        })
+        .collect();
+
+      ok(json!({
+        "e": if failed_xname_vec.is_empty() { 0 } else { -1 },
+        "err_msg": "",
+        "xnames": failed_xname_vec,
+      }))
+    }
+    "get_xname_status" => {
+      let status = power_status(store, &xname_vec, "", "");
+
+      let xname_with_state = |state: &str| -> Vec<Value> {
+        status["status"]
+          .as_array()
+          .into_iter()
+          .flatten()
+          .filter(|status| status["powerState"] == state)
+          .map(|status| status["xname"].clone())
+          .collect()
+      };
+
+      ok(json!({
+        "e": 0,
+        "err_msg": "",
+        "on": xname_with_state("on"),
+        "off": xname_with_state("off"),
+        "undefined": xname_with_state("undefined"),
+      }))
+    }
+    _ => error(404, &format!("CAPMC operation '{}' not found", operation)),
+  }
+}
@@ -0,0 +1,197 @@
+use std::{
+  net::SocketAddr,
+  sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use axum::{
+  body::Bytes,
+  extract::State,
+  http::{HeaderMap, Method, StatusCode, Uri},
+  response::{IntoResponse, Response},
+  Json, Router,
+};
+use serde_json::Value;
+use tokio::{net::TcpListener, sync::oneshot};
+
+use crate::{
+  error::Error,
+  stand_in::{
+    routes,
+    store::{problem_details, CsmStore, Fault, RecordedRequest},
+  },
+};
+
+type SharedStore = Arc<Mutex<CsmStore>>;
+
+/// HTTP server emulating the CSM APIs used by csm-rs (HSM, BSS, CFS v2/v3, BOS v2, IMS, PCS
+/// and CAPMC). Requests are served from an in-memory [`CsmStore`].
+///
+/// The server stops when the `CsmStandIn` is dropped.
+///
+/// ```ignore
+/// let stand_in = CsmStandIn::start(CsmStore::default()).await?;
+/// let csm = Csm::new(stand_in.base_url(), &[]);
+/// ```
+pub struct CsmStandIn {
+  base_url: String,
+  store: SharedStore,
+  shutdown_tx: Option<oneshot::Sender<()>>,
+}
+
+impl CsmStandIn {
+  /// Starts a server listening on a random port on localhost
+  pub async fn start(store: CsmStore) -> Result<Self, Error> {
+    Self::bind("127.0.0.1:0", store).await
+  }
+
+  /// Starts a server listening on `address` (eg: '127.0.0.1:8080')
+  pub async fn bind(address: &str, store: CsmStore) -> Result<Self, Error> {
+    let listener = TcpListener::bind(address).await?;
+    let local_address: SocketAddr = listener.local_addr()?;
+
+    let store = Arc::new(Mutex::new(store));
+
+    let app = Router::new().fallback(handle).with_state(store.clone());
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+
+    tokio::spawn(async move {
+      let server = axum::serve(listener, app).with_graceful_shutdown(async {
+        let _ = shutdown_rx.await;
+      });
+
+      if let Err(e) = server.await {
+        log::error!("CSM stand-in server failed. Reason:\n{}", e);
+      }
+    });
+
+    log::info!("CSM stand-in server listening on {}", local_address);
+
+    Ok(Self {
+      base_url: format!("http://{}", local_address),
+      store,
+      shutdown_tx: Some(shutdown_tx),
+    })
+  }
+
+  /// Base URL to use as `shasta_base_url` (eg: 'http://127.0.0.1:41234')
+  pub fn base_url(&self) -> &str {
+    &self.base_url
+  }
+
+  /// Gives access to the server state, eg to seed it or to check the effects of a request
+  pub fn store(&self) -> MutexGuard<'_, CsmStore> {
+    lock(&self.store)
+  }
+
+  /// Makes the server return an error for the next requests matching the fault
+  pub fn inject_fault(&self, fault: Fault) {
+    self.store().faults.push(fault);
+  }
+
+  /// Returns the list of requests received so far
+  pub fn requests(&self) -> Vec<RecordedRequest> {
+    self.store().requests.clone()
+  }
+}
+
+impl Drop for CsmStandIn {
+  fn drop(&mut self) {
+    if let Some(shutdown_tx) = self.shutdown_tx.take() {
+      let _ = shutdown_tx.send(());
+    }
+  }
+}
+
+fn lock(store: &SharedStore) -> MutexGuard<'_, CsmStore> {
+  store.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Removes empty segments, so '//smd/hsm' and '/smd/hsm' are the same path, and adds the
+/// service prefix some csm-rs http clients skip ('hsm/v2/...' is served as
+/// 'smd/hsm/v2/...' and 'boot/v1/...' as 'bss/boot/v1/...')
+fn normalize_path(path: &str) -> Vec<String> {
+  let mut segment_vec: Vec<String> = path
+    .split('/')
+    .filter(|segment| !segment.is_empty())
+    .map(str::to_string)
+    .collect();
+
+  match segment_vec.first().map(String::as_str) {
+    Some("hsm") => segment_vec.insert(0, "smd".to_string()),
+    Some("boot") => segment_vec.insert(0, "bss".to_string()),
+    _ => {}
+  }
+
+  segment_vec
+}
+
+fn parse_query(uri: &Uri) -> Vec<(String, String)> {
+  uri
+    .query()
+    .map(|query| {
+      reqwest::Url::parse(&format!("http://stand-in/?{}", query))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default()
+    })
+    .unwrap_or_default()
+}
+
+fn is_authorized(store: &CsmStore, headers: &HeaderMap) -> bool {
+  store.auth_token.as_ref().is_none_or(|auth_token| {
+    headers
+      .get("authorization")
+      .and_then(|header| header.to_str().ok())
+      .and_then(|header| header.strip_prefix("Bearer "))
+      .is_some_and(|token| token == auth_token)
+  })
+}
+
+async fn handle(
+  State(store): State<SharedStore>,
+  method: Method,
+  uri: Uri,
+  headers: HeaderMap,
+  body: Bytes,
+) -> Response {
+  let segment_vec = normalize_path(uri.path());
+  let path = format!("/{}", segment_vec.join("/"));
+  let query = parse_query(&uri);
+  let body: Option<Value> = serde_json::from_slice(&body).ok();
+
+  let mut store = lock(&store);
+
+  store.requests.push(RecordedRequest {
+    method: method.to_string(),
+    path: path.clone(),
+    query: query.clone(),
+    body: body.clone(),
+  });
+
+  let (status, payload) = if !is_authorized(&store, &headers) {
+    (401, Some(problem_details(401, "Invalid or missing token")))
+  } else if let Some(fault) = store.take_fault(method.as_str(), &path) {
+    (fault.status, Some(fault.body))
+  } else {
+    let segment_vec: Vec<&str> =
+      segment_vec.iter().map(String::as_str).collect();
+
+    routes::route(
+      &mut store,
+      method.as_str(),
+      &segment_vec,
+      &routes::Query(query),
+      body,
+    )
+  };
+
+  log::debug!("CSM stand-in {} {} -> {}", method, path, status);
+
+  let status =
+    StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+  match payload {
+    Some(payload) => (status, Json(payload)).into_response(),
+    None => status.into_response(),
+  }
+}
@@ -0,0 +1,191 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// In-memory state served by the CSM stand-in server. Every resource is kept with the same
+/// JSON structure CSM APIs use, so a store can be seeded with payloads captured from a real
+/// system (eg: `curl .../smd/hsm/v2/groups`).
+///
+/// Fixture example (JSON):
+///
+/// ```json
+/// {
+///   "groups": [{ "label": "zinal", "members": { "ids": ["x1000c0s0b0n0"] } }],
+///   "components": [{ "ID": "x1000c0s0b0n0", "Type": "Node", "NID": 1 }],
+///   "power_state": { "x1000c0s0b0n0": "on" }
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CsmStore {
+  // HSM
+  pub groups: Vec<Value>,
+  pub components: Vec<Value>,
+  /// HSM `Inventory/Hardware/Query/{xname}` response per xname
+  pub hardware_inventory: HashMap<String, Value>,
+  pub redfish_endpoints: Vec<Value>,
+  // BSS
+  pub boot_parameters: Vec<Value>,
+  // CFS
+  pub cfs_configurations: Vec<Value>,
+  pub cfs_sessions: Vec<Value>,
+  pub cfs_components: Vec<Value>,
+  // BOS
+  pub bos_sessiontemplates: Vec<Value>,
+  pub bos_sessions: Vec<Value>,
+  // IMS
+  pub ims_images: Vec<Value>,
+  pub ims_deleted_images: Vec<Value>,
+  pub ims_jobs: Vec<Value>,
+  // PCS/CAPMC
+  /// Power state per xname, either "on" or "off"
+  pub power_state: HashMap<String, String>,
+  pub power_transitions: Vec<Value>,
+  /// Token expected in the 'Authorization' header. Any token is accepted if missing
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub auth_token: Option<String>,
+  /// Errors to return instead of processing the request
+  #[serde(skip)]
+  pub faults: Vec<Fault>,
+  /// Requests received by the server
+  #[serde(skip)]
+  pub requests: Vec<RecordedRequest>,
+}
+
+/// Error injected in the stand-in server. The first request matching `method` and
+/// `path_prefix` gets a response with `status` and `body` instead of being processed.
+#[derive(Debug, Clone)]
+pub struct Fault {
+  /// HTTP method to match, any method if missing
+  pub method: Option<String>,
+  /// Path prefix to match (eg: '/smd/hsm/v2/groups')
+  pub path_prefix: String,
+  pub status: u16,
+  pub body: Value,
+  /// Number of requests this fault applies to
+  pub times: usize,
+}
+
+impl Fault {
+  pub fn new(method: Option<&str>, path_prefix: &str, status: u16) -> Self {
+    Self {
+      method: method.map(str::to_uppercase),
+      path_prefix: path_prefix.to_string(),
+      status,
+      body: problem_details(status, "Fault injected by CSM stand-in"),
+      times: 1,
+    }
+  }
+
+  pub fn with_body(mut self, body: Value) -> Self {
+    self.body = body;
+    self
+  }
+
+  pub fn times(mut self, times: usize) -> Self {
+    self.times = times;
+    self
+  }
+
+  fn matches(&self, method: &str, path: &str) -> bool {
+    self.times > 0
+      && self.method.as_deref().is_none_or(|m| m == method)
+      && path.starts_with(&self.path_prefix)
+  }
+}
+
+/// Request received by the stand-in server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+  pub method: String,
+  /// Normalized path (eg: '/smd/hsm/v2/groups')
+  pub path: String,
+  pub query: Vec<(String, String)>,
+  pub body: Option<Value>,
+}
+
+/// Builds an RFC 7807 problem details payload, this is the error format used by most CSM
+/// services
+pub fn problem_details(status: u16, detail: &str) -> Value {
+  let title = reqwest::StatusCode::from_u16(status)
+    .ok()
+    .and_then(|status_code| status_code.canonical_reason())
+    .unwrap_or("Error");
+
+  serde_json::json!({
+    "type": "about:blank",
+    "title": title,
+    "detail": detail,
+    "status": status,
+  })
+}
+
+impl CsmStore {
+  /// Reads store from a JSON fixture file
+  pub fn from_file(path: &Path) -> Result<Self, Error> {
+    let content = fs::read_to_string(path)?;
+
+    serde_json::from_str(&content).map_err(|e| {
+      Error::Message(format!(
+        "Could not parse CSM stand-in fixture file '{}'. Reason:\n{}",
+        path.display(),
+        e
+      ))
+    })
+  }
+
+  /// Returns and consumes the fault matching a request, if any
+  pub fn take_fault(&mut self, method: &str, path: &str) -> Option<Fault> {
+    let fault = self
+      .faults
+      .iter_mut()
+      .find(|fault| fault.matches(method, path))?;
+
+    fault.times -= 1;
+
+    Some(fault.clone())
+  }
+
+  /// Returns the list of members of a group
+  pub fn get_group_members(&self, label: &str) -> Option<Vec<String>> {
+    self.find_group(label).map(|group| {
+      group
+        .pointer("/members/ids")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+    })
+  }
+
+  pub fn find_group(&self, label: &str) -> Option<&Value> {
+    self.groups.iter().find(|group| group["label"] == label)
+  }
+
+  pub fn find_group_mut(&mut self, label: &str) -> Option<&mut Value> {
+    self.groups.iter_mut().find(|group| group["label"] == label)
+  }
+
+  /// Returns the list of group labels a node belongs to
+  pub fn get_node_groups(&self, xname: &str) -> Vec<String> {
+    self
+      .groups
+      .iter()
+      .filter(|group| {
+        group
+          .pointer("/members/ids")
+          .and_then(Value::as_array)
+          .is_some_and(|member_vec| {
+            member_vec.iter().any(|member| member == xname)
+          })
+      })
+      .filter_map(|group| group["label"].as_str())
+      .map(str::to_string)
+      .collect()
+  }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+use crate::error::Error;
+
+use super::Audit;
+
+/// Appends audit events to a local file in JSON lines format
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct File {
+  pub path: String,
+}
+
+impl Audit for File {
+  async fn produce_message(&self, data: &[u8]) -> Result<(), Error> {
+    let mut line = data.to_vec();
+    line.push(b'\n');
+
+    let mut file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)
+      .await?;
+
+    // Single write so lines from concurrent processes do not interleave
+    file.write_all(&line).await?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+use std::fs;
+
+use crate::audit::{Audit, AuditEvent, AuditOutcome, AuditUser};
+
+use super::File;
+
+fn event(command: &str) -> AuditEvent {
+  AuditEvent::new(
+    AuditUser {
+      id: "jdoe".to_string(),
+      name: "John Doe".to_string(),
+    },
+    "alps",
+    command,
+  )
+}
+
+#[tokio::test]
+async fn test_audit_appends_json_lines() {
+  let dir = std::env::temp_dir().join(format!(
+    "manta-audit-file-{}-{}",
+    std::process::id(),
+    "append"
+  ));
+  fs::create_dir_all(&dir).unwrap();
+
+  let path = dir.join("audit.jsonl");
+
+  let file = File {
+    path: path.to_string_lossy().to_string(),
+  };
+
+  file.audit(&event("add node")).await.unwrap();
+  file
+    .audit(&event("delete node").with_outcome(AuditOutcome::Failure))
+    .await
+    .unwrap();
+
+  let event_vec: Vec<AuditEvent> = fs::read_to_string(&path)
+    .unwrap()
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect();
+
+  assert_eq!(event_vec.len(), 2);
+  assert_eq!(event_vec[0].command, "add node");
+  assert_eq!(event_vec[0].outcome, AuditOutcome::Success);
+  assert_eq!(event_vec[1].command, "delete node");
+  assert_eq!(event_vec[1].outcome, AuditOutcome::Failure);
+
+  fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_audit_missing_directory() {
+  let file = File {
+    path: std::env::temp_dir()
+      .join(format!("manta-audit-file-{}-missing", std::process::id()))
+      .join("audit.jsonl")
+      .to_string_lossy()
+      .to_string(),
+  };
+
+  assert!(file.audit(&event("add node")).await.is_err());
+}
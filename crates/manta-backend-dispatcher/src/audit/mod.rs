@@ -0,0 +1,209 @@
+pub mod file;
+pub mod syslog;
+pub mod webhook;
+
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, Display};
+
+use crate::{error::Error, types::kafka::Kafka};
+
+use self::{file::File, syslog::Syslog, webhook::Webhook};
+
+/// Audit configuration (`[auditor]` section in manta configuration file).
+/// Events are sent to every sink listed, eg:
+///
+/// ```toml
+/// [[auditor.sinks]]
+/// type = "file"
+/// path = "/var/log/manta/audit.jsonl"
+///
+/// [[auditor.sinks]]
+/// type = "syslog"
+/// host = "syslog.cscs.ch"
+/// protocol = "tcp"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Auditor {
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub sinks: Vec<AuditSink>,
+  /// Kafka sink, kept for configuration files created before audit sinks
+  /// were introduced. Same as a `kafka` entry in `sinks`
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kafka: Option<Kafka>,
+  /// Site added to the events, not part of the configuration section since
+  /// it comes from the site in use
+  #[serde(skip)]
+  pub site: String,
+}
+
+impl Auditor {
+  pub fn sink_vec(&self) -> Vec<AuditSink> {
+    self
+      .sinks
+      .iter()
+      .cloned()
+      .chain(self.kafka.clone().map(AuditSink::Kafka))
+      .collect()
+  }
+
+  pub fn with_site(mut self, site: &str) -> Self {
+    self.site = site.to_string();
+    self
+  }
+
+  /// New event about an operation run on this auditor's site
+  pub fn new_event(&self, user: AuditUser, command: &str) -> AuditEvent {
+    AuditEvent::new(user, &self.site, command)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.sinks.is_empty() && self.kafka.is_none()
+  }
+
+  /// Sends an audit event to all sinks. Audit must not stop the operation
+  /// audited, therefore, sink failures are logged and ignored
+  pub async fn send(&self, event: &AuditEvent) {
+    for sink in self.sink_vec() {
+      if let Err(e) = sink.audit(event).await {
+        log::warn!(
+          "Could not send audit event to {} sink. Reason:\n{}",
+          sink,
+          e
+        );
+      }
+    }
+  }
+}
+
+/// Destination of audit events
+#[derive(Serialize, Deserialize, Debug, Clone, Display)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum AuditSink {
+  /// Local file, one JSON event per line
+  File(File),
+  /// RFC 5424 syslog server
+  Syslog(Syslog),
+  Kafka(Kafka),
+  /// HTTP endpoint receiving each event as a JSON POST request
+  Webhook(Webhook),
+}
+
+impl Audit for AuditSink {
+  async fn produce_message(&self, data: &[u8]) -> Result<(), Error> {
+    match self {
+      AuditSink::File(file) => file.produce_message(data).await,
+      AuditSink::Syslog(syslog) => syslog.produce_message(data).await,
+      AuditSink::Kafka(kafka) => kafka.produce_message(data).await,
+      AuditSink::Webhook(webhook) => webhook.produce_message(data).await,
+    }
+  }
+
+  async fn audit(&self, event: &AuditEvent) -> Result<(), Error> {
+    match self {
+      AuditSink::File(file) => file.audit(event).await,
+      AuditSink::Syslog(syslog) => syslog.audit(event).await,
+      AuditSink::Kafka(kafka) => kafka.audit(event).await,
+      AuditSink::Webhook(webhook) => webhook.audit(event).await,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditUser {
+  /// JWT 'preferred_username' claim
+  pub id: String,
+  /// JWT 'name' claim
+  pub name: String,
+}
+
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Display, AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum AuditOutcome {
+  Success,
+  Failure,
+}
+
+/// Operation run by a user against a site
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuditEvent {
+  pub timestamp: DateTime<Utc>,
+  pub user: AuditUser,
+  pub site: String,
+  /// Operation (eg: 'power on nodes')
+  pub command: String,
+  /// Human readable description of the operation
+  pub message: String,
+  /// Nodes affected by the operation
+  pub xnames: Vec<String>,
+  /// Groups affected by the operation
+  pub groups: Vec<String>,
+  pub outcome: AuditOutcome,
+}
+
+impl AuditEvent {
+  /// New successful event without targets. Use `with_*` methods to add the
+  /// xnames and groups affected
+  pub fn new(user: AuditUser, site: &str, command: &str) -> Self {
+    Self {
+      timestamp: Utc::now(),
+      user,
+      site: site.to_string(),
+      command: command.to_string(),
+      message: command.to_string(),
+      xnames: Vec::new(),
+      groups: Vec::new(),
+      outcome: AuditOutcome::Success,
+    }
+  }
+
+  pub fn with_message(mut self, message: &str) -> Self {
+    self.message = message.to_string();
+    self
+  }
+
+  pub fn with_xnames<T: ToString>(mut self, xname_vec: &[T]) -> Self {
+    self.xnames = xname_vec.iter().map(ToString::to_string).collect();
+    self
+  }
+
+  pub fn with_groups<T: ToString>(mut self, group_vec: &[T]) -> Self {
+    self.groups = group_vec.iter().map(ToString::to_string).collect();
+    self
+  }
+
+  pub fn with_outcome(mut self, outcome: AuditOutcome) -> Self {
+    self.outcome = outcome;
+    self
+  }
+}
+
+pub trait Audit {
+  fn produce_message(
+    &self,
+    data: &[u8],
+  ) -> impl Future<Output = Result<(), Error>> + Send;
+
+  /// Sends an audit event serialized as JSON
+  fn audit(
+    &self,
+    event: &AuditEvent,
+  ) -> impl Future<Output = Result<(), Error>> + Send
+  where
+    Self: Sync,
+  {
+    async move {
+      let data = serde_json::to_vec(event)?;
+      self.produce_message(&data).await
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,119 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::{
+  io::AsyncWriteExt,
+  net::{TcpStream, UdpSocket},
+};
+
+use crate::error::Error;
+
+use super::{Audit, AuditEvent, AuditOutcome};
+
+/// 'log audit' facility (RFC 5424 section 6.2.1)
+const FACILITY_LOG_AUDIT: u8 = 13;
+const SEVERITY_WARNING: u8 = 4;
+const SEVERITY_INFORMATIONAL: u8 = 6;
+
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogProtocol {
+  #[default]
+  Udp,
+  Tcp,
+}
+
+/// Sends audit events to a syslog server as RFC 5424 messages. Messages sent
+/// over TCP use octet counting framing (RFC 6587)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Syslog {
+  pub host: String,
+  #[serde(default = "default_port")]
+  pub port: u16,
+  #[serde(default)]
+  pub protocol: SyslogProtocol,
+  #[serde(default = "default_app_name")]
+  pub app_name: String,
+}
+
+fn default_port() -> u16 {
+  514
+}
+
+fn default_app_name() -> String {
+  "manta".to_string()
+}
+
+impl Syslog {
+  /// Formats a RFC 5424 message, eg:
+  /// `<110>1 2025-06-01T10:00:00.000Z login01 manta 1234 audit - {...}`
+  pub fn format_message(
+    &self,
+    severity: u8,
+    timestamp: DateTime<Utc>,
+    data: &[u8],
+  ) -> String {
+    let hostname = std::env::var("HOSTNAME")
+      .ok()
+      .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+      .map(|hostname| hostname.trim().to_string())
+      .filter(|hostname| !hostname.is_empty())
+      .unwrap_or("-".to_string());
+
+    format!(
+      "<{}>1 {} {} {} {} audit - {}",
+      FACILITY_LOG_AUDIT * 8 + severity,
+      timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+      hostname,
+      self.app_name,
+      std::process::id(),
+      String::from_utf8_lossy(data)
+    )
+  }
+
+  async fn send(&self, message: &str) -> Result<(), Error> {
+    let address = format!("{}:{}", self.host, self.port);
+
+    match self.protocol {
+      SyslogProtocol::Udp => {
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket.send_to(message.as_bytes(), address).await?;
+      }
+      SyslogProtocol::Tcp => {
+        let mut stream = TcpStream::connect(address).await?;
+        stream
+          .write_all(format!("{} {}", message.len(), message).as_bytes())
+          .await?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl Audit for Syslog {
+  async fn produce_message(&self, data: &[u8]) -> Result<(), Error> {
+    let message = self.format_message(SEVERITY_INFORMATIONAL, Utc::now(), data);
+
+    self.send(&message).await
+  }
+
+  async fn audit(&self, event: &AuditEvent) -> Result<(), Error> {
+    let severity = match event.outcome {
+      AuditOutcome::Success => SEVERITY_INFORMATIONAL,
+      AuditOutcome::Failure => SEVERITY_WARNING,
+    };
+
+    let message = self.format_message(
+      severity,
+      event.timestamp,
+      &serde_json::to_vec(event)?,
+    );
+
+    self.send(&message).await
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,105 @@
+use chrono::{TimeZone, Utc};
+use tokio::{
+  io::AsyncReadExt,
+  net::{TcpListener, UdpSocket},
+};
+
+use crate::audit::{Audit, AuditEvent, AuditOutcome, AuditUser};
+
+use super::{Syslog, SyslogProtocol};
+
+fn syslog(port: u16, protocol: SyslogProtocol) -> Syslog {
+  Syslog {
+    host: "127.0.0.1".to_string(),
+    port,
+    protocol,
+    app_name: "manta".to_string(),
+  }
+}
+
+fn event() -> AuditEvent {
+  AuditEvent::new(
+    AuditUser {
+      id: "jdoe".to_string(),
+      name: "John Doe".to_string(),
+    },
+    "alps",
+    "power on nodes",
+  )
+}
+
+/// Checks the message has the RFC 5424 header fields expected and returns its
+/// structured data and message part
+fn parse_message(message: &str, priority: u8) -> String {
+  let field_vec: Vec<&str> = message.splitn(8, ' ').collect();
+
+  assert_eq!(field_vec.len(), 8, "{}", message);
+  assert_eq!(field_vec[0], format!("<{}>1", priority));
+  assert!(!field_vec[2].is_empty());
+  assert_eq!(field_vec[3], "manta");
+  assert_eq!(field_vec[4], std::process::id().to_string());
+  assert_eq!(field_vec[5], "audit");
+  assert_eq!(field_vec[6], "-");
+
+  field_vec[1].to_string() + " " + field_vec[7]
+}
+
+#[test]
+fn test_format_message() {
+  let timestamp = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+
+  let message =
+    syslog(514, SyslogProtocol::Udp).format_message(6, timestamp, b"{}");
+
+  // 'log audit' facility (13) and 'informational' severity (6)
+  assert_eq!(parse_message(&message, 110), "2025-06-01T10:00:00.000Z {}");
+}
+
+#[tokio::test]
+async fn test_audit_udp() {
+  let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+  let port = socket.local_addr().unwrap().port();
+
+  let event = event();
+
+  syslog(port, SyslogProtocol::Udp)
+    .audit(&event)
+    .await
+    .unwrap();
+
+  let mut buffer = vec![0; 65536];
+  let len = socket.recv(&mut buffer).await.unwrap();
+  let message = String::from_utf8_lossy(&buffer[..len]).to_string();
+
+  let data = parse_message(&message, 110);
+  let json = data.split_once(' ').unwrap().1;
+
+  assert_eq!(
+    serde_json::from_str::<AuditEvent>(json).unwrap().command,
+    event.command
+  );
+}
+
+#[tokio::test]
+async fn test_audit_tcp_failure() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port();
+
+  let event = event().with_outcome(AuditOutcome::Failure);
+
+  syslog(port, SyslogProtocol::Tcp)
+    .audit(&event)
+    .await
+    .unwrap();
+
+  let (mut stream, _) = listener.accept().await.unwrap();
+  let mut frame = String::new();
+  stream.read_to_string(&mut frame).await.unwrap();
+
+  // Octet counting framing
+  let (len, message) = frame.split_once(' ').unwrap();
+  assert_eq!(len.parse::<usize>().unwrap(), message.len());
+
+  // 'log audit' facility (13) and 'warning' severity (4)
+  parse_message(message, 108);
+}
@@ -0,0 +1,64 @@
+use serde_json::json;
+
+use super::{AuditSink, Auditor};
+
+#[test]
+fn test_sink_vec_includes_legacy_kafka() {
+  let auditor: Auditor = serde_json::from_value(json!({
+    "sinks": [
+      { "type": "file", "path": "/var/log/manta/audit.jsonl" },
+      { "type": "syslog", "host": "syslog.cscs.ch", "protocol": "tcp" },
+    ],
+    "kafka": { "brokers": ["kafka.cscs.ch:9092"], "topic": "manta-audit" },
+  }))
+  .unwrap();
+
+  let sink_vec = auditor.sink_vec();
+
+  assert!(!auditor.is_empty());
+  assert_eq!(sink_vec.len(), 3);
+  assert!(matches!(sink_vec[0], AuditSink::File(_)));
+  assert!(matches!(sink_vec[1], AuditSink::Syslog(_)));
+  assert!(
+    matches!(&sink_vec[2], AuditSink::Kafka(kafka) if kafka.topic == "manta-audit")
+  );
+}
+
+#[test]
+fn test_sink_vec_legacy_kafka_only() {
+  let auditor: Auditor = serde_json::from_value(json!({
+    "kafka": { "brokers": ["kafka.cscs.ch:9092"], "topic": "manta-audit" },
+  }))
+  .unwrap();
+
+  let sink_vec = auditor.sink_vec();
+
+  assert!(!auditor.is_empty());
+  assert_eq!(sink_vec.len(), 1);
+  assert!(matches!(sink_vec[0], AuditSink::Kafka(_)));
+}
+
+#[test]
+fn test_empty_auditor() {
+  let auditor: Auditor = serde_json::from_value(json!({})).unwrap();
+
+  assert!(auditor.is_empty());
+  assert!(auditor.sink_vec().is_empty());
+}
+
+#[test]
+fn test_new_event() {
+  let auditor = Auditor::default().with_site("alps");
+
+  let event = auditor.new_event(
+    super::AuditUser {
+      id: "jdoe".to_string(),
+      name: "John Doe".to_string(),
+    },
+    "power on nodes",
+  );
+
+  assert_eq!(event.site, "alps");
+  assert_eq!(event.command, "power on nodes");
+  assert_eq!(event.outcome, super::AuditOutcome::Success);
+}
@@ -0,0 +1,36 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+use super::Audit;
+
+/// Posts audit events to an HTTP endpoint
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Webhook {
+  pub url: String,
+  /// Extra headers sent with each request (eg: 'Authorization')
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub headers: HashMap<String, String>,
+}
+
+impl Audit for Webhook {
+  async fn produce_message(&self, data: &[u8]) -> Result<(), Error> {
+    let client = reqwest::Client::builder()
+      .timeout(Duration::from_secs(5))
+      .build()?;
+
+    let request = self.headers.iter().fold(
+      client
+        .post(&self.url)
+        .header("Content-Type", "application/json")
+        .body(data.to_vec()),
+      |request, (key, value)| request.header(key, value),
+    );
+
+    request.send().await?.error_for_status()?;
+
+    Ok(())
+  }
+}
@@ -1,8 +1,12 @@
-use crate::error::Error;
+use crate::{error::Error, types::capability::Capabilities};
 
 pub trait BackendTrait {
   fn test_backend_trait(&self) -> String;
 
+  /// Operations supported by the backend. Frontends use it to hide or reject
+  /// commands the backend does not implement
+  fn capabilities(&self) -> Capabilities;
+
   // AUTHORIZATION
   fn get_api_token(
     &self,
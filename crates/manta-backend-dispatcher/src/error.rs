@@ -20,4 +20,152 @@ pub enum Error {
   CsmError(Value),
   #[error("ERROR - Console: {0}")]
   ConsoleError(String),
+  /// Resource does not exist (HTTP 404)
+  #[error("ERROR - {service}: {resource} '{id}' not found")]
+  NotFound {
+    service: String,
+    resource: String,
+    id: String,
+  },
+  /// Authentication token missing, not valid or expired (HTTP 401)
+  #[error("ERROR - {service}: unauthorized. {detail}")]
+  Unauthorized { service: String, detail: String },
+  /// Authentication token valid but without enough permissions (HTTP 403)
+  #[error("ERROR - {service}: forbidden. {detail}")]
+  Forbidden { service: String, detail: String },
+  /// Resource already exists or request conflicts with its current state
+  /// (HTTP 409)
+  #[error("ERROR - {service}: conflict with {resource} '{id}'. {detail}")]
+  Conflict {
+    service: String,
+    resource: String,
+    id: String,
+    detail: String,
+  },
+  /// Request rejected by the service (HTTP 400 or 422)
+  #[error("ERROR - {service}: bad request. {detail}")]
+  BadRequest { service: String, detail: String },
+  /// Operation did not finish in time (HTTP 504 or client timeout)
+  #[error("ERROR - {service}: timeout. {detail}")]
+  Timeout { service: String, detail: String },
+  /// Any other HTTP error returned by a backend service
+  #[error("ERROR - {service}: http status {status}. {detail}")]
+  Http {
+    service: String,
+    status: u16,
+    detail: String,
+  },
+  /// Operation not supported by the backend in use
+  #[error("ERROR - {0} not implemented for this backend")]
+  NotImplemented(String),
+}
+
+impl Error {
+  /// Builds the error matching the HTTP status returned by a backend service.
+  /// `resource` and `id` identify the resource the request was about (eg:
+  /// 'group' and 'zinal') and `detail` is the reason given by the service
+  pub fn from_http_status(
+    service: &str,
+    resource: &str,
+    id: &str,
+    status: u16,
+    detail: &str,
+  ) -> Self {
+    let service = service.to_string();
+    let detail = detail.to_string();
+
+    match status {
+      400 | 422 => Error::BadRequest { service, detail },
+      401 => Error::Unauthorized { service, detail },
+      403 => Error::Forbidden { service, detail },
+      404 => Error::NotFound {
+        service,
+        resource: resource.to_string(),
+        id: id.to_string(),
+      },
+      409 => Error::Conflict {
+        service,
+        resource: resource.to_string(),
+        id: id.to_string(),
+        detail,
+      },
+      408 | 504 => Error::Timeout { service, detail },
+      501 => Error::NotImplemented(detail),
+      _ => Error::Http {
+        service,
+        status,
+        detail,
+      },
+    }
+  }
+
+  /// HTTP status code that best describes the error, eg to answer a client
+  /// request which failed because of this error
+  pub fn http_status(&self) -> u16 {
+    match self {
+      Error::NotFound { .. } => 404,
+      Error::Unauthorized { .. } => 401,
+      Error::Forbidden { .. } => 403,
+      Error::Conflict { .. } => 409,
+      Error::BadRequest { .. } => 400,
+      Error::Timeout { .. } => 504,
+      Error::NotImplemented(_) => 501,
+      Error::Http { status, .. } if *status >= 400 => *status,
+      Error::RequestError { response, .. } => response
+        .status()
+        .map(|status| status.as_u16())
+        .unwrap_or(502),
+      Error::NetError(e) if e.is_timeout() => 504,
+      Error::NetError(_) => 502,
+      _ => 500,
+    }
+  }
+
+  /// Service the error comes from, eg 'CFS', if known
+  pub fn service(&self) -> Option<&str> {
+    match self {
+      Error::NotFound { service, .. }
+      | Error::Unauthorized { service, .. }
+      | Error::Forbidden { service, .. }
+      | Error::Conflict { service, .. }
+      | Error::BadRequest { service, .. }
+      | Error::Timeout { service, .. }
+      | Error::Http { service, .. } => Some(service),
+      _ => None,
+    }
+  }
+
+  pub fn is_not_found(&self) -> bool {
+    matches!(self, Error::NotFound { .. })
+  }
+
+  pub fn is_unauthorized(&self) -> bool {
+    matches!(self, Error::Unauthorized { .. } | Error::Forbidden { .. })
+  }
+
+  pub fn is_not_implemented(&self) -> bool {
+    matches!(self, Error::NotImplemented(_))
+  }
+
+  /// Hint to help users fix the problem, if any
+  pub fn hint(&self) -> Option<&'static str> {
+    match self {
+      Error::Unauthorized { .. } => {
+        Some("Authentication token missing or expired, please log in again")
+      }
+      Error::Forbidden { .. } => {
+        Some("Your user does not have enough permissions for this operation")
+      }
+      Error::NotImplemented(_) => {
+        Some("Operation not supported by the backend configured for this site")
+      }
+      Error::Timeout { .. } => {
+        Some("Service did not answer in time, please try again later")
+      }
+      Error::NetError(_) => {
+        Some("Could not reach the backend, check the site URL and network")
+      }
+      _ => None,
+    }
+  }
 }
@@ -23,10 +23,6 @@ pub trait SatTrait {
     _debug_on_failure: bool,
     _dry_run: bool,
   ) -> impl Future<Output = Result<(), Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Apply SAT file command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Apply SAT file command".to_string())) }
   }
 }
@@ -20,10 +20,6 @@ pub trait ApplySessionTrait {
     _ansible_passthrough: Option<String>,
     // _watch_logs: bool,
   ) -> impl Future<Output = Result<(String, String), Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Apply session command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Apply session command".to_string())) }
   }
 }
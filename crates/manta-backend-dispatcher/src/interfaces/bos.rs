@@ -15,11 +15,7 @@ pub trait ClusterTemplateTrait {
     _shasta_root_cert: &[u8],
     _bos_session_template_id_opt: Option<&str>,
   ) -> impl Future<Output = Result<Vec<BosSessionTemplate>, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Get template command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Get template command".to_string())) }
   }
 
   fn get_and_filter_templates(
@@ -32,11 +28,7 @@ pub trait ClusterTemplateTrait {
     _bos_sessiontemplate_name_opt: Option<&String>,
     _limit_number_opt: Option<&u8>,
   ) -> impl Future<Output = Result<Vec<BosSessionTemplate>, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Get templates command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Get templates command".to_string())) }
   }
 
   fn get_all_templates(
@@ -46,9 +38,8 @@ pub trait ClusterTemplateTrait {
     _shasta_root_cert: &[u8],
   ) -> impl Future<Output = Result<Vec<BosSessionTemplate>, Error>> + Send {
     async {
-      Err(Error::Message(
-        "Get all templates command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get all templates command".to_string(),
       ))
     }
   }
@@ -61,11 +52,7 @@ pub trait ClusterTemplateTrait {
     _bos_template: &BosSessionTemplate,
     _bos_template_name: &str,
   ) -> impl Future<Output = Result<BosSessionTemplate, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Put template command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Put template command".to_string())) }
   }
 
   fn delete_template(
@@ -75,11 +62,7 @@ pub trait ClusterTemplateTrait {
     _shasta_root_cert: &[u8],
     _bos_template_id: &str,
   ) -> impl Future<Output = Result<(), Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Delete template command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Delete template command".to_string())) }
   }
 }
 
@@ -92,9 +75,8 @@ pub trait ClusterSessionTrait {
     _bos_session: BosSession,
   ) -> impl Future<Output = Result<Value, Error>> + Send {
     async {
-      Err(Error::Message(
-        "Create session template command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Create session template command".to_string(),
       ))
     }
   }
@@ -22,9 +22,8 @@ pub trait CfsTrait {
     _k8s: &K8sDetails,
   ) -> impl Future<Output = Result<Self::T, Error>> {
     async {
-      Err(Error::Message(
-        "Get session logs stream command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get session logs stream command".to_string(),
       ))
     }
   }
@@ -37,19 +36,14 @@ pub trait CfsTrait {
     _k8s: &K8sDetails,
   ) -> impl Future<Output = Result<Self::T, Error>> {
     async {
-      Err(Error::Message(
-                "Get session logs stream by xname command not implemented for this backend"
-                    .to_string(),
-            ))
+      Err(Error::NotImplemented(
+        "Get session logs stream by xname command".to_string(),
+      ))
     }
   }
 
   fn get_cfs_health(&self) -> impl Future<Output = Result<(), Error>> {
-    async {
-      Err(Error::Message(
-        "Get CFS health command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Get CFS health command".to_string())) }
   }
 
   fn post_session(
@@ -59,11 +53,7 @@ pub trait CfsTrait {
     _shasta_root_cert: &[u8],
     _session: &CfsSessionPostRequest,
   ) -> impl Future<Output = Result<CfsSessionGetResponse, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Post session command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Post session command".to_string())) }
   }
 
   fn get_sessions(
@@ -82,11 +72,7 @@ pub trait CfsTrait {
     _tags_opt: Option<String>,
   ) -> impl Future<Output = Result<Vec<CfsSessionGetResponse>, Error>> + Send
   {
-    async {
-      Err(Error::Message(
-        "Get sessions command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Get sessions command".to_string())) }
   }
 
   fn get_sessions_by_xname(
@@ -106,9 +92,8 @@ pub trait CfsTrait {
   ) -> impl Future<Output = Result<Vec<CfsSessionGetResponse>, Error>> + Send
   {
     async {
-      Err(Error::Message(
-        "Get sessions by xname command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get sessions by xname command".to_string(),
       ))
     }
   }
@@ -129,9 +114,8 @@ pub trait CfsTrait {
   ) -> impl Future<Output = Result<Vec<CfsSessionGetResponse>, Error>> + Send
   {
     async {
-      Err(Error::Message(
-        "Get and filter sessions command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get and filter sessions command".to_string(),
       ))
     }
   }
@@ -145,9 +129,8 @@ pub trait CfsTrait {
   ) -> impl Future<Output = Result<Vec<CfsConfigurationResponse>, Error>> + Send
   {
     async {
-      Err(Error::Message(
-        "Get configuration command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get configuration command".to_string(),
       ))
     }
   }
@@ -164,9 +147,8 @@ pub trait CfsTrait {
   ) -> impl Future<Output = Result<Vec<CfsConfigurationResponse>, Error>> + Send
   {
     async {
-      Err(Error::Message(
-        "Get and filter configuration command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get and filter configuration command".to_string(),
       ))
     }
   }
@@ -180,10 +162,9 @@ pub trait CfsTrait {
     _site_name: &str, // FIXME: Should we move 'site_name' as Self.site_name?
   ) -> impl Future<Output = Result<LayerDetails, Error>> + Send {
     async {
-      Err(Error::Message(
-                "Get configuration layer details command not implemented for this backend"
-                    .to_string(),
-            ))
+      Err(Error::NotImplemented(
+        "Get configuration layer details command".to_string(),
+      ))
     }
   }
 
@@ -197,10 +178,9 @@ pub trait CfsTrait {
     _playbook_file_name_opt: Option<&String>,
   ) -> impl Future<Output = Result<CfsConfigurationRequest, Error>> {
     async {
-      Err(Error::Message(
-                "Create configuration from repos command not implemented for this backend"
-                    .to_string(),
-            ))
+      Err(Error::NotImplemented(
+        "Create configuration from repos command".to_string(),
+      ))
     }
   }
 
@@ -216,9 +196,8 @@ pub trait CfsTrait {
     _configuration_name: &str,
   ) -> impl Future<Output = Result<CfsConfigurationResponse, Error>> + Send {
     async {
-      Err(Error::Message(
-        "Put configuration command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Put configuration command".to_string(),
       ))
     }
   }
@@ -233,9 +212,8 @@ pub trait CfsTrait {
     _enabled: bool,
   ) -> impl Future<Output = Result<(), Error>> + Send {
     async {
-      Err(Error::Message(
-        "Update runtime configuration command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Update runtime configuration command".to_string(),
       ))
     }
   }
@@ -257,11 +235,7 @@ pub trait CfsTrait {
       Error,
     >,
   > + Send {
-    async {
-      Err(Error::Message(
-        "Get derivatives command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Get derivatives command".to_string())) }
   }
 
   fn get_cfs_components(
@@ -274,9 +248,8 @@ pub trait CfsTrait {
     _status: Option<&str>,
   ) -> impl Future<Output = Result<Vec<Component>, Error>> + Send {
     async {
-      Err(Error::Message(
-        "Get CFS conponents command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get CFS conponents command".to_string(),
       ))
     }
   }
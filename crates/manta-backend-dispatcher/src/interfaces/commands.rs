@@ -16,9 +16,8 @@ pub trait CommandsTrait {
     _assume_yes: bool,
   ) -> impl Future<Output = Result<(), Error>> + Send {
     async {
-      Err(Error::Message(
-        "Delete and cancel session command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Delete and cancel session command".to_string(),
       ))
     }
   }
@@ -36,10 +35,9 @@ pub trait CommandsTrait {
     _assume_yes: bool,
   ) -> impl Future<Output = Result<(), Error>> + Send {
     async {
-      Err(Error::Message(
-                "Delete data related to CFS configuration command not implemented for this backend"
-                    .to_string(),
-            ))
+      Err(Error::NotImplemented(
+        "Delete data related to CFS configuration command".to_string(),
+      ))
     }
   }
 }
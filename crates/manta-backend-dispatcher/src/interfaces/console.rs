@@ -19,9 +19,8 @@ pub trait ConsoleTrait {
     _k8s: &K8sDetails,
   ) -> impl Future<Output = Result<(Self::T, Self::U), Error>> + Send {
     async {
-      Err(Error::Message(
-        "Attach to console command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Attach to console command".to_string(),
       ))
     }
   }
@@ -14,9 +14,8 @@ pub trait GetImagesAndDetailsTrait {
   ) -> impl Future<Output = Result<Vec<(Image, String, String, bool)>, Error>> + Send
   {
     async {
-      Err(Error::Message(
-        "Get images and details command not implemented for this backend"
-          .to_string(),
+      Err(Error::NotImplemented(
+        "Get images and details command".to_string(),
       ))
     }
   }
@@ -10,11 +10,7 @@ pub trait ImsTrait {
     _shasta_root_cert: &[u8],
     _image_id_opt: Option<&str>,
   ) -> impl Future<Output = Result<Vec<Image>, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Get images command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Get images command".to_string())) }
   }
 
   fn get_all_images(
@@ -23,17 +19,11 @@ pub trait ImsTrait {
     _shasta_base_url: &str,
     _shasta_root_cert: &[u8],
   ) -> impl Future<Output = Result<Vec<Image>, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Get all images command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Get all images command".to_string())) }
   }
 
   fn filter_images(&self, _image_vec: &mut Vec<Image>) -> Result<(), Error> {
-    Err(Error::Message(
-      "Filter images command not implemented for this backend".to_string(),
-    ))
+    Err(Error::NotImplemented("Filter images command".to_string()))
   }
 
   fn delete_image(
@@ -43,10 +33,6 @@ pub trait ImsTrait {
     _shasta_root_cert: &[u8],
     _image_id: &str,
   ) -> impl Future<Output = Result<(), Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Delete image command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Delete image command".to_string())) }
   }
 }
@@ -11,10 +11,6 @@ pub trait MigrateBackupTrait {
     _bos: Option<&String>,
     _destination: Option<&String>,
   ) -> impl Future<Output = Result<(), Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Migrate/backup command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Migrate/backup command".to_string())) }
   }
 }
@@ -14,10 +14,6 @@ pub trait MigrateRestoreTrait {
     _ims_file: Option<&String>,
     _image_dir: Option<&String>,
   ) -> impl Future<Output = Result<(), Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Migrate/restore command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Migrate/restore command".to_string())) }
   }
 }
@@ -1,6 +1,7 @@
 use serde_json::Value;
 
 use crate::error::Error;
+use crate::types::pcs::power_cap::types::PowerCapTaskInfo;
 use crate::types::pcs::power_status::types::PowerStatusAll;
 
 pub trait PCSTrait {
@@ -9,11 +10,7 @@ pub trait PCSTrait {
     _auth_token: &str,
     _nodes: &[String],
   ) -> impl std::future::Future<Output = Result<Value, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Power on command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Power on command".to_string())) }
   }
 
   fn power_off_sync(
@@ -22,11 +19,7 @@ pub trait PCSTrait {
     _nodes: &[String],
     _force: bool,
   ) -> impl std::future::Future<Output = Result<Value, Error>> + Send {
-    async {
-      Err(Error::Message(
-        "Power off command not implemented for this backend".to_string(),
-      ))
-    }
+    async { Err(Error::NotImplemented("Power off command".to_string())) }
   }
 
   fn power_reset_sync(
@@ -35,9 +28,59 @@ pub trait PCSTrait {
     _nodes: &[String],
     _force: bool,
   ) -> impl std::future::Future<Output = Result<Value, Error>> + Send {
+    async { Err(Error::NotImplemented("Power reset command".to_string())) }
+  }
+
+  /// Creates a power on transition and returns its ID without waiting for it to complete
+  fn power_on(
+    &self,
+    _auth_token: &str,
+    _nodes: &[String],
+  ) -> impl std::future::Future<Output = Result<String, Error>> + Send {
+    async { Err(Error::NotImplemented("Power on command".to_string())) }
+  }
+
+  /// Creates a power off transition and returns its ID without waiting for it to complete
+  fn power_off(
+    &self,
+    _auth_token: &str,
+    _nodes: &[String],
+    _force: bool,
+  ) -> impl std::future::Future<Output = Result<String, Error>> + Send {
+    async { Err(Error::NotImplemented("Power off command".to_string())) }
+  }
+
+  /// Creates a power reset transition and returns its ID without waiting for it to complete
+  fn power_reset(
+    &self,
+    _auth_token: &str,
+    _nodes: &[String],
+    _force: bool,
+  ) -> impl std::future::Future<Output = Result<String, Error>> + Send {
+    async { Err(Error::NotImplemented("Power reset command".to_string())) }
+  }
+
+  /// Returns a power transition, including the task of each node
+  fn get_power_transition(
+    &self,
+    _auth_token: &str,
+    _transition_id: &str,
+  ) -> impl std::future::Future<Output = Result<Value, Error>> + Send {
+    async {
+      Err(Error::NotImplemented(
+        "Get power transition command".to_string(),
+      ))
+    }
+  }
+
+  /// Returns all the power transitions known by PCS
+  fn get_power_transitions(
+    &self,
+    _auth_token: &str,
+  ) -> impl std::future::Future<Output = Result<Vec<Value>, Error>> + Send {
     async {
-      Err(Error::Message(
-        "Power reset command not implemented for this backend".to_string(),
+      Err(Error::NotImplemented(
+        "Get power transitions command".to_string(),
       ))
     }
   }
@@ -53,10 +96,33 @@ pub trait PCSTrait {
   ) -> impl std::future::Future<Output = Result<PowerStatusAll, Error>> + Send
   {
     // TODO: this should return PowerStatusAll instead
+    async { Err(Error::NotImplemented("Power status command".to_string())) }
+  }
+
+  /// Reads the power cap controls of the nodes. Waits for the snapshot task to complete
+  fn power_cap_snapshot(
+    &self,
+    _auth_token: &str,
+    _nodes: &[String],
+  ) -> impl std::future::Future<Output = Result<PowerCapTaskInfo, Error>> + Send
+  {
     async {
-      Err(Error::Message(
-        "Power status command not implemented for this backend".to_string(),
+      Err(Error::NotImplemented(
+        "Power cap snapshot command".to_string(),
       ))
     }
   }
+
+  /// Sets the power cap control `control_name` of the nodes to `watts`. Waits for the patch
+  /// task to complete
+  fn power_cap_patch(
+    &self,
+    _auth_token: &str,
+    _nodes: &[String],
+    _control_name: &str,
+    _watts: usize,
+  ) -> impl std::future::Future<Output = Result<PowerCapTaskInfo, Error>> + Send
+  {
+    async { Err(Error::NotImplemented("Power cap patch command".to_string())) }
+  }
 }
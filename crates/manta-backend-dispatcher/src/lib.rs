@@ -2,4 +2,5 @@ pub mod audit;
 pub mod contracts;
 pub mod error;
 pub mod interfaces;
+pub mod mock;
 pub mod types;
@@ -0,0 +1,2829 @@
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+  pin::Pin,
+  sync::{Arc, Mutex, PoisonError},
+};
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use futures_io::AsyncBufRead;
+use hostlist_parser::parse;
+use regex::Regex;
+use serde_json::{json, Value};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::{
+  contracts::BackendTrait,
+  error::Error,
+  interfaces::{
+    apply_hw_cluster_pin::ApplyHwClusterPin,
+    apply_sat_file::SatTrait,
+    apply_session::ApplySessionTrait,
+    bos::{ClusterSessionTrait, ClusterTemplateTrait},
+    bss::BootParametersTrait,
+    cfs::CfsTrait,
+    commands::CommandsTrait,
+    console::ConsoleTrait,
+    get_images_and_details::GetImagesAndDetailsTrait,
+    hsm::{
+      component::ComponentTrait, group::GroupTrait,
+      hardware_inventory::HardwareInventory,
+      redfish_endpoint::RedfishEndpointTrait,
+    },
+    ims::ImsTrait,
+    migrate_backup::MigrateBackupTrait,
+    migrate_restore::MigrateRestoreTrait,
+    pcs::PCSTrait,
+  },
+  mock::types::MockState,
+  types::{
+    bos::{
+      session::BosSession,
+      session_template::{BootSet, BosSessionTemplate, Cfs},
+    },
+    capability::{Capabilities, Capability},
+    cfs::{
+      cfs_configuration_details::LayerDetails,
+      cfs_configuration_request::{
+        CfsConfigurationRequest, Layer as RequestLayer,
+      },
+      cfs_configuration_response::{
+        AdditionalInventory, CfsConfigurationResponse, Layer,
+      },
+      component::Component as CfsComponent,
+      session::{
+        Ansible, Artifact, CfsSessionGetResponse, CfsSessionPostRequest,
+        Configuration, Group as CfsSessionGroup, Session, Status,
+      },
+    },
+    hsm::inventory::{RedfishEndpoint, RedfishEndpointArray},
+    ims::Image,
+    pcs::{
+      power_status::types::{
+        ManagementState, PowerState, PowerStatus, PowerStatusAll,
+      },
+      transitions::types::Operation,
+    },
+    BootParameters, Component, ComponentArrayPostArray, ComponentCreate,
+    Group, HWInventoryByLocationList, NodeMetadataArray,
+  },
+};
+
+/// In-memory backend used to run manta workflows without a live CSM/OCHAMI system.
+/// The backend state is seeded from a fixture file (see [`MockState`]) and all operations
+/// are applied to that state. Changes are saved back to the fixture file, so consecutive
+/// commands see the changes of the previous ones. Clones of a `MockBackend` share the same
+/// state.
+#[derive(Clone, Default)]
+pub struct MockBackend {
+  state: Arc<Mutex<MockState>>,
+  /// File the state is saved to after each change, none if the backend was created from
+  /// an existing state
+  fixture_path_opt: Option<PathBuf>,
+}
+
+impl MockBackend {
+  /// Creates a mock backend seeded with the content of a JSON or YAML fixture file. The
+  /// fixture path may be prefixed with 'file://'
+  pub fn new(fixture_file: &str) -> Result<Self, Error> {
+    let fixture_path =
+      Path::new(fixture_file.strip_prefix("file://").unwrap_or(fixture_file));
+
+    let state = MockState::from_file(fixture_path)?;
+
+    Ok(Self {
+      state: Arc::new(Mutex::new(state)),
+      fixture_path_opt: Some(fixture_path.to_path_buf()),
+    })
+  }
+
+  /// Creates a mock backend seeded with an existing state. Changes are kept in memory
+  pub fn from_state(state: MockState) -> Self {
+    Self {
+      state: Arc::new(Mutex::new(state)),
+      fixture_path_opt: None,
+    }
+  }
+
+  /// Returns a snapshot of the current state
+  pub fn get_state(&self) -> MockState {
+    self.read(|state| state.clone())
+  }
+
+  fn read<R>(&self, f: impl FnOnce(&MockState) -> R) -> R {
+    let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+    f(&state)
+  }
+
+  fn write<R>(&self, f: impl FnOnce(&mut MockState) -> R) -> R {
+    let mut state =
+      self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let result = f(&mut state);
+
+    if let Some(fixture_path) = &self.fixture_path_opt {
+      if let Err(e) = state.to_file(fixture_path) {
+        log::error!(
+          "Could not save mock state to fixture file '{}'. Reason:\n{}",
+          fixture_path.display(),
+          e
+        );
+      }
+    }
+
+    result
+  }
+}
+
+/// Returns current time in the same format used by CSM APIs
+fn now() -> String {
+  Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn new_id() -> String {
+  uuid::Uuid::new_v4().to_string()
+}
+
+/// Returns 'true' if the value requested by the user is missing or if it matches any of the
+/// comma separated values. Comparison is case insensitive, same as HSM does
+fn matches_filter(filter_opt: Option<&str>, value_opt: Option<&str>) -> bool {
+  filter_opt.is_none_or(|filter| {
+    value_opt.is_some_and(|value| {
+      filter
+        .split(',')
+        .any(|filter_value| filter_value.trim().eq_ignore_ascii_case(value))
+    })
+  })
+}
+
+/// Converts a glob pattern (eg: 'compute-*') into a regex
+fn glob_to_regex(pattern: &str) -> Result<Regex, Error> {
+  let regex_str = format!(
+    "^{}$",
+    regex::escape(pattern)
+      .replace(r"\*", ".*")
+      .replace(r"\?", ".")
+  );
+
+  Regex::new(&regex_str).map_err(|e| Error::Message(e.to_string()))
+}
+
+fn component_from_component_create(component: ComponentCreate) -> Component {
+  Component {
+    id: Some(component.id),
+    r#type: Some("Node".to_string()),
+    state: Some(component.state),
+    flag: component.flag,
+    enabled: component.enabled,
+    software_status: component.software_status,
+    role: component.role,
+    sub_role: component.sub_role,
+    nid: component.nid,
+    subtype: component.subtype,
+    net_type: component.net_type,
+    arch: component.arch,
+    class: component.class,
+    reservation_disabled: None,
+    locked: None,
+  }
+}
+
+fn layer_from_request_layer(layer: RequestLayer) -> Layer {
+  Layer {
+    name: layer.name.unwrap_or_default(),
+    clone_url: layer.clone_url.unwrap_or_default(),
+    source: layer.source,
+    commit: layer.commit,
+    playbook: layer.playbook,
+    branch: layer.branch,
+  }
+}
+
+/// Returns the list of xnames targeted by a CFS session, either through its target groups or
+/// through its ansible limit
+fn get_session_xname_vec(
+  state: &MockState,
+  session: &CfsSessionGetResponse,
+) -> Vec<String> {
+  let mut xname_vec = session.get_target_xname().unwrap_or_default();
+
+  if let Some(target_group_vec) = session.get_target_hsm() {
+    xname_vec.extend(state.get_group_vec_members(&target_group_vec));
+  }
+
+  xname_vec
+}
+
+/// Returns the list of xnames targeted by a BOS sessiontemplate
+fn get_template_xname_vec(
+  state: &MockState,
+  template: &BosSessionTemplate,
+) -> Vec<String> {
+  if template.boot_sets.is_none() {
+    return Vec::new();
+  }
+
+  let mut xname_vec = template.get_target_xname();
+  xname_vec.extend(state.get_group_vec_members(&template.get_target_hsm()));
+
+  xname_vec
+}
+
+/// Creates a CFS session and, if the session builds an image, the resulting IMS images
+fn create_session(
+  state: &mut MockState,
+  session: &CfsSessionPostRequest,
+) -> Result<CfsSessionGetResponse, Error> {
+  if state
+    .cfs_sessions
+    .iter()
+    .any(|cfs_session| cfs_session.name.as_ref() == Some(&session.name))
+  {
+    return Err(Error::Conflict {
+      service: "CFS".to_string(),
+      resource: "session".to_string(),
+      id: session.name.to_string(),
+      detail: "Resource already exists".to_string(),
+    });
+  }
+
+  if !state.cfs_configurations.iter().any(|configuration| {
+    configuration.name == session.configuration_name
+  }) {
+    return Err(Error::NotFound {
+      service: "CFS".to_string(),
+      resource: "configuration".to_string(),
+      id: session.configuration_name.to_string(),
+    });
+  }
+
+  let timestamp = now();
+
+  // Images built by the session
+  let mut artifact_vec = Vec::new();
+
+  if session.target.definition.as_deref() == Some("image") {
+    for image_map in session.target.image_map.iter().flatten() {
+      let image = Image {
+        id: Some(new_id()),
+        created: Some(timestamp.clone()),
+        name: image_map.result_name.clone(),
+        link: None,
+        arch: None,
+      };
+
+      artifact_vec.push(Artifact {
+        image_id: Some(image_map.source_id.clone()),
+        result_id: image.id.clone(),
+        r#type: Some("ims_customized_image".to_string()),
+      });
+
+      state.images.push(image);
+    }
+  }
+
+  let cfs_session = CfsSessionGetResponse {
+    name: Some(session.name.clone()),
+    configuration: Some(Configuration {
+      name: Some(session.configuration_name.clone()),
+      limit: session.configuration_limit.clone(),
+    }),
+    ansible: Some(Ansible {
+      config: session.ansible_config.clone(),
+      limit: session.ansible_limit.clone(),
+      verbosity: session.ansible_verbosity.map(u64::from),
+      passthrough: session.ansible_passthrough.clone(),
+    }),
+    target: Some(session.target.clone()),
+    status: Some(Status {
+      artifacts: Some(artifact_vec),
+      session: Some(Session {
+        job: None,
+        ims_job: None,
+        completion_time: Some(timestamp.clone()),
+        start_time: Some(timestamp),
+        status: Some("complete".to_string()),
+        succeeded: Some("true".to_string()),
+      }),
+    }),
+    tags: session.tags.clone(),
+    debug_on_failure: session.debug_on_failure,
+    logs: None,
+  };
+
+  state.cfs_sessions.push(cfs_session.clone());
+
+  Ok(cfs_session)
+}
+
+/// Runs a PCS transition against the mock state and returns it in the same format as PCS
+/// 'transitions' API
+fn create_power_transition(
+  state: &mut MockState,
+  operation: &str,
+  nodes: &[String],
+) -> Value {
+  let new_power_state = if operation.ends_with("off") { "off" } else { "on" };
+
+  let task_vec: Vec<Value> = nodes
+    .iter()
+    .map(|xname| {
+      let exists = state
+        .components
+        .iter()
+        .any(|component| component.id.as_ref() == Some(xname));
+
+      if exists {
+        state
+          .power_state
+          .insert(xname.clone(), new_power_state.to_string());
+
+        json!({
+          "xname": xname,
+          "taskStatus": "succeeded",
+          "taskStatusDescription": format!("Transition confirmed, {}", new_power_state),
+        })
+      } else {
+        json!({
+          "xname": xname,
+          "taskStatus": "failed",
+          "taskStatusDescription": "Component not found",
+        })
+      }
+    })
+    .collect();
+
+  let failed = task_vec
+    .iter()
+    .filter(|task| task["taskStatus"] == "failed")
+    .count();
+
+  let transition = json!({
+    "transitionID": new_id(),
+    "operation": operation,
+    "createTime": now(),
+    "transitionStatus": "completed",
+    "taskCounts": {
+      "total": task_vec.len(),
+      "succeeded": task_vec.len() - failed,
+      "failed": failed,
+    },
+    "tasks": task_vec,
+  });
+
+  state.power_transitions.push(transition.clone());
+
+  transition
+}
+
+impl BackendTrait for MockBackend {
+  fn test_backend_trait(&self) -> String {
+    println!("in mock backend");
+    "in mock backend".to_string()
+  }
+
+  fn capabilities(&self) -> Capabilities {
+    // Everything but the operations needing access to nodes, containers or
+    // external services
+    Capabilities::new(&[
+      Capability::Group,
+      Capability::Component,
+      Capability::HardwareInventory,
+      Capability::RedfishEndpoint,
+      Capability::BootParameters,
+      Capability::Power,
+      Capability::Cfs,
+      Capability::Bos,
+      Capability::Ims,
+      Capability::ApplySession,
+      Capability::SatFile,
+      Capability::DeleteSession,
+      Capability::DeleteConfiguration,
+    ])
+  }
+
+  async fn get_api_token(&self, _site_name: &str) -> Result<String, Error> {
+    Ok(self.read(|state| {
+      state
+        .auth_token
+        .clone()
+        .unwrap_or_else(|| "mock-token".to_string())
+    }))
+  }
+
+  /// Get list of xnames from NIDs
+  /// The list of NIDs can be:
+  ///     - comma separated list of NIDs (eg: nid000001,nid000002,nid000003)
+  ///     - regex (eg: nid00000.*)
+  ///     - hostlist (eg: nid0000[01-15])
+  async fn nid_to_xname(
+    &self,
+    _auth_token: &str,
+    user_input_nid: &str,
+    is_regex: bool,
+  ) -> Result<Vec<String>, Error> {
+    let node_vec: Vec<(String, usize)> = self.read(|state| {
+      state
+        .components
+        .iter()
+        .filter_map(|component| component.id.clone().zip(component.nid))
+        .collect()
+    });
+
+    if is_regex {
+      let regex_vec: Vec<Regex> = user_input_nid
+        .split(",")
+        .map(|regex_str| Regex::new(regex_str.trim()))
+        .collect::<Result<Vec<Regex>, regex::Error>>()
+        .map_err(|e| Error::Message(e.to_string()))?;
+
+      Ok(
+        node_vec
+          .into_iter()
+          .filter(|(_, nid)| {
+            let nid_long = format!("nid{:06}", nid);
+            regex_vec.iter().any(|regex| regex.is_match(&nid_long))
+          })
+          .map(|(xname, _)| xname)
+          .collect(),
+      )
+    } else {
+      let nid_hostlist_expanded_vec = parse(user_input_nid).map_err(|e| {
+        Error::Message(format!(
+          "Could not parse list of nodes as a hostlist. Reason:\n{}Exit",
+          e
+        ))
+      })?;
+
+      let mut nid_short_vec = Vec::new();
+
+      for nid_long in nid_hostlist_expanded_vec {
+        let nid_short = nid_long
+          .strip_prefix("nid")
+          .ok_or_else(|| {
+            Error::Message(format!(
+              "Nid '{}' not valid, 'nid' prefix missing",
+              nid_long
+            ))
+          })?
+          .parse::<usize>()
+          .map_err(|e| Error::Message(e.to_string()))?;
+
+        nid_short_vec.push(nid_short);
+      }
+
+      Ok(
+        node_vec
+          .into_iter()
+          .filter(|(_, nid)| nid_short_vec.contains(nid))
+          .map(|(xname, _)| xname)
+          .collect(),
+      )
+    }
+  }
+}
+
+impl GroupTrait for MockBackend {
+  async fn get_group_available(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Group>, Error> {
+    let available_groups_name =
+      self.get_group_name_available(auth_token).await?;
+
+    Ok(self.read(|state| {
+      state
+        .groups
+        .iter()
+        .filter(|group| available_groups_name.contains(&group.label))
+        .cloned()
+        .collect()
+    }))
+  }
+
+  async fn get_group_name_available(
+    &self,
+    _jwt_token: &str,
+  ) -> Result<Vec<String>, Error> {
+    Ok(self.read(|state| {
+      state.group_available.clone().unwrap_or_else(|| {
+        state.groups.iter().map(|group| group.label.clone()).collect()
+      })
+    }))
+  }
+
+  async fn add_group(
+    &self,
+    _auth_token: &str,
+    group: Group,
+  ) -> Result<Group, Error> {
+    self.write(|state| {
+      if state
+        .groups
+        .iter()
+        .any(|existing_group| existing_group.label == group.label)
+      {
+        return Err(Error::Conflict {
+          service: "HSM".to_string(),
+          resource: "group".to_string(),
+          id: group.label.to_string(),
+          detail: "Resource already exists".to_string(),
+        });
+      }
+
+      state.groups.push(group.clone());
+
+      Ok(group)
+    })
+  }
+
+  async fn get_member_vec_from_group_name_vec(
+    &self,
+    _auth_token: &str,
+    hsm_group_name_vec: Vec<String>,
+  ) -> Result<Vec<String>, Error> {
+    Ok(self.read(|state| state.get_group_vec_members(&hsm_group_name_vec)))
+  }
+
+  async fn get_group_map_and_filter_by_group_vec(
+    &self,
+    _auth_token: &str,
+    hsm_name_vec: Vec<&str>,
+  ) -> Result<HashMap<String, Vec<String>>, Error> {
+    Ok(self.read(|state| {
+      state
+        .groups
+        .iter()
+        .filter(|group| hsm_name_vec.contains(&group.label.as_str()))
+        .map(|group| (group.label.clone(), group.get_members()))
+        .collect()
+    }))
+  }
+
+  async fn get_group_map_and_filter_by_member_vec(
+    &self,
+    _auth_token: &str,
+    member_vec: &[&str],
+  ) -> Result<HashMap<String, Vec<String>>, Error> {
+    Ok(self.read(|state| {
+      state
+        .groups
+        .iter()
+        .filter(|group| {
+          group
+            .get_members()
+            .iter()
+            .any(|member| member_vec.contains(&member.as_str()))
+        })
+        .map(|group| (group.label.clone(), group.get_members()))
+        .collect()
+    }))
+  }
+
+  async fn get_all_groups(
+    &self,
+    _auth_token: &str,
+  ) -> Result<Vec<Group>, Error> {
+    Ok(self.read(|state| state.groups.clone()))
+  }
+
+  async fn get_group(
+    &self,
+    _auth_token: &str,
+    hsm_name: &str,
+  ) -> Result<Group, Error> {
+    self.read(|state| {
+      state
+        .groups
+        .iter()
+        .find(|group| group.label == hsm_name)
+        .cloned()
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "group".to_string(),
+            id: hsm_name.to_string(),
+          }
+        })
+    })
+  }
+
+  async fn get_groups(
+    &self,
+    _auth_token: &str,
+    hsm_name_vec: Option<&[&str]>,
+  ) -> Result<Vec<Group>, Error> {
+    Ok(self.read(|state| {
+      state
+        .groups
+        .iter()
+        .filter(|group| {
+          hsm_name_vec.is_none_or(|hsm_name_vec| {
+            hsm_name_vec.contains(&group.label.as_str())
+          })
+        })
+        .cloned()
+        .collect()
+    }))
+  }
+
+  async fn delete_group(
+    &self,
+    _auth_token: &str,
+    hsm_group_label: &str,
+  ) -> Result<Value, Error> {
+    self.write(|state| {
+      let group_count = state.groups.len();
+
+      state.groups.retain(|group| group.label != hsm_group_label);
+
+      if state.groups.len() == group_count {
+        return Err(Error::NotFound {
+          service: "HSM".to_string(),
+          resource: "group".to_string(),
+          id: hsm_group_label.to_string(),
+        });
+      }
+
+      Ok(json!({ "code": 0, "message": "deleted 1 entry" }))
+    })
+  }
+
+  async fn get_hsm_map_and_filter_by_hsm_name_vec(
+    &self,
+    auth_token: &str,
+    hsm_name_vec: Vec<&str>,
+  ) -> Result<HashMap<String, Vec<String>>, Error> {
+    self
+      .get_group_map_and_filter_by_group_vec(auth_token, hsm_name_vec)
+      .await
+  }
+
+  async fn post_member(
+    &self,
+    auth_token: &str,
+    group_label: &str,
+    members: &str,
+  ) -> Result<Value, Error> {
+    self
+      .add_members_to_group(auth_token, group_label, vec![members])
+      .await?;
+
+    Ok(json!([{
+      "URI": format!("/hsm/v2/groups/{}/members/{}", group_label, members)
+    }]))
+  }
+
+  async fn add_members_to_group(
+    &self,
+    _auth_token: &str,
+    group_label: &str,
+    members: Vec<&str>,
+  ) -> Result<Vec<String>, Error> {
+    self.write(|state| {
+      let mut member_vec = state
+        .get_group_members(group_label)
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "group".to_string(),
+            id: group_label.to_string(),
+          }
+        })?;
+
+      member_vec.extend(members.iter().map(|member| member.to_string()));
+
+      state.set_group_members(group_label, member_vec)?;
+
+      Ok(state.get_group_members(group_label).unwrap_or_default())
+    })
+  }
+
+  async fn delete_member_from_group(
+    &self,
+    _auth_token: &str,
+    group_label: &str,
+    xname: &str,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      let mut member_vec = state
+        .get_group_members(group_label)
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "group".to_string(),
+            id: group_label.to_string(),
+          }
+        })?;
+
+      if !member_vec.iter().any(|member| member == xname) {
+        return Err(Error::Message(format!(
+          "Node '{}' is not a member of group '{}'",
+          xname, group_label
+        )));
+      }
+
+      member_vec.retain(|member| member != xname);
+
+      state.set_group_members(group_label, member_vec)
+    })
+  }
+
+  async fn update_group_members(
+    &self,
+    _auth_token: &str,
+    group_name: &str,
+    members_to_remove: &Vec<String>,
+    members_to_add: &Vec<String>,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      let mut member_vec =
+        state.get_group_members(group_name).ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "group".to_string(),
+            id: group_name.to_string(),
+          }
+        })?;
+
+      member_vec.retain(|member| !members_to_remove.contains(member));
+      member_vec.extend(members_to_add.iter().cloned());
+
+      state.set_group_members(group_name, member_vec)
+    })
+  }
+
+  async fn migrate_group_members(
+    &self,
+    _shasta_token: &str,
+    target_hsm_group_name: &str,
+    parent_hsm_group_name: &str,
+    new_target_hsm_members: Vec<&str>,
+  ) -> Result<(Vec<String>, Vec<String>), Error> {
+    self.write(|state| {
+      let mut parent_member_vec = state
+        .get_group_members(parent_hsm_group_name)
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "group".to_string(),
+            id: parent_hsm_group_name.to_string(),
+          }
+        })?;
+
+      let mut target_member_vec = state
+        .get_group_members(target_hsm_group_name)
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "group".to_string(),
+            id: target_hsm_group_name.to_string(),
+          }
+        })?;
+
+      // Check nodes belong to parent HSM group
+      if !new_target_hsm_members
+        .iter()
+        .all(|xname| parent_member_vec.iter().any(|member| member == xname))
+      {
+        return Err(Error::Message(format!(
+          "Nodes '{}' not valid",
+          new_target_hsm_members.join(", ")
+        )));
+      }
+
+      target_member_vec.extend(
+        new_target_hsm_members.iter().map(|xname| xname.to_string()),
+      );
+      target_member_vec.sort();
+      target_member_vec.dedup();
+
+      parent_member_vec.retain(|member| !target_member_vec.contains(member));
+
+      state.set_group_members(target_hsm_group_name, target_member_vec)?;
+      state.set_group_members(parent_hsm_group_name, parent_member_vec)?;
+
+      Ok((
+        state
+          .get_group_members(target_hsm_group_name)
+          .unwrap_or_default(),
+        state
+          .get_group_members(parent_hsm_group_name)
+          .unwrap_or_default(),
+      ))
+    })
+  }
+}
+
+impl HardwareInventory for MockBackend {
+  async fn post_inventory_hardware(
+    &self,
+    _auth_token: &str,
+    hardware: HWInventoryByLocationList,
+  ) -> Result<Value, Error> {
+    let hardware_value_vec: Vec<Value> = hardware
+      .hardware
+      .unwrap_or_default()
+      .iter()
+      .map(serde_json::to_value)
+      .collect::<Result<Vec<Value>, serde_json::Error>>()?;
+
+    self.write(|state| {
+      let mut created = 0;
+
+      for hardware_value in hardware_value_vec {
+        // Only nodes are stored, other components are already part of the node inventory
+        if let (Some(xname), Some("Node")) =
+          (hardware_value["ID"].as_str(), hardware_value["Type"].as_str())
+        {
+          state
+            .hardware_inventory
+            .insert(xname.to_string(), json!({ "Nodes": [hardware_value] }));
+          created += 1;
+        }
+      }
+
+      Ok(json!({
+        "code": 0,
+        "message": format!("Created or modified {} entries", created)
+      }))
+    })
+  }
+
+  async fn get_inventory_hardware(
+    &self,
+    _auth_token: &str,
+    xname: &str,
+  ) -> Result<Value, Error> {
+    self.read(|state| {
+      state
+        .hardware_inventory
+        .get(xname)
+        .and_then(|hw_inventory| hw_inventory.pointer("/Nodes/0"))
+        .cloned()
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "hardware inventory".to_string(),
+            id: xname.to_string(),
+          }
+        })
+    })
+  }
+
+  async fn get_inventory_hardware_query(
+    &self,
+    _auth_token: &str,
+    xname: &str,
+    _type: Option<&str>,
+    _children: Option<bool>,
+    _parents: Option<bool>,
+    _partition: Option<&str>,
+    _format: Option<&str>,
+  ) -> Result<Value, Error> {
+    self.read(|state| {
+      state.hardware_inventory.get(xname).cloned().ok_or_else(|| {
+        Error::NotFound {
+          service: "HSM".to_string(),
+          resource: "hardware inventory".to_string(),
+          id: xname.to_string(),
+        }
+      })
+    })
+  }
+}
+
+impl ComponentTrait for MockBackend {
+  async fn get_all_nodes(
+    &self,
+    _auth_token: &str,
+    _nid_only: Option<&str>,
+  ) -> Result<NodeMetadataArray, Error> {
+    Ok(self.read(|state| NodeMetadataArray {
+      components: Some(
+        state
+          .components
+          .iter()
+          .filter(|component| {
+            matches_filter(Some("Node"), component.r#type.as_deref())
+          })
+          .cloned()
+          .collect(),
+      ),
+    }))
+  }
+
+  async fn get(
+    &self,
+    _auth_token: &str,
+    id: Option<&str>,
+    r#type: Option<&str>,
+    state: Option<&str>,
+    flag: Option<&str>,
+    role: Option<&str>,
+    subrole: Option<&str>,
+    enabled: Option<&str>,
+    software_status: Option<&str>,
+    subtype: Option<&str>,
+    arch: Option<&str>,
+    class: Option<&str>,
+    nid: Option<&str>,
+    _nid_start: Option<&str>,
+    _nid_end: Option<&str>,
+    _partition: Option<&str>,
+    group: Option<&str>,
+    _state_only: Option<&str>,
+    _flag_only: Option<&str>,
+    _role_only: Option<&str>,
+    _nid_only: Option<&str>,
+  ) -> Result<NodeMetadataArray, Error> {
+    Ok(self.read(|mock_state| {
+      let group_member_vec_opt = group.map(|group| {
+        mock_state.get_group_vec_members(
+          &group
+            .split(',')
+            .map(|label| label.trim().to_string())
+            .collect::<Vec<String>>(),
+        )
+      });
+
+      let component_vec = mock_state
+        .components
+        .iter()
+        .filter(|component| {
+          matches_filter(id, component.id.as_deref())
+            && matches_filter(r#type, component.r#type.as_deref())
+            && matches_filter(state, component.state.as_deref())
+            && matches_filter(flag, component.flag.as_deref())
+            && matches_filter(role, component.role.as_deref())
+            && matches_filter(subrole, component.sub_role.as_deref())
+            && matches_filter(
+              enabled,
+              component.enabled.map(|enabled| enabled.to_string()).as_deref(),
+            )
+            && matches_filter(
+              software_status,
+              component.software_status.as_deref(),
+            )
+            && matches_filter(subtype, component.subtype.as_deref())
+            && matches_filter(arch, component.arch.as_deref())
+            && matches_filter(class, component.class.as_deref())
+            && matches_filter(
+              nid,
+              component.nid.map(|nid| nid.to_string()).as_deref(),
+            )
+            && group_member_vec_opt.as_ref().is_none_or(|member_vec| {
+              component
+                .id
+                .as_ref()
+                .is_some_and(|xname| member_vec.contains(xname))
+            })
+        })
+        .cloned()
+        .collect();
+
+      NodeMetadataArray {
+        components: Some(component_vec),
+      }
+    }))
+  }
+
+  async fn get_node_metadata_available(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Component>, Error> {
+    let xname_available_vec: Vec<String> = self
+      .get_group_available(auth_token)
+      .await?
+      .iter()
+      .flat_map(|group| group.get_members())
+      .collect();
+
+    Ok(
+      self
+        .get_all_nodes(auth_token, Some("true"))
+        .await?
+        .components
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|component| {
+          component
+            .id
+            .as_ref()
+            .is_some_and(|xname| xname_available_vec.contains(xname))
+        })
+        .collect(),
+    )
+  }
+
+  async fn post_nodes(
+    &self,
+    _auth_token: &str,
+    component: ComponentArrayPostArray,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      let force = component.force.unwrap_or(false);
+
+      for component_create in component.components {
+        let position = state.components.iter().position(|component| {
+          component.id.as_ref() == Some(&component_create.id)
+        });
+
+        match position {
+          Some(_) if !force => {
+            return Err(Error::Conflict {
+              service: "HSM".to_string(),
+              resource: "component".to_string(),
+              id: component_create.id.to_string(),
+              detail: "Resource already exists".to_string(),
+            });
+          }
+          Some(position) => {
+            state.components[position] =
+              component_from_component_create(component_create);
+          }
+          None => {
+            state
+              .components
+              .push(component_from_component_create(component_create));
+          }
+        }
+      }
+
+      Ok(())
+    })
+  }
+
+  async fn delete_node(
+    &self,
+    _auth_token: &str,
+    id: &str,
+  ) -> Result<Value, Error> {
+    self.write(|state| {
+      let component_count = state.components.len();
+
+      state
+        .components
+        .retain(|component| component.id.as_deref() != Some(id));
+
+      if state.components.len() == component_count {
+        return Err(Error::NotFound {
+          service: "HSM".to_string(),
+          resource: "component".to_string(),
+          id: id.to_string(),
+        });
+      }
+
+      state.hardware_inventory.remove(id);
+      state.power_state.remove(id);
+
+      Ok(json!({ "code": 0, "message": "deleted 1 entry" }))
+    })
+  }
+}
+
+impl PCSTrait for MockBackend {
+  async fn power_on_sync(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+  ) -> Result<Value, Error> {
+    Ok(self.write(|state| create_power_transition(state, "on", nodes)))
+  }
+
+  async fn power_off_sync(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<Value, Error> {
+    let operation = if force { "force-off" } else { "soft-off" };
+
+    Ok(self.write(|state| create_power_transition(state, operation, nodes)))
+  }
+
+  async fn power_reset_sync(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<Value, Error> {
+    let operation = if force {
+      "hard-restart"
+    } else {
+      "soft-restart"
+    };
+
+    Ok(self.write(|state| create_power_transition(state, operation, nodes)))
+  }
+
+  async fn power_status(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+    power_status_filter: Option<&str>,
+    _management_state_filter: Option<&str>,
+  ) -> Result<PowerStatusAll, Error> {
+    let timestamp = now();
+
+    Ok(self.read(|state| {
+      let status = nodes
+        .iter()
+        .filter_map(|xname| {
+          let power_state =
+            state.power_state.get(xname).map(String::as_str);
+
+          if !matches_filter(power_status_filter, power_state) {
+            return None;
+          }
+
+          let (power_state, supported_power_transitions) = match power_state
+          {
+            Some("on") => (
+              PowerState::On,
+              vec![
+                Operation::SoftOff,
+                Operation::ForceOff,
+                Operation::SoftRestart,
+                Operation::HardRestart,
+              ],
+            ),
+            Some("off") => (PowerState::Off, vec![Operation::On]),
+            _ => (PowerState::Undefined, Vec::new()),
+          };
+
+          Some(PowerStatus {
+            xname: xname.clone(),
+            power_state: Some(power_state),
+            management_state: Some(ManagementState::Available),
+            error: None,
+            supported_power_transitions,
+            last_updated: timestamp.clone(),
+          })
+        })
+        .collect();
+
+      PowerStatusAll { status }
+    }))
+  }
+  // NOTE: mock transitions complete immediately
+  async fn power_on(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+  ) -> Result<String, Error> {
+    let transition =
+      self.write(|state| create_power_transition(state, "on", nodes));
+
+    Ok(
+      transition["transitionID"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string(),
+    )
+  }
+
+  async fn power_off(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    let operation = if force { "force-off" } else { "soft-off" };
+
+    let transition =
+      self.write(|state| create_power_transition(state, operation, nodes));
+
+    Ok(
+      transition["transitionID"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string(),
+    )
+  }
+
+  async fn power_reset(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    let operation = if force {
+      "hard-restart"
+    } else {
+      "soft-restart"
+    };
+
+    let transition =
+      self.write(|state| create_power_transition(state, operation, nodes));
+
+    Ok(
+      transition["transitionID"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string(),
+    )
+  }
+
+  async fn get_power_transition(
+    &self,
+    _auth_token: &str,
+    transition_id: &str,
+  ) -> Result<Value, Error> {
+    self.read(|state| {
+      state
+        .power_transitions
+        .iter()
+        .find(|transition| transition["transitionID"] == transition_id)
+        .cloned()
+        .ok_or_else(|| Error::NotFound {
+          service: "PCS".to_string(),
+          resource: "transition".to_string(),
+          id: transition_id.to_string(),
+        })
+    })
+  }
+
+  async fn get_power_transitions(
+    &self,
+    _auth_token: &str,
+  ) -> Result<Vec<Value>, Error> {
+    Ok(self.read(|state| state.power_transitions.clone()))
+  }
+}
+
+impl BootParametersTrait for MockBackend {
+  async fn get_all_bootparameters(
+    &self,
+    _auth_token: &str,
+  ) -> Result<Vec<BootParameters>, Error> {
+    Ok(self.read(|state| state.boot_parameters.clone()))
+  }
+
+  async fn get_bootparameters(
+    &self,
+    _auth_token: &str,
+    nodes: &[String],
+  ) -> Result<Vec<BootParameters>, Error> {
+    Ok(self.read(|state| {
+      state
+        .boot_parameters
+        .iter()
+        .filter(|boot_parameters| {
+          boot_parameters.hosts.iter().any(|host| nodes.contains(host))
+        })
+        .cloned()
+        .collect()
+    }))
+  }
+
+  async fn add_bootparameters(
+    &self,
+    _auth_token: &str,
+    boot_parameters: &BootParameters,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      if let Some(host) = boot_parameters.hosts.iter().find(|host| {
+        state
+          .boot_parameters
+          .iter()
+          .any(|existing| existing.hosts.contains(host))
+      }) {
+        return Err(Error::Conflict {
+          service: "BSS".to_string(),
+          resource: "boot parameters".to_string(),
+          id: host.to_string(),
+          detail: "Resource already exists".to_string(),
+        });
+      }
+
+      state.boot_parameters.push(boot_parameters.clone());
+
+      Ok(())
+    })
+  }
+
+  async fn update_bootparameters(
+    &self,
+    _auth_token: &str,
+    boot_parameters: &BootParameters,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      // Hosts in the new boot parameters are removed from any other boot parameters
+      for existing in state.boot_parameters.iter_mut() {
+        existing
+          .hosts
+          .retain(|host| !boot_parameters.hosts.contains(host));
+      }
+
+      state
+        .boot_parameters
+        .retain(|existing| !existing.hosts.is_empty());
+
+      state.boot_parameters.push(boot_parameters.clone());
+
+      Ok(())
+    })
+  }
+
+  async fn delete_bootparameters(
+    &self,
+    _auth_token: &str,
+    boot_parameters: &BootParameters,
+  ) -> Result<String, Error> {
+    self.write(|state| {
+      for existing in state.boot_parameters.iter_mut() {
+        existing
+          .hosts
+          .retain(|host| !boot_parameters.hosts.contains(host));
+      }
+
+      state
+        .boot_parameters
+        .retain(|existing| !existing.hosts.is_empty());
+
+      Ok(format!(
+        "Boot parameters for hosts '{}' deleted",
+        boot_parameters.hosts.join(", ")
+      ))
+    })
+  }
+}
+
+impl RedfishEndpointTrait for MockBackend {
+  async fn get_all_redfish_endpoints(
+    &self,
+    _auth_token: &str,
+  ) -> Result<RedfishEndpointArray, Error> {
+    Ok(self.read(|state| RedfishEndpointArray {
+      redfish_endpoints: Some(state.redfish_endpoints.clone()),
+    }))
+  }
+
+  async fn get_redfish_endpoints(
+    &self,
+    _auth_token: &str,
+    id: Option<&str>,
+    fqdn: Option<&str>,
+    r#type: Option<&str>,
+    uuid: Option<&str>,
+    macaddr: Option<&str>,
+    ip_address: Option<&str>,
+    _last_status: Option<&str>,
+  ) -> Result<RedfishEndpointArray, Error> {
+    Ok(self.read(|state| RedfishEndpointArray {
+      redfish_endpoints: Some(
+        state
+          .redfish_endpoints
+          .iter()
+          .filter(|redfish_endpoint| {
+            matches_filter(id, Some(&redfish_endpoint.id))
+              && matches_filter(fqdn, redfish_endpoint.fqdn.as_deref())
+              && matches_filter(r#type, redfish_endpoint.r#type.as_deref())
+              && matches_filter(uuid, redfish_endpoint.uuid.as_deref())
+              && matches_filter(
+                macaddr,
+                redfish_endpoint.mac_addr.as_deref(),
+              )
+              && matches_filter(
+                ip_address,
+                redfish_endpoint.ip_address.as_deref(),
+              )
+          })
+          .cloned()
+          .collect(),
+      ),
+    }))
+  }
+
+  async fn add_redfish_endpoint(
+    &self,
+    _auth_token: &str,
+    redfish_endpoint: &RedfishEndpointArray,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      for new_redfish_endpoint in
+        redfish_endpoint.redfish_endpoints.iter().flatten()
+      {
+        if state
+          .redfish_endpoints
+          .iter()
+          .any(|existing| existing.id == new_redfish_endpoint.id)
+        {
+          return Err(Error::Conflict {
+            service: "HSM".to_string(),
+            resource: "redfish endpoint".to_string(),
+            id: new_redfish_endpoint.id.to_string(),
+            detail: "Resource already exists".to_string(),
+          });
+        }
+
+        state.redfish_endpoints.push(new_redfish_endpoint.clone());
+      }
+
+      Ok(())
+    })
+  }
+
+  async fn update_redfish_endpoint(
+    &self,
+    _auth_token: &str,
+    redfish_endpoint: &RedfishEndpoint,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      let existing = state
+        .redfish_endpoints
+        .iter_mut()
+        .find(|existing| existing.id == redfish_endpoint.id)
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "HSM".to_string(),
+            resource: "redfish endpoint".to_string(),
+            id: redfish_endpoint.id.to_string(),
+          }
+        })?;
+
+      *existing = redfish_endpoint.clone();
+
+      Ok(())
+    })
+  }
+
+  async fn delete_redfish_endpoint(
+    &self,
+    _auth_token: &str,
+    id: &str,
+  ) -> Result<Value, Error> {
+    self.write(|state| {
+      let redfish_endpoint_count = state.redfish_endpoints.len();
+
+      state
+        .redfish_endpoints
+        .retain(|redfish_endpoint| redfish_endpoint.id != id);
+
+      if state.redfish_endpoints.len() == redfish_endpoint_count {
+        return Err(Error::NotFound {
+          service: "HSM".to_string(),
+          resource: "redfish endpoint".to_string(),
+          id: id.to_string(),
+        });
+      }
+
+      Ok(json!({ "code": 0, "message": "deleted 1 entry" }))
+    })
+  }
+}
+
+impl CfsTrait for MockBackend {
+  type T = Pin<Box<dyn AsyncBufRead + Send>>;
+
+  async fn get_cfs_health(&self) -> Result<(), Error> {
+    Ok(())
+  }
+
+  async fn post_session(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    session: &CfsSessionPostRequest,
+  ) -> Result<CfsSessionGetResponse, Error> {
+    self.write(|state| create_session(state, session))
+  }
+
+  async fn get_sessions(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    session_name_opt: Option<&String>,
+    limit_opt: Option<u8>,
+    _after_id_opt: Option<String>,
+    _min_age_opt: Option<String>,
+    _max_age_opt: Option<String>,
+    status_opt: Option<String>,
+    name_contains_opt: Option<String>,
+    is_succeded_opt: Option<bool>,
+    _tags_opt: Option<String>,
+  ) -> Result<Vec<CfsSessionGetResponse>, Error> {
+    let mut cfs_session_vec: Vec<CfsSessionGetResponse> =
+      self.read(|state| {
+        state
+          .cfs_sessions
+          .iter()
+          .filter(|cfs_session| {
+            let session_status = cfs_session
+              .status
+              .as_ref()
+              .and_then(|status| status.session.as_ref());
+
+            session_name_opt
+              .is_none_or(|name| cfs_session.name.as_ref() == Some(name))
+              && name_contains_opt.as_ref().is_none_or(|name_contains| {
+                cfs_session
+                  .name
+                  .as_ref()
+                  .is_some_and(|name| name.contains(name_contains))
+              })
+              && matches_filter(
+                status_opt.as_deref(),
+                session_status.and_then(|session| session.status.as_deref()),
+              )
+              && is_succeded_opt.is_none_or(|is_succeded| {
+                session_status
+                  .and_then(|session| session.succeeded.as_deref())
+                  .is_some_and(|succeeded| {
+                    succeeded == is_succeded.to_string()
+                  })
+              })
+          })
+          .cloned()
+          .collect()
+      });
+
+    cfs_session_vec.sort_by_key(|cfs_session| cfs_session.get_start_time());
+
+    if let Some(limit) = limit_opt {
+      cfs_session_vec = cfs_session_vec
+        .split_off(cfs_session_vec.len().saturating_sub(limit as usize));
+    }
+
+    Ok(cfs_session_vec)
+  }
+
+  async fn get_sessions_by_xname(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    xname_vec: &[&str],
+    limit_opt: Option<u8>,
+    after_id_opt: Option<String>,
+    min_age_opt: Option<String>,
+    max_age_opt: Option<String>,
+    status_opt: Option<String>,
+    name_contains_opt: Option<String>,
+    is_succeded_opt: Option<bool>,
+    tags_opt: Option<String>,
+  ) -> Result<Vec<CfsSessionGetResponse>, Error> {
+    let mut cfs_session_vec = self
+      .get_sessions(
+        shasta_token,
+        shasta_base_url,
+        shasta_root_cert,
+        None,
+        None,
+        after_id_opt,
+        min_age_opt,
+        max_age_opt,
+        status_opt,
+        name_contains_opt,
+        is_succeded_opt,
+        tags_opt,
+      )
+      .await?;
+
+    self.read(|state| {
+      cfs_session_vec.retain(|cfs_session| {
+        get_session_xname_vec(state, cfs_session)
+          .iter()
+          .any(|xname| xname_vec.contains(&xname.as_str()))
+      })
+    });
+
+    if let Some(limit) = limit_opt {
+      cfs_session_vec = cfs_session_vec
+        .split_off(cfs_session_vec.len().saturating_sub(limit as usize));
+    }
+
+    Ok(cfs_session_vec)
+  }
+
+  async fn get_and_filter_sessions(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    hsm_group_name_vec_opt: Option<Vec<String>>,
+    xname_vec_opt: Option<Vec<&str>>,
+    _min_age_opt: Option<&String>,
+    _max_age_opt: Option<&String>,
+    status_opt: Option<&String>,
+    cfs_session_name_opt: Option<&String>,
+    limit_number_opt: Option<&u8>,
+    is_succeded_opt: Option<bool>,
+  ) -> Result<Vec<CfsSessionGetResponse>, Error> {
+    let mut cfs_session_vec = self
+      .get_sessions(
+        shasta_token,
+        shasta_base_url,
+        shasta_root_cert,
+        cfs_session_name_opt,
+        None,
+        None,
+        None,
+        None,
+        status_opt.cloned(),
+        None,
+        is_succeded_opt,
+        None,
+      )
+      .await?;
+
+    self.read(|state| {
+      if let Some(hsm_group_name_vec) =
+        hsm_group_name_vec_opt.filter(|group_vec| !group_vec.is_empty())
+      {
+        let hsm_member_vec = state.get_group_vec_members(&hsm_group_name_vec);
+
+        cfs_session_vec.retain(|cfs_session| {
+          cfs_session.get_target_hsm().is_some_and(|target_group_vec| {
+            target_group_vec
+              .iter()
+              .any(|group| hsm_group_name_vec.contains(group))
+          }) || cfs_session
+            .get_target_xname()
+            .is_some_and(|xname_vec| {
+              xname_vec.iter().any(|xname| hsm_member_vec.contains(xname))
+            })
+        });
+      }
+
+      if let Some(xname_vec) = xname_vec_opt {
+        cfs_session_vec.retain(|cfs_session| {
+          get_session_xname_vec(state, cfs_session)
+            .iter()
+            .any(|xname| xname_vec.contains(&xname.as_str()))
+        });
+      }
+    });
+
+    if let Some(limit_number) = limit_number_opt {
+      cfs_session_vec = cfs_session_vec.split_off(
+        cfs_session_vec
+          .len()
+          .saturating_sub(*limit_number as usize),
+      );
+    }
+
+    if cfs_session_vec.is_empty() {
+      return Err(Error::Message("No CFS session found".to_string()));
+    }
+
+    Ok(cfs_session_vec)
+  }
+
+  async fn get_configuration(
+    &self,
+    _auth_token: &str,
+    _base_url: &str,
+    _root_cert: &[u8],
+    configuration_name_opt: Option<&String>,
+  ) -> Result<Vec<CfsConfigurationResponse>, Error> {
+    Ok(self.read(|state| {
+      state
+        .cfs_configurations
+        .iter()
+        .filter(|configuration| {
+          configuration_name_opt.is_none_or(|name| &configuration.name == name)
+        })
+        .cloned()
+        .collect()
+    }))
+  }
+
+  async fn get_and_filter_configuration(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    configuration_name: Option<&str>,
+    configuration_name_pattern: Option<&str>,
+    hsm_group_name_vec: &[String],
+    limit_number_opt: Option<&u8>,
+  ) -> Result<Vec<CfsConfigurationResponse>, Error> {
+    let pattern_regex_opt =
+      configuration_name_pattern.map(glob_to_regex).transpose()?;
+
+    let mut configuration_vec: Vec<CfsConfigurationResponse> =
+      self.read(|state| {
+        // Configurations used by CFS sessions or BOS sessiontemplates targeting the groups
+        let configuration_in_use_vec: Vec<String> = state
+          .cfs_sessions
+          .iter()
+          .filter(|cfs_session| {
+            cfs_session.get_target_hsm().is_some_and(|target_group_vec| {
+              target_group_vec
+                .iter()
+                .any(|group| hsm_group_name_vec.contains(group))
+            })
+          })
+          .filter_map(|cfs_session| cfs_session.get_configuration_name())
+          .chain(
+            state
+              .bos_sessiontemplates
+              .iter()
+              .filter(|template| {
+                template.boot_sets.is_some()
+                  && template
+                    .get_target_hsm()
+                    .iter()
+                    .any(|group| hsm_group_name_vec.contains(group))
+              })
+              .filter_map(|template| template.get_confguration()),
+          )
+          .collect();
+
+        state
+          .cfs_configurations
+          .iter()
+          .filter(|configuration| {
+            configuration_name.is_none_or(|name| configuration.name == name)
+              && pattern_regex_opt
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&configuration.name))
+              && (hsm_group_name_vec.is_empty()
+                || configuration_in_use_vec.contains(&configuration.name)
+                || hsm_group_name_vec
+                  .iter()
+                  .any(|group| configuration.name.contains(group.as_str())))
+          })
+          .cloned()
+          .collect()
+      });
+
+    configuration_vec.sort_by(|a, b| a.last_updated.cmp(&b.last_updated));
+
+    if let Some(limit_number) = limit_number_opt {
+      configuration_vec = configuration_vec.split_off(
+        configuration_vec
+          .len()
+          .saturating_sub(*limit_number as usize),
+      );
+    }
+
+    Ok(configuration_vec)
+  }
+
+  async fn get_configuration_layer_details(
+    &self,
+    _shasta_root_cert: &[u8],
+    _gitea_base_url: &str,
+    _gitea_token: &str,
+    layer: Layer,
+    _site_name: &str,
+  ) -> Result<LayerDetails, Error> {
+    let repo_name = layer
+      .clone_url
+      .trim_end_matches(".git")
+      .rsplit('/')
+      .next()
+      .unwrap_or_default()
+      .to_string();
+
+    Ok(LayerDetails::new(
+      &layer.name,
+      &repo_name,
+      layer.commit.as_deref().unwrap_or_default(),
+      "mock",
+      "",
+      layer.branch.as_deref().unwrap_or_default(),
+      "",
+      &layer.playbook,
+    ))
+  }
+
+  async fn create_configuration_from_repos(
+    &self,
+    _gitea_token: &str,
+    gitea_base_url: &str,
+    _shasta_root_cert: &[u8],
+    repo_name_vec: Vec<String>,
+    local_git_commit_vec: Vec<String>,
+    playbook_file_name_opt: Option<&String>,
+  ) -> Result<CfsConfigurationRequest, Error> {
+    let mut configuration = CfsConfigurationRequest::new();
+
+    for (repo_name, commit) in repo_name_vec.iter().zip(local_git_commit_vec) {
+      configuration.add_layer(RequestLayer::new(
+        Some(repo_name.clone()),
+        Some(format!("{}/cray/{}.git", gitea_base_url, repo_name)),
+        None,
+        playbook_file_name_opt
+          .cloned()
+          .unwrap_or_else(|| "site.yml".to_string()),
+        Some(commit),
+        None,
+        None,
+      ));
+    }
+
+    Ok(configuration)
+  }
+
+  async fn put_configuration(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    configuration: &CfsConfigurationRequest,
+    configuration_name: &str,
+  ) -> Result<CfsConfigurationResponse, Error> {
+    self.write(|state| {
+      if state
+        .cfs_configurations
+        .iter()
+        .any(|existing| existing.name == configuration_name)
+      {
+        return Err(Error::Conflict {
+          service: "CFS".to_string(),
+          resource: "configuration".to_string(),
+          id: configuration_name.to_string(),
+          detail: "Resource already exists".to_string(),
+        });
+      }
+
+      let configuration = CfsConfigurationResponse {
+        name: configuration_name.to_string(),
+        last_updated: now(),
+        layers: configuration
+          .layers
+          .clone()
+          .unwrap_or_default()
+          .into_iter()
+          .map(layer_from_request_layer)
+          .collect(),
+        additional_inventory: configuration.additional_inventory.clone().map(
+          |additional_inventory| AdditionalInventory {
+            name: additional_inventory.name.unwrap_or_default(),
+            clone_url: additional_inventory.clone_url,
+            commit: additional_inventory.commit,
+            branch: additional_inventory.branch,
+          },
+        ),
+      };
+
+      state.cfs_configurations.push(configuration.clone());
+
+      Ok(configuration)
+    })
+  }
+
+  async fn update_runtime_configuration(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    xnames: Vec<String>,
+    desired_configuration: &str,
+    enabled: bool,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      for xname in xnames {
+        let cfs_component = match state
+          .cfs_components
+          .iter_mut()
+          .find(|cfs_component| cfs_component.id.as_ref() == Some(&xname))
+        {
+          Some(cfs_component) => cfs_component,
+          None => {
+            state.cfs_components.push(CfsComponent {
+              id: Some(xname),
+              state: Some(Vec::new()),
+              desired_config: None,
+              error_count: Some(0),
+              retry_policy: None,
+              enabled: None,
+              configuration_status: None,
+              tags: None,
+              logs: None,
+            });
+
+            state.cfs_components.last_mut().unwrap()
+          }
+        };
+
+        cfs_component.desired_config = Some(desired_configuration.to_string());
+        cfs_component.enabled = Some(enabled);
+        cfs_component.error_count = Some(0);
+        cfs_component.configuration_status = Some("configured".to_string());
+      }
+
+      Ok(())
+    })
+  }
+
+  async fn get_derivatives(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    configuration_name: &str,
+  ) -> Result<
+    (
+      Option<Vec<CfsSessionGetResponse>>,
+      Option<Vec<BosSessionTemplate>>,
+      Option<Vec<Image>>,
+    ),
+    Error,
+  > {
+    Ok(self.read(|state| {
+      let cfs_session_vec: Vec<CfsSessionGetResponse> = state
+        .cfs_sessions
+        .iter()
+        .filter(|cfs_session| {
+          cfs_session.get_configuration_name().as_deref()
+            == Some(configuration_name)
+        })
+        .cloned()
+        .collect();
+
+      let bos_sessiontemplate_vec: Vec<BosSessionTemplate> = state
+        .bos_sessiontemplates
+        .iter()
+        .filter(|template| {
+          template.get_confguration().as_deref() == Some(configuration_name)
+        })
+        .cloned()
+        .collect();
+
+      let image_id_vec: Vec<String> = cfs_session_vec
+        .iter()
+        .flat_map(|cfs_session| cfs_session.get_result_id_vec())
+        .collect();
+
+      let image_vec: Vec<Image> = state
+        .images
+        .iter()
+        .filter(|image| {
+          image
+            .id
+            .as_ref()
+            .is_some_and(|id| image_id_vec.contains(id))
+        })
+        .cloned()
+        .collect();
+
+      (
+        Some(cfs_session_vec),
+        Some(bos_sessiontemplate_vec),
+        Some(image_vec),
+      )
+    }))
+  }
+
+  async fn get_cfs_components(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    configuration_name: Option<&str>,
+    components_ids: Option<&str>,
+    status: Option<&str>,
+  ) -> Result<Vec<CfsComponent>, Error> {
+    Ok(self.read(|state| {
+      state
+        .cfs_components
+        .iter()
+        .filter(|cfs_component| {
+          configuration_name.is_none_or(|configuration_name| {
+            cfs_component.desired_config.as_deref() == Some(configuration_name)
+          }) && matches_filter(components_ids, cfs_component.id.as_deref())
+            && matches_filter(
+              status,
+              cfs_component.configuration_status.as_deref(),
+            )
+        })
+        .cloned()
+        .collect()
+    }))
+  }
+}
+
+impl SatTrait for MockBackend {
+  async fn apply_sat_file(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    _vault_base_url: &str,
+    _vault_secret_path: &str,
+    _k8s_api_url: &str,
+    _shasta_k8s_secrets: serde_json::Value,
+    sat_template_file_yaml: serde_yaml::Value,
+    _hsm_group_available_vec: &Vec<String>,
+    _ansible_verbosity_opt: Option<u8>,
+    _ansible_passthrough_opt: Option<&String>,
+    _gitea_base_url: &str,
+    _gitea_token: &str,
+    do_not_reboot: bool,
+    _watch_logs: bool,
+    _debug_on_failure: bool,
+    dry_run: bool,
+  ) -> Result<(), Error> {
+    let sat_file: Value = serde_json::to_value(&sat_template_file_yaml)?;
+
+    let empty_vec = Vec::new();
+
+    // CONFIGURATIONS
+    for configuration_value in
+      sat_file["configurations"].as_array().unwrap_or(&empty_vec)
+    {
+      let configuration_name =
+        configuration_value["name"].as_str().ok_or_else(|| {
+          Error::Message(
+            "SAT file configuration 'name' missing".to_string(),
+          )
+        })?;
+
+      let mut configuration = CfsConfigurationRequest::new();
+
+      for layer_value in
+        configuration_value["layers"].as_array().unwrap_or(&empty_vec)
+      {
+        configuration.add_layer(RequestLayer::new(
+          layer_value["name"].as_str().map(str::to_string),
+          layer_value
+            .pointer("/git/url")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+          None,
+          layer_value["playbook"]
+            .as_str()
+            .unwrap_or("site.yml")
+            .to_string(),
+          layer_value
+            .pointer("/git/commit")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+          layer_value
+            .pointer("/git/branch")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+          None,
+        ));
+      }
+
+      if dry_run {
+        log::info!("Dry run - CFS configuration '{}'", configuration_name);
+        continue;
+      }
+
+      // Replace existing CFS configuration
+      self.write(|state| {
+        state
+          .cfs_configurations
+          .retain(|existing| existing.name != configuration_name)
+      });
+
+      self
+        .put_configuration(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          &configuration,
+          configuration_name,
+        )
+        .await?;
+    }
+
+    // IMAGES
+    // Maps SAT image 'ref_name' to image name
+    let mut image_ref_map: HashMap<String, String> = HashMap::new();
+
+    for image_value in sat_file["images"].as_array().unwrap_or(&empty_vec) {
+      let image_name = image_value["name"].as_str().ok_or_else(|| {
+        Error::Message("SAT file image 'name' missing".to_string())
+      })?;
+
+      if let Some(ref_name) = image_value["ref_name"].as_str() {
+        image_ref_map.insert(ref_name.to_string(), image_name.to_string());
+      }
+
+      if dry_run {
+        log::info!("Dry run - IMS image '{}'", image_name);
+        continue;
+      }
+
+      let base_image_id = image_value
+        .pointer("/base/ims/id")
+        .or_else(|| image_value.pointer("/ims/id"))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+      match image_value["configuration"].as_str() {
+        Some(configuration_name) => {
+          // Image needs to be configured, simulate the CFS session building the image
+          let group_name_vec: Vec<String> = image_value
+            ["configuration_group_names"]
+            .as_array()
+            .unwrap_or(&empty_vec)
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+
+          let session = CfsSessionPostRequest::new(
+            format!("{}-{}", image_name, &new_id()[..8]),
+            configuration_name.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            true,
+            Some(group_name_vec),
+            Some(base_image_id),
+            None,
+            false,
+            Some(image_name.to_string()),
+          );
+
+          self
+            .post_session(
+              shasta_token,
+              shasta_base_url,
+              shasta_root_cert,
+              &session,
+            )
+            .await?;
+        }
+        None => self.write(|state| {
+          state.images.push(Image {
+            id: Some(new_id()),
+            created: Some(now()),
+            name: image_name.to_string(),
+            link: None,
+            arch: None,
+          })
+        }),
+      }
+    }
+
+    // SESSION TEMPLATES
+    for template_value in
+      sat_file["session_templates"].as_array().unwrap_or(&empty_vec)
+    {
+      let template_name = template_value["name"].as_str().ok_or_else(|| {
+        Error::Message("SAT file session template 'name' missing".to_string())
+      })?;
+
+      let image_name_opt = template_value
+        .pointer("/image/ims/name")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| {
+          template_value
+            .pointer("/image/image_ref")
+            .and_then(Value::as_str)
+            .and_then(|image_ref| image_ref_map.get(image_ref).cloned())
+        });
+
+      // Most recent image with the name requested
+      let image_id_opt = self.read(|state| {
+        state
+          .images
+          .iter()
+          .filter(|image| Some(&image.name) == image_name_opt.as_ref())
+          .max_by(|a, b| a.created.cmp(&b.created))
+          .and_then(|image| image.id.clone())
+      });
+
+      let cfs = Cfs {
+        configuration: template_value["configuration"]
+          .as_str()
+          .map(str::to_string),
+      };
+
+      let mut boot_set_map: HashMap<String, BootSet> = serde_json::from_value(
+        template_value
+          .pointer("/bos_parameters/boot_sets")
+          .cloned()
+          .unwrap_or(json!({})),
+      )?;
+
+      for boot_set in boot_set_map.values_mut() {
+        boot_set.path = image_id_opt
+          .as_ref()
+          .map(|image_id| format!("s3://boot-images/{}/manifest.json", image_id));
+        boot_set.r#type = Some("s3".to_string());
+        boot_set.cfs = Some(cfs.clone());
+      }
+
+      let template = BosSessionTemplate {
+        name: Some(template_name.to_string()),
+        tenant: None,
+        description: None,
+        enable_cfs: Some(true),
+        cfs: Some(cfs),
+        boot_sets: Some(boot_set_map),
+        links: None,
+      };
+
+      if dry_run {
+        log::info!("Dry run - BOS sessiontemplate '{}'", template_name);
+        continue;
+      }
+
+      self
+        .put_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          &template,
+          template_name,
+        )
+        .await?;
+
+      if !do_not_reboot {
+        let xname_vec =
+          self.read(|state| get_template_xname_vec(state, &template));
+
+        self.write(|state| create_power_transition(state, "on", &xname_vec));
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl ApplyHwClusterPin for MockBackend {}
+
+impl ImsTrait for MockBackend {
+  async fn get_images(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    image_id_opt: Option<&str>,
+  ) -> Result<Vec<Image>, Error> {
+    Ok(self.read(|state| {
+      state
+        .images
+        .iter()
+        .filter(|image| {
+          image_id_opt.is_none_or(|id| image.id.as_deref() == Some(id))
+        })
+        .cloned()
+        .collect()
+    }))
+  }
+
+  async fn get_all_images(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+  ) -> Result<Vec<Image>, Error> {
+    Ok(self.read(|state| state.images.clone()))
+  }
+
+  fn filter_images(&self, image_vec: &mut Vec<Image>) -> Result<(), Error> {
+    // Sort images by creation time order ASC
+    image_vec.sort_by(|a, b| a.created.cmp(&b.created));
+
+    Ok(())
+  }
+
+  async fn delete_image(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    image_id: &str,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      let image_count = state.images.len();
+
+      state
+        .images
+        .retain(|image| image.id.as_deref() != Some(image_id));
+
+      if state.images.len() == image_count {
+        return Err(Error::NotFound {
+          service: "IMS".to_string(),
+          resource: "image".to_string(),
+          id: image_id.to_string(),
+        });
+      }
+
+      Ok(())
+    })
+  }
+}
+
+impl ApplySessionTrait for MockBackend {
+  async fn i_apply_session(
+    &self,
+    gitea_token: &str,
+    gitea_base_url: &str,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    cfs_conf_sess_name: Option<&String>,
+    playbook_yaml_file_name_opt: Option<&String>,
+    hsm_group: Option<&String>,
+    repos_paths: Vec<PathBuf>,
+    ansible_limit: Option<String>,
+    ansible_verbosity: Option<String>,
+    ansible_passthrough: Option<String>,
+  ) -> Result<(String, String), Error> {
+    let name = cfs_conf_sess_name
+      .cloned()
+      .unwrap_or_else(|| format!("mock-{}", &new_id()[..8]));
+
+    let repo_name_vec: Vec<String> = repos_paths
+      .iter()
+      .filter_map(|repo_path| repo_path.file_name())
+      .map(|repo_name| repo_name.to_string_lossy().to_string())
+      .collect();
+
+    let commit_vec = vec!["mock".to_string(); repo_name_vec.len()];
+
+    let configuration = self
+      .create_configuration_from_repos(
+        gitea_token,
+        gitea_base_url,
+        shasta_root_cert,
+        repo_name_vec,
+        commit_vec,
+        playbook_yaml_file_name_opt,
+      )
+      .await?;
+
+    // Replace existing CFS configuration
+    self.write(|state| {
+      state
+        .cfs_configurations
+        .retain(|existing| existing.name != name)
+    });
+
+    self
+      .put_configuration(
+        shasta_token,
+        shasta_base_url,
+        shasta_root_cert,
+        &configuration,
+        &name,
+      )
+      .await?;
+
+    let mut session = CfsSessionPostRequest::new(
+      name.clone(),
+      name.clone(),
+      None,
+      ansible_limit,
+      None,
+      ansible_verbosity.and_then(|verbosity| verbosity.parse().ok()),
+      ansible_passthrough,
+      false,
+      None,
+      None,
+      None,
+      false,
+      None,
+    );
+
+    if let Some(hsm_group) = hsm_group {
+      session.target.groups = Some(vec![CfsSessionGroup {
+        name: hsm_group.clone(),
+        members: Vec::new(),
+      }]);
+    }
+
+    self
+      .post_session(shasta_token, shasta_base_url, shasta_root_cert, &session)
+      .await?;
+
+    Ok((name.clone(), name))
+  }
+}
+
+impl MigrateRestoreTrait for MockBackend {}
+
+impl MigrateBackupTrait for MockBackend {}
+
+impl GetImagesAndDetailsTrait for MockBackend {
+  async fn get_images_and_details(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    hsm_group_name_vec: &[String],
+    id_opt: Option<&String>,
+    limit_number: Option<&u8>,
+  ) -> Result<Vec<(Image, String, String, bool)>, Error> {
+    let mut image_detail_vec = self.read(|state| {
+      let hsm_member_vec = state.get_group_vec_members(hsm_group_name_vec);
+
+      let mut image_vec: Vec<Image> = state
+        .images
+        .iter()
+        .filter(|image| id_opt.is_none_or(|id| image.id.as_ref() == Some(id)))
+        .cloned()
+        .collect();
+
+      image_vec.sort_by(|a, b| a.created.cmp(&b.created));
+
+      image_vec
+        .into_iter()
+        .filter_map(|image| {
+          let image_id = image.id.clone().unwrap_or_default();
+
+          // CFS session which built the image
+          let cfs_session_opt = state.cfs_sessions.iter().find(|cfs_session| {
+            cfs_session.get_result_id_vec().contains(&image_id)
+          });
+
+          let configuration_name = cfs_session_opt
+            .and_then(|cfs_session| cfs_session.get_configuration_name())
+            .unwrap_or_default();
+
+          let target_group_vec = cfs_session_opt
+            .and_then(|cfs_session| cfs_session.get_target_hsm())
+            .unwrap_or_default();
+
+          // Nodes booting the image
+          let boot_xname_vec: Vec<&String> = state
+            .boot_parameters
+            .iter()
+            .filter(|boot_parameters| {
+              boot_parameters.get_boot_image() == image_id
+            })
+            .flat_map(|boot_parameters| boot_parameters.hosts.iter())
+            .collect();
+
+          let is_in_group = hsm_group_name_vec.is_empty()
+            || target_group_vec
+              .iter()
+              .any(|group| hsm_group_name_vec.contains(group))
+            || hsm_group_name_vec
+              .iter()
+              .any(|group| image.name.contains(group.as_str()))
+            || boot_xname_vec
+              .iter()
+              .any(|xname| hsm_member_vec.contains(xname));
+
+          is_in_group.then(|| {
+            (
+              image,
+              configuration_name,
+              target_group_vec.join(", "),
+              !boot_xname_vec.is_empty(),
+            )
+          })
+        })
+        .collect::<Vec<(Image, String, String, bool)>>()
+    });
+
+    if let Some(limit_number) = limit_number {
+      image_detail_vec = image_detail_vec.split_off(
+        image_detail_vec
+          .len()
+          .saturating_sub(*limit_number as usize),
+      );
+    }
+
+    Ok(image_detail_vec)
+  }
+}
+
+impl ClusterSessionTrait for MockBackend {
+  async fn post_template_session(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    bos_session: BosSession,
+  ) -> Result<Value, Error> {
+    self.write(|state| {
+      let template = state
+        .bos_sessiontemplates
+        .iter()
+        .find(|template| {
+          template.name.as_ref() == Some(&bos_session.template_name)
+        })
+        .cloned()
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "BOS".to_string(),
+            resource: "session template".to_string(),
+            id: bos_session.template_name.to_string(),
+          }
+        })?;
+
+      let mut xname_vec = get_template_xname_vec(state, &template);
+
+      // BOS session 'limit' restricts the nodes affected by the session
+      if let Some(limit) = &bos_session.limit {
+        let limit_vec: Vec<&str> = limit.split(',').map(str::trim).collect();
+        xname_vec.retain(|xname| limit_vec.contains(&xname.as_str()));
+      }
+
+      let operation = match bos_session.operation.as_ref() {
+        Some(operation) if operation.to_string() == "shutdown" => "off",
+        _ => "on",
+      };
+
+      create_power_transition(state, operation, &xname_vec);
+
+      let mut bos_session_value = serde_json::to_value(&bos_session)?;
+      bos_session_value["name"] =
+        json!(bos_session.name.clone().unwrap_or_else(new_id));
+      bos_session_value["components"] = json!(xname_vec.join(","));
+      bos_session_value["status"] = json!({
+        "status": "complete",
+        "start_time": now(),
+        "end_time": now(),
+      });
+
+      state.bos_sessions.push(bos_session_value.clone());
+
+      Ok(bos_session_value)
+    })
+  }
+}
+
+impl ClusterTemplateTrait for MockBackend {
+  async fn get_template(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    bos_session_template_id_opt: Option<&str>,
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    Ok(self.read(|state| {
+      state
+        .bos_sessiontemplates
+        .iter()
+        .filter(|template| {
+          bos_session_template_id_opt
+            .is_none_or(|name| template.name.as_deref() == Some(name))
+        })
+        .cloned()
+        .collect()
+    }))
+  }
+
+  async fn get_and_filter_templates(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    hsm_group_name_vec: &Vec<String>,
+    hsm_member_vec: &[String],
+    bos_sessiontemplate_name_opt: Option<&String>,
+    limit_number_opt: Option<&u8>,
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    let mut template_vec: Vec<BosSessionTemplate> = self.read(|state| {
+      state
+        .bos_sessiontemplates
+        .iter()
+        .filter(|template| {
+          bos_sessiontemplate_name_opt
+            .is_none_or(|name| template.name.as_ref() == Some(name))
+            && template.boot_sets.is_some()
+            && (template
+              .get_target_hsm()
+              .iter()
+              .any(|group| hsm_group_name_vec.contains(group))
+              || template
+                .get_target_xname()
+                .iter()
+                .any(|xname| hsm_member_vec.contains(xname)))
+        })
+        .cloned()
+        .collect()
+    });
+
+    if let Some(limit_number) = limit_number_opt {
+      template_vec = template_vec.split_off(
+        template_vec.len().saturating_sub(*limit_number as usize),
+      );
+    }
+
+    Ok(template_vec)
+  }
+
+  async fn get_all_templates(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    Ok(self.read(|state| state.bos_sessiontemplates.clone()))
+  }
+
+  async fn put_template(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    bos_template: &BosSessionTemplate,
+    bos_template_name: &str,
+  ) -> Result<BosSessionTemplate, Error> {
+    self.write(|state| {
+      let mut template = bos_template.clone();
+      template.name = Some(bos_template_name.to_string());
+
+      state
+        .bos_sessiontemplates
+        .retain(|existing| existing.name.as_deref() != Some(bos_template_name));
+      state.bos_sessiontemplates.push(template.clone());
+
+      Ok(template)
+    })
+  }
+
+  async fn delete_template(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    bos_template_id: &str,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      let template_count = state.bos_sessiontemplates.len();
+
+      state
+        .bos_sessiontemplates
+        .retain(|template| template.name.as_deref() != Some(bos_template_id));
+
+      if state.bos_sessiontemplates.len() == template_count {
+        return Err(Error::NotFound {
+          service: "BOS".to_string(),
+          resource: "session template".to_string(),
+          id: bos_template_id.to_string(),
+        });
+      }
+
+      Ok(())
+    })
+  }
+}
+
+impl CommandsTrait for MockBackend {
+  async fn i_delete_and_cancel_session(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    hsm_group_available_vec: Vec<String>,
+    cfs_session_name: &str,
+    dry_run: bool,
+    _assume_yes: bool,
+  ) -> Result<(), Error> {
+    self.write(|state| {
+      let cfs_session = state
+        .cfs_sessions
+        .iter()
+        .find(|cfs_session| {
+          cfs_session.name.as_deref() == Some(cfs_session_name)
+        })
+        .cloned()
+        .ok_or_else(|| {
+          Error::NotFound {
+            service: "CFS".to_string(),
+            resource: "session".to_string(),
+            id: cfs_session_name.to_string(),
+          }
+        })?;
+
+      // Check user has access to the nodes targeted by the session
+      let hsm_member_available_vec =
+        state.get_group_vec_members(&hsm_group_available_vec);
+
+      if !get_session_xname_vec(state, &cfs_session)
+        .iter()
+        .all(|xname| hsm_member_available_vec.contains(xname))
+      {
+        return Err(Error::Message(format!(
+          "CFS session '{}' targets nodes the user does not have access to",
+          cfs_session_name
+        )));
+      }
+
+      if dry_run {
+        log::info!("Dry run - CFS session '{}' not deleted", cfs_session_name);
+        return Ok(());
+      }
+
+      // Delete images built by the session
+      let image_id_vec = cfs_session.get_result_id_vec();
+
+      state.images.retain(|image| {
+        image
+          .id
+          .as_ref()
+          .is_none_or(|image_id| !image_id_vec.contains(image_id))
+      });
+
+      state.cfs_sessions.retain(|cfs_session| {
+        cfs_session.name.as_deref() != Some(cfs_session_name)
+      });
+
+      log::info!("CFS session '{}' deleted", cfs_session_name);
+
+      Ok(())
+    })
+  }
+
+  async fn i_delete_data_related_to_cfs_configuration(
+    &self,
+    _shasta_token: &str,
+    _shasta_base_url: &str,
+    _shasta_root_cert: &[u8],
+    _hsm_name_available_vec: Vec<String>,
+    configuration_name_opt: Option<&String>,
+    configuration_name_pattern: Option<&String>,
+    since_opt: Option<NaiveDateTime>,
+    until_opt: Option<NaiveDateTime>,
+    _assume_yes: bool,
+  ) -> Result<(), Error> {
+    let pattern_regex_opt = configuration_name_pattern
+      .map(|pattern| glob_to_regex(pattern))
+      .transpose()?;
+
+    self.write(|state| {
+      let configuration_name_vec: Vec<String> = state
+        .cfs_configurations
+        .iter()
+        .filter(|configuration| {
+          let last_updated_opt =
+            DateTime::parse_from_rfc3339(&configuration.last_updated)
+              .ok()
+              .map(|last_updated| last_updated.naive_utc());
+
+          configuration_name_opt.is_none_or(|name| &configuration.name == name)
+            && pattern_regex_opt
+              .as_ref()
+              .is_none_or(|regex| regex.is_match(&configuration.name))
+            && since_opt.is_none_or(|since| {
+              last_updated_opt.is_some_and(|last_updated| last_updated >= since)
+            })
+            && until_opt.is_none_or(|until| {
+              last_updated_opt.is_some_and(|last_updated| last_updated <= until)
+            })
+        })
+        .map(|configuration| configuration.name.clone())
+        .collect();
+
+      if configuration_name_vec.is_empty() {
+        return Err(Error::Message(
+          "No CFS configuration found".to_string(),
+        ));
+      }
+
+      // Delete CFS sessions, images and BOS sessiontemplates related to the CFS configurations
+      let image_id_vec: Vec<String> = state
+        .cfs_sessions
+        .iter()
+        .filter(|cfs_session| {
+          cfs_session
+            .get_configuration_name()
+            .is_some_and(|name| configuration_name_vec.contains(&name))
+        })
+        .flat_map(|cfs_session| cfs_session.get_result_id_vec())
+        .collect();
+
+      state.images.retain(|image| {
+        image
+          .id
+          .as_ref()
+          .is_none_or(|image_id| !image_id_vec.contains(image_id))
+      });
+
+      state.cfs_sessions.retain(|cfs_session| {
+        cfs_session
+          .get_configuration_name()
+          .is_none_or(|name| !configuration_name_vec.contains(&name))
+      });
+
+      state.bos_sessiontemplates.retain(|template| {
+        template
+          .get_confguration()
+          .is_none_or(|name| !configuration_name_vec.contains(&name))
+      });
+
+      state.cfs_configurations.retain(|configuration| {
+        !configuration_name_vec.contains(&configuration.name)
+      });
+
+      log::info!(
+        "CFS configurations '{}' and related data deleted",
+        configuration_name_vec.join(", ")
+      );
+
+      Ok(())
+    })
+  }
+}
+
+impl ConsoleTrait for MockBackend {
+  type T = Box<dyn AsyncWrite + Unpin>;
+  type U = Box<dyn AsyncRead + Unpin>;
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,307 @@
+use std::fs;
+
+use serde_json::json;
+
+use crate::{
+  contracts::BackendTrait,
+  error::Error,
+  interfaces::{
+    bss::BootParametersTrait, hsm::group::GroupTrait, pcs::PCSTrait,
+  },
+  mock::types::MockState,
+  types::{
+    pcs::power_status::types::PowerState, BootParameters, Group, Member,
+  },
+};
+
+use super::MockBackend;
+
+const AUTH_TOKEN: &str = "mock-token";
+
+fn state() -> MockState {
+  serde_json::from_value(json!({
+    "groups": [
+      { "label": "zinal", "members": { "ids": ["x1000c0s0b0n0", "x1000c0s0b0n1"] } },
+      { "label": "nodes_free", "members": { "ids": ["x1000c0s1b0n0"] } },
+    ],
+    "components": [
+      { "ID": "x1000c0s0b0n0", "Type": "Node", "NID": 1 },
+      { "ID": "x1000c0s0b0n1", "Type": "Node", "NID": 2 },
+      { "ID": "x1000c0s1b0n0", "Type": "Node", "NID": 3 },
+    ],
+    "boot_parameters": [
+      { "hosts": ["x1000c0s0b0n0", "x1000c0s0b0n1"], "params": "console=ttyS0", "kernel": "kernel-a", "initrd": "initrd-a" },
+    ],
+    "power_state": { "x1000c0s0b0n0": "on", "x1000c0s0b0n1": "on", "x1000c0s1b0n0": "off" },
+  }))
+  .unwrap()
+}
+
+fn mock() -> MockBackend {
+  MockBackend::from_state(state())
+}
+
+fn group(label: &str, member_vec: &[&str]) -> Group {
+  Group {
+    label: label.to_string(),
+    description: None,
+    tags: None,
+    members: Some(Member {
+      ids: Some(member_vec.iter().map(|member| member.to_string()).collect()),
+    }),
+    exclusive_group: None,
+  }
+}
+
+#[tokio::test]
+async fn test_add_group_conflict() {
+  let mock = mock();
+
+  let result = mock.add_group(AUTH_TOKEN, group("zinal", &[])).await;
+
+  assert!(matches!(result, Err(Error::Conflict { .. })));
+
+  mock
+    .add_group(AUTH_TOKEN, group("eiger", &["x1000c0s1b0n0"]))
+    .await
+    .unwrap();
+
+  assert_eq!(
+    mock
+      .get_group(AUTH_TOKEN, "eiger")
+      .await
+      .unwrap()
+      .get_members(),
+    vec!["x1000c0s1b0n0".to_string()]
+  );
+}
+
+#[tokio::test]
+async fn test_add_members_to_group_sorted_and_deduplicated() {
+  let mock = mock();
+
+  let member_vec = mock
+    .add_members_to_group(
+      AUTH_TOKEN,
+      "zinal",
+      vec!["x1000c0s1b0n0", "x1000c0s0b0n0"],
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(
+    member_vec,
+    vec!["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s1b0n0"]
+  );
+}
+
+#[tokio::test]
+async fn test_delete_member_from_group() {
+  let mock = mock();
+
+  assert!(mock
+    .delete_member_from_group(AUTH_TOKEN, "zinal", "x1000c0s1b0n0")
+    .await
+    .is_err());
+
+  assert!(matches!(
+    mock
+      .delete_member_from_group(AUTH_TOKEN, "missing", "x1000c0s0b0n0")
+      .await,
+    Err(Error::NotFound { .. })
+  ));
+
+  mock
+    .delete_member_from_group(AUTH_TOKEN, "zinal", "x1000c0s0b0n0")
+    .await
+    .unwrap();
+
+  assert_eq!(
+    mock
+      .get_group(AUTH_TOKEN, "zinal")
+      .await
+      .unwrap()
+      .get_members(),
+    vec!["x1000c0s0b0n1".to_string()]
+  );
+}
+
+#[tokio::test]
+async fn test_migrate_group_members() {
+  let mock = mock();
+
+  let (target_member_vec, parent_member_vec) = mock
+    .migrate_group_members(
+      AUTH_TOKEN,
+      "zinal",
+      "nodes_free",
+      vec!["x1000c0s1b0n0"],
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(
+    target_member_vec,
+    vec!["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s1b0n0"]
+  );
+  assert!(parent_member_vec.is_empty());
+
+  // Nodes not in the parent group can't be migrated
+  assert!(mock
+    .migrate_group_members(
+      AUTH_TOKEN,
+      "nodes_free",
+      "zinal",
+      vec!["x9999c0s0b0n0"],
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_power_transition_updates_power_status() {
+  let mock = mock();
+
+  let node_vec = vec!["x1000c0s0b0n0".to_string(), "x9999c0s0b0n0".to_string()];
+
+  let transition_id =
+    mock.power_off(AUTH_TOKEN, &node_vec, false).await.unwrap();
+
+  let transition = mock
+    .get_power_transition(AUTH_TOKEN, &transition_id)
+    .await
+    .unwrap();
+
+  assert_eq!(transition["operation"], "soft-off");
+  assert_eq!(transition["taskCounts"]["succeeded"], 1);
+  assert_eq!(transition["taskCounts"]["failed"], 1);
+
+  let power_status = mock
+    .power_status(AUTH_TOKEN, &node_vec[..1], None, None)
+    .await
+    .unwrap();
+
+  assert_eq!(power_status.status.len(), 1);
+  assert!(matches!(
+    power_status.status[0].power_state,
+    Some(PowerState::Off)
+  ));
+
+  // Power status filter
+  let power_status = mock
+    .power_status(
+      AUTH_TOKEN,
+      &["x1000c0s0b0n0".to_string(), "x1000c0s0b0n1".to_string()],
+      Some("on"),
+      None,
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(power_status.status.len(), 1);
+  assert_eq!(power_status.status[0].xname, "x1000c0s0b0n1");
+}
+
+#[tokio::test]
+async fn test_update_bootparameters_moves_hosts() {
+  let mock = mock();
+
+  let boot_parameters = BootParameters {
+    hosts: vec!["x1000c0s0b0n1".to_string()],
+    macs: None,
+    nids: None,
+    params: "console=ttyS1".to_string(),
+    kernel: "kernel-b".to_string(),
+    initrd: "initrd-b".to_string(),
+    cloud_init: None,
+  };
+
+  assert!(matches!(
+    mock.add_bootparameters(AUTH_TOKEN, &boot_parameters).await,
+    Err(Error::Conflict { .. })
+  ));
+
+  mock
+    .update_bootparameters(AUTH_TOKEN, &boot_parameters)
+    .await
+    .unwrap();
+
+  let boot_parameters_vec = mock
+    .get_bootparameters(AUTH_TOKEN, &["x1000c0s0b0n0".to_string()])
+    .await
+    .unwrap();
+
+  assert_eq!(boot_parameters_vec.len(), 1);
+  assert_eq!(boot_parameters_vec[0].hosts, vec!["x1000c0s0b0n0"]);
+  assert_eq!(boot_parameters_vec[0].kernel, "kernel-a");
+
+  let boot_parameters_vec = mock
+    .get_bootparameters(AUTH_TOKEN, &["x1000c0s0b0n1".to_string()])
+    .await
+    .unwrap();
+
+  assert_eq!(boot_parameters_vec.len(), 1);
+  assert_eq!(boot_parameters_vec[0].kernel, "kernel-b");
+}
+
+#[tokio::test]
+async fn test_nid_to_xname() {
+  let mock = mock();
+
+  let mut xname_vec = mock
+    .nid_to_xname(AUTH_TOKEN, "nid00000[1-2]", false)
+    .await
+    .unwrap();
+  xname_vec.sort();
+
+  assert_eq!(xname_vec, vec!["x1000c0s0b0n0", "x1000c0s0b0n1"]);
+
+  let xname_vec = mock
+    .nid_to_xname(AUTH_TOKEN, "nid00000[3]", true)
+    .await
+    .unwrap();
+
+  assert_eq!(xname_vec, vec!["x1000c0s1b0n0"]);
+}
+
+#[tokio::test]
+async fn test_changes_saved_to_fixture_file() {
+  let fixture_path =
+    std::env::temp_dir().join(format!("mock-{}.yaml", uuid::Uuid::new_v4()));
+
+  state().to_file(&fixture_path).unwrap();
+
+  let mock =
+    MockBackend::new(&format!("file://{}", fixture_path.display())).unwrap();
+
+  mock
+    .add_members_to_group(AUTH_TOKEN, "zinal", vec!["x1000c0s1b0n0"])
+    .await
+    .unwrap();
+
+  // A new backend, eg the next command, sees the change
+  let mock = MockBackend::new(fixture_path.to_str().unwrap()).unwrap();
+
+  assert_eq!(
+    mock.get_state().get_group_members("zinal").unwrap(),
+    vec!["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s1b0n0"]
+  );
+
+  fs::remove_file(&fixture_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_from_state_not_saved() {
+  let mock = mock();
+
+  mock
+    .add_members_to_group(AUTH_TOKEN, "zinal", vec!["x1000c0s1b0n0"])
+    .await
+    .unwrap();
+
+  assert!(mock.fixture_path_opt.is_none());
+  assert_eq!(
+    mock.get_state().get_group_members("zinal").unwrap().len(),
+    3
+  );
+}
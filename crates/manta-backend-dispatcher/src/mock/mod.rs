@@ -0,0 +1,2 @@
+pub mod backend_connector;
+pub mod types;
@@ -0,0 +1,172 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+  error::Error,
+  types::{
+    bos::session_template::BosSessionTemplate,
+    cfs::{
+      cfs_configuration_response::CfsConfigurationResponse,
+      component::Component as CfsComponent, session::CfsSessionGetResponse,
+    },
+    hsm::inventory::RedfishEndpoint,
+    ims::Image,
+    BootParameters, Component, Group,
+  },
+};
+
+/// State of the mock backend. This is also the format of the fixture file used to seed the
+/// mock backend, all fields are optional.
+///
+/// Fixture example (YAML):
+///
+/// ```yaml
+/// groups:
+///   - label: zinal
+///     members:
+///       ids: [x1000c0s0b0n0]
+/// components:
+///   - ID: x1000c0s0b0n0
+///     Type: Node
+///     NID: 1
+///     Role: Compute
+/// power_state:
+///   x1000c0s0b0n0: "on"
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MockState {
+  /// Token returned by `get_api_token`
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub auth_token: Option<String>,
+  /// Group names the user has access to. All groups are available if missing
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub group_available: Option<Vec<String>>,
+  // HSM
+  pub groups: Vec<Group>,
+  pub components: Vec<Component>,
+  pub redfish_endpoints: Vec<RedfishEndpoint>,
+  /// Hardware inventory per xname, as returned by HSM
+  /// `Inventory/Hardware/Query/{xname}`
+  pub hardware_inventory: HashMap<String, Value>,
+  // BSS
+  pub boot_parameters: Vec<BootParameters>,
+  // PCS
+  /// Power state per xname, either "on" or "off"
+  pub power_state: HashMap<String, String>,
+  pub power_transitions: Vec<Value>,
+  // CFS
+  pub cfs_configurations: Vec<CfsConfigurationResponse>,
+  pub cfs_sessions: Vec<CfsSessionGetResponse>,
+  pub cfs_components: Vec<CfsComponent>,
+  // BOS
+  pub bos_sessiontemplates: Vec<BosSessionTemplate>,
+  pub bos_sessions: Vec<Value>,
+  // IMS
+  pub images: Vec<Image>,
+}
+
+impl MockState {
+  /// Reads mock state from a fixture file. Files with extension 'yaml' or 'yml' are parsed as
+  /// YAML, anything else is parsed as JSON
+  pub fn from_file(path: &Path) -> Result<Self, Error> {
+    let content = fs::read_to_string(path)?;
+
+    if is_yaml_file(path) {
+      serde_yaml::from_str(&content).map_err(|e| {
+        Error::Message(format!(
+          "Could not parse mock fixture file '{}'. Reason:\n{}",
+          path.display(),
+          e
+        ))
+      })
+    } else {
+      serde_json::from_str(&content).map_err(|e| {
+        Error::Message(format!(
+          "Could not parse mock fixture file '{}'. Reason:\n{}",
+          path.display(),
+          e
+        ))
+      })
+    }
+  }
+
+  /// Saves mock state to a fixture file, in YAML or JSON depending on the file extension
+  /// same as [`MockState::from_file`]. The file is replaced atomically
+  pub fn to_file(&self, path: &Path) -> Result<(), Error> {
+    let content = if is_yaml_file(path) {
+      serde_yaml::to_string(self).map_err(|e| Error::Message(e.to_string()))?
+    } else {
+      serde_json::to_string_pretty(self)?
+    };
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+  }
+
+  /// Returns the list of members of a group
+  pub fn get_group_members(&self, label: &str) -> Option<Vec<String>> {
+    self
+      .groups
+      .iter()
+      .find(|group| group.label == label)
+      .map(|group| group.get_members())
+  }
+
+  /// Returns the list of members of a list of groups
+  pub fn get_group_vec_members(&self, label_vec: &[String]) -> Vec<String> {
+    let mut member_vec: Vec<String> = self
+      .groups
+      .iter()
+      .filter(|group| label_vec.contains(&group.label))
+      .flat_map(|group| group.get_members())
+      .collect();
+
+    member_vec.sort();
+    member_vec.dedup();
+
+    member_vec
+  }
+
+  /// Returns a mutable reference to a group
+  pub fn get_group_mut(&mut self, label: &str) -> Result<&mut Group, Error> {
+    self
+      .groups
+      .iter_mut()
+      .find(|group| group.label == label)
+      .ok_or_else(|| Error::NotFound {
+        service: "HSM".to_string(),
+        resource: "group".to_string(),
+        id: label.to_string(),
+      })
+  }
+
+  /// Sets the list of members of a group
+  pub fn set_group_members(
+    &mut self,
+    label: &str,
+    mut member_vec: Vec<String>,
+  ) -> Result<(), Error> {
+    member_vec.sort();
+    member_vec.dedup();
+
+    let group = self.get_group_mut(label)?;
+
+    group.members.get_or_insert_with(Default::default).ids = Some(member_vec);
+
+    Ok(())
+  }
+}
+
+fn is_yaml_file(path: &Path) -> bool {
+  path
+    .extension()
+    .is_some_and(|extension| extension == "yaml" || extension == "yml")
+}
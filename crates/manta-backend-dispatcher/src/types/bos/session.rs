@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::Error;
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BosSession {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub name: Option<String>,
@@ -24,6 +25,11 @@ pub struct BosSession {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = BosSessionOperation)
+)]
 pub enum Operation {
   #[serde(rename = "boot")]
   Boot,
@@ -53,6 +59,11 @@ impl Operation {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = BosSessionStatus)
+)]
 pub struct Status {
   pub start_time: String,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,6 +74,7 @@ pub struct Status {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum StatusLabel {
   #[serde(rename = "pending")]
   Pending,
@@ -3,6 +3,11 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = BosSessionTemplateLink)
+)]
 pub struct Link {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub rel: Option<String>,
@@ -11,12 +16,14 @@ pub struct Link {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Cfs {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub configuration: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BootSet {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub name: Option<String>,
@@ -45,6 +52,7 @@ pub struct BootSet {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BosSessionTemplate {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub name: Option<String>,
@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString, IntoStaticStr};
+
+/// Group of operations a backend may support. Each capability matches one of
+/// the backend interfaces (eg: `Console` -> `ConsoleTrait`)
+#[derive(
+  Debug,
+  EnumIter,
+  EnumString,
+  IntoStaticStr,
+  AsRefStr,
+  Display,
+  Serialize,
+  Deserialize,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+  Hash,
+)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum Capability {
+  /// `GroupTrait`
+  Group,
+  /// `ComponentTrait`
+  Component,
+  /// `HardwareInventory`
+  HardwareInventory,
+  /// `RedfishEndpointTrait`
+  RedfishEndpoint,
+  /// `BootParametersTrait`
+  BootParameters,
+  /// `PCSTrait` except power capping
+  Power,
+  /// `PCSTrait::power_cap_snapshot` and `PCSTrait::power_cap_patch`
+  PowerCap,
+  /// `CfsTrait` except session logs
+  Cfs,
+  /// `CfsTrait::get_session_logs_stream` and
+  /// `CfsTrait::get_session_logs_stream_by_xname`
+  CfsSessionLogs,
+  /// `ClusterSessionTrait` and `ClusterTemplateTrait`
+  Bos,
+  /// `ImsTrait` and `GetImagesAndDetailsTrait`
+  Ims,
+  /// `ApplySessionTrait`
+  ApplySession,
+  /// `SatTrait`
+  SatFile,
+  /// `ApplyHwClusterPin`
+  HwClusterPin,
+  /// `MigrateBackupTrait`
+  MigrateBackup,
+  /// `MigrateRestoreTrait`
+  MigrateRestore,
+  /// `CommandsTrait::i_delete_and_cancel_session`
+  DeleteSession,
+  /// `CommandsTrait::i_delete_data_related_to_cfs_configuration`
+  DeleteConfiguration,
+  /// `ConsoleTrait`
+  Console,
+}
+
+/// Set of capabilities supported by a backend
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(transparent)]
+pub struct Capabilities(BTreeSet<Capability>);
+
+impl Capabilities {
+  pub fn new(capability_vec: &[Capability]) -> Self {
+    Self(capability_vec.iter().copied().collect())
+  }
+
+  /// Every capability, for backends implementing all interfaces
+  pub fn all() -> Self {
+    Self(Capability::iter().collect())
+  }
+
+  pub fn contains(&self, capability: Capability) -> bool {
+    self.0.contains(&capability)
+  }
+
+  /// Capabilities missing in this set
+  pub fn missing(&self) -> Vec<Capability> {
+    Capability::iter()
+      .filter(|capability| !self.contains(*capability))
+      .collect()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = Capability> + '_ {
+    self.0.iter().copied()
+  }
+}
+
+impl FromIterator<Capability> for Capabilities {
+  fn from_iter<I: IntoIterator<Item = Capability>>(iter: I) -> Self {
+    Self(iter.into_iter().collect())
+  }
+}
@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsConfigurationRequestLayer)
+)]
 pub struct Layer {
   #[serde(skip_serializing_if = "Option::is_none")]
   // Either commit or branch is passed
@@ -45,12 +50,22 @@ impl Layer {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsConfigurationRequestSpecialParameter)
+)]
 pub struct SpecialParameter {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ims_required_dkms: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsConfigurationRequestAdditionalInventory)
+)]
 pub struct AdditionalInventory {
   pub name: Option<String>,
   pub clone_url: String,
@@ -60,6 +75,7 @@ pub struct AdditionalInventory {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CfsConfigurationRequest {
   pub description: Option<String>,
   pub layers: Option<Vec<Layer>>,
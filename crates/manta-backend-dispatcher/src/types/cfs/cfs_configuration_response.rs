@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)] // TODO: investigate why serde can Deserialize dynamically syzed structs `Vec<Layer>`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+// TODO: investigate why serde can Deserialize dynamically syzed structs `Vec<Layer>`
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsConfigurationNext)
+)]
 pub struct Next {
   pub limit: Option<u8>,
   pub after_id: Option<String>,
@@ -8,6 +14,11 @@ pub struct Next {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsConfigurationLayer)
+)]
 pub struct Layer {
   pub name: String,
   // #[serde(rename = "cloneUrl")]
@@ -23,6 +34,11 @@ pub struct Layer {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsConfigurationAdditionalInventory)
+)]
 pub struct AdditionalInventory {
   pub name: String,
   pub clone_url: String,
@@ -31,12 +47,18 @@ pub struct AdditionalInventory {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsConfigurationSpecialParameter)
+)]
 pub struct SpecialParameter {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ims_required_dkms: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CfsConfigurationResponse {
   pub name: String,
   pub last_updated: String,
@@ -47,6 +69,7 @@ pub struct CfsConfigurationResponse {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CfsConfigurationVecResponse {
   pub configurations: Vec<CfsConfigurationResponse>,
   pub next: Option<Next>,
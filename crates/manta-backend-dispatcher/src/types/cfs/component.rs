@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct State {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub clone_url: Option<String>,
@@ -15,6 +16,11 @@ pub struct State {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsComponent)
+)]
 pub struct Component {
   pub id: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -36,6 +42,7 @@ pub struct Component {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ComponentVec {
   pub components: Vec<Component>,
 }
@@ -3,12 +3,19 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CfsSessionGetResponseList {
   pub sessions: Vec<CfsSessionGetResponse>,
   pub next: Option<Next>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)] // TODO: investigate why serde can Deserialize dynamically syzed structs `Vec<Layer>`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+// TODO: investigate why serde can Deserialize dynamically syzed structs `Vec<Layer>`
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsSessionNext)
+)]
 pub struct Next {
   pub limit: Option<u8>,
   pub after_id: Option<String>,
@@ -16,6 +23,7 @@ pub struct Next {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Configuration {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub name: Option<String>,
@@ -24,6 +32,7 @@ pub struct Configuration {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Ansible {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub config: Option<String>,
@@ -36,18 +45,25 @@ pub struct Ansible {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsSessionGroup)
+)]
 pub struct Group {
   pub name: String,
   pub members: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ImageMap {
   pub source_id: String,
   pub result_name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Target {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub definition: Option<String>,
@@ -57,6 +73,7 @@ pub struct Target {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Artifact {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub image_id: Option<String>,
@@ -67,6 +84,7 @@ pub struct Artifact {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Session {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub job: Option<String>,
@@ -83,6 +101,11 @@ pub struct Session {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = CfsSessionStatus)
+)]
 pub struct Status {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub artifacts: Option<Vec<Artifact>>,
@@ -91,6 +114,7 @@ pub struct Status {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CfsSessionGetResponse {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub name: Option<String>,
@@ -225,6 +249,7 @@ impl CfsSessionGetResponse {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CfsSessionPostRequest {
   pub name: String,
   pub configuration_name: String,
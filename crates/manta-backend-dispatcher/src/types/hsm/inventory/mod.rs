@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DiscoveryInfo {
   #[serde(rename = "LastAttempt")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -14,6 +15,7 @@ pub struct DiscoveryInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishEndpoint {
   #[serde(rename = "ID")]
   pub id: String,
@@ -68,6 +70,7 @@ pub struct RedfishEndpoint {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishEndpointArray {
   #[serde(rename = "RedfishEndpoints")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ImsImageRecord2Update {
   pub link: Link,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -8,6 +9,11 @@ pub struct ImsImageRecord2Update {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = ImsLink)
+)]
 pub struct Link {
   pub path: String,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -16,6 +22,7 @@ pub struct Link {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Image {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub id: Option<String>,
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::{audit::Audit, error::Error};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Kafka {
   pub brokers: Vec<String>,
   pub topic: String,
@@ -64,7 +65,12 @@ impl Audit for Kafka {
       .set("bootstrap.servers", brokers)
       .set("message.timeout.ms", "5000")
       .create()
-      .expect("Producer creation error");
+      .map_err(|e| {
+        Error::Message(format!(
+          "Could not create Kafka producer. Reason:\n{}",
+          e
+        ))
+      })?;
 
     let delivery_status = producer
       .send::<Vec<u8>, _, _>(
@@ -86,6 +92,13 @@ impl Audit for Kafka {
       delivery_status
     );
 
+    if let Err((e, _)) = delivery_status {
+      return Err(Error::Message(format!(
+        "Could not deliver message to Kafka topic '{}'. Reason:\n{}",
+        topic_name, e
+      )));
+    }
+
     /* // This loop is non blocking: all messages will be sent one after the other, without waiting
     // for the results.
     let futures = (0..5)
@@ -1,4 +1,5 @@
 pub mod bos;
+pub mod capability;
 pub mod cfs;
 pub mod hsm;
 pub mod ims;
@@ -14,6 +15,7 @@ use strum_macros::{AsRefStr, Display, EnumIter, EnumString, IntoStaticStr};
 use crate::error::Error;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum K8sAuth {
   #[serde(rename = "native")]
   Native {
@@ -26,6 +28,7 @@ pub enum K8sAuth {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct K8sDetails {
   pub api_url: String,
   pub authentication: K8sAuth,
@@ -43,6 +46,7 @@ pub struct K8sDetails {
   Deserialize,
   Clone,
 )]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum ArtifactType {
   Memory,
   Processor,
@@ -59,6 +63,7 @@ pub enum ArtifactType {
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct NodeSummary {
   pub xname: String,
   pub r#type: String,
@@ -364,10 +369,16 @@ impl NodeSummary {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ArtifactSummary {
   pub xname: String,
   pub r#type: ArtifactType,
   pub info: Option<String>,
+  /// Changes when the FRU in this location is replaced
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub fru_id: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub serial_number: Option<String>,
 }
 
 impl From<HWInvByLocProcessor> for ArtifactSummary {
@@ -375,9 +386,13 @@ impl From<HWInvByLocProcessor> for ArtifactSummary {
     ArtifactSummary {
       xname: value.id,
       r#type: ArtifactType::from_str(value.r#type.unwrap().as_str()).unwrap(),
+      fru_id: value.populated_fru.as_ref().and_then(
+        |hw_inv_by_fru_processor| hw_inv_by_fru_processor.fru_id.clone(),
+      ),
       info: value.populated_fru.and_then(|hw_inv_by_fru_processor| {
         hw_inv_by_fru_processor.processor_fru_info.model
       }),
+      serial_number: None,
     }
   }
 }
@@ -428,6 +443,15 @@ impl From<HWInvByLocMemory> for ArtifactSummary {
     ArtifactSummary {
       xname: value.id,
       r#type: ArtifactType::from_str(value.r#type.unwrap().as_str()).unwrap(),
+      fru_id: value
+        .populated_fru
+        .as_ref()
+        .and_then(|hw_inv_by_fru_memory| hw_inv_by_fru_memory.fru_id.clone()),
+      serial_number: value.populated_fru.as_ref().and_then(
+        |hw_inv_by_fru_memory| {
+          hw_inv_by_fru_memory.memory_fru_info.serial_number.clone()
+        },
+      ),
       info: value.populated_fru.and_then(|hw_inv_by_fru_memory| {
         hw_inv_by_fru_memory
           .memory_fru_info
@@ -495,9 +519,13 @@ impl From<HWInvByLocNodeAccel> for ArtifactSummary {
     ArtifactSummary {
       xname: value.id,
       r#type: ArtifactType::from_str(value.r#type.unwrap().as_str()).unwrap(),
+      fru_id: value.populated_fru.as_ref().and_then(
+        |hw_inv_by_fru_node_accel| hw_inv_by_fru_node_accel.fru_id.clone(),
+      ),
       info: value.populated_fru.and_then(|hw_inv_by_fru_node_accel| {
         hw_inv_by_fru_node_accel.node_accel_fru_info.model
       }),
+      serial_number: None,
     }
   }
 }
@@ -543,6 +571,15 @@ impl From<HWInvByLocHSNNIC> for ArtifactSummary {
     ArtifactSummary {
       xname: value.id,
       r#type: ArtifactType::from_str(value.r#type.unwrap().as_str()).unwrap(),
+      fru_id: value
+        .populated_fru
+        .as_ref()
+        .and_then(|hw_inv_by_fru_hsn_nic| hw_inv_by_fru_hsn_nic.fru_id.clone()),
+      serial_number: value.populated_fru.as_ref().and_then(
+        |hw_inv_by_fru_hsn_nic| {
+          hw_inv_by_fru_hsn_nic.hsn_nic_fru_info.serial_number.clone()
+        },
+      ),
       info: value.populated_fru.and_then(|hw_inv_by_fru_hsn_nic| {
         hw_inv_by_fru_hsn_nic.hsn_nic_fru_info.model
       }),
@@ -597,6 +634,11 @@ impl ArtifactSummary {
       info: processor_value
         .pointer("/PopulatedFRU/ProcessorFRUInfo/Model")
         .map(|model| model.as_str().unwrap().to_string()),
+      fru_id: Self::get_fru_id_from_value(&processor_value),
+      serial_number: Self::get_serial_number_from_value(
+        &processor_value,
+        "ProcessorFRUInfo",
+      ),
     }
   }
 
@@ -610,6 +652,11 @@ impl ArtifactSummary {
         .map(|capacity_mib| {
           capacity_mib.as_number().unwrap().to_string() + " MiB"
         }),
+      fru_id: Self::get_fru_id_from_value(&memory_value),
+      serial_number: Self::get_serial_number_from_value(
+        &memory_value,
+        "MemoryFRUInfo",
+      ),
     }
   }
 
@@ -623,6 +670,11 @@ impl ArtifactSummary {
       info: nodehsnnic_value
         .pointer("/NodeHsnNicLocationInfo/Description")
         .map(|description| description.as_str().unwrap().to_string()),
+      fru_id: Self::get_fru_id_from_value(&nodehsnnic_value),
+      serial_number: Self::get_serial_number_from_value(
+        &nodehsnnic_value,
+        "HSNNICFRUInfo",
+      ),
     }
   }
 
@@ -634,12 +686,36 @@ impl ArtifactSummary {
       info: nodeaccel_value
         .pointer("/PopulatedFRU/NodeAccelFRUInfo/Model")
         .map(|model| model.as_str().unwrap().to_string()),
+      fru_id: Self::get_fru_id_from_value(&nodeaccel_value),
+      serial_number: Self::get_serial_number_from_value(
+        &nodeaccel_value,
+        "NodeAccelFRUInfo",
+      ),
     }
   }
+
+  fn get_fru_id_from_value(artifact_value: &Value) -> Option<String> {
+    artifact_value
+      .pointer("/PopulatedFRU/FRUID")
+      .and_then(Value::as_str)
+      .map(str::to_string)
+  }
+
+  /// 'fru_info_key' is the FRU info field of the artifact type, eg 'MemoryFRUInfo'
+  fn get_serial_number_from_value(
+    artifact_value: &Value,
+    fru_info_key: &str,
+  ) -> Option<String> {
+    artifact_value
+      .pointer(&format!("/PopulatedFRU/{fru_info_key}/SerialNumber"))
+      .and_then(Value::as_str)
+      .map(str::to_string)
+  }
 }
 
 // From OCHAMI API
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Group {
   pub label: String,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -654,11 +730,13 @@ pub struct Group {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Member {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub ids: Option<Vec<String>>,
 }
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct XnameId {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub id: Option<String>,
@@ -703,6 +781,7 @@ impl Group {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct NodeMetadataArray {
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "Components")]
@@ -710,6 +789,7 @@ pub struct NodeMetadataArray {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Component {
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "ID")]
@@ -759,6 +839,7 @@ pub struct Component {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ComponentArrayPostQuery {
   #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "ComponentIDs")]
@@ -809,6 +890,7 @@ pub struct ComponentArrayPostQuery {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ComponentArrayPostByNidQuery {
   #[serde(rename = "NIDRanges")]
   pub nid_ranges: Vec<String>,
@@ -829,6 +911,7 @@ pub struct ComponentArrayPostByNidQuery {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ComponentArrayPostArray {
   #[serde(rename = "Components")]
   pub components: Vec<ComponentCreate>,
@@ -838,6 +921,7 @@ pub struct ComponentArrayPostArray {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ComponentCreate {
   #[serde(rename = "ID")]
   pub id: String,
@@ -876,6 +960,7 @@ pub struct ComponentCreate {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ComponentPut {
   pub component: ComponentCreate,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -884,6 +969,7 @@ pub struct ComponentPut {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BootParameters {
   #[serde(default)]
   pub hosts: Vec<String>,
@@ -1388,6 +1474,7 @@ impl BootParameters {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum ComponentType {
   CDU,
   CabinetCDU,
@@ -1425,6 +1512,7 @@ pub enum ComponentType {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProcessorId {
   #[serde(rename = "EffectiveFamily")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1447,6 +1535,7 @@ pub struct ProcessorId {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByFRUProcessor {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1464,6 +1553,7 @@ pub struct HWInvByFRUProcessor {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByFRUMemory {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1481,6 +1571,7 @@ pub struct HWInvByFRUMemory {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByFRUHSNNIC {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1498,6 +1589,7 @@ pub struct HWInvByFRUHSNNIC {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByFRUNodeAccel {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1516,6 +1608,7 @@ pub struct HWInvByFRUNodeAccel {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishProcessorFRUInfo {
   #[serde(rename = "InstructionSet")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1547,6 +1640,7 @@ pub struct RedfishProcessorFRUInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishMemoryFRUInfo {
   #[serde(rename = "BaseModuleType")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1587,6 +1681,7 @@ pub struct RedfishMemoryFRUInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInventoryByFRU {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1602,6 +1697,7 @@ pub struct HWInventoryByFRU {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishChassisLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1618,6 +1714,7 @@ pub struct RedfishChassisLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocChassis {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1647,6 +1744,7 @@ pub struct HWInvByLocChassis {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocNodeEnclosure {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1670,6 +1768,7 @@ pub struct HWInvByLocNodeEnclosure {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocComputeModule {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1696,6 +1795,7 @@ pub struct HWInvByLocComputeModule {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocHSNBoard {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1719,6 +1819,7 @@ pub struct HWInvByLocHSNBoard {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocRouterModule {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1743,6 +1844,7 @@ pub struct HWInvByLocRouterModule {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocCabinet {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1769,6 +1871,7 @@ pub struct HWInvByLocCabinet {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocMgmtSwitch {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1792,6 +1895,7 @@ pub struct HWInvByLocMgmtSwitch {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocMgmtHLSwitch {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1815,6 +1919,7 @@ pub struct HWInvByLocMgmtHLSwitch {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocCDUMgmtSwitch {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1838,6 +1943,7 @@ pub struct HWInvByLocCDUMgmtSwitch {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProcessorSummary {
   #[serde(rename = "Count")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1848,6 +1954,7 @@ pub struct ProcessorSummary {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MemorySummary {
   #[serde(rename = "TotalSystemMemoryGiB")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1855,6 +1962,7 @@ pub struct MemorySummary {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishSystemLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1877,6 +1985,7 @@ pub struct RedfishSystemLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishProcessorLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1893,6 +2002,7 @@ pub struct RedfishProcessorLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocProcessor {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1915,6 +2025,7 @@ pub struct HWInvByLocProcessor {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocNodeAccel {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1938,6 +2049,7 @@ pub struct HWInvByLocNodeAccel {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishDriveLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1951,6 +2063,7 @@ pub struct RedfishDriveLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocDrive {
   #[serde(rename = "ID")]
   pub id: String,
@@ -1974,6 +2087,7 @@ pub struct HWInvByLocDrive {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MemoryLocation {
   #[serde(rename = "Socket")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -1990,6 +2104,7 @@ pub struct MemoryLocation {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishMemoryLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2006,6 +2121,7 @@ pub struct RedfishMemoryLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocMemory {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2028,6 +2144,7 @@ pub struct HWInvByLocMemory {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishNodeAccelRiserLocationInfo {
   #[serde(rename = "Name")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2038,6 +2155,7 @@ pub struct RedfishNodeAccelRiserLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocNodeAccelRiser {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2061,6 +2179,7 @@ pub struct HWInvByLocNodeAccelRiser {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HSNNICLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2074,6 +2193,7 @@ pub struct HSNNICLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocHSNNIC {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2098,6 +2218,7 @@ pub struct HWInvByLocHSNNIC {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishSystemFRUInfo {
   #[serde(rename = "AssetTag")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2129,6 +2250,7 @@ pub struct RedfishSystemFRUInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByFRUNode {
   #[serde(rename = "FRUID")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2146,6 +2268,7 @@ pub struct HWInvByFRUNode {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HSNNICFRUInfo {
   #[serde(rename = "Manufacturer")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2165,6 +2288,7 @@ pub struct HSNNICFRUInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocNode {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2206,6 +2330,7 @@ pub struct HWInvByLocNode {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishPDULocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2222,6 +2347,7 @@ pub struct RedfishPDULocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishOutletLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2235,6 +2361,7 @@ pub struct RedfishOutletLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocOutlet {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2258,6 +2385,7 @@ pub struct HWInvByLocOutlet {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocPDU {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2284,6 +2412,7 @@ pub struct HWInvByLocPDU {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishCMMRectifierLocationInfo {
   #[serde(rename = "Name")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2294,6 +2423,7 @@ pub struct RedfishCMMRectifierLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocCMMRectifier {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2317,6 +2447,7 @@ pub struct HWInvByLocCMMRectifier {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishNodeEnclosurePowerSupplyLocationInfo {
   #[serde(rename = "Name")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2327,6 +2458,7 @@ pub struct RedfishNodeEnclosurePowerSupplyLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocNodePowerSupply {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2351,6 +2483,7 @@ pub struct HWInvByLocNodePowerSupply {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RedfishManagerLocationInfo {
   #[serde(rename = "Id")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2373,6 +2506,7 @@ pub struct RedfishManagerLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocNodeBMC {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2396,6 +2530,7 @@ pub struct HWInvByLocNodeBMC {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInvByLocRouterBMC {
   #[serde(rename = "ID")]
   pub id: String,
@@ -2425,6 +2560,7 @@ pub struct HWInventoryList {
 } */
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInventory {
   #[serde(rename = "XName")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2501,6 +2637,7 @@ pub struct HWInventory {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Hardware {
   #[serde(rename = "Hardware")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -2508,6 +2645,7 @@ pub struct Hardware {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct NodeLocationInfo {
   #[serde(rename = "Id")]
   pub id: String,
@@ -2529,6 +2667,7 @@ pub struct NodeLocationInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(untagged)] // <-- this is important. More info https://serde.rs/enum-representations.html#untagged
 pub enum HWInventoryByLocation {
   /* HWInvByLocCabinet(HWInvByLocCabinet),
@@ -2557,6 +2696,7 @@ pub enum HWInventoryByLocation {
 
 /// struct used in POST and GET endpoints that manage multiple instances of 'HWInventoryByLocation'
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct HWInventoryByLocationList {
   #[serde(rename = "Hardware")]
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TaskCounts {
+  pub total: usize,
+  pub new: usize,
+  #[serde(rename = "in-progress")]
+  pub in_progress: usize,
+  pub failed: usize,
+  pub succeeded: usize,
+  #[serde(rename = "un-supported")]
+  pub un_supported: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Limit {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "hostLimitMax")]
+  pub host_limit_max: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "hostLimitMin")]
+  pub host_limit_min: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "powerupPower")]
+  pub powerup_power: Option<usize>,
+}
+
+/// Power cap control of a node (eg: 'Node Power Limit') with its value and the range it
+/// accepts, all in watts
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PowerCapLimit {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "currentValue")]
+  pub current_value: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "maximumValue")]
+  pub maximum_value: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "minimumValue")]
+  pub minimum_value: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PowerCapComponent {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub xname: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub limits: Option<Limit>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "powerCapLimits")]
+  pub power_cap_limits: Option<Vec<PowerCapLimit>>,
+}
+
+/// Power cap snapshot or patch task. Components are only populated once the task is
+/// completed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PowerCapTaskInfo {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "taskID")]
+  pub task_id: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub r#type: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "taskCreateTime")]
+  pub task_create_time: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "automaticExpirationTime")]
+  pub automatic_expiration_time: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "taskStatus")]
+  pub task_status: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "taskCounts")]
+  pub task_counts: Option<TaskCounts>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub components: Option<Vec<PowerCapComponent>>,
+}
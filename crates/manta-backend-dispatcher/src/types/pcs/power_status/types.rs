@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::types::pcs::transitions::types::Operation;
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum PowerState {
   #[serde(rename = "on")]
   On,
@@ -13,12 +14,14 @@ pub enum PowerState {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum ManagementState {
   Unavailable,
   Available,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PowerStatus {
   pub xname: String,
 //  #[serde(skip_serializing_if = "Option::is_none")]
@@ -42,6 +45,7 @@ pub struct PowerStatus {
 //TODO: define a PowerStatusAll,
 // This is actually what is returned from the pcs endpoint
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PowerStatusAll {
   pub status: Vec<PowerStatus>
 }
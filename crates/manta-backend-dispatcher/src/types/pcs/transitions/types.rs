@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Location {
   pub xname: String,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -9,6 +10,11 @@ pub struct Location {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  schema(as = TransitionOperation)
+)]
 pub enum Operation {
   #[serde(rename = "on")]
   On,
@@ -27,6 +33,7 @@ pub enum Operation {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct Transition {
   pub operation: Operation,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,81 @@
+mod middleware;
+mod validator;
+
+pub use crate::auth::middleware::{ADMIN_ROLES, authenticate, require_roles};
+pub use crate::auth::validator::TokenValidator;
+
+use axum::{
+  extract::FromRequestParts,
+  http::request::Parts,
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use serde_json::Value;
+
+/// User owning the token of the request. Added to the request extensions by
+/// the `authenticate` middleware
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+  /// 'preferred_username' claim
+  pub id: String,
+  /// 'name' claim
+  pub name: String,
+  /// Groups the user has access to, plus 'pa_admin' for administrators
+  pub roles: Vec<String>,
+  /// Token to forward to the backend
+  pub token: String,
+}
+
+impl AuthUser {
+  pub fn new(token: &str, claims: &Value, roles_claim: &str) -> Self {
+    let get_claim =
+      |claim: &str| claims[claim].as_str().unwrap_or("MISSING").to_string();
+
+    let roles = claims
+      .pointer(roles_claim)
+      .and_then(Value::as_array)
+      .map(|role_vec| {
+        role_vec
+          .iter()
+          .filter_map(Value::as_str)
+          .map(str::to_string)
+          .collect()
+      })
+      .unwrap_or_default();
+
+    AuthUser {
+      id: get_claim("preferred_username"),
+      name: get_claim("name"),
+      roles,
+      token: token.to_string(),
+    }
+  }
+
+  pub fn has_any_role(&self, role_vec: &[&str]) -> bool {
+    self
+      .roles
+      .iter()
+      .any(|role| role_vec.contains(&role.as_str()))
+  }
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request_parts(
+    parts: &mut Parts,
+    _state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    parts
+      .extensions
+      .get::<AuthUser>()
+      .cloned()
+      .ok_or_else(|| StatusCode::UNAUTHORIZED.into_response())
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension,
+  extract::{Request, State},
+  middleware::Next,
+  response::Response,
+};
+use manta_backend_dispatcher::error::Error;
+
+use crate::{
+  auth::AuthUser, common::site_backend::SiteBackend,
+  http_response::backend_error_response, jwt_utils::get_auth_token,
+};
+
+/// Roles allowed to call the routes changing the system regardless of groups,
+/// eg redfish endpoints or boot parameters
+pub const ADMIN_ROLES: &[&str] = &["pa_admin"];
+
+/// Validates the token in the 'authorization' header against the identity
+/// provider of the site of the request and adds the user owning it to the
+/// request extensions. Answers 401 if the token is missing or not valid. Must
+/// run after `select_site`
+pub async fn authenticate(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  mut request: Request,
+  next: Next,
+) -> Response {
+  let Some(auth_token) = get_auth_token(request.headers()) else {
+    return backend_error_response(Error::Unauthorized {
+      service: "manta-ws".to_string(),
+      detail: "authentication token missing".to_string(),
+    });
+  };
+
+  match site_backend.token_validator.validate(auth_token).await {
+    Ok(user) => {
+      request.extensions_mut().insert(user);
+      next.run(request).await
+    }
+    Err(e) => {
+      tracing::warn!("Request rejected. Reason:\n{}", e);
+      backend_error_response(e)
+    }
+  }
+}
+
+/// Answers 403 unless the user has one of the roles. Must run after
+/// `authenticate`
+pub async fn require_roles(
+  State(role_vec): State<&'static [&'static str]>,
+  user: AuthUser,
+  request: Request,
+  next: Next,
+) -> Response {
+  if !user.has_any_role(role_vec) {
+    return backend_error_response(Error::Forbidden {
+      service: "manta-ws".to_string(),
+      detail: format!(
+        "user '{}' needs one of the roles '{}'",
+        user.id,
+        role_vec.join(", ")
+      ),
+    });
+  }
+
+  next.run(request).await
+}
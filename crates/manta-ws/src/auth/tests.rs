@@ -0,0 +1,426 @@
+use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+
+use axum::{
+  Extension, Router,
+  body::Body,
+  http::{Request, StatusCode},
+  middleware,
+  routing::get,
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use manta_backend_dispatcher::error::Error;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use crate::common::{
+  config::types::{AuthConfig, MantaConfiguration, Site},
+  site_backend::SiteBackend,
+};
+
+use super::{
+  ADMIN_ROLES, AuthUser, TokenValidator, authenticate, require_roles,
+};
+
+const SECRET: &[u8] = b"manta-ws-test-secret";
+const ISSUER: &str = "https://api.alps.example/keycloak/realms/shasta";
+const AUDIENCE: &str = "shasta";
+
+/// Directory only used by the test with a JWKS file and a root certificate.
+/// Key 'test-key' has no algorithm, key 'hs512-key' only accepts HS512
+fn test_dir(test_name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!(
+    "manta-ws-auth-{}-{}",
+    std::process::id(),
+    test_name
+  ));
+
+  fs::create_dir_all(&dir).unwrap();
+
+  let k = URL_SAFE_NO_PAD.encode(SECRET);
+
+  let jwks = json!({
+    "keys": [
+      { "kty": "oct", "kid": "test-key", "k": k },
+      { "kty": "oct", "kid": "hs512-key", "alg": "HS512", "k": k },
+    ]
+  });
+
+  fs::write(dir.join("jwks.json"), jwks.to_string()).unwrap();
+  fs::write(dir.join("root_cert.pem"), "").unwrap();
+
+  dir
+}
+
+fn auth_config(dir: &PathBuf) -> AuthConfig {
+  AuthConfig {
+    jwks_url: None,
+    jwks_file: Some(dir.join("jwks.json").display().to_string()),
+    issuer: Some(ISSUER.to_string()),
+    audience: Some(AUDIENCE.to_string()),
+    roles_claim: "/realm_access/roles".to_string(),
+    algorithms: vec![Algorithm::HS256],
+  }
+}
+
+fn claims(roles: &[&str]) -> Value {
+  json!({
+    "preferred_username": "alice",
+    "name": "Alice",
+    "iss": ISSUER,
+    "aud": AUDIENCE,
+    "exp": Utc::now().timestamp() + 3600,
+    "realm_access": { "roles": roles },
+  })
+}
+
+fn token(
+  algorithm: Algorithm,
+  kid_opt: Option<&str>,
+  claims: &Value,
+  secret: &[u8],
+) -> String {
+  let header = Header {
+    kid: kid_opt.map(str::to_string),
+    ..Header::new(algorithm)
+  };
+
+  encode(&header, claims, &EncodingKey::from_secret(secret)).unwrap()
+}
+
+fn valid_token(roles: &[&str]) -> String {
+  token(Algorithm::HS256, Some("test-key"), &claims(roles), SECRET)
+}
+
+async fn validator(dir: &PathBuf) -> TokenValidator {
+  TokenValidator::new(Some(auth_config(dir)), Vec::new())
+    .await
+    .unwrap()
+}
+
+fn assert_unauthorized(rslt: Result<AuthUser, Error>) {
+  assert!(
+    matches!(rslt, Err(Error::Unauthorized { .. })),
+    "expected unauthorized, got {:?}",
+    rslt
+  );
+}
+
+#[tokio::test]
+async fn test_validate_valid_token() {
+  let dir = test_dir("valid");
+
+  let user = validator(&dir)
+    .await
+    .validate(&valid_token(&["zinal"]))
+    .await
+    .unwrap();
+
+  assert_eq!(user.id, "alice");
+  assert_eq!(user.name, "Alice");
+  assert_eq!(user.roles, vec!["zinal".to_string()]);
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_bad_signature() {
+  let dir = test_dir("signature");
+
+  let token = token(
+    Algorithm::HS256,
+    Some("test-key"),
+    &claims(&["zinal"]),
+    b"another-secret",
+  );
+
+  assert_unauthorized(validator(&dir).await.validate(&token).await);
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_expired_token() {
+  let dir = test_dir("expired");
+
+  let mut claims = claims(&["zinal"]);
+  claims["exp"] = json!(Utc::now().timestamp() - 3600);
+
+  let token = token(Algorithm::HS256, Some("test-key"), &claims, SECRET);
+
+  assert_unauthorized(validator(&dir).await.validate(&token).await);
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_algorithm_not_allowed() {
+  let dir = test_dir("algorithm");
+
+  let validator = validator(&dir).await;
+
+  // Key without algorithm, only the algorithms in the configuration
+  let token = token(
+    Algorithm::HS384,
+    Some("test-key"),
+    &claims(&["zinal"]),
+    SECRET,
+  );
+
+  assert_unauthorized(validator.validate(&token).await);
+
+  // Key with algorithm, only the algorithm of the key
+  let token = token(
+    Algorithm::HS256,
+    Some("hs512-key"),
+    &claims(&["zinal"]),
+    SECRET,
+  );
+
+  assert_unauthorized(validator.validate(&token).await);
+
+  let token = token(
+    Algorithm::HS512,
+    Some("hs512-key"),
+    &claims(&["zinal"]),
+    SECRET,
+  );
+
+  assert!(validator.validate(&token).await.is_ok());
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_unknown_or_missing_kid() {
+  let dir = test_dir("kid");
+
+  let validator = validator(&dir).await;
+
+  let token_unknown_kid = token(
+    Algorithm::HS256,
+    Some("unknown-key"),
+    &claims(&["zinal"]),
+    SECRET,
+  );
+
+  assert_unauthorized(validator.validate(&token_unknown_kid).await);
+
+  let token_missing_kid =
+    token(Algorithm::HS256, None, &claims(&["zinal"]), SECRET);
+
+  assert_unauthorized(validator.validate(&token_missing_kid).await);
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_issuer_mismatch() {
+  let dir = test_dir("issuer");
+
+  let mut claims = claims(&["zinal"]);
+  claims["iss"] = json!("https://keycloak.example/realms/other");
+
+  let token = token(Algorithm::HS256, Some("test-key"), &claims, SECRET);
+
+  assert_unauthorized(validator(&dir).await.validate(&token).await);
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_audience_mismatch() {
+  let dir = test_dir("audience");
+
+  let mut claims = claims(&["zinal"]);
+  claims["aud"] = json!("another-client");
+
+  let token = token(Algorithm::HS256, Some("test-key"), &claims, SECRET);
+
+  assert_unauthorized(validator(&dir).await.validate(&token).await);
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_unverified_token_has_no_roles() {
+  let validator = TokenValidator::new(None, Vec::new()).await.unwrap();
+
+  assert!(validator.is_unverified());
+
+  // Any signature is accepted, roles in the claims are ignored
+  let token = token(
+    Algorithm::HS256,
+    None,
+    &claims(&["pa_admin"]),
+    b"another-secret",
+  );
+
+  let user = validator.validate(&token).await.unwrap();
+
+  assert_eq!(user.id, "alice");
+  assert!(user.roles.is_empty());
+}
+
+/// Configuration with site 'alps'
+fn configuration(
+  dir: &PathBuf,
+  auth_opt: Option<AuthConfig>,
+  insecure_skip_token_verification: bool,
+) -> MantaConfiguration {
+  let site = Site {
+    backend: "csm".to_string(),
+    socks5_proxy: None,
+    shasta_base_url: "https://api.alps.example/apis".to_string(),
+    k8s: None,
+    vault_base_url: None,
+    vault_secret_path: None,
+    root_ca_cert_file: dir.join("root_cert.pem").display().to_string(),
+    mock_fixture_file: None,
+    keycloak_base_url: None,
+    auth: auth_opt,
+    metrics_token_file: None,
+  };
+
+  MantaConfiguration {
+    log: "info".to_string(),
+    site: "alps".to_string(),
+    parent_hsm_group: "nodes_free".to_string(),
+    audit_file: dir.join("audit.log").display().to_string(),
+    sites: HashMap::from([("alps".to_string(), site)]),
+    auditor: None,
+    auth: None,
+    insecure_skip_token_verification,
+    metrics: None,
+  }
+}
+
+async fn whoami(user: AuthUser) -> String {
+  user.id
+}
+
+/// '/whoami' for any user and '/admin' for administrators only
+fn router(site_backend: SiteBackend) -> Router {
+  Router::new()
+    .route("/whoami", get(whoami))
+    .route(
+      "/admin",
+      get(whoami).route_layer(middleware::from_fn_with_state(
+        ADMIN_ROLES,
+        require_roles,
+      )),
+    )
+    .route_layer(middleware::from_fn(authenticate))
+    .layer(Extension(Arc::new(site_backend)))
+}
+
+async fn get_status(
+  router: &Router,
+  uri: &str,
+  token_opt: Option<&str>,
+) -> StatusCode {
+  let mut request_builder = Request::builder().uri(uri);
+
+  if let Some(token) = token_opt {
+    request_builder =
+      request_builder.header("authorization", format!("Bearer {}", token));
+  }
+
+  router
+    .clone()
+    .oneshot(request_builder.body(Body::empty()).unwrap())
+    .await
+    .unwrap()
+    .status()
+}
+
+#[tokio::test]
+async fn test_authenticate() {
+  let dir = test_dir("authenticate");
+
+  let site_backend = SiteBackend::new(
+    &configuration(&dir, Some(auth_config(&dir)), false),
+    "alps",
+  )
+  .await
+  .unwrap();
+
+  let router = router(site_backend);
+
+  assert_eq!(
+    get_status(&router, "/whoami", Some(&valid_token(&["zinal"]))).await,
+    StatusCode::OK
+  );
+  assert_eq!(
+    get_status(&router, "/whoami", None).await,
+    StatusCode::UNAUTHORIZED
+  );
+  assert_eq!(
+    get_status(&router, "/whoami", Some("not-a-token")).await,
+    StatusCode::UNAUTHORIZED
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_require_roles() {
+  let dir = test_dir("roles");
+
+  let site_backend = SiteBackend::new(
+    &configuration(&dir, Some(auth_config(&dir)), false),
+    "alps",
+  )
+  .await
+  .unwrap();
+
+  let router = router(site_backend);
+
+  assert_eq!(
+    get_status(&router, "/admin", Some(&valid_token(&["zinal"]))).await,
+    StatusCode::FORBIDDEN
+  );
+  assert_eq!(
+    get_status(&router, "/admin", Some(&valid_token(&["pa_admin"]))).await,
+    StatusCode::OK
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_unverified_site_needs_opt_in() {
+  let dir = test_dir("insecure");
+
+  assert!(
+    SiteBackend::new(&configuration(&dir, None, false), "alps")
+      .await
+      .is_err()
+  );
+
+  let site_backend = SiteBackend::new(&configuration(&dir, None, true), "alps")
+    .await
+    .unwrap();
+
+  let router = router(site_backend);
+
+  // Token signed with an unknown key claiming to be an administrator
+  let token = token(
+    Algorithm::HS256,
+    None,
+    &claims(&["pa_admin"]),
+    b"another-secret",
+  );
+
+  assert_eq!(
+    get_status(&router, "/whoami", Some(&token)).await,
+    StatusCode::OK
+  );
+  assert_eq!(
+    get_status(&router, "/admin", Some(&token)).await,
+    StatusCode::FORBIDDEN
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
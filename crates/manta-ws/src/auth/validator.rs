@@ -0,0 +1,202 @@
+use std::{
+  str::FromStr,
+  sync::RwLock,
+  time::{Duration, Instant},
+};
+
+use jsonwebtoken::{
+  Algorithm, DecodingKey, Validation, decode, decode_header, jwk::JwkSet,
+};
+use manta_backend_dispatcher::error::Error;
+use serde_json::Value;
+
+use crate::{
+  auth::AuthUser, common::config::types::AuthConfig,
+  jwt_utils::get_claims_from_jwt_token,
+};
+
+/// Minimum time between two downloads of the JWKS when a token is signed with
+/// an unknown key
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Validates the JWT tokens sent to the API against the keys of the identity
+/// provider
+pub struct TokenValidator {
+  config: Option<AuthConfig>,
+  root_cert: Vec<u8>,
+  jwks: RwLock<JwkSet>,
+  last_refresh: RwLock<Option<Instant>>,
+}
+
+impl TokenValidator {
+  /// Loads the JWKS from the file or URL in the configuration. The root
+  /// certificate is used to connect to the JWKS URL
+  pub async fn new(
+    config: Option<AuthConfig>,
+    root_cert: Vec<u8>,
+  ) -> Result<Self, Error> {
+    let validator = TokenValidator {
+      config,
+      root_cert,
+      jwks: RwLock::new(JwkSet { keys: Vec::new() }),
+      last_refresh: RwLock::new(None),
+    };
+
+    if let Some(config) = &validator.config {
+      let jwks = fetch_jwks(config, &validator.root_cert).await?;
+      *validator.jwks.write().unwrap() = jwks;
+      *validator.last_refresh.write().unwrap() = Some(Instant::now());
+    }
+
+    Ok(validator)
+  }
+
+  /// True if tokens are only decoded because there is no auth configuration
+  pub fn is_unverified(&self) -> bool {
+    self.config.is_none()
+  }
+
+  /// Checks the signature, expiration, issuer and audience of the token and
+  /// returns the user owning it. Without auth configuration the token is only
+  /// decoded and the user gets no roles
+  pub async fn validate(&self, token: &str) -> Result<AuthUser, Error> {
+    let Some(config) = &self.config else {
+      let claims = get_claims_from_jwt_token(token).map_err(unauthorized)?;
+
+      if claims["exp"]
+        .as_i64()
+        .is_some_and(|exp| exp < chrono::Utc::now().timestamp())
+      {
+        return Err(unauthorized("token expired"));
+      }
+
+      // Anyone can forge the claims of a token which signature is not checked
+      return Ok(AuthUser {
+        roles: Vec::new(),
+        ..AuthUser::new(token, &claims, "/realm_access/roles")
+      });
+    };
+
+    let header = decode_header(token).map_err(unauthorized)?;
+
+    let Some(kid) = header.kid else {
+      return Err(unauthorized("token has no 'kid' header"));
+    };
+
+    // Keys may have been rotated by the identity provider
+    if self.find_key(&kid).is_none() && self.refresh_allowed() {
+      tracing::info!("Key '{}' not found in JWKS, reloading it", kid);
+
+      let jwks = fetch_jwks(config, &self.root_cert).await?;
+      *self.jwks.write().unwrap() = jwks;
+      *self.last_refresh.write().unwrap() = Some(Instant::now());
+    }
+
+    let Some((decoding_key, key_algorithm_opt)) = self.find_key(&kid) else {
+      return Err(unauthorized(format!("key '{}' not found in JWKS", kid)));
+    };
+
+    // The algorithm in the token header is only accepted if the key or the
+    // configuration allow it
+    let algorithm_vec = match key_algorithm_opt {
+      Some(key_algorithm) => vec![key_algorithm],
+      None => config.algorithms.clone(),
+    };
+
+    if !algorithm_vec.contains(&header.alg) {
+      return Err(unauthorized(format!(
+        "token algorithm '{:?}' not allowed for key '{}'",
+        header.alg, kid
+      )));
+    }
+
+    let mut validation = Validation::new(header.alg);
+    validation.algorithms = algorithm_vec;
+
+    if let Some(issuer) = &config.issuer {
+      validation.set_issuer(&[issuer]);
+    }
+
+    match &config.audience {
+      Some(audience) => validation.set_audience(&[audience]),
+      None => validation.validate_aud = false,
+    }
+
+    let claims = decode::<Value>(token, &decoding_key, &validation)
+      .map_err(unauthorized)?
+      .claims;
+
+    Ok(AuthUser::new(token, &claims, &config.roles_claim))
+  }
+
+  /// Returns the key and, if the JWKS has it, the algorithm of the key
+  fn find_key(&self, kid: &str) -> Option<(DecodingKey, Option<Algorithm>)> {
+    let jwks = self.jwks.read().unwrap();
+
+    let jwk = jwks.find(kid)?;
+
+    let decoding_key = DecodingKey::from_jwk(jwk).ok()?;
+
+    let algorithm_opt =
+      jwk.common.key_algorithm.as_ref().and_then(|key_algorithm| {
+        Algorithm::from_str(&key_algorithm.to_string()).ok()
+      });
+
+    Some((decoding_key, algorithm_opt))
+  }
+
+  fn refresh_allowed(&self) -> bool {
+    self
+      .last_refresh
+      .read()
+      .unwrap()
+      .is_none_or(|last_refresh| last_refresh.elapsed() > JWKS_REFRESH_INTERVAL)
+  }
+}
+
+/// Reads the JWKS from the local file if configured, otherwise downloads it
+async fn fetch_jwks(
+  config: &AuthConfig,
+  root_cert: &[u8],
+) -> Result<JwkSet, Error> {
+  if let Some(jwks_file) = &config.jwks_file {
+    let jwks_content = std::fs::read_to_string(jwks_file)?;
+
+    return Ok(serde_json::from_str(&jwks_content)?);
+  }
+
+  let Some(jwks_url) = &config.jwks_url else {
+    return Err(Error::Message(
+      "Auth configuration needs either 'jwks_url' or 'jwks_file'".to_string(),
+    ));
+  };
+
+  let mut client_builder = reqwest::Client::builder();
+
+  if let Ok(certificate) = reqwest::Certificate::from_pem(root_cert) {
+    client_builder = client_builder.add_root_certificate(certificate);
+  }
+
+  let client = client_builder
+    .build()
+    .map_err(|e| Error::Message(e.to_string()))?;
+
+  client
+    .get(jwks_url)
+    .send()
+    .await
+    .and_then(|response| response.error_for_status())
+    .map_err(|e| {
+      Error::Message(format!("Could not fetch JWKS from '{}': {}", jwks_url, e))
+    })?
+    .json::<JwkSet>()
+    .await
+    .map_err(|e| Error::Message(format!("Could not parse JWKS: {}", e)))
+}
+
+fn unauthorized(detail: impl ToString) -> Error {
+  Error::Unauthorized {
+    service: "manta-ws".to_string(),
+    detail: detail.to_string(),
+  }
+}
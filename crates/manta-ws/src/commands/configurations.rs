@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  extract::Query,
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error, interfaces::cfs::CfsTrait,
+  types::cfs::cfs_configuration_response::CfsConfigurationResponse,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConfigurationQueryParams {
+  name: Option<String>,
+  /// Glob pattern, eg 'tasna-*'
+  pattern: Option<String>,
+  group: Option<String>,
+  limit: Option<u8>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/configurations",
+    params(ConfigurationQueryParams),
+    responses(
+        (status = 200, description = "CFS configurations related to the groups the user has access to", body = Vec<CfsConfigurationResponse>),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_configurations(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Query(params): Query<ConfigurationQueryParams>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  match get_and_filter_configurations(&site_backend, auth_token, &params).await
+  {
+    Ok(configuration_vec) => {
+      (StatusCode::OK, Json(configuration_vec)).into_response()
+    }
+    Err(e) => backend_error_response(e),
+  }
+}
+
+async fn get_and_filter_configurations(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  params: &ConfigurationQueryParams,
+) -> Result<Vec<CfsConfigurationResponse>, Error> {
+  let group_vec = authorization::get_groups_available(
+    &site_backend.backend,
+    auth_token,
+    params.group.as_deref(),
+  )
+  .await?;
+
+  site_backend
+    .backend
+    .get_and_filter_configuration(
+      auth_token,
+      &site_backend.site.shasta_base_url,
+      &site_backend.shasta_root_cert,
+      params.name.as_deref(),
+      params.pattern.as_deref(),
+      &group_vec,
+      params.limit.as_ref(),
+    )
+    .await
+}
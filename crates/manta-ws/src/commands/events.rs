@@ -0,0 +1,188 @@
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+  Extension,
+  extract::{
+    Query, WebSocketUpgrade,
+    ws::{Message, Utf8Bytes, WebSocket},
+  },
+  response::{
+    IntoResponse, Response,
+    sse::{self, KeepAlive, Sse},
+  },
+};
+use manta_backend_dispatcher::{
+  error::Error, interfaces::hsm::group::GroupTrait,
+};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use utoipa::IntoParams;
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  events::{Event, EventFilter},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventQueryParams {
+  /// Only events related to this group
+  group: Option<String>,
+  /// Comma separated list of event types (node-state, cfs-component,
+  /// cfs-session, power-transition)
+  types: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/events",
+    params(EventQueryParams),
+    responses(
+        (status = 200, description = "Server-sent events stream of changes to nodes, CFS components, CFS sessions and PCS transitions related to the groups the user has access to", body = Event, content_type = "text/event-stream"),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to the group")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_events(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Query(params): Query<EventQueryParams>,
+) -> Response {
+  let filter = match get_event_filter(&site_backend, &user, &params).await {
+    Ok(filter) => filter,
+    Err(e) => return backend_error_response(e),
+  };
+
+  let subscription = site_backend.events.subscribe(&user);
+
+  let stream =
+    futures::stream::unfold((subscription, filter), |mut state| async move {
+      let (subscription, filter) = &mut state;
+
+      let event = recv_event(&mut subscription.receiver, filter).await?;
+
+      let sse_event = sse::Event::default()
+        .event(event.change.kind())
+        .json_data(&event)
+        .unwrap_or_else(|_| sse::Event::default().comment("invalid event"));
+
+      Some((Ok::<_, Infallible>(sse_event), state))
+    });
+
+  Sse::new(stream)
+    .keep_alive(KeepAlive::default())
+    .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/events/ws",
+    params(EventQueryParams),
+    responses(
+        (status = 101, description = "Websocket sending the same events as '/events' as JSON text messages"),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to the group")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn ws_events(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Query(params): Query<EventQueryParams>,
+  ws: WebSocketUpgrade,
+) -> Response {
+  let filter = match get_event_filter(&site_backend, &user, &params).await {
+    Ok(filter) => filter,
+    Err(e) => return backend_error_response(e),
+  };
+
+  ws.on_upgrade(move |socket| send_events(socket, site_backend, user, filter))
+}
+
+/// Forwards the events to the websocket until the client disconnects
+async fn send_events(
+  mut socket: WebSocket,
+  site_backend: Arc<SiteBackend>,
+  user: AuthUser,
+  filter: EventFilter,
+) {
+  let mut subscription = site_backend.events.subscribe(&user);
+
+  loop {
+    tokio::select! {
+      message_opt = socket.recv() => match message_opt {
+        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+        // Messages from the client are ignored
+        Some(Ok(_)) => {}
+      },
+      event_opt = recv_event(&mut subscription.receiver, &filter) => {
+        let Some(event) = event_opt else {
+          break;
+        };
+
+        let Ok(event_json) = serde_json::to_string(&event) else {
+          continue;
+        };
+
+        if socket
+          .send(Message::Text(Utf8Bytes::from(event_json)))
+          .await
+          .is_err()
+        {
+          break;
+        }
+      }
+    }
+  }
+}
+
+/// Next event matching the filter, none once the site stops sending events
+async fn recv_event(
+  receiver: &mut tokio::sync::broadcast::Receiver<Event>,
+  filter: &EventFilter,
+) -> Option<Event> {
+  loop {
+    match receiver.recv().await {
+      Ok(event) if filter.matches(&event) => return Some(event),
+      Ok(_) => {}
+      Err(RecvError::Lagged(skipped)) => {
+        tracing::warn!("Event subscriber too slow, {} events dropped", skipped)
+      }
+      Err(RecvError::Closed) => return None,
+    }
+  }
+}
+
+/// Groups and nodes the user has access to, restricted to the group in the
+/// request if any
+async fn get_event_filter(
+  site_backend: &SiteBackend,
+  user: &AuthUser,
+  params: &EventQueryParams,
+) -> Result<EventFilter, Error> {
+  let group_vec = authorization::get_groups_available(
+    &site_backend.backend,
+    &user.token,
+    params.group.as_deref(),
+  )
+  .await?;
+
+  let xname_vec = site_backend
+    .backend
+    .get_member_vec_from_group_name_vec(&user.token, group_vec.clone())
+    .await?;
+
+  Ok(EventFilter {
+    group_set: group_vec.into_iter().collect(),
+    xname_set: xname_vec.into_iter().collect(),
+    kind_vec: params.types.as_ref().map(|types| {
+      types
+        .split(',')
+        .map(|kind| kind.trim().to_string())
+        .collect()
+    }),
+  })
+}
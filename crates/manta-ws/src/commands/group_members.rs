@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  extract::Path,
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error, interfaces::hsm::group::GroupTrait,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct GroupMembersRequest {
+  xnames: Vec<String>,
+}
+
+/// Adds nodes to a group. Returns the members of the group
+#[utoipa::path(
+    post,
+    path = "/group/{group}/members",
+    params(
+        ("group" = String, Path, description = "Group name")
+    ),
+    request_body = GroupMembersRequest,
+    responses(
+        (status = 200, description = "Members of the group", body = Vec<String>),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to the group or nodes")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn post_group_members(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Path(group): Path<String>,
+  Json(request): Json<GroupMembersRequest>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  if request.xnames.is_empty() {
+    return backend_error_response(Error::BadRequest {
+      service: "manta-ws".to_string(),
+      detail: "List of xnames is empty".to_string(),
+    });
+  }
+
+  let xname_vec: Vec<&str> =
+    request.xnames.iter().map(String::as_str).collect();
+
+  let result = async {
+    authorization::get_groups_available(
+      &site_backend.backend,
+      auth_token,
+      Some(group.as_str()),
+    )
+    .await?;
+
+    authorization::validate_target_hsm_members(
+      &site_backend.backend,
+      auth_token,
+      &request.xnames,
+    )
+    .await?;
+
+    site_backend
+      .backend
+      .add_members_to_group(auth_token, &group, xname_vec.clone())
+      .await
+  }
+  .await;
+
+  site_backend
+    .audit(auth_token, "add nodes to group", &xname_vec, result.is_ok())
+    .await;
+
+  match result {
+    Ok(member_vec) => (StatusCode::OK, Json(member_vec)).into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+/// Removes a node from a group
+#[utoipa::path(
+    delete,
+    path = "/group/{group}/members/{xname}",
+    params(
+        ("group" = String, Path, description = "Group name"),
+        ("xname" = String, Path, description = "Node xname")
+    ),
+    responses(
+        (status = 200, description = "Node removed from the group"),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to the group")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_group_member(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Path((group, xname)): Path<(String, String)>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  let result = async {
+    authorization::get_groups_available(
+      &site_backend.backend,
+      auth_token,
+      Some(group.as_str()),
+    )
+    .await?;
+
+    site_backend
+      .backend
+      .delete_member_from_group(auth_token, &group, &xname)
+      .await
+  }
+  .await;
+
+  site_backend
+    .audit(
+      auth_token,
+      "remove nodes from group",
+      &[&xname],
+      result.is_ok(),
+    )
+    .await;
+
+  match result {
+    Ok(()) => StatusCode::OK.into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{
+    apply_hw_cluster_pin::ApplyHwClusterPin, hsm::group::GroupTrait,
+  },
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct HwClusterRequest {
+  target: String,
+  parent: String,
+  /// Hardware components and quantities the target group must have, eg
+  /// 'a100:4:epyc:10'
+  pattern: String,
+  /// Validates the request without changing the groups
+  #[serde(default)]
+  dry_run: bool,
+  #[serde(default)]
+  create_target_group: bool,
+  #[serde(default)]
+  delete_empty_parent_group: bool,
+}
+
+/// Members of the target and parent groups once the request is processed
+#[derive(Serialize, Debug, ToSchema)]
+pub struct HwClusterResponse {
+  target: Vec<String>,
+  parent: Vec<String>,
+}
+
+enum HwClusterOperation {
+  Pin,
+  Unpin,
+}
+
+/// Moves nodes between the parent and target groups to match the hardware
+/// requested, keeping as many nodes in the target group as possible
+#[utoipa::path(
+    post,
+    path = "/hw-cluster/pin",
+    request_body = HwClusterRequest,
+    responses(
+        (status = 200, description = "Members of the groups", body = HwClusterResponse),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to the groups")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn post_hw_cluster_pin(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Json(request): Json<HwClusterRequest>,
+) -> Response {
+  apply_hw_cluster(&site_backend, user, request, HwClusterOperation::Pin).await
+}
+
+/// Moves nodes between the parent and target groups to match the hardware
+/// requested, regardless of the nodes currently in the target group
+#[utoipa::path(
+    post,
+    path = "/hw-cluster/unpin",
+    request_body = HwClusterRequest,
+    responses(
+        (status = 200, description = "Members of the groups", body = HwClusterResponse),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to the groups")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn post_hw_cluster_unpin(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Json(request): Json<HwClusterRequest>,
+) -> Response {
+  apply_hw_cluster(&site_backend, user, request, HwClusterOperation::Unpin)
+    .await
+}
+
+async fn apply_hw_cluster(
+  site_backend: &SiteBackend,
+  user: AuthUser,
+  request: HwClusterRequest,
+  operation: HwClusterOperation,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  let result =
+    apply_hw_cluster_pattern(site_backend, auth_token, &request, &operation)
+      .await;
+
+  if !request.dry_run {
+    let xname_vec = result
+      .as_ref()
+      .map(|response| response.target.clone())
+      .unwrap_or_default();
+
+    site_backend
+      .audit(
+        auth_token,
+        "apply hardware cluster",
+        &xname_vec.iter().map(String::as_str).collect::<Vec<_>>(),
+        result.is_ok(),
+      )
+      .await;
+  }
+
+  match result {
+    Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+async fn apply_hw_cluster_pattern(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  request: &HwClusterRequest,
+  operation: &HwClusterOperation,
+) -> Result<HwClusterResponse, Error> {
+  let backend = &site_backend.backend;
+
+  authorization::get_groups_available(
+    backend,
+    auth_token,
+    Some(request.target.as_str()),
+  )
+  .await?;
+
+  authorization::get_groups_available(
+    backend,
+    auth_token,
+    Some(request.parent.as_str()),
+  )
+  .await?;
+
+  let shasta_base_url = &site_backend.site.shasta_base_url;
+  let shasta_root_cert = &site_backend.shasta_root_cert;
+
+  match operation {
+    HwClusterOperation::Pin => {
+      backend
+        .apply_hw_cluster_pin(
+          auth_token,
+          shasta_base_url,
+          shasta_root_cert,
+          &request.target,
+          &request.parent,
+          &request.pattern,
+          !request.dry_run,
+          request.create_target_group,
+          request.delete_empty_parent_group,
+        )
+        .await?
+    }
+    HwClusterOperation::Unpin => {
+      backend
+        .apply_hw_cluster_unpin(
+          auth_token,
+          shasta_base_url,
+          shasta_root_cert,
+          &request.target,
+          &request.parent,
+          &request.pattern,
+          !request.dry_run,
+          request.create_target_group,
+          request.delete_empty_parent_group,
+        )
+        .await?
+    }
+  }
+
+  // Parent group may have been deleted if it is empty
+  let parent_member_vec =
+    match backend.get_group(auth_token, &request.parent).await {
+      Ok(group) => group.get_members(),
+      Err(Error::NotFound { .. }) => Vec::new(),
+      Err(e) => return Err(e),
+    };
+
+  Ok(HwClusterResponse {
+    target: backend
+      .get_group(auth_token, &request.target)
+      .await?
+      .get_members(),
+    parent: parent_member_vec,
+  })
+}
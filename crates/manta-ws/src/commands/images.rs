@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  extract::Query,
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error, interfaces::get_images_and_details::GetImagesAndDetailsTrait,
+  types::ims::Image,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImageQueryParams {
+  group: Option<String>,
+  id: Option<String>,
+  limit: Option<u8>,
+}
+
+/// IMS image with the CFS configuration used to build it and the groups
+/// related to it
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ImageDetails {
+  image: Image,
+  configuration: String,
+  groups: String,
+  /// Image used to boot nodes
+  boot_image: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/images",
+    params(ImageQueryParams),
+    responses(
+        (status = 200, description = "Images related to the groups the user has access to", body = Vec<ImageDetails>),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_images(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Query(params): Query<ImageQueryParams>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  match get_image_details(&site_backend, auth_token, &params).await {
+    Ok(image_details_vec) => {
+      (StatusCode::OK, Json(image_details_vec)).into_response()
+    }
+    Err(e) => backend_error_response(e),
+  }
+}
+
+async fn get_image_details(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  params: &ImageQueryParams,
+) -> Result<Vec<ImageDetails>, Error> {
+  let group_vec = authorization::get_groups_available(
+    &site_backend.backend,
+    auth_token,
+    params.group.as_deref(),
+  )
+  .await?;
+
+  let image_details_vec = site_backend
+    .backend
+    .get_images_and_details(
+      auth_token,
+      &site_backend.site.shasta_base_url,
+      &site_backend.shasta_root_cert,
+      &group_vec,
+      params.id.as_ref(),
+      params.limit.as_ref(),
+    )
+    .await?
+    .into_iter()
+    .map(|(image, configuration, groups, boot_image)| ImageDetails {
+      image,
+      configuration,
+      groups,
+      boot_image,
+    })
+    .collect();
+
+  Ok(image_details_vec)
+}
@@ -0,0 +1,333 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  extract::{Path, Query, State},
+  response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use hyper::StatusCode;
+use manta_backend_dispatcher::error::Error;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+  auth::{ADMIN_ROLES, AuthUser},
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+  jobs::{Job, JobLogEntry, JobOperation, JobRequest, JobStatus, JobStore},
+  jwt_utils::get_claims_from_jwt_token,
+};
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JobQueryParams {
+  status: Option<JobStatus>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/jobs",
+    request_body = JobRequest,
+    responses(
+        (status = 201, description = "Job scheduled", body = Job),
+        (status = BAD_REQUEST, description = "Time window not valid or later than the token expiry"),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User can't access some of the nodes")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn post_job(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  State(job_store): State<Arc<JobStore>>,
+  user: AuthUser,
+  Json(job_request): Json<JobRequest>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  let not_before = job_request.not_before.unwrap_or_else(Utc::now);
+
+  if job_request
+    .not_after
+    .is_some_and(|not_after| not_after <= not_before)
+  {
+    return backend_error_response(Error::BadRequest {
+      service: "manta-ws".to_string(),
+      detail: "'not_after' must be later than 'not_before'".to_string(),
+    });
+  }
+
+  // The job runs with the token of the user, it can't be scheduled past the
+  // token expiry
+  let token_exp_opt = get_claims_from_jwt_token(auth_token)
+    .ok()
+    .and_then(|claims| claims["exp"].as_i64())
+    .and_then(|exp| DateTime::from_timestamp(exp, 0));
+
+  if let Some(token_exp) = token_exp_opt.filter(|token_exp| {
+    not_before >= *token_exp
+      || job_request
+        .not_after
+        .is_some_and(|not_after| not_after > *token_exp)
+  }) {
+    return backend_error_response(Error::BadRequest {
+      service: "manta-ws".to_string(),
+      detail: format!(
+        "Job time window must end before the authentication token expires at {}",
+        token_exp.to_rfc3339()
+      ),
+    });
+  }
+
+  let xname_vec: Vec<String> = job_request.operation.xnames().to_vec();
+
+  if xname_vec.is_empty()
+    && !matches!(job_request.operation, JobOperation::SatFile { .. })
+  {
+    return backend_error_response(Error::BadRequest {
+      service: "manta-ws".to_string(),
+      detail: "List of xnames is empty".to_string(),
+    });
+  }
+
+  // Jobs change the nodes later on, the user must have access to them now
+  // like with any other endpoint changing the nodes
+  if let Err(e) = authorization::validate_target_hsm_members(
+    &site_backend.backend,
+    auth_token,
+    &xname_vec,
+  )
+  .await
+  {
+    return backend_error_response(e);
+  }
+
+  let job_rslt = job_store.create(
+    &user.id,
+    &site_backend.site_name,
+    auth_token,
+    job_request,
+  );
+
+  site_backend
+    .audit(
+      auth_token,
+      "schedule job",
+      &xname_vec.iter().map(String::as_str).collect::<Vec<_>>(),
+      job_rslt.is_ok(),
+    )
+    .await;
+
+  match job_rslt {
+    Ok(job) => (StatusCode::CREATED, Json(job)).into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs",
+    params(JobQueryParams),
+    responses(
+        (status = 200, description = "Scheduled jobs of the user in the site without their logs, administrators get every job in the site", body = Vec<Job>),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_all_jobs(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  State(job_store): State<Arc<JobStore>>,
+  user: AuthUser,
+  Query(params): Query<JobQueryParams>,
+) -> Response {
+  // Logs are only returned by '/jobs/{id}/logs'
+  let job_vec: Vec<Job> = job_store
+    .list()
+    .into_iter()
+    .filter(|job| can_access_job(job, &user, &site_backend.site_name))
+    .filter(|job| params.status.is_none_or(|status| job.status == status))
+    .map(|job| Job {
+      logs: Vec::new(),
+      ..job
+    })
+    .collect();
+
+  (StatusCode::OK, Json(job_vec)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    params(
+        ("id" = u64, Path, description = "Job id")
+    ),
+    responses(
+        (status = 200, description = "Job without its logs", body = Job),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "Job scheduled by another user or for another site"),
+        (status = NOT_FOUND, description = "Job not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_job(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  State(job_store): State<Arc<JobStore>>,
+  user: AuthUser,
+  Path(id): Path<u64>,
+) -> Response {
+  match job_store.get(id) {
+    Some(job) if !can_access_job(&job, &user, &site_backend.site_name) => {
+      backend_error_response(job_forbidden(id))
+    }
+    Some(job) => (
+      StatusCode::OK,
+      Json(Job {
+        logs: Vec::new(),
+        ..job
+      }),
+    )
+      .into_response(),
+    None => backend_error_response(job_not_found(id)),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/logs",
+    params(
+        ("id" = u64, Path, description = "Job id")
+    ),
+    responses(
+        (status = 200, description = "Job logs", body = Vec<JobLogEntry>),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "Job scheduled by another user or for another site"),
+        (status = NOT_FOUND, description = "Job not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_job_logs(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  State(job_store): State<Arc<JobStore>>,
+  user: AuthUser,
+  Path(id): Path<u64>,
+) -> Response {
+  match job_store.get(id) {
+    Some(job) if !can_access_job(&job, &user, &site_backend.site_name) => {
+      backend_error_response(job_forbidden(id))
+    }
+    Some(job) => (StatusCode::OK, Json(job.logs)).into_response(),
+    None => backend_error_response(job_not_found(id)),
+  }
+}
+
+/// Cancels a job. Only jobs waiting for their time window can be cancelled
+#[utoipa::path(
+    delete,
+    path = "/jobs/{id}",
+    params(
+        ("id" = u64, Path, description = "Job id")
+    ),
+    responses(
+        (status = 200, description = "Job cancelled", body = Job),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "Job scheduled by another user or for another site"),
+        (status = CONFLICT, description = "Job is not pending"),
+        (status = NOT_FOUND, description = "Job not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_job(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  State(job_store): State<Arc<JobStore>>,
+  user: AuthUser,
+  Path(id): Path<u64>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  match job_store.get(id) {
+    Some(job) if !can_access_job(&job, &user, &site_backend.site_name) => {
+      return backend_error_response(job_forbidden(id));
+    }
+    Some(_) => {}
+    None => return backend_error_response(job_not_found(id)),
+  }
+
+  let mut status = None;
+
+  let job_rslt = job_store.update(id, |job| {
+    status = Some(job.status);
+
+    if job.status == JobStatus::Pending {
+      job.status = JobStatus::Cancelled;
+      job.finished = Some(Utc::now());
+      job.log(format!("Cancelled by {}", user.id));
+    }
+  });
+
+  let job_rslt = match (job_rslt, status) {
+    (Ok(job), Some(JobStatus::Pending)) => Ok(job),
+    (Ok(job), _) => Err(Error::Conflict {
+      service: "manta-ws".to_string(),
+      resource: "job".to_string(),
+      id: id.to_string(),
+      detail: format!(
+        "Only pending jobs can be cancelled, job is {:?}",
+        job.status
+      ),
+    }),
+    (Err(e), _) => Err(e),
+  };
+
+  if let Ok(job) = &job_rslt {
+    site_backend
+      .audit(
+        auth_token,
+        "cancel job",
+        &job
+          .operation
+          .xnames()
+          .iter()
+          .map(String::as_str)
+          .collect::<Vec<_>>(),
+        true,
+      )
+      .await;
+  }
+
+  match job_rslt {
+    Ok(job) => (
+      StatusCode::OK,
+      Json(Job {
+        logs: Vec::new(),
+        ..job
+      }),
+    )
+      .into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+fn job_not_found(id: u64) -> Error {
+  Error::NotFound {
+    service: "manta-ws".to_string(),
+    resource: "job".to_string(),
+    id: id.to_string(),
+  }
+}
+
+fn job_forbidden(id: u64) -> Error {
+  Error::Forbidden {
+    service: "manta-ws".to_string(),
+    detail: format!("Job '{}' was scheduled by another user or site", id),
+  }
+}
+
+/// Users can only access the jobs they scheduled for the site of the request,
+/// administrators can access every job of the site
+fn can_access_job(job: &Job, user: &AuthUser, site_name: &str) -> bool {
+  job.site == site_name
+    && (job.owner == user.id || user.has_any_role(ADMIN_ROLES))
+}
+
+#[cfg(test)]
+mod tests;
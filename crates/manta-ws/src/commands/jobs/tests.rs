@@ -0,0 +1,78 @@
+use chrono::Utc;
+
+use crate::{
+  auth::AuthUser,
+  jobs::{Job, JobOperation, JobStatus},
+};
+
+use super::can_access_job;
+
+fn job(owner: &str, site: &str) -> Job {
+  let now = Utc::now();
+
+  Job {
+    id: 1,
+    owner: owner.to_string(),
+    site: site.to_string(),
+    operation: JobOperation::PowerOn {
+      xnames: vec!["x1000c0s0b0n0".to_string()],
+    },
+    status: JobStatus::Pending,
+    created: now,
+    not_before: now,
+    not_after: None,
+    started: None,
+    finished: None,
+    logs: Vec::new(),
+  }
+}
+
+fn user(id: &str, roles: &[&str]) -> AuthUser {
+  AuthUser {
+    id: id.to_string(),
+    name: id.to_string(),
+    roles: roles.iter().map(|role| role.to_string()).collect(),
+    token: "token".to_string(),
+  }
+}
+
+#[test]
+fn test_can_access_job_owner() {
+  assert!(can_access_job(
+    &job("alice", "alps"),
+    &user("alice", &["zinal"]),
+    "alps"
+  ));
+}
+
+#[test]
+fn test_can_access_job_another_user() {
+  assert!(!can_access_job(
+    &job("alice", "alps"),
+    &user("bob", &["zinal"]),
+    "alps"
+  ));
+}
+
+#[test]
+fn test_can_access_job_admin() {
+  assert!(can_access_job(
+    &job("alice", "alps"),
+    &user("bob", &["pa_admin"]),
+    "alps"
+  ));
+}
+
+#[test]
+fn test_can_access_job_another_site() {
+  assert!(!can_access_job(
+    &job("alice", "alps"),
+    &user("alice", &["zinal"]),
+    "prealps"
+  ));
+  assert!(!can_access_job(
+    &job("alice", "alps"),
+    &user("bob", &["pa_admin"]),
+    "prealps"
+  ));
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{bss::BootParametersTrait, pcs::PCSTrait},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct KernelParametersRequest {
+  xnames: Vec<String>,
+  /// Space separated list of kernel parameters, eg 'quiet console=ttyS0'
+  params: String,
+  /// Nodes whose kernel parameters changed are rebooted unless this is set
+  #[serde(default)]
+  do_not_reboot: bool,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct KernelParametersResponse {
+  /// Nodes whose kernel parameters changed
+  changed: Vec<String>,
+  /// Summary of the PCS power reset of the nodes changed
+  power_transition: Option<Value>,
+}
+
+enum KernelParametersOperation {
+  Apply,
+  Delete,
+}
+
+#[utoipa::path(
+    put,
+    path = "/kernel-parameters",
+    request_body = KernelParametersRequest,
+    responses(
+        (status = 200, description = "Kernel parameters applied", body = KernelParametersResponse),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to some nodes")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn apply_kernel_parameters(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Json(request): Json<KernelParametersRequest>,
+) -> Response {
+  update_kernel_parameters(
+    &site_backend,
+    user,
+    request,
+    KernelParametersOperation::Apply,
+  )
+  .await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/kernel-parameters",
+    request_body = KernelParametersRequest,
+    responses(
+        (status = 200, description = "Kernel parameters deleted", body = KernelParametersResponse),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to some nodes")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_kernel_parameters(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Json(request): Json<KernelParametersRequest>,
+) -> Response {
+  update_kernel_parameters(
+    &site_backend,
+    user,
+    request,
+    KernelParametersOperation::Delete,
+  )
+  .await
+}
+
+async fn update_kernel_parameters(
+  site_backend: &SiteBackend,
+  user: AuthUser,
+  request: KernelParametersRequest,
+  operation: KernelParametersOperation,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  if request.xnames.is_empty() {
+    return backend_error_response(Error::BadRequest {
+      service: "manta-ws".to_string(),
+      detail: "List of xnames is empty".to_string(),
+    });
+  }
+
+  let result =
+    update_boot_parameters(site_backend, auth_token, &request, &operation)
+      .await;
+
+  let command = match operation {
+    KernelParametersOperation::Apply => "apply kernel parameters",
+    KernelParametersOperation::Delete => "delete kernel parameters",
+  };
+
+  site_backend
+    .audit(
+      auth_token,
+      command,
+      &request
+        .xnames
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>(),
+      result.is_ok(),
+    )
+    .await;
+
+  match result {
+    Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+async fn update_boot_parameters(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  request: &KernelParametersRequest,
+  operation: &KernelParametersOperation,
+) -> Result<KernelParametersResponse, Error> {
+  let backend = &site_backend.backend;
+
+  authorization::validate_target_hsm_members(
+    backend,
+    auth_token,
+    &request.xnames,
+  )
+  .await?;
+
+  let boot_parameter_vec = backend
+    .get_bootparameters(auth_token, &request.xnames)
+    .await?;
+
+  let mut changed_xname_vec: Vec<String> = Vec::new();
+
+  for mut boot_parameter in boot_parameter_vec {
+    let changed = match operation {
+      KernelParametersOperation::Apply => {
+        boot_parameter.apply_kernel_params(&request.params)
+      }
+      KernelParametersOperation::Delete => {
+        boot_parameter.delete_kernel_params(&request.params)
+      }
+    };
+
+    if changed {
+      backend
+        .update_bootparameters(auth_token, &boot_parameter)
+        .await?;
+
+      changed_xname_vec.extend(boot_parameter.hosts);
+    }
+  }
+
+  changed_xname_vec.sort();
+  changed_xname_vec.dedup();
+
+  let power_transition =
+    if request.do_not_reboot || changed_xname_vec.is_empty() {
+      None
+    } else {
+      Some(
+        backend
+          .power_reset_sync(auth_token, &changed_xname_vec, true)
+          .await?,
+      )
+    };
+
+  Ok(KernelParametersResponse {
+    changed: changed_xname_vec,
+    power_transition,
+  })
+}
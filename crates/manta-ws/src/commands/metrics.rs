@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension,
+  http::{HeaderMap, header},
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::error::Error;
+
+use crate::{
+  common::{config::types::MantaConfiguration, site_backend::SiteBackend},
+  http_response::backend_error_response,
+  jwt_utils::get_auth_token,
+  metrics::{self, METRICS},
+};
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Metrics in Prometheus text format: nodes per group, state and role, CFS sessions per status, failed CFS components per group, BOS session templates and IMS images of the site, plus request latencies and backend errors. Inventory gauges are computed with the service token of the site ('metrics_token_file') and left out if the site has none", body = String, content_type = "text/plain"),
+        (status = UNAUTHORIZED, description = "Scrape token missing or not valid, only if 'metrics.scrape_token_file' is configured")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_metrics(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  headers: HeaderMap,
+) -> Response {
+  if let Err(e) = validate_scrape_token(&site_backend.configuration, &headers) {
+    return backend_error_response(e);
+  }
+
+  let mut out = String::new();
+
+  if let Some(metrics_token_file) = &site_backend.site.metrics_token_file {
+    let inventory_rslt = match read_token_file(metrics_token_file) {
+      Ok(metrics_token) => {
+        metrics::render_inventory(&site_backend, &metrics_token, &mut out).await
+      }
+      Err(e) => Err(e),
+    };
+
+    if let Err(e) = inventory_rslt {
+      return backend_error_response(e);
+    }
+  }
+
+  METRICS.render(&mut out);
+
+  (
+    StatusCode::OK,
+    [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+    out,
+  )
+    .into_response()
+}
+
+/// Checks the request has the scrape token, if any is configured
+pub fn validate_scrape_token(
+  configuration: &MantaConfiguration,
+  headers: &HeaderMap,
+) -> Result<(), Error> {
+  let Some(scrape_token_file) = configuration
+    .metrics
+    .as_ref()
+    .and_then(|metrics| metrics.scrape_token_file.as_ref())
+  else {
+    return Ok(());
+  };
+
+  let scrape_token = read_token_file(scrape_token_file)?;
+
+  if get_auth_token(headers) != Some(scrape_token.as_str()) {
+    return Err(Error::Unauthorized {
+      service: "manta-ws".to_string(),
+      detail: "scrape token missing or not valid".to_string(),
+    });
+  }
+
+  Ok(())
+}
+
+/// Reads a token from a file. Read on every scrape so tokens can be rotated
+/// without restarting the server
+fn read_token_file(path: &str) -> Result<String, Error> {
+  std::fs::read_to_string(path)
+    .map(|token| token.trim().to_string())
+    .map_err(|e| {
+      Error::Message(format!(
+        "Could not read token file '{}'. Reason:\n{}",
+        path, e
+      ))
+    })
+}
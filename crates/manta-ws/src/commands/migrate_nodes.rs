@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  extract::{Path, Query},
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error, interfaces::hsm::group::GroupTrait, types::Group,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NodeMigrationQueryParams {
+  /// Comma separated list of xnames
+  ids: String,
+  /// Create the target group if it does not exist
+  #[serde(default)]
+  create_hsm_group: bool,
+}
+
+/// Members of the target and parent groups after the migration
+#[derive(Serialize, Debug, ToSchema)]
+pub struct NodeMigrationResponse {
+  target: Vec<String>,
+  parent: Vec<String>,
+}
+
+/// Moves nodes from the parent group to the target group
+#[utoipa::path(
+    put,
+    path = "/node-migration/target/{target}/parent/{parent}",
+    params(
+        ("target" = String, Path, description = "Group the nodes are moved to"),
+        ("parent" = String, Path, description = "Group the nodes are moved from"),
+        NodeMigrationQueryParams
+    ),
+    responses(
+        (status = 200, description = "Members of the groups after the migration", body = NodeMigrationResponse),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing"),
+        (status = FORBIDDEN, description = "User has no access to the groups or nodes")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn node_migration(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Path((target, parent)): Path<(String, String)>,
+  Query(params): Query<NodeMigrationQueryParams>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  let xname_vec: Vec<String> = params
+    .ids
+    .split(',')
+    .map(str::trim)
+    .filter(|xname| !xname.is_empty())
+    .map(str::to_string)
+    .collect();
+
+  if xname_vec.is_empty() {
+    return backend_error_response(Error::BadRequest {
+      service: "manta-ws".to_string(),
+      detail: "List of xnames is empty".to_string(),
+    });
+  }
+
+  tracing::info!(
+    "Migrate nodes '{}' from parent '{}' to target '{}'",
+    xname_vec.join(","),
+    parent,
+    target
+  );
+
+  let result = migrate_group_members(
+    &site_backend,
+    auth_token,
+    &target,
+    &parent,
+    &xname_vec,
+    params.create_hsm_group,
+  )
+  .await;
+
+  site_backend
+    .audit(
+      auth_token,
+      "migrate nodes",
+      &xname_vec.iter().map(String::as_str).collect::<Vec<_>>(),
+      result.is_ok(),
+    )
+    .await;
+
+  match result {
+    Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+async fn migrate_group_members(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  target: &str,
+  parent: &str,
+  xname_vec: &[String],
+  create_hsm_group: bool,
+) -> Result<NodeMigrationResponse, Error> {
+  let backend = &site_backend.backend;
+
+  authorization::get_groups_available(backend, auth_token, Some(parent))
+    .await?;
+
+  authorization::validate_target_hsm_members(backend, auth_token, xname_vec)
+    .await?;
+
+  match backend.get_group(auth_token, target).await {
+    Ok(_) => {
+      authorization::get_groups_available(backend, auth_token, Some(target))
+        .await?;
+    }
+    Err(Error::NotFound { .. }) if create_hsm_group => {
+      tracing::info!("Group '{}' does not exist, creating it", target);
+
+      backend
+        .add_group(
+          auth_token,
+          Group {
+            label: target.to_string(),
+            description: None,
+            tags: None,
+            members: None,
+            exclusive_group: Some("false".to_string()),
+          },
+        )
+        .await?;
+    }
+    Err(e) => return Err(e),
+  }
+
+  let (mut target_member_vec, mut parent_member_vec) = backend
+    .migrate_group_members(
+      auth_token,
+      target,
+      parent,
+      xname_vec.iter().map(String::as_str).collect(),
+    )
+    .await?;
+
+  target_member_vec.sort();
+  parent_member_vec.sort();
+
+  Ok(NodeMigrationResponse {
+    target: target_member_vec,
+    parent: parent_member_vec,
+  })
+}
@@ -1 +1,12 @@
+pub mod configurations;
+pub mod events;
+pub mod group_members;
+pub mod hw_cluster;
+pub mod images;
+pub mod jobs;
+pub mod kernel_parameters;
+pub mod metrics;
+pub mod migrate_nodes;
 pub mod redfish;
+pub mod sat_file;
+pub mod templates;
@@ -1,53 +1,35 @@
+use std::sync::Arc;
+
 use crate::{
-  common::{self, config::types::MantaConfiguration},
-  manta_backend_dispatcher::StaticBackendDispatcher,
+  auth::AuthUser, common::site_backend::SiteBackend,
+  http_response::backend_error_status,
 };
 use axum::{
-  Json,
+  Extension, Json,
   extract::Path,
   response::{IntoResponse, Response},
 };
-use hyper::{HeaderMap, StatusCode};
+use hyper::StatusCode;
 use manta_backend_dispatcher::{
   interfaces::hsm::redfish_endpoint::RedfishEndpointTrait,
   types::hsm::inventory::{RedfishEndpoint, RedfishEndpointArray},
 };
 
-pub async fn get_all_redfish(headers: HeaderMap) -> Response {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
+#[utoipa::path(
+    get,
+    path = "/redfish",
+    responses(
+        (status = 200, description = "All redfish endpoints", body = RedfishEndpointArray)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_all_redfish(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+) -> Response {
+  let backend = &site_backend.backend;
 
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let boot_parameters_rslt =
     backend.get_all_redfish_endpoints(auth_token).await;
@@ -57,51 +39,31 @@ pub async fn get_all_redfish(headers: HeaderMap) -> Response {
       return (StatusCode::OK, Json(boot_parameters_vec)).into_response();
     }
     Err(e) => {
-      return (StatusCode::INTERNAL_SERVER_ERROR, Json(e.to_string()))
-        .into_response();
+      return (backend_error_status(&e), Json(e.to_string())).into_response();
     }
   }
 }
 
+#[utoipa::path(
+    get,
+    path = "/redfish/{xname}",
+    params(
+        ("xname" = String, Path, description = "Redfish endpoint xname")
+    ),
+    responses(
+        (status = 200, description = "Redfish endpoint", body = RedfishEndpointArray)
+    ),
+    security(("bearer_auth" = []))
+)]
 #[axum::debug_handler]
 pub async fn get_redfish(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(xname): Path<String>,
 ) -> Response {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
+  let backend = &site_backend.backend;
 
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let boot_parameters_rslt = backend
     .get_redfish_endpoints(
@@ -121,117 +83,97 @@ pub async fn get_redfish(
       return (StatusCode::OK, Json(boot_parameters_vec)).into_response();
     }
     Err(e) => {
-      return (StatusCode::INTERNAL_SERVER_ERROR, Json(e.to_string()))
-        .into_response();
+      return (backend_error_status(&e), Json(e.to_string())).into_response();
     }
   }
 }
 
+#[utoipa::path(
+    post,
+    path = "/redfish",
+    request_body = RedfishEndpointArray,
+    responses(
+        (status = 200, description = "Redfish endpoints added")
+    ),
+    security(("bearer_auth" = []))
+)]
 #[axum::debug_handler]
 pub async fn post_redfish(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Json(redfish_endpoint): Json<RedfishEndpointArray>,
 ) -> Response {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
+  let backend = &site_backend.backend;
 
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let boot_parameters_rslt = backend
     .add_redfish_endpoint(auth_token, &redfish_endpoint)
     .await;
 
+  site_backend
+    .audit(
+      auth_token,
+      "add redfish endpoints",
+      &redfish_endpoint
+        .redfish_endpoints
+        .iter()
+        .flatten()
+        .map(|redfish_endpoint| redfish_endpoint.id.as_str())
+        .collect::<Vec<_>>(),
+      boot_parameters_rslt.is_ok(),
+    )
+    .await;
+
   match boot_parameters_rslt {
     Ok(boot_parameters_vec) => {
       return (StatusCode::OK, Json(boot_parameters_vec)).into_response();
     }
     Err(e) => {
-      return (StatusCode::INTERNAL_SERVER_ERROR, Json(e.to_string()))
-        .into_response();
+      return (backend_error_status(&e), Json(e.to_string())).into_response();
     }
   }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/redfish/{xname}",
+    params(
+        ("xname" = String, Path, description = "Redfish endpoint xname")
+    ),
+    responses(
+        (status = 200, description = "Redfish endpoint deleted", body = serde_json::Value)
+    ),
+    security(("bearer_auth" = []))
+)]
 #[axum::debug_handler]
 pub async fn delete_redfish(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(xname): Path<String>,
 ) -> Response {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
+  let backend = &site_backend.backend;
 
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let boot_parameters_rslt =
     backend.delete_redfish_endpoint(auth_token, &xname).await;
 
+  site_backend
+    .audit(
+      auth_token,
+      "delete redfish endpoint",
+      &[&xname],
+      boot_parameters_rslt.is_ok(),
+    )
+    .await;
+
   match boot_parameters_rslt {
     Ok(boot_parameters_vec) => {
       return (StatusCode::OK, Json(boot_parameters_vec)).into_response();
     }
     Err(e) => {
-      return (StatusCode::INTERNAL_SERVER_ERROR, Json(e.to_string()))
-        .into_response();
+      return (backend_error_status(&e), Json(e.to_string())).into_response();
     }
   }
 }
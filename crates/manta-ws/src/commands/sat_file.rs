@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  response::{IntoResponse, Response},
+};
+use csm_rs::common::vault::http_client::{
+  fetch_shasta_k8s_secrets_from_vault, fetch_shasta_vcs_token,
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{apply_sat_file::SatTrait, hsm::group::GroupTrait},
+  types::K8sAuth,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+  auth::AuthUser,
+  common::{config::types::Site, site_backend::SiteBackend},
+  http_response::backend_error_response,
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// SAT file to apply. The file must already be rendered, values files are not
+/// supported
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct SatFileRequest {
+  sat_file: String,
+  #[serde(default)]
+  do_not_reboot: bool,
+  #[serde(default)]
+  dry_run: bool,
+}
+
+#[utoipa::path(
+    post,
+    path = "/sat-file",
+    request_body = SatFileRequest,
+    responses(
+        (status = 200, description = "SAT file applied"),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn post_sat_file(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Json(request): Json<SatFileRequest>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  let result = apply_sat_file(
+    &site_backend.backend,
+    &site_backend.site_name,
+    &site_backend.site,
+    &site_backend.shasta_root_cert,
+    auth_token,
+    &request.sat_file,
+    request.do_not_reboot,
+    request.dry_run,
+  )
+  .await;
+
+  site_backend
+    .audit(auth_token, "apply sat-file", &[], result.is_ok())
+    .await;
+
+  match result {
+    Ok(()) => StatusCode::OK.into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+/// Applies a SAT file to the groups the user has access to
+pub async fn apply_sat_file(
+  backend: &StaticBackendDispatcher,
+  site_name: &str,
+  site: &Site,
+  shasta_root_cert: &[u8],
+  auth_token: &str,
+  sat_file: &str,
+  do_not_reboot: bool,
+  dry_run: bool,
+) -> Result<(), Error> {
+  let sat_template_file_yaml: serde_yaml::Value =
+    serde_yaml::from_str(sat_file)
+      .map_err(|e| Error::Message(format!("SAT file not valid: {}", e)))?;
+
+  let vault_base_url = site.vault_base_url.as_ref().ok_or_else(|| {
+    Error::Message("vault_base_url missing in site configuration".to_string())
+  })?;
+
+  let k8s_details = site.k8s.as_ref().ok_or_else(|| {
+    Error::Message("k8s section missing in site configuration".to_string())
+  })?;
+
+  let shasta_k8s_secrets = match &k8s_details.authentication {
+    K8sAuth::Native {
+      certificate_authority_data,
+      client_certificate_data,
+      client_key_data,
+    } => {
+      serde_json::json!({ "certificate-authority-data": certificate_authority_data, "client-certificate-data": client_certificate_data, "client-key-data": client_key_data })
+    }
+    K8sAuth::Vault {
+      base_url: vault_base_url,
+    } => {
+      fetch_shasta_k8s_secrets_from_vault(vault_base_url, auth_token, site_name)
+        .await
+        .map_err(|e| Error::Message(e.to_string()))?
+    }
+  };
+
+  let gitea_token =
+    fetch_shasta_vcs_token(auth_token, vault_base_url, site_name)
+      .await
+      .map_err(|e| Error::Message(e.to_string()))?;
+
+  let gitea_base_url = format!(
+    "{}/vcs",
+    site
+      .shasta_base_url
+      .strip_suffix("/apis")
+      .unwrap_or(&site.shasta_base_url)
+  );
+
+  let hsm_group_available_vec =
+    backend.get_group_name_available(auth_token).await?;
+
+  backend
+    .apply_sat_file(
+      auth_token,
+      &site.shasta_base_url,
+      shasta_root_cert,
+      vault_base_url,
+      site_name,
+      &k8s_details.api_url,
+      shasta_k8s_secrets,
+      sat_template_file_yaml,
+      &hsm_group_available_vec,
+      None,
+      None,
+      &gitea_base_url,
+      &gitea_token,
+      do_not_reboot,
+      false,
+      false,
+      dry_run,
+    )
+    .await
+}
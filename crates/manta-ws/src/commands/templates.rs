@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use axum::{
+  Extension, Json,
+  extract::Query,
+  response::{IntoResponse, Response},
+};
+use hyper::StatusCode;
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{bos::ClusterTemplateTrait, hsm::group::GroupTrait},
+  types::bos::session_template::BosSessionTemplate,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+  auth::AuthUser,
+  common::{authorization, site_backend::SiteBackend},
+  http_response::backend_error_response,
+};
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TemplateQueryParams {
+  name: Option<String>,
+  group: Option<String>,
+  limit: Option<u8>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/templates",
+    params(TemplateQueryParams),
+    responses(
+        (status = 200, description = "BOS session templates related to the groups the user has access to", body = Vec<BosSessionTemplate>),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_templates(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+  Query(params): Query<TemplateQueryParams>,
+) -> Response {
+  let auth_token = user.token.as_str();
+
+  match get_and_filter_templates(&site_backend, auth_token, &params).await {
+    Ok(template_vec) => (StatusCode::OK, Json(template_vec)).into_response(),
+    Err(e) => backend_error_response(e),
+  }
+}
+
+async fn get_and_filter_templates(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  params: &TemplateQueryParams,
+) -> Result<Vec<BosSessionTemplate>, Error> {
+  let group_vec = authorization::get_groups_available(
+    &site_backend.backend,
+    auth_token,
+    params.group.as_deref(),
+  )
+  .await?;
+
+  let member_vec = site_backend
+    .backend
+    .get_member_vec_from_group_name_vec(auth_token, group_vec.clone())
+    .await?;
+
+  site_backend
+    .backend
+    .get_and_filter_templates(
+      auth_token,
+      &site_backend.site.shasta_base_url,
+      &site_backend.shasta_root_cert,
+      &group_vec,
+      &member_vec,
+      params.name.as_ref(),
+      params.limit.as_ref(),
+    )
+    .await
+}
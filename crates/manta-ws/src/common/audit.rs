@@ -1,14 +1,45 @@
-use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use manta_backend_dispatcher::audit::{
+  AuditEvent, AuditOutcome, AuditUser, Auditor,
+};
 
-use super::kafka::Kafka;
+use crate::jwt_utils::get_claims_from_jwt_token;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Auditor {
-  pub kafka: Kafka,
-  // pub syslog: Option<Syslog>,
+/// User owning the authentication token, to be added to audit events
+pub fn get_user(token: &str) -> AuditUser {
+  let claims = get_claims_from_jwt_token(token).unwrap_or_default();
+
+  let get_claim =
+    |claim: &str| claims[claim].as_str().unwrap_or("MISSING").to_string();
+
+  AuditUser {
+    id: get_claim("preferred_username"),
+    name: get_claim("name"),
+  }
 }
 
-pub trait Audit {
-  async fn produce_message(&self, data: &[u8]) -> Result<()>;
+/// Sends an audit event about an operation requested to the API. Does nothing
+/// if no audit sink is configured
+pub async fn send_event(
+  auditor_opt: Option<&Auditor>,
+  site_name: &str,
+  auth_token: &str,
+  command: &str,
+  xname_vec: &[&str],
+  succeeded: bool,
+) {
+  let Some(auditor) = auditor_opt else {
+    return;
+  };
+
+  let outcome = if succeeded {
+    AuditOutcome::Success
+  } else {
+    AuditOutcome::Failure
+  };
+
+  let event = AuditEvent::new(get_user(auth_token), site_name, command)
+    .with_xnames(xname_vec)
+    .with_outcome(outcome);
+
+  auditor.send(&event).await;
 }
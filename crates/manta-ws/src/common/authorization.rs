@@ -0,0 +1,61 @@
+use manta_backend_dispatcher::{
+  error::Error, interfaces::hsm::group::GroupTrait,
+};
+
+use crate::manta_backend_dispatcher::StaticBackendDispatcher;
+
+/// Returns the groups the request works with. If the request targets a group,
+/// the user must have access to it, otherwise all the groups the user has
+/// access to are returned
+pub async fn get_groups_available(
+  backend: &StaticBackendDispatcher,
+  auth_token: &str,
+  group_opt: Option<&str>,
+) -> Result<Vec<String>, Error> {
+  let group_available_vec =
+    backend.get_group_name_available(auth_token).await?;
+
+  match group_opt {
+    Some(group) if !group_available_vec.iter().any(|elem| elem == group) => {
+      Err(Error::Forbidden {
+        service: "manta-ws".to_string(),
+        detail: format!("Can't access group '{}'", group),
+      })
+    }
+    Some(group) => Ok(vec![group.to_string()]),
+    None => Ok(group_available_vec),
+  }
+}
+
+/// Validates the user has access to all the nodes, this is, all nodes belong
+/// to a group the user has access to
+pub async fn validate_target_hsm_members(
+  backend: &StaticBackendDispatcher,
+  auth_token: &str,
+  xname_vec: &[String],
+) -> Result<(), Error> {
+  let group_available_vec =
+    backend.get_group_name_available(auth_token).await?;
+
+  let xname_available_vec = backend
+    .get_member_vec_from_group_name_vec(auth_token, group_available_vec)
+    .await?;
+
+  let xname_not_available_vec: Vec<&str> = xname_vec
+    .iter()
+    .filter(|xname| !xname_available_vec.contains(xname))
+    .map(String::as_str)
+    .collect();
+
+  if xname_not_available_vec.is_empty() {
+    Ok(())
+  } else {
+    Err(Error::Forbidden {
+      service: "manta-ws".to_string(),
+      detail: format!(
+        "Can't access nodes '{}'",
+        xname_not_available_vec.join(", ")
+      ),
+    })
+  }
+}
@@ -68,6 +68,21 @@ pub fn get_default_manta_audit_file_path() -> PathBuf {
   log_file_path
 }
 
+/// File where the scheduled jobs are persisted ($XDG_DATA/manta/jobs.json)
+pub fn get_default_jobs_file_path() -> PathBuf {
+  // XDG Base Directory Specification
+  let project_dirs = ProjectDirs::from(
+    "local", /*qualifier*/
+    "cscs",  /*organization*/
+    "manta", /*application*/
+  );
+
+  let mut jobs_file_path = PathBuf::from(project_dirs.unwrap().data_dir());
+  jobs_file_path.push("jobs.json");
+
+  jobs_file_path
+}
+
 pub fn get_default_mgmt_plane_ca_cert_file_path() -> PathBuf {
   // XDG Base Directory Specification
   let project_dirs = ProjectDirs::from(
@@ -1,8 +1,7 @@
 use std::collections::HashMap;
 
-use crate::common::audit::Auditor;
-
-use manta_backend_dispatcher::types::K8sDetails;
+use jsonwebtoken::Algorithm;
+use manta_backend_dispatcher::{audit::Auditor, types::K8sDetails};
 use serde::{Deserialize, Serialize};
 
 /* #[derive(Serialize, Deserialize, Debug)]
@@ -56,6 +55,17 @@ pub struct Site {
   pub vault_secret_path: Option<String>,
   // pub vault_role_id: Option<String>,
   pub root_ca_cert_file: String,
+  /// Fixture file seeding the state of the 'mock' backend
+  pub mock_fixture_file: Option<String>,
+  /// Keycloak used by '/authenticate', defaults to the 'keycloak' API next to
+  /// 'shasta_base_url'
+  pub keycloak_base_url: Option<String>,
+  /// Token validation for this site, overrides the global 'auth' section
+  pub auth: Option<AuthConfig>,
+  /// File with the backend token of a service account with access to every
+  /// group. '/metrics' uses it to compute the inventory gauges, which are left
+  /// out if missing
+  pub metrics_token_file: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -66,4 +76,50 @@ pub struct MantaConfiguration {
   pub audit_file: String,
   pub sites: HashMap<String, Site>,
   pub auditor: Option<Auditor>,
+  pub auth: Option<AuthConfig>,
+  /// Accept tokens without validating them in sites without 'auth' section.
+  /// Only meant for testing, users of these sites get no roles
+  #[serde(default)]
+  pub insecure_skip_token_verification: bool,
+  /// Scraping of '/metrics'
+  pub metrics: Option<MetricsConfig>,
+}
+
+/// Access to '/metrics', which does not need a user token
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MetricsConfig {
+  /// File with the token Prometheus sends as 'Authorization: Bearer', eg the
+  /// 'credentials_file' of the scrape config. '/metrics' is open if missing
+  pub scrape_token_file: Option<String>,
+}
+
+/// Validation of the JWT tokens sent to the API. Sites without it are not
+/// loaded unless 'insecure_skip_token_verification' is set
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthConfig {
+  /// JWKS endpoint of the identity provider, eg
+  /// 'https://api.cmn.alps.cscs.ch/keycloak/realms/shasta/protocol/openid-connect/certs'
+  pub jwks_url: Option<String>,
+  /// Local JWKS file, used instead of 'jwks_url' for offline testing
+  pub jwks_file: Option<String>,
+  /// Expected 'iss' claim
+  pub issuer: Option<String>,
+  /// Expected 'aud' claim
+  pub audience: Option<String>,
+  /// JSON pointer to the claim with the roles of the user. Roles are the
+  /// groups the user has access to plus 'pa_admin' for administrators
+  #[serde(default = "default_roles_claim")]
+  pub roles_claim: String,
+  /// Signing algorithms accepted for keys without 'alg' in the JWKS. Keys
+  /// with 'alg' only accept that algorithm
+  #[serde(default = "default_algorithms")]
+  pub algorithms: Vec<Algorithm>,
+}
+
+fn default_roles_claim() -> String {
+  "/realm_access/roles".to_string()
+}
+
+fn default_algorithms() -> Vec<Algorithm> {
+  vec![Algorithm::RS256]
 }
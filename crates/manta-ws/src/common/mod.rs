@@ -1,3 +1,4 @@
 pub mod audit;
+pub mod authorization;
 pub mod config;
-pub mod kafka;
+pub mod site_backend;
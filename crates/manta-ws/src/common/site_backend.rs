@@ -0,0 +1,291 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use axum::{
+  extract::{Request, State},
+  http::Uri,
+  middleware::Next,
+  response::Response,
+};
+use manta_backend_dispatcher::error::Error;
+use serde::Serialize;
+
+use crate::{
+  auth::TokenValidator,
+  common::{
+    audit,
+    config::{
+      self,
+      types::{MantaConfiguration, Site},
+    },
+  },
+  events::EventHub,
+  http_response::backend_error_response,
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Header selecting the site of a request, alternative to the '/sites/{site}'
+/// path prefix
+pub const SITE_HEADER: &str = "x-manta-site";
+
+/// Configuration of the server with the backend of the site it works with
+pub struct SiteBackend {
+  pub configuration: MantaConfiguration,
+  pub site_name: String,
+  pub site: Site,
+  pub shasta_root_cert: Vec<u8>,
+  pub backend: StaticBackendDispatcher,
+  /// Validates the tokens issued by the identity provider of the site
+  pub token_validator: TokenValidator,
+  /// Changes detected by the poller of the site, see '/events'
+  pub events: Arc<EventHub>,
+}
+
+impl SiteBackend {
+  /// Creates the backend of a site in the configuration
+  pub async fn new(
+    configuration: &MantaConfiguration,
+    site_name: &str,
+  ) -> Result<Self, Error> {
+    let Some(site) = configuration.sites.get(site_name).cloned() else {
+      return Err(Error::Message(format!(
+        "Site '{}' not found in configuration",
+        site_name
+      )));
+    };
+
+    let shasta_root_cert =
+      config::get_csm_root_cert_content(&site.root_ca_cert_file)?;
+
+    let backend = StaticBackendDispatcher::new(
+      &site.backend,
+      &site.shasta_base_url,
+      &shasta_root_cert,
+      site.mock_fixture_file.as_deref(),
+    );
+
+    let auth_config_opt =
+      site.auth.clone().or_else(|| configuration.auth.clone());
+
+    if auth_config_opt.is_none()
+      && !configuration.insecure_skip_token_verification
+    {
+      return Err(Error::Message(format!(
+        "No 'auth' configuration for site '{}'. Set 'insecure_skip_token_verification = true' to accept tokens without validating them",
+        site_name
+      )));
+    }
+
+    let token_validator =
+      TokenValidator::new(auth_config_opt, shasta_root_cert.clone()).await?;
+
+    if token_validator.is_unverified() {
+      tracing::warn!(
+        "No 'auth' configuration for site '{}', token signatures are not validated and users get no roles",
+        site_name
+      );
+    }
+
+    Ok(SiteBackend {
+      configuration: configuration.clone(),
+      site_name: site_name.to_string(),
+      site,
+      shasta_root_cert,
+      backend,
+      token_validator,
+      events: Arc::new(EventHub::default()),
+    })
+  }
+
+  /// Sends an audit event about an operation requested to the API
+  pub async fn audit(
+    &self,
+    auth_token: &str,
+    command: &str,
+    xname_vec: &[&str],
+    succeeded: bool,
+  ) {
+    audit::send_event(
+      self.configuration.auditor.as_ref(),
+      &self.site_name,
+      auth_token,
+      command,
+      xname_vec,
+      succeeded,
+    )
+    .await;
+  }
+}
+
+/// Site in the 'GET /sites' response
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct SiteSummary {
+  pub name: String,
+  pub backend: String,
+  pub shasta_base_url: String,
+  /// Site used by requests without site selector
+  pub default: bool,
+}
+
+/// Backends of all the sites in the configuration, created at startup
+pub struct Sites {
+  default_site_name: String,
+  site_backends: BTreeMap<String, Arc<SiteBackend>>,
+}
+
+impl Sites {
+  /// Reads the configuration and creates the backend of every site in it.
+  /// Sites whose backend can't be created are skipped, except the default one
+  pub async fn load() -> Result<Self, Error> {
+    let configuration: MantaConfiguration = config::get_configuration()
+      .await?
+      .try_deserialize()
+      .map_err(|e| Error::Message(e.to_string()))?;
+
+    let mut site_backends = BTreeMap::new();
+
+    for site_name in configuration.sites.keys() {
+      match SiteBackend::new(&configuration, site_name).await {
+        Ok(site_backend) => {
+          site_backends.insert(site_name.clone(), Arc::new(site_backend));
+        }
+        Err(e) if *site_name == configuration.site => return Err(e),
+        Err(e) => {
+          tracing::warn!("Site '{}' not available. Reason:\n{}", site_name, e)
+        }
+      }
+    }
+
+    if !site_backends.contains_key(&configuration.site) {
+      return Err(Error::Message(format!(
+        "Site '{}' not found in configuration",
+        configuration.site
+      )));
+    }
+
+    Ok(Sites {
+      default_site_name: configuration.site,
+      site_backends,
+    })
+  }
+
+  /// Backend of the site, or of the default site if none
+  pub fn get(
+    &self,
+    site_name: Option<&str>,
+  ) -> Result<Arc<SiteBackend>, Error> {
+    let site_name = site_name.unwrap_or(&self.default_site_name);
+
+    self
+      .site_backends
+      .get(site_name)
+      .cloned()
+      .ok_or_else(|| Error::NotFound {
+        service: "manta-ws".to_string(),
+        resource: "site".to_string(),
+        id: site_name.to_string(),
+      })
+  }
+
+  pub fn site_backends(&self) -> impl Iterator<Item = Arc<SiteBackend>> + '_ {
+    self.site_backends.values().cloned()
+  }
+
+  pub fn summary(&self) -> Vec<SiteSummary> {
+    self
+      .site_backends
+      .values()
+      .map(|site_backend| SiteSummary {
+        name: site_backend.site_name.clone(),
+        backend: site_backend.site.backend.clone(),
+        shasta_base_url: site_backend.site.shasta_base_url.clone(),
+        default: site_backend.site_name == self.default_site_name,
+      })
+      .collect()
+  }
+}
+
+/// Site selected by a request that is not in the configuration, answered with
+/// 404 by `require_site`
+#[derive(Clone, Debug)]
+pub struct UnknownSite(pub String);
+
+/// Adds the backend of the site selected by the request to its extensions.
+/// The site is taken from the '/sites/{site}' path prefix, which is removed
+/// before routing, or from the 'x-manta-site' header. Requests without
+/// selector go to the default site. Unknown sites are only rejected by the
+/// routes needing a site, see `require_site`
+pub async fn select_site(
+  State(sites): State<Arc<Sites>>,
+  mut request: Request,
+  next: Next,
+) -> Response {
+  let path = request.uri().path().to_string();
+
+  let site_name_opt = match path.strip_prefix("/sites/") {
+    Some(site_path) => {
+      let (site_name, route_path) = match site_path.find('/') {
+        Some(index) => site_path.split_at(index),
+        None => (site_path, "/"),
+      };
+
+      let path_and_query = match request.uri().query() {
+        Some(query) => format!("{}?{}", route_path, query),
+        None => route_path.to_string(),
+      };
+
+      match Uri::builder().path_and_query(path_and_query).build() {
+        Ok(uri) => *request.uri_mut() = uri,
+        Err(e) => {
+          return backend_error_response(Error::BadRequest {
+            service: "manta-ws".to_string(),
+            detail: e.to_string(),
+          });
+        }
+      }
+
+      Some(site_name.to_string())
+    }
+    None => request
+      .headers()
+      .get(SITE_HEADER)
+      .and_then(|site_name| site_name.to_str().ok())
+      .map(str::to_string),
+  };
+
+  match sites.get(site_name_opt.as_deref()) {
+    Ok(site_backend) => {
+      request.extensions_mut().insert(site_backend);
+    }
+    Err(_) => {
+      request
+        .extensions_mut()
+        .insert(UnknownSite(site_name_opt.unwrap_or_default()));
+    }
+  }
+
+  next.run(request).await
+}
+
+/// Answers 404 if the site selected by the request is not in the
+/// configuration. Must run after `select_site`, on the routes using the
+/// backend of a site
+pub async fn require_site(request: Request, next: Next) -> Response {
+  if request.extensions().get::<Arc<SiteBackend>>().is_some() {
+    return next.run(request).await;
+  }
+
+  let site_name = request
+    .extensions()
+    .get::<UnknownSite>()
+    .map(|unknown_site| unknown_site.0.clone())
+    .unwrap_or_default();
+
+  backend_error_response(Error::NotFound {
+    service: "manta-ws".to_string(),
+    resource: "site".to_string(),
+    id: site_name,
+  })
+}
+
+#[cfg(test)]
+mod tests;
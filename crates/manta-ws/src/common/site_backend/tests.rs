@@ -0,0 +1,216 @@
+use std::{
+  collections::{BTreeMap, HashMap},
+  fs,
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use axum::{
+  Extension, Router,
+  body::{Body, to_bytes},
+  extract::State,
+  http::{Request, StatusCode, Uri},
+  middleware,
+  routing::get,
+};
+use tower::{Layer, ServiceExt};
+
+use crate::common::config::types::{MantaConfiguration, Site};
+
+use super::{SITE_HEADER, SiteBackend, Sites, require_site, select_site};
+
+/// Directory only used by the test with the root certificate of the sites
+fn test_dir(test_name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!(
+    "manta-ws-site-{}-{}",
+    std::process::id(),
+    test_name
+  ));
+
+  fs::create_dir_all(&dir).unwrap();
+  fs::write(dir.join("root_cert.pem"), "").unwrap();
+
+  dir
+}
+
+fn site(dir: &Path, shasta_base_url: &str) -> Site {
+  Site {
+    backend: "csm".to_string(),
+    socks5_proxy: None,
+    shasta_base_url: shasta_base_url.to_string(),
+    k8s: None,
+    vault_base_url: None,
+    vault_secret_path: None,
+    root_ca_cert_file: dir.join("root_cert.pem").display().to_string(),
+    mock_fixture_file: None,
+    keycloak_base_url: None,
+    auth: None,
+    metrics_token_file: None,
+  }
+}
+
+/// Sites 'alps', the default one, and 'prealps'
+async fn sites(dir: &Path) -> Arc<Sites> {
+  let configuration = MantaConfiguration {
+    log: "info".to_string(),
+    site: "alps".to_string(),
+    parent_hsm_group: "nodes_free".to_string(),
+    audit_file: dir.join("audit.log").display().to_string(),
+    sites: HashMap::from([
+      (
+        "alps".to_string(),
+        site(dir, "https://api.alps.example/apis"),
+      ),
+      (
+        "prealps".to_string(),
+        site(dir, "https://api.prealps.example/apis"),
+      ),
+    ]),
+    auditor: None,
+    auth: None,
+    insecure_skip_token_verification: true,
+    metrics: None,
+  };
+
+  let mut site_backends = BTreeMap::new();
+
+  for site_name in ["alps", "prealps"] {
+    site_backends.insert(
+      site_name.to_string(),
+      Arc::new(SiteBackend::new(&configuration, site_name).await.unwrap()),
+    );
+  }
+
+  Arc::new(Sites {
+    default_site_name: "alps".to_string(),
+    site_backends,
+  })
+}
+
+/// Site and URI seen by the handler
+async fn site_uri(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  uri: Uri,
+) -> String {
+  format!("{} {}", site_backend.site_name, uri)
+}
+
+async fn site_names(State(sites): State<Arc<Sites>>) -> String {
+  sites
+    .summary()
+    .into_iter()
+    .map(|site_summary| site_summary.name)
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+/// Sends the request through the site selection, '/site' and '/' need a site,
+/// '/sites' does not
+async fn send(
+  sites: &Arc<Sites>,
+  uri: &str,
+  site_name_opt: Option<&str>,
+) -> (StatusCode, String) {
+  let mut request_builder = Request::builder().uri(uri);
+
+  if let Some(site_name) = site_name_opt {
+    request_builder = request_builder.header(SITE_HEADER, site_name);
+  }
+
+  let site_routes = Router::new()
+    .route("/", get(site_uri))
+    .route("/site", get(site_uri))
+    .route_layer(middleware::from_fn(require_site));
+
+  let app = Router::new()
+    .route("/sites", get(site_names))
+    .merge(site_routes)
+    .with_state(sites.clone());
+
+  let response = middleware::from_fn_with_state(sites.clone(), select_site)
+    .layer(app)
+    .oneshot(request_builder.body(Body::empty()).unwrap())
+    .await
+    .unwrap();
+
+  let status = response.status();
+  let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+  (status, String::from_utf8_lossy(&body).to_string())
+}
+
+#[tokio::test]
+async fn test_site_path_prefix() {
+  let dir = test_dir("prefix");
+  let sites = sites(&dir).await;
+
+  assert_eq!(
+    send(&sites, "/sites/prealps/site", None).await,
+    (StatusCode::OK, "prealps /site".to_string())
+  );
+  assert_eq!(
+    send(&sites, "/sites/prealps/site?group=zinal", None).await,
+    (StatusCode::OK, "prealps /site?group=zinal".to_string())
+  );
+  assert_eq!(
+    send(&sites, "/sites/prealps", None).await,
+    (StatusCode::OK, "prealps /".to_string())
+  );
+
+  // The path prefix takes precedence over the header
+  assert_eq!(
+    send(&sites, "/sites/prealps/site", Some("alps")).await,
+    (StatusCode::OK, "prealps /site".to_string())
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_site_header() {
+  let dir = test_dir("header");
+  let sites = sites(&dir).await;
+
+  assert_eq!(
+    send(&sites, "/site", Some("prealps")).await,
+    (StatusCode::OK, "prealps /site".to_string())
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_default_site() {
+  let dir = test_dir("default");
+  let sites = sites(&dir).await;
+
+  assert_eq!(
+    send(&sites, "/site", None).await,
+    (StatusCode::OK, "alps /site".to_string())
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_unknown_site() {
+  let dir = test_dir("unknown");
+  let sites = sites(&dir).await;
+
+  assert_eq!(
+    send(&sites, "/sites/eiger/site", None).await.0,
+    StatusCode::NOT_FOUND
+  );
+  assert_eq!(
+    send(&sites, "/site", Some("eiger")).await.0,
+    StatusCode::NOT_FOUND
+  );
+
+  // Routes not needing a site ignore the unknown site
+  assert_eq!(
+    send(&sites, "/sites", Some("eiger")).await,
+    (StatusCode::OK, "alps,prealps".to_string())
+  );
+
+  fs::remove_dir_all(dir).unwrap();
+}
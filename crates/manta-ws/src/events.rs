@@ -0,0 +1,7 @@
+mod hub;
+mod poller;
+mod types;
+
+pub use crate::events::hub::EventHub;
+pub use crate::events::poller::start;
+pub use crate::events::types::{Change, Event, EventFilter};
@@ -0,0 +1,86 @@
+use std::{
+  collections::BTreeMap,
+  sync::{
+    Arc, Mutex,
+    atomic::{AtomicU64, Ordering},
+  },
+};
+
+use tokio::sync::broadcast::{self, Receiver, Sender};
+
+use crate::{auth::AuthUser, jwt_utils::get_claims_from_jwt_token};
+
+use super::types::Event;
+
+/// Events buffered per subscriber, older events are dropped for subscribers
+/// not keeping up
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Fans out the events of a site to its subscribers
+pub struct EventHub {
+  sender: Sender<Event>,
+  /// User of each subscriber, the poller borrows the token of one of them to
+  /// query the backend
+  user_map: Mutex<BTreeMap<u64, AuthUser>>,
+  next_id: AtomicU64,
+}
+
+impl Default for EventHub {
+  fn default() -> Self {
+    EventHub {
+      sender: broadcast::channel(CHANNEL_CAPACITY).0,
+      user_map: Mutex::new(BTreeMap::new()),
+      next_id: AtomicU64::new(0),
+    }
+  }
+}
+
+impl EventHub {
+  /// Registers a subscriber until the subscription is dropped
+  pub fn subscribe(self: &Arc<Self>, user: &AuthUser) -> Subscription {
+    let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+    self.user_map.lock().unwrap().insert(id, user.clone());
+
+    Subscription {
+      hub: self.clone(),
+      id,
+      receiver: self.sender.subscribe(),
+    }
+  }
+
+  pub fn publish(&self, event: Event) {
+    // Fails only if there are no subscribers
+    let _ = self.sender.send(event);
+  }
+
+  /// Subscriber whose token expires the latest, none if nobody is
+  /// subscribed
+  pub fn poller_user(&self) -> Option<AuthUser> {
+    self
+      .user_map
+      .lock()
+      .unwrap()
+      .values()
+      .max_by_key(|user| {
+        get_claims_from_jwt_token(&user.token)
+          .ok()
+          .and_then(|claims| claims["exp"].as_i64())
+          .unwrap_or_default()
+      })
+      .cloned()
+  }
+}
+
+/// Events of a site received by a subscriber
+pub struct Subscription {
+  hub: Arc<EventHub>,
+  id: u64,
+  pub receiver: Receiver<Event>,
+}
+
+impl Drop for Subscription {
+  fn drop(&mut self) {
+    self.hub.user_map.lock().unwrap().remove(&self.id);
+  }
+}
@@ -0,0 +1,435 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{cfs::CfsTrait, hsm::component::ComponentTrait, pcs::PCSTrait},
+};
+use serde_json::Value;
+
+use crate::common::site_backend::{SiteBackend, Sites};
+
+use super::types::{Change, Event};
+
+/// Seconds between two snapshots of the backend
+const POLL_INTERVAL: u64 = 10;
+
+/// Last values seen, per component, session or transition. A snapshot is
+/// missing until it is taken the first time, so the initial state of the
+/// system is not sent as changes
+#[derive(Default)]
+struct Snapshots {
+  node_state: Option<HashMap<String, NodeState>>,
+  cfs_component: Option<HashMap<String, CfsComponentState>>,
+  cfs_session: Option<HashMap<String, CfsSessionState>>,
+  power_transition: Option<HashMap<String, PowerTransitionState>>,
+  /// Nodes of the transitions sent as changes, transitions removed from PCS
+  /// can't be queried anymore
+  power_transition_xnames: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct NodeState {
+  state: Option<String>,
+  flag: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct CfsComponentState {
+  desired_config: Option<String>,
+  configuration_status: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct CfsSessionState {
+  status: Option<String>,
+  succeeded: Option<String>,
+  xnames: Vec<String>,
+  groups: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PowerTransitionState {
+  operation: Option<String>,
+  status: Option<String>,
+}
+
+/// Starts a background task per site polling its backend while the site has
+/// subscribers
+pub fn start(sites: &Sites) {
+  for site_backend in sites.site_backends() {
+    tokio::spawn(poll(site_backend));
+  }
+}
+
+async fn poll(site_backend: Arc<SiteBackend>) {
+  let mut snapshots = Snapshots::default();
+  let mut poller_user_id_opt: Option<String> = None;
+
+  loop {
+    tokio::time::sleep(Duration::from_secs(POLL_INTERVAL)).await;
+
+    // Subscribers bring the token used to query the backend
+    let Some(poller_user) = site_backend.events.poller_user() else {
+      snapshots = Snapshots::default();
+      poller_user_id_opt = None;
+      continue;
+    };
+
+    // Users may see different parts of the system, comparing their snapshots
+    // would send the differences as changes
+    if poller_user_id_opt.as_ref() != Some(&poller_user.id) {
+      snapshots = Snapshots::default();
+      poller_user_id_opt = Some(poller_user.id.clone());
+    }
+
+    for change in
+      poll_changes(&site_backend, &poller_user.token, &mut snapshots).await
+    {
+      site_backend.events.publish(Event {
+        site: site_backend.site_name.clone(),
+        time: Utc::now(),
+        change,
+      });
+    }
+  }
+}
+
+/// Takes a new snapshot of every source and returns the differences with the
+/// previous one. Sources failing keep their previous snapshot
+async fn poll_changes(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  snapshots: &mut Snapshots,
+) -> Vec<Change> {
+  let mut change_vec = Vec::new();
+
+  match get_node_states(site_backend, auth_token).await {
+    Ok(current) => {
+      change_vec.extend(diff(&snapshots.node_state, &current).map(
+        |(xname, previous, current)| Change::NodeState {
+          xname,
+          previous_state: previous.as_ref().and_then(|p| p.state.clone()),
+          state: current.as_ref().and_then(|c| c.state.clone()),
+          previous_flag: previous.and_then(|p| p.flag),
+          flag: current.and_then(|c| c.flag),
+        },
+      ));
+      snapshots.node_state = Some(current);
+    }
+    Err(e) => log_poll_error(site_backend, "HSM components", e),
+  }
+
+  match get_cfs_component_states(site_backend, auth_token).await {
+    Ok(current) => {
+      change_vec.extend(diff(&snapshots.cfs_component, &current).map(
+        |(xname, previous, current)| Change::CfsComponent {
+          xname,
+          previous_desired_config:
+            previous.as_ref().and_then(|p| p.desired_config.clone()),
+          desired_config:
+            current.as_ref().and_then(|c| c.desired_config.clone()),
+          previous_status: previous.and_then(|p| p.configuration_status),
+          configuration_status: current.and_then(|c| c.configuration_status),
+        },
+      ));
+      snapshots.cfs_component = Some(current);
+    }
+    Err(e) => log_poll_error(site_backend, "CFS components", e),
+  }
+
+  match get_cfs_session_states(site_backend, auth_token).await {
+    Ok(current) => {
+      change_vec.extend(diff(&snapshots.cfs_session, &current).map(
+        |(name, previous, current)| {
+          // Removed sessions keep their targets so subscribers get the event
+          let target = current.as_ref().or(previous.as_ref());
+
+          Change::CfsSession {
+            name,
+            previous_status: previous.as_ref().and_then(|p| p.status.clone()),
+            status: current.as_ref().and_then(|c| c.status.clone()),
+            succeeded: current.as_ref().and_then(|c| c.succeeded.clone()),
+            xnames: target.map(|t| t.xnames.clone()).unwrap_or_default(),
+            groups: target.map(|t| t.groups.clone()).unwrap_or_default(),
+          }
+        },
+      ));
+      snapshots.cfs_session = Some(current);
+    }
+    Err(e) => log_poll_error(site_backend, "CFS sessions", e),
+  }
+
+  match get_power_transition_states(site_backend, auth_token).await {
+    Ok(current) => {
+      for (id, previous, current) in diff(&snapshots.power_transition, &current)
+      {
+        // Transitions are listed without their tasks
+        let xnames = match &current {
+          Some(_) => {
+            let xnames =
+              get_power_transition_xnames(site_backend, auth_token, &id)
+                .await
+                .unwrap_or_default();
+
+            snapshots
+              .power_transition_xnames
+              .insert(id.clone(), xnames.clone());
+
+            xnames
+          }
+          None => snapshots
+            .power_transition_xnames
+            .remove(&id)
+            .unwrap_or_default(),
+        };
+
+        change_vec.push(Change::PowerTransition {
+          id,
+          operation: current
+            .as_ref()
+            .or(previous.as_ref())
+            .and_then(|t| t.operation.clone()),
+          previous_status: previous.and_then(|p| p.status),
+          status: current.and_then(|c| c.status),
+          xnames,
+        });
+      }
+      snapshots.power_transition = Some(current);
+    }
+    Err(e) => log_poll_error(site_backend, "PCS transitions", e),
+  }
+
+  change_vec
+}
+
+/// Entries new, different or removed since the previous snapshot, with their
+/// previous and current values. The previous value is missing for new entries
+/// and the current one for removed entries. Nothing is returned for the first
+/// snapshot
+fn diff<T: Clone + PartialEq>(
+  previous_opt: &Option<HashMap<String, T>>,
+  current: &HashMap<String, T>,
+) -> impl Iterator<Item = (String, Option<T>, Option<T>)> {
+  let change_vec: Vec<(String, Option<T>, Option<T>)> = match previous_opt {
+    Some(previous_map) => current
+      .iter()
+      .filter(|(id, value)| previous_map.get(*id) != Some(*value))
+      .map(|(id, value)| {
+        (
+          id.clone(),
+          previous_map.get(id).cloned(),
+          Some(value.clone()),
+        )
+      })
+      .chain(
+        previous_map
+          .iter()
+          .filter(|(id, _)| !current.contains_key(*id))
+          .map(|(id, value)| (id.clone(), Some(value.clone()), None)),
+      )
+      .collect(),
+    None => Vec::new(),
+  };
+
+  change_vec.into_iter()
+}
+
+fn log_poll_error(site_backend: &SiteBackend, source: &str, error: Error) {
+  match error {
+    // Not all backends provide every source
+    Error::NotImplemented(_) => tracing::debug!(
+      "Site '{}' does not provide {}",
+      site_backend.site_name,
+      source
+    ),
+    _ => tracing::warn!(
+      "Could not poll {} of site '{}'. Reason:\n{}",
+      source,
+      site_backend.site_name,
+      error
+    ),
+  }
+}
+
+async fn get_node_states(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+) -> Result<HashMap<String, NodeState>, Error> {
+  let node_metadata = site_backend
+    .backend
+    .get(
+      auth_token,
+      None,
+      Some("Node"),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await?;
+
+  Ok(
+    node_metadata
+      .components
+      .unwrap_or_default()
+      .into_iter()
+      .filter_map(|component| {
+        Some((
+          component.id?,
+          NodeState {
+            state: component.state,
+            flag: component.flag,
+          },
+        ))
+      })
+      .collect(),
+  )
+}
+
+async fn get_cfs_component_states(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+) -> Result<HashMap<String, CfsComponentState>, Error> {
+  let component_vec = site_backend
+    .backend
+    .get_cfs_components(
+      auth_token,
+      &site_backend.site.shasta_base_url,
+      &site_backend.shasta_root_cert,
+      None,
+      None,
+      None,
+    )
+    .await?;
+
+  Ok(
+    component_vec
+      .into_iter()
+      .filter_map(|component| {
+        Some((
+          component.id?,
+          CfsComponentState {
+            desired_config: component.desired_config,
+            configuration_status: component.configuration_status,
+          },
+        ))
+      })
+      .collect(),
+  )
+}
+
+async fn get_cfs_session_states(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+) -> Result<HashMap<String, CfsSessionState>, Error> {
+  let session_vec = site_backend
+    .backend
+    .get_sessions(
+      auth_token,
+      &site_backend.site.shasta_base_url,
+      &site_backend.shasta_root_cert,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await?;
+
+  Ok(
+    session_vec
+      .into_iter()
+      .filter_map(|session| {
+        let session_status = session
+          .status
+          .as_ref()
+          .and_then(|status| status.session.as_ref());
+
+        Some((
+          session.name.clone()?,
+          CfsSessionState {
+            status: session_status.and_then(|status| status.status.clone()),
+            succeeded: session_status
+              .and_then(|status| status.succeeded.clone()),
+            xnames: session.get_target_xname().unwrap_or_default(),
+            groups: session.get_target_hsm().unwrap_or_default(),
+          },
+        ))
+      })
+      .collect(),
+  )
+}
+
+async fn get_power_transition_states(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+) -> Result<HashMap<String, PowerTransitionState>, Error> {
+  let transition_vec = site_backend
+    .backend
+    .get_power_transitions(auth_token)
+    .await?;
+
+  let get_field = |transition: &Value, field: &str| {
+    transition[field].as_str().map(str::to_string)
+  };
+
+  Ok(
+    transition_vec
+      .iter()
+      .filter_map(|transition| {
+        Some((
+          get_field(transition, "transitionID")?,
+          PowerTransitionState {
+            operation: get_field(transition, "operation"),
+            status: get_field(transition, "transitionStatus"),
+          },
+        ))
+      })
+      .collect(),
+  )
+}
+
+async fn get_power_transition_xnames(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  transition_id: &str,
+) -> Result<Vec<String>, Error> {
+  let transition = site_backend
+    .backend
+    .get_power_transition(auth_token, transition_id)
+    .await?;
+
+  Ok(
+    transition["tasks"]
+      .as_array()
+      .map(|task_vec| {
+        task_vec
+          .iter()
+          .filter_map(|task| task["xname"].as_str().map(str::to_string))
+          .collect()
+      })
+      .unwrap_or_default(),
+  )
+}
+
+#[cfg(test)]
+mod tests;
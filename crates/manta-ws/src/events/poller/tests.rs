@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use super::diff;
+
+fn snapshot(entry_vec: &[(&str, &str)]) -> HashMap<String, String> {
+  entry_vec
+    .iter()
+    .map(|(id, value)| (id.to_string(), value.to_string()))
+    .collect()
+}
+
+fn sorted_diff(
+  previous_opt: &Option<HashMap<String, String>>,
+  current: &HashMap<String, String>,
+) -> Vec<(String, Option<String>, Option<String>)> {
+  let mut change_vec: Vec<_> = diff(previous_opt, current).collect();
+  change_vec.sort_by(|a, b| a.0.cmp(&b.0));
+  change_vec
+}
+
+#[test]
+fn test_diff_first_snapshot_has_no_changes() {
+  let current = snapshot(&[("x1000c0s0b0n0", "Ready")]);
+
+  assert!(sorted_diff(&None, &current).is_empty());
+}
+
+#[test]
+fn test_diff_same_snapshot_has_no_changes() {
+  let current = snapshot(&[("x1000c0s0b0n0", "Ready")]);
+
+  assert!(sorted_diff(&Some(current.clone()), &current).is_empty());
+}
+
+#[test]
+fn test_diff_added_changed_and_removed() {
+  let previous = snapshot(&[
+    ("x1000c0s0b0n0", "Ready"),
+    ("x1000c0s0b0n1", "Ready"),
+    ("x1000c0s0b1n0", "Off"),
+  ]);
+  let current = snapshot(&[
+    ("x1000c0s0b0n0", "Ready"),
+    ("x1000c0s0b0n1", "Standby"),
+    ("x1000c0s0b1n1", "On"),
+  ]);
+
+  assert_eq!(
+    sorted_diff(&Some(previous), &current),
+    vec![
+      (
+        "x1000c0s0b0n1".to_string(),
+        Some("Ready".to_string()),
+        Some("Standby".to_string())
+      ),
+      ("x1000c0s0b1n0".to_string(), Some("Off".to_string()), None),
+      ("x1000c0s0b1n1".to_string(), None, Some("On".to_string())),
+    ]
+  );
+}
+
+#[test]
+fn test_diff_everything_removed() {
+  let previous = snapshot(&[("x1000c0s0b0n0", "Ready")]);
+
+  assert_eq!(
+    sorted_diff(&Some(previous), &HashMap::new()),
+    vec![("x1000c0s0b0n0".to_string(), Some("Ready".to_string()), None)]
+  );
+}
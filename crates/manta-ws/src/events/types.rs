@@ -0,0 +1,122 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Change detected by the poller of a site
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct Event {
+  pub site: String,
+  pub time: DateTime<Utc>,
+  #[serde(flatten)]
+  pub change: Change,
+}
+
+/// Values before and after the change. Previous values are missing for
+/// components, sessions or transitions created since the last poll and current
+/// values for the ones removed
+#[derive(Serialize, Debug, Clone, ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Change {
+  /// HSM component state or flag changed
+  NodeState {
+    xname: String,
+    previous_state: Option<String>,
+    state: Option<String>,
+    previous_flag: Option<String>,
+    flag: Option<String>,
+  },
+  /// CFS component configuration status or desired configuration changed
+  CfsComponent {
+    xname: String,
+    previous_desired_config: Option<String>,
+    desired_config: Option<String>,
+    previous_status: Option<String>,
+    configuration_status: Option<String>,
+  },
+  /// CFS session status changed
+  CfsSession {
+    name: String,
+    previous_status: Option<String>,
+    status: Option<String>,
+    succeeded: Option<String>,
+    xnames: Vec<String>,
+    groups: Vec<String>,
+  },
+  /// PCS transition status changed
+  PowerTransition {
+    id: String,
+    operation: Option<String>,
+    previous_status: Option<String>,
+    status: Option<String>,
+    xnames: Vec<String>,
+  },
+}
+
+impl Change {
+  /// Event type, eg 'node-state'
+  pub fn kind(&self) -> &'static str {
+    match self {
+      Change::NodeState { .. } => "node-state",
+      Change::CfsComponent { .. } => "cfs-component",
+      Change::CfsSession { .. } => "cfs-session",
+      Change::PowerTransition { .. } => "power-transition",
+    }
+  }
+
+  fn xnames(&self) -> Vec<&str> {
+    match self {
+      Change::NodeState { xname, .. } | Change::CfsComponent { xname, .. } => {
+        vec![xname]
+      }
+      Change::CfsSession { xnames, .. }
+      | Change::PowerTransition { xnames, .. } => {
+        xnames.iter().map(String::as_str).collect()
+      }
+    }
+  }
+
+  fn groups(&self) -> &[String] {
+    match self {
+      Change::CfsSession { groups, .. } => groups,
+      _ => &[],
+    }
+  }
+}
+
+/// Events a subscriber receives. Built when the subscriber connects, later
+/// changes to the group members are not taken into account
+#[derive(Debug, Clone)]
+pub struct EventFilter {
+  pub group_set: HashSet<String>,
+  pub xname_set: HashSet<String>,
+  /// Event types, all if missing
+  pub kind_vec: Option<Vec<String>>,
+}
+
+impl EventFilter {
+  /// True if the event is of a requested type and relates to a group or node
+  /// the subscriber has access to
+  pub fn matches(&self, event: &Event) -> bool {
+    let change = &event.change;
+
+    if self.kind_vec.as_ref().is_some_and(|kind_vec| {
+      !kind_vec.iter().any(|kind| kind == change.kind())
+    }) {
+      return false;
+    }
+
+    change
+      .groups()
+      .iter()
+      .any(|group| self.group_set.contains(group))
+      || change
+        .xnames()
+        .iter()
+        .any(|xname| self.xname_set.contains(*xname))
+  }
+}
+
+#[cfg(test)]
+mod tests;
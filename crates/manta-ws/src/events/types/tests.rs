@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+
+use super::{Change, Event, EventFilter};
+
+fn event(change: Change) -> Event {
+  Event {
+    site: "alps".to_string(),
+    time: Utc::now(),
+    change,
+  }
+}
+
+fn node_state(xname: &str) -> Event {
+  event(Change::NodeState {
+    xname: xname.to_string(),
+    previous_state: Some("Ready".to_string()),
+    state: Some("Off".to_string()),
+    previous_flag: None,
+    flag: None,
+  })
+}
+
+fn cfs_session(xnames: &[&str], groups: &[&str]) -> Event {
+  event(Change::CfsSession {
+    name: "batcher-4176d230".to_string(),
+    previous_status: Some("pending".to_string()),
+    status: Some("running".to_string()),
+    succeeded: Some("none".to_string()),
+    xnames: xnames.iter().map(|xname| xname.to_string()).collect(),
+    groups: groups.iter().map(|group| group.to_string()).collect(),
+  })
+}
+
+fn filter(
+  groups: &[&str],
+  xnames: &[&str],
+  kinds: Option<&[&str]>,
+) -> EventFilter {
+  EventFilter {
+    group_set: groups.iter().map(|group| group.to_string()).collect(),
+    xname_set: xnames.iter().map(|xname| xname.to_string()).collect(),
+    kind_vec: kinds
+      .map(|kind_vec| kind_vec.iter().map(|kind| kind.to_string()).collect()),
+  }
+}
+
+#[test]
+fn test_matches_node_in_filter() {
+  let filter = filter(&["zinal"], &["x1000c0s0b0n0"], None);
+
+  assert!(filter.matches(&node_state("x1000c0s0b0n0")));
+}
+
+#[test]
+fn test_matches_node_not_in_filter() {
+  let filter = filter(&["zinal"], &["x1000c0s0b0n0"], None);
+
+  assert!(!filter.matches(&node_state("x1000c0s0b0n1")));
+}
+
+#[test]
+fn test_matches_session_by_group() {
+  let filter = filter(&["zinal"], &[], None);
+
+  assert!(filter.matches(&cfs_session(&[], &["zinal"])));
+  assert!(!filter.matches(&cfs_session(&[], &["tasna"])));
+}
+
+#[test]
+fn test_matches_session_by_xname() {
+  let filter = filter(&["zinal"], &["x1000c0s0b0n0"], None);
+
+  assert!(filter.matches(&cfs_session(&["x1000c0s0b0n0"], &[])));
+  assert!(!filter.matches(&cfs_session(&["x1000c0s0b0n1"], &["tasna"])));
+}
+
+#[test]
+fn test_matches_event_types() {
+  let filter = filter(
+    &["zinal"],
+    &["x1000c0s0b0n0"],
+    Some(&["cfs-session", "power-transition"]),
+  );
+
+  assert!(filter.matches(&cfs_session(&[], &["zinal"])));
+  assert!(!filter.matches(&node_state("x1000c0s0b0n0")));
+}
+
+#[test]
+fn test_matches_nothing_without_access() {
+  let filter = EventFilter {
+    group_set: HashSet::new(),
+    xname_set: HashSet::new(),
+    kind_vec: None,
+  };
+
+  assert!(!filter.matches(&node_state("x1000c0s0b0n0")));
+  assert!(!filter.matches(&cfs_session(&["x1000c0s0b0n0"], &["zinal"])));
+}
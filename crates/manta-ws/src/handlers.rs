@@ -1,3 +1,3 @@
-mod get_kernel_parameters;
+pub mod get_kernel_parameters;
 
 pub use crate::handlers::get_kernel_parameters::get_kernel_parameters;
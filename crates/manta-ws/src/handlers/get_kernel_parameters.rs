@@ -9,6 +9,20 @@ use std::collections::HashMap;
 use crate::backend_api::*;
 use crate::http_response::*;
 
+#[utoipa::path(
+    get,
+    path = "/kernel-parameters",
+    params(
+        ("dc" = String, Query, description = "Site name"),
+        ("node" = Vec<String>, Query, description = "Node xname, can be repeated")
+    ),
+    responses(
+        (status = 200, description = "Kernel parameters per node", body = HashMap<String, String>),
+        (status = 400, description = "Missing or unrecognized query parameters"),
+        (status = 404, description = "Kernel parameters not found for some nodes")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_kernel_parameters(
   headers: HeaderMap,
   Query(params): Query<Vec<(String, String)>>,
@@ -1,10 +1,12 @@
 use axum::{
+  Json,
   http::StatusCode,
   response::{IntoResponse, Response},
 };
+use manta_backend_dispatcher::error::Error;
 use std::collections::HashMap;
 
-use crate::log::*;
+use crate::{log::*, metrics::METRICS};
 
 pub struct ErrorResponse {
   pub code: axum::http::StatusCode,
@@ -38,3 +40,18 @@ impl ErrorResponse {
 pub fn error_respond(code: StatusCode, reason: String) -> Response {
   ErrorResponse::new(code, reason).respond()
 }
+
+/// HTTP status code to answer with when a backend operation fails, eg 404 if the
+/// resource does not exist or 501 if the backend does not support the operation.
+/// The error is counted in the '/metrics' backend errors
+pub fn backend_error_status(error: &Error) -> StatusCode {
+  METRICS.count_backend_error(error);
+
+  StatusCode::from_u16(error.http_status())
+    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Response to a request whose backend operation failed
+pub fn backend_error_response(error: Error) -> Response {
+  (backend_error_status(&error), Json(error.to_string())).into_response()
+}
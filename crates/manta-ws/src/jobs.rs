@@ -0,0 +1,9 @@
+mod executor;
+mod store;
+mod types;
+
+pub use crate::jobs::executor::start;
+pub use crate::jobs::store::JobStore;
+pub use crate::jobs::types::{
+  Job, JobLogEntry, JobOperation, JobRequest, JobStatus,
+};
@@ -0,0 +1,216 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{bss::BootParametersTrait, pcs::PCSTrait},
+};
+use serde_json::Value;
+
+use crate::{
+  commands::sat_file::apply_sat_file,
+  common::{
+    authorization,
+    site_backend::{SiteBackend, Sites},
+  },
+  jwt_utils::get_claims_from_jwt_token,
+};
+
+use super::{
+  store::JobStore,
+  types::{Job, JobOperation, JobStatus},
+};
+
+/// Seconds between two checks of the jobs whose time window started
+const CHECK_INTERVAL: u64 = 10;
+
+/// Starts the background task running the jobs once their time window starts
+pub fn start(job_store: Arc<JobStore>, sites: Arc<Sites>) {
+  tokio::spawn(async move {
+    loop {
+      match job_store.take_due_jobs() {
+        Ok(due_job_vec) => {
+          for (job, auth_token) in due_job_vec {
+            tokio::spawn(run(
+              job_store.clone(),
+              sites.clone(),
+              job,
+              auth_token,
+            ));
+          }
+        }
+        Err(e) => tracing::error!("Could not check scheduled jobs: {}", e),
+      }
+
+      tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL)).await;
+    }
+  });
+}
+
+async fn run(
+  job_store: Arc<JobStore>,
+  sites: Arc<Sites>,
+  job: Job,
+  auth_token: String,
+) {
+  tracing::info!("Running job {} ({})", job.id, job.operation.name());
+
+  let site_backend_rslt = sites.get(Some(&job.site));
+
+  let result = match &site_backend_rslt {
+    Ok(site_backend) => {
+      execute(site_backend, &auth_token, &job.operation).await
+    }
+    Err(e) => Err(Error::Message(e.to_string())),
+  };
+
+  if let Ok(site_backend) = &site_backend_rslt {
+    site_backend
+      .audit(
+        &auth_token,
+        job.operation.name(),
+        &job
+          .operation
+          .xnames()
+          .iter()
+          .map(String::as_str)
+          .collect::<Vec<_>>(),
+        result.is_ok(),
+      )
+      .await;
+  }
+
+  let update_rslt = job_store.update(job.id, |job| {
+    match &result {
+      Ok(message) => {
+        job.log(message.as_str());
+        job.status = JobStatus::Succeeded;
+      }
+      Err(e) => {
+        job.log(e.to_string());
+        job.status = JobStatus::Failed;
+      }
+    }
+
+    job.finished = Some(Utc::now());
+  });
+
+  if let Err(e) = update_rslt {
+    tracing::error!("Could not save result of job {}: {}", job.id, e);
+  }
+}
+
+/// Runs the operation of a job. Returns a message describing the result
+async fn execute(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  operation: &JobOperation,
+) -> Result<String, Error> {
+  // Tokens are stored when the job is scheduled and may expire before the
+  // job runs
+  let token_expired = get_claims_from_jwt_token(auth_token)
+    .ok()
+    .and_then(|claims| claims["exp"].as_i64())
+    .is_some_and(|exp| exp < Utc::now().timestamp());
+
+  if token_expired {
+    return Err(Error::Unauthorized {
+      service: "manta-ws".to_string(),
+      detail: "Authentication token of the job expired".to_string(),
+    });
+  }
+
+  let backend = &site_backend.backend;
+
+  // Group membership may have changed since the job was scheduled
+  authorization::validate_target_hsm_members(
+    backend,
+    auth_token,
+    operation.xnames(),
+  )
+  .await?;
+
+  match operation {
+    JobOperation::PowerOn { xnames } => {
+      let power_summary = backend.power_on_sync(auth_token, xnames).await?;
+      Ok(get_power_summary_message(&power_summary))
+    }
+    JobOperation::PowerOff { xnames, force } => {
+      let power_summary =
+        backend.power_off_sync(auth_token, xnames, *force).await?;
+      Ok(get_power_summary_message(&power_summary))
+    }
+    JobOperation::PowerReset { xnames, force } => {
+      let power_summary =
+        backend.power_reset_sync(auth_token, xnames, *force).await?;
+      Ok(get_power_summary_message(&power_summary))
+    }
+    JobOperation::BootImage {
+      xnames,
+      image_id,
+      kernel_parameters,
+    } => {
+      let boot_parameter_vec =
+        backend.get_bootparameters(auth_token, xnames).await?;
+
+      // Kernel parameters go first because changing them also resets the
+      // boot image
+      for mut boot_parameter in boot_parameter_vec {
+        if let Some(kernel_parameters) = kernel_parameters {
+          boot_parameter.apply_kernel_params(kernel_parameters);
+        }
+
+        boot_parameter.update_boot_image(image_id)?;
+
+        backend
+          .update_bootparameters(auth_token, &boot_parameter)
+          .await?;
+      }
+
+      Ok(format!(
+        "Boot image of nodes {} set to '{}'",
+        xnames.join(", "),
+        image_id
+      ))
+    }
+    JobOperation::SatFile {
+      sat_file,
+      do_not_reboot,
+      dry_run,
+    } => {
+      apply_sat_file(
+        backend,
+        &site_backend.site_name,
+        &site_backend.site,
+        &site_backend.shasta_root_cert,
+        auth_token,
+        sat_file,
+        *do_not_reboot,
+        *dry_run,
+      )
+      .await?;
+
+      Ok("SAT file applied".to_string())
+    }
+  }
+}
+
+/// Summary of a PCS power transition, eg: 'Transition <id> completed: 3
+/// succeeded, 1 failed'
+fn get_power_summary_message(power_summary: &Value) -> String {
+  format!(
+    "Transition {} {}: {} succeeded, {} failed",
+    power_summary["transitionID"].as_str().unwrap_or_default(),
+    power_summary["transitionStatus"]
+      .as_str()
+      .unwrap_or_default(),
+    power_summary
+      .pointer("/taskCounts/succeeded")
+      .and_then(Value::as_u64)
+      .unwrap_or_default(),
+    power_summary
+      .pointer("/taskCounts/failed")
+      .and_then(Value::as_u64)
+      .unwrap_or_default()
+  )
+}
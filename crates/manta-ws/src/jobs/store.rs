@@ -0,0 +1,208 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+use chrono::Utc;
+use manta_backend_dispatcher::error::Error;
+use serde::{Deserialize, Serialize};
+
+use super::types::{Job, JobRequest, JobStatus};
+
+/// Job as persisted on disk. The authentication token of the user who
+/// scheduled the job is needed to run it later and is never sent back to the
+/// API clients
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StoredJob {
+  #[serde(flatten)]
+  job: Job,
+  auth_token: String,
+}
+
+/// Jobs persisted to a JSON file. The file is rewritten after each change
+pub struct JobStore {
+  path: PathBuf,
+  job_vec: Mutex<Vec<StoredJob>>,
+}
+
+impl JobStore {
+  /// Loads the jobs from the file. Jobs left running by a previous instance
+  /// of the server are marked as failed
+  pub fn open(path: &Path) -> Result<Self, Error> {
+    let mut job_vec: Vec<StoredJob> = if path.exists() {
+      serde_json::from_str(&fs::read_to_string(path)?)?
+    } else {
+      Vec::new()
+    };
+
+    for stored_job in job_vec
+      .iter_mut()
+      .filter(|stored_job| stored_job.job.status == JobStatus::Running)
+    {
+      stored_job.job.status = JobStatus::Failed;
+      stored_job.job.finished = Some(Utc::now());
+      stored_job.job.log("Interrupted by a restart of the server");
+    }
+
+    let job_store = JobStore {
+      path: path.to_path_buf(),
+      job_vec: Mutex::new(job_vec),
+    };
+
+    job_store.save(&job_store.job_vec.lock().unwrap())?;
+
+    Ok(job_store)
+  }
+
+  fn save(&self, job_vec: &[StoredJob]) -> Result<(), Error> {
+    if let Some(parent) = self.path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    // Write to a temporary file and rename it so the store is never left
+    // half written
+    let tmp_path = self.path.with_extension("json.tmp");
+
+    fs::write(&tmp_path, serde_json::to_string_pretty(job_vec)?)?;
+
+    #[cfg(unix)]
+    {
+      use std::os::unix::fs::PermissionsExt;
+      fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    fs::rename(&tmp_path, &self.path)?;
+
+    Ok(())
+  }
+
+  /// Adds a new pending job and returns it
+  pub fn create(
+    &self,
+    owner: &str,
+    site: &str,
+    auth_token: &str,
+    job_request: JobRequest,
+  ) -> Result<Job, Error> {
+    let mut job_vec = self.job_vec.lock().unwrap();
+
+    let now = Utc::now();
+
+    let mut job = Job {
+      id: job_vec
+        .iter()
+        .map(|stored_job| stored_job.job.id)
+        .max()
+        .unwrap_or(0)
+        + 1,
+      owner: owner.to_string(),
+      site: site.to_string(),
+      operation: job_request.operation,
+      status: JobStatus::Pending,
+      created: now,
+      not_before: job_request.not_before.unwrap_or(now),
+      not_after: job_request.not_after,
+      started: None,
+      finished: None,
+      logs: Vec::new(),
+    };
+
+    job.log(format!("Scheduled by {}", owner));
+
+    job_vec.push(StoredJob {
+      job: job.clone(),
+      auth_token: auth_token.to_string(),
+    });
+
+    self.save(&job_vec)?;
+
+    Ok(job)
+  }
+
+  pub fn get(&self, id: u64) -> Option<Job> {
+    self
+      .job_vec
+      .lock()
+      .unwrap()
+      .iter()
+      .find(|stored_job| stored_job.job.id == id)
+      .map(|stored_job| stored_job.job.clone())
+  }
+
+  pub fn list(&self) -> Vec<Job> {
+    self
+      .job_vec
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|stored_job| stored_job.job.clone())
+      .collect()
+  }
+
+  /// Applies a change to a job and persists it. Returns the job updated
+  pub fn update(
+    &self,
+    id: u64,
+    f: impl FnOnce(&mut Job),
+  ) -> Result<Job, Error> {
+    let mut job_vec = self.job_vec.lock().unwrap();
+
+    let stored_job = job_vec
+      .iter_mut()
+      .find(|stored_job| stored_job.job.id == id)
+      .ok_or_else(|| Error::NotFound {
+        service: "manta-ws".to_string(),
+        resource: "job".to_string(),
+        id: id.to_string(),
+      })?;
+
+    f(&mut stored_job.job);
+
+    let job = stored_job.job.clone();
+
+    self.save(&job_vec)?;
+
+    Ok(job)
+  }
+
+  /// Marks as running the pending jobs whose time window started and as
+  /// expired the ones whose time window is over. Returns the jobs to run with
+  /// the authentication token to run them
+  pub fn take_due_jobs(&self) -> Result<Vec<(Job, String)>, Error> {
+    let mut job_vec = self.job_vec.lock().unwrap();
+
+    let now = Utc::now();
+
+    let mut due_job_vec = Vec::new();
+
+    for stored_job in job_vec
+      .iter_mut()
+      .filter(|stored_job| stored_job.job.status == JobStatus::Pending)
+      .filter(|stored_job| stored_job.job.not_before <= now)
+    {
+      if stored_job
+        .job
+        .not_after
+        .is_some_and(|not_after| not_after < now)
+      {
+        stored_job.job.status = JobStatus::Expired;
+        stored_job.job.finished = Some(now);
+        stored_job.job.log("Time window is over, job not executed");
+      } else {
+        stored_job.job.status = JobStatus::Running;
+        stored_job.job.started = Some(now);
+        stored_job.job.log("Started");
+        due_job_vec
+          .push((stored_job.job.clone(), stored_job.auth_token.clone()));
+      }
+    }
+
+    self.save(&job_vec)?;
+
+    Ok(due_job_vec)
+  }
+}
+
+#[cfg(test)]
+mod tests;
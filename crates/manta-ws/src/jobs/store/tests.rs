@@ -0,0 +1,180 @@
+use std::{fs, path::PathBuf};
+
+use chrono::{Duration, Utc};
+
+use crate::jobs::{JobOperation, JobRequest, JobStatus};
+
+use super::JobStore;
+
+/// Path of the store in a directory only used by the test
+fn store_path(test_name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!(
+    "manta-ws-jobs-{}-{}",
+    std::process::id(),
+    test_name
+  ));
+
+  let _ = fs::remove_dir_all(&dir);
+
+  dir.join("jobs.json")
+}
+
+fn job_request(
+  not_before_opt: Option<Duration>,
+  not_after_opt: Option<Duration>,
+) -> JobRequest {
+  let now = Utc::now();
+
+  JobRequest {
+    operation: JobOperation::PowerOff {
+      xnames: vec!["x1000c0s0b0n0".to_string()],
+      force: false,
+    },
+    not_before: not_before_opt.map(|offset| now + offset),
+    not_after: not_after_opt.map(|offset| now + offset),
+  }
+}
+
+#[test]
+fn test_create_and_reopen() {
+  let path = store_path("reopen");
+
+  let job_store = JobStore::open(&path).unwrap();
+
+  let job_1 = job_store
+    .create("alice", "alps", "token-1", job_request(None, None))
+    .unwrap();
+  let job_2 = job_store
+    .create("bob", "alps", "token-2", job_request(None, None))
+    .unwrap();
+
+  assert_eq!(job_1.id, 1);
+  assert_eq!(job_2.id, 2);
+  assert_eq!(job_1.status, JobStatus::Pending);
+
+  let job_store = JobStore::open(&path).unwrap();
+
+  let job_vec = job_store.list();
+  assert_eq!(job_vec.len(), 2);
+  assert_eq!(job_store.get(2).unwrap().owner, "bob");
+  assert!(job_store.get(3).is_none());
+
+  // Tokens are persisted to run the jobs later but never returned
+  assert!(fs::read_to_string(&path).unwrap().contains("token-1"));
+  assert!(!serde_json::to_string(&job_vec).unwrap().contains("token-1"));
+
+  fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_only_readable_by_owner() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let path = store_path("permissions");
+
+  let job_store = JobStore::open(&path).unwrap();
+
+  job_store
+    .create("alice", "alps", "token", job_request(None, None))
+    .unwrap();
+
+  let mode = fs::metadata(&path).unwrap().permissions().mode();
+
+  assert_eq!(mode & 0o777, 0o600);
+
+  fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_running_jobs_fail_on_restart() {
+  let path = store_path("restart");
+
+  let job_store = JobStore::open(&path).unwrap();
+
+  let job = job_store
+    .create("alice", "alps", "token", job_request(None, None))
+    .unwrap();
+
+  job_store
+    .update(job.id, |job| job.status = JobStatus::Running)
+    .unwrap();
+
+  let job_store = JobStore::open(&path).unwrap();
+
+  let job = job_store.get(job.id).unwrap();
+  assert_eq!(job.status, JobStatus::Failed);
+  assert!(job.finished.is_some());
+
+  fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_take_due_jobs() {
+  let path = store_path("due");
+
+  let job_store = JobStore::open(&path).unwrap();
+
+  let due_job = job_store
+    .create("alice", "alps", "token-due", job_request(None, None))
+    .unwrap();
+  let future_job = job_store
+    .create(
+      "alice",
+      "alps",
+      "token-future",
+      job_request(Some(Duration::hours(1)), None),
+    )
+    .unwrap();
+  let expired_job = job_store
+    .create(
+      "alice",
+      "alps",
+      "token-expired",
+      job_request(Some(Duration::hours(-2)), Some(Duration::hours(-1))),
+    )
+    .unwrap();
+
+  let due_job_vec = job_store.take_due_jobs().unwrap();
+
+  assert_eq!(due_job_vec.len(), 1);
+  assert_eq!(due_job_vec[0].0.id, due_job.id);
+  assert_eq!(due_job_vec[0].1, "token-due");
+
+  assert_eq!(
+    job_store.get(due_job.id).unwrap().status,
+    JobStatus::Running
+  );
+  assert!(job_store.get(due_job.id).unwrap().started.is_some());
+  assert_eq!(
+    job_store.get(future_job.id).unwrap().status,
+    JobStatus::Pending
+  );
+  assert_eq!(
+    job_store.get(expired_job.id).unwrap().status,
+    JobStatus::Expired
+  );
+
+  // Jobs are only taken once
+  assert!(job_store.take_due_jobs().unwrap().is_empty());
+
+  // Changes are persisted
+  let job_store = JobStore::open(&path).unwrap();
+  assert_eq!(
+    job_store.get(expired_job.id).unwrap().status,
+    JobStatus::Expired
+  );
+
+  fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_update_unknown_job() {
+  let path = store_path("update");
+
+  let job_store = JobStore::open(&path).unwrap();
+
+  assert!(job_store.update(1, |_| {}).is_err());
+
+  fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
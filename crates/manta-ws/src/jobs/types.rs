@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Operation executed by a job
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum JobOperation {
+  PowerOn {
+    xnames: Vec<String>,
+  },
+  PowerOff {
+    xnames: Vec<String>,
+    #[serde(default)]
+    force: bool,
+  },
+  PowerReset {
+    xnames: Vec<String>,
+    #[serde(default)]
+    force: bool,
+  },
+  /// Changes the boot image (and optionally the kernel parameters) of the
+  /// nodes. Nodes are not rebooted
+  BootImage {
+    xnames: Vec<String>,
+    image_id: String,
+    kernel_parameters: Option<String>,
+  },
+  /// Applies a SAT file. The file must already be rendered, values files are
+  /// not supported
+  SatFile {
+    sat_file: String,
+    #[serde(default)]
+    do_not_reboot: bool,
+    #[serde(default)]
+    dry_run: bool,
+  },
+}
+
+impl JobOperation {
+  /// Nodes affected by the operation, empty for SAT files
+  pub fn xnames(&self) -> &[String] {
+    match self {
+      JobOperation::PowerOn { xnames }
+      | JobOperation::PowerOff { xnames, .. }
+      | JobOperation::PowerReset { xnames, .. }
+      | JobOperation::BootImage { xnames, .. } => xnames,
+      JobOperation::SatFile { .. } => &[],
+    }
+  }
+
+  /// Name of the operation used in the audit events
+  pub fn name(&self) -> &'static str {
+    match self {
+      JobOperation::PowerOn { .. } => "power on nodes",
+      JobOperation::PowerOff { .. } => "power off nodes",
+      JobOperation::PowerReset { .. } => "power reset nodes",
+      JobOperation::BootImage { .. } => "apply boot nodes",
+      JobOperation::SatFile { .. } => "apply sat-file",
+    }
+  }
+}
+
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+  /// Waiting for its time window
+  Pending,
+  Running,
+  Succeeded,
+  Failed,
+  Cancelled,
+  /// Time window finished before the job could start
+  Expired,
+}
+
+impl JobStatus {
+  pub fn is_finished(&self) -> bool {
+    !matches!(self, JobStatus::Pending | JobStatus::Running)
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct JobLogEntry {
+  pub timestamp: DateTime<Utc>,
+  pub message: String,
+}
+
+/// Body of the request to schedule a job
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct JobRequest {
+  pub operation: JobOperation,
+  /// Job starts as soon as possible if missing
+  pub not_before: Option<DateTime<Utc>>,
+  /// Job expires if it could not start before this time
+  pub not_after: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Job {
+  pub id: u64,
+  pub owner: String,
+  pub site: String,
+  pub operation: JobOperation,
+  pub status: JobStatus,
+  pub created: DateTime<Utc>,
+  pub not_before: DateTime<Utc>,
+  pub not_after: Option<DateTime<Utc>>,
+  pub started: Option<DateTime<Utc>>,
+  pub finished: Option<DateTime<Utc>>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub logs: Vec<JobLogEntry>,
+}
+
+impl Job {
+  pub fn log(&mut self, message: impl Into<String>) {
+    self.logs.push(JobLogEntry {
+      timestamp: Utc::now(),
+      message: message.into(),
+    });
+  }
+}
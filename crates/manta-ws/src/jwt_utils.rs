@@ -1,15 +1,29 @@
 use std::error::Error;
 
-use base64::decode;
+use axum::http::HeaderMap;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use serde_json::Value;
 
+/// Claims of the token. The signature is not checked, use `TokenValidator` for
+/// tokens sent to the API
 pub fn get_claims_from_jwt_token(token: &str) -> Result<Value, Box<dyn Error>> {
-  let base64_claims = token.split(".").nth(1).expect("JWT Token not valid");
+  let base64_claims = token.split(".").nth(1).ok_or("JWT Token not valid")?;
 
-  let claims_u8 = decode(base64_claims).unwrap();
+  // JWT segments are base64url encoded without padding
+  let claims_u8 =
+    URL_SAFE_NO_PAD.decode(base64_claims.trim_end_matches('='))?;
 
-  Ok(
-    serde_json::from_str::<Value>(std::str::from_utf8(&claims_u8).unwrap())
-      .unwrap(),
-  )
+  Ok(serde_json::from_str::<Value>(std::str::from_utf8(
+    &claims_u8,
+  )?)?)
+}
+
+/// Authentication token in the 'authorization' header
+pub fn get_auth_token(headers: &HeaderMap) -> Option<&str> {
+  headers
+    .get("authorization")?
+    .to_str()
+    .ok()?
+    .split(" ")
+    .nth(1)
 }
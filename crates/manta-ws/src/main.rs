@@ -1,46 +1,54 @@
+mod auth;
 mod backend_api;
 mod commands;
 mod common;
+mod events;
 mod handlers;
 mod http_response;
+mod jobs;
 mod jwt_utils;
 mod log;
 mod manta_backend_dispatcher;
+mod metrics;
 
 use ::manta_backend_dispatcher::{
+  contracts::BackendTrait,
   interfaces::{
     bss::BootParametersTrait, cfs::CfsTrait, hsm::group::GroupTrait,
     pcs::PCSTrait,
   },
-  types::{BootParameters, K8sAuth, K8sDetails},
+  types::{
+    BootParameters, Group, K8sAuth, K8sDetails,
+    cfs::session::CfsSessionGetResponse,
+    pcs::power_status::types::PowerStatusAll,
+  },
 };
 use axum::{
-  Json, Router, debug_handler,
+  Extension, Json, Router, ServiceExt, debug_handler,
   extract::{
-    ConnectInfo, Path, Query, WebSocketUpgrade,
+    ConnectInfo, FromRef, Path, Query, Request, State, WebSocketUpgrade,
     ws::{Message, Utf8Bytes, WebSocket},
   },
   http::{HeaderMap, StatusCode},
+  middleware,
   response::{IntoResponse, Response},
-  routing::{delete, get, post, put},
+  routing::{MethodRouter, Route, delete, get, post, put},
 };
 use axum_extra::{TypedHeader, headers};
 use bytes::Bytes;
-use common::config::types::MantaConfiguration;
-use config::Config;
 use csm_rs::{
   common::vault::http_client::fetch_shasta_k8s_secrets_from_vault,
   hsm::hw_inventory::hw_component::types::NodeSummary,
 };
-use directories::ProjectDirs;
 use futures::{AsyncBufReadExt, SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
-  fs::File, io::Read, net::SocketAddr, ops::ControlFlow, path::PathBuf,
+  convert::Infallible, net::SocketAddr, ops::ControlFlow, path::PathBuf,
   sync::Arc,
 };
 use tokio::{io::AsyncWriteExt, sync::Semaphore};
+use tower::{Layer, Service};
 use tower_http::{
   cors::CorsLayer,
   services::ServeDir,
@@ -50,7 +58,11 @@ use tracing_subscriber::{
   prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt,
 };
 
-use crate::jwt_utils::get_claims_from_jwt_token;
+use crate::auth::AuthUser;
+use crate::common::site_backend::{
+  SiteBackend, SiteSummary, Sites, require_site, select_site,
+};
+use crate::http_response::backend_error_status;
 
 use tokio_util::io::ReaderStream;
 
@@ -58,12 +70,27 @@ use anyhow::{Result, bail};
 
 use crate::handlers::*;
 
-use manta_backend_dispatcher::StaticBackendDispatcher;
-
-use commands::redfish::{
-  delete_redfish, get_all_redfish, get_redfish, post_redfish,
+use commands::{
+  configurations::get_configurations,
+  events::{get_events, ws_events},
+  group_members::{delete_group_member, post_group_members},
+  hw_cluster::{post_hw_cluster_pin, post_hw_cluster_unpin},
+  images::get_images,
+  jobs::{delete_job, get_all_jobs, get_job, get_job_logs, post_job},
+  kernel_parameters::{apply_kernel_parameters, delete_kernel_parameters},
+  metrics::get_metrics,
+  migrate_nodes::node_migration,
+  redfish::{delete_redfish, get_all_redfish, get_redfish, post_redfish},
+  sat_file::post_sat_file,
+  templates::get_templates,
+};
+use utoipa::{
+  IntoParams, Modify, OpenApi, ToSchema,
+  openapi::{
+    OpenApi as OpenApiDoc,
+    security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+  },
 };
-use utoipa::{OpenApi, ToSchema, openapi::OpenApi as OpenApiDoc};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -72,10 +99,127 @@ use utoipa::{OpenApi, ToSchema, openapi::OpenApi as OpenApiDoc};
     description = "API for managing Manta services",
     version = "0.1.2"
   ),
-  paths(root, test_ping, test_whoami, get_openapi, get_version, create_user,)
+  paths(
+    root,
+    test_ping,
+    test_whoami,
+    test_ws,
+    get_openapi,
+    get_version,
+    get_capabilities,
+    get_sites,
+    create_user,
+    authenticate,
+    get_cfs_health_check,
+    get_bos_health_check,
+    get_cfs_session,
+    ws_cfs_session_logs,
+    ws_console,
+    handlers::get_kernel_parameters::get_kernel_parameters,
+    commands::kernel_parameters::apply_kernel_parameters,
+    commands::kernel_parameters::delete_kernel_parameters,
+    get_all_bss_boot_parameters,
+    get_bss_boot_parameters,
+    post_bss_boot_parameters,
+    delete_bss_boot_parameters,
+    commands::redfish::get_all_redfish,
+    commands::redfish::get_redfish,
+    commands::redfish::post_redfish,
+    commands::redfish::delete_redfish,
+    get_all_groups,
+    get_group_details,
+    get_hsm_hardware,
+    commands::group_members::post_group_members,
+    commands::group_members::delete_group_member,
+    power_off_node,
+    power_on_node,
+    power_reset_node,
+    power_status_node,
+    commands::migrate_nodes::node_migration,
+    commands::images::get_images,
+    commands::configurations::get_configurations,
+    commands::templates::get_templates,
+    commands::hw_cluster::post_hw_cluster_pin,
+    commands::hw_cluster::post_hw_cluster_unpin,
+    commands::sat_file::post_sat_file,
+    commands::jobs::get_all_jobs,
+    commands::jobs::post_job,
+    commands::jobs::get_job,
+    commands::jobs::delete_job,
+    commands::jobs::get_job_logs,
+    commands::events::get_events,
+    commands::events::ws_events,
+    commands::metrics::get_metrics,
+  ),
+  modifiers(&SecurityAddon)
 )]
 pub struct ApiDoc;
 
+/// State shared by the handlers
+#[derive(Clone, FromRef)]
+struct AppState {
+  job_store: Arc<jobs::JobStore>,
+  sites: Arc<Sites>,
+}
+
+/// Router recording the path of its routes, so they can be checked against
+/// the OpenApi document
+#[derive(Default)]
+struct ApiRouter {
+  router: Router<AppState>,
+  path_vec: Vec<&'static str>,
+}
+
+impl ApiRouter {
+  fn route(
+    mut self,
+    path: &'static str,
+    method_router: MethodRouter<AppState>,
+  ) -> Self {
+    self.router = self.router.route(path, method_router);
+    self.path_vec.push(path);
+    self
+  }
+
+  fn merge(mut self, other: ApiRouter) -> Self {
+    self.router = self.router.merge(other.router);
+    self.path_vec.extend(other.path_vec);
+    self
+  }
+
+  fn route_layer<L>(mut self, layer: L) -> Self
+  where
+    L: Layer<Route> + Clone + Send + Sync + 'static,
+    L::Service: Service<Request> + Clone + Send + Sync + 'static,
+    <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+    <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+    <L::Service as Service<Request>>::Future: Send + 'static,
+  {
+    self.router = self.router.route_layer(layer);
+    self
+  }
+}
+
+/// Adds the bearer token authentication used by the endpoints talking to the
+/// backend
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+  fn modify(&self, openapi: &mut OpenApiDoc) {
+    if let Some(components) = openapi.components.as_mut() {
+      components.add_security_scheme(
+        "bearer_auth",
+        SecurityScheme::Http(
+          HttpBuilder::new()
+            .scheme(HttpAuthScheme::Bearer)
+            .bearer_format("JWT")
+            .build(),
+        ),
+      );
+    }
+  }
+}
+
 #[tokio::main]
 async fn main() {
   // initialize tracing
@@ -89,40 +233,115 @@ async fn main() {
 
   let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
 
-  // build our application with a route
-  let app = Router::new()
-    .fallback_service(
-      ServeDir::new(assets_dir).append_index_html_on_directories(true),
-    )
-    // `GET /` goes to `root`
-    .route("/", get(root))
+  // Backend of every site in the configuration
+  let sites = match Sites::load().await {
+    Ok(sites) => Arc::new(sites),
+    Err(e) => {
+      eprintln!("ERROR - Could not load configuration. Reason:\n{}", e);
+      std::process::exit(1);
+    }
+  };
+
+  // Scheduled jobs
+  let job_store =
+    match jobs::JobStore::open(&common::config::get_default_jobs_file_path()) {
+      Ok(job_store) => Arc::new(job_store),
+      Err(e) => {
+        eprintln!("ERROR - Could not load scheduled jobs. Reason:\n{}", e);
+        std::process::exit(1);
+      }
+    };
+
+  jobs::start(job_store.clone(), sites.clone());
+
+  // Changes pushed to the '/events' subscribers
+  events::start(&sites);
+
+  let app = build_app(
+    api_routes(),
+    AppState {
+      job_store,
+      sites: sites.clone(),
+    },
+    assets_dir,
+  );
+
+  // The site is selected before routing so the '/sites/{site}' prefix can be
+  // removed from the path
+  let app = middleware::from_fn_with_state(sites, select_site).layer(app);
+
+  // run our app with hyper
+  // `axum::Server` is a re-export of `hyper::Server`
+  let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
+  println!("listening on {}", addr);
+  //    axum::Server::bind(&addr)
+  //        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+  //        .await
+  //        .unwrap();
+  axum::serve(
+    tokio::net::TcpListener::bind(addr).await.unwrap(),
+    ServiceExt::<Request>::into_make_service_with_connect_info::<SocketAddr>(
+      app,
+    ),
+  )
+  .await
+  .unwrap()
+}
+
+/// Routes of the API, all of them must be in `ApiDoc`
+fn api_routes() -> ApiRouter {
+  // Routes requiring a valid token
+  let authenticated_routes = ApiRouter::default()
     .route("/test/whoami", get(test_whoami))
-    .route("/test/ping", get(test_ping))
-    .route("/test/ws", get(test_ws))
-    .route("/openapi", get(get_openapi))
-    .route("/version", get(get_version))
-    .route("/users", post(create_user))
     .route("/cfs/health", get(get_cfs_health_check))
     .route("/bos/health", get(get_bos_health_check))
-    .route("/kernel-parameters", get(get_kernel_parameters))
+    .route(
+      "/kernel-parameters",
+      get(get_kernel_parameters)
+        .put(apply_kernel_parameters)
+        .delete(delete_kernel_parameters),
+    )
     .route("/bss/boot-parameters", get(get_all_bss_boot_parameters))
     .route("/bss/boot-parameters/{xname}", get(get_bss_boot_parameters))
-    .route("/bss/boot-parameters", post(post_bss_boot_parameters))
+    .route(
+      "/bss/boot-parameters",
+      post(post_bss_boot_parameters).route_layer(
+        middleware::from_fn_with_state(auth::ADMIN_ROLES, auth::require_roles),
+      ),
+    )
     .route(
       "/bss/boot-parameters/{xname}",
-      delete(delete_bss_boot_parameters),
+      delete(delete_bss_boot_parameters).route_layer(
+        middleware::from_fn_with_state(auth::ADMIN_ROLES, auth::require_roles),
+      ),
     )
     .route("/redfish", get(get_all_redfish))
     .route("/redfish/{xname}", get(get_redfish))
-    .route("/redfish", post(post_redfish))
-    .route("/redfish/{xname}", delete(delete_redfish))
-    .route("/authenticate", get(authenticate))
+    .route(
+      "/redfish",
+      post(post_redfish).route_layer(middleware::from_fn_with_state(
+        auth::ADMIN_ROLES,
+        auth::require_roles,
+      )),
+    )
+    .route(
+      "/redfish/{xname}",
+      delete(delete_redfish).route_layer(middleware::from_fn_with_state(
+        auth::ADMIN_ROLES,
+        auth::require_roles,
+      )),
+    )
     .route("/console/{xname}", get(ws_console))
     .route("/cfssession/{cfssession}", get(get_cfs_session))
     .route("/cfssession/{cfssession}/logs", get(ws_cfs_session_logs))
     .route("/group", get(get_all_groups))
     .route("/group/{group}", get(get_group_details))
     .route("/group/{group}/hardware", get(get_hsm_hardware))
+    .route("/group/{group}/members", post(post_group_members))
+    .route(
+      "/group/{group}/members/{xname}",
+      delete(delete_group_member),
+    )
     .route("/node/{node}/power-off", get(power_off_node))
     .route("/node/{node}/power-on", get(power_on_node))
     .route("/node/{node}/power-reset", get(power_reset_node))
@@ -131,23 +350,58 @@ async fn main() {
       "/node-migration/target/{target}/parent/{parent}",
       put(node_migration),
     )
+    .route("/images", get(get_images))
+    .route("/configurations", get(get_configurations))
+    .route("/templates", get(get_templates))
+    .route("/hw-cluster/pin", post(post_hw_cluster_pin))
+    .route("/hw-cluster/unpin", post(post_hw_cluster_unpin))
+    .route("/sat-file", post(post_sat_file))
+    .route("/jobs", get(get_all_jobs).post(post_job))
+    .route("/jobs/{id}", get(get_job).delete(delete_job))
+    .route("/jobs/{id}/logs", get(get_job_logs))
+    .route("/events", get(get_events))
+    .route("/events/ws", get(ws_events))
+    .route_layer(middleware::from_fn(auth::authenticate));
+
+  // Routes using the backend of the site selected by the request
+  let site_routes = ApiRouter::default()
+    .route("/capabilities", get(get_capabilities))
+    .route("/authenticate", get(authenticate))
+    // Protected by the scrape token instead of a user token
+    .route("/metrics", get(get_metrics))
+    .merge(authenticated_routes)
+    .route_layer(middleware::from_fn(require_site));
+
+  // Routes not using the backend of any site
+  ApiRouter::default()
+    .route("/", get(root))
+    .route("/test/ping", get(test_ping))
+    .route("/test/ws", get(test_ws))
+    .route("/openapi", get(get_openapi))
+    .route("/version", get(get_version))
+    .route("/sites", get(get_sites))
+    .route("/users", post(create_user))
+    .merge(site_routes)
+}
+
+/// Serves the API routes and the static assets
+fn build_app(
+  api_routes: ApiRouter,
+  app_state: AppState,
+  assets_dir: PathBuf,
+) -> Router {
+  api_routes
+    .router
+    .fallback_service(
+      ServeDir::new(assets_dir).append_index_html_on_directories(true),
+    )
+    .with_state(app_state)
+    .layer(middleware::from_fn(metrics::track_requests))
     .layer(CorsLayer::very_permissive())
     .layer(
       TraceLayer::new_for_http()
         .make_span_with(DefaultMakeSpan::default().include_headers(true)),
-    );
-
-  // run our app with hyper
-  // `axum::Server` is a re-export of `hyper::Server`
-  let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
-  println!("listening on {}", addr);
-  //    axum::Server::bind(&addr)
-  //        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
-  //        .await
-  //        .unwrap();
-  axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), app)
-    .await
-    .unwrap()
+    )
 }
 
 // the input to our `create_user` handler
@@ -186,6 +440,30 @@ async fn get_version() -> &'static str {
   env!("CARGO_PKG_VERSION")
 }
 
+#[utoipa::path(
+    get,
+    path = "/capabilities",
+    responses(
+        (status = 200, description = "Get operations supported by the backend of the site", body = Vec<String>)
+    )
+)]
+async fn get_capabilities(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+) -> Response {
+  (StatusCode::OK, Json(site_backend.backend.capabilities())).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/sites",
+    responses(
+        (status = 200, description = "Get sites in the configuration and their backend", body = Vec<SiteSummary>)
+    )
+)]
+async fn get_sites(State(sites): State<Arc<Sites>>) -> Json<Vec<SiteSummary>> {
+  Json(sites.summary())
+}
+
 #[utoipa::path(
     get,
     path = "/test/whoami",
@@ -194,20 +472,8 @@ async fn get_version() -> &'static str {
         (status = UNAUTHORIZED, description = "Authentication header/token missing")
     )
 )]
-async fn test_whoami(headers: HeaderMap) -> Result<String, StatusCode> {
-  // Get auth token
-  let auth_token = if let Some(auth_header) = headers.get("authorization") {
-    auth_header.to_str().unwrap().split(" ").nth(1).unwrap()
-  } else {
-    return Err(StatusCode::UNAUTHORIZED);
-  };
-
-  let claims_json = get_claims_from_jwt_token(auth_token).unwrap();
-
-  Ok(format!(
-    "Hello {}!!!",
-    claims_json["name"].as_str().unwrap()
-  ))
+async fn test_whoami(user: AuthUser) -> String {
+  format!("Hello {}!!!", user.name)
 }
 
 #[utoipa::path(
@@ -287,47 +553,28 @@ async fn create_user(
   (StatusCode::CREATED, Json(user))
 }
 
+#[utoipa::path(
+    get,
+    path = "/cfssession/{cfssession}",
+    params(
+        ("cfssession" = String, Path, description = "CFS session name")
+    ),
+    responses(
+        (status = 200, description = "CFS session", body = Vec<CfsSessionGetResponse>),
+        (status = UNAUTHORIZED, description = "Authentication header/token missing")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_cfs_session(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(cfs_session_name): Path<String>,
 ) -> Result<Json<Value>, StatusCode> {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
+  let backend = &site_backend.backend;
+  let shasta_base_url = &site_backend.site.shasta_base_url;
+  let shasta_root_cert = &site_backend.shasta_root_cert;
 
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_token = if let Some(auth_header) = headers.get("authorization") {
-    auth_header.to_str().unwrap().split(" ").nth(1).unwrap()
-  } else {
-    return Err(StatusCode::UNAUTHORIZED);
-  };
+  let auth_token = user.token.as_str();
 
   let hsm_group_available_vec: Vec<String> =
     backend.get_group_name_available(&auth_token).await.unwrap();
@@ -355,51 +602,32 @@ async fn get_cfs_session(
   Ok(Json(serde_json::to_value(cfs_session_vec).unwrap()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/cfssession/{cfssession}/logs",
+    params(
+        ("cfssession" = String, Path, description = "CFS session name")
+    ),
+    responses(
+        (status = 101, description = "Websocket streaming the CFS session logs")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn ws_cfs_session_logs(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(cfs_session_name): Path<String>,
   ws: WebSocketUpgrade,
   user_agent: Option<TypedHeader<headers::UserAgent>>,
   ConnectInfo(addr): ConnectInfo<SocketAddr>,
 ) -> impl IntoResponse {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site.clone();
-  let site_detail_value_opt = configuration.sites.get(&site_name).cloned();
-
-  let site = site_detail_value_opt.unwrap();
-
-  let k8s_details = site
+  let k8s_details = site_backend
+    .site
     .k8s
+    .clone()
     .expect("ERROR - k8s section not found in configuration");
 
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap();
-  let auth_token = auth_header
-    .to_str()
-    .unwrap()
-    .split(" ")
-    .nth(1)
-    .unwrap()
-    .to_string();
+  let auth_token = user.token;
 
   let user_agent = if let Some(TypedHeader(user_agent)) = user_agent {
     user_agent.to_string()
@@ -414,9 +642,8 @@ async fn ws_cfs_session_logs(
     get_cfs_session_logs(
       socket,
       addr,
-      backend,
+      site_backend,
       auth_token,
-      site_name,
       cfs_session_name,
       k8s_details,
     )
@@ -426,16 +653,16 @@ async fn ws_cfs_session_logs(
 async fn get_cfs_session_logs(
   mut socket: WebSocket,
   _who: SocketAddr,
-  backend: StaticBackendDispatcher,
+  site_backend: Arc<SiteBackend>,
   shasta_token: String,
-  site_name: String,
   cfs_session_name: String,
   k8s_details: K8sDetails,
 ) {
-  let logs_stream = backend
+  let logs_stream = site_backend
+    .backend
     .get_session_logs_stream(
       &shasta_token,
-      &site_name,
+      &site_backend.site_name,
       &cfs_session_name,
       &k8s_details,
     )
@@ -456,76 +683,31 @@ async fn get_cfs_session_logs(
   }
 }
 
-pub fn get_configuration_file_path() -> PathBuf {
-  // XDG Base Directory Specification
-  let project_dirs = ProjectDirs::from(
-    "local", /*qualifier*/
-    "cscs",  /*organization*/
-    "manta", /*application*/
-  );
-
-  PathBuf::from(project_dirs.unwrap().config_dir())
-}
-
-/// Reads configuration parameters related to manta from environment variables or file. If both
-/// defiend, then environment variables takes preference
-pub fn get_configuration() -> Config {
-  let mut config_path = get_configuration_file_path();
-  config_path.push("config.toml"); // ~/.config/manta/config is the file
-
-  ::config::Config::builder()
-    .add_source(::config::File::from(config_path))
-    .add_source(
-      ::config::Environment::with_prefix("MANTA")
-        .try_parsing(true)
-        .prefix_separator("_"),
+#[utoipa::path(
+    get,
+    path = "/authenticate",
+    responses(
+        (status = 200, description = "Authentication token", body = String),
+        (status = UNAUTHORIZED, description = "Wrong credentials")
     )
-    .build()
-    .unwrap()
-}
-
-pub fn get_csm_root_cert_content(site: &str) -> Vec<u8> {
-  let mut config_path = get_configuration_file_path();
-  config_path.push(site.to_string() + "_root_cert.pem");
-
-  let mut buf = Vec::new();
-  let root_cert_file_rslt = File::open(config_path);
-
-  let _ = match root_cert_file_rslt {
-    Ok(mut file) => file.read_to_end(&mut buf),
-    Err(_) => {
-      eprintln!("Root cert file for CSM not found. Exit");
-      std::process::exit(1);
-    }
-  };
-
-  buf
-}
-
-async fn authenticate(headers: HeaderMap) -> Result<String, StatusCode> {
-  let settings = get_configuration();
-
-  let site_detail_hashmap = settings.get_table("sites").unwrap();
-  let site_detail_value = site_detail_hashmap
-    .get("alps")
-    .unwrap()
+)]
+async fn authenticate(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  headers: HeaderMap,
+) -> Result<String, StatusCode> {
+  let keycloak_base_url = site_backend
+    .site
+    .keycloak_base_url
     .clone()
-    .into_table()
-    .unwrap();
-
-  /* let shasta_base_url = site_detail_value
-  .get("shasta_base_url")
-  .unwrap()
-  .to_string(); */
-  let keycloak_base_url = site_detail_value
-    .get("keycloak_base_url")
-    .unwrap()
-    .to_string();
-  // let k8s_api_url = site_detail_value.get("k8s_api_url").unwrap().to_string();
-
-  // let settings_hsm_group_name_opt = settings.get_string("hsm_group").ok();
-
-  let shasta_root_cert = get_csm_root_cert_content("alps");
+    .unwrap_or_else(|| {
+      let shasta_base_url = &site_backend.site.shasta_base_url;
+
+      shasta_base_url
+        .strip_suffix("/apis")
+        .unwrap_or(shasta_base_url)
+        .to_string()
+        + "/keycloak"
+    });
 
   let base64_user_credentials =
     if let Some(usercredentials) = headers.get("authorization") {
@@ -547,7 +729,7 @@ async fn authenticate(headers: HeaderMap) -> Result<String, StatusCode> {
   let auth_token_result =
     csm_rs::common::authentication::get_token_from_shasta_endpoint(
       &keycloak_base_url,
-      &shasta_root_cert,
+      &site_backend.shasta_root_cert,
       username,
       password,
     )
@@ -569,8 +751,20 @@ async fn authenticate(headers: HeaderMap) -> Result<String, StatusCode> {
 /// websocket protocol will occur.
 /// This is the last point where we can extract TCP/IP metadata such as IP address of the client
 /// as well as things from HTTP headers such as user-agent of the browser etc.
+#[utoipa::path(
+    get,
+    path = "/console/{xname}",
+    params(
+        ("xname" = String, Path, description = "Node xname")
+    ),
+    responses(
+        (status = 101, description = "Websocket attached to the node console")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn ws_console(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(xname): Path<String>,
   ws: WebSocketUpgrade,
   user_agent: Option<TypedHeader<headers::UserAgent>>,
@@ -584,51 +778,24 @@ async fn ws_console(
   println!("`{user_agent}` connected.");
   // finalize the upgrade process by returning upgrade callback.
   // we can customize the callback by sending additional info such as address.
-  ws.on_upgrade(move |socket| handle_socket(headers, socket, xname))
+  ws.on_upgrade(move |socket| {
+    handle_socket(site_backend, user.token, socket, xname)
+  })
 }
 
 /// Actual websocket statemachine (one will be spawned per connection)
-async fn handle_socket(headers: HeaderMap, socket: WebSocket, xname: String) {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let k8s_details = site
+async fn handle_socket(
+  site_backend: Arc<SiteBackend>,
+  auth_token: String,
+  socket: WebSocket,
+  xname: String,
+) {
+  let k8s_details = site_backend
+    .site
     .k8s
     .as_ref()
     .expect("ERROR - k8s section not found in configuration");
 
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap().to_string();
-
   let shasta_k8s_secrets = match &k8s_details.authentication {
     K8sAuth::Native {
       certificate_authority_data,
@@ -642,7 +809,7 @@ async fn handle_socket(headers: HeaderMap, socket: WebSocket, xname: String) {
     } => fetch_shasta_k8s_secrets_from_vault(
       &vault_base_url,
       &auth_token,
-      &site_name,
+      &site_backend.site_name,
     )
     .await
     .unwrap(),
@@ -752,40 +919,12 @@ fn process_message(msg: Message, who: SocketAddr) -> ControlFlow<(), ()> {
 }
 
 async fn get_service_health(
-  headers: HeaderMap,
+  site_backend: &SiteBackend,
+  auth_token: &str,
   service: &str,
 ) -> Result<Json<serde_json::Value>> {
-  // Configuration
-  let settings = common::config::get_configuration().await?;
-
-  let configuration: MantaConfiguration = settings.try_deserialize()?;
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => bail!("ERROR - Site '{}' not found in configuration", site_name),
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file)?;
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap().to_string();
+  let shasta_base_url = &site_backend.site.shasta_base_url;
+  let shasta_root_cert = &site_backend.shasta_root_cert;
 
   let response: Value = match service {
     // NOTE: sending always 500 error is a BAD practice, we
@@ -813,10 +952,20 @@ async fn get_service_health(
   Ok(Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/cfs/health",
+    responses(
+        (status = 200, description = "CFS health check", body = Value),
+        (status = 500, description = "CFS health check failed")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_cfs_health_check(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-  let response = get_service_health(headers, "cfs")
+  let response = get_service_health(&site_backend, &user.token, "cfs")
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
   // NOTE: sending always 500 error is a BAD practice, we
@@ -826,10 +975,20 @@ async fn get_cfs_health_check(
   Ok(response)
 }
 
+#[utoipa::path(
+    get,
+    path = "/bos/health",
+    responses(
+        (status = 200, description = "BOS health check", body = Value),
+        (status = 500, description = "BOS health check failed")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_bos_health_check(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-  let response = get_service_health(headers, "bos")
+  let response = get_service_health(&site_backend, &user.token, "bos")
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
   // NOTE: sending always 500 error is a BAD practice, we
@@ -839,41 +998,21 @@ async fn get_bos_health_check(
   Ok(response)
 }
 
-async fn get_all_bss_boot_parameters(headers: HeaderMap) -> Response {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
+#[utoipa::path(
+    get,
+    path = "/bss/boot-parameters",
+    responses(
+        (status = 200, description = "Boot parameters of all nodes", body = Vec<BootParameters>)
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_all_bss_boot_parameters(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+) -> Response {
+  let backend = &site_backend.backend;
 
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let boot_parameters_rslt = backend.get_all_bootparameters(auth_token).await;
 
@@ -882,52 +1021,32 @@ async fn get_all_bss_boot_parameters(headers: HeaderMap) -> Response {
       return (StatusCode::OK, Json(boot_parameters_vec)).into_response();
     }
     Err(e) => {
-      return (StatusCode::INTERNAL_SERVER_ERROR, Json(e.to_string()))
-        .into_response();
+      return (backend_error_status(&e), Json(e.to_string())).into_response();
     }
   }
 }
 
+#[utoipa::path(
+    get,
+    path = "/bss/boot-parameters/{xname}",
+    params(
+        ("xname" = String, Path, description = "Node xname")
+    ),
+    responses(
+        (status = 200, description = "Boot parameters of the node", body = Vec<BootParameters>)
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_bss_boot_parameters(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   // Json(nodes): Json<Vec<String>>,
   Path(xname): Path<String>,
 ) -> Json<Vec<BootParameters>> {
   println!("DEBUG - TEST 1");
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
+  let backend = &site_backend.backend;
 
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let boot_parameters_rslt =
     backend.get_bootparameters(auth_token, &[xname]).await;
@@ -935,131 +1054,100 @@ async fn get_bss_boot_parameters(
   Json(boot_parameters_rslt.unwrap())
 }
 
+#[utoipa::path(
+    post,
+    path = "/bss/boot-parameters",
+    request_body = BootParameters,
+    responses(
+        (status = 200, description = "Boot parameters created")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn post_bss_boot_parameters(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Json(boot_parameters): Json<BootParameters>,
 ) -> () {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
+  let backend = &site_backend.backend;
 
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
+  let auth_token = user.token.as_str();
 
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
+  let response_rslt = backend
+    .add_bootparameters(auth_token, &boot_parameters)
+    .await;
 
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  site_backend
+    .audit(
+      auth_token,
+      "add boot parameters",
+      &boot_parameters
+        .hosts
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>(),
+      response_rslt.is_ok(),
+    )
+    .await;
 
-  backend
-    .add_bootparameters(auth_token, &boot_parameters)
-    .await
-    .unwrap()
+  response_rslt.unwrap()
 }
 
+#[utoipa::path(
+    delete,
+    path = "/bss/boot-parameters/{xname}",
+    params(
+        ("xname" = String, Path, description = "Node xname")
+    ),
+    request_body = BootParameters,
+    responses(
+        (status = 200, description = "Boot parameters deleted")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn delete_bss_boot_parameters(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Json(boot_parameters): Json<BootParameters>,
 ) -> () {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
+  let backend = &site_backend.backend;
 
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
-  backend
+  let response_rslt = backend
     .delete_bootparameters(auth_token, &boot_parameters)
-    .await
-    .unwrap();
-}
-
-async fn get_all_groups(headers: HeaderMap) -> Json<serde_json::Value> {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
+    .await;
 
-  let root_ca_cert_file = &site.root_ca_cert_file;
+  site_backend
+    .audit(
+      auth_token,
+      "delete boot parameters",
+      &boot_parameters
+        .hosts
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>(),
+      response_rslt.is_ok(),
+    )
+    .await;
 
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
+  response_rslt.unwrap();
+}
 
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
+#[utoipa::path(
+    get,
+    path = "/group",
+    responses(
+        (status = 200, description = "Groups the user has access to", body = Vec<Group>)
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_all_groups(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
+) -> Json<serde_json::Value> {
+  let backend = &site_backend.backend;
 
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let hsm_group_available_name_vec = backend
     .get_group_available(auth_token)
@@ -1085,44 +1173,27 @@ async fn get_all_groups(headers: HeaderMap) -> Json<serde_json::Value> {
   }
 }
 
+#[utoipa::path(
+    get,
+    path = "/group/{group}",
+    params(
+        ("group" = String, Path, description = "Group name")
+    ),
+    responses(
+        (status = 200, description = "Details of the nodes in the group", body = Value)
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_group_details(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
   Path(group): Path<String>,
-  headers: HeaderMap,
+  user: AuthUser,
 ) -> Json<serde_json::Value> {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
+  let backend = &site_backend.backend;
+  let shasta_base_url = &site_backend.site.shasta_base_url;
+  let shasta_root_cert = &site_backend.shasta_root_cert;
 
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let group = backend.get_group(&auth_token, &group).await.unwrap();
 
@@ -1140,44 +1211,26 @@ async fn get_group_details(
   Json(serde_json::to_value(response).unwrap())
 }
 
+#[utoipa::path(
+    get,
+    path = "/group/{group}/hardware",
+    params(
+        ("group" = String, Path, description = "Group name")
+    ),
+    responses(
+        (status = 200, description = "Hardware summary of the nodes in the group", body = Value)
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn get_hsm_hardware(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(group): Path<String>,
 ) -> Json<serde_json::Value> {
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
+  let shasta_base_url = &site_backend.site.shasta_base_url;
+  let shasta_root_cert = &site_backend.shasta_root_cert;
 
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let hsm_group = csm_rs::hsm::group::http_client::get(
     &auth_token,
@@ -1238,202 +1291,156 @@ async fn get_hsm_hardware(
   Json(serde_json::to_value(hsm_summary).unwrap())
 }
 
+#[utoipa::path(
+    get,
+    path = "/node/{node}/power-off",
+    params(
+        ("node" = String, Path, description = "Node xname")
+    ),
+    responses(
+        (status = 200, description = "Power off requested")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn power_off_node(
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
   Path(node): Path<String>,
-  headers: HeaderMap,
+  user: AuthUser,
 ) -> Result<(), StatusCode> {
   tracing::info!("Power OFF node {}", node);
 
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
+  let backend = &site_backend.backend;
 
-  let root_ca_cert_file = &site.root_ca_cert_file;
+  let auth_token = user.token.as_str();
 
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let response_rslt = backend
+    .power_off_sync(auth_token, std::slice::from_ref(&node), true)
+    .await;
 
-  let response_rslt = backend.power_off_sync(auth_token, &[node], true).await;
+  site_backend
+    .audit(
+      auth_token,
+      "power off node",
+      &[&node],
+      response_rslt.is_ok(),
+    )
+    .await;
 
   match response_rslt {
     Ok(_) => Ok(()),
-    Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    Err(e) => Err(backend_error_status(&e)),
   }
 }
 
+#[utoipa::path(
+    get,
+    path = "/node/{node}/power-on",
+    params(
+        ("node" = String, Path, description = "Node xname")
+    ),
+    responses(
+        (status = 200, description = "Power on requested")
+    ),
+    security(("bearer_auth" = []))
+)]
 #[debug_handler]
 async fn power_on_node(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(node): Path<String>,
 ) -> Result<(), StatusCode> {
   tracing::info!("Power ON node {}", node);
 
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
+  let backend = &site_backend.backend;
 
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
+  let auth_token = user.token.as_str();
 
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let response_rslt = backend
+    .power_on_sync(auth_token, std::slice::from_ref(&node))
+    .await;
 
-  let response_rslt = backend.power_on_sync(auth_token, &[node]).await;
+  site_backend
+    .audit(auth_token, "power on node", &[&node], response_rslt.is_ok())
+    .await;
 
   match response_rslt {
     Ok(_) => Ok(()),
-    Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    Err(e) => Err(backend_error_status(&e)),
   }
 }
 
+#[utoipa::path(
+    get,
+    path = "/node/{node}/power-reset",
+    params(
+        ("node" = String, Path, description = "Node xname")
+    ),
+    responses(
+        (status = 200, description = "Power reset requested")
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn power_reset_node(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(node): Path<String>,
 ) -> Result<(), StatusCode> {
   tracing::debug!("Power RESET node {}", node);
 
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
+  let backend = &site_backend.backend;
 
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
+  let auth_token = user.token.as_str();
 
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
-
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let response_rslt = backend
+    .power_on_sync(auth_token, std::slice::from_ref(&node))
+    .await;
 
-  let response_rslt = backend.power_on_sync(auth_token, &[node]).await;
+  site_backend
+    .audit(
+      auth_token,
+      "power reset node",
+      &[&node],
+      response_rslt.is_ok(),
+    )
+    .await;
 
   match response_rslt {
     Ok(_) => Ok(()),
-    Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    Err(e) => Err(backend_error_status(&e)),
   }
 }
 
 // TODO: these need to be imported from csm-rs and ochami-rs ? or dispatcher ?
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PowerStatusQueryParams {
   power_state_filter: Option<String>,
   management_state_filter: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/node/{node}/power-status",
+    params(
+        ("node" = String, Path, description = "Node xname"),
+        PowerStatusQueryParams
+    ),
+    responses(
+        (status = 200, description = "Power status of the node", body = PowerStatusAll)
+    ),
+    security(("bearer_auth" = []))
+)]
 async fn power_status_node(
-  headers: HeaderMap,
+  Extension(site_backend): Extension<Arc<SiteBackend>>,
+  user: AuthUser,
   Path(node): Path<String>,
   Query(query_param): Query<PowerStatusQueryParams>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
   tracing::debug!("Power STATUS node {}", node);
 
-  // Configuration
-  let settings = common::config::get_configuration().await.unwrap();
-
-  let configuration: MantaConfiguration = settings.try_deserialize().unwrap();
-
-  let site_name: String = configuration.site;
-  let site_detail_value_opt = configuration.sites.get(&site_name);
-
-  let site = match site_detail_value_opt {
-    Some(site_detail_value) => site_detail_value,
-    None => {
-      eprintln!("ERROR - Site '{}' not found in configuration", site_name);
-      std::process::exit(1);
-    }
-  };
-
-  let backend_tech = &site.backend;
-  let shasta_base_url = &site.shasta_base_url;
-
-  let root_ca_cert_file = &site.root_ca_cert_file;
-
-  let shasta_root_cert =
-    common::config::get_csm_root_cert_content(&root_ca_cert_file).unwrap();
-
-  // Backend
-  let backend = StaticBackendDispatcher::new(
-    &backend_tech,
-    &shasta_base_url,
-    &shasta_root_cert,
-  );
+  let backend = &site_backend.backend;
 
-  // Get auth token
-  let auth_header = headers.get("authorization").unwrap().to_str().unwrap();
-  let auth_token = auth_header.split(" ").nth(1).unwrap();
+  let auth_token = user.token.as_str();
 
   let response = backend
     .power_status(
@@ -1448,109 +1455,9 @@ async fn power_status_node(
 
   match response {
     Ok(response) => Ok(Json(response)),
-    Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    Err(e) => Err(backend_error_status(&e)),
   }
 }
 
-#[derive(Deserialize, Debug)]
-pub struct NodeMigrationQueryParams {
-  ids: String,
-  create_hsm_group: bool,
-}
-
-async fn node_migration(
-  Path((target, parent)): Path<(String, String)>,
-  Query(query_param): Query<NodeMigrationQueryParams>,
-  headers: HeaderMap,
-) -> Result<(), StatusCode> {
-  tracing::info!(
-    "Migrate nodes '{}' from parent '{}' to target {}. Create HSM group if doesn't exists? {}",
-    query_param.ids,
-    parent,
-    target,
-    query_param.create_hsm_group
-  );
-
-  let ids = query_param.ids;
-  let create_hsm_group = query_param.create_hsm_group;
-
-  let settings = get_configuration();
-
-  let site_detail_hashmap = settings.get_table("sites").unwrap();
-  let site_detail_value = site_detail_hashmap
-    .get("alps")
-    .unwrap()
-    .clone()
-    .into_table()
-    .unwrap();
-
-  let shasta_base_url = site_detail_value
-    .get("shasta_base_url")
-    .unwrap()
-    .to_string();
-
-  let shasta_root_cert = get_csm_root_cert_content("alps");
-
-  // Get auth token
-  let auth_token = if let Some(auth_header) = headers.get("authorization") {
-    auth_header.to_str().unwrap().split(" ").nth(1).unwrap()
-  } else {
-    return Err(StatusCode::UNAUTHORIZED);
-  };
-
-  let new_target_hsm_members = ids
-    .split(',')
-    .map(|xname| xname.trim())
-    .collect::<Vec<&str>>();
-
-  if csm_rs::hsm::group::http_client::get(
-    auth_token,
-    &shasta_base_url,
-    &shasta_root_cert,
-    Some(&[&target]),
-    None,
-  )
-  .await
-  .is_ok()
-  {
-    tracing::debug!("The HSM group {} exists, good.", target);
-  } else {
-    if create_hsm_group {
-      tracing::info!(
-        "HSM group {} does not exist, but the option to create the group has been selected, creating it now.",
-        target.to_string()
-      );
-      csm_rs::hsm::group::http_client::create_new_group(
-        auth_token,
-        &shasta_base_url,
-        &shasta_root_cert,
-        &target,
-        &[],
-        "false",
-        "",
-        &[],
-      )
-      .await
-      .expect("Unable to create new HSM group");
-    } else {
-      tracing::error!(
-        "HSM group {} does not exist, but the option to create the group was NOT specificied, cannot continue.",
-        target.to_string()
-      );
-      return Err(StatusCode::UNPROCESSABLE_ENTITY);
-    }
-  }
-
-  let _ = csm_rs::hsm::group::utils::migrate_hsm_members(
-    auth_token,
-    &shasta_base_url,
-    &shasta_root_cert,
-    &target,
-    &parent,
-    new_target_hsm_members,
-    true,
-  )
-  .await;
-
-  Ok(())
-}
+#[cfg(test)]
+mod tests;
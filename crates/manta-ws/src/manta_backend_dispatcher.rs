@@ -16,8 +16,10 @@ use manta_backend_dispatcher::{
     apply_hw_cluster_pin::ApplyHwClusterPin,
     apply_sat_file::SatTrait,
     apply_session::ApplySessionTrait,
+    bos::ClusterTemplateTrait,
     bss::BootParametersTrait,
     cfs::CfsTrait,
+    get_images_and_details::GetImagesAndDetailsTrait,
     hsm::{
       component::ComponentTrait, group::GroupTrait,
       hardware_inventory::HardwareInventory,
@@ -32,6 +34,7 @@ use manta_backend_dispatcher::{
     BootParameters, Component, ComponentArrayPostArray, Group,
     HWInventoryByLocationList, K8sDetails, NodeMetadataArray,
     bos::session_template::BosSessionTemplate,
+    capability::Capabilities,
     cfs::{
       cfs_configuration_details::LayerDetails,
       cfs_configuration_request::CfsConfigurationRequest,
@@ -50,6 +53,7 @@ use StaticBackendDispatcher::*;
 use futures::AsyncBufRead;
 
 use csm_rs::backend_connector::Csm;
+use manta_backend_dispatcher::mock::backend_connector::MockBackend;
 use ochami_rs::backend_connector::Ochami;
 use serde_json::Value;
 
@@ -57,16 +61,41 @@ use serde_json::Value;
 pub enum StaticBackendDispatcher {
   CSM(Csm),
   OCHAMI(Ochami),
+  MOCK(MockBackend),
 }
 
 impl StaticBackendDispatcher {
-  pub fn new(backend_type: &str, base_url: &str, root_cert: &[u8]) -> Self {
+  pub fn new(
+    backend_type: &str,
+    base_url: &str,
+    root_cert: &[u8],
+    mock_fixture_file_opt: Option<&str>,
+  ) -> Self {
     let csm = Csm::new(base_url, root_cert);
     let ochami = Ochami::new(base_url, root_cert);
 
     match backend_type {
       "csm" => Self::CSM(csm).into(),
       "ochami" => Self::OCHAMI(ochami).into(),
+      "mock" => {
+        let Some(mock_fixture_file) = mock_fixture_file_opt else {
+          eprintln!(
+            "ERROR - Backend 'mock' requires 'mock_fixture_file' in the site configuration"
+          );
+          std::process::exit(1);
+        };
+
+        match MockBackend::new(mock_fixture_file) {
+          Ok(mock) => Self::MOCK(mock),
+          Err(e) => {
+            eprintln!(
+              "ERROR - Could not load mock backend fixture '{}'. Reason:\n{}",
+              mock_fixture_file, e
+            );
+            std::process::exit(1);
+          }
+        }
+      }
       _ => {
         eprintln!("ERROR - Backend '{}' not supported", backend_type);
         std::process::exit(1);
@@ -83,6 +112,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_group_available(auth_token).await,
       OCHAMI(b) => b.get_group_available(auth_token).await,
+      MOCK(b) => b.get_group_available(auth_token).await,
     }
   }
 
@@ -93,6 +123,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_group_name_available(jwt_token).await,
       OCHAMI(b) => b.get_group_name_available(jwt_token).await,
+      MOCK(b) => b.get_group_name_available(jwt_token).await,
     }
   }
 
@@ -104,6 +135,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.add_group(auth_token, hsm_group).await,
       OCHAMI(b) => b.add_group(auth_token, hsm_group).await,
+      MOCK(b) => b.add_group(auth_token, hsm_group).await,
     }
   }
 
@@ -122,6 +154,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_member_vec_from_group_name_vec(auth_token, hsm_group_name_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_member_vec_from_group_name_vec(auth_token, hsm_group_name_vec)
+          .await
+      }
     }
   }
 
@@ -139,6 +175,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_group_map_and_filter_by_group_vec(auth_token, hsm_name_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_group_map_and_filter_by_group_vec(auth_token, hsm_name_vec)
+          .await
+      }
     }
   }
 
@@ -156,6 +196,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_group_map_and_filter_by_member_vec(auth_token, member_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_group_map_and_filter_by_member_vec(auth_token, member_vec)
+          .await
+      }
     }
   }
 
@@ -166,6 +210,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_all_groups(auth_token).await,
       OCHAMI(b) => b.get_all_groups(auth_token).await,
+      MOCK(b) => b.get_all_groups(auth_token).await,
     }
   }
 
@@ -177,6 +222,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_group(auth_token, hsm_name).await,
       OCHAMI(b) => b.get_group(auth_token, hsm_name).await,
+      MOCK(b) => b.get_group(auth_token, hsm_name).await,
     }
   }
 
@@ -188,6 +234,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_groups(auth_token, hsm_name_vec).await,
       OCHAMI(b) => b.get_groups(auth_token, hsm_name_vec).await,
+      MOCK(b) => b.get_groups(auth_token, hsm_name_vec).await,
     }
   }
 
@@ -199,6 +246,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_group(auth_token, hsm_group_label).await,
       OCHAMI(b) => b.delete_group(auth_token, hsm_group_label).await,
+      MOCK(b) => b.delete_group(auth_token, hsm_group_label).await,
     }
   }
 
@@ -216,6 +264,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_hsm_map_and_filter_by_hsm_name_vec(auth_token, hsm_name_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_hsm_map_and_filter_by_hsm_name_vec(auth_token, hsm_name_vec)
+          .await
+      }
     }
   }
 
@@ -228,6 +280,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.post_member(auth_token, group_label, xname).await,
       OCHAMI(b) => b.post_member(auth_token, group_label, xname).await,
+      MOCK(b) => b.post_member(auth_token, group_label, xname).await,
     }
   }
 
@@ -248,6 +301,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.add_members_to_group(auth_token, group_label, xnames)
           .await
       }
+      MOCK(b) => {
+        b.add_members_to_group(auth_token, group_label, xnames)
+          .await
+      }
     }
   }
 
@@ -266,6 +323,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.delete_member_from_group(auth_token, group_label, xname)
           .await
       }
+      MOCK(b) => {
+        b.delete_member_from_group(auth_token, group_label, xname)
+          .await
+      }
     }
   }
 
@@ -296,6 +357,15 @@ impl GroupTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.migrate_group_members(
+          auth_token,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          new_target_hsm_members,
+        )
+        .await
+      }
     }
   }
 
@@ -326,6 +396,15 @@ impl GroupTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.update_group_members(
+          auth_token,
+          group_name,
+          members_to_remove,
+          members_to_add,
+        )
+        .await
+      }
     }
   }
 }
@@ -339,6 +418,7 @@ impl HardwareInventory for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_inventory_hardware(auth_token, xname).await,
       OCHAMI(b) => b.get_inventory_hardware(auth_token, xname).await,
+      MOCK(b) => b.get_inventory_hardware(auth_token, xname).await,
     }
   }
 
@@ -365,6 +445,12 @@ impl HardwareInventory for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_inventory_hardware_query(
+          auth_token, xname, r#type, children, parents, partition, format,
+        )
+        .await
+      }
     }
   }
 
@@ -376,6 +462,7 @@ impl HardwareInventory for StaticBackendDispatcher {
     match self {
       CSM(b) => b.post_inventory_hardware(auth_token, hardware).await,
       OCHAMI(b) => b.post_inventory_hardware(auth_token, hardware).await,
+      MOCK(b) => b.post_inventory_hardware(auth_token, hardware).await,
     }
   }
 }
@@ -389,6 +476,7 @@ impl ComponentTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_all_nodes(auth_token, nid_only).await,
       OCHAMI(b) => b.get_all_nodes(auth_token, nid_only).await,
+      MOCK(b) => b.get_all_nodes(auth_token, nid_only).await,
     }
   }
 
@@ -399,6 +487,7 @@ impl ComponentTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_node_metadata_available(auth_token).await,
       OCHAMI(b) => b.get_node_metadata_available(auth_token).await,
+      MOCK(b) => b.get_node_metadata_available(auth_token).await,
     }
   }
 
@@ -479,6 +568,32 @@ impl ComponentTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get(
+          auth_token,
+          id,
+          r#type,
+          state,
+          flag,
+          role,
+          subrole,
+          enabled,
+          software_status,
+          subtype,
+          arch,
+          class,
+          nid,
+          nid_start,
+          nid_end,
+          partition,
+          group,
+          state_only,
+          flag_only,
+          role_only,
+          nid_only,
+        )
+        .await
+      }
     }
   }
 
@@ -490,6 +605,7 @@ impl ComponentTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.post_nodes(auth_token, component).await,
       OCHAMI(b) => b.post_nodes(auth_token, component).await,
+      MOCK(b) => b.post_nodes(auth_token, component).await,
     }
   }
 
@@ -501,6 +617,7 @@ impl ComponentTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_node(auth_token, id).await,
       OCHAMI(b) => b.delete_node(auth_token, id).await,
+      MOCK(b) => b.delete_node(auth_token, id).await,
     }
   }
 }
@@ -514,6 +631,7 @@ impl PCSTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.power_on_sync(auth_token, nodes).await,
       OCHAMI(b) => b.power_on_sync(auth_token, nodes).await,
+      MOCK(b) => b.power_on_sync(auth_token, nodes).await,
     }
   }
 
@@ -526,6 +644,7 @@ impl PCSTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.power_off_sync(auth_token, nodes, force).await,
       OCHAMI(b) => b.power_off_sync(auth_token, nodes, force).await,
+      MOCK(b) => b.power_off_sync(auth_token, nodes, force).await,
     }
   }
 
@@ -538,6 +657,7 @@ impl PCSTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.power_reset_sync(auth_token, nodes, force).await,
       OCHAMI(b) => b.power_reset_sync(auth_token, nodes, force).await,
+      MOCK(b) => b.power_reset_sync(auth_token, nodes, force).await,
     }
   }
 
@@ -552,6 +672,30 @@ impl PCSTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.power_status(auth_token, nodes, power_status_filter, management_state_filter).await,
       OCHAMI(b) => b.power_status(auth_token, nodes, power_status_filter, management_state_filter).await,
+      MOCK(b) => b.power_status(auth_token, nodes, power_status_filter, management_state_filter).await,
+    }
+  }
+
+  async fn get_power_transition(
+    &self,
+    auth_token: &str,
+    transition_id: &str,
+  ) -> Result<Value, Error> {
+    match self {
+      CSM(b) => b.get_power_transition(auth_token, transition_id).await,
+      OCHAMI(b) => b.get_power_transition(auth_token, transition_id).await,
+      MOCK(b) => b.get_power_transition(auth_token, transition_id).await,
+    }
+  }
+
+  async fn get_power_transitions(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Value>, Error> {
+    match self {
+      CSM(b) => b.get_power_transitions(auth_token).await,
+      OCHAMI(b) => b.get_power_transitions(auth_token).await,
+      MOCK(b) => b.get_power_transitions(auth_token).await,
     }
   }
 }
@@ -564,6 +708,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_all_bootparameters(auth_token).await,
       OCHAMI(b) => b.get_all_bootparameters(auth_token).await,
+      MOCK(b) => b.get_all_bootparameters(auth_token).await,
     }
   }
 
@@ -575,6 +720,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_bootparameters(auth_token, nodes).await,
       OCHAMI(b) => b.get_bootparameters(auth_token, nodes).await,
+      MOCK(b) => b.get_bootparameters(auth_token, nodes).await,
     }
   }
 
@@ -586,6 +732,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.add_bootparameters(auth_token, boot_parameters).await,
       OCHAMI(b) => b.add_bootparameters(auth_token, boot_parameters).await,
+      MOCK(b) => b.add_bootparameters(auth_token, boot_parameters).await,
     }
   }
 
@@ -597,6 +744,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.update_bootparameters(auth_token, boot_parameters).await,
       OCHAMI(b) => b.update_bootparameters(auth_token, boot_parameters).await,
+      MOCK(b) => b.update_bootparameters(auth_token, boot_parameters).await,
     }
   }
 
@@ -608,6 +756,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_bootparameters(auth_token, boot_parameters).await,
       OCHAMI(b) => b.delete_bootparameters(auth_token, boot_parameters).await,
+      MOCK(b) => b.delete_bootparameters(auth_token, boot_parameters).await,
     }
   }
 }
@@ -620,6 +769,7 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_all_redfish_endpoints(auth_token).await,
       OCHAMI(b) => b.get_all_redfish_endpoints(auth_token).await,
+      MOCK(b) => b.get_all_redfish_endpoints(auth_token).await,
     }
   }
 
@@ -661,6 +811,19 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_redfish_endpoints(
+          auth_token,
+          id,
+          fqdn,
+          r#type,
+          uuid,
+          macaddr,
+          ip_address,
+          last_status,
+        )
+        .await
+      }
     }
   }
 
@@ -672,6 +835,7 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.add_redfish_endpoint(auth_token, redfish_endpoint).await,
       OCHAMI(b) => b.add_redfish_endpoint(auth_token, redfish_endpoint).await,
+      MOCK(b) => b.add_redfish_endpoint(auth_token, redfish_endpoint).await,
     }
   }
 
@@ -689,6 +853,10 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
         b.update_redfish_endpoint(auth_token, redfish_endpoint)
           .await
       }
+      MOCK(b) => {
+        b.update_redfish_endpoint(auth_token, redfish_endpoint)
+          .await
+      }
     }
   }
 
@@ -700,6 +868,7 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_redfish_endpoint(auth_token, id).await,
       OCHAMI(b) => b.delete_redfish_endpoint(auth_token, id).await,
+      MOCK(b) => b.delete_redfish_endpoint(auth_token, id).await,
     }
   }
 }
@@ -710,11 +879,20 @@ impl BackendTrait for StaticBackendDispatcher {
     "in manta backend".to_string()
   }
 
+  fn capabilities(&self) -> Capabilities {
+    match self {
+      CSM(b) => b.capabilities(),
+      OCHAMI(b) => b.capabilities(),
+      MOCK(b) => b.capabilities(),
+    }
+  }
+
   // AUTHENTICATION
   async fn get_api_token(&self, site_name: &str) -> Result<String, Error> {
     match self {
       CSM(b) => b.get_api_token(site_name).await,
       OCHAMI(b) => b.get_api_token(site_name).await,
+      MOCK(b) => b.get_api_token(site_name).await,
     }
   }
 
@@ -727,6 +905,7 @@ impl BackendTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.nid_to_xname(auth_token, user_input_nid, is_regex).await,
       OCHAMI(b) => b.nid_to_xname(auth_token, user_input_nid, is_regex).await,
+      MOCK(b) => b.nid_to_xname(auth_token, user_input_nid, is_regex).await,
     }
   }
 }
@@ -750,6 +929,10 @@ impl CfsTrait for StaticBackendDispatcher {
         b.post_session(shasta_token, shasta_base_url, shasta_root_cert, session)
           .await
       }
+      MOCK(b) => {
+        b.post_session(shasta_token, shasta_base_url, shasta_root_cert, session)
+          .await
+      }
     }
   }
 
@@ -803,6 +986,23 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_sessions(
+          auth_token,
+          base_url,
+          root_cert,
+          session_name_opt,
+          limit_opt,
+          after_id_opt,
+          min_age_opt,
+          max_age_opt,
+          status_opt,
+          name_contains_opt,
+          is_succeded_opt,
+          tags_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -853,6 +1053,22 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_and_filter_sessions(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec_opt,
+          xname_vec_opt,
+          min_age_opt,
+          max_age_opt,
+          status_opt,
+          cfs_session_name_opt,
+          limit_number_opt,
+          is_succeded_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -906,6 +1122,23 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_sessions_by_xname(
+          auth_token,
+          base_url,
+          root_cert,
+          xname_vec,
+          limit_opt,
+          after_id_opt,
+          min_age_opt,
+          max_age_opt,
+          status_opt,
+          name_contains_opt,
+          is_succeded_opt,
+          tags_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -941,6 +1174,17 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.create_configuration_from_repos(
+          gitea_token,
+          gitea_base_url,
+          shasta_root_cert,
+          repo_name_vec,
+          local_git_commit_vec,
+          playbook_file_name_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -970,6 +1214,15 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_configuration(
+          auth_token,
+          base_url,
+          root_cert,
+          cfs_configuration_name_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -1008,6 +1261,18 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_and_filter_configuration(
+          auth_token,
+          base_url,
+          root_cert,
+          configuration_name,
+          configuration_name_pattern,
+          hsm_group_name_vec,
+          limit_number_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -1040,6 +1305,16 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_configuration_layer_details(
+          shasta_root_cert,
+          gitea_base_url,
+          gitea_token,
+          layer,
+          site_name,
+        )
+        .await
+      }
     }
   }
 
@@ -1075,6 +1350,17 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.update_runtime_configuration(
+          auth_token,
+          base_url,
+          root_cert,
+          xnames,
+          desired_configuration,
+          enabled,
+        )
+        .await
+      }
     }
   }
 
@@ -1107,6 +1393,16 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.put_configuration(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          configuration,
+          configuration_name,
+        )
+        .await
+      }
     }
   }
 
@@ -1134,6 +1430,10 @@ impl CfsTrait for StaticBackendDispatcher {
         b.get_derivatives(auth_token, base_url, root_cert, configuration_name)
           .await
       }
+      MOCK(b) => {
+        b.get_derivatives(auth_token, base_url, root_cert, configuration_name)
+          .await
+      }
     }
   }
 
@@ -1166,6 +1466,16 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_session_logs_stream(
+          shasta_token,
+          site_name,
+          cfs_session_name,
+          // k8s_api_url,
+          k8s,
+        )
+        .await
+      }
     }
   }
 
@@ -1185,6 +1495,59 @@ impl CfsTrait for StaticBackendDispatcher {
         b.get_session_logs_stream_by_xname(auth_token, site_name, xname, k8s)
           .await
       }
+      MOCK(b) => {
+        b.get_session_logs_stream_by_xname(auth_token, site_name, xname, k8s)
+          .await
+      }
+    }
+  }
+
+  async fn get_cfs_components(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    configuration_name: Option<&str>,
+    components_ids: Option<&str>,
+    status: Option<&str>,
+  ) -> Result<
+    Vec<manta_backend_dispatcher::types::cfs::component::Component>,
+    Error,
+  > {
+    match self {
+      CSM(b) => {
+        b.get_cfs_components(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          configuration_name,
+          components_ids,
+          status,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.get_cfs_components(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          configuration_name,
+          components_ids,
+          status,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.get_cfs_components(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          configuration_name,
+          components_ids,
+          status,
+        )
+        .await
+      }
     }
   }
 }
@@ -1255,6 +1618,28 @@ impl SatTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.apply_sat_file(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          vault_base_url,
+          vault_secret_path,
+          k8s_api_url,
+          shasta_k8s_secrets,
+          sat_template_file_yaml,
+          hsm_group_available_vec,
+          ansible_verbosity_opt,
+          ansible_passthrough_opt,
+          gitea_base_url,
+          gitea_token,
+          do_not_reboot,
+          watch_logs,
+          debug_on_failure,
+          dry_run,
+        )
+        .await
+      }
     }
   }
 }
@@ -1301,6 +1686,78 @@ impl ApplyHwClusterPin for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.apply_hw_cluster_pin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
+    }
+  }
+
+  async fn apply_hw_cluster_unpin(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    target_hsm_group_name: &str,
+    parent_hsm_group_name: &str,
+    pattern: &str,
+    nodryrun: bool,
+    create_target_hsm_group: bool,
+    delete_empty_parent_hsm_group: bool,
+  ) -> Result<(), Error> {
+    match self {
+      CSM(b) => {
+        b.apply_hw_cluster_unpin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.apply_hw_cluster_unpin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.apply_hw_cluster_unpin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
     }
   }
 }
@@ -1332,6 +1789,15 @@ impl ImsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_images(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          image_id_opt,
+        )
+        .await
+      }
     }
   }
 }
@@ -1399,6 +1865,27 @@ impl ApplySessionTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.i_apply_session(
+          gitea_token,
+          gitea_base_url,
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          // k8s_api_url,
+          cfs_conf_sess_name,
+          playbook_yaml_file_name_opt,
+          hsm_group,
+          repos_paths,
+          ansible_limit,
+          ansible_verbosity,
+          ansible_passthrough,
+          // watch_logs,
+          /* kafka_audit,
+          k8s, */
+        )
+        .await
+      }
     }
   }
 }
@@ -1442,6 +1929,19 @@ impl MigrateRestoreTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.migrate_restore(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_file,
+          cfs_file,
+          hsm_file,
+          ims_file,
+          image_dir,
+        )
+        .await
+      }
     }
   }
 }
@@ -1476,6 +1976,259 @@ impl MigrateBackupTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.migrate_backup(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos,
+          destination,
+        )
+        .await
+      }
+    }
+  }
+}
+
+impl GetImagesAndDetailsTrait for StaticBackendDispatcher {
+  async fn get_images_and_details(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    hsm_group_name_vec: &[String],
+    id_opt: Option<&String>,
+    limit_number: Option<&u8>,
+  ) -> Result<Vec<(Image, String, String, bool)>, Error> {
+    match self {
+      CSM(b) => {
+        b.get_images_and_details(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          id_opt,
+          limit_number,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.get_images_and_details(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          id_opt,
+          limit_number,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.get_images_and_details(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          id_opt,
+          limit_number,
+        )
+        .await
+      }
+    }
+  }
+}
+
+impl ClusterTemplateTrait for StaticBackendDispatcher {
+  async fn get_template(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    bos_session_template_id_opt: Option<&str>,
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    match self {
+      CSM(b) => {
+        b.get_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_session_template_id_opt,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.get_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_session_template_id_opt,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.get_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_session_template_id_opt,
+        )
+        .await
+      }
+    }
+  }
+
+  async fn get_and_filter_templates(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    hsm_group_name_vec: &Vec<String>,
+    hsm_member_vec: &[String],
+    bos_sessiontemplate_name_opt: Option<&String>,
+    limit_number_opt: Option<&u8>,
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    match self {
+      CSM(b) => {
+        b.get_and_filter_templates(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          hsm_member_vec,
+          bos_sessiontemplate_name_opt,
+          limit_number_opt,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.get_and_filter_templates(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          hsm_member_vec,
+          bos_sessiontemplate_name_opt,
+          limit_number_opt,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.get_and_filter_templates(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          hsm_member_vec,
+          bos_sessiontemplate_name_opt,
+          limit_number_opt,
+        )
+        .await
+      }
+    }
+  }
+
+  async fn get_all_templates(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+  ) -> Result<Vec<BosSessionTemplate>, Error> {
+    cache::get_or_fetch("bos/sessiontemplates", shasta_token, &(), || async {
+      match self {
+        CSM(b) => {
+          b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+        OCHAMI(b) => {
+          b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+        MOCK(b) => {
+          b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+      }
+    })
+    .await
+  }
+
+  async fn put_template(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    bos_template: &BosSessionTemplate,
+    bos_template_name: &str,
+  ) -> Result<BosSessionTemplate, Error> {
+    match self {
+      CSM(b) => {
+        b.put_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template,
+          bos_template_name,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.put_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template,
+          bos_template_name,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.put_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template,
+          bos_template_name,
+        )
+        .await
+      }
+    }
+  }
+
+  async fn delete_template(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    bos_template_id: &str,
+  ) -> Result<(), Error> {
+    match self {
+      CSM(b) => {
+        b.delete_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template_id,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.delete_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template_id,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.delete_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template_id,
+        )
+        .await
+      }
     }
   }
 }
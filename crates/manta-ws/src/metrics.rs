@@ -0,0 +1,7 @@
+mod inventory;
+mod middleware;
+mod registry;
+
+pub use crate::metrics::inventory::render_inventory;
+pub use crate::metrics::middleware::track_requests;
+pub use crate::metrics::registry::METRICS;
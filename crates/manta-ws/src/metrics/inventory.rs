@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{
+    bos::ClusterTemplateTrait, cfs::CfsTrait, hsm::component::ComponentTrait,
+    hsm::group::GroupTrait, ims::ImsTrait,
+  },
+};
+
+use crate::common::site_backend::SiteBackend;
+
+use super::registry::{METRICS, write_header, write_sample};
+
+/// Appends the gauges describing the groups the token has access to. Sources
+/// the backend can't provide are left out
+pub async fn render_inventory(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+  out: &mut String,
+) -> Result<(), Error> {
+  let site = site_backend.site_name.as_str();
+  let backend = &site_backend.backend;
+  let shasta_base_url = &site_backend.site.shasta_base_url;
+  let shasta_root_cert = &site_backend.shasta_root_cert;
+
+  let group_vec = backend.get_group_name_available(auth_token).await?;
+
+  let group_member_map: BTreeMap<String, Vec<String>> = backend
+    .get_group_map_and_filter_by_group_vec(
+      auth_token,
+      group_vec.iter().map(String::as_str).collect(),
+    )
+    .await?
+    .into_iter()
+    .collect();
+
+  // Nodes per group, state and role
+  match get_node_state_role_map(site_backend, auth_token).await {
+    Ok(node_state_role_map) => {
+      let name = "manta_hsm_group_nodes";
+
+      write_header(out, name, "gauge", "Nodes per group, state and role");
+
+      for (group, member_vec) in &group_member_map {
+        let mut count_map: BTreeMap<&(String, String), u64> = BTreeMap::new();
+
+        for state_role in member_vec
+          .iter()
+          .filter_map(|xname| node_state_role_map.get(xname))
+        {
+          *count_map.entry(state_role).or_default() += 1;
+        }
+
+        for ((state, role), count) in count_map {
+          write_sample(
+            out,
+            name,
+            &[
+              ("site", site),
+              ("group", group.as_str()),
+              ("state", state.as_str()),
+              ("role", role.as_str()),
+            ],
+            count,
+          );
+        }
+      }
+    }
+    Err(e) => skip_source(site, "HSM components", &e),
+  }
+
+  // CFS sessions per status
+  let session_rslt = backend
+    .get_and_filter_sessions(
+      auth_token,
+      shasta_base_url,
+      shasta_root_cert,
+      Some(group_vec.clone()),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await;
+
+  match session_rslt {
+    Ok(session_vec) => {
+      let name = "manta_cfs_sessions";
+
+      write_header(out, name, "gauge", "CFS sessions per status");
+
+      let mut count_map: BTreeMap<String, u64> = BTreeMap::new();
+
+      for session in &session_vec {
+        let status = session
+          .status
+          .as_ref()
+          .and_then(|status| status.session.as_ref())
+          .and_then(|session| session.status.clone())
+          .unwrap_or_else(|| "unknown".to_string());
+
+        *count_map.entry(status).or_default() += 1;
+      }
+
+      for (status, count) in count_map {
+        write_sample(
+          out,
+          name,
+          &[("site", site), ("status", status.as_str())],
+          count,
+        );
+      }
+    }
+    Err(e) => skip_source(site, "CFS sessions", &e),
+  }
+
+  // Failed CFS components per group
+  let component_rslt = backend
+    .get_cfs_components(
+      auth_token,
+      shasta_base_url,
+      shasta_root_cert,
+      None,
+      None,
+      Some("failed"),
+    )
+    .await;
+
+  match component_rslt {
+    Ok(component_vec) => {
+      let name = "manta_cfs_components_failed";
+
+      write_header(
+        out,
+        name,
+        "gauge",
+        "Nodes per group whose CFS configuration failed",
+      );
+
+      let failed_xname_set: HashSet<&str> = component_vec
+        .iter()
+        .filter_map(|component| component.id.as_deref())
+        .collect();
+
+      for (group, member_vec) in &group_member_map {
+        let count = member_vec
+          .iter()
+          .filter(|xname| failed_xname_set.contains(xname.as_str()))
+          .count();
+
+        write_sample(
+          out,
+          name,
+          &[("site", site), ("group", group.as_str())],
+          count,
+        );
+      }
+    }
+    Err(e) => skip_source(site, "CFS components", &e),
+  }
+
+  // BOS session templates
+  let member_vec: Vec<String> =
+    group_member_map.values().flatten().cloned().collect();
+
+  let template_rslt = backend
+    .get_and_filter_templates(
+      auth_token,
+      shasta_base_url,
+      shasta_root_cert,
+      &group_vec,
+      &member_vec,
+      None,
+      None,
+    )
+    .await;
+
+  match template_rslt {
+    Ok(template_vec) => {
+      let name = "manta_bos_session_templates";
+
+      write_header(out, name, "gauge", "BOS session templates");
+      write_sample(out, name, &[("site", site)], template_vec.len());
+    }
+    Err(e) => skip_source(site, "BOS session templates", &e),
+  }
+
+  // IMS images
+  let image_rslt = backend
+    .get_images(auth_token, shasta_base_url, shasta_root_cert, None)
+    .await;
+
+  match image_rslt {
+    Ok(image_vec) => {
+      let name = "manta_ims_images";
+
+      write_header(out, name, "gauge", "IMS images");
+      write_sample(out, name, &[("site", site)], image_vec.len());
+    }
+    Err(e) => skip_source(site, "IMS images", &e),
+  }
+
+  Ok(())
+}
+
+/// State and role of every node
+async fn get_node_state_role_map(
+  site_backend: &SiteBackend,
+  auth_token: &str,
+) -> Result<HashMap<String, (String, String)>, Error> {
+  let node_metadata = site_backend
+    .backend
+    .get(
+      auth_token,
+      None,
+      Some("Node"),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await?;
+
+  Ok(
+    node_metadata
+      .components
+      .unwrap_or_default()
+      .into_iter()
+      .filter_map(|component| {
+        Some((
+          component.id?,
+          (
+            component.state.unwrap_or_else(|| "unknown".to_string()),
+            component.role.unwrap_or_else(|| "unknown".to_string()),
+          ),
+        ))
+      })
+      .collect(),
+  )
+}
+
+fn skip_source(site: &str, source: &str, error: &Error) {
+  if !error.is_not_implemented() {
+    METRICS.count_backend_error(error);
+
+    tracing::warn!(
+      "Could not get {} of site '{}' for metrics. Reason:\n{}",
+      source,
+      site,
+      error
+    );
+  }
+}
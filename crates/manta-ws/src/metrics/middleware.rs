@@ -0,0 +1,43 @@
+use std::{sync::Arc, time::Instant};
+
+use axum::{
+  extract::{MatchedPath, Request},
+  middleware::Next,
+  response::Response,
+};
+
+use crate::common::site_backend::SiteBackend;
+
+use super::registry::METRICS;
+
+/// Records the latency of the request. Requests not matching any route, eg
+/// static assets, are recorded under the 'unmatched' route
+pub async fn track_requests(request: Request, next: Next) -> Response {
+  let site = request
+    .extensions()
+    .get::<Arc<SiteBackend>>()
+    .map(|site_backend| site_backend.site_name.clone())
+    .unwrap_or_default();
+
+  let method = request.method().to_string();
+
+  let route = request
+    .extensions()
+    .get::<MatchedPath>()
+    .map(|matched_path| matched_path.as_str().to_string())
+    .unwrap_or_else(|| "unmatched".to_string());
+
+  let start = Instant::now();
+
+  let response = next.run(request).await;
+
+  METRICS.observe_request(
+    &site,
+    &method,
+    &route,
+    response.status().as_u16(),
+    start.elapsed().as_secs_f64(),
+  );
+
+  response
+}
@@ -0,0 +1,197 @@
+use std::{
+  collections::BTreeMap,
+  fmt::Display,
+  sync::{LazyLock, Mutex},
+};
+
+use manta_backend_dispatcher::error::Error;
+
+/// Upper bounds, in seconds, of the request latency histogram buckets
+const LATENCY_BUCKETS: [f64; 11] = [
+  0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Metrics collected while serving requests
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::default);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestLabels {
+  site: String,
+  method: String,
+  route: String,
+  status: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct BackendErrorLabels {
+  service: String,
+  status: u16,
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+  /// Cumulative count per bucket of `LATENCY_BUCKETS`
+  bucket_count_vec: [u64; LATENCY_BUCKETS.len()],
+  count: u64,
+  sum: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+  request_latency_map: Mutex<BTreeMap<RequestLabels, Histogram>>,
+  backend_error_map: Mutex<BTreeMap<BackendErrorLabels, u64>>,
+}
+
+impl Metrics {
+  pub fn observe_request(
+    &self,
+    site: &str,
+    method: &str,
+    route: &str,
+    status: u16,
+    seconds: f64,
+  ) {
+    let labels = RequestLabels {
+      site: site.to_string(),
+      method: method.to_string(),
+      route: route.to_string(),
+      status,
+    };
+
+    let mut request_latency_map = self.request_latency_map.lock().unwrap();
+    let histogram = request_latency_map.entry(labels).or_default();
+
+    for (bucket_count, bound) in
+      histogram.bucket_count_vec.iter_mut().zip(LATENCY_BUCKETS)
+    {
+      if seconds <= bound {
+        *bucket_count += 1;
+      }
+    }
+
+    histogram.count += 1;
+    histogram.sum += seconds;
+  }
+
+  /// Counts a failed backend call. Errors raised by manta-ws itself, eg
+  /// validating a request, are not counted
+  pub fn count_backend_error(&self, error: &Error) {
+    let service = error.service().unwrap_or("unknown");
+
+    if service == "manta-ws" {
+      return;
+    }
+
+    let labels = BackendErrorLabels {
+      service: service.to_string(),
+      status: error.http_status(),
+    };
+
+    *self
+      .backend_error_map
+      .lock()
+      .unwrap()
+      .entry(labels)
+      .or_default() += 1;
+  }
+
+  /// Request latencies and backend errors in Prometheus text format
+  pub fn render(&self, out: &mut String) {
+    let name = "manta_ws_request_duration_seconds";
+
+    write_header(out, name, "histogram", "Latency of the API requests");
+
+    for (labels, histogram) in self.request_latency_map.lock().unwrap().iter() {
+      let status = labels.status.to_string();
+      let label_vec = [
+        ("site", labels.site.as_str()),
+        ("method", labels.method.as_str()),
+        ("route", labels.route.as_str()),
+        ("status", status.as_str()),
+      ];
+
+      for (bucket_count, bound) in
+        histogram.bucket_count_vec.iter().zip(LATENCY_BUCKETS)
+      {
+        let bound = bound.to_string();
+        let bucket_label_vec =
+          [&label_vec[..], &[("le", bound.as_str())]].concat();
+
+        write_sample(
+          out,
+          &format!("{}_bucket", name),
+          &bucket_label_vec,
+          bucket_count,
+        );
+      }
+
+      let bucket_label_vec = [&label_vec[..], &[("le", "+Inf")]].concat();
+
+      write_sample(
+        out,
+        &format!("{}_bucket", name),
+        &bucket_label_vec,
+        histogram.count,
+      );
+      write_sample(out, &format!("{}_sum", name), &label_vec, histogram.sum);
+      write_sample(
+        out,
+        &format!("{}_count", name),
+        &label_vec,
+        histogram.count,
+      );
+    }
+
+    let name = "manta_ws_backend_errors_total";
+
+    write_header(out, name, "counter", "Failed calls to the backend services");
+
+    for (labels, count) in self.backend_error_map.lock().unwrap().iter() {
+      let status = labels.status.to_string();
+
+      write_sample(
+        out,
+        name,
+        &[
+          ("service", labels.service.as_str()),
+          ("status", status.as_str()),
+        ],
+        count,
+      );
+    }
+  }
+}
+
+/// Writes the HELP and TYPE lines of a metric
+pub fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+  out.push_str(&format!("# HELP {} {}\n", name, help));
+  out.push_str(&format!("# TYPE {} {}\n", name, kind));
+}
+
+/// Writes a sample of a metric, eg 'name{label="value"} 1'
+pub fn write_sample(
+  out: &mut String,
+  name: &str,
+  label_vec: &[(&str, &str)],
+  value: impl Display,
+) {
+  let labels = label_vec
+    .iter()
+    .map(|(label, value)| {
+      format!(
+        "{}=\"{}\"",
+        label,
+        value
+          .replace('\\', "\\\\")
+          .replace('"', "\\\"")
+          .replace('\n', "\\n")
+      )
+    })
+    .collect::<Vec<_>>()
+    .join(",");
+
+  out.push_str(&format!("{}{{{}}} {}\n", name, labels, value));
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,146 @@
+use manta_backend_dispatcher::error::Error;
+
+use super::{Metrics, write_header, write_sample};
+
+#[test]
+fn test_write_header() {
+  let mut out = String::new();
+
+  write_header(&mut out, "manta_ims_images", "gauge", "IMS images");
+
+  assert_eq!(
+    out,
+    "# HELP manta_ims_images IMS images\n# TYPE manta_ims_images gauge\n"
+  );
+}
+
+#[test]
+fn test_write_sample() {
+  let mut out = String::new();
+
+  write_sample(
+    &mut out,
+    "manta_hsm_group_nodes",
+    &[("site", "alps"), ("group", "zinal")],
+    3,
+  );
+
+  assert_eq!(
+    out,
+    "manta_hsm_group_nodes{site=\"alps\",group=\"zinal\"} 3\n"
+  );
+}
+
+#[test]
+fn test_write_sample_escapes_label_values() {
+  let mut out = String::new();
+
+  write_sample(
+    &mut out,
+    "manta_cfs_sessions",
+    &[("status", "a\\b\"c\nd")],
+    1,
+  );
+
+  assert_eq!(out, "manta_cfs_sessions{status=\"a\\\\b\\\"c\\nd\"} 1\n");
+}
+
+#[test]
+fn test_render_histogram() {
+  let metrics = Metrics::default();
+
+  // Values exactly representable so the sum is exact
+  for seconds in [0.0078125, 0.0625, 2.0, 16.0] {
+    metrics.observe_request("alps", "GET", "/images", 200, seconds);
+  }
+
+  let mut out = String::new();
+  metrics.render(&mut out);
+
+  let labels = "site=\"alps\",method=\"GET\",route=\"/images\",status=\"200\"";
+
+  for (le, count) in [
+    ("0.005", 0),
+    ("0.01", 1),
+    ("0.025", 1),
+    ("0.05", 1),
+    ("0.1", 2),
+    ("0.25", 2),
+    ("0.5", 2),
+    ("1", 2),
+    ("2.5", 3),
+    ("5", 3),
+    ("10", 3),
+    ("+Inf", 4),
+  ] {
+    let sample = format!(
+      "manta_ws_request_duration_seconds_bucket{{{},le=\"{}\"}} {}\n",
+      labels, le, count
+    );
+
+    assert!(out.contains(&sample), "{} not in:\n{}", sample, out);
+  }
+
+  assert!(out.contains(&format!(
+    "manta_ws_request_duration_seconds_sum{{{}}} 18.0703125\n",
+    labels
+  )));
+  assert!(out.contains(&format!(
+    "manta_ws_request_duration_seconds_count{{{}}} 4\n",
+    labels
+  )));
+  assert!(out.contains("# TYPE manta_ws_request_duration_seconds histogram\n"));
+}
+
+#[test]
+fn test_render_histogram_per_labels() {
+  let metrics = Metrics::default();
+
+  metrics.observe_request("alps", "GET", "/images", 200, 0.1);
+  metrics.observe_request("alps", "GET", "/images", 404, 0.1);
+  metrics.observe_request("prealps", "GET", "/images", 200, 0.1);
+
+  let mut out = String::new();
+  metrics.render(&mut out);
+
+  assert_eq!(
+    out
+      .lines()
+      .filter(|line| line.starts_with("manta_ws_request_duration_seconds_count"))
+      .count(),
+    3
+  );
+}
+
+#[test]
+fn test_render_backend_errors() {
+  let metrics = Metrics::default();
+
+  for _ in 0..2 {
+    metrics.count_backend_error(&Error::NotFound {
+      service: "CFS".to_string(),
+      resource: "session".to_string(),
+      id: "batcher-1".to_string(),
+    });
+  }
+
+  // Errors raised by manta-ws itself are not backend errors
+  metrics.count_backend_error(&Error::Forbidden {
+    service: "manta-ws".to_string(),
+    detail: "user 'alice' needs one of the roles 'pa_admin'".to_string(),
+  });
+
+  let mut out = String::new();
+  metrics.render(&mut out);
+
+  let sample_vec: Vec<&str> = out
+    .lines()
+    .filter(|line| line.starts_with("manta_ws_backend_errors_total"))
+    .collect();
+
+  assert_eq!(
+    sample_vec,
+    ["manta_ws_backend_errors_total{service=\"CFS\",status=\"404\"} 2"]
+  );
+  assert!(out.contains("# TYPE manta_ws_backend_errors_total counter\n"));
+}
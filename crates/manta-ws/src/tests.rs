@@ -0,0 +1,34 @@
+use std::collections::BTreeSet;
+
+use utoipa::OpenApi;
+
+use super::{ApiDoc, api_routes};
+
+#[test]
+fn test_api_routes_in_openapi() {
+  let openapi = ApiDoc::openapi();
+
+  let openapi_path_set: BTreeSet<&str> =
+    openapi.paths.paths.keys().map(String::as_str).collect();
+
+  let route_path_vec = api_routes().path_vec;
+  let route_path_set: BTreeSet<&str> = route_path_vec.iter().copied().collect();
+
+  let undocumented_path_vec: Vec<&&str> =
+    route_path_set.difference(&openapi_path_set).collect();
+
+  assert!(
+    undocumented_path_vec.is_empty(),
+    "Routes missing in the OpenApi document: {:?}",
+    undocumented_path_vec
+  );
+
+  let unrouted_path_vec: Vec<&&str> =
+    openapi_path_set.difference(&route_path_set).collect();
+
+  assert!(
+    unrouted_path_vec.is_empty(),
+    "Paths in the OpenApi document without route: {:?}",
+    unrouted_path_vec
+  );
+}
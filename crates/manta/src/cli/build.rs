@@ -1,21 +1,44 @@
-use clap::{arg, value_parser, ArgAction, ArgGroup, Command, ValueHint};
-use manta_backend_dispatcher::types::ArtifactType;
+use clap::{
+  arg, builder::ArgPredicate, value_parser, Arg, ArgAction, ArgGroup,
+  ArgMatches, Command, ValueHint,
+};
+use manta_backend_dispatcher::{
+  error::Error,
+  types::{
+    capability::{Capabilities, Capability},
+    ArtifactType,
+  },
+};
 use strum::IntoEnumIterator;
 
 use std::path::PathBuf;
 
+use crate::{
+  cli::commands::apply_hw_cluster_pin::constraints::AllocationConstraints,
+  common::output::{OutputFormat, OutputSelection},
+};
+
 pub fn build_cli() -> Command {
   Command::new(env!("CARGO_PKG_NAME"))
     .term_width(100)
     .version(env!("CARGO_PKG_VERSION"))
     .arg_required_else_help(true)
+    .arg(
+      arg!(--"no-cache" "Do not use the response cache, query the backend")
+        .global(true),
+    )
     .subcommand(subcommand_config())
+    .subcommand(subcommand_cache())
     .subcommand(subcommand_get())
     .subcommand(subcommand_add())
     .subcommand(subcommand_update())
     .subcommand(subcommand_apply())
     .subcommand(subcommand_delete())
     .subcommand(subcommand_migrate())
+    .subcommand(subcommand_plan())
+    .subcommand(subcommand_diff())
+    .subcommand(subcommand_import())
+    .subcommand(subcommand_export())
     .subcommand(subcommand_power())
     .subcommand(subcommand_log())
     .subcommand(subcommand_console())
@@ -26,6 +49,178 @@ pub fn build_cli() -> Command {
   .subcommand(subcommand_upload_bood_image()) */
 }
 
+/// Backend capability needed by each subcommand. Subcommands not listed here work with any
+/// backend
+pub const SUBCOMMAND_CAPABILITY_VEC: &[(&[&str], Capability)] = &[
+  (&["get", "groups"], Capability::Group),
+  (&["get", "hardware"], Capability::HardwareInventory),
+  (&["get", "sessions"], Capability::Cfs),
+  (&["get", "configurations"], Capability::Cfs),
+  (&["get", "templates"], Capability::Bos),
+  (&["get", "cluster"], Capability::Cfs),
+  (&["get", "nodes"], Capability::Cfs),
+  (&["get", "images"], Capability::Ims),
+  (&["get", "boot-parameters"], Capability::BootParameters),
+  (&["get", "kernel-parameters"], Capability::BootParameters),
+  (&["get", "redfish-endpoints"], Capability::RedfishEndpoint),
+  (&["get", "power-cap"], Capability::PowerCap),
+  (&["get", "power-transition"], Capability::Power),
+  (&["add", "node"], Capability::Component),
+  (&["add", "group"], Capability::Group),
+  (&["add", "hardware"], Capability::HwClusterPin),
+  (&["add", "boot-parameters"], Capability::BootParameters),
+  (&["add", "kernel-parameters"], Capability::BootParameters),
+  (&["add", "redfish-endpoint"], Capability::RedfishEndpoint),
+  (
+    &["import", "redfish-endpoints"],
+    Capability::RedfishEndpoint,
+  ),
+  (
+    &["export", "redfish-endpoints"],
+    Capability::RedfishEndpoint,
+  ),
+  (&["update", "boot-parameters"], Capability::BootParameters),
+  (&["update", "redfish-endpoint"], Capability::RedfishEndpoint),
+  (&["apply", "hardware"], Capability::HwClusterPin),
+  (&["apply", "configuration"], Capability::Cfs),
+  (&["apply", "sat-file"], Capability::SatFile),
+  (&["apply", "boot"], Capability::BootParameters),
+  (&["apply", "kernel-parameters"], Capability::BootParameters),
+  (&["apply", "session"], Capability::ApplySession),
+  (&["apply", "ephemeral-environment"], Capability::Ims),
+  (&["apply", "template"], Capability::Bos),
+  (&["apply", "power-cap"], Capability::PowerCap),
+  (&["delete", "group"], Capability::Group),
+  (&["delete", "node"], Capability::Component),
+  (&["delete", "hardware"], Capability::HwClusterPin),
+  (&["delete", "images"], Capability::Ims),
+  (
+    &["delete", "configurations"],
+    Capability::DeleteConfiguration,
+  ),
+  (&["delete", "session"], Capability::DeleteSession),
+  (&["delete", "kernel-parameters"], Capability::BootParameters),
+  (&["delete", "boot-parameters"], Capability::BootParameters),
+  (&["delete", "redfish-endpoint"], Capability::RedfishEndpoint),
+  (
+    &["migrate", "vCluster", "backup"],
+    Capability::MigrateBackup,
+  ),
+  (
+    &["migrate", "vCluster", "restore"],
+    Capability::MigrateRestore,
+  ),
+  (&["migrate", "nodes"], Capability::Group),
+  (&["power"], Capability::Power),
+  (&["log"], Capability::CfsSessionLogs),
+  (&["console"], Capability::Console),
+  (&["add-nodes-to-groups"], Capability::Group),
+  (&["remove-nodes-from-groups"], Capability::Group),
+];
+
+/// Subcommands that do not change the system. Only these can use the response cache, any
+/// other subcommand invalidates it
+pub const READ_ONLY_SUBCOMMAND_VEC: &[&[&str]] = &[
+  &["get"],
+  &["config", "show"],
+  &["plan"],
+  &["diff"],
+  &["export"],
+  &["log"],
+  &["console"],
+  &["validate-local-repo"],
+];
+
+/// Returns true if the subcommand in the command line does not change the system
+pub fn is_read_only_subcommand(cli_root: &ArgMatches) -> bool {
+  let mut subcommand_path = Vec::new();
+  let mut matches = cli_root;
+
+  while let Some((name, subcommand_matches)) = matches.subcommand() {
+    subcommand_path.push(name);
+    matches = subcommand_matches;
+  }
+
+  READ_ONLY_SUBCOMMAND_VEC
+    .iter()
+    .any(|path| subcommand_path.starts_with(path))
+}
+
+/// Returns true if the subcommand was called with '--dry-run'
+pub fn is_dry_run(cli_root: &ArgMatches) -> bool {
+  let mut matches = cli_root;
+
+  while let Some((_, subcommand_matches)) = matches.subcommand() {
+    matches = subcommand_matches;
+
+    if let Ok(Some(true)) = matches.try_get_one::<bool>("dry-run") {
+      return true;
+    }
+  }
+
+  false
+}
+
+/// Hides the subcommands the backend does not support from the help and shell completions
+pub fn hide_unsupported_subcommands(
+  mut cli: Command,
+  capabilities: &Capabilities,
+) -> Command {
+  for (subcommand_path, capability) in SUBCOMMAND_CAPABILITY_VEC {
+    if !capabilities.contains(*capability) {
+      cli = hide_subcommand(cli, subcommand_path);
+    }
+  }
+
+  cli
+}
+
+/// Hides a subcommand given its path (eg: ["get", "sessions"]). Paths not found in the
+/// command are ignored
+pub fn hide_subcommand(cli: Command, subcommand_path: &[&str]) -> Command {
+  let Some((name, subcommand_path)) = subcommand_path.split_first() else {
+    return cli;
+  };
+
+  let Some(subcommand_name) = cli
+    .find_subcommand(name)
+    .map(|subcommand| subcommand.get_name().to_string())
+  else {
+    log::warn!("Subcommand '{}' not found, can't hide it", name);
+    return cli;
+  };
+
+  cli.mut_subcommand(subcommand_name, |subcommand| {
+    if subcommand_path.is_empty() {
+      subcommand.hide(true)
+    } else {
+      hide_subcommand(subcommand, subcommand_path)
+    }
+  })
+}
+
+/// Returns the subcommand in the command line (eg: 'get sessions') if the backend does not
+/// have the capability it needs
+pub fn get_unsupported_subcommand(
+  cli_root: &ArgMatches,
+  capabilities: &Capabilities,
+) -> Option<String> {
+  let mut subcommand_path = Vec::new();
+  let mut matches = cli_root;
+
+  while let Some((name, subcommand_matches)) = matches.subcommand() {
+    subcommand_path.push(name);
+    matches = subcommand_matches;
+  }
+
+  SUBCOMMAND_CAPABILITY_VEC
+    .iter()
+    .find(|(path, capability)| {
+      subcommand_path.starts_with(path) && !capabilities.contains(*capability)
+    })
+    .map(|_| subcommand_path.join(" "))
+}
+
 pub fn subcommand_config() -> Command {
   // Enforce user to choose a HSM group is hsm_available config param is not empty. This is to
   // make sure tenants like PSI won't unset parameter hsm_group and take over all HSM groups.
@@ -234,11 +429,7 @@ pub fn subcommand_get_group() -> Command {
     .arg(
       arg!(<VALUE> "Group name. Returns all groups if missing").required(false),
     )
-    .arg(
-      arg!(-o --output <VALUE> "Output format")
-        .value_parser(["json", "table"])
-        .default_value("table"),
-    )
+    .arg(arg_get_output(&[]))
 }
 
 pub fn subcommand_get_hardware() -> Command {
@@ -248,9 +439,18 @@ pub fn subcommand_get_hardware() -> Command {
     .about("Get hw components for a cluster")
     .arg(arg!(<CLUSTER_NAME> "Name of the cluster").required(true))
     .arg(
-      arg!(-o --output <FORMAT> "Output format")
-        .value_parser(["json", "summary", "details"])
-        .default_value("summary"),
+      arg!(-o --output <FORMAT> "Output format. 'xnames' prints the comma separated list of nodes, usable as input to other commands. Defaults to 'xnames' if '--filter' is used")
+        .value_parser(["json", "summary", "details", "xnames"])
+        .default_value("summary")
+        .default_value_if("filter", ArgPredicate::IsPresent, Some("xnames")),
+    )
+    .arg(
+      arg!(-f --filter <EXPRESSION> "Only nodes whose hw components match the expression. Fields: xname, processor.count, processor.model, accelerator.count, accelerator.model, memory.count, memory.total, hsn_nic.count, hsn_nic.model. Operators: ==, !=, <, <=, >, >=, ~ (regex), !~, and, or, not.\neg 'accelerator.model ~ \"A100\" and memory.total >= 512GiB and processor.count == 2'"),
+    )
+    .arg(
+      arg!(-s --snapshot <FILE> "Save the hardware inventory of each node, including serial numbers, to a snapshot file usable with 'diff hardware' and 'plan hw-cluster --inventory-file'")
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::FilePath),
     );
 
   let command_get_hw_configuration_node = Command::new("node")
@@ -259,7 +459,15 @@ pub fn subcommand_get_hardware() -> Command {
     .about("Get hw components for some nodes")
     .arg(arg!(<XNAMES> "Comma separated list of xnames.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0'").required(true))
     .arg(arg!(-t --type <TYPE> "Filters output to specific type").value_parser(ArtifactType::iter().map(|e| e.into()).collect::<Vec<&str>>()))
-    .arg(arg!(-o --output <FORMAT> "Output format. If missing it will print output data in human redeable (table) format").value_parser(["json"]));
+    .arg(
+      arg!(-o --output <FORMAT> "Output format. If missing it will print output data in human redeable (table) format. 'xnames' prints the comma separated list of nodes, usable as input to other commands. Defaults to 'xnames' if '--filter' is used")
+        .value_parser(["json", "xnames"])
+        .default_value_if("filter", ArgPredicate::IsPresent, Some("xnames")),
+    )
+    .arg(
+      arg!(-f --filter <EXPRESSION> "Only nodes whose hw components match the expression. Same syntax as 'get hardware cluster --filter'")
+        .conflicts_with("type"),
+    );
 
   Command::new("hardware")
     // .visible_alias("hw")
@@ -280,7 +488,9 @@ pub fn subcommand_get_cfs_configuration() -> Command {
       arg!(-l --limit <VALUE> "Filter records to the <VALUE> most common number of CFS configurations created")
         .value_parser(value_parser!(u8).range(1..)),
     )
-    .arg(arg!(-o --output <FORMAT> "Output format. If missing, it will print output data in human redeable (table) format").value_parser(["json"]))
+    .arg(arg_get_output(&[]))
+    .arg(arg_get_query())
+    .arg(arg_get_columns())
     .arg(arg!(-H --"hsm-group" <HSM_GROUP_NAME> "hsm group name"))
       .group(ArgGroup::new("hsm-group_or_configuration").args(["hsm-group", "name"]))
     .group(ArgGroup::new("configuration_limit").args(["most-recent", "limit"]))
@@ -300,7 +510,10 @@ pub fn subcommand_get_cfs_session() -> Command {
       arg!(-l --limit <VALUE> "Return only last <VALUE> sessions created")
         .value_parser(value_parser!(u8).range(1..)),
     )
-    .arg(arg!(-o --output <FORMAT> "Output format. If missing, it will print output data in human redeable (table) format").value_parser(["json"]))
+    .arg(arg_get_output(&[]))
+    .arg(arg_get_query())
+    .arg(arg_get_columns())
+    .arg(arg_get_watch())
     .arg(arg!(-x --xnames <XNAMES> "Comma separated list of xnames.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0'"))
     .arg(arg!(-H --"hsm-group" <HSM_GROUP_NAME> "hsm group name"))
     .group(ArgGroup::new("hsm-group_or_xnames_or_name").args([
@@ -322,7 +535,9 @@ pub fn subcommand_get_bos_template() -> Command {
         .value_parser(value_parser!(u8).range(1..)),
     )
     .arg(arg!(-H --"hsm-group" <HSM_GROUP_NAME> "hsm group name"))
-    .arg(arg!(-o --output <FORMAT> "Output format. If missing it will print output data in human redeable (table) format").value_parser(["json", "table"]).default_value("table"))
+    .arg(arg_get_output(&[]))
+    .arg(arg_get_query())
+    .arg(arg_get_columns())
     .group(ArgGroup::new("hsm-group_or_template").args(["hsm-group", "name"]))
 }
 
@@ -333,7 +548,7 @@ pub fn subcommand_get_cluster_details() -> Command {
     .arg(arg!(-n --"nids-only-one-line" "Prints nids in one line eg nidxxxxxx,nidyyyyyy,nidzzzzzz,..."))
     .arg(arg!(-x --"xnames-only-one-line" "Prints xnames in one line eg x1001c1s5b0n0,x1001c1s5b0n1,..."))
     .arg(arg!(-s --"status" "Get cluster status:\n - OK: All nodes are operational (booted and configured)\n - OFF: At least one node is OFF\n - ON: No nodes OFF and at least one is ON\n - STANDBY: At least one node's heartbeat is lost\n - UNCONFIGURED: All nodes are READY but at least one of them is being configured\n - FAILED: At least one node configuration failed"))
-    .arg(arg!(-o --output <FORMAT> "Output format. If missing it will print output data in human readable (table) format").value_parser(["table", "table-wide", "json", "summary"]).default_value("table"))
+    .arg(arg_get_output(&["summary"]))
     .arg_required_else_help(true)
     .arg(arg!(<HSM_GROUP_NAME> "hsm group name"))
 }
@@ -345,7 +560,10 @@ pub fn subcommand_get_node_details() -> Command {
     .arg(arg!(-n --"nids-only-one-line" "Prints nids in one line eg nidxxxxxx,nidyyyyyy,nidzzzzzz,..."))
     .arg(arg!(-s --"status" "Get cluster status:\n - OK: All nodes are operational (booted and configured)\n - OFF: At least one node is OFF\n - ON: No nodes OFF and at least one is ON\n - STANDBY: At least one node's heartbeat is lost\n - UNCONFIGURED: All nodes are READY but at least one of them is being configured\n - FAILED: At least one node configuration failed"))
     .arg(arg!(-S --"include-siblings" "Output includes extra nodes related to the ones requested by used. 2 nodes are siblings if they share the same power supply.").action(ArgAction::SetTrue))
-    .arg(arg!(-o --output <FORMAT> "Output format. If missing it will print output data in human readable (table) format").value_parser(["table", "table-wide", "json", "summary"]).default_value("table"))
+    .arg(arg_get_output(&["summary"]))
+    .arg(arg_get_query())
+    .arg(arg_get_columns())
+    .arg(arg_get_watch().conflicts_with_all(["status", "nids-only-one-line"]))
     .arg_required_else_help(true)
     .arg(arg!(<VALUE> "Comma separated list of nids or xnames. Can use comma separated list of nodes or expressions. A node can be represented as an xname or nid and expressions accepted are hostlist or regex.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0', 'nid001313,nid001314', 'x1003c1s7b0n[0-1],x1003c1s7b1n0', 'nid00131[0-9]' or 'nid00131.*'"))
 }
@@ -368,6 +586,9 @@ pub fn subcommand_get_images() -> Command {
         .value_parser(value_parser!(u8).range(1..)),
     )
     .arg(arg!(-H --"hsm-group" <HSM_GROUP_NAME> "hsm group name"))
+    .arg(arg_get_output(&[]))
+    .arg(arg_get_query())
+    .arg(arg_get_columns())
 }
 
 pub fn subcommand_get_boot_parameters() -> Command {
@@ -375,6 +596,8 @@ pub fn subcommand_get_boot_parameters() -> Command {
     // .visible_aliases(["n", "node"])
     .about("Get boot-parameters information")
     .arg(arg!(-H --hosts <VALUE> "Comma separated list of xnames requesting boot script"))
+    // JSON by default, scripts rely on it
+    .arg(arg_get_output(&[]).default_value("json"))
 
   // FIXME: Ignoring nids and macs to avoid checking if tenant has access to the nodes
   // using the nids or macs
@@ -409,9 +632,128 @@ pub fn subcommand_get_redfish_endpoints() -> Command {
     .arg(arg!(-u --uuid <VALUE> "Retrieve the RedfishEndpoint with the given UUID."))
     .arg(arg!(-m --macaddr <VALUE> "Retrieve the RedfishEndpoint with the given MAC address."))
     .arg(arg!(-I --ipaddress <VALUE> "Retrieve the RedfishEndpoint with the given IP address. A blank string will get Redfish endpoints without IP addresses."))
+    // JSON by default, scripts rely on it
+    .arg(arg_get_output(&[]).default_value("json"))
   // .arg(arg!(-l --"last-status" <VALUE> "Retrieve the RedfishEndpoints with the given discovery status."))
 }
 
+pub fn subcommand_get_power_cap() -> Command {
+  Command::new("power-cap")
+    .arg_required_else_help(true)
+    .about("Get power cap controls of nodes, values in watts")
+    .subcommand(
+      Command::new("cluster")
+        .arg_required_else_help(true)
+        .about("Get power cap controls of all nodes in a cluster")
+        .arg(arg_get_output(&[]))
+        .arg(arg!(<CLUSTER_NAME> "Cluster name")),
+    )
+    .subcommand(
+      Command::new("nodes")
+        .arg_required_else_help(true)
+        .about("Get power cap controls of a group of nodes")
+        .arg(arg_get_output(&[]))
+        .arg(arg!(<VALUE> "List of xnames or nids. Can use comma separated list of nodes or expressions. A node can be represented as an xname or nid and expressions accepted are hostlist or regex.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0', 'nid001313,nid001314', 'x1003c1s7b0n[0-1],x1003c1s7b1n0', 'nid00131[0-9]' or 'nid00131.*'")),
+    )
+}
+
+pub fn subcommand_get_power_transition() -> Command {
+  Command::new("power-transition")
+    .arg_required_else_help(true)
+    .about("Get the status of a power transition and the task of each node")
+    .arg(arg!(-w --wait "Show the progress of the power transition until it completes").action(ArgAction::SetTrue))
+    .arg(arg_get_output(&[]))
+    .arg(arg!(<TRANSITION_ID> "Power transition ID"))
+}
+
+/// '--output' argument shared by all 'get' subcommands. Some subcommands accept extra
+/// formats on top of the common ones (eg 'summary')
+pub fn arg_get_output(extra_format_vec: &[&'static str]) -> Arg {
+  let format_vec: Vec<&'static str> = OutputFormat::iter()
+    .map(|format| format.into())
+    .chain(extra_format_vec.iter().copied())
+    .collect();
+
+  arg!(-o --output <FORMAT> "Output format")
+    .value_parser(format_vec)
+    .default_value("table")
+}
+
+/// '--query' argument to filter and reshape the records returned by some 'get' subcommands
+pub fn arg_get_query() -> Arg {
+  arg!(-q --query <JMESPATH> "JMESPath expression applied to the list of records. Field names are the ones shown with '--output json'.\neg \"[?power_status=='OFF'].{xname: xname, nid: nid}\"")
+}
+
+/// '--columns' argument to choose the columns printed by some 'get' subcommands
+pub fn arg_get_columns() -> Arg {
+  arg!(-c --columns <COLUMNS> "Comma separated list of columns to print. Each column is a JMESPath expression evaluated against every record and can be renamed with HEADER=EXPRESSION.\neg 'xname,power_status,IMAGE=boot_image_id'")
+}
+
+/// '--watch' argument to keep refreshing the table printed by some 'get' subcommands
+pub fn arg_get_watch() -> Arg {
+  arg!(-w --watch [INTERVAL] "Refresh the table every INTERVAL seconds (5 if missing) until Ctrl-C is pressed. Rows whose power status, configuration status or session status changed since the previous refresh are highlighted")
+    .value_parser(value_parser!(u64).range(1..))
+    .default_missing_value("5")
+}
+
+/// Returns the query and custom columns selected with the arguments created by
+/// `arg_get_query` and `arg_get_columns`
+pub fn get_output_selection(
+  cli_matches: &ArgMatches,
+) -> Result<OutputSelection, Error> {
+  OutputSelection::new(
+    cli_matches.get_one::<String>("query").map(String::as_str),
+    cli_matches.get_one::<String>("columns").map(String::as_str),
+  )
+}
+
+/// Returns the output format selected with the argument created by `arg_get_output`
+pub fn get_output_format(
+  cli_matches: &ArgMatches,
+) -> Result<OutputFormat, Error> {
+  let output = cli_matches
+    .get_one::<String>("output")
+    .ok_or_else(|| Error::Message("'output' argument missing".to_string()))?;
+
+  output
+    .parse::<OutputFormat>()
+    .map_err(|_| Error::Message(format!("output format '{output}' not valid")))
+}
+
+/// Arguments to restrict the nodes picked by 'apply hardware cluster' and 'plan hw-cluster'
+pub fn args_allocation_constraints() -> [Arg; 4] {
+  [
+    arg!(--affinity <LEVEL> "Keep all the nodes of the target cluster within one cabinet or chassis. The one moving fewer nodes is picked").value_parser(["cabinet", "chassis"]),
+    arg!(--"spread-chassis" <NUM> "Spread the nodes of the target cluster across this number of chassis").value_parser(value_parser!(u64).range(1..)),
+    arg!(--"exclude-failed" "Never pick nodes with failed components (HSM flag 'Warning' or 'Alert'). They are moved to the parent cluster").action(ArgAction::SetTrue),
+    arg!(--"prefer-target" "Pick nodes already in the target cluster before any other node. Always the case unless '--unpin-nodes' is used").action(ArgAction::SetTrue),
+  ]
+}
+
+/// Returns the constraints selected with the arguments created by
+/// `args_allocation_constraints`. 'failed_xname_vec' are the nodes excluded with
+/// '--exclude-failed'
+pub fn get_allocation_constraints(
+  cli_matches: &ArgMatches,
+  failed_xname_vec: Vec<String>,
+) -> Result<AllocationConstraints, Error> {
+  Ok(AllocationConstraints {
+    affinity: cli_matches
+      .get_one::<String>("affinity")
+      .map(|affinity| affinity.parse())
+      .transpose()?,
+    spread_chassis: cli_matches
+      .get_one::<u64>("spread-chassis")
+      .map(|num| *num as usize),
+    exclude_xname_vec: if cli_matches.get_flag("exclude-failed") {
+      failed_xname_vec
+    } else {
+      Vec::new()
+    },
+    prefer_target: cli_matches.get_flag("prefer-target"),
+  })
+}
+
 pub fn subcommand_get() -> Command {
   Command::new("get")
     // .visible_alias("g")
@@ -429,6 +771,8 @@ pub fn subcommand_get() -> Command {
     .subcommand(subcommand_get_boot_parameters())
     .subcommand(subcommand_get_kernel_parameters())
     .subcommand(subcommand_get_redfish_endpoints())
+    .subcommand(subcommand_get_power_cap())
+    .subcommand(subcommand_get_power_transition())
 }
 
 pub fn subcommand_apply_hw_component() -> Command {
@@ -447,6 +791,7 @@ pub fn subcommand_apply_hw_component() -> Command {
       .arg(arg!(-c --"create-target-hsm-group" "If the target cluster name does not exist as HSM group, create it."))
       .arg(arg!(-D --"delete-empty-parent-hsm-group" "If the target HSM group is empty after this action, remove it."))
       .arg(arg!(-u --"unpin-nodes" "It will try to get any nodes available."))
+      .args(args_allocation_constraints())
     )
 }
 
@@ -569,6 +914,10 @@ pub fn subcommand_apply_boot_cluster() -> Command {
     .arg(arg!(--"do-not-reboot" "By default, nodes will restart if SAT file builds an image which is assigned to the nodes through a BOS sessiontemplate, if you do not want to reboot the nodes, then use this flag. The SAT file will be processeed as usual and different elements created but the nodes won't reboot. This means, you will have to run 'manta apply template' command with the sessoin_template created'").action(ArgAction::SetTrue))
     .arg(arg!(-x --"dry-run" "Simulates the execution of the command without making any actual changes.").action(ArgAction::SetTrue))
     .group(ArgGroup::new("boot-image_or_boot-config").args(["boot-image", "boot-image-configuration"]))
+    .arg(arg!(--"batch-size" <N> "Reboot nodes in batches of N nodes (rolling reboot). A batch is rebooted once the nodes in the previous batch are back 'Ready' in HSM with CFS configuration status 'configured'").value_parser(value_parser!(u64).range(1..)).conflicts_with("do-not-reboot"))
+    .arg(arg!(--"max-unavailable" <N> "Max number of nodes down at the same time during a rolling reboot, including nodes that failed. Defaults to the batch size").value_parser(value_parser!(u64).range(1..)).conflicts_with("do-not-reboot"))
+    .arg(arg!(--"max-failed" <N> "Number of nodes allowed to fail during a rolling reboot before aborting the remaining batches").value_parser(value_parser!(usize)).default_value("0"))
+    .arg(arg!(--"health-timeout" <SECONDS> "Seconds to wait for the nodes in a batch to be healthy during a rolling reboot").value_parser(value_parser!(u64)).default_value("1800"))
     .arg(arg!(<CLUSTER_NAME> "Cluster name").required(true))
 }
 
@@ -613,6 +962,8 @@ pub fn subcommand_power() -> Command {
             .about("Command to power on all nodes in a cluster")
             .arg(arg!(-R --reason <TEXT> "reason to power on"))
             .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+            .arg(arg!(--"no-wait" "Print the power transition ID and exit without waiting for it to complete. Use 'manta get power-transition' to check its status").action(ArgAction::SetTrue))
+            .arg(arg!(--"retry-failed" [ATTEMPTS] "Power transition is created again for the nodes whose task failed, up to ATTEMPTS times (1 by default)").value_parser(value_parser!(usize)).default_missing_value("1").default_value("0").conflicts_with("no-wait"))
             .arg(arg!(-o --output <FORMAT> "Output format.").value_parser(["table", "json"]).default_value("table"))
             .arg(arg!(<CLUSTER_NAME> "Cluster name")),
         )
@@ -622,6 +973,8 @@ pub fn subcommand_power() -> Command {
             .arg_required_else_help(true)
             .about("Command to power on a group of nodes.\neg: 'x1001c1s0b0n1,x1001c1s0b1n0'")
             .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+            .arg(arg!(--"no-wait" "Print the power transition ID and exit without waiting for it to complete. Use 'manta get power-transition' to check its status").action(ArgAction::SetTrue))
+            .arg(arg!(--"retry-failed" [ATTEMPTS] "Power transition is created again for the nodes whose task failed, up to ATTEMPTS times (1 by default)").value_parser(value_parser!(usize)).default_missing_value("1").default_value("0").conflicts_with("no-wait"))
             .arg(arg!(-o --output <FORMAT> "Output format.").value_parser(["table", "json"]).default_value("table"))
             .arg(arg!(<VALUE> "List of xnames or nids. Can use comma separated list of nodes or expressions. A node can be represented as an xname or nid and expressions accepted are hostlist or regex.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0', 'nid001313,nid001314', 'x1003c1s7b0n[0-1],x1003c1s7b1n0', 'nid00131[0-9]' or 'nid00131.*'")),
         ),
@@ -638,6 +991,8 @@ pub fn subcommand_power() -> Command {
             .arg(arg!(-g --graceful "graceful shutdow").action(ArgAction::SetFalse))
             .arg(arg!(-R --reason <TEXT> "reason to power off"))
             .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+            .arg(arg!(--"no-wait" "Print the power transition ID and exit without waiting for it to complete. Use 'manta get power-transition' to check its status").action(ArgAction::SetTrue))
+            .arg(arg!(--"retry-failed" [ATTEMPTS] "Power transition is created again for the nodes whose task failed, up to ATTEMPTS times (1 by default)").value_parser(value_parser!(usize)).default_missing_value("1").default_value("0").conflicts_with("no-wait"))
             .arg(arg!(-o --output <FORMAT> "Output format.").value_parser(["table", "json"]).default_value("table"))
             .arg(arg!(<CLUSTER_NAME> "Cluster name")),
         )
@@ -648,6 +1003,8 @@ pub fn subcommand_power() -> Command {
             .about("Command to power off a group of nodes.\neg: 'x1001c1s0b0n1,x1001c1s0b1n0'")
             .arg(arg!(-g --graceful "graceful shutdown").action(ArgAction::SetFalse))
             .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+            .arg(arg!(--"no-wait" "Print the power transition ID and exit without waiting for it to complete. Use 'manta get power-transition' to check its status").action(ArgAction::SetTrue))
+            .arg(arg!(--"retry-failed" [ATTEMPTS] "Power transition is created again for the nodes whose task failed, up to ATTEMPTS times (1 by default)").value_parser(value_parser!(usize)).default_missing_value("1").default_value("0").conflicts_with("no-wait"))
             .arg(arg!(-o --output <FORMAT> "Output format.").value_parser(["table", "json"]).default_value("table"))
             .arg(arg!(<VALUE> "List of xnames or nids. Can use comma separated list of nodes or expressions. A node can be represented as an xname or nid and expressions accepted are hostlist or regex.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0', 'nid001313,nid001314', 'x1003c1s7b0n[0-1],x1003c1s7b1n0', 'nid00131[0-9]' or 'nid00131.*'")),
         ),
@@ -663,8 +1020,14 @@ pub fn subcommand_power() -> Command {
             .about("Command to power reset all nodes in a cluster")
             .arg(arg!(-g --graceful "graceful power reset").action(ArgAction::SetFalse))
             .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+            .arg(arg!(--"no-wait" "Print the power transition ID and exit without waiting for it to complete. Use 'manta get power-transition' to check its status").action(ArgAction::SetTrue))
+            .arg(arg!(--"retry-failed" [ATTEMPTS] "Power transition is created again for the nodes whose task failed, up to ATTEMPTS times (1 by default)").value_parser(value_parser!(usize)).default_missing_value("1").default_value("0").conflicts_with("no-wait"))
             .arg(arg!(-o --output <FORMAT> "Output format.").value_parser(["table", "json"]).default_value("table"))
             .arg(arg!(-r --reason <TEXT> "reason to power reset"))
+            .arg(arg!(--"batch-size" <N> "Reboot nodes in batches of N nodes (rolling reboot). A batch is rebooted once the nodes in the previous batch are back 'Ready' in HSM with CFS configuration status 'configured'").value_parser(value_parser!(u64).range(1..)).conflicts_with("no-wait"))
+            .arg(arg!(--"max-unavailable" <N> "Max number of nodes down at the same time during a rolling reboot, including nodes that failed. Defaults to the batch size").value_parser(value_parser!(u64).range(1..)).conflicts_with("no-wait"))
+            .arg(arg!(--"max-failed" <N> "Number of nodes allowed to fail during a rolling reboot before aborting the remaining batches").value_parser(value_parser!(usize)).default_value("0"))
+            .arg(arg!(--"health-timeout" <SECONDS> "Seconds to wait for the nodes in a batch to be healthy during a rolling reboot").value_parser(value_parser!(u64)).default_value("1800"))
             .arg(arg!(<CLUSTER_NAME> "Cluster name")),
         )
         .subcommand(
@@ -674,6 +1037,8 @@ pub fn subcommand_power() -> Command {
             .about("Command to power reset a group of nodes.\neg: 'x1001c1s0b0n1,x1001c1s0b1n0'")
             .arg(arg!(-g --graceful "graceful power reset").action(ArgAction::SetFalse))
             .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+            .arg(arg!(--"no-wait" "Print the power transition ID and exit without waiting for it to complete. Use 'manta get power-transition' to check its status").action(ArgAction::SetTrue))
+            .arg(arg!(--"retry-failed" [ATTEMPTS] "Power transition is created again for the nodes whose task failed, up to ATTEMPTS times (1 by default)").value_parser(value_parser!(usize)).default_missing_value("1").default_value("0").conflicts_with("no-wait"))
             .arg(arg!(-o --output <FORMAT> "Output format.").value_parser(["table", "json"]).default_value("table"))
             .arg(arg!(<VALUE> "List of xnames or nids. Can use comma separated list of nodes or expressions. A node can be represented as an xname or nid and expressions accepted are hostlist or regex.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0', 'nid001313,nid001314', 'x1003c1s7b0n[0-1],x1003c1s7b1n0', 'nid00131[0-9]' or 'nid00131.*'")),
         ),
@@ -687,6 +1052,17 @@ pub fn subcommand_log() -> Command {
     .arg(arg!([VALUE] "Show logs related to a session name, group name, xname or nid. eg: x1003c1s7b0n0, nid001313, zinal, batcher-64d35a81-d0e1-496d-9eda-0010e502f2a3"))
 }
 
+pub fn subcommand_cache() -> Command {
+  Command::new("cache")
+    .arg_required_else_help(true)
+    .about("Manage the response cache")
+    .subcommand(
+      Command::new("clear")
+        .about("Delete the cached responses of the current site")
+        .arg(arg!(-a --all "Delete the cached responses of all sites").action(ArgAction::SetTrue)),
+    )
+}
+
 pub fn subcommand_validate_local_repo() -> Command {
   Command::new("validate-local-repo")
     // .visible_alias("vlr")
@@ -903,6 +1279,34 @@ pub fn subcommand_add() -> Command {
     .subcommand(subcommand_add_redfish_endpoint())
 }
 
+pub fn subcommand_apply_power_cap() -> Command {
+  Command::new("power-cap")
+    .arg_required_else_help(true)
+    .about("Set power cap of nodes")
+    .subcommand(
+      Command::new("cluster")
+        .arg_required_else_help(true)
+        .about("Set power cap of all nodes in a cluster")
+        .arg(arg!(-w --watts <WATTS> "New power cap value in watts").value_parser(value_parser!(usize)).required(true))
+        .arg(arg!(-C --control <NAME> "Power cap control to change").default_value("Node Power Limit"))
+        .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+        .arg(arg!(-d --"dry-run" "Validates the new power cap without changing it").action(ArgAction::SetTrue))
+        .arg(arg_get_output(&[]))
+        .arg(arg!(<CLUSTER_NAME> "Cluster name")),
+    )
+    .subcommand(
+      Command::new("nodes")
+        .arg_required_else_help(true)
+        .about("Set power cap of a group of nodes")
+        .arg(arg!(-w --watts <WATTS> "New power cap value in watts").value_parser(value_parser!(usize)).required(true))
+        .arg(arg!(-C --control <NAME> "Power cap control to change").default_value("Node Power Limit"))
+        .arg(arg!(-y --"assume-yes" "Automatic yes to prompts; assume 'yes' as answer to all prompts and run non-interactively.").action(ArgAction::SetTrue))
+        .arg(arg!(-d --"dry-run" "Validates the new power cap without changing it").action(ArgAction::SetTrue))
+        .arg(arg_get_output(&[]))
+        .arg(arg!(<VALUE> "List of xnames or nids. Can use comma separated list of nodes or expressions. A node can be represented as an xname or nid and expressions accepted are hostlist or regex.\neg 'x1003c1s7b0n0,1003c1s7b0n1,x1003c1s7b1n0', 'nid001313,nid001314', 'x1003c1s7b0n[0-1],x1003c1s7b1n0', 'nid00131[0-9]' or 'nid00131.*'")),
+    )
+}
+
 pub fn subcommand_apply() -> Command {
   Command::new("apply")
     // .visible_alias("a")
@@ -923,6 +1327,62 @@ pub fn subcommand_apply() -> Command {
     .subcommand(subcommand_apply_session())
     .subcommand(subcommand_apply_ephemeral_environment())
     .subcommand(subcommand_apply_template())
+    .subcommand(subcommand_apply_power_cap())
+}
+
+pub fn subcommand_plan() -> Command {
+  Command::new("plan")
+    .arg_required_else_help(true)
+    .about("Calculate changes to the system without applying them")
+    .subcommand(Command::new("hw-cluster")
+      .arg_required_else_help(true)
+      .about("Calculate the nodes 'apply hardware cluster' would move between the target and parent clusters, the score of each node and why it was picked. Can run offline against a hardware inventory snapshot")
+      .arg(arg!(-P --pattern <VALUE> "Hw pattern with keywords to fuzzy find hardware components to assign to the cluster like <hw component name>:<hw component quantity>[:<hw component name>:<hw component quantity>]. Eg 'a100:12:epyc:5'").required(true))
+      .arg(arg!(-t --"target-cluster" <TARGET_CLUSTER_NAME> "Target cluster name. This is the name of the cluster the pattern is applying to.").required(true))
+      .arg(arg!(-p --"parent-cluster" <PARENT_CLUSTER_NAME> "Parent cluster name. The parent cluster is the one offering and receiving resources from the target cluster.").required(true))
+      .arg(arg!(-u --"unpin-nodes" "It will try to get any nodes available.").action(ArgAction::SetTrue))
+      .arg(arg!(-i --"inventory-file" <FILE> "Hardware inventory snapshot to use instead of the live system. No access to the system is needed").value_parser(value_parser!(PathBuf)).value_hint(ValueHint::FilePath))
+      .arg(arg!(-s --"save-inventory" <FILE> "Save the hardware inventory of both clusters to a snapshot file usable with '--inventory-file'").value_parser(value_parser!(PathBuf)).value_hint(ValueHint::FilePath).conflicts_with("inventory-file"))
+      .arg(arg!(-o --output <FORMAT> "Output format").value_parser(["json", "yaml"]).default_value("json"))
+      .args(args_allocation_constraints())
+    )
+}
+
+pub fn subcommand_diff() -> Command {
+  Command::new("diff")
+    .arg_required_else_help(true)
+    .about("Compare the system across time")
+    .subcommand(Command::new("hardware")
+      .arg_required_else_help(true)
+      .about("FRUs added, removed or replaced in each node between two hardware snapshots created with 'get hardware cluster --snapshot' or 'plan hw-cluster --save-inventory'")
+      .arg(arg!(<SNAPSHOT_BEFORE> "Hardware snapshot file").value_parser(value_parser!(PathBuf)).value_hint(ValueHint::FilePath))
+      .arg(arg!(<SNAPSHOT_AFTER> "Hardware snapshot file or 'live' to compare against the current hardware of the nodes in the first snapshot and its cluster").value_hint(ValueHint::FilePath))
+      .arg(arg!(-o --output <FORMAT> "Output format").value_parser(["table", "json"]).default_value("table"))
+    )
+}
+
+pub fn subcommand_import() -> Command {
+  Command::new("import")
+    .arg_required_else_help(true)
+    .about("Add elements to the system in bulk from a file")
+    .subcommand(Command::new("redfish-endpoints")
+      .arg_required_else_help(true)
+      .about("Add the redfish endpoints in a CSV or YAML file, eg the BMCs of a new cabinet. IDs must be BMC xnames and IDs, FQDNs, MAC and IP addresses can't be duplicated. Columns/keys are the ones produced by 'export redfish-endpoints'")
+      .arg(arg!(<FILE> "CSV ('.csv') or YAML ('.yaml' or '.yml') file with the redfish endpoints").value_parser(value_parser!(PathBuf)).value_hint(ValueHint::FilePath))
+      .arg(arg!(-u --"update-existing" "Update the redfish endpoints already in the system instead of failing").action(ArgAction::SetTrue))
+      .arg(arg!(-x --"dry-run" "Validate the file and show the redfish endpoints to add or update without changing the system").action(ArgAction::SetTrue))
+    )
+}
+
+pub fn subcommand_export() -> Command {
+  Command::new("export")
+    .arg_required_else_help(true)
+    .about("Save elements in the system to a file usable with 'import'")
+    .subcommand(Command::new("redfish-endpoints")
+      .about("Redfish endpoints in the system in the format used by 'import redfish-endpoints'. Passwords are never exported")
+      .arg(arg!(-o --output <FORMAT> "Output format, ignored if '--file' is used").value_parser(["yaml", "csv"]).default_value("yaml"))
+      .arg(arg!(-f --file <FILE> "Save the redfish endpoints to this file instead of printing them. The format is taken from the extension ('.csv', '.yaml' or '.yml')").value_parser(value_parser!(PathBuf)).value_hint(ValueHint::FilePath))
+    )
 }
 
 pub fn subcommand_migrate() -> Command {
@@ -997,3 +1457,6 @@ pub fn subcommand_upload_boot_image() -> Command {
     .about("Uploads a boot image")
     .arg(arg!(<IMAGE_ID> "Image id to upload"))
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,67 @@
+use clap::Command;
+use manta_backend_dispatcher::types::capability::{Capabilities, Capability};
+
+use super::{
+  build_cli, hide_subcommand, hide_unsupported_subcommands,
+  READ_ONLY_SUBCOMMAND_VEC, SUBCOMMAND_CAPABILITY_VEC,
+};
+
+fn find_subcommand<'a>(
+  cli: &'a Command,
+  subcommand_path: &[&str],
+) -> Option<&'a Command> {
+  subcommand_path
+    .iter()
+    .try_fold(cli, |command, name| command.find_subcommand(name))
+}
+
+#[test]
+fn test_subcommand_capability_paths_exist() {
+  let cli = build_cli();
+
+  for (subcommand_path, _) in SUBCOMMAND_CAPABILITY_VEC {
+    assert!(
+      find_subcommand(&cli, subcommand_path).is_some(),
+      "Subcommand '{}' not found",
+      subcommand_path.join(" ")
+    );
+  }
+}
+
+#[test]
+fn test_read_only_subcommand_paths_exist() {
+  let cli = build_cli();
+
+  for subcommand_path in READ_ONLY_SUBCOMMAND_VEC {
+    assert!(
+      find_subcommand(&cli, subcommand_path).is_some(),
+      "Subcommand '{}' not found",
+      subcommand_path.join(" ")
+    );
+  }
+}
+
+#[test]
+fn test_hide_unsupported_subcommands() {
+  let cli = hide_unsupported_subcommands(
+    build_cli(),
+    &Capabilities::new(&[Capability::Cfs]),
+  );
+
+  assert!(!find_subcommand(&cli, &["get", "sessions"])
+    .unwrap()
+    .is_hide_set());
+  assert!(find_subcommand(&cli, &["get", "images"])
+    .unwrap()
+    .is_hide_set());
+  assert!(find_subcommand(&cli, &["power"]).unwrap().is_hide_set());
+  assert!(!find_subcommand(&cli, &["get"]).unwrap().is_hide_set());
+}
+
+#[test]
+fn test_hide_missing_subcommand() {
+  let cli = hide_subcommand(build_cli(), &["get", "missing"]);
+  let cli = hide_subcommand(cli, &["missing", "sessions"]);
+
+  assert!(!find_subcommand(&cli, &["get"]).unwrap().is_hide_set());
+}
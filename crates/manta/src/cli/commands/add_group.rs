@@ -1,14 +1,14 @@
-use crate::common::{self, jwt_ops};
+use crate::common;
 use crate::{
-  common::{
-    audit::Audit, authorization::validate_target_hsm_members, kafka::Kafka,
-  },
+  common::{audit, authorization::validate_target_hsm_members},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 use dialoguer::theme::ColorfulTheme;
 use manta_backend_dispatcher::interfaces::hsm::component::ComponentTrait;
 use manta_backend_dispatcher::{
-  interfaces::hsm::group::GroupTrait, types::Group,
+  audit::{AuditOutcome, Auditor},
+  interfaces::hsm::group::GroupTrait,
+  types::Group,
 };
 
 /// Creates a group of nodes. It is allowed to create a group with no nodes.
@@ -20,7 +20,7 @@ pub async fn exec(
   hosts_expression_opt: Option<&String>,
   assume_yes: bool,
   dryrun: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   let xname_vec_opt: Option<Vec<String>> = match hosts_expression_opt {
     Some(hosts_expression) => {
@@ -112,6 +112,22 @@ pub async fn exec(
   // Call backend to create group
   let result = backend.add_group(&auth_token, group).await;
 
+  // Audit
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(auth_token), "create group")
+      .with_message(&format!("Create Group '{}'", label))
+      .with_xnames(&xname_vec_opt.unwrap_or_default())
+      .with_groups(&[label]);
+
+    let event = match &result {
+      Ok(_) => event,
+      Err(_) => event.with_outcome(AuditOutcome::Failure),
+    };
+
+    auditor.send(&event).await;
+  }
+
   match result {
     Ok(_) => {
       eprintln!("Group '{}' created", label);
@@ -121,21 +137,4 @@ pub async fn exec(
       std::process::exit(1);
     }
   }
-
-  // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(auth_token).unwrap_or_default();
-    let user_id =
-      jwt_ops::get_preferred_username(auth_token).unwrap_or_default();
-
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": xname_vec_opt.unwrap_or_default()}, "group": label, "message": format!("Create Group '{}'", label)});
-
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
-
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
-  }
 }
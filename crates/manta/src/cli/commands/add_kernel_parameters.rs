@@ -1,9 +1,10 @@
 use crate::{
-  common::{self, audit::Audit, jwt_ops, kafka::Kafka},
+  common::{self, audit},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 use dialoguer::theme::ColorfulTheme;
 use manta_backend_dispatcher::{
+  audit::Auditor,
   error::Error,
   interfaces::{
     bss::BootParametersTrait,
@@ -21,7 +22,7 @@ pub async fn exec(
   hosts_expression: &str,
   assume_yes: bool,
   do_not_reboot: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) -> Result<(), Error> {
   let mut need_restart = false;
   log::info!("Add kernel parameters");
@@ -81,6 +82,9 @@ pub async fn exec(
 
   log::debug!("new kernel params: {:#?}", current_node_boot_params_vec);
 
+  // Nodes whose boot parameters could not be updated
+  let mut failed_xname_vec: Vec<String> = Vec::new();
+
   for mut boot_parameter in current_node_boot_params_vec {
     log::info!(
       "Add '{:?}' kernel parameters to '{}'",
@@ -101,7 +105,8 @@ pub async fn exec(
 
       if let Err(e) = boot_parametes_rslt {
         eprintln!("{:#?}", e);
-        std::process::exit(1);
+        failed_xname_vec = boot_parameter.hosts;
+        break;
       }
 
       if need_restart {
@@ -114,10 +119,7 @@ pub async fn exec(
   }
 
   // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
-
+  if let Some(auditor) = auditor_opt {
     // FIXME: We should not need to make this call here but at the beginning of the method as a
     // prerequisite
     let xnames: Vec<&str> =
@@ -127,15 +129,19 @@ pub async fn exec(
       .get_group_map_and_filter_by_member_vec(shasta_token, &xnames)
       .await?;
 
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": xname_vec}, "group": group_map_vec.keys().collect::<Vec<_>>(), "message": format!("Add kernel parameters: {}", kernel_params)});
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "add kernel parameters")
+      .with_message(&format!("Add kernel parameters: {}", kernel_params))
+      .with_xnames(&xname_vec)
+      .with_groups(&group_map_vec.keys().collect::<Vec<_>>());
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    let event = audit::with_failed_xnames(event, &failed_xname_vec);
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+    auditor.send(&event).await;
+  }
+
+  if !failed_xname_vec.is_empty() {
+    std::process::exit(1);
   }
 
   // Reboot if needed
@@ -152,8 +158,10 @@ pub async fn exec(
         &xname_to_reboot_vec.join(","),
         true,
         assume_yes,
+        false,
+        0,
         "table",
-        kafka_audit_opt,
+        auditor_opt,
       )
       .await;
     }
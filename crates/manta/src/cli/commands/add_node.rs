@@ -1,5 +1,6 @@
 use anyhow::Result;
 use manta_backend_dispatcher::{
+  audit::{AuditOutcome, Auditor},
   interfaces::hsm::{
     component::ComponentTrait, group::GroupTrait,
     hardware_inventory::HardwareInventory,
@@ -10,7 +11,7 @@ use manta_backend_dispatcher::{
 };
 
 use crate::{
-  common::{audit::Audit, jwt_ops, kafka::Kafka},
+  common::audit,
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -22,7 +23,7 @@ pub async fn exec(
   enabled: bool,
   arch_opt: Option<String>,
   hw_inventory_opt: Option<HWInventoryByLocationList>,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) -> Result<()> {
   // Create node api payload
   let component: ComponentCreate = ComponentCreate {
@@ -45,38 +46,40 @@ pub async fn exec(
     force: Some(true),
   };
 
-  // Add node to backend
-  backend.post_nodes(shasta_token, components).await?;
+  let result: Result<()> = async {
+    // Add node to backend
+    backend.post_nodes(shasta_token, components).await?;
 
-  log::info!("Node saved '{}'", id);
+    log::info!("Node saved '{}'", id);
 
-  // Add hardware inventory
-  if let Some(hw_inventory) = hw_inventory_opt {
-    log::info!("Adding hardware inventory for '{}'", id);
-    backend
-      .post_inventory_hardware(&shasta_token, hw_inventory)
-      .await?;
-  }
+    // Add hardware inventory
+    if let Some(hw_inventory) = hw_inventory_opt {
+      log::info!("Adding hardware inventory for '{}'", id);
+      backend
+        .post_inventory_hardware(&shasta_token, hw_inventory)
+        .await?;
+    }
 
-  // Add node to group
-  backend.post_member(shasta_token, group, id).await?;
+    // Add node to group
+    backend.post_member(shasta_token, group, id).await?;
 
-  // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap_or_default();
-    let user_id =
-      jwt_ops::get_preferred_username(shasta_token).unwrap_or_default();
+    Ok(())
+  }
+  .await;
 
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": id}, "group": [], "message": "add node"});
+  // Audit
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "add node")
+      .with_xnames(&[id]);
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    let event = match &result {
+      Ok(_) => event,
+      Err(_) => event.with_outcome(AuditOutcome::Failure),
+    };
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+    auditor.send(&event).await;
   }
 
-  Ok(())
+  result
 }
@@ -1,10 +1,11 @@
 use dialoguer::{theme::ColorfulTheme, Confirm};
+use manta_backend_dispatcher::audit::{AuditOutcome, Auditor};
 use manta_backend_dispatcher::interfaces::hsm::{
   component::ComponentTrait, group::GroupTrait,
 };
 
 use crate::{
-  common::{self, audit::Audit, jwt_ops, kafka::Kafka},
+  common::{self, audit},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -15,7 +16,7 @@ pub async fn exec(
   target_hsm_name: &String,
   hosts_expression: &str,
   dryrun: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   // Convert user input to xname
   let node_metadata_available_vec = backend
@@ -91,6 +92,22 @@ pub async fn exec(
     .add_members_to_group(shasta_token, &target_hsm_name, xnames_to_move)
     .await;
 
+  // Audit
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "add nodes to group")
+      .with_message(&format!("add nodes to group: {}", target_hsm_name))
+      .with_xnames(&xname_to_move_vec)
+      .with_groups(&[target_hsm_name]);
+
+    let event = match &node_migration_rslt {
+      Ok(_) => event,
+      Err(_) => event.with_outcome(AuditOutcome::Failure),
+    };
+
+    auditor.send(&event).await;
+  }
+
   match node_migration_rslt {
     Ok(mut target_hsm_group_member_vec) => {
       target_hsm_group_member_vec.sort();
@@ -101,20 +118,4 @@ pub async fn exec(
     }
     Err(e) => eprintln!("{}", e),
   }
-
-  // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
-
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": xname_to_move_vec}, "group": vec![target_hsm_name], "message": format!("add nodes to group: {}", target_hsm_name)});
-
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
-
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
-  }
 }
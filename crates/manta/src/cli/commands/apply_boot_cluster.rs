@@ -1,7 +1,9 @@
+use manta_backend_dispatcher::audit::Auditor;
 use manta_backend_dispatcher::interfaces::hsm::group::GroupTrait;
 
 use crate::{
-  cli::commands::apply_boot_node, common::kafka::Kafka,
+  cli::commands::apply_boot_node,
+  common::rolling_reboot::RollingReboot,
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -19,8 +21,9 @@ pub async fn exec(
   hsm_group_name: &String,
   assume_yes: bool,
   do_not_reboot: bool,
+  rolling_reboot_opt: Option<&RollingReboot>,
   dry_run: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   let xname_vec_rslt = backend
     .get_member_vec_from_group_name_vec(
@@ -49,8 +52,9 @@ pub async fn exec(
     &xname_vec.join(","),
     assume_yes,
     do_not_reboot,
+    rolling_reboot_opt,
     dry_run,
-    kafka_audit_opt,
+    auditor_opt,
   )
   .await;
 
@@ -1,7 +1,9 @@
 use crate::{
   cli::commands::power_reset_nodes,
   common::{
-    self, ims_ops::get_image_vec_related_cfs_configuration_name, kafka::Kafka,
+    self, audit,
+    ims_ops::get_image_vec_related_cfs_configuration_name,
+    rolling_reboot::{self, RollingReboot},
   },
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
@@ -9,6 +11,7 @@ use crate::{
 use anyhow::Error;
 use dialoguer::{theme::ColorfulTheme, Confirm};
 use manta_backend_dispatcher::{
+  audit::Auditor,
   interfaces::{
     bss::BootParametersTrait, cfs::CfsTrait, hsm::component::ComponentTrait,
     ims::ImsTrait,
@@ -28,8 +31,9 @@ pub async fn exec(
   hosts_expression: &str,
   assume_yes: bool,
   do_not_reboot: bool,
+  rolling_reboot_opt: Option<&RollingReboot>,
   dry_run: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) -> Result<(), Error> {
   let mut need_restart = false;
 
@@ -282,16 +286,56 @@ pub async fn exec(
         .map(|xname| xname.to_string())
         .collect();
 
-      power_reset_nodes::exec(
-        &backend,
-        shasta_token,
-        &nodes.join(","),
-        true,
-        assume_yes,
-        "table",
-        kafka_audit_opt,
-      )
-      .await;
+      if let Some(rolling_reboot) = rolling_reboot_opt {
+        let summary = rolling_reboot::exec(
+          backend,
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          &nodes,
+          true,
+          rolling_reboot,
+        )
+        .await;
+
+        println!(
+          "Rolling reboot finished: {} healthy, {} failed, {} skipped",
+          summary.healthy.len(),
+          summary.failed.len(),
+          summary.skipped.len()
+        );
+
+        if let Some(auditor) = auditor_opt {
+          let event = auditor
+            .new_event(audit::get_user(shasta_token), "power reset nodes")
+            .with_xnames(&nodes)
+            .with_message(&summary.audit_message(rolling_reboot))
+            .with_outcome(summary.audit_outcome());
+
+          auditor.send(&event).await;
+        }
+
+        if !summary.failed.is_empty() || !summary.skipped.is_empty() {
+          return Err(Error::msg(format!(
+            "Node/s failed: {}\nNode/s not rebooted: {}",
+            summary.failed.join(", "),
+            summary.skipped.join(", ")
+          )));
+        }
+      } else {
+        power_reset_nodes::exec(
+          &backend,
+          shasta_token,
+          &nodes.join(","),
+          true,
+          assume_yes,
+          false,
+          0,
+          "table",
+          auditor_opt,
+        )
+        .await;
+      }
     }
 
     Ok(())
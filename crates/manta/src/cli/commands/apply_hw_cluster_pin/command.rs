@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 
 use crate::{
-  cli::commands::apply_hw_cluster_pin::utils::{
-    calculate_hsm_hw_component_summary, get_hsm_node_hw_component_counter,
-    resolve_hw_description_to_xnames,
+  cli::commands::{
+    apply_hw_cluster_pin::constraints::{
+      resolve_hw_description_to_xnames_with_constraints, AllocationConstraints,
+    },
+    apply_hw_cluster_pin::utils::{
+      calculate_hsm_hw_component_summary, get_hsm_node_hw_component_counter,
+    },
   },
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
@@ -20,6 +24,7 @@ pub async fn exec(
   dryrun: bool,
   create_target_hsm_group: bool,
   delete_empty_parent_hsm_group: bool,
+  constraints: &AllocationConstraints,
 ) {
   // *********************************************************************************************************
   // PREPREQUISITES - FORMAT USER INPUT
@@ -241,12 +246,23 @@ pub async fn exec(
   let (
     target_hsm_node_hw_component_count_vec,
     parent_hsm_node_hw_component_count_vec,
-  ) = resolve_hw_description_to_xnames(
+    _,
+    _,
+  ) = match resolve_hw_description_to_xnames_with_constraints(
     target_hsm_node_hw_component_count_vec,
     parent_hsm_node_hw_component_count_vec,
     user_defined_target_hsm_hw_component_count_hashmap,
+    constraints,
+    false,
   )
-  .await;
+  .await
+  {
+    Ok(resolution) => resolution,
+    Err(e) => {
+      eprintln!("ERROR - {}", e);
+      std::process::exit(1);
+    }
+  };
 
   // Calculate hw component counters (summary) across all node within the HSM group
   let target_hsm_hw_component_summary_hashmap =
@@ -0,0 +1,472 @@
+use std::{
+  cmp::{Ordering, Reverse},
+  collections::{BTreeMap, HashMap, HashSet},
+  fmt,
+  str::FromStr,
+};
+
+use manta_backend_dispatcher::error::Error;
+use serde::Serialize;
+
+use crate::{
+  cli::commands::apply_hw_cluster_pin,
+  common::node_ops::{self, NodeLocation},
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+use super::utils::{
+  calculate_hsm_hw_component_summary, calculate_hsm_node_scores_from_final_hsm,
+  calculate_hw_component_scarcity_scores, keep_iterating_final_hsm,
+};
+
+/// HSM flags of nodes with failed components
+const FAILED_NODE_FLAG_VEC: &[&str] = &["Warning", "Alert"];
+
+/// Part of the system nodes can be kept together in
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TopologyLevel {
+  Cabinet,
+  Chassis,
+}
+
+impl TopologyLevel {
+  /// Cabinet or chassis xname the node belongs to, none if the xname is not a node xname
+  pub fn get_domain(&self, xname: &str) -> Option<String> {
+    NodeLocation::from_xname(xname).map(|location| match self {
+      TopologyLevel::Cabinet => location.cabinet_xname(),
+      TopologyLevel::Chassis => location.chassis_xname(),
+    })
+  }
+}
+
+impl fmt::Display for TopologyLevel {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TopologyLevel::Cabinet => write!(f, "cabinet"),
+      TopologyLevel::Chassis => write!(f, "chassis"),
+    }
+  }
+}
+
+impl FromStr for TopologyLevel {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "cabinet" => Ok(TopologyLevel::Cabinet),
+      "chassis" => Ok(TopologyLevel::Chassis),
+      _ => Err(Error::Message(format!(
+        "Topology level '{}' not valid, use 'cabinet' or 'chassis'",
+        value
+      ))),
+    }
+  }
+}
+
+/// Restrictions on the nodes picked for the target HSM group on top of the hw components
+/// requested
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AllocationConstraints {
+  /// Keep all the nodes of the target HSM group within one cabinet or chassis
+  pub affinity: Option<TopologyLevel>,
+  /// Spread the nodes of the target HSM group across this number of chassis
+  pub spread_chassis: Option<usize>,
+  /// Nodes never picked, eg nodes with failed components. They end up in the parent HSM
+  /// group
+  pub exclude_xname_vec: Vec<String>,
+  /// Pick nodes already in the target HSM group before any other node. Always the case
+  /// when pinning nodes
+  pub prefer_target: bool,
+}
+
+impl AllocationConstraints {
+  pub fn is_empty(&self) -> bool {
+    self.affinity.is_none()
+      && self.spread_chassis.is_none()
+      && self.exclude_xname_vec.is_empty()
+      && !self.prefer_target
+  }
+}
+
+/// True if the HSM flag of a node means some of its components failed
+pub fn is_failed_node_flag(flag: &str) -> bool {
+  FAILED_NODE_FLAG_VEC.contains(&flag)
+}
+
+/// Nodes in the system with failed components
+pub async fn get_failed_xname_vec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+) -> Result<Vec<String>, Error> {
+  Ok(
+    node_ops::get_node_flag_map(backend, shasta_token)
+      .await?
+      .into_iter()
+      .filter(|(_, flag)| is_failed_node_flag(flag))
+      .map(|(xname, _)| xname)
+      .collect(),
+  )
+}
+
+/// Same as `resolve_hw_description_to_xnames_with_scores` (pin or unpin) but the nodes picked
+/// for the target HSM group also meet the constraints. Without constraints the pin or unpin
+/// algorithm is used as is
+pub async fn resolve_hw_description_to_xnames_with_constraints(
+  target_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  parent_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  user_defined_target_hsm_hw_component_count_hashmap: HashMap<String, usize>,
+  constraints: &AllocationConstraints,
+  unpin: bool,
+) -> Result<
+  (
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, f32)>,
+    HashMap<String, f32>,
+  ),
+  Error,
+> {
+  if constraints.is_empty() {
+    let resolution = if unpin {
+      csm_rs::commands::apply_hw_cluster_unpin::utils::resolve_hw_description_to_xnames_with_scores(
+        target_hsm_node_hw_component_count_vec,
+        parent_hsm_node_hw_component_count_vec,
+        user_defined_target_hsm_hw_component_count_hashmap,
+      )
+      .map_err(|e| Error::Message(e.to_string()))?
+    } else {
+      apply_hw_cluster_pin::utils::resolve_hw_description_to_xnames_with_scores(
+        target_hsm_node_hw_component_count_vec,
+        parent_hsm_node_hw_component_count_vec,
+        user_defined_target_hsm_hw_component_count_hashmap,
+      )
+      .await?
+    };
+
+    return Ok(resolution);
+  }
+
+  if constraints.affinity == Some(TopologyLevel::Chassis)
+    && constraints.spread_chassis.is_some_and(|num| num > 1)
+  {
+    return Err(Error::Message(
+      "Nodes can't be kept within one chassis and spread across several chassis"
+        .to_string(),
+    ));
+  }
+
+  let target_xname_set: HashSet<String> =
+    target_hsm_node_hw_component_count_vec
+      .iter()
+      .map(|(xname, _)| xname.clone())
+      .collect();
+
+  // Nodes in both groups are counted once
+  let mut combined_hsm_node_hw_component_count_vec =
+    parent_hsm_node_hw_component_count_vec.clone();
+
+  for elem in target_hsm_node_hw_component_count_vec {
+    if !combined_hsm_node_hw_component_count_vec
+      .iter()
+      .any(|(xname, _)| xname.eq(&elem.0))
+    {
+      combined_hsm_node_hw_component_count_vec.push(elem);
+    }
+  }
+
+  let candidate_vec: Vec<(String, HashMap<String, usize>)> =
+    combined_hsm_node_hw_component_count_vec
+      .iter()
+      .filter(|(xname, _)| !constraints.exclude_xname_vec.contains(xname))
+      .cloned()
+      .collect();
+
+  // Candidates per cabinet or chassis. Without affinity all candidates are in the same
+  // domain
+  let mut domain_candidate_map: BTreeMap<
+    String,
+    Vec<(String, HashMap<String, usize>)>,
+  > = BTreeMap::new();
+
+  for candidate in candidate_vec {
+    let domain_opt = match constraints.affinity {
+      Some(topology_level) => topology_level.get_domain(&candidate.0),
+      None => Some(String::new()),
+    };
+
+    if let Some(domain) = domain_opt {
+      domain_candidate_map
+        .entry(domain)
+        .or_default()
+        .push(candidate);
+    } else {
+      log::warn!(
+        "Node '{}' location unknown, it can't be picked with affinity constraints",
+        candidate.0
+      );
+    }
+  }
+
+  // Keep the domain moving the fewer nodes
+  let mut best_resolution_opt: Option<(
+    usize,
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, f32)>,
+    HashMap<String, f32>,
+  )> = None;
+  let mut error_opt = None;
+
+  for (domain, domain_candidate_vec) in domain_candidate_map {
+    match calculate_target_hsm_constrained(
+      domain_candidate_vec,
+      &target_xname_set,
+      &user_defined_target_hsm_hw_component_count_hashmap,
+      constraints,
+      unpin,
+    )
+    .await
+    {
+      Ok((selected_vec, selected_node_score_vec, scarcity_scores_hashmap)) => {
+        let selected_xname_set: HashSet<&String> =
+          selected_vec.iter().map(|(xname, _)| xname).collect();
+
+        let num_nodes_moved = selected_xname_set
+          .iter()
+          .filter(|xname| !target_xname_set.contains(**xname))
+          .count()
+          + target_xname_set
+            .iter()
+            .filter(|xname| !selected_xname_set.contains(xname))
+            .count();
+
+        log::info!(
+          "Domain '{}' fulfills the request moving {} nodes",
+          domain,
+          num_nodes_moved
+        );
+
+        if best_resolution_opt.as_ref().is_none_or(
+          |(best_num_nodes_moved, ..)| num_nodes_moved < *best_num_nodes_moved,
+        ) {
+          best_resolution_opt = Some((
+            num_nodes_moved,
+            selected_vec,
+            selected_node_score_vec,
+            scarcity_scores_hashmap,
+          ));
+        }
+      }
+      Err(e) => {
+        log::info!(
+          "Domain '{}' can't fulfill the request. Reason:\n{}",
+          domain,
+          e
+        );
+        error_opt = Some(e);
+      }
+    }
+  }
+
+  let (_, selected_vec, selected_node_score_vec, scarcity_scores_hashmap) =
+    best_resolution_opt.ok_or_else(|| match constraints.affinity {
+      Some(topology_level) => Error::Message(format!(
+        "No {} has enough hw components to fulfill the request",
+        topology_level
+      )),
+      None => error_opt.unwrap_or_else(|| {
+        Error::Message("No nodes left to pick from".to_string())
+      }),
+    })?;
+
+  // Nodes not picked, including the ones excluded, go to the parent HSM group
+  let parent_hsm_node_hw_component_count_vec =
+    combined_hsm_node_hw_component_count_vec
+      .into_iter()
+      .filter(|(xname, _)| {
+        !selected_vec.iter().any(|(selected, _)| selected.eq(xname))
+      })
+      .collect();
+
+  Ok((
+    selected_vec,
+    parent_hsm_node_hw_component_count_vec,
+    selected_node_score_vec,
+    scarcity_scores_hashmap,
+  ))
+}
+
+/// Picks nodes from the candidates until the hw components requested are fulfilled. Each
+/// iteration picks the node with the highest score among the ones providing hw components
+/// still needed, after applying the preferences of the constraints:
+///  - nodes in the target HSM group first (pin or 'prefer_target')
+///  - nodes in the chassis with fewer nodes picked so far ('spread_chassis')
+/// Returns the nodes picked, their score when picked and the hw component scarcity scores
+pub async fn calculate_target_hsm_constrained(
+  candidate_vec: Vec<(String, HashMap<String, usize>)>,
+  target_xname_set: &HashSet<String>,
+  user_defined_target_hsm_hw_component_count_hashmap: &HashMap<String, usize>,
+  constraints: &AllocationConstraints,
+  unpin: bool,
+) -> Result<
+  (
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, f32)>,
+    HashMap<String, f32>,
+  ),
+  Error,
+> {
+  let candidate_hw_component_summary_hashmap =
+    calculate_hsm_hw_component_summary(&candidate_vec);
+
+  // Hw components left once the request is fulfilled
+  let mut final_hw_component_summary_hashmap: HashMap<String, usize> =
+    HashMap::new();
+
+  for (hw_component, qty) in user_defined_target_hsm_hw_component_count_hashmap
+  {
+    let available_qty = candidate_hw_component_summary_hashmap
+      .get(hw_component)
+      .copied()
+      .unwrap_or(0);
+
+    if available_qty < *qty {
+      return Err(Error::Message(format!(
+        "Not enough '{}'. Requested {}, available {}",
+        hw_component, qty, available_qty
+      )));
+    }
+
+    final_hw_component_summary_hashmap
+      .insert(hw_component.clone(), available_qty - qty);
+  }
+
+  let hw_component_scarcity_scores_hashmap =
+    calculate_hw_component_scarcity_scores(&candidate_vec).await;
+
+  let prefer_target = constraints.prefer_target || !unpin;
+
+  let get_chassis = |xname: &str| {
+    TopologyLevel::Chassis
+      .get_domain(xname)
+      .unwrap_or_else(|| xname.to_string())
+  };
+
+  let mut remaining_vec = candidate_vec;
+  let mut selected_vec = Vec::new();
+  let mut selected_node_score_vec = Vec::new();
+  let mut chassis_num_selected_hashmap: HashMap<String, usize> = HashMap::new();
+
+  loop {
+    let remaining_hw_component_summary_hashmap =
+      calculate_hsm_hw_component_summary(&remaining_vec);
+
+    if !keep_iterating_final_hsm(
+      &final_hw_component_summary_hashmap,
+      &remaining_hw_component_summary_hashmap,
+    ) {
+      break;
+    }
+
+    let node_score_vec = calculate_hsm_node_scores_from_final_hsm(
+      &remaining_vec,
+      &remaining_hw_component_summary_hashmap,
+      &final_hw_component_summary_hashmap,
+      &hw_component_scarcity_scores_hashmap,
+    );
+
+    // Once the nodes picked span the number of chassis requested, the remaining nodes are
+    // picked from the same chassis
+    let spread_reached = constraints
+      .spread_chassis
+      .is_some_and(|num| chassis_num_selected_hashmap.len() >= num);
+
+    let get_preference = |xname: &str| {
+      let num_selected_in_chassis = if constraints.spread_chassis.is_some() {
+        chassis_num_selected_hashmap
+          .get(&get_chassis(xname))
+          .copied()
+          .unwrap_or(0)
+      } else {
+        0
+      };
+
+      (
+        prefer_target && target_xname_set.contains(xname),
+        Reverse(num_selected_in_chassis),
+      )
+    };
+
+    let best_candidate_index = remaining_vec
+      .iter()
+      .zip(&node_score_vec)
+      .enumerate()
+      .filter(|(_, ((_, hw_component_count), _))| {
+        // Only nodes providing hw components still needed
+        hw_component_count.iter().any(|(hw_component, qty)| {
+          *qty > 0
+            && final_hw_component_summary_hashmap
+              .get(hw_component)
+              .is_some_and(|final_qty| {
+                remaining_hw_component_summary_hashmap
+                  .get(hw_component)
+                  .is_some_and(|remaining_qty| remaining_qty > final_qty)
+              })
+        })
+      })
+      .filter(|(_, ((xname, _), _))| {
+        !spread_reached
+          || chassis_num_selected_hashmap.contains_key(&get_chassis(xname))
+      })
+      .max_by(
+        |(_, ((xname_a, _), score_a)), (_, ((xname_b, _), score_b))| {
+          get_preference(xname_a)
+            .cmp(&get_preference(xname_b))
+            .then(score_a.1.partial_cmp(&score_b.1).unwrap_or(Ordering::Equal))
+            .then(xname_b.cmp(xname_a))
+        },
+      )
+      .map(|(index, _)| index)
+      .ok_or_else(|| {
+        Error::Message(format!(
+          "Nodes in the {} chassis picked can't fulfill the request",
+          chassis_num_selected_hashmap.len()
+        ))
+      })?;
+
+    let best_candidate = remaining_vec.remove(best_candidate_index);
+
+    log::info!(
+      "Best candidate is '{}' with score {} and hw component counters {:?}",
+      best_candidate.0,
+      node_score_vec[best_candidate_index].1,
+      best_candidate.1
+    );
+
+    *chassis_num_selected_hashmap
+      .entry(get_chassis(&best_candidate.0))
+      .or_default() += 1;
+
+    selected_node_score_vec.push((
+      best_candidate.0.clone(),
+      node_score_vec[best_candidate_index].1,
+    ));
+    selected_vec.push(best_candidate);
+  }
+
+  if let Some(num_chassis) = constraints.spread_chassis {
+    if chassis_num_selected_hashmap.len() < num_chassis {
+      return Err(Error::Message(format!(
+        "Nodes picked span {} chassis, {} requested",
+        chassis_num_selected_hashmap.len(),
+        num_chassis
+      )));
+    }
+  }
+
+  Ok((
+    selected_vec,
+    selected_node_score_vec,
+    hw_component_scarcity_scores_hashmap,
+  ))
+}
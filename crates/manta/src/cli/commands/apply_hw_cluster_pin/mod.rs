@@ -1,4 +1,5 @@
 pub mod command;
+pub mod constraints;
 #[cfg(test)]
 mod tests;
 pub mod utils;
@@ -1,9 +1,30 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::cli::commands::apply_hw_cluster_pin::utils::{
-  calculate_hsm_hw_component_summary, resolve_hw_description_to_xnames,
+use crate::cli::commands::apply_hw_cluster_pin::{
+  constraints::{
+    calculate_target_hsm_constrained,
+    resolve_hw_description_to_xnames_with_constraints, AllocationConstraints,
+    TopologyLevel,
+  },
+  utils::{
+    calculate_hsm_hw_component_summary, resolve_hw_description_to_xnames,
+  },
 };
 
+/// Nodes with 2 epyc processors each
+fn epyc_node_vec(xname_vec: &[&str]) -> Vec<(String, HashMap<String, usize>)> {
+  xname_vec
+    .iter()
+    .map(|xname| (xname.to_string(), HashMap::from([("epyc".to_string(), 2)])))
+    .collect()
+}
+
+fn get_xname_set(
+  node_vec: &[(String, HashMap<String, usize>)],
+) -> HashSet<String> {
+  node_vec.iter().map(|(xname, _)| xname.clone()).collect()
+}
+
 #[tokio::test]
 pub async fn test_hsm_hw_management_1() {
   let user_request_hw_summary = HashMap::from([("epyc".to_string(), 8)]);
@@ -234,7 +255,8 @@ pub async fn test_hsm_hw_management_1() {
       hsm_nodes_free_hw_conters,
       user_request_hw_summary.clone(),
     )
-    .await;
+    .await
+    .unwrap();
 
   println!(
     "DEBUG - target HSM group:\n{:#?}",
@@ -422,7 +444,8 @@ pub async fn test_hsm_hw_management_2() {
       hsm_nodes_free_hw_conters,
       user_request_hw_summary.clone(),
     )
-    .await;
+    .await
+    .unwrap();
 
   println!(
     "DEBUG - target HSM group:\n{:#?}",
@@ -464,3 +487,253 @@ pub async fn test_hsm_hw_management_2() {
 
   assert!(success)
 }
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_chassis_affinity() {
+  let target_vec = epyc_node_vec(&["x1000c0s0b0n0"]);
+  let parent_vec = epyc_node_vec(&[
+    "x1000c0s1b0n0",
+    "x1000c1s0b0n0",
+    "x1000c1s0b0n1",
+    "x1000c1s1b0n0",
+  ]);
+
+  let constraints = AllocationConstraints {
+    affinity: Some(TopologyLevel::Chassis),
+    ..Default::default()
+  };
+
+  // Only chassis 'x1000c1' has 6 epyc
+  let (new_target_vec, new_parent_vec, _, _) =
+    resolve_hw_description_to_xnames_with_constraints(
+      target_vec,
+      parent_vec,
+      HashMap::from([("epyc".to_string(), 6)]),
+      &constraints,
+      false,
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(
+    get_xname_set(&new_target_vec),
+    HashSet::from([
+      "x1000c1s0b0n0".to_string(),
+      "x1000c1s0b0n1".to_string(),
+      "x1000c1s1b0n0".to_string(),
+    ])
+  );
+  assert_eq!(
+    get_xname_set(&new_parent_vec),
+    HashSet::from(["x1000c0s0b0n0".to_string(), "x1000c0s1b0n0".to_string()])
+  );
+}
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_affinity_moves_fewer_nodes() {
+  let target_vec = epyc_node_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1"]);
+  let parent_vec = epyc_node_vec(&[
+    "x1000c0s1b0n0",
+    "x1000c1s0b0n0",
+    "x1000c1s0b0n1",
+    "x1000c1s1b0n0",
+  ]);
+
+  let constraints = AllocationConstraints {
+    affinity: Some(TopologyLevel::Chassis),
+    ..Default::default()
+  };
+
+  let (new_target_vec, _, _, _) =
+    resolve_hw_description_to_xnames_with_constraints(
+      target_vec,
+      parent_vec,
+      HashMap::from([("epyc".to_string(), 6)]),
+      &constraints,
+      false,
+    )
+    .await
+    .unwrap();
+
+  // Chassis 'x1000c0' moves 1 node, chassis 'x1000c1' moves 5 nodes
+  assert_eq!(
+    get_xname_set(&new_target_vec),
+    HashSet::from([
+      "x1000c0s0b0n0".to_string(),
+      "x1000c0s0b0n1".to_string(),
+      "x1000c0s1b0n0".to_string(),
+    ])
+  );
+}
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_affinity_and_spread_not_valid()
+{
+  let constraints = AllocationConstraints {
+    affinity: Some(TopologyLevel::Chassis),
+    spread_chassis: Some(2),
+    ..Default::default()
+  };
+
+  let rslt = resolve_hw_description_to_xnames_with_constraints(
+    epyc_node_vec(&["x1000c0s0b0n0"]),
+    epyc_node_vec(&["x1000c1s0b0n0"]),
+    HashMap::from([("epyc".to_string(), 4)]),
+    &constraints,
+    false,
+  )
+  .await;
+
+  assert!(rslt.is_err());
+}
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_failed_nodes_excluded() {
+  let target_vec = epyc_node_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1"]);
+  let parent_vec = epyc_node_vec(&["x1000c0s1b0n0"]);
+
+  let constraints = AllocationConstraints {
+    exclude_xname_vec: vec!["x1000c0s0b0n0".to_string()],
+    ..Default::default()
+  };
+
+  let (new_target_vec, new_parent_vec, _, _) =
+    resolve_hw_description_to_xnames_with_constraints(
+      target_vec,
+      parent_vec,
+      HashMap::from([("epyc".to_string(), 4)]),
+      &constraints,
+      false,
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(
+    get_xname_set(&new_target_vec),
+    HashSet::from(["x1000c0s0b0n1".to_string(), "x1000c0s1b0n0".to_string()])
+  );
+  assert_eq!(
+    get_xname_set(&new_parent_vec),
+    HashSet::from(["x1000c0s0b0n0".to_string()])
+  );
+}
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_failed_nodes_not_enough() {
+  let constraints = AllocationConstraints {
+    exclude_xname_vec: vec!["x1000c0s0b0n0".to_string()],
+    ..Default::default()
+  };
+
+  let rslt = resolve_hw_description_to_xnames_with_constraints(
+    epyc_node_vec(&["x1000c0s0b0n0"]),
+    epyc_node_vec(&["x1000c0s1b0n0"]),
+    HashMap::from([("epyc".to_string(), 4)]),
+    &constraints,
+    false,
+  )
+  .await;
+
+  assert!(rslt.is_err());
+}
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_spread_chassis() {
+  let candidate_vec = epyc_node_vec(&[
+    "x1000c0s0b0n0",
+    "x1000c0s0b0n1",
+    "x1000c1s0b0n0",
+    "x1000c1s0b0n1",
+  ]);
+
+  let constraints = AllocationConstraints {
+    spread_chassis: Some(2),
+    ..Default::default()
+  };
+
+  let (selected_vec, selected_node_score_vec, _) =
+    calculate_target_hsm_constrained(
+      candidate_vec,
+      &HashSet::new(),
+      &HashMap::from([("epyc".to_string(), 4)]),
+      &constraints,
+      true,
+    )
+    .await
+    .unwrap();
+
+  let chassis_set: HashSet<String> = selected_vec
+    .iter()
+    .filter_map(|(xname, _)| TopologyLevel::Chassis.get_domain(xname))
+    .collect();
+
+  assert_eq!(selected_vec.len(), 2);
+  assert_eq!(selected_node_score_vec.len(), 2);
+  assert_eq!(
+    chassis_set,
+    HashSet::from(["x1000c0".to_string(), "x1000c1".to_string()])
+  );
+}
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_spread_chassis_not_reached() {
+  let constraints = AllocationConstraints {
+    spread_chassis: Some(2),
+    ..Default::default()
+  };
+
+  let rslt = calculate_target_hsm_constrained(
+    epyc_node_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1"]),
+    &HashSet::new(),
+    &HashMap::from([("epyc".to_string(), 4)]),
+    &constraints,
+    true,
+  )
+  .await;
+
+  assert!(rslt.is_err());
+}
+
+#[tokio::test]
+pub async fn test_hsm_hw_management_constrained_prefer_target() {
+  let candidate_vec = epyc_node_vec(&["x1000c0s0b0n0", "x1000c1s0b0n0"]);
+  let target_xname_set = HashSet::from(["x1000c1s0b0n0".to_string()]);
+  let user_request_hw_summary = HashMap::from([("epyc".to_string(), 2)]);
+
+  // Same score, the first xname is picked
+  let (selected_vec, _, _) = calculate_target_hsm_constrained(
+    candidate_vec.clone(),
+    &target_xname_set,
+    &user_request_hw_summary,
+    &AllocationConstraints::default(),
+    true,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(
+    get_xname_set(&selected_vec),
+    HashSet::from(["x1000c0s0b0n0".to_string()])
+  );
+
+  // Nodes in the target HSM group are picked first
+  let constraints = AllocationConstraints {
+    prefer_target: true,
+    ..Default::default()
+  };
+
+  let (selected_vec, _, _) = calculate_target_hsm_constrained(
+    candidate_vec,
+    &target_xname_set,
+    &user_request_hw_summary,
+    &constraints,
+    true,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(
+    get_xname_set(&selected_vec),
+    HashSet::from(["x1000c1s0b0n0".to_string()])
+  );
+}
@@ -5,11 +5,45 @@ use manta_backend_dispatcher::interfaces::hsm::hardware_inventory::HardwareInven
 use serde_json::Value;
 use tokio::sync::Semaphore;
 
+use manta_backend_dispatcher::error::Error;
+
 use crate::{common, manta_backend_dispatcher::StaticBackendDispatcher};
 
 // Returns a tuple (target_hsm, parent_hsm) with 2 list of nodes and its hardware components.
 // The left tuple element are the nodes moved from the
 pub async fn resolve_hw_description_to_xnames(
+  target_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  parent_hsm_node_hw_component_count_vec: Vec<(String, HashMap<String, usize>)>,
+  user_defined_target_hsm_hw_component_count_hashmap: HashMap<String, usize>,
+) -> Result<
+  (
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, HashMap<String, usize>)>,
+  ),
+  Error,
+> {
+  let (
+    target_hsm_node_hw_component_count_vec,
+    parent_hsm_node_hw_component_count_vec,
+    _,
+    _,
+  ) = resolve_hw_description_to_xnames_with_scores(
+    target_hsm_node_hw_component_count_vec,
+    parent_hsm_node_hw_component_count_vec,
+    user_defined_target_hsm_hw_component_count_hashmap,
+  )
+  .await?;
+
+  Ok((
+    target_hsm_node_hw_component_count_vec,
+    parent_hsm_node_hw_component_count_vec,
+  ))
+}
+
+/// Same as `resolve_hw_description_to_xnames` but also returns the nodes moved to the target
+/// HSM group in the order they were picked with the score they had at that moment, and the
+/// scarcity score of each hw component
+pub async fn resolve_hw_description_to_xnames_with_scores(
   mut target_hsm_node_hw_component_count_vec: Vec<(
     String,
     HashMap<String, usize>,
@@ -19,10 +53,15 @@ pub async fn resolve_hw_description_to_xnames(
     HashMap<String, usize>,
   )>,
   user_defined_target_hsm_hw_component_count_hashmap: HashMap<String, usize>,
-) -> (
-  Vec<(String, HashMap<String, usize>)>,
-  Vec<(String, HashMap<String, usize>)>,
-) {
+) -> Result<
+  (
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, HashMap<String, usize>)>,
+    Vec<(String, f32)>,
+    HashMap<String, f32>,
+  ),
+  Error,
+> {
   // *********************************************************************************************************
   // CALCULATE 'COMBINED HSM' WITH TARGET HSM AND PARENT HSM ELEMENTS COMBINED
   // NOTE: PARENT HSM may contain elements in TARGET HSM, we need to only add those xnames
@@ -72,6 +111,8 @@ pub async fn resolve_hw_description_to_xnames(
   }
 
   // Calculate new target HSM group
+  let mut selected_node_score_vec: Vec<(String, f32)> = Vec::new();
+
   let hw_component_counters_to_move_out_from_combined_hsm =
     calculate_target_hsm_pin(
       &final_combined_target_parent_hsm_hw_component_summary.clone(),
@@ -81,15 +122,18 @@ pub async fn resolve_hw_description_to_xnames(
       &mut combined_target_parent_hsm_node_hw_component_count_vec,
       &mut target_hsm_node_hw_component_count_vec,
       &mut parent_hsm_node_hw_component_count_vec,
+      &mut selected_node_score_vec,
       &hw_component_scarcity_scores_hashmap,
-    );
+    )?;
 
   let new_target_hsm_node_hw_component_count_vec =
     hw_component_counters_to_move_out_from_combined_hsm;
-  (
+  Ok((
     new_target_hsm_node_hw_component_count_vec,
     combined_target_parent_hsm_node_hw_component_count_vec,
-  )
+    selected_node_score_vec,
+    hw_component_scarcity_scores_hashmap,
+  ))
 }
 
 /// Pin means this function should be used when the user wants to keep as much nodes in
@@ -153,15 +197,6 @@ pub fn get_best_candidate_in_target_and_parent_hsm_pin(
   } else {
     None
   }
-
-  /* if let Some(target_best_candidate) = target_best_candidate_tuple {
-      target_best_candidate
-  } else if let Some(parent_best_candidate) = parent_best_candidate_tuple {
-      parent_best_candidate
-  } else {
-      eprintln!("ERROR - No best candidate found.");
-      std::process::exit(1);
-  } */
 }
 
 /// Generates a list of tuples with xnames and the hardware summary for each node. This method
@@ -190,10 +225,12 @@ pub fn calculate_target_hsm_pin(
     String,
     HashMap<String, usize>,
   )>,
+  selected_node_score_vec: &mut Vec<(String, f32)>, // nodes moved to target
+  // HSM group in the order they were picked, with the score they had at that moment
   hw_component_scarcity_scores_hashmap: &HashMap<String, f32>, // hw
                                                                // component type score for as much hsm groups related to the stakeholders using these
                                                                // nodes
-) -> Vec<(String, HashMap<String, usize>)> {
+) -> Result<Vec<(String, HashMap<String, usize>)>, Error> {
   ////////////////////////////////
   // Initialize
 
@@ -254,31 +291,6 @@ pub fn calculate_target_hsm_pin(
     HashMap<String, usize>,
   )> = Vec::new();
 
-  /* // Get best candidate in 'target' HSM group
-  let target_best_candidate_tuple =
-      get_best_candidate_in_hsm_based_on_scarcity_node_score_pin(
-          &mut target_hsm_node_score_tuple_vec,
-          target_hsm_node_hw_component_count_vec,
-      );
-
-  // Get best candidate in 'parent' HSM group
-  let parent_best_candidate_tuple =
-      get_best_candidate_in_hsm_based_on_scarcity_node_score_pin(
-          &mut parent_hsm_node_score_tuple_vec,
-          parent_hsm_node_hw_component_count_vec,
-      );
-
-  // If best candidate exists (in 'target' HSM group), then use it. Otherwise, use the one in 'parent' HSM group
-  let (mut best_candidate, mut best_candidate_counters) =
-      if let Some(target_best_candidate) = target_best_candidate_tuple {
-          target_best_candidate
-      } else if let Some(parent_best_candidate) = parent_best_candidate_tuple {
-          parent_best_candidate
-      } else {
-          eprintln!("ERROR - No best candidate found.");
-          std::process::exit(1);
-      }; */
-
   let (mut best_candidate, mut best_candidate_counters) =
     get_best_candidate_in_target_and_parent_hsm_pin(
       &mut target_hsm_node_score_tuple_vec,
@@ -286,10 +298,7 @@ pub fn calculate_target_hsm_pin(
       target_hsm_node_hw_component_count_vec,
       parent_hsm_node_hw_component_count_vec,
     )
-    .unwrap_or_else(|| {
-      eprintln!("ERROR - No best candidate found.");
-      std::process::exit(1);
-    });
+    .ok_or_else(no_best_candidate_error)?;
 
   // Check if we need to keep iterating
   let mut work_to_do = keep_iterating_final_hsm(
@@ -341,6 +350,8 @@ pub fn calculate_target_hsm_pin(
     nodes_migrated_from_combination_target_parent_hsm
       .push((best_candidate.0.clone(), best_candidate_counters.clone()));
 
+    selected_node_score_vec.push(best_candidate.clone());
+
     // Remove best candidate from combined HSM group
     combination_target_parent_hsm_node_hw_component_count_vec
       .retain(|(node, _)| !node.eq(&best_candidate.0));
@@ -413,31 +424,6 @@ pub fn calculate_target_hsm_pin(
         .or_insert(vec![node.clone()]);
     }
 
-    /* // Get best candidate in 'target' HSM group
-    let target_best_candidate_tuple =
-        get_best_candidate_in_hsm_based_on_scarcity_node_score_pin(
-            &mut target_hsm_node_score_tuple_vec,
-            target_hsm_node_hw_component_count_vec,
-        );
-
-    // Get best candidate in 'parent' HSM group
-    let parent_best_candidate_tuple =
-        get_best_candidate_in_hsm_based_on_scarcity_node_score_pin(
-            &mut parent_hsm_node_score_tuple_vec,
-            parent_hsm_node_hw_component_count_vec,
-        );
-
-    // If best candidate exists (in 'target' HSM group), then use it. Otherwise, use the one in 'parent' HSM group
-    (best_candidate, best_candidate_counters) =
-        if let Some(target_best_candidate) = target_best_candidate_tuple {
-            target_best_candidate
-        } else if let Some(parent_best_candidate) = parent_best_candidate_tuple {
-            parent_best_candidate
-        } else {
-            eprintln!("ERROR - No best candidate found.");
-            std::process::exit(1);
-        }; */
-
     (best_candidate, best_candidate_counters) =
       get_best_candidate_in_target_and_parent_hsm_pin(
         &mut target_hsm_node_score_tuple_vec,
@@ -445,7 +431,7 @@ pub fn calculate_target_hsm_pin(
         target_hsm_node_hw_component_count_vec,
         parent_hsm_node_hw_component_count_vec,
       )
-      .expect("ERROR - No best candidate found.");
+      .ok_or_else(no_best_candidate_error)?;
 
     // Check if we need to keep iterating
     work_to_do = keep_iterating_final_hsm(
@@ -476,7 +462,14 @@ pub fn calculate_target_hsm_pin(
     &parent_hsm_node_score_tuple_vec,
   );
 
-  nodes_migrated_from_combination_target_parent_hsm
+  Ok(nodes_migrated_from_combination_target_parent_hsm)
+}
+
+fn no_best_candidate_error() -> Error {
+  Error::Message(
+    "No node left in the target or parent HSM groups to meet the hardware requested"
+      .to_string(),
+  )
 }
 
 pub async fn calculate_hw_component_scarcity_scores(
@@ -844,27 +837,11 @@ pub async fn get_hsm_node_hw_component_counter(
   }
 
   while let Some(message) = tasks.join_next().await {
-    if let Ok(mut node_hw_component_vec_tuple) = message {
-      node_hw_component_vec_tuple.1.sort();
-
-      let mut node_hw_component_count_hashmap: HashMap<String, usize> =
-        HashMap::new();
-
-      for node_hw_property_vec in node_hw_component_vec_tuple.1 {
-        let count = node_hw_component_count_hashmap
-          .entry(node_hw_property_vec)
-          .or_insert(0);
-        *count += 1;
-      }
-
-      let node_memory_total_capacity: u64 =
-        node_hw_component_vec_tuple.2.iter().sum();
-
-      node_hw_component_count_hashmap.insert(
-        "memory".to_string(),
-        (node_memory_total_capacity / mem_lcm)
-          .try_into()
-          .unwrap_or(0),
+    if let Ok(node_hw_component_vec_tuple) = message {
+      let node_hw_component_count_hashmap = calculate_node_hw_component_count(
+        node_hw_component_vec_tuple.1,
+        &node_hw_component_vec_tuple.2,
+        mem_lcm,
       );
 
       target_hsm_node_hw_component_count_vec.push((
@@ -881,3 +858,34 @@ pub async fn get_hsm_node_hw_component_counter(
 
   target_hsm_node_hw_component_count_vec
 }
+
+/// Counts the hw components of a node. Memory capacity is added as 'memory' and normalized
+/// with `mem_lcm`
+pub fn calculate_node_hw_component_count(
+  mut node_hw_component_vec: Vec<String>,
+  node_memory_vec: &[u64],
+  mem_lcm: u64,
+) -> HashMap<String, usize> {
+  node_hw_component_vec.sort();
+
+  let mut node_hw_component_count_hashmap: HashMap<String, usize> =
+    HashMap::new();
+
+  for node_hw_property_vec in node_hw_component_vec {
+    let count = node_hw_component_count_hashmap
+      .entry(node_hw_property_vec)
+      .or_insert(0);
+    *count += 1;
+  }
+
+  let node_memory_total_capacity: u64 = node_memory_vec.iter().sum();
+
+  node_hw_component_count_hashmap.insert(
+    "memory".to_string(),
+    (node_memory_total_capacity / mem_lcm)
+      .try_into()
+      .unwrap_or(0),
+  );
+
+  node_hw_component_count_hashmap
+}
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
-use crate::cli::commands::apply_hw_cluster_unpin::utils::{
-  calculate_hsm_hw_component_summary, resolve_hw_description_to_xnames,
+use csm_rs::commands::apply_hw_cluster_unpin::utils::{
+  calculate_target_hsm_unpin, get_best_candidate_in_hsm_unpin,
+  resolve_hw_description_to_xnames,
 };
 
+use crate::cli::commands::apply_hw_cluster_unpin::utils::calculate_hsm_hw_component_summary;
+
 #[tokio::test]
 pub async fn test_hsm_hw_management_1() {
   let user_request_hw_summary = HashMap::from([("epyc".to_string(), 8)]);
@@ -234,7 +237,7 @@ pub async fn test_hsm_hw_management_1() {
       hsm_nodes_free_hw_conters,
       user_request_hw_summary.clone(),
     )
-    .await;
+    .unwrap();
 
   println!(
     "DEBUG - target HSM group:\n{:#?}",
@@ -421,7 +424,7 @@ pub async fn test_hsm_hw_management_2() {
       hsm_nodes_free_hw_conters,
       user_request_hw_summary.clone(),
     )
-    .await;
+    .unwrap();
 
   println!(
     "DEBUG - target HSM group:\n{:#?}",
@@ -451,3 +454,44 @@ pub async fn test_hsm_hw_management_2() {
 
   assert!(success)
 }
+
+#[test]
+fn test_get_best_candidate_in_hsm_unpin_with_nan_score() {
+  let mut hsm_score_vec = vec![
+    ("x1000c0s0b0n0".to_string(), f32::NAN),
+    ("x1000c0s0b0n1".to_string(), 2.0),
+    ("x1000c0s0b1n0".to_string(), 5.0),
+  ];
+
+  let hsm_hw_component_vec: Vec<(String, HashMap<String, usize>)> =
+    hsm_score_vec
+      .iter()
+      .map(|(xname, _)| {
+        (xname.clone(), HashMap::from([("epyc".to_string(), 2)]))
+      })
+      .collect();
+
+  let ((best_candidate, _), _) =
+    get_best_candidate_in_hsm_unpin(&mut hsm_score_vec, &hsm_hw_component_vec)
+      .unwrap();
+
+  // NaN sorts above every other score with 'total_cmp' instead of panicking
+  assert_eq!(best_candidate, "x1000c0s0b0n0");
+}
+
+#[test]
+fn test_get_best_candidate_in_hsm_unpin_empty() {
+  assert!(get_best_candidate_in_hsm_unpin(&mut [], &[]).is_none());
+}
+
+#[test]
+fn test_calculate_target_hsm_unpin_without_nodes_is_an_error() {
+  let rslt = calculate_target_hsm_unpin(
+    &HashMap::from([("epyc".to_string(), 2)]),
+    &mut Vec::new(),
+    &mut Vec::new(),
+    &HashMap::new(),
+  );
+
+  assert!(rslt.is_err());
+}
@@ -0,0 +1,210 @@
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use manta_backend_dispatcher::{
+  audit::{AuditOutcome, Auditor},
+  interfaces::{hsm::group::GroupTrait, pcs::PCSTrait},
+  types::pcs::power_cap::types::PowerCapComponent,
+};
+
+use crate::{
+  common::{
+    audit,
+    output::{self, OutputFormat},
+  },
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Sets the power cap control `control_name` of a list of nodes to `watts`. The nodes are
+/// validated against the current power cap snapshot before changing anything
+pub async fn exec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  xname_vec: &[String],
+  control_name: &str,
+  watts: usize,
+  assume_yes: bool,
+  dry_run: bool,
+  output: OutputFormat,
+  auditor_opt: Option<&Auditor>,
+) {
+  if xname_vec.is_empty() {
+    eprintln!("The list of nodes to operate is empty. Nothing to do. Exit");
+    std::process::exit(0);
+  }
+
+  // Validate the new value against the range each node accepts
+  let power_cap_snapshot = backend
+    .power_cap_snapshot(shasta_token, xname_vec)
+    .await
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not get power cap. Reason:\n{e}\nExit");
+      std::process::exit(1);
+    });
+
+  let error_vec = get_error_vec(
+    xname_vec,
+    &power_cap_snapshot.components.unwrap_or_default(),
+    control_name,
+    watts,
+  );
+
+  if !error_vec.is_empty() {
+    eprintln!(
+      "ERROR - Can't set power cap '{}' to {}W:\n{}\nExit",
+      control_name,
+      watts,
+      error_vec.join("\n")
+    );
+    std::process::exit(1);
+  }
+
+  if dry_run {
+    println!(
+      "Dry-run enabled. Power cap '{}' of nodes {:?} would be set to {}W",
+      control_name, xname_vec, watts
+    );
+    return;
+  }
+
+  if !assume_yes {
+    if Confirm::with_theme(&ColorfulTheme::default())
+      .with_prompt(format!(
+        "{:?}\nPower cap '{}' of the nodes above will be set to {}W. Please confirm to proceed?",
+        xname_vec.join(", "),
+        control_name,
+        watts
+      ))
+      .interact()
+      .unwrap()
+    {
+      log::info!("Continue",);
+    } else {
+      println!("Cancelled by user. Aborting.");
+      std::process::exit(0);
+    }
+  }
+
+  let power_cap_patch_rslt = backend
+    .power_cap_patch(shasta_token, xname_vec, control_name, watts)
+    .await;
+
+  // Audit
+  if let Some(auditor) = auditor_opt {
+    let group_map = backend
+      .get_group_map_and_filter_by_member_vec(
+        shasta_token,
+        &xname_vec
+          .iter()
+          .map(|member| member.as_str())
+          .collect::<Vec<_>>(),
+      )
+      .await
+      .unwrap_or_default();
+
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "apply power-cap")
+      .with_xnames(xname_vec)
+      .with_groups(&group_map.keys().collect::<Vec<_>>())
+      .with_message(&format!("{}={}W", control_name, watts));
+
+    let event = match &power_cap_patch_rslt {
+      Ok(_) => event,
+      Err(_) => event.with_outcome(AuditOutcome::Failure),
+    };
+
+    auditor.send(&event).await;
+  }
+
+  let power_cap_patch = power_cap_patch_rslt.unwrap_or_else(|e| {
+    eprintln!(
+      "ERROR - Could not set power cap of node/s '{:?}'. Reason:\n{e}",
+      xname_vec
+    );
+    std::process::exit(1);
+  });
+
+  // The patch task only reports errors, show the values the nodes have now
+  let power_cap_snapshot = backend
+    .power_cap_snapshot(shasta_token, xname_vec)
+    .await
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not get power cap. Reason:\n{e}\nExit");
+      std::process::exit(1);
+    });
+
+  let mut component_vec = power_cap_snapshot.components.unwrap_or_default();
+
+  // Keep the errors reported by the patch task
+  for patched_component in power_cap_patch.components.unwrap_or_default() {
+    if let Some(component) = component_vec
+      .iter_mut()
+      .find(|component| component.xname == patched_component.xname)
+    {
+      component.error = patched_component.error.or(component.error.take());
+    }
+  }
+
+  component_vec.sort_by(|a, b| a.xname.cmp(&b.xname));
+
+  if let Err(e) = output::print(&component_vec, output) {
+    eprintln!("ERROR - Could not print power cap. Reason:\n{e}");
+    std::process::exit(1);
+  }
+}
+
+/// Returns why `watts` can't be set to the control `control_name` of each node
+/// in `xname_vec`, nodes missing from the power cap snapshot can't be set either
+pub fn get_error_vec(
+  xname_vec: &[String],
+  component_vec: &[PowerCapComponent],
+  control_name: &str,
+  watts: usize,
+) -> Vec<String> {
+  xname_vec
+    .iter()
+    .filter_map(|xname| {
+      match component_vec
+        .iter()
+        .find(|component| component.xname.as_ref() == Some(xname))
+      {
+        Some(component) => validate(component, control_name, watts),
+        None => Some(format!(" - {}: not in power cap snapshot", xname)),
+      }
+    })
+    .collect()
+}
+
+/// Returns why `watts` can't be set to the control `control_name` of a node, if so
+fn validate(
+  component: &PowerCapComponent,
+  control_name: &str,
+  watts: usize,
+) -> Option<String> {
+  let xname = component.xname.clone().unwrap_or_default();
+
+  if let Some(error) = &component.error {
+    return Some(format!(" - {}: {}", xname, error));
+  }
+
+  let Some(limit) = component
+    .power_cap_limits
+    .as_deref()
+    .unwrap_or_default()
+    .iter()
+    .find(|limit| limit.name.as_deref() == Some(control_name))
+  else {
+    return Some(format!(" - {}: no control '{}'", xname, control_name));
+  };
+
+  match (limit.minimum_value, limit.maximum_value) {
+    (Some(min), _) if watts < min => {
+      Some(format!(" - {}: minimum is {}W", xname, min))
+    }
+    (_, Some(max)) if watts > max => {
+      Some(format!(" - {}: maximum is {}W", xname, max))
+    }
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests;
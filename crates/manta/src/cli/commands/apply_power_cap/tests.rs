@@ -0,0 +1,114 @@
+use manta_backend_dispatcher::types::pcs::power_cap::types::{
+  PowerCapComponent, PowerCapLimit,
+};
+
+use super::get_error_vec;
+
+const CONTROL_NAME: &str = "Node Power Limit";
+
+/// Node accepting between 350W and 925W on the control 'Node Power Limit'
+fn component(xname: &str) -> PowerCapComponent {
+  PowerCapComponent {
+    xname: Some(xname.to_string()),
+    error: None,
+    limits: None,
+    power_cap_limits: Some(vec![PowerCapLimit {
+      name: Some(CONTROL_NAME.to_string()),
+      current_value: Some(500),
+      maximum_value: Some(925),
+      minimum_value: Some(350),
+    }]),
+  }
+}
+
+#[test]
+fn test_get_error_vec() {
+  let mut failed_component = component("x1000c0s0b1n0");
+  failed_component.error = Some("Unable to reach node".to_string());
+
+  let mut no_limit_component = component("x1000c0s0b1n1");
+  no_limit_component.power_cap_limits = None;
+
+  let component_vec = vec![
+    component("x1000c0s0b0n0"),
+    component("x1000c0s0b0n1"),
+    failed_component,
+    no_limit_component,
+  ];
+
+  // (nodes, control, watts, expected errors)
+  let case_vec: Vec<(&[&str], &str, usize, Vec<&str>)> = vec![
+    (
+      &["x1000c0s0b0n0", "x1000c0s0b0n1"],
+      CONTROL_NAME,
+      500,
+      vec![],
+    ),
+    (&["x1000c0s0b0n0"], CONTROL_NAME, 350, vec![]),
+    (&["x1000c0s0b0n0"], CONTROL_NAME, 925, vec![]),
+    (
+      &["x1000c0s0b0n0", "x1000c0s0b0n1"],
+      CONTROL_NAME,
+      349,
+      vec![
+        " - x1000c0s0b0n0: minimum is 350W",
+        " - x1000c0s0b0n1: minimum is 350W",
+      ],
+    ),
+    (
+      &["x1000c0s0b0n0"],
+      CONTROL_NAME,
+      926,
+      vec![" - x1000c0s0b0n0: maximum is 925W"],
+    ),
+    (
+      &["x1000c0s0b0n0"],
+      "Accelerator0 Power Limit",
+      500,
+      vec![" - x1000c0s0b0n0: no control 'Accelerator0 Power Limit'"],
+    ),
+    (
+      &["x1000c0s0b1n1"],
+      CONTROL_NAME,
+      500,
+      vec![" - x1000c0s0b1n1: no control 'Node Power Limit'"],
+    ),
+    (
+      &["x1000c0s0b1n0"],
+      CONTROL_NAME,
+      500,
+      vec![" - x1000c0s0b1n0: Unable to reach node"],
+    ),
+    (
+      &["x1000c0s1b0n0"],
+      CONTROL_NAME,
+      500,
+      vec![" - x1000c0s1b0n0: not in power cap snapshot"],
+    ),
+    (&[], CONTROL_NAME, 500, vec![]),
+    (&[], "Accelerator0 Power Limit", 0, vec![]),
+  ];
+
+  for (xname_vec, control_name, watts, expected_error_vec) in case_vec {
+    let xname_vec: Vec<String> =
+      xname_vec.iter().map(|xname| xname.to_string()).collect();
+
+    assert_eq!(
+      get_error_vec(&xname_vec, &component_vec, control_name, watts),
+      expected_error_vec,
+      "nodes {:?}, control '{}', {}W",
+      xname_vec,
+      control_name,
+      watts
+    );
+  }
+}
+
+#[test]
+fn test_get_error_vec_empty_snapshot() {
+  assert!(get_error_vec(&[], &[], CONTROL_NAME, 500).is_empty());
+  assert_eq!(
+    get_error_vec(&["x1000c0s0b0n0".to_string()], &[], CONTROL_NAME, 500),
+    vec![" - x1000c0s0b0n0: not in power cap snapshot"]
+  );
+}
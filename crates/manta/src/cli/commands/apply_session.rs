@@ -2,13 +2,14 @@ use std::path::PathBuf;
 
 use futures::{AsyncBufReadExt, TryStreamExt};
 use manta_backend_dispatcher::{
+  audit::{AuditOutcome, Auditor},
   error::Error,
   interfaces::{apply_session::ApplySessionTrait, cfs::CfsTrait},
   types::K8sDetails,
 };
 
 use crate::{
-  common::{audit::Audit, jwt_ops, kafka::Kafka},
+  common::audit,
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -30,10 +31,10 @@ pub async fn exec(
   ansible_verbosity: Option<String>,
   ansible_passthrough: Option<String>,
   watch_logs: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
   k8s: &K8sDetails,
 ) -> Result<(String, String), Error> {
-  let (cfs_configuration_name, cfs_session_name) = backend
+  let apply_session_rslt = backend
     .i_apply_session(
       gitea_token,
       gitea_base_url,
@@ -52,7 +53,24 @@ pub async fn exec(
       /* kafka_audit,
       k8s, */
     )
-    .await?;
+    .await;
+
+  // Audit
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "apply session")
+      .with_xnames(&ansible_limit.iter().collect::<Vec<_>>())
+      .with_groups(&hsm_group_opt.iter().collect::<Vec<_>>());
+
+    let event = match &apply_session_rslt {
+      Ok(_) => event,
+      Err(_) => event.with_outcome(AuditOutcome::Failure),
+    };
+
+    auditor.send(&event).await;
+  }
+
+  let (cfs_configuration_name, cfs_session_name) = apply_session_rslt?;
 
   // FIXME: refactor becase this code is duplicated in command `manta apply sat-file` and also in
   // `manta logs`
@@ -69,21 +87,5 @@ pub async fn exec(
     }
   }
 
-  // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
-
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": ansible_limit}, "group": vec![hsm_group_opt], "message": "Apply session"});
-
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
-
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
-  }
-
   Ok((cfs_configuration_name, cfs_session_name))
 }
@@ -0,0 +1,16 @@
+use manta_backend_dispatcher::error::Error;
+
+use crate::common::cache;
+
+/// Deletes the cached responses of a site or all sites
+pub fn exec(site_name: &str, all: bool) -> Result<(), Error> {
+  if all {
+    cache::clear(None)?;
+    println!("Response cache cleared for all sites");
+  } else {
+    cache::clear(Some(site_name))?;
+    println!("Response cache cleared for site '{}'", site_name);
+  }
+
+  Ok(())
+}
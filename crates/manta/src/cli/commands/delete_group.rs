@@ -1,9 +1,11 @@
 use crate::{
-  common::{audit::Audit, jwt_ops, kafka::Kafka},
+  common::audit,
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 use manta_backend_dispatcher::{
-  error::Error, interfaces::hsm::group::GroupTrait,
+  audit::{AuditOutcome, Auditor},
+  error::Error,
+  interfaces::hsm::group::GroupTrait,
 };
 
 pub async fn exec(
@@ -11,7 +13,7 @@ pub async fn exec(
   auth_token: &str,
   label: &str,
   force: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   if !force {
     // Validate if group can be deleted
@@ -21,6 +23,21 @@ pub async fn exec(
   // Delete group
   let result = backend.delete_group(auth_token, label).await;
 
+  // Audit
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(auth_token), "delete group")
+      .with_message(&format!("Delete Group '{}'", label))
+      .with_groups(&[label]);
+
+    let event = match &result {
+      Ok(_) => event,
+      Err(_) => event.with_outcome(AuditOutcome::Failure),
+    };
+
+    auditor.send(&event).await;
+  }
+
   match result {
     Ok(_) => {
       eprintln!("Group '{}' deleted", label);
@@ -30,23 +47,6 @@ pub async fn exec(
       std::process::exit(1);
     }
   }
-
-  // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(auth_token).unwrap_or_default();
-    let user_id =
-      jwt_ops::get_preferred_username(auth_token).unwrap_or_default();
-
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "group": label, "message": format!("Delete Group '{}'", label)});
-
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
-
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
-  }
 }
 
 // Checks if a group can be deleted.
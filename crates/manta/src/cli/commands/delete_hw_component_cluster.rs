@@ -261,14 +261,16 @@ pub async fn exec(
 
   // Downscale parent HSM group
   let hw_component_counters_to_move_out_from_target_hsm =
-        crate::cli::commands::apply_hw_cluster_unpin::utils::calculate_target_hsm_unpin(
-            &final_target_hsm_hw_component_summary.clone(),
-            &final_target_hsm_hw_component_summary
-                .into_keys()
-                .collect::<Vec<String>>(),
+        csm_rs::commands::apply_hw_cluster_unpin::utils::calculate_target_hsm_unpin(
+            &final_target_hsm_hw_component_summary,
             &mut target_hsm_node_hw_component_count_vec,
+            &mut Vec::new(),
             &combined_target_parent_hsm_hw_component_type_scores_based_on_scarcity_hashmap,
-        );
+        )
+        .unwrap_or_else(|e| {
+          eprintln!("ERROR - {}", e);
+          std::process::exit(1);
+        });
 
   // *********************************************************************************************************
   // PREPARE INFORMATION TO SHOW
@@ -1,5 +1,6 @@
 use dialoguer::theme::ColorfulTheme;
 use manta_backend_dispatcher::{
+  audit::Auditor,
   error::Error,
   interfaces::{
     bss::BootParametersTrait,
@@ -9,7 +10,7 @@ use manta_backend_dispatcher::{
 };
 
 use crate::{
-  common::{self, audit::Audit, jwt_ops, kafka::Kafka},
+  common::{self, audit},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -22,7 +23,7 @@ pub async fn exec(
   hosts_expression: &str,
   assume_yes: bool,
   do_not_reboot: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) -> Result<(), Error> {
   let mut need_restart = false;
   println!("Delete kernel parameters");
@@ -78,6 +79,9 @@ pub async fn exec(
     current_node_boot_params_vec
   );
 
+  // Nodes whose boot parameters could not be updated
+  let mut failed_xname_vec: Vec<String> = Vec::new();
+
   for mut boot_parameter in current_node_boot_params_vec {
     log::info!(
       "Deleting '{}' kernel parameters for nodes '{:?}'",
@@ -98,7 +102,8 @@ pub async fn exec(
 
       if let Err(e) = boot_parametes_rslt {
         eprintln!("{:#?}", e);
-        std::process::exit(1);
+        failed_xname_vec = boot_parameter.hosts;
+        break;
       }
 
       if need_restart {
@@ -111,10 +116,7 @@ pub async fn exec(
   }
 
   // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
-
+  if let Some(auditor) = auditor_opt {
     // FIXME: We should not need to make this call here but at the beginning of the method as a
     // prerequisite
     let xnames: Vec<&str> =
@@ -125,15 +127,19 @@ pub async fn exec(
       .await
       .map_err(|e| Error::Message(e.to_string()))?;
 
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": xname_vec}, "group": group_map_vec.keys().collect::<Vec<&String>>(), "message": format!("Delete kernel parameters: {}", kernel_params)});
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "delete kernel parameters")
+      .with_message(&format!("Delete kernel parameters: {}", kernel_params))
+      .with_xnames(&xname_vec)
+      .with_groups(&group_map_vec.keys().collect::<Vec<_>>());
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    let event = audit::with_failed_xnames(event, &failed_xname_vec);
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+    auditor.send(&event).await;
+  }
+
+  if !failed_xname_vec.is_empty() {
+    std::process::exit(1);
   }
 
   // Reboot if needed
@@ -150,8 +156,10 @@ pub async fn exec(
         &xname_to_reboot_vec.join(","),
         true,
         assume_yes,
+        false,
+        0,
         "table",
-        kafka_audit_opt,
+        auditor_opt,
       )
       .await;
     }
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use comfy_table::{Cell, Color, Table};
+use manta_backend_dispatcher::{
+  error::Error,
+  types::{ArtifactSummary, NodeSummary},
+};
+use serde::Serialize;
+
+use crate::common::hw_inventory_snapshot::HwInventorySnapshot;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FruChangeType {
+  Added,
+  Removed,
+  Replaced,
+}
+
+/// Change of the FRU in a location of a node between two hardware snapshots
+#[derive(Serialize, Debug)]
+pub struct FruChange {
+  pub node: String,
+  pub location: String,
+  pub r#type: String,
+  pub change: FruChangeType,
+  pub info_before: Option<String>,
+  pub info_after: Option<String>,
+  pub serial_number_before: Option<String>,
+  pub serial_number_after: Option<String>,
+}
+
+/// Prints the FRUs added, removed or replaced in each node between two hardware snapshots
+pub fn exec(
+  snapshot_before: &HwInventorySnapshot,
+  snapshot_after: &HwInventorySnapshot,
+  output: &str,
+) -> Result<(), Error> {
+  if snapshot_before.site != snapshot_after.site {
+    return Err(Error::Message(format!(
+      "Hardware snapshots belong to different sites, '{}' and '{}'",
+      snapshot_before.site, snapshot_after.site
+    )));
+  }
+
+  let fru_change_vec = diff(
+    &snapshot_before.get_node_summary_vec(),
+    &snapshot_after.get_node_summary_vec(),
+  );
+
+  if output == "json" {
+    println!("{}", serde_json::to_string_pretty(&fru_change_vec)?);
+  } else if fru_change_vec.is_empty() {
+    println!(
+      "No hardware changes between {} and {}",
+      snapshot_before.created, snapshot_after.created
+    );
+  } else {
+    print_table(&fru_change_vec);
+  }
+
+  Ok(())
+}
+
+/// FRU changes per node and location. A FRU is replaced if its FRU ID, serial number or
+/// description changed. Nodes missing in one of the lists have all their FRUs added or
+/// removed
+pub fn diff(
+  node_summary_before_vec: &[NodeSummary],
+  node_summary_after_vec: &[NodeSummary],
+) -> Vec<FruChange> {
+  let fru_before_map = get_fru_map(node_summary_before_vec);
+  let fru_after_map = get_fru_map(node_summary_after_vec);
+
+  let location_set: BTreeSet<&(String, String)> =
+    fru_before_map.keys().chain(fru_after_map.keys()).collect();
+
+  location_set
+    .into_iter()
+    .filter_map(|key| {
+      let (node, location) = key;
+
+      let fru_before_opt = fru_before_map.get(key).copied();
+      let fru_after_opt = fru_after_map.get(key).copied();
+
+      let change = match (fru_before_opt, fru_after_opt) {
+        (Some(_), None) => FruChangeType::Removed,
+        (None, Some(_)) => FruChangeType::Added,
+        (Some(fru_before), Some(fru_after))
+          if is_replaced(fru_before, fru_after) =>
+        {
+          FruChangeType::Replaced
+        }
+        _ => return None,
+      };
+
+      let fru = fru_after_opt.or(fru_before_opt)?;
+
+      Some(FruChange {
+        node: node.clone(),
+        location: location.clone(),
+        r#type: fru.r#type.to_string(),
+        change,
+        info_before: fru_before_opt.and_then(|fru| fru.info.clone()),
+        info_after: fru_after_opt.and_then(|fru| fru.info.clone()),
+        serial_number_before: fru_before_opt
+          .and_then(|fru| fru.serial_number.clone()),
+        serial_number_after: fru_after_opt
+          .and_then(|fru| fru.serial_number.clone()),
+      })
+    })
+    .collect()
+}
+
+/// FRUs of every node keyed by node and location xname
+fn get_fru_map(
+  node_summary_vec: &[NodeSummary],
+) -> BTreeMap<(String, String), &ArtifactSummary> {
+  node_summary_vec
+    .iter()
+    .flat_map(|node_summary| {
+      node_summary
+        .processors
+        .iter()
+        .chain(&node_summary.memory)
+        .chain(&node_summary.node_accels)
+        .chain(&node_summary.node_hsn_nics)
+        .map(|fru| ((node_summary.xname.clone(), fru.xname.clone()), fru))
+    })
+    .collect()
+}
+
+fn is_replaced(
+  fru_before: &ArtifactSummary,
+  fru_after: &ArtifactSummary,
+) -> bool {
+  let changed = |before: &Option<String>, after: &Option<String>| {
+    before.is_some() && after.is_some() && before != after
+  };
+
+  changed(&fru_before.fru_id, &fru_after.fru_id)
+    || changed(&fru_before.serial_number, &fru_after.serial_number)
+    || fru_before.info != fru_after.info
+}
+
+fn print_table(fru_change_vec: &[FruChange]) {
+  let mut table = Table::new();
+
+  table.set_header(vec![
+    "Node",
+    "Location",
+    "Type",
+    "Change",
+    "Before",
+    "Serial Number Before",
+    "After",
+    "Serial Number After",
+  ]);
+
+  for fru_change in fru_change_vec {
+    let change_cell = match fru_change.change {
+      FruChangeType::Added => Cell::new("added").fg(Color::Green),
+      FruChangeType::Removed => Cell::new("removed").fg(Color::Red),
+      FruChangeType::Replaced => Cell::new("replaced").fg(Color::Yellow),
+    };
+
+    table.add_row(vec![
+      Cell::new(&fru_change.node),
+      Cell::new(&fru_change.location),
+      Cell::new(&fru_change.r#type),
+      change_cell,
+      Cell::new(fru_change.info_before.as_deref().unwrap_or_default()),
+      Cell::new(
+        fru_change
+          .serial_number_before
+          .as_deref()
+          .unwrap_or_default(),
+      ),
+      Cell::new(fru_change.info_after.as_deref().unwrap_or_default()),
+      Cell::new(
+        fru_change
+          .serial_number_after
+          .as_deref()
+          .unwrap_or_default(),
+      ),
+    ]);
+  }
+
+  println!("{table}");
+}
+
+#[cfg(test)]
+mod tests;
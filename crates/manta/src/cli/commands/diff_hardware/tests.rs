@@ -0,0 +1,204 @@
+use manta_backend_dispatcher::types::{
+  ArtifactSummary, ArtifactType, NodeSummary,
+};
+
+use crate::common::hw_inventory_snapshot::HwInventorySnapshot;
+
+use super::{diff, exec, FruChangeType};
+
+fn processor(
+  xname: &str,
+  fru_id: &str,
+  serial_number: &str,
+  info: &str,
+) -> ArtifactSummary {
+  ArtifactSummary {
+    xname: xname.to_string(),
+    r#type: ArtifactType::Processor,
+    info: Some(info.to_string()),
+    fru_id: Some(fru_id.to_string()),
+    serial_number: Some(serial_number.to_string()),
+  }
+}
+
+fn node_summary(xname: &str, processors: Vec<ArtifactSummary>) -> NodeSummary {
+  NodeSummary {
+    xname: xname.to_string(),
+    r#type: "Node".to_string(),
+    processors,
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_diff_same_hardware_has_no_changes() {
+  let node_summary_vec = vec![node_summary(
+    "x1000c0s0b0n0",
+    vec![processor(
+      "x1000c0s0b0n0p0",
+      "Processor.AMD.0001",
+      "0001",
+      "AMD EPYC 7742",
+    )],
+  )];
+
+  assert!(diff(&node_summary_vec, &node_summary_vec).is_empty());
+}
+
+#[test]
+fn test_diff_added_removed_and_replaced() {
+  let node_summary_before_vec = vec![node_summary(
+    "x1000c0s0b0n0",
+    vec![
+      processor(
+        "x1000c0s0b0n0p0",
+        "Processor.AMD.0001",
+        "0001",
+        "AMD EPYC 7742",
+      ),
+      processor(
+        "x1000c0s0b0n0p1",
+        "Processor.AMD.0002",
+        "0002",
+        "AMD EPYC 7742",
+      ),
+    ],
+  )];
+
+  let node_summary_after_vec = vec![node_summary(
+    "x1000c0s0b0n0",
+    vec![
+      processor(
+        "x1000c0s0b0n0p0",
+        "Processor.AMD.0003",
+        "0003",
+        "AMD EPYC 7742",
+      ),
+      processor(
+        "x1000c0s0b0n0p2",
+        "Processor.AMD.0004",
+        "0004",
+        "AMD EPYC 7742",
+      ),
+    ],
+  )];
+
+  let fru_change_vec = diff(&node_summary_before_vec, &node_summary_after_vec);
+
+  let change_vec: Vec<(&str, FruChangeType)> = fru_change_vec
+    .iter()
+    .map(|fru_change| (fru_change.location.as_str(), fru_change.change))
+    .collect();
+
+  assert_eq!(
+    change_vec,
+    vec![
+      ("x1000c0s0b0n0p0", FruChangeType::Replaced),
+      ("x1000c0s0b0n0p1", FruChangeType::Removed),
+      ("x1000c0s0b0n0p2", FruChangeType::Added),
+    ]
+  );
+
+  let replaced = &fru_change_vec[0];
+  assert_eq!(replaced.serial_number_before.as_deref(), Some("0001"));
+  assert_eq!(replaced.serial_number_after.as_deref(), Some("0003"));
+
+  let removed = &fru_change_vec[1];
+  assert_eq!(removed.serial_number_before.as_deref(), Some("0002"));
+  assert_eq!(removed.serial_number_after, None);
+
+  let added = &fru_change_vec[2];
+  assert_eq!(added.serial_number_before, None);
+  assert_eq!(added.serial_number_after.as_deref(), Some("0004"));
+}
+
+#[test]
+fn test_diff_replaced_when_description_changes() {
+  let node_summary_before_vec = vec![node_summary(
+    "x1000c0s0b0n0",
+    vec![processor(
+      "x1000c0s0b0n0p0",
+      "Processor.AMD.0001",
+      "0001",
+      "AMD EPYC 7742",
+    )],
+  )];
+
+  let node_summary_after_vec = vec![node_summary(
+    "x1000c0s0b0n0",
+    vec![processor(
+      "x1000c0s0b0n0p0",
+      "Processor.AMD.0001",
+      "0001",
+      "AMD EPYC 7763",
+    )],
+  )];
+
+  let fru_change_vec = diff(&node_summary_before_vec, &node_summary_after_vec);
+
+  assert_eq!(fru_change_vec.len(), 1);
+  assert_eq!(fru_change_vec[0].change, FruChangeType::Replaced);
+}
+
+#[test]
+fn test_diff_not_replaced_when_serial_number_missing() {
+  let mut fru_after = processor(
+    "x1000c0s0b0n0p0",
+    "Processor.AMD.0001",
+    "0001",
+    "AMD EPYC 7742",
+  );
+  fru_after.serial_number = None;
+
+  let node_summary_before_vec = vec![node_summary(
+    "x1000c0s0b0n0",
+    vec![processor(
+      "x1000c0s0b0n0p0",
+      "Processor.AMD.0001",
+      "0001",
+      "AMD EPYC 7742",
+    )],
+  )];
+
+  let node_summary_after_vec =
+    vec![node_summary("x1000c0s0b0n0", vec![fru_after])];
+
+  assert!(diff(&node_summary_before_vec, &node_summary_after_vec).is_empty());
+}
+
+#[test]
+fn test_diff_node_missing() {
+  let node_summary_before_vec = vec![node_summary(
+    "x1000c0s0b0n0",
+    vec![processor(
+      "x1000c0s0b0n0p0",
+      "Processor.AMD.0001",
+      "0001",
+      "AMD EPYC 7742",
+    )],
+  )];
+
+  let removed_vec = diff(&node_summary_before_vec, &[]);
+  assert_eq!(removed_vec.len(), 1);
+  assert_eq!(removed_vec[0].node, "x1000c0s0b0n0");
+  assert_eq!(removed_vec[0].change, FruChangeType::Removed);
+
+  let added_vec = diff(&[], &node_summary_before_vec);
+  assert_eq!(added_vec.len(), 1);
+  assert_eq!(added_vec[0].change, FruChangeType::Added);
+}
+
+#[test]
+fn test_exec_different_sites() {
+  let snapshot_before = HwInventorySnapshot {
+    site: "alps".to_string(),
+    ..Default::default()
+  };
+
+  let snapshot_after = HwInventorySnapshot {
+    site: "prealps".to_string(),
+    ..Default::default()
+  };
+
+  assert!(exec(&snapshot_before, &snapshot_after, "json").is_err());
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use manta_backend_dispatcher::{
+  error::Error, interfaces::hsm::redfish_endpoint::RedfishEndpointTrait,
+};
+
+use crate::{
+  common::redfish_endpoint_file::{
+    self, RedfishEndpointFileFormat, RedfishEndpointRecord,
+  },
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Prints or saves to a file the redfish endpoints in the system in the format used by
+/// 'import redfish-endpoints'. If a file is used, the format is taken from its extension
+pub async fn exec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  output: &str,
+  file_opt: Option<&Path>,
+) -> Result<(), Error> {
+  let format = match file_opt {
+    Some(file) => RedfishEndpointFileFormat::from_path(file)?,
+    None if output == "csv" => RedfishEndpointFileFormat::Csv,
+    None => RedfishEndpointFileFormat::Yaml,
+  };
+
+  let mut record_vec: Vec<RedfishEndpointRecord> = backend
+    .get_all_redfish_endpoints(shasta_token)
+    .await?
+    .redfish_endpoints
+    .unwrap_or_default()
+    .into_iter()
+    .map(RedfishEndpointRecord::from)
+    .collect();
+
+  // Stable order so the file can be kept in git
+  record_vec.sort_by(|record_a, record_b| record_a.id.cmp(&record_b.id));
+
+  let content = redfish_endpoint_file::to_string(&record_vec, format)?;
+
+  match file_opt {
+    Some(file) => {
+      std::fs::write(file, content)?;
+
+      println!(
+        "{} redfish endpoints exported to '{}'",
+        record_vec.len(),
+        file.display()
+      );
+    }
+    None => print!("{}", content),
+  }
+
+  Ok(())
+}
@@ -14,7 +14,7 @@ pub async fn exec(
   _kernel: Option<&String>,
   _initrd: Option<&String>,
 ) -> Result<Vec<BootParameters>, Error> {
-  println!("Get boot parameters");
+  log::info!("Get boot parameters");
 
   let hosts: Vec<String> = xnames.split(',').map(String::from).collect();
 
@@ -1,7 +1,11 @@
 use manta_backend_dispatcher::interfaces::hsm::group::GroupTrait;
 
 use crate::{
-  common::node_ops, manta_backend_dispatcher::StaticBackendDispatcher,
+  common::{
+    node_ops,
+    output::{self, OutputFormat},
+  },
+  manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
 /// Get nodes status/configuration for some nodes filtered by a HSM group.
@@ -96,17 +100,15 @@ pub async fn exec(
     } else {
       println!("{}", node_xname_list.join(","));
     }
-  } else if output_opt.is_some() && output_opt.unwrap().eq("json") {
-    println!(
-      "{}",
-      serde_json::to_string_pretty(&node_details_list).unwrap()
-    );
   } else if output_opt.is_some() && output_opt.unwrap().eq("summary") {
     node_ops::print_summary(node_details_list);
-  } else if output_opt.is_some() && output_opt.unwrap().eq("table-wide") {
-    node_ops::print_table_wide(node_details_list);
-  } else if output_opt.is_some() && output_opt.unwrap().eq("table") {
-    node_ops::print_table(node_details_list);
+  } else if let Some(output) =
+    output_opt.and_then(|output| output.parse::<OutputFormat>().ok())
+  {
+    output::print(&node_details_list, output).unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not print node details. Reason:\n{e}");
+      std::process::exit(1);
+    });
   } else {
     eprintln!("ERROR - output value not recognized or missing. Exit");
     std::process::exit(1);
@@ -5,7 +5,10 @@ use manta_backend_dispatcher::{
 };
 
 use crate::{
-  common::cfs_configuration_utils::print_table_struct,
+  common::{
+    cfs_configuration_utils::print_table_struct,
+    output::{self, OutputFormat, OutputSelection},
+  },
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -20,7 +23,8 @@ pub async fn exec(
   configuration_name_pattern: Option<&String>,
   hsm_group_name_vec: &Vec<String>,
   limit: Option<&u8>,
-  output_opt: Option<&String>,
+  output: OutputFormat,
+  output_selection: &OutputSelection,
   site_name: &str,
 ) {
   let cfs_configuration_vec: Vec<CfsConfigurationResponse> = backend
@@ -44,11 +48,14 @@ pub async fn exec(
     std::process::exit(0);
   }
 
-  if output_opt.is_some() && output_opt.unwrap().eq("json") {
-    println!(
-      "{}",
-      serde_json::to_string_pretty(&cfs_configuration_vec).unwrap()
-    );
+  // NOTE: default table output shows the configuration details (layers and derivatives)
+  // when only one configuration is found
+  if output != OutputFormat::Table || !output_selection.is_empty() {
+    output::print_selection(&cfs_configuration_vec, output, output_selection)
+      .unwrap_or_else(|e| {
+        eprintln!("ERROR - Could not print configurations. Reason:\n{e}");
+        std::process::exit(1);
+      });
   } else {
     if cfs_configuration_vec.len() == 1 {
       // Get CFS configuration details with data from VCS/Gitea
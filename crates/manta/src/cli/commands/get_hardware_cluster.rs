@@ -1,5 +1,6 @@
 use std::{
   collections::{HashMap, HashSet},
+  path::PathBuf,
   sync::Arc,
   time::Instant,
 };
@@ -11,16 +12,23 @@ use manta_backend_dispatcher::{
 };
 use tokio::sync::Semaphore;
 
-use crate::manta_backend_dispatcher::StaticBackendDispatcher;
+use crate::{
+  common::{
+    hw_inventory_query::HwInventoryQuery,
+    hw_inventory_snapshot::HwInventorySnapshot,
+  },
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
 
 pub async fn exec(
   backend: StaticBackendDispatcher,
   shasta_token: &str,
   hsm_group_name: &str,
   output_opt: Option<&String>,
+  filter_opt: Option<&HwInventoryQuery>,
+  snapshot_file_opt: Option<&PathBuf>,
+  site_name: &str,
 ) {
-  let pipe_size = 15;
-
   // Target HSM group
   let hsm_group = backend
     .get_group(shasta_token, hsm_group_name)
@@ -37,43 +45,133 @@ pub async fn exec(
     hsm_group_target_members
   );
 
-  let mut hsm_summary: Vec<NodeSummary> = Vec::new();
-
   let start_total = Instant::now();
 
+  // The snapshot keeps the hardware inventory of each node, so the hardware components are
+  // taken from it instead of asking the backend twice
+  let mut hsm_summary = if let Some(snapshot_file) = snapshot_file_opt {
+    let snapshot = HwInventorySnapshot::fetch(
+      &backend,
+      shasta_token,
+      site_name,
+      &[hsm_group_name.to_string()],
+      &[],
+    )
+    .await
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not get hardware snapshot. Reason:\n{}", e);
+      std::process::exit(1);
+    });
+
+    if let Err(e) = snapshot.save(snapshot_file) {
+      eprintln!("ERROR - Could not save hardware snapshot. Reason:\n{}", e);
+      std::process::exit(1);
+    }
+
+    snapshot.get_node_summary_vec()
+  } else {
+    get_node_summary_vec(&backend, shasta_token, &hsm_group_target_members)
+      .await
+  };
+
+  let duration = start_total.elapsed();
+
+  log::info!(
+    "Time elapsed in http calls to get hw inventory for HSM '{}' is: {:?}",
+    hsm_group_name,
+    duration
+  );
+
+  if let Some(filter) = filter_opt {
+    hsm_summary.retain(|node_summary| filter.matches(node_summary));
+  }
+
+  if output_opt.is_some_and(|output| output.eq("json")) {
+    for node_summary in &hsm_summary {
+      println!("{}", serde_json::to_string_pretty(&node_summary).unwrap());
+    }
+  } else if output_opt.is_some_and(|output| output.eq("xnames")) {
+    print_xnames(&hsm_summary);
+  } else if output_opt.is_some_and(|output| output.eq("pattern")) {
+    let hsm_node_hw_component_count_hashmap =
+      get_cluster_hw_pattern(hsm_summary);
+    print_to_terminal_cluster_hw_pattern(
+      hsm_group_name,
+      hsm_node_hw_component_count_hashmap,
+    )
+  } else if output_opt.is_some_and(|output| output.eq("details")) {
+    print_table_details(&hsm_summary);
+  } else if output_opt.is_some_and(|output| output.eq("summary")) {
+    let hsm_node_hw_component_summary =
+      calculate_hsm_hw_component_summary(&hsm_summary);
+
+    print_table_summary(&hsm_node_hw_component_summary);
+  } else {
+    eprintln!("'output' value not valid. Exit");
+  }
+}
+
+/// Prints the sorted comma separated list of nodes, usable as input to other commands. Fails
+/// if there are no nodes so an empty list is never passed to the next command
+pub fn print_xnames(node_summary_vec: &[NodeSummary]) {
+  if node_summary_vec.is_empty() {
+    eprintln!("ERROR - No nodes found");
+    std::process::exit(1);
+  }
+
+  let mut xname_vec: Vec<&str> = node_summary_vec
+    .iter()
+    .map(|node_summary| node_summary.xname.as_str())
+    .collect();
+
+  xname_vec.sort();
+
+  println!("{}", xname_vec.join(","));
+}
+
+/// Hardware components of each node, nodes without hardware inventory have no components
+pub async fn get_node_summary_vec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  xname_vec: &[String],
+) -> Vec<NodeSummary> {
+  let pipe_size = 15;
+
+  let mut hsm_summary: Vec<NodeSummary> = Vec::new();
+
   let mut tasks = tokio::task::JoinSet::new();
 
   let sem = Arc::new(Semaphore::new(pipe_size));
   // make it faster
 
-  let num_hsm_group_members = hsm_group_target_members.len();
+  let num_xnames = xname_vec.len();
 
   let mut i = 1;
 
   // Calculate number of digits of a number
-  let width = num_hsm_group_members.checked_ilog10().unwrap_or(0) as usize + 1;
+  let width = num_xnames.checked_ilog10().unwrap_or(0) as usize + 1;
 
   // Get HW inventory details for target HSM group
-  for hsm_member in hsm_group_target_members.iter() {
+  for xname in xname_vec.iter() {
     log::info!(
-            "\rGetting hw components for node '{hsm_member}' [{:>width$}/{num_hsm_group_members}]",
-            i + 1
-        );
+      "\rGetting hw components for node '{xname}' [{:>width$}/{num_xnames}]",
+      i + 1
+    );
 
     let backend_cp = backend.clone();
     let shasta_token_string = shasta_token.to_string(); // TODO: make it static
-    let hsm_member_string = hsm_member.to_string(); // TODO: make it static
-                                                    //
+    let xname_string = xname.to_string(); // TODO: make it static
+
     let permit = Arc::clone(&sem).acquire_owned().await;
 
-    // log::debug!("Getting HW inventory details for node '{}'", hsm_member);
+    // log::debug!("Getting HW inventory details for node '{}'", xname);
 
     tasks.spawn(async move {
       let _permit = permit; // Wait semaphore to allow new tasks https://github.com/tokio-rs/tokio/discussions/2648#discussioncomment-34885
       let hw_inventory_value = backend_cp
         .get_inventory_hardware_query(
           &shasta_token_string,
-          &hsm_member_string,
+          &xname_string,
           None,
           None,
           None,
@@ -91,7 +189,7 @@ pub async fn exec(
         }
         None => {
           let mut node_summary_default = NodeSummary::default();
-          node_summary_default.xname = hsm_member_string;
+          node_summary_default.xname = xname_string;
           node_summary_default
         }
       };
@@ -116,35 +214,7 @@ pub async fn exec(
     } */
   }
 
-  let duration = start_total.elapsed();
-
-  log::info!(
-    "Time elapsed in http calls to get hw inventory for HSM '{}' is: {:?}",
-    hsm_group_name,
-    duration
-  );
-
-  if output_opt.is_some_and(|output| output.eq("json")) {
-    for node_summary in &hsm_summary {
-      println!("{}", serde_json::to_string_pretty(&node_summary).unwrap());
-    }
-  } else if output_opt.is_some_and(|output| output.eq("pattern")) {
-    let hsm_node_hw_component_count_hashmap =
-      get_cluster_hw_pattern(hsm_summary);
-    print_to_terminal_cluster_hw_pattern(
-      hsm_group_name,
-      hsm_node_hw_component_count_hashmap,
-    )
-  } else if output_opt.is_some_and(|output| output.eq("details")) {
-    print_table_details(&hsm_summary);
-  } else if output_opt.is_some_and(|output| output.eq("summary")) {
-    let hsm_node_hw_component_summary =
-      calculate_hsm_hw_component_summary(&hsm_summary);
-
-    print_table_summary(&hsm_node_hw_component_summary);
-  } else {
-    eprintln!("'output' value not valid. Exit");
-  }
+  hsm_summary
 }
 
 pub fn calculate_hsm_hw_component_summary(
@@ -4,7 +4,11 @@ use manta_backend_dispatcher::{
 };
 use std::string::ToString;
 
-use crate::manta_backend_dispatcher::StaticBackendDispatcher;
+use crate::{
+  cli::commands::get_hardware_cluster::{get_node_summary_vec, print_xnames},
+  common::hw_inventory_query::HwInventoryQuery,
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
 
 /// Get nodes status/configuration for some nodes filtered by a HSM group.
 pub async fn exec(
@@ -13,7 +17,35 @@ pub async fn exec(
   xname: &str,
   type_artifact_opt: Option<&String>,
   output_opt: Option<&String>,
+  filter_opt: Option<&HwInventoryQuery>,
 ) {
+  // The filter is evaluated against the hw components of each node in the list
+  if filter_opt.is_some()
+    || output_opt.is_some_and(|output| output.eq("xnames"))
+  {
+    let xname_vec: Vec<String> =
+      xname.split(',').map(|xname| xname.to_string()).collect();
+
+    let mut node_summary_vec =
+      get_node_summary_vec(backend, shasta_token, &xname_vec).await;
+
+    if let Some(filter) = filter_opt {
+      node_summary_vec.retain(|node_summary| filter.matches(node_summary));
+    }
+
+    if output_opt.is_some_and(|output| output.eq("json")) {
+      for node_summary in &node_summary_vec {
+        println!("{}", serde_json::to_string_pretty(&node_summary).unwrap());
+      }
+    } else if output_opt.is_some_and(|output| output.eq("xnames")) {
+      print_xnames(&node_summary_vec);
+    } else {
+      print_table(&node_summary_vec);
+    }
+
+    return;
+  }
+
   let mut node_hw_inventory = &backend
     .get_inventory_hardware_query(
       shasta_token,
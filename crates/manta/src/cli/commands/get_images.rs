@@ -1,11 +1,13 @@
-use chrono::{DateTime, Local, NaiveDateTime};
 use comfy_table::Table;
 use manta_backend_dispatcher::{
   error::Error, interfaces::get_images_and_details::GetImagesAndDetailsTrait,
   types::ims::Image,
 };
 
-use crate::manta_backend_dispatcher::StaticBackendDispatcher;
+use crate::{
+  common::output::{self, format_creation_date, OutputFormat, OutputSelection},
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
 
 /// If filtering by HSM group, then image name must include HSM group name (It assumms each image
 /// is built for a specific cluster based on ansible vars used by the CFS session). The reason
@@ -18,6 +20,8 @@ pub async fn exec(
   hsm_group_name_vec: &[String],
   id_opt: Option<&String>,
   limit_number: Option<&u8>,
+  output: OutputFormat,
+  output_selection: &OutputSelection,
 ) {
   let image_detail_vec_rslt: Result<Vec<(Image, String, String, bool)>, Error> =
     backend
@@ -39,7 +43,25 @@ pub async fn exec(
     }
   };
 
-  // Print data
+  // NOTE: default table output shows the CFS configuration and HSM groups related to each
+  // image, other formats print the IMS image records
+  if output == OutputFormat::Table && output_selection.is_empty() {
+    print_table(&image_detail_vec);
+  } else {
+    let image_vec: Vec<Image> = image_detail_vec
+      .into_iter()
+      .map(|image_details| image_details.0)
+      .collect();
+
+    output::print_selection(&image_vec, output, output_selection)
+      .unwrap_or_else(|e| {
+        eprintln!("ERROR - Could not print images. Reason:\n{e}");
+        std::process::exit(1);
+      });
+  }
+}
+
+pub fn print_table(image_detail_vec: &[(Image, String, String, bool)]) {
   let mut table = Table::new();
 
   table.set_header(vec![
@@ -53,17 +75,8 @@ pub async fn exec(
   ]);
 
   for image_details in image_detail_vec {
-    let creation_date = image_details.0.created.as_ref().unwrap();
-
-    // NOTE: CSM can have different date formats, so we need to try to parse it in different
-    // ways
-    let creation_date = if let Ok(v) = creation_date.parse::<NaiveDateTime>() {
-      v.format("%d/%m/%Y %H:%M:%S").to_string()
-    } else if let Ok(v) = creation_date.parse::<DateTime<Local>>() {
-      v.naive_local().format("%d/%m/%Y %H:%M:%S").to_string()
-    } else {
-      creation_date.to_string()
-    };
+    let creation_date =
+      format_creation_date(image_details.0.created.as_ref().unwrap());
 
     table.add_row(vec![
       image_details.0.id.as_ref().unwrap(),
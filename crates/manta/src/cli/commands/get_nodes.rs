@@ -1,6 +1,13 @@
 use manta_backend_dispatcher::interfaces::hsm::component::ComponentTrait;
 
-use crate::{common, manta_backend_dispatcher::StaticBackendDispatcher};
+use crate::{
+  common::{
+    self,
+    output::{self, OutputFormat, OutputSelection},
+    watch,
+  },
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
 
 /// Get nodes status/configuration for some nodes filtered by a HSM group.
 pub async fn exec(
@@ -14,7 +21,9 @@ pub async fn exec(
   silent_nid: bool,
   silent_xname: bool,
   output_opt: Option<&String>,
+  output_selection: &OutputSelection,
   status: bool,
+  watch_interval_opt: Option<u64>,
 ) {
   // Convert user input to xname
   let node_metadata_available_vec = backend
@@ -47,6 +56,37 @@ pub async fn exec(
   node_list.sort();
   node_list.dedup();
 
+  if let Some(watch_interval) = watch_interval_opt {
+    if !output_opt
+      .is_some_and(|output| output == "table" || output == "table-wide")
+    {
+      eprintln!("ERROR - watch mode only supports table outputs. Exit");
+      std::process::exit(1);
+    }
+
+    watch::watch(
+      &format!("manta get nodes {hosts_expression}"),
+      watch_interval,
+      output_opt.is_some_and(|output| output == "table-wide"),
+      output_selection,
+      || {
+        csm_rs::node::utils::get_node_details(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          node_list.to_vec(),
+        )
+      },
+    )
+    .await
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not watch nodes. Reason:\n{e}");
+      std::process::exit(1);
+    });
+
+    return;
+  }
+
   let node_details_list_rslt = csm_rs::node::utils::get_node_details(
     shasta_token,
     shasta_base_url,
@@ -117,17 +157,16 @@ pub async fn exec(
     } else {
       println!("{}", node_xname_list.join(","));
     }
-  } else if output_opt.is_some() && output_opt.unwrap().eq("json") {
-    println!(
-      "{}",
-      serde_json::to_string_pretty(&node_details_list).unwrap()
-    );
   } else if output_opt.is_some() && output_opt.unwrap().eq("summary") {
     common::node_ops::print_summary(node_details_list);
-  } else if output_opt.is_some() && output_opt.unwrap().eq("table-wide") {
-    common::node_ops::print_table_wide(node_details_list);
-  } else if output_opt.is_some() && output_opt.unwrap().eq("table") {
-    common::node_ops::print_table(node_details_list);
+  } else if let Some(output) =
+    output_opt.and_then(|output| output.parse::<OutputFormat>().ok())
+  {
+    output::print_selection(&node_details_list, output, output_selection)
+      .unwrap_or_else(|e| {
+        eprintln!("ERROR - Could not print node details. Reason:\n{e}");
+        std::process::exit(1);
+      });
   } else {
     eprintln!("ERROR - output value not recognized or missing. Exit");
     std::process::exit(1);
@@ -0,0 +1,38 @@
+use manta_backend_dispatcher::interfaces::pcs::PCSTrait;
+
+use crate::{
+  common::output::{self, OutputFormat},
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Prints the power cap controls of a list of nodes
+pub async fn exec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  xname_vec: &[String],
+  output: OutputFormat,
+) {
+  if xname_vec.is_empty() {
+    eprintln!("The list of nodes to operate is empty. Nothing to do. Exit");
+    std::process::exit(0);
+  }
+
+  log::info!("Get power cap for nodes: {:?}", xname_vec);
+
+  let power_cap_task = backend
+    .power_cap_snapshot(shasta_token, xname_vec)
+    .await
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not get power cap. Reason:\n{e}\nExit");
+      std::process::exit(1);
+    });
+
+  let mut component_vec = power_cap_task.components.unwrap_or_default();
+
+  component_vec.sort_by(|a, b| a.xname.cmp(&b.xname));
+
+  if let Err(e) = output::print(&component_vec, output) {
+    eprintln!("ERROR - Could not print power cap. Reason:\n{e}");
+    std::process::exit(1);
+  }
+}
@@ -0,0 +1,68 @@
+use manta_backend_dispatcher::{error::Error, interfaces::pcs::PCSTrait};
+
+use crate::{
+  common::{
+    output::{self, OutputFormat},
+    pcs_utils::{self, TransitionTask},
+  },
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Prints the status of a power transition and the task of each node. If `wait`, shows the
+/// progress of the transition until it completes. JSON and YAML print the whole transition
+/// as returned by PCS, any other format prints its tasks
+pub async fn exec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  transition_id: &str,
+  wait: bool,
+  output_format: OutputFormat,
+) -> Result<(), Error> {
+  let transition_rslt = if wait {
+    pcs_utils::wait_to_complete(backend, shasta_token, transition_id).await
+  } else {
+    backend
+      .get_power_transition(shasta_token, transition_id)
+      .await
+  };
+
+  let transition = transition_rslt.unwrap_or_else(|e| {
+    eprintln!(
+      "ERROR - Could not get power transition '{}'. Reason:\n{}",
+      transition_id, e
+    );
+    std::process::exit(1);
+  });
+
+  match output_format {
+    OutputFormat::Json => {
+      println!("{}", serde_json::to_string_pretty(&transition)?)
+    }
+    OutputFormat::Yaml => println!(
+      "{}",
+      serde_yaml::to_string(&transition)
+        .map_err(|e| Error::Message(format!("Could not render YAML: {e}")))?
+    ),
+    OutputFormat::Table | OutputFormat::TableWide => {
+      println!(
+        "\nTransition ID: {}",
+        transition["transitionID"].as_str().unwrap_or_default()
+      );
+      println!(
+        "Transition Status: {}",
+        transition["transitionStatus"].as_str().unwrap_or_default()
+      );
+
+      output::print(
+        &TransitionTask::from_transition(&transition),
+        output_format,
+      )?;
+    }
+    OutputFormat::Csv | OutputFormat::Ndjson => output::print(
+      &TransitionTask::from_transition(&transition),
+      output_format,
+    )?,
+  }
+
+  Ok(())
+}
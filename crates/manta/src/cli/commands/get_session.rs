@@ -1,6 +1,12 @@
 use manta_backend_dispatcher::interfaces::cfs::CfsTrait;
 
-use crate::{common, manta_backend_dispatcher::StaticBackendDispatcher};
+use crate::{
+  common::{
+    output::{self, OutputFormat, OutputSelection},
+    watch,
+  },
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
 
 pub async fn exec(
   backend: &StaticBackendDispatcher,
@@ -14,13 +20,51 @@ pub async fn exec(
   status_opt: Option<&String>,
   cfs_session_name_opt: Option<&String>,
   limit_number_opt: Option<&u8>,
-  output_opt: Option<&String>,
+  output: OutputFormat,
+  output_selection: &OutputSelection,
+  watch_interval_opt: Option<u64>,
 ) {
   log::info!(
     "Get CFS sessions for HSM groups: {:?}",
     hsm_group_name_available_vec_opt
   );
 
+  if let Some(watch_interval) = watch_interval_opt {
+    if !matches!(output, OutputFormat::Table | OutputFormat::TableWide) {
+      eprintln!("ERROR - watch mode only supports table outputs. Exit");
+      std::process::exit(1);
+    }
+
+    watch::watch(
+      "manta get sessions",
+      watch_interval,
+      output == OutputFormat::TableWide,
+      output_selection,
+      move || {
+        backend.get_and_filter_sessions(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_available_vec_opt.clone(),
+          xname_vec_opt.clone(),
+          min_age_opt,
+          max_age_opt,
+          status_opt,
+          cfs_session_name_opt,
+          limit_number_opt,
+          None,
+        )
+      },
+    )
+    .await
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not watch CFS sessions. Reason:\n{e}");
+      std::process::exit(1);
+    });
+
+    return;
+  }
+
   let cfs_session_vec = backend
     .get_and_filter_sessions(
       shasta_token,
@@ -41,12 +85,9 @@ pub async fn exec(
       std::process::exit(1);
     });
 
-  if output_opt.is_some() && output_opt.unwrap().eq("json") {
-    println!(
-      "{}",
-      serde_json::to_string_pretty(&cfs_session_vec).unwrap()
-    );
-  } else {
-    common::cfs_session_utils::print_table_struct(&cfs_session_vec);
-  }
+  output::print_selection(&cfs_session_vec, output, output_selection)
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not print CFS sessions. Reason:\n{e}");
+      std::process::exit(1);
+    });
 }
@@ -1,6 +1,9 @@
 use manta_backend_dispatcher::interfaces::bos::ClusterTemplateTrait;
 
-use crate::manta_backend_dispatcher::StaticBackendDispatcher;
+use crate::{
+  common::output::{self, OutputFormat, OutputSelection},
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
 
 pub async fn exec(
   backend: &StaticBackendDispatcher,
@@ -11,7 +14,8 @@ pub async fn exec(
   hsm_member_vec: &[String],
   bos_sessiontemplate_name_opt: Option<&String>,
   limit_number_opt: Option<&u8>,
-  output: &str,
+  output: OutputFormat,
+  output_selection: &OutputSelection,
 ) {
   log::info!(
     "Get BOS sessiontemplates for HSM groups: {:?}",
@@ -44,16 +48,11 @@ pub async fn exec(
   if bos_sessiontemplate_vec.is_empty() {
     println!("No BOS template found!");
     std::process::exit(0);
-  } else {
-    if output == "table" {
-      crate::common::bos_sessiontemplate_utils::print_table_struct(
-        bos_sessiontemplate_vec,
-      );
-    } else if output == "json" {
-      println!(
-        "{}",
-        serde_json::to_string_pretty(&bos_sessiontemplate_vec).unwrap()
-      );
-    }
   }
+
+  output::print_selection(&bos_sessiontemplate_vec, output, output_selection)
+    .unwrap_or_else(|e| {
+      eprintln!("ERROR - Could not print BOS sessiontemplates. Reason:\n{e}");
+      std::process::exit(1);
+    });
 }
@@ -0,0 +1,177 @@
+use std::path::Path;
+
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::hsm::redfish_endpoint::RedfishEndpointTrait,
+  types::hsm::inventory::{RedfishEndpoint, RedfishEndpointArray},
+};
+
+use crate::{
+  common::redfish_endpoint_file::{self, RedfishEndpointRecord},
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Adds the redfish endpoints in a CSV or YAML file. Endpoints already in the system are
+/// only updated if `update_existing`. Prints the endpoints applied and fails listing the
+/// ones that could not be applied
+pub async fn exec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  file: &Path,
+  update_existing: bool,
+  dry_run: bool,
+) -> Result<(), Error> {
+  let record_vec = redfish_endpoint_file::read(file)?;
+
+  redfish_endpoint_file::validate(&record_vec)?;
+
+  let existing_redfish_endpoint_vec = backend
+    .get_all_redfish_endpoints(shasta_token)
+    .await?
+    .redfish_endpoints
+    .unwrap_or_default();
+
+  let (update_record_vec, add_record_vec): (Vec<_>, Vec<_>) =
+    record_vec.into_iter().partition(|record| {
+      existing_redfish_endpoint_vec
+        .iter()
+        .any(|redfish_endpoint| redfish_endpoint.id.eq(&record.id))
+    });
+
+  let conflict_vec =
+    get_conflict_vec(&add_record_vec, &existing_redfish_endpoint_vec);
+
+  if !conflict_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "Redfish endpoints conflict with endpoints in the system:\n - {}",
+      conflict_vec.join("\n - ")
+    )));
+  }
+
+  if !update_record_vec.is_empty() && !update_existing {
+    return Err(Error::Message(format!(
+      "Redfish endpoints already in the system: {}. Use '--update-existing' to update them",
+      update_record_vec
+        .iter()
+        .map(|record| record.id.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+    )));
+  }
+
+  if dry_run {
+    println!(
+      "Dry run - Redfish endpoints to add: {}",
+      get_id_list(&add_record_vec)
+    );
+    println!(
+      "Dry run - Redfish endpoints to update: {}",
+      get_id_list(&update_record_vec)
+    );
+
+    return Ok(());
+  }
+
+  let mut applied_vec = Vec::new();
+  let mut failed_vec = Vec::new();
+
+  // Endpoints are applied one by one so a failure doesn't hide which ones made it to the
+  // system
+  for record in add_record_vec {
+    let id = record.id.clone();
+
+    let redfish_endpoint_array = RedfishEndpointArray {
+      redfish_endpoints: Some(vec![RedfishEndpoint::from(record)]),
+    };
+
+    match backend
+      .add_redfish_endpoint(shasta_token, &redfish_endpoint_array)
+      .await
+    {
+      Ok(()) => applied_vec.push(format!("'{}' added", id)),
+      Err(e) => failed_vec.push(format!("'{}' not added. Reason: {}", id, e)),
+    }
+  }
+
+  for record in update_record_vec {
+    let id = record.id.clone();
+
+    match backend
+      .update_redfish_endpoint(shasta_token, &record.into())
+      .await
+    {
+      Ok(()) => applied_vec.push(format!("'{}' updated", id)),
+      Err(e) => failed_vec.push(format!("'{}' not updated. Reason: {}", id, e)),
+    }
+  }
+
+  if !applied_vec.is_empty() {
+    println!(
+      "Redfish endpoints applied:\n - {}",
+      applied_vec.join("\n - ")
+    );
+  }
+
+  if !failed_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "{} of {} redfish endpoints failed:\n - {}",
+      failed_vec.len(),
+      applied_vec.len() + failed_vec.len(),
+      failed_vec.join("\n - ")
+    )));
+  }
+
+  Ok(())
+}
+
+/// New endpoints using the FQDN, MAC address or IP address of another endpoint in the system
+fn get_conflict_vec(
+  add_record_vec: &[RedfishEndpointRecord],
+  existing_redfish_endpoint_vec: &[RedfishEndpoint],
+) -> Vec<String> {
+  let mut conflict_vec = Vec::new();
+
+  for record in add_record_vec {
+    for redfish_endpoint in existing_redfish_endpoint_vec {
+      let field_vec = [
+        ("FQDN", &record.fqdn, &redfish_endpoint.fqdn),
+        ("MAC address", &record.mac_addr, &redfish_endpoint.mac_addr),
+        (
+          "IP address",
+          &record.ip_address,
+          &redfish_endpoint.ip_address,
+        ),
+      ];
+
+      for (field_name, value_opt, existing_value_opt) in field_vec {
+        if let (Some(value), Some(existing_value)) =
+          (value_opt, existing_value_opt)
+        {
+          if !value.is_empty() && value.eq_ignore_ascii_case(existing_value) {
+            conflict_vec.push(format!(
+              "{} '{}' of '{}' already used by '{}'",
+              field_name, value, record.id, redfish_endpoint.id
+            ));
+          }
+        }
+      }
+    }
+  }
+
+  conflict_vec
+}
+
+fn get_id_list(record_vec: &[RedfishEndpointRecord]) -> String {
+  if record_vec.is_empty() {
+    "none".to_string()
+  } else {
+    record_vec
+      .iter()
+      .map(|record| record.id.as_str())
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,85 @@
+use manta_backend_dispatcher::types::hsm::inventory::RedfishEndpoint;
+
+use crate::common::redfish_endpoint_file::RedfishEndpointRecord;
+
+use super::get_conflict_vec;
+
+fn record(
+  id: &str,
+  fqdn: &str,
+  mac_addr: &str,
+  ip_address: &str,
+) -> RedfishEndpointRecord {
+  RedfishEndpointRecord {
+    id: id.to_string(),
+    fqdn: Some(fqdn.to_string()),
+    mac_addr: Some(mac_addr.to_string()),
+    ip_address: Some(ip_address.to_string()),
+    ..Default::default()
+  }
+}
+
+fn existing_redfish_endpoint_vec() -> Vec<RedfishEndpoint> {
+  vec![RedfishEndpoint::from(record(
+    "x1000c0s0b0",
+    "x1000c0s0b0.hmn",
+    "a4:bf:01:00:00:01",
+    "10.254.1.1",
+  ))]
+}
+
+#[test]
+fn test_get_conflict_vec_no_conflicts() {
+  let add_record_vec = vec![record(
+    "x1000c0s1b0",
+    "x1000c0s1b0.hmn",
+    "a4:bf:01:00:00:02",
+    "10.254.1.2",
+  )];
+
+  assert!(
+    get_conflict_vec(&add_record_vec, &existing_redfish_endpoint_vec())
+      .is_empty()
+  );
+}
+
+#[test]
+fn test_get_conflict_vec_every_field() {
+  let add_record_vec = vec![record(
+    "x1000c0s1b0",
+    "X1000C0S0B0.hmn",
+    "A4:BF:01:00:00:01",
+    "10.254.1.1",
+  )];
+
+  let conflict_vec =
+    get_conflict_vec(&add_record_vec, &existing_redfish_endpoint_vec());
+
+  assert_eq!(
+    conflict_vec,
+    vec![
+      "FQDN 'X1000C0S0B0.hmn' of 'x1000c0s1b0' already used by 'x1000c0s0b0'",
+      "MAC address 'A4:BF:01:00:00:01' of 'x1000c0s1b0' already used by 'x1000c0s0b0'",
+      "IP address '10.254.1.1' of 'x1000c0s1b0' already used by 'x1000c0s0b0'",
+    ]
+  );
+}
+
+#[test]
+fn test_get_conflict_vec_empty_and_missing_values() {
+  let add_record_vec = vec![
+    record("x1000c0s1b0", "", "", ""),
+    RedfishEndpointRecord {
+      id: "x1000c0s2b0".to_string(),
+      ..Default::default()
+    },
+  ];
+
+  let existing_redfish_endpoint_vec =
+    vec![RedfishEndpoint::from(record("x1000c0s0b0", "", "", ""))];
+
+  assert!(
+    get_conflict_vec(&add_record_vec, &existing_redfish_endpoint_vec)
+      .is_empty()
+  );
+}
@@ -1,3 +1,4 @@
+use manta_backend_dispatcher::audit::Auditor;
 use std::collections::HashMap;
 
 use manta_backend_dispatcher::interfaces::hsm::{
@@ -5,7 +6,7 @@ use manta_backend_dispatcher::interfaces::hsm::{
 };
 
 use crate::{
-  common::{self, audit::Audit, jwt_ops, kafka::Kafka},
+  common::{self, audit},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -17,7 +18,7 @@ pub async fn exec(
   hosts_expression: &str,
   nodryrun: bool,
   create_hsm_group: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   // Filter xnames to the ones members to HSM groups the user has access to
   //
@@ -86,6 +87,9 @@ pub async fn exec(
 
   log::debug!("xnames to move: {:?}", xname_to_move_vec);
 
+  // Nodes that could not be migrated
+  let mut failed_xname_vec: Vec<String> = Vec::new();
+
   for target_hsm_name in target_hsm_name_vec {
     if backend
       .get_group(shasta_token, &target_hsm_name)
@@ -142,24 +146,33 @@ pub async fn exec(
             parent_hsm_name, parent_hsm_group_member_vec
           );
         }
-        Err(e) => eprintln!("{}", e),
+        Err(e) => {
+          eprintln!("{}", e);
+          failed_xname_vec.extend(xname_to_move_vec.iter().cloned());
+        }
       }
     }
   }
 
   // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
-
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": xname_to_move_vec}, "group": vec![parent_hsm_name_vec, target_hsm_name_vec], "message": format!("Migrate nodes from {:?} to {:?}", parent_hsm_name_vec, target_hsm_name_vec)});
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "migrate nodes")
+      .with_message(&format!(
+        "Migrate nodes from {:?} to {:?}",
+        parent_hsm_name_vec, target_hsm_name_vec
+      ))
+      .with_xnames(&xname_to_move_vec)
+      .with_groups(
+        &[
+          parent_hsm_name_vec.as_slice(),
+          target_hsm_name_vec.as_slice(),
+        ]
+        .concat(),
+      );
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    let event = audit::with_failed_xnames(event, &failed_xname_vec);
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+    auditor.send(&event).await;
   }
 }
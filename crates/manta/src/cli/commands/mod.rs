@@ -9,9 +9,11 @@ pub mod apply_ephemeral_env;
 pub mod apply_hw_cluster_pin;
 pub mod apply_hw_cluster_unpin;
 pub mod apply_kernel_parameters;
+pub mod apply_power_cap;
 pub mod apply_sat_file;
 pub mod apply_session;
 pub mod apply_template;
+pub mod cache_clear;
 pub mod config_set_hsm;
 pub mod config_set_log;
 pub mod config_set_parent_hsm;
@@ -27,6 +29,8 @@ pub mod delete_hw_component_cluster;
 pub mod delete_image;
 pub mod delete_kernel_parameters;
 pub mod delete_node;
+pub mod diff_hardware;
+pub mod export_redfish_endpoints;
 pub mod get_boot_parameters;
 pub mod get_cluster;
 pub mod get_configuration;
@@ -36,12 +40,16 @@ pub mod get_hardware_node;
 pub mod get_images;
 pub mod get_kernel_parameters;
 pub mod get_nodes;
+pub mod get_power_cap;
+pub mod get_power_transition;
 pub mod get_session;
 pub mod get_template;
+pub mod import_redfish_endpoints;
 pub mod log;
 pub mod migrate_backup;
 pub mod migrate_nodes_between_hsm_groups;
 pub mod migrate_restore;
+pub mod plan_hw_cluster;
 pub mod power_off_cluster;
 pub mod power_off_nodes;
 pub mod power_on_cluster;
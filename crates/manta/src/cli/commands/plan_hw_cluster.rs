@@ -0,0 +1,488 @@
+use std::{
+  collections::{BTreeMap, HashMap},
+  path::Path,
+};
+
+use manta_backend_dispatcher::error::Error;
+use serde::Serialize;
+
+use crate::{
+  cli::commands::apply_hw_cluster_pin::{
+    constraints::{
+      resolve_hw_description_to_xnames_with_constraints, AllocationConstraints,
+      TopologyLevel,
+    },
+    utils::{
+      calculate_hsm_hw_component_summary,
+      calculate_hsm_node_scores_from_final_hsm,
+      calculate_node_hw_component_count, get_node_hw_properties_from_value,
+    },
+  },
+  common::hw_inventory_snapshot::HwInventorySnapshot,
+};
+
+/// Used to normalize and quantify memory capacity, same value used by 'apply hardware
+/// cluster'
+const MEM_LCM: u64 = 16384; // 1024 * 16
+
+/// Result of moving nodes between a target and a parent group to fulfill a hw pattern
+#[derive(Serialize, Debug)]
+pub struct HwClusterPlan {
+  pub site: String,
+  /// Hardware inventory snapshot the plan is based on, missing if based on the live system
+  pub inventory_file: Option<String>,
+  pub unpin: bool,
+  /// Restrictions on the nodes picked for the target group
+  pub constraints: AllocationConstraints,
+  /// Hw components requested for the target group
+  pub requested: BTreeMap<String, usize>,
+  /// Higher for the hw components scarce in both groups combined
+  pub scarcity_scores: BTreeMap<String, f32>,
+  pub target_group: GroupPlan,
+  pub parent_group: GroupPlan,
+  /// Nodes moved from the parent group to the target group
+  pub nodes_to_target: Vec<String>,
+  /// Nodes moved from the target group to the parent group
+  pub nodes_to_parent: Vec<String>,
+  /// Nodes picked for the target group, in the order they were picked
+  pub selections: Vec<NodeSelection>,
+  /// Every node in both groups
+  pub nodes: Vec<NodePlan>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GroupPlan {
+  pub name: String,
+  pub members_before: Vec<String>,
+  pub members_after: Vec<String>,
+  pub hw_component_summary_before: BTreeMap<String, usize>,
+  pub hw_component_summary_after: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NodePlan {
+  pub xname: String,
+  pub group_before: String,
+  pub group_after: String,
+  pub hw_components: BTreeMap<String, usize>,
+  /// Score before any node is picked, the higher the better the node fits the target group
+  pub initial_score: f32,
+  /// Never picked because of the constraints
+  pub excluded: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NodeSelection {
+  pub xname: String,
+  pub from_group: String,
+  /// Score of the node when it was picked
+  pub score: f32,
+  pub reasons: Vec<String>,
+}
+
+/// Prints the nodes 'apply hardware cluster' would move between the target and parent
+/// groups, without changing anything
+pub async fn exec(
+  snapshot: &HwInventorySnapshot,
+  inventory_file_opt: Option<&Path>,
+  target_hsm_group_name: &str,
+  parent_hsm_group_name: &str,
+  pattern: &str,
+  unpin: bool,
+  constraints: &AllocationConstraints,
+  output: &str,
+) -> Result<(), Error> {
+  let plan = calculate_plan(
+    snapshot,
+    inventory_file_opt,
+    target_hsm_group_name,
+    parent_hsm_group_name,
+    pattern,
+    unpin,
+    constraints,
+  )
+  .await?;
+
+  let plan_str = if output == "yaml" {
+    serde_yaml::to_string(&plan).map_err(|e| Error::Message(e.to_string()))?
+  } else {
+    serde_json::to_string_pretty(&plan)?
+  };
+
+  println!("{}", plan_str);
+
+  Ok(())
+}
+
+pub async fn calculate_plan(
+  snapshot: &HwInventorySnapshot,
+  inventory_file_opt: Option<&Path>,
+  target_hsm_group_name: &str,
+  parent_hsm_group_name: &str,
+  pattern: &str,
+  unpin: bool,
+  constraints: &AllocationConstraints,
+) -> Result<HwClusterPlan, Error> {
+  let requested_hashmap = parse_pattern(pattern)?;
+
+  let mut requested_hw_component_vec: Vec<String> =
+    requested_hashmap.keys().cloned().collect();
+
+  requested_hw_component_vec.sort();
+
+  let target_member_vec = snapshot.get_members(target_hsm_group_name);
+  let parent_member_vec = snapshot.get_members(parent_hsm_group_name);
+
+  let target_hsm_node_hw_component_count_vec = get_hsm_node_hw_component_count(
+    snapshot,
+    target_member_vec,
+    &requested_hw_component_vec,
+  )?;
+
+  let parent_hsm_node_hw_component_count_vec = get_hsm_node_hw_component_count(
+    snapshot,
+    parent_member_vec,
+    &requested_hw_component_vec,
+  )?;
+
+  // Nodes in both groups are counted once
+  let mut combined_hsm_node_hw_component_count_vec =
+    parent_hsm_node_hw_component_count_vec.clone();
+
+  for elem in &target_hsm_node_hw_component_count_vec {
+    if !parent_member_vec.contains(&elem.0) {
+      combined_hsm_node_hw_component_count_vec.push(elem.clone());
+    }
+  }
+
+  combined_hsm_node_hw_component_count_vec
+    .sort_by(|(xname_a, _), (xname_b, _)| xname_a.cmp(xname_b));
+
+  let combined_hsm_hw_component_summary = calculate_hsm_hw_component_summary(
+    &combined_hsm_node_hw_component_count_vec,
+  );
+
+  for (hw_component, qty) in &requested_hashmap {
+    let available_qty = combined_hsm_hw_component_summary
+      .get(hw_component)
+      .copied()
+      .unwrap_or(0);
+
+    if available_qty < *qty {
+      return Err(Error::Message(format!(
+        "Not enough '{}' in groups '{}' and '{}'. Requested {}, available {}",
+        hw_component,
+        target_hsm_group_name,
+        parent_hsm_group_name,
+        qty,
+        available_qty
+      )));
+    }
+  }
+
+  let (
+    new_target_hsm_node_hw_component_count_vec,
+    new_parent_hsm_node_hw_component_count_vec,
+    selected_node_score_vec,
+    hw_component_scarcity_scores_hashmap,
+  ) = resolve_hw_description_to_xnames_with_constraints(
+    target_hsm_node_hw_component_count_vec.clone(),
+    parent_hsm_node_hw_component_count_vec.clone(),
+    requested_hashmap.clone(),
+    constraints,
+    unpin,
+  )
+  .await?;
+
+  // Hw components left in both groups combined once the request is fulfilled
+  let final_hsm_hw_component_summary: HashMap<String, usize> =
+    requested_hashmap
+      .iter()
+      .map(|(hw_component, qty)| {
+        (
+          hw_component.clone(),
+          combined_hsm_hw_component_summary
+            .get(hw_component)
+            .copied()
+            .unwrap_or(0)
+            .saturating_sub(*qty),
+        )
+      })
+      .collect();
+
+  let initial_score_vec = calculate_hsm_node_scores_from_final_hsm(
+    &combined_hsm_node_hw_component_count_vec,
+    &combined_hsm_hw_component_summary,
+    &final_hsm_hw_component_summary,
+    &hw_component_scarcity_scores_hashmap,
+  );
+
+  let new_target_member_vec: Vec<String> =
+    get_sorted_xnames(&new_target_hsm_node_hw_component_count_vec);
+
+  let get_group_name = |xname: &str, member_vec: &[String]| {
+    if member_vec.iter().any(|member| member == xname) {
+      target_hsm_group_name.to_string()
+    } else {
+      parent_hsm_group_name.to_string()
+    }
+  };
+
+  // Explain every pick, tracking how many of each requested hw component are still needed
+  let mut missing_hashmap = requested_hashmap.clone();
+
+  let selection_vec = selected_node_score_vec
+    .iter()
+    .zip(&new_target_hsm_node_hw_component_count_vec)
+    .map(|((xname, score), (_, hw_component_count))| {
+      let from_group = get_group_name(xname, target_member_vec);
+
+      let mut reason_vec = explain_selection(
+        from_group == target_hsm_group_name,
+        !unpin || constraints.prefer_target,
+        hw_component_count,
+        &mut missing_hashmap,
+      );
+
+      if let Some(location) = explain_location(xname, constraints) {
+        reason_vec.push(location);
+      }
+
+      NodeSelection {
+        xname: xname.clone(),
+        from_group,
+        score: *score,
+        reasons: reason_vec,
+      }
+    })
+    .collect();
+
+  let node_vec = combined_hsm_node_hw_component_count_vec
+    .iter()
+    .map(|(xname, hw_component_count)| NodePlan {
+      xname: xname.clone(),
+      group_before: get_group_name(xname, target_member_vec),
+      group_after: get_group_name(xname, &new_target_member_vec),
+      hw_components: to_btreemap(hw_component_count),
+      initial_score: initial_score_vec
+        .iter()
+        .find(|(node, _)| node == xname)
+        .map(|(_, score)| *score)
+        .unwrap_or_default(),
+      excluded: constraints.exclude_xname_vec.contains(xname),
+    })
+    .collect();
+
+  let nodes_to_target = new_target_member_vec
+    .iter()
+    .filter(|xname| !target_member_vec.contains(*xname))
+    .cloned()
+    .collect();
+
+  let nodes_to_parent = target_member_vec
+    .iter()
+    .filter(|xname| !new_target_member_vec.contains(*xname))
+    .cloned()
+    .collect();
+
+  Ok(HwClusterPlan {
+    site: snapshot.site.clone(),
+    inventory_file: inventory_file_opt.map(|path| path.display().to_string()),
+    unpin,
+    constraints: constraints.clone(),
+    requested: to_btreemap(&requested_hashmap),
+    scarcity_scores: hw_component_scarcity_scores_hashmap.into_iter().collect(),
+    target_group: GroupPlan {
+      name: target_hsm_group_name.to_string(),
+      members_before: get_sorted_xnames(
+        &target_hsm_node_hw_component_count_vec,
+      ),
+      members_after: new_target_member_vec,
+      hw_component_summary_before: to_btreemap(
+        &calculate_hsm_hw_component_summary(
+          &target_hsm_node_hw_component_count_vec,
+        ),
+      ),
+      hw_component_summary_after: to_btreemap(
+        &calculate_hsm_hw_component_summary(
+          &new_target_hsm_node_hw_component_count_vec,
+        ),
+      ),
+    },
+    parent_group: GroupPlan {
+      name: parent_hsm_group_name.to_string(),
+      members_before: get_sorted_xnames(
+        &parent_hsm_node_hw_component_count_vec,
+      ),
+      members_after: get_sorted_xnames(
+        &new_parent_hsm_node_hw_component_count_vec,
+      ),
+      hw_component_summary_before: to_btreemap(
+        &calculate_hsm_hw_component_summary(
+          &parent_hsm_node_hw_component_count_vec,
+        ),
+      ),
+      hw_component_summary_after: to_btreemap(
+        &calculate_hsm_hw_component_summary(
+          &new_parent_hsm_node_hw_component_count_vec,
+        ),
+      ),
+    },
+    nodes_to_target,
+    nodes_to_parent,
+    selections: selection_vec,
+    nodes: node_vec,
+  })
+}
+
+/// Parses a pattern like 'a100:4:epyc:10' into the quantity requested of each hw component
+fn parse_pattern(pattern: &str) -> Result<HashMap<String, usize>, Error> {
+  let pattern_element_vec: Vec<String> = pattern
+    .to_lowercase()
+    .split(':')
+    .map(str::to_string)
+    .collect();
+
+  pattern_element_vec
+    .chunks(2)
+    .map(|hw_component_counter| match hw_component_counter {
+      [hw_component, qty] if !hw_component.is_empty() => qty
+        .parse::<usize>()
+        .map(|qty| (hw_component.clone(), qty))
+        .ok(),
+      _ => None,
+    })
+    .collect::<Option<HashMap<String, usize>>>()
+    .ok_or_else(|| {
+      Error::Message(format!(
+        "Pattern '{}' not valid. Please make sure to follow <hw component>:<counter>:... eg a100:4:epyc:10:instinct:8",
+        pattern
+      ))
+    })
+}
+
+/// Hw component counters of each node, using the hardware inventory in the snapshot
+fn get_hsm_node_hw_component_count(
+  snapshot: &HwInventorySnapshot,
+  member_vec: &[String],
+  requested_hw_component_vec: &[String],
+) -> Result<Vec<(String, HashMap<String, usize>)>, Error> {
+  let mut hsm_node_hw_component_count_vec = member_vec
+    .iter()
+    .map(|xname| -> Result<(String, HashMap<String, usize>), Error> {
+      let node_hw_inventory_value = snapshot
+        .nodes
+        .get(xname)
+        .filter(|value| value.pointer("/Nodes/0").is_some())
+        .ok_or_else(|| {
+          Error::Message(format!(
+            "Hardware inventory of node '{xname}' missing"
+          ))
+        })?;
+
+      let (node_hw_component_vec, node_memory_vec) =
+        get_node_hw_properties_from_value(
+          node_hw_inventory_value,
+          requested_hw_component_vec.to_vec(),
+        );
+
+      Ok((
+        xname.clone(),
+        calculate_node_hw_component_count(
+          node_hw_component_vec,
+          &node_memory_vec,
+          MEM_LCM,
+        ),
+      ))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+  hsm_node_hw_component_count_vec
+    .sort_by(|(xname_a, _), (xname_b, _)| xname_a.cmp(xname_b));
+
+  Ok(hsm_node_hw_component_count_vec)
+}
+
+/// Reasons a node was picked for the target group. Updates the quantity still needed of
+/// each requested hw component
+fn explain_selection(
+  in_target_group: bool,
+  prefer_target: bool,
+  hw_component_count: &HashMap<String, usize>,
+  missing_hashmap: &mut HashMap<String, usize>,
+) -> Vec<String> {
+  let mut reason_vec = vec![match (in_target_group, prefer_target) {
+    (true, true) => {
+      "already in target group, nodes in target group are picked first"
+        .to_string()
+    }
+    (true, false) => "already in target group".to_string(),
+    (false, _) => "taken from parent group".to_string(),
+  }];
+
+  for (hw_component, qty) in to_btreemap(hw_component_count) {
+    if qty == 0 {
+      continue;
+    }
+
+    match missing_hashmap.get_mut(&hw_component) {
+      Some(missing_qty) if *missing_qty > 0 => {
+        reason_vec.push(format!(
+          "provides {} '{}', {} still needed",
+          qty, hw_component, missing_qty
+        ));
+
+        *missing_qty = missing_qty.saturating_sub(qty);
+      }
+      Some(_) => reason_vec.push(format!(
+        "provides {} '{}' beyond the quantity requested",
+        qty, hw_component
+      )),
+      None => {
+        reason_vec.push(format!("has {} '{}' not requested", qty, hw_component))
+      }
+    }
+  }
+
+  reason_vec
+}
+
+/// Location of a node picked when the constraints depend on it
+fn explain_location(
+  xname: &str,
+  constraints: &AllocationConstraints,
+) -> Option<String> {
+  let topology_level = match (constraints.affinity, constraints.spread_chassis)
+  {
+    (Some(topology_level), _) => topology_level,
+    (None, Some(_)) => TopologyLevel::Chassis,
+    (None, None) => return None,
+  };
+
+  topology_level
+    .get_domain(xname)
+    .map(|domain| format!("in {} '{}'", topology_level, domain))
+}
+
+fn get_sorted_xnames(
+  hsm_node_hw_component_count_vec: &[(String, HashMap<String, usize>)],
+) -> Vec<String> {
+  let mut xname_vec: Vec<String> = hsm_node_hw_component_count_vec
+    .iter()
+    .map(|(xname, _)| xname.clone())
+    .collect();
+
+  xname_vec.sort();
+
+  xname_vec
+}
+
+fn to_btreemap(hashmap: &HashMap<String, usize>) -> BTreeMap<String, usize> {
+  hashmap
+    .iter()
+    .map(|(key, value)| (key.clone(), *value))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests;
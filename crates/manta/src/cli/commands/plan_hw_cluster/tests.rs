@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{json, Value};
+
+use crate::{
+  cli::commands::apply_hw_cluster_pin::constraints::AllocationConstraints,
+  common::hw_inventory_snapshot::HwInventorySnapshot,
+};
+
+use super::{calculate_plan, parse_pattern};
+
+/// HSM hardware inventory of a node with 2 processors and 32GiB of memory
+fn node_hw_inventory(processor_model: &str) -> Value {
+  let processor = json!({
+    "PopulatedFRU": { "ProcessorFRUInfo": { "Model": processor_model } }
+  });
+  let memory = json!({
+    "PopulatedFRU": { "MemoryFRUInfo": { "CapacityMiB": 16384 } }
+  });
+
+  json!({
+    "Nodes": [{
+      "Processors": [processor.clone(), processor],
+      "Memory": [memory.clone(), memory],
+    }]
+  })
+}
+
+/// Target group 'zinal' with 2 nodes and parent group 'nodes_free' with 3 nodes, every
+/// node with 2 epyc processors
+fn snapshot() -> HwInventorySnapshot {
+  let target_member_vec =
+    vec!["x1000c0s0b0n0".to_string(), "x1000c0s0b0n1".to_string()];
+  let parent_member_vec = vec![
+    "x1000c0s1b0n0".to_string(),
+    "x1000c0s1b0n1".to_string(),
+    "x1000c0s2b0n0".to_string(),
+  ];
+
+  let nodes = target_member_vec
+    .iter()
+    .chain(&parent_member_vec)
+    .map(|xname| (xname.clone(), node_hw_inventory("AMD EPYC 7742")))
+    .collect();
+
+  HwInventorySnapshot {
+    site: "alps".to_string(),
+    groups: BTreeMap::from([
+      ("zinal".to_string(), target_member_vec),
+      ("nodes_free".to_string(), parent_member_vec),
+    ]),
+    nodes,
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_parse_pattern() {
+  assert_eq!(
+    parse_pattern("A100:4:epyc:10").unwrap(),
+    HashMap::from([("a100".to_string(), 4), ("epyc".to_string(), 10)])
+  );
+}
+
+#[test]
+fn test_parse_pattern_not_valid() {
+  for pattern in ["", "a100", "a100:four", ":4", "a100:4:", "a100:-1"] {
+    assert!(parse_pattern(pattern).is_err(), "pattern '{}'", pattern);
+  }
+}
+
+#[tokio::test]
+async fn test_calculate_plan_pin_keeps_target_nodes() {
+  let plan = calculate_plan(
+    &snapshot(),
+    None,
+    "zinal",
+    "nodes_free",
+    "epyc:6",
+    false,
+    &AllocationConstraints::default(),
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(plan.site, "alps");
+  assert_eq!(plan.requested, BTreeMap::from([("epyc".to_string(), 6)]));
+
+  let member_after_vec = &plan.target_group.members_after;
+  assert_eq!(member_after_vec.len(), 3);
+  assert!(member_after_vec.contains(&"x1000c0s0b0n0".to_string()));
+  assert!(member_after_vec.contains(&"x1000c0s0b0n1".to_string()));
+  assert_eq!(
+    plan.target_group.hw_component_summary_after.get("epyc"),
+    Some(&6)
+  );
+
+  assert_eq!(plan.nodes_to_target.len(), 1);
+  assert!(plan.nodes_to_parent.is_empty());
+  assert_eq!(plan.parent_group.members_after.len(), 2);
+
+  // Nodes of the target group are picked first
+  assert_eq!(plan.selections.len(), 3);
+  assert!(plan.selections[..2]
+    .iter()
+    .all(|selection| selection.from_group == "zinal"));
+  assert_eq!(plan.selections[2].from_group, "nodes_free");
+
+  assert_eq!(plan.nodes.len(), 5);
+}
+
+#[tokio::test]
+async fn test_calculate_plan_unpin_fulfills_request() {
+  let plan = calculate_plan(
+    &snapshot(),
+    None,
+    "zinal",
+    "nodes_free",
+    "epyc:4",
+    true,
+    &AllocationConstraints::default(),
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(plan.target_group.members_after.len(), 2);
+  assert_eq!(
+    plan.target_group.hw_component_summary_after.get("epyc"),
+    Some(&4)
+  );
+  assert_eq!(plan.parent_group.members_after.len(), 3);
+  assert_eq!(plan.nodes_to_target.len(), plan.nodes_to_parent.len());
+}
+
+#[tokio::test]
+async fn test_calculate_plan_not_enough_hw_components() {
+  let rslt = calculate_plan(
+    &snapshot(),
+    None,
+    "zinal",
+    "nodes_free",
+    "epyc:12",
+    false,
+    &AllocationConstraints::default(),
+  )
+  .await;
+
+  assert!(rslt.is_err());
+}
+
+#[tokio::test]
+async fn test_calculate_plan_missing_hw_inventory() {
+  let mut snapshot = snapshot();
+  snapshot.nodes.remove("x1000c0s1b0n0");
+
+  let rslt = calculate_plan(
+    &snapshot,
+    None,
+    "zinal",
+    "nodes_free",
+    "epyc:4",
+    false,
+    &AllocationConstraints::default(),
+  )
+  .await;
+
+  assert!(rslt.is_err());
+}
@@ -1,10 +1,9 @@
 use dialoguer::{theme::ColorfulTheme, Confirm};
-use manta_backend_dispatcher::interfaces::{
-  hsm::group::GroupTrait, pcs::PCSTrait,
-};
+use manta_backend_dispatcher::audit::Auditor;
+use manta_backend_dispatcher::interfaces::hsm::group::GroupTrait;
 
 use crate::{
-  common::{self, audit::Audit, jwt_ops, kafka::Kafka},
+  common::{self, audit, pcs_utils::PowerAction},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -14,8 +13,10 @@ pub async fn exec(
   hsm_group_name_arg: &str,
   force: bool,
   assume_yes: bool,
+  no_wait: bool,
+  retry_failed: usize,
   output: &str,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   let xname_vec = backend
     .get_member_vec_from_group_name_vec(
@@ -41,38 +42,50 @@ pub async fn exec(
     }
   }
 
-  let power_mgmt_summary_rslt = backend
-    .power_off_sync(shasta_token, &xname_vec, force)
-    .await;
+  let transition_vec = common::pcs_utils::exec_power_transition(
+    backend,
+    shasta_token,
+    PowerAction::Off { force },
+    &xname_vec,
+    no_wait,
+    retry_failed,
+  )
+  .await
+  .unwrap_or_else(|e| {
+    eprintln!(
+      "ERROR - Could not power off node/s '{:?}'. Reason:\n{}",
+      xname_vec, e
+    );
 
-  let power_mgmt_summary = match power_mgmt_summary_rslt {
-    Ok(value) => value,
-    Err(e) => {
-      eprintln!(
-        "ERROR - Could not power off node/s '{:?}'. Reason:\n{}",
-        xname_vec,
-        e.to_string()
-      );
+    std::process::exit(1);
+  });
 
-      std::process::exit(1);
-    }
-  };
+  let failed_xname_vec = transition_vec
+    .last()
+    .map(common::pcs_utils::get_failed_xname_vec)
+    .unwrap_or_default();
 
-  common::pcs_utils::print_summary_table(power_mgmt_summary, output);
+  for transition in transition_vec {
+    common::pcs_utils::print_summary_table(transition, output);
+  }
 
   // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "power off cluster")
+      .with_groups(&[hsm_group_name_arg]);
 
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "group": hsm_group_name_arg, "message": "power off"});
+    let event = audit::with_failed_xnames(event, &failed_xname_vec);
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    auditor.send(&event).await;
+  }
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+  // Nodes still failing after the retries
+  if !failed_xname_vec.is_empty() {
+    eprintln!(
+      "ERROR - Could not power off node/s: {}",
+      failed_xname_vec.join(", ")
+    );
+    std::process::exit(1);
   }
 }
@@ -1,11 +1,11 @@
 use dialoguer::{theme::ColorfulTheme, Confirm};
-use manta_backend_dispatcher::interfaces::{
-  hsm::{component::ComponentTrait, group::GroupTrait},
-  pcs::PCSTrait,
+use manta_backend_dispatcher::audit::Auditor;
+use manta_backend_dispatcher::interfaces::hsm::{
+  component::ComponentTrait, group::GroupTrait,
 };
 
 use crate::{
-  common::{self, audit::Audit, jwt_ops, kafka::Kafka},
+  common::{self, audit, pcs_utils::PowerAction},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -14,8 +14,10 @@ pub async fn exec(
   shasta_token: &str,
   hosts_expression: &str,
   assume_yes: bool,
+  no_wait: bool,
+  retry_failed: usize,
   output: &str,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   // Convert user input to xname
   /* let xname_available_vec: Vec<String> = backend
@@ -114,29 +116,35 @@ pub async fn exec(
   )
   .await
   .map_err(|e| Error::Message(e.to_string())); */
-  let power_mgmt_summary_rslt =
-    backend.power_on_sync(shasta_token, &xname_vec).await;
+  let transition_vec = common::pcs_utils::exec_power_transition(
+    backend,
+    shasta_token,
+    PowerAction::On,
+    &xname_vec,
+    no_wait,
+    retry_failed,
+  )
+  .await
+  .unwrap_or_else(|e| {
+    eprintln!(
+      "ERROR - Could not power on node/s '{:?}'. Reason:\n{}",
+      xname_vec, e
+    );
 
-  let power_mgmt_summary = match power_mgmt_summary_rslt {
-    Ok(value) => value,
-    Err(e) => {
-      eprintln!(
-        "ERROR - Could not power on node/s '{:?}'. Reason:\n{}",
-        xname_vec,
-        e.to_string()
-      );
+    std::process::exit(1);
+  });
 
-      std::process::exit(1);
-    }
-  };
+  let failed_xname_vec = transition_vec
+    .last()
+    .map(common::pcs_utils::get_failed_xname_vec)
+    .unwrap_or_default();
 
-  common::pcs_utils::print_summary_table(power_mgmt_summary, output);
+  for transition in transition_vec {
+    common::pcs_utils::print_summary_table(transition, output);
+  }
 
   // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
-
+  if let Some(auditor) = auditor_opt {
     let group_map = backend
       .get_group_map_and_filter_by_member_vec(
         shasta_token,
@@ -148,15 +156,23 @@ pub async fn exec(
       .await
       .unwrap();
 
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": xname_vec}, "group": group_map.keys().collect::<Vec<_>>(), "message": "power on"});
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "power on nodes")
+      .with_xnames(&xname_vec)
+      .with_groups(&group_map.keys().collect::<Vec<_>>());
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    let event = audit::with_failed_xnames(event, &failed_xname_vec);
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+    auditor.send(&event).await;
     // log::info!(target: "app::audit", "User: {} ({}) ; Operation: Power on nodes {:?}", jwt_ops::get_name(shasta_token).unwrap(), jwt_ops::get_preferred_username(shasta_token).unwrap(), xname_vec);
   }
+
+  // Nodes still failing after the retries
+  if !failed_xname_vec.is_empty() {
+    eprintln!(
+      "ERROR - Could not power on node/s: {}",
+      failed_xname_vec.join(", ")
+    );
+    std::process::exit(1);
+  }
 }
@@ -1,10 +1,11 @@
 use dialoguer::{theme::ColorfulTheme, Confirm};
+use manta_backend_dispatcher::audit::Auditor;
 use manta_backend_dispatcher::interfaces::hsm::{
   component::ComponentTrait, group::GroupTrait,
 };
 
 use crate::{
-  common::{self, audit::Audit, jwt_ops, kafka::Kafka},
+  common::{self, audit},
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
 
@@ -15,7 +16,7 @@ pub async fn exec(
   target_hsm_name: &String,
   hosts_expression: &str,
   dryrun: bool,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) {
   // Convert user input to xname
   let node_metadata_available_vec = backend
@@ -81,26 +82,35 @@ pub async fn exec(
   }
 
   // Remove xnames from HSM group
+  let mut failed_xname_vec = Vec::new();
+
   for xname in &xname_to_move_vec {
-    let _ = backend
+    if let Err(e) = backend
       .delete_member_from_group(shasta_token, &target_hsm_name, &xname)
       .await
-      .unwrap();
+    {
+      eprintln!(
+        "ERROR - Could not remove node '{}' from group '{}'. Reason:\n{}",
+        xname, target_hsm_name, e
+      );
+      failed_xname_vec.push(xname.clone());
+    }
   }
 
   // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap();
-    let user_id = jwt_ops::get_preferred_username(shasta_token).unwrap();
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "remove nodes from group")
+      .with_message(&format!("Remove nodes from group '{}'", target_hsm_name))
+      .with_xnames(&xname_to_move_vec)
+      .with_groups(&[target_hsm_name]);
 
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": xname_to_move_vec}, "group": vec![target_hsm_name], "message": format!("Remove nodes from group '{}'", target_hsm_name)});
+    let event = audit::with_failed_xnames(event, &failed_xname_vec);
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    auditor.send(&event).await;
+  }
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+  if !failed_xname_vec.is_empty() {
+    std::process::exit(1);
   }
 }
@@ -1,11 +1,11 @@
 use manta_backend_dispatcher::{
-  error::Error, interfaces::bss::BootParametersTrait, types::BootParameters,
+  audit::{AuditOutcome, Auditor},
+  error::Error,
+  interfaces::bss::BootParametersTrait,
+  types::BootParameters,
 };
 
-use crate::{
-  common::{audit::Audit, jwt_ops, kafka::Kafka},
-  manta_backend_dispatcher::StaticBackendDispatcher,
-};
+use crate::{common::audit, manta_backend_dispatcher::StaticBackendDispatcher};
 
 pub async fn exec(
   backend: &StaticBackendDispatcher,
@@ -16,7 +16,7 @@ pub async fn exec(
   params: Option<&String>,
   kernel: Option<&String>,
   initrd: Option<&String>,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
 ) -> Result<(), Error> {
   println!("Update boot parameters");
 
@@ -44,26 +44,23 @@ pub async fn exec(
 
   log::debug!("new boot params: {:#?}", boot_parameters);
 
-  backend
+  let result = backend
     .update_bootparameters(shasta_token, &boot_parameters)
-    .await?;
+    .await;
 
   // Audit
-  if let Some(kafka_audit) = kafka_audit_opt {
-    let username = jwt_ops::get_name(shasta_token).unwrap_or_default();
-    let user_id =
-      jwt_ops::get_preferred_username(shasta_token).unwrap_or_default();
-
-    let msg_json = serde_json::json!(
-        { "user": {"id": user_id, "name": username}, "host": {"hostname": hosts}, "message": format!("Update boot parameters")});
+  if let Some(auditor) = auditor_opt {
+    let event = auditor
+      .new_event(audit::get_user(shasta_token), "update boot parameters")
+      .with_xnames(&hosts);
 
-    let msg_data = serde_json::to_string(&msg_json)
-      .expect("Could not serialize audit message data");
+    let event = match &result {
+      Ok(_) => event,
+      Err(_) => event.with_outcome(AuditOutcome::Failure),
+    };
 
-    if let Err(e) = kafka_audit.produce_message(msg_data.as_bytes()).await {
-      log::warn!("Failed producing messages: {}", e);
-    }
+    auditor.send(&event).await;
   }
 
-  Ok(())
+  result
 }
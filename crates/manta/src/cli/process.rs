@@ -1,6 +1,8 @@
 use clap_complete::{generate, generate_to};
 use manta_backend_dispatcher::{
+  audit::Auditor,
   contracts::BackendTrait,
+  error::Error,
   interfaces::{
     bss::BootParametersTrait,
     commands::CommandsTrait,
@@ -26,11 +28,22 @@ use config::Config;
 use k8s_openapi::chrono;
 
 use crate::{
-  cli::commands::{add_node, validate_local_repo},
+  cli::{
+    build::{
+      get_allocation_constraints, get_output_format, get_output_selection,
+      get_unsupported_subcommand, is_dry_run, is_read_only_subcommand,
+    },
+    commands::{add_node, validate_local_repo},
+  },
   common::{
     authorization::{get_groups_available, validate_target_hsm_members},
+    cache::{self, ResponseCache},
     config::types::MantaConfiguration,
-    kafka::Kafka,
+    hw_inventory_query::HwInventoryQuery,
+    hw_inventory_snapshot::HwInventorySnapshot,
+    output::OutputFormat,
+    pcs_utils,
+    rolling_reboot::RollingReboot,
   },
   manta_backend_dispatcher::StaticBackendDispatcher,
 };
@@ -39,16 +52,19 @@ use super::commands::{
   self, add_group, add_hw_component_cluster, add_kernel_parameters,
   add_nodes_to_hsm_groups, apply_boot_cluster, apply_boot_node,
   apply_ephemeral_env, apply_hw_cluster_pin, apply_hw_cluster_unpin,
-  apply_kernel_parameters, apply_sat_file, apply_session, apply_template,
-  config_set_hsm, config_set_log, config_set_parent_hsm, config_set_site,
-  config_show, config_unset_auth, config_unset_hsm, config_unset_parent_hsm,
+  apply_kernel_parameters, apply_power_cap, apply_sat_file, apply_session,
+  apply_template, cache_clear, config_set_hsm, config_set_log,
+  config_set_parent_hsm, config_set_site, config_show, config_unset_auth,
+  config_unset_hsm, config_unset_parent_hsm,
   console_cfs_session_image_target_ansible, console_node, delete_group,
   delete_hw_component_cluster, delete_image, delete_kernel_parameters,
-  get_boot_parameters, get_cluster, get_configuration, get_hardware_node,
-  get_images, get_kernel_parameters, get_nodes, get_session, get_template,
-  migrate_backup, migrate_nodes_between_hsm_groups, power_off_cluster,
-  power_off_nodes, power_on_cluster, power_on_nodes, power_reset_cluster,
-  power_reset_nodes, remove_nodes_from_hsm_groups, update_boot_parameters,
+  export_redfish_endpoints, get_boot_parameters, get_cluster,
+  get_configuration, get_hardware_node, get_images, get_kernel_parameters,
+  get_nodes, get_power_cap, get_power_transition, get_session, get_template,
+  import_redfish_endpoints, migrate_backup, migrate_nodes_between_hsm_groups,
+  plan_hw_cluster, power_off_cluster, power_off_nodes, power_on_cluster,
+  power_on_nodes, power_reset_cluster, power_reset_nodes,
+  remove_nodes_from_hsm_groups, update_boot_parameters,
 };
 use serde_json::Value;
 
@@ -61,7 +77,7 @@ pub async fn process_cli(
   gitea_base_url: &str,
   settings_hsm_group_name_opt: Option<&String>,
   k8s_api_url: Option<&String>,
-  kafka_audit_opt: Option<&Kafka>,
+  auditor_opt: Option<&Auditor>,
   settings: &Config,
   configuration: &MantaConfiguration,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -77,7 +93,42 @@ pub async fn process_cli(
 
   let cli_root = cli.clone().get_matches();
 
-  if let Some(cli_config) = cli_root.subcommand_matches("config") {
+  // Reject subcommands the backend does not support before doing anything else
+  if let Some(subcommand) =
+    get_unsupported_subcommand(&cli_root, &backend.capabilities())
+  {
+    return Err(Box::new(Error::NotImplemented(format!(
+      "'{}' command",
+      subcommand
+    ))));
+  }
+
+  // Cache responses only for commands that do not change the system. Any other command
+  // invalidates the cached responses of the site once it succeeds since they may be stale
+  let is_read_only = is_read_only_subcommand(&cli_root);
+
+  if is_read_only {
+    if let Some(cache_config) = configuration
+      .cache
+      .as_ref()
+      .filter(|_| !cli_root.get_flag("no-cache"))
+    {
+      match ResponseCache::new(&site_name, cache_config.ttl) {
+        Ok(response_cache) => cache::init(response_cache),
+        Err(e) => log::warn!("Response cache disabled. Reason:\n{}", e),
+      }
+    }
+  }
+
+  let invalidate_cache = !is_read_only
+    && !is_dry_run(&cli_root)
+    && cli_root.subcommand_matches("cache").is_none();
+
+  if let Some(cli_cache) = cli_root.subcommand_matches("cache") {
+    if let Some(cli_cache_clear) = cli_cache.subcommand_matches("clear") {
+      cache_clear::exec(&site_name, cli_cache_clear.get_flag("all"))?;
+    }
+  } else if let Some(cli_config) = cli_root.subcommand_matches("config") {
     if let Some(_cli_config_show) = cli_config.subcommand_matches("show") {
       let shasta_token_rslt = backend.get_api_token(&site_name).await;
 
@@ -217,6 +268,12 @@ pub async fn process_cli(
 
           let assume_yes: bool = cli_power_on_cluster.get_flag("assume-yes");
 
+          let no_wait: bool = cli_power_on_cluster.get_flag("no-wait");
+
+          let retry_failed: usize = *cli_power_on_cluster
+            .get_one::<usize>("retry-failed")
+            .expect("The 'retry-failed' argument must have a value");
+
           let output: &str =
             cli_power_on_cluster.get_one::<String>("output").unwrap();
 
@@ -225,8 +282,10 @@ pub async fn process_cli(
             &shasta_token,
             target_hsm_group,
             assume_yes,
+            no_wait,
+            retry_failed,
             output,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
         } else if let Some(cli_power_on_node) =
@@ -240,6 +299,12 @@ pub async fn process_cli(
 
           let assume_yes: bool = cli_power_on_node.get_flag("assume-yes");
 
+          let no_wait: bool = cli_power_on_node.get_flag("no-wait");
+
+          let retry_failed: usize = *cli_power_on_node
+            .get_one::<usize>("retry-failed")
+            .expect("The 'retry-failed' argument must have a value");
+
           let output: &str =
             cli_power_on_node.get_one::<String>("output").unwrap();
 
@@ -248,8 +313,10 @@ pub async fn process_cli(
             &shasta_token,
             xname_requested,
             assume_yes,
+            no_wait,
+            retry_failed,
             output,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
         }
@@ -284,14 +351,22 @@ pub async fn process_cli(
 
           let assume_yes: bool = cli_power_off_cluster.get_flag("assume-yes");
 
+          let no_wait: bool = cli_power_off_cluster.get_flag("no-wait");
+
+          let retry_failed: usize = *cli_power_off_cluster
+            .get_one::<usize>("retry-failed")
+            .expect("The 'retry-failed' argument must have a value");
+
           power_off_cluster::exec(
             &backend,
             &shasta_token,
             target_hsm_group,
             *force,
             assume_yes,
+            no_wait,
+            retry_failed,
             output,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
         } else if let Some(cli_power_off_node) =
@@ -309,6 +384,12 @@ pub async fn process_cli(
 
           let assume_yes: bool = cli_power_off_node.get_flag("assume-yes");
 
+          let no_wait: bool = cli_power_off_node.get_flag("no-wait");
+
+          let retry_failed: usize = *cli_power_off_node
+            .get_one::<usize>("retry-failed")
+            .expect("The 'retry-failed' argument must have a value");
+
           let output: &str =
             cli_power_off_node.get_one::<String>("output").unwrap();
 
@@ -318,8 +399,10 @@ pub async fn process_cli(
             xname_requested,
             *force,
             assume_yes,
+            no_wait,
+            retry_failed,
             output,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
         }
@@ -356,14 +439,28 @@ pub async fn process_cli(
 
           let assume_yes: bool = cli_power_reset_cluster.get_flag("assume-yes");
 
+          let no_wait: bool = cli_power_reset_cluster.get_flag("no-wait");
+
+          let retry_failed: usize = *cli_power_reset_cluster
+            .get_one::<usize>("retry-failed")
+            .expect("The 'retry-failed' argument must have a value");
+
+          let rolling_reboot_opt =
+            RollingReboot::from_arg_matches(cli_power_reset_cluster);
+
           power_reset_cluster::exec(
             backend,
             &shasta_token,
+            shasta_base_url,
+            shasta_root_cert,
             target_hsm_group,
             *force,
             assume_yes,
+            no_wait,
+            retry_failed,
+            rolling_reboot_opt.as_ref(),
             output,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
         } else if let Some(cli_power_reset_node) =
@@ -381,6 +478,12 @@ pub async fn process_cli(
 
           let assume_yes: bool = cli_power_reset_node.get_flag("assume-yes");
 
+          let no_wait: bool = cli_power_reset_node.get_flag("no-wait");
+
+          let retry_failed: usize = *cli_power_reset_node
+            .get_one::<usize>("retry-failed")
+            .expect("The 'retry-failed' argument must have a value");
+
           let output: &str =
             cli_power_reset_node.get_one::<String>("output").unwrap();
 
@@ -390,8 +493,10 @@ pub async fn process_cli(
             xname_requested,
             *force,
             assume_yes,
+            no_wait,
+            retry_failed,
             output,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
         }
@@ -443,7 +548,7 @@ pub async fn process_cli(
           enabled,
           arch_opt,
           hw_inventory_opt,
-          kafka_audit_opt,
+          auditor_opt,
         )
         .await;
 
@@ -498,7 +603,7 @@ pub async fn process_cli(
           node_expression,
           true,
           false,
-          kafka_audit_opt,
+          auditor_opt,
         )
         .await;
       } else if let Some(cli_add_hw_configuration) =
@@ -663,7 +768,7 @@ pub async fn process_cli(
           nodes,
           assume_yes,
           do_not_reboot,
-          kafka_audit_opt,
+          auditor_opt,
         )
         .await;
 
@@ -796,7 +901,7 @@ pub async fn process_cli(
           params,
           kernel,
           initrd,
-          kafka_audit_opt,
+          auditor_opt,
         )
         .await;
 
@@ -897,9 +1002,7 @@ pub async fn process_cli(
         )
         .await?;
 
-        let output = cli_get_groups
-          .get_one::<String>("output")
-          .expect("ERROR - 'output' argument is mandatory");
+        let output = get_output_format(cli_get_groups)?;
 
         let hsm_group_vec: Vec<&str> = target_hsm_group_vec
           .iter()
@@ -932,11 +1035,19 @@ pub async fn process_cli(
           )
           .await?;
 
+          let filter_opt = cli_get_hardware_cluster
+            .get_one::<String>("filter")
+            .map(|filter| filter.parse::<HwInventoryQuery>())
+            .transpose()?;
+
           commands::get_hardware_cluster::exec(
             backend,
             &shasta_token,
             target_hsm_group_vec.first().unwrap(),
             cli_get_hardware_cluster.get_one::<String>("output"),
+            filter_opt.as_ref(),
+            cli_get_hardware_cluster.get_one::<PathBuf>("snapshot"),
+            &site_name,
           )
           .await;
         } else if let Some(cli_get_hardware_node) =
@@ -954,12 +1065,18 @@ pub async fn process_cli(
           validate_target_hsm_members(&backend, &shasta_token, &xname_vec)
             .await;
 
+          let filter_opt = cli_get_hardware_node
+            .get_one::<String>("filter")
+            .map(|filter| filter.parse::<HwInventoryQuery>())
+            .transpose()?;
+
           get_hardware_node::exec(
             &backend,
             &shasta_token,
             xnames,
             cli_get_hardware_node.get_one::<String>("type"),
             cli_get_hardware_node.get_one::<String>("output"),
+            filter_opt.as_ref(),
           )
           .await;
         }
@@ -1007,7 +1124,8 @@ pub async fn process_cli(
           cli_get_configuration.get_one::<String>("pattern"),
           &target_hsm_group_vec,
           limit,
-          cli_get_configuration.get_one("output"),
+          get_output_format(cli_get_configuration)?,
+          &get_output_selection(cli_get_configuration)?,
           &site_name,
         )
         .await;
@@ -1051,7 +1169,9 @@ pub async fn process_cli(
           cli_get_session.get_one::<String>("status"),
           cli_get_session.get_one::<String>("name"),
           limit,
-          cli_get_session.get_one("output"),
+          get_output_format(cli_get_session)?,
+          &get_output_selection(cli_get_session)?,
+          cli_get_session.get_one::<u64>("watch").copied(),
         )
         .await;
       } else if let Some(cli_get_template) =
@@ -1061,9 +1181,7 @@ pub async fn process_cli(
 
         let hsm_group_name_arg_opt = cli_get_template.try_get_one("hsm-group");
 
-        let output: &String = cli_get_template
-          .get_one("output")
-          .expect("ERROR - output must be a valid value");
+        let output = get_output_format(cli_get_template)?;
 
         let target_hsm_group_vec = get_groups_available(
           &backend,
@@ -1099,6 +1217,7 @@ pub async fn process_cli(
           cli_get_template.get_one::<String>("name"),
           limit_number_opt,
           output,
+          &get_output_selection(cli_get_template)?,
         )
         .await;
       } else if let Some(cli_get_cluster) =
@@ -1161,7 +1280,9 @@ pub async fn process_cli(
           *nids_only,
           false,
           output,
+          &get_output_selection(cli_get_nodes)?,
           status,
+          cli_get_nodes.get_one::<u64>("watch").copied(),
         )
         .await;
       } else if let Some(cli_get_images) = cli_get.subcommand_matches("images")
@@ -1186,6 +1307,8 @@ pub async fn process_cli(
           &target_hsm_group_vec,
           cli_get_images.get_one::<String>("id"),
           cli_get_images.get_one::<u8>("limit"),
+          get_output_format(cli_get_images)?,
+          &get_output_selection(cli_get_images)?,
         )
         .await;
       } else if let Some(cli_get_boot_parameters) =
@@ -1208,7 +1331,10 @@ pub async fn process_cli(
           )
           .await?;
 
-        println!("{}", serde_json::to_string_pretty(&boot_parameters_vec)?);
+        crate::common::output::print(
+          &boot_parameters_vec,
+          get_output_format(cli_get_boot_parameters)?,
+        )?;
       } else if let Some(cli_get_kernel_parameters) =
         cli_get.subcommand_matches("kernel-parameters")
       {
@@ -1305,7 +1431,91 @@ pub async fn process_cli(
           )
           .await?;
 
-        println!("{}", serde_json::to_string_pretty(&redfish_endpoints)?);
+        let output_format = get_output_format(cli_get_redfish_endopints)?;
+
+        // Same JSON document as HSM returns, '{"RedfishEndpoints": [...]}'
+        if output_format == OutputFormat::Json {
+          println!("{}", serde_json::to_string_pretty(&redfish_endpoints)?);
+        } else {
+          crate::common::output::print(
+            &redfish_endpoints.redfish_endpoints.unwrap_or_default(),
+            output_format,
+          )?;
+        }
+      } else if let Some(cli_get_power_cap) =
+        cli_get.subcommand_matches("power-cap")
+      {
+        let shasta_token = backend.get_api_token(&site_name).await?;
+
+        let (cli_get_power_cap_target, xname_vec) =
+          if let Some(cli_get_power_cap_cluster) =
+            cli_get_power_cap.subcommand_matches("cluster")
+          {
+            let hsm_group_name_arg = cli_get_power_cap_cluster
+              .get_one::<String>("CLUSTER_NAME")
+              .expect("The 'cluster name' argument must have a value");
+
+            let target_hsm_group_vec = get_groups_available(
+              &backend,
+              &shasta_token,
+              Some(hsm_group_name_arg),
+              settings_hsm_group_name_opt,
+            )
+            .await?;
+
+            let xname_vec = pcs_utils::get_target_xname_vec(
+              &backend,
+              &shasta_token,
+              None,
+              target_hsm_group_vec.first().map(String::as_str),
+            )
+            .await?;
+
+            (cli_get_power_cap_cluster, xname_vec)
+          } else if let Some(cli_get_power_cap_nodes) =
+            cli_get_power_cap.subcommand_matches("nodes")
+          {
+            let xname_vec = pcs_utils::get_target_xname_vec(
+              &backend,
+              &shasta_token,
+              cli_get_power_cap_nodes
+                .get_one::<String>("VALUE")
+                .map(String::as_str),
+              None,
+            )
+            .await?;
+
+            (cli_get_power_cap_nodes, xname_vec)
+          } else {
+            unreachable!("'get power-cap' requires a subcommand")
+          };
+
+        get_power_cap::exec(
+          &backend,
+          &shasta_token,
+          &xname_vec,
+          get_output_format(cli_get_power_cap_target)?,
+        )
+        .await;
+      } else if let Some(cli_get_power_transition) =
+        cli_get.subcommand_matches("power-transition")
+      {
+        let shasta_token = backend.get_api_token(&site_name).await?;
+
+        let transition_id = cli_get_power_transition
+          .get_one::<String>("TRANSITION_ID")
+          .expect("The 'transition id' argument must have a value");
+
+        let wait: bool = cli_get_power_transition.get_flag("wait");
+
+        get_power_transition::exec(
+          &backend,
+          &shasta_token,
+          transition_id,
+          wait,
+          get_output_format(cli_get_power_transition)?,
+        )
+        .await?;
       }
     } else if let Some(cli_apply) = cli_root.subcommand_matches("apply") {
       if let Some(cli_apply_hw) = cli_apply.subcommand_matches("hardware") {
@@ -1350,6 +1560,20 @@ pub async fn process_cli(
             .get_one::<bool>("unpin-nodes")
             .unwrap_or(&false);
 
+          let failed_xname_vec =
+            if cli_apply_hw_cluster.get_flag("exclude-failed") {
+              apply_hw_cluster_pin::constraints::get_failed_xname_vec(
+                &backend,
+                &shasta_token,
+              )
+              .await?
+            } else {
+              Vec::new()
+            };
+
+          let constraints =
+            get_allocation_constraints(cli_apply_hw_cluster, failed_xname_vec)?;
+
           if *is_unpin {
             apply_hw_cluster_unpin::command::exec(
               &backend,
@@ -1360,6 +1584,7 @@ pub async fn process_cli(
               dryrun,
               create_target_hsm_group,
               delete_empty_parent_hsm_group,
+              &constraints,
             )
             .await;
           } else {
@@ -1372,6 +1597,7 @@ pub async fn process_cli(
               dryrun,
               create_target_hsm_group,
               delete_empty_parent_hsm_group,
+              &constraints,
             )
             .await;
           }
@@ -1455,7 +1681,7 @@ pub async fn process_cli(
           *cli_apply_session
             .get_one::<bool>("watch-logs")
             .unwrap_or(&false),
-          kafka_audit_opt,
+          auditor_opt,
           &site
             .k8s
             .as_ref()
@@ -1689,7 +1915,7 @@ pub async fn process_cli(
           nodes,
           assume_yes,
           do_not_reboot,
-          kafka_audit_opt,
+          auditor_opt,
         )
         .await;
 
@@ -1746,8 +1972,9 @@ pub async fn process_cli(
             hosts_string,
             assume_yes,
             do_not_reboot,
+            None,
             dry_run,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
 
@@ -1783,6 +2010,9 @@ pub async fn process_cli(
 
           let dry_run = cli_apply_boot_cluster.get_flag("dry-run");
 
+          let rolling_reboot_opt =
+            RollingReboot::from_arg_matches(cli_apply_boot_cluster);
+
           // Validate
           //
           // Check user has provided valid HSM group name
@@ -1810,11 +2040,251 @@ pub async fn process_cli(
             target_hsm_group_name,
             assume_yes,
             do_not_reboot,
+            rolling_reboot_opt.as_ref(),
             dry_run,
-            kafka_audit_opt,
+            auditor_opt,
           )
           .await;
         }
+      } else if let Some(cli_apply_power_cap) =
+        cli_apply.subcommand_matches("power-cap")
+      {
+        let shasta_token = backend.get_api_token(&site_name).await?;
+
+        let (cli_apply_power_cap_target, xname_vec) =
+          if let Some(cli_apply_power_cap_cluster) =
+            cli_apply_power_cap.subcommand_matches("cluster")
+          {
+            let hsm_group_name_arg = cli_apply_power_cap_cluster
+              .get_one::<String>("CLUSTER_NAME")
+              .expect("The 'cluster name' argument must have a value");
+
+            let target_hsm_group_vec = get_groups_available(
+              &backend,
+              &shasta_token,
+              Some(hsm_group_name_arg),
+              settings_hsm_group_name_opt,
+            )
+            .await?;
+
+            let xname_vec = pcs_utils::get_target_xname_vec(
+              &backend,
+              &shasta_token,
+              None,
+              target_hsm_group_vec.first().map(String::as_str),
+            )
+            .await?;
+
+            (cli_apply_power_cap_cluster, xname_vec)
+          } else if let Some(cli_apply_power_cap_nodes) =
+            cli_apply_power_cap.subcommand_matches("nodes")
+          {
+            let xname_vec = pcs_utils::get_target_xname_vec(
+              &backend,
+              &shasta_token,
+              cli_apply_power_cap_nodes
+                .get_one::<String>("VALUE")
+                .map(String::as_str),
+              None,
+            )
+            .await?;
+
+            (cli_apply_power_cap_nodes, xname_vec)
+          } else {
+            unreachable!("'apply power-cap' requires a subcommand")
+          };
+
+        apply_power_cap::exec(
+          &backend,
+          &shasta_token,
+          &xname_vec,
+          cli_apply_power_cap_target
+            .get_one::<String>("control")
+            .expect("The 'control' argument must have a value"),
+          *cli_apply_power_cap_target
+            .get_one::<usize>("watts")
+            .expect("The 'watts' argument must have a value"),
+          cli_apply_power_cap_target.get_flag("assume-yes"),
+          cli_apply_power_cap_target.get_flag("dry-run"),
+          get_output_format(cli_apply_power_cap_target)?,
+          auditor_opt,
+        )
+        .await;
+      }
+    } else if let Some(cli_plan) = cli_root.subcommand_matches("plan") {
+      if let Some(cli_plan_hw_cluster) =
+        cli_plan.subcommand_matches("hw-cluster")
+      {
+        let target_hsm_group_name = cli_plan_hw_cluster
+          .get_one::<String>("target-cluster")
+          .expect("The 'target-cluster' argument must have a value");
+
+        let parent_hsm_group_name = cli_plan_hw_cluster
+          .get_one::<String>("parent-cluster")
+          .expect("The 'parent-cluster' argument must have a value");
+
+        let inventory_file_opt =
+          cli_plan_hw_cluster.get_one::<PathBuf>("inventory-file");
+
+        // Offline plans use the hardware inventory snapshot only
+        let snapshot = if let Some(inventory_file) = inventory_file_opt {
+          HwInventorySnapshot::load(inventory_file)?
+        } else {
+          let shasta_token = backend.get_api_token(&site_name).await?;
+
+          let target_hsm_group_vec = get_groups_available(
+            &backend,
+            &shasta_token,
+            Some(target_hsm_group_name),
+            settings_hsm_group_name_opt,
+          )
+          .await?;
+
+          let parent_hsm_group_vec = get_groups_available(
+            &backend,
+            &shasta_token,
+            Some(parent_hsm_group_name),
+            settings_hsm_group_name_opt,
+          )
+          .await?;
+
+          HwInventorySnapshot::fetch(
+            &backend,
+            &shasta_token,
+            &site_name,
+            &[target_hsm_group_vec, parent_hsm_group_vec].concat(),
+            &[],
+          )
+          .await?
+        };
+
+        if let Some(save_inventory) =
+          cli_plan_hw_cluster.get_one::<PathBuf>("save-inventory")
+        {
+          snapshot.save(save_inventory)?;
+        }
+
+        // Nodes with failed components according to the snapshot
+        let failed_xname_vec = snapshot
+          .flags
+          .iter()
+          .filter(|(_, flag)| {
+            apply_hw_cluster_pin::constraints::is_failed_node_flag(flag)
+          })
+          .map(|(xname, _)| xname.clone())
+          .collect();
+
+        let constraints =
+          get_allocation_constraints(cli_plan_hw_cluster, failed_xname_vec)?;
+
+        plan_hw_cluster::exec(
+          &snapshot,
+          inventory_file_opt.map(PathBuf::as_path),
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          cli_plan_hw_cluster.get_one::<String>("pattern").unwrap(),
+          cli_plan_hw_cluster.get_flag("unpin-nodes"),
+          &constraints,
+          cli_plan_hw_cluster.get_one::<String>("output").unwrap(),
+        )
+        .await?;
+      }
+    } else if let Some(cli_diff) = cli_root.subcommand_matches("diff") {
+      if let Some(cli_diff_hardware) = cli_diff.subcommand_matches("hardware") {
+        let snapshot_before = HwInventorySnapshot::load(
+          cli_diff_hardware
+            .get_one::<PathBuf>("SNAPSHOT_BEFORE")
+            .expect("The 'SNAPSHOT_BEFORE' argument must have a value"),
+        )?;
+
+        let snapshot_after_arg = cli_diff_hardware
+          .get_one::<String>("SNAPSHOT_AFTER")
+          .expect("The 'SNAPSHOT_AFTER' argument must have a value");
+
+        let snapshot_after = if snapshot_after_arg == "live" {
+          if snapshot_before.site != site_name {
+            return Err(Box::new(Error::Message(format!(
+              "Hardware snapshot belongs to site '{}' but the current site is '{}'",
+              snapshot_before.site, site_name
+            ))));
+          }
+
+          let shasta_token = backend.get_api_token(&site_name).await?;
+
+          let mut hsm_group_vec = Vec::new();
+
+          for group_name in snapshot_before.groups.keys() {
+            hsm_group_vec.extend(
+              get_groups_available(
+                &backend,
+                &shasta_token,
+                Some(group_name),
+                settings_hsm_group_name_opt,
+              )
+              .await?,
+            );
+          }
+
+          // Nodes moved out of the clusters since the first snapshot are compared too
+          let xname_vec: Vec<String> =
+            snapshot_before.nodes.keys().cloned().collect();
+
+          let xname_vec =
+            validate_target_hsm_members(&backend, &shasta_token, &xname_vec)
+              .await;
+
+          HwInventorySnapshot::fetch(
+            &backend,
+            &shasta_token,
+            &site_name,
+            &hsm_group_vec,
+            &xname_vec,
+          )
+          .await?
+        } else {
+          HwInventorySnapshot::load(&PathBuf::from(snapshot_after_arg))?
+        };
+
+        commands::diff_hardware::exec(
+          &snapshot_before,
+          &snapshot_after,
+          cli_diff_hardware.get_one::<String>("output").unwrap(),
+        )?;
+      }
+    } else if let Some(cli_import) = cli_root.subcommand_matches("import") {
+      if let Some(cli_import_redfish_endpoints) =
+        cli_import.subcommand_matches("redfish-endpoints")
+      {
+        let shasta_token = backend.get_api_token(&site_name).await?;
+
+        import_redfish_endpoints::exec(
+          &backend,
+          &shasta_token,
+          cli_import_redfish_endpoints
+            .get_one::<PathBuf>("FILE")
+            .expect("The 'FILE' argument must have a value"),
+          cli_import_redfish_endpoints.get_flag("update-existing"),
+          cli_import_redfish_endpoints.get_flag("dry-run"),
+        )
+        .await?;
+      }
+    } else if let Some(cli_export) = cli_root.subcommand_matches("export") {
+      if let Some(cli_export_redfish_endpoints) =
+        cli_export.subcommand_matches("redfish-endpoints")
+      {
+        let shasta_token = backend.get_api_token(&site_name).await?;
+
+        export_redfish_endpoints::exec(
+          &backend,
+          &shasta_token,
+          cli_export_redfish_endpoints
+            .get_one::<String>("output")
+            .unwrap(),
+          cli_export_redfish_endpoints
+            .get_one::<PathBuf>("file")
+            .map(PathBuf::as_path),
+        )
+        .await?;
       }
     } else if let Some(cli_log) = cli_root.subcommand_matches("log") {
       let shasta_token = backend.get_api_token(&site_name).await?;
@@ -1969,7 +2439,7 @@ pub async fn process_cli(
           xnames_string,
           !dry_run,
           false,
-          kafka_audit_opt,
+          auditor_opt,
         )
         .await;
       } else if let Some(_cli_migrate_vcluster) =
@@ -2046,14 +2516,8 @@ pub async fn process_cli(
           .get_one("force")
           .expect("The 'force' argument must have a value");
 
-        delete_group::exec(
-          &backend,
-          &shasta_token,
-          label,
-          force,
-          kafka_audit_opt,
-        )
-        .await;
+        delete_group::exec(&backend, &shasta_token, label, force, auditor_opt)
+          .await;
       } else if let Some(cli_delete_node) =
         cli_delete.subcommand_matches("node")
       {
@@ -2219,7 +2683,7 @@ pub async fn process_cli(
           node_expression,
           assume_yes,
           do_not_reboot,
-          kafka_audit_opt,
+          auditor_opt,
         )
         .await;
 
@@ -2268,7 +2732,7 @@ pub async fn process_cli(
             session_name,
             dry_run,
             assume_yes,
-            kafka_audit_opt,
+            auditor_opt,
         )
         .await; */
       } else if let Some(cli_delete_configurations) =
@@ -2423,7 +2887,7 @@ pub async fn process_cli(
         target_hsm_name,
         hosts_expression,
         dryrun,
-        kafka_audit_opt,
+        auditor_opt,
       )
       .await;
     } else if let Some(cli_remove_nodes) =
@@ -2445,7 +2909,7 @@ pub async fn process_cli(
         target_hsm_name,
         nodes,
         dryrun,
-        kafka_audit_opt,
+        auditor_opt,
       )
       .await;
     } else if let Some(_) = cli_root.subcommand_matches("download-boot-image") {
@@ -2455,5 +2919,12 @@ pub async fn process_cli(
     }
   }
 
+  // Failed commands return or exit before getting here
+  if invalidate_cache {
+    if let Err(e) = cache::clear(Some(&site_name)) {
+      log::warn!("Could not invalidate response cache. Reason:\n{}", e);
+    }
+  }
+
   Ok(())
 }
@@ -1,14 +1,29 @@
-use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use manta_backend_dispatcher::audit::{AuditEvent, AuditOutcome, AuditUser};
 
-use super::kafka::Kafka;
+use super::jwt_ops;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Auditor {
-  pub kafka: Kafka,
-  // pub syslog: Option<Syslog>,
+/// User owning the authentication token, to be added to audit events
+pub fn get_user(token: &str) -> AuditUser {
+  AuditUser {
+    id: jwt_ops::get_preferred_username(token).unwrap_or_default(),
+    name: jwt_ops::get_name(token).unwrap_or_default(),
+  }
 }
 
-pub trait Audit {
-  async fn produce_message(&self, data: &[u8]) -> Result<()>;
+/// Marks the event as failed and adds the nodes the operation failed on to its message.
+/// The event is returned unchanged if no node failed
+pub fn with_failed_xnames(
+  event: AuditEvent,
+  failed_xname_vec: &[String],
+) -> AuditEvent {
+  if failed_xname_vec.is_empty() {
+    return event;
+  }
+
+  let message =
+    format!("{}, failed: {}", event.message, failed_xname_vec.join(", "));
+
+  event
+    .with_message(&message)
+    .with_outcome(AuditOutcome::Failure)
 }
@@ -0,0 +1,200 @@
+use std::{
+  fs,
+  future::Future,
+  path::{Path, PathBuf},
+  sync::OnceLock,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use directories::ProjectDirs;
+use manta_backend_dispatcher::error::Error;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// Response cache used by the backend dispatcher. Only set for commands that do not change
+/// the system and when the user did not pass '--no-cache'
+static RESPONSE_CACHE: OnceLock<ResponseCache> = OnceLock::new();
+
+/// On-disk cache of slow backend responses. Responses are stored per site under the XDG
+/// cache directory, one file per endpoint and query
+#[derive(Debug)]
+pub struct ResponseCache {
+  dir: PathBuf,
+  ttl: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+  endpoint: String,
+  created: u64,
+  response: Value,
+}
+
+impl ResponseCache {
+  pub fn new(site_name: &str, ttl_secs: u64) -> Result<Self, Error> {
+    Ok(Self::with_dir(get_cache_dir(site_name)?, ttl_secs))
+  }
+
+  /// Cache storing responses in `dir`
+  pub fn with_dir(dir: PathBuf, ttl_secs: u64) -> Self {
+    ResponseCache {
+      dir,
+      ttl: Duration::from_secs(ttl_secs),
+    }
+  }
+
+  fn get_entry_path(&self, key: &str) -> PathBuf {
+    self.dir.join(format!("{:x}.json", md5::compute(key)))
+  }
+
+  fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+    let entry: CacheEntry =
+      serde_json::from_slice(&fs::read(self.get_entry_path(key)).ok()?).ok()?;
+
+    let age = now().saturating_sub(entry.created);
+
+    if age >= self.ttl.as_secs() {
+      log::debug!("Cached response for '{}' expired", entry.endpoint);
+      return None;
+    }
+
+    log::debug!(
+      "Using cached response for '{}' ({}s old)",
+      entry.endpoint,
+      age
+    );
+
+    serde_json::from_value(entry.response).ok()
+  }
+
+  fn set<T: Serialize>(&self, key: &str, endpoint: &str, response: &T) {
+    let entry_rslt =
+      serde_json::to_value(response).map(|response| CacheEntry {
+        endpoint: endpoint.to_string(),
+        created: now(),
+        response,
+      });
+
+    let write_rslt = entry_rslt
+      .map_err(Error::from)
+      .and_then(|entry| Ok(serde_json::to_vec(&entry)?))
+      .and_then(|entry| {
+        fs::create_dir_all(&self.dir)?;
+        Ok(fs::write(self.get_entry_path(key), entry)?)
+      });
+
+    // A broken cache must never break a command
+    if let Err(e) = write_rslt {
+      log::warn!(
+        "Could not cache response for '{}'. Reason:\n{}",
+        endpoint,
+        e
+      );
+    }
+  }
+}
+
+/// Enables the response cache for the rest of the command
+pub fn init(response_cache: ResponseCache) {
+  log::debug!("Response cache enabled: {:?}", response_cache);
+
+  let _ = RESPONSE_CACHE.set(response_cache);
+}
+
+/// Returns the directory where responses of all sites are cached
+pub fn get_cache_root_dir() -> Result<PathBuf, Error> {
+  // XDG Base Directory Specification
+  let project_dirs = ProjectDirs::from(
+    "local", /*qualifier*/
+    "cscs",  /*organization*/
+    "manta", /*application*/
+  )
+  .ok_or_else(|| {
+    Error::Message(
+      "Could not find the cache directory, home directory of the user missing"
+        .to_string(),
+    )
+  })?;
+
+  Ok(project_dirs.cache_dir().join("responses"))
+}
+
+/// Returns the directory where responses for a site are cached
+pub fn get_cache_dir(site_name: &str) -> Result<PathBuf, Error> {
+  Ok(get_cache_root_dir()?.join(site_name))
+}
+
+/// Deletes the cached responses of a site, or all sites if `site_name_opt` is missing
+pub fn clear(site_name_opt: Option<&str>) -> Result<(), Error> {
+  let cache_dir = match site_name_opt {
+    Some(site_name) => get_cache_dir(site_name)?,
+    None => get_cache_root_dir()?,
+  };
+
+  remove_cache_dir(&cache_dir)
+}
+
+fn remove_cache_dir(cache_dir: &Path) -> Result<(), Error> {
+  if cache_dir.exists() {
+    log::info!("Deleting cache directory '{}'", cache_dir.display());
+    fs::remove_dir_all(cache_dir)?;
+  }
+
+  Ok(())
+}
+
+/// Returns the cached response for `endpoint` and `query` if the cache is enabled and the
+/// response did not expire, otherwise calls `fetch` and caches its response. The auth token
+/// is part of the key so users never see responses cached for someone else
+pub async fn get_or_fetch<T, Q, F, Fut>(
+  endpoint: &str,
+  auth_token: &str,
+  query: &Q,
+  fetch: F,
+) -> Result<T, Error>
+where
+  T: Serialize + DeserializeOwned,
+  Q: Serialize + ?Sized,
+  F: FnOnce() -> Fut,
+  Fut: Future<Output = Result<T, Error>>,
+{
+  let Some(response_cache) = RESPONSE_CACHE.get() else {
+    return fetch().await;
+  };
+
+  let key = get_key(endpoint, auth_token, query)?;
+
+  if let Some(response) = response_cache.get(&key) {
+    return Ok(response);
+  }
+
+  let response = fetch().await?;
+
+  response_cache.set(&key, endpoint, &response);
+
+  Ok(response)
+}
+
+/// Key of a cached response. The auth token is hashed so it is never stored in clear
+fn get_key<Q: Serialize + ?Sized>(
+  endpoint: &str,
+  auth_token: &str,
+  query: &Q,
+) -> Result<String, Error> {
+  Ok(format!(
+    "{:x}:{}?{}",
+    md5::compute(auth_token),
+    endpoint,
+    serde_json::to_string(query)?
+  ))
+}
+
+fn now() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_secs())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+use std::{fs, path::PathBuf};
+
+use serde_json::json;
+
+use super::{get_key, now, remove_cache_dir, CacheEntry, ResponseCache};
+
+fn temp_dir() -> PathBuf {
+  std::env::temp_dir().join(format!("manta-cache-{}", uuid::Uuid::new_v4()))
+}
+
+#[test]
+fn test_key_depends_on_token_endpoint_and_query() {
+  let key =
+    get_key("bss/bootparameters", "token-a", &["x1000c0s0b0n0"]).unwrap();
+
+  assert_eq!(
+    key,
+    get_key("bss/bootparameters", "token-a", &["x1000c0s0b0n0"]).unwrap()
+  );
+  assert_ne!(
+    key,
+    get_key("bss/bootparameters", "token-b", &["x1000c0s0b0n0"]).unwrap()
+  );
+  assert_ne!(
+    key,
+    get_key("bss/bootparameters", "token-a", &["x1000c0s0b0n1"]).unwrap()
+  );
+  assert_ne!(
+    key,
+    get_key("hsm/groups", "token-a", &["x1000c0s0b0n0"]).unwrap()
+  );
+
+  // The token is never stored in clear
+  assert!(!key.contains("token-a"));
+}
+
+#[test]
+fn test_cached_response_until_ttl_expires() {
+  let dir = temp_dir();
+  let response_cache = ResponseCache::with_dir(dir.clone(), 60);
+
+  assert_eq!(response_cache.get::<Vec<String>>("key"), None);
+
+  response_cache.set("key", "hsm/groups", &vec!["zinal".to_string()]);
+
+  assert_eq!(
+    response_cache.get::<Vec<String>>("key"),
+    Some(vec!["zinal".to_string()])
+  );
+
+  // Entry created longer than the TTL ago
+  let entry = CacheEntry {
+    endpoint: "hsm/groups".to_string(),
+    created: now() - 61,
+    response: json!(["zinal"]),
+  };
+
+  fs::write(
+    response_cache.get_entry_path("key"),
+    serde_json::to_vec(&entry).unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(response_cache.get::<Vec<String>>("key"), None);
+
+  fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_ttl_zero_never_returns_cached_responses() {
+  let dir = temp_dir();
+  let response_cache = ResponseCache::with_dir(dir.clone(), 0);
+
+  response_cache.set("key", "hsm/groups", &vec!["zinal".to_string()]);
+
+  assert_eq!(response_cache.get::<Vec<String>>("key"), None);
+
+  fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_broken_entry_ignored() {
+  let dir = temp_dir();
+  let response_cache = ResponseCache::with_dir(dir.clone(), 60);
+
+  fs::create_dir_all(&dir).unwrap();
+  fs::write(response_cache.get_entry_path("key"), "not json").unwrap();
+
+  assert_eq!(response_cache.get::<Vec<String>>("key"), None);
+
+  fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_clear() {
+  let dir = temp_dir();
+  let response_cache = ResponseCache::with_dir(dir.join("zinal"), 60);
+
+  response_cache.set("key", "hsm/groups", &vec!["zinal".to_string()]);
+
+  assert!(dir.join("zinal").exists());
+
+  remove_cache_dir(&dir).unwrap();
+
+  assert!(!dir.exists());
+  assert_eq!(response_cache.get::<Vec<String>>("key"), None);
+
+  // Clearing a cache that does not exist is not an error
+  remove_cache_dir(&dir).unwrap();
+}
@@ -115,14 +115,6 @@ pub fn check_cfs_session_against_groups_available(
   })
 }
 
-pub fn print_table_struct(
-  get_cfs_session_value_list: &Vec<types::cfs::session::CfsSessionGetResponse>,
-) {
-  let table = get_table_struct(get_cfs_session_value_list);
-
-  println!("{table}");
-}
-
 pub fn get_table_struct(
   get_cfs_session_value_list: &Vec<types::cfs::session::CfsSessionGetResponse>,
 ) -> Table {
@@ -11,16 +11,13 @@ use config::Config;
 use dialoguer::{Input, Select};
 use directories::ProjectDirs;
 use manta_backend_dispatcher::{
+  audit::{AuditSink, Auditor},
   error::Error,
-  types::{K8sAuth, K8sDetails},
+  types::{kafka::Kafka, K8sAuth, K8sDetails},
 };
 use types::{MantaConfiguration, Site};
 
-use crate::common::{
-  audit::Auditor,
-  check_network_connectivity::check_network_connectivity_to_backend,
-  kafka::Kafka,
-};
+use crate::common::check_network_connectivity::check_network_connectivity_to_backend;
 
 pub fn get_default_config_path() -> PathBuf {
   // XDG Base Directory Specification
@@ -271,15 +268,19 @@ pub async fn create_new_config_file(config_file_path_opt: Option<&PathBuf>) {
     "".to_string()
   };
 
-  // If both kafka broker and topic are empty, then auditor is None
+  // If kafka broker or topic are empty, then auditor is None. More
+  // sinks (file, syslog, webhook) can be added later in the configuration file
   let auditor =
-    if audit_kafka_brokers.is_empty() && audit_kafka_topic.is_empty() {
+    if !audit_kafka_brokers.is_empty() && !audit_kafka_topic.is_empty() {
       let kafka = Kafka {
         brokers: vec![audit_kafka_brokers],
         topic: audit_kafka_topic,
       };
 
-      Some(Auditor { kafka })
+      Some(Auditor {
+        sinks: vec![AuditSink::Kafka(kafka)],
+        ..Default::default()
+      })
     } else {
       None
     };
@@ -327,6 +328,7 @@ pub async fn create_new_config_file(config_file_path_opt: Option<&PathBuf>) {
     root_ca_cert_file,
     k8s: Some(k8s_details),
     backend,
+    mock_fixture_file: None,
   };
 
   let mut site_hashmap = HashMap::new();
@@ -339,6 +341,7 @@ pub async fn create_new_config_file(config_file_path_opt: Option<&PathBuf>) {
     audit_file,
     sites: site_hashmap,
     auditor,
+    cache: None,
   };
 
   let config_file_content = toml::to_string(&config_toml).unwrap();
@@ -1,8 +1,6 @@
 use std::collections::HashMap;
 
-use crate::common::audit::Auditor;
-
-use manta_backend_dispatcher::types::K8sDetails;
+use manta_backend_dispatcher::{audit::Auditor, types::K8sDetails};
 use serde::{Deserialize, Serialize};
 
 /* #[derive(Serialize, Deserialize, Debug)]
@@ -56,6 +54,15 @@ pub struct Site {
   pub vault_secret_path: Option<String>,
   // pub vault_role_id: Option<String>,
   pub root_ca_cert_file: String,
+  /// Fixture file seeding the state of the 'mock' backend
+  pub mock_fixture_file: Option<String>,
+}
+
+/// Response cache, see `common::cache`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Cache {
+  /// Seconds a cached response is valid
+  pub ttl: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -66,4 +73,5 @@ pub struct MantaConfiguration {
   pub audit_file: String,
   pub sites: HashMap<String, Site>,
   pub auditor: Option<Auditor>,
+  pub cache: Option<Cache>,
 }
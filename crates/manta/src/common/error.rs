@@ -0,0 +1,11 @@
+use manta_backend_dispatcher::error::Error;
+
+/// Prints an error returned while processing a command. Errors coming from the backend
+/// get a hint on how to solve them, if any (eg: log in again if the token expired)
+pub fn print_error(error: &(dyn std::error::Error + 'static)) {
+  eprintln!("{}", error);
+
+  if let Some(hint) = error.downcast_ref::<Error>().and_then(Error::hint) {
+    eprintln!("HINT - {}", hint);
+  }
+}
@@ -0,0 +1,564 @@
+use std::{fmt, str::FromStr};
+
+use manta_backend_dispatcher::{error::Error, types::NodeSummary};
+use regex::Regex;
+
+/// Node properties a filter expression can use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+  Xname,
+  ProcessorCount,
+  ProcessorModel,
+  AcceleratorCount,
+  AcceleratorModel,
+  MemoryCount,
+  MemoryTotal,
+  HsnNicCount,
+  HsnNicModel,
+}
+
+const FIELD_VEC: &[(&str, Field)] = &[
+  ("xname", Field::Xname),
+  ("processor.count", Field::ProcessorCount),
+  ("processor.model", Field::ProcessorModel),
+  ("accelerator.count", Field::AcceleratorCount),
+  ("accelerator.model", Field::AcceleratorModel),
+  ("memory.count", Field::MemoryCount),
+  ("memory.total", Field::MemoryTotal),
+  ("hsn_nic.count", Field::HsnNicCount),
+  ("hsn_nic.model", Field::HsnNicModel),
+];
+
+impl Field {
+  fn is_numeric(&self) -> bool {
+    !matches!(
+      self,
+      Field::Xname
+        | Field::ProcessorModel
+        | Field::AcceleratorModel
+        | Field::HsnNicModel
+    )
+  }
+
+  fn get_text_vec(&self, node_summary: &NodeSummary) -> Vec<String> {
+    let artifact_vec = match self {
+      Field::Xname => return vec![node_summary.xname.clone()],
+      Field::ProcessorModel => &node_summary.processors,
+      Field::AcceleratorModel => &node_summary.node_accels,
+      Field::HsnNicModel => &node_summary.node_hsn_nics,
+      _ => return Vec::new(),
+    };
+
+    artifact_vec
+      .iter()
+      .filter_map(|artifact| artifact.info.clone())
+      .collect()
+  }
+
+  fn get_number(&self, node_summary: &NodeSummary) -> f64 {
+    match self {
+      Field::ProcessorCount => node_summary.processors.len() as f64,
+      Field::AcceleratorCount => node_summary.node_accels.len() as f64,
+      Field::MemoryCount => node_summary.memory.len() as f64,
+      Field::HsnNicCount => node_summary.node_hsn_nics.len() as f64,
+      // Memory capacity is like '16384 MiB'
+      Field::MemoryTotal => node_summary
+        .memory
+        .iter()
+        .filter_map(|memory| {
+          memory
+            .info
+            .as_ref()
+            .and_then(|info| info.split(' ').next())
+            .and_then(|capacity_mib| capacity_mib.parse::<f64>().ok())
+        })
+        .map(|capacity_mib| capacity_mib * 1024.0 * 1024.0)
+        .sum(),
+      _ => 0.0,
+    }
+  }
+}
+
+impl FromStr for Field {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    FIELD_VEC
+      .iter()
+      .find(|(name, _)| name.eq_ignore_ascii_case(value))
+      .map(|(_, field)| *field)
+      .ok_or_else(|| {
+        Error::Message(format!(
+          "Field '{}' not valid. Valid fields are: {}",
+          value,
+          FIELD_VEC
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ")
+        ))
+      })
+  }
+}
+
+impl fmt::Display for Field {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = FIELD_VEC
+      .iter()
+      .find(|(_, field)| field == self)
+      .map(|(name, _)| *name)
+      .unwrap_or_default();
+
+    write!(f, "{}", name)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+  Eq,
+  Ne,
+  Lt,
+  Le,
+  Gt,
+  Ge,
+  Match,
+  NotMatch,
+}
+
+impl fmt::Display for CompareOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let op = match self {
+      CompareOp::Eq => "==",
+      CompareOp::Ne => "!=",
+      CompareOp::Lt => "<",
+      CompareOp::Le => "<=",
+      CompareOp::Gt => ">",
+      CompareOp::Ge => ">=",
+      CompareOp::Match => "~",
+      CompareOp::NotMatch => "!~",
+    };
+
+    write!(f, "{}", op)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Ident(String),
+  Text(String),
+  /// Number and whether it had a size unit
+  Number(f64, bool),
+  Op(CompareOp),
+  And,
+  Or,
+  Not,
+  LParen,
+  RParen,
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+  Text(String),
+  Regex(Regex),
+  Number(f64),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+  And(Box<Expr>, Box<Expr>),
+  Or(Box<Expr>, Box<Expr>),
+  Not(Box<Expr>),
+  Compare {
+    field: Field,
+    op: CompareOp,
+    operand: Operand,
+  },
+}
+
+impl Expr {
+  fn matches(&self, node_summary: &NodeSummary) -> bool {
+    match self {
+      Expr::And(left, right) => {
+        left.matches(node_summary) && right.matches(node_summary)
+      }
+      Expr::Or(left, right) => {
+        left.matches(node_summary) || right.matches(node_summary)
+      }
+      Expr::Not(expr) => !expr.matches(node_summary),
+      Expr::Compare { field, op, operand } => match operand {
+        Operand::Number(number) => {
+          let value = field.get_number(node_summary);
+
+          match op {
+            CompareOp::Eq => value == *number,
+            CompareOp::Ne => value != *number,
+            CompareOp::Lt => value < *number,
+            CompareOp::Le => value <= *number,
+            CompareOp::Gt => value > *number,
+            CompareOp::Ge => value >= *number,
+            CompareOp::Match | CompareOp::NotMatch => false,
+          }
+        }
+        Operand::Text(text) => {
+          let any_equal = field
+            .get_text_vec(node_summary)
+            .iter()
+            .any(|value| value == text);
+
+          match op {
+            CompareOp::Eq => any_equal,
+            _ => !any_equal,
+          }
+        }
+        Operand::Regex(regex) => {
+          let any_match = field
+            .get_text_vec(node_summary)
+            .iter()
+            .any(|value| regex.is_match(value));
+
+          match op {
+            CompareOp::Match => any_match,
+            _ => !any_match,
+          }
+        }
+      },
+    }
+  }
+}
+
+/// Filter expression evaluated against the hardware inventory of a node, eg:
+/// `accelerator.model ~ "A100" and memory.total >= 512GiB and processor.count == 2`
+///
+/// Comparisons can be combined with `and`, `or`, `not` and parentheses. Text fields
+/// (`xname`, `*.model`) support `==`, `!=`, `~` (regex match) and `!~`, and match if any of
+/// the node components matches. Numeric fields (`*.count`, `memory.total`) support `==`,
+/// `!=`, `<`, `<=`, `>` and `>=`. `memory.total` is in bytes and accepts the units B, KiB,
+/// MiB, GiB and TiB. Fields, operators and values are validated when parsing so evaluating
+/// the expression against a node can't fail
+#[derive(Debug, Clone)]
+pub struct HwInventoryQuery {
+  expr: Expr,
+}
+
+impl HwInventoryQuery {
+  /// True if the hardware inventory of the node fulfills the expression
+  pub fn matches(&self, node_summary: &NodeSummary) -> bool {
+    self.expr.matches(node_summary)
+  }
+}
+
+impl FromStr for HwInventoryQuery {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let token_vec = tokenize(value)?;
+
+    let mut parser = Parser {
+      token_vec,
+      position: 0,
+    };
+
+    let expr = parser.parse_or()?;
+
+    if let Some(token) = parser.peek() {
+      return Err(Error::Message(format!(
+        "Filter expression not valid, unexpected {:?} after the end of the expression",
+        token
+      )));
+    }
+
+    Ok(Self { expr })
+  }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, Error> {
+  let char_vec: Vec<char> = expression.chars().collect();
+
+  let mut token_vec = Vec::new();
+  let mut i = 0;
+
+  while i < char_vec.len() {
+    let c = char_vec[i];
+    let next_opt = char_vec.get(i + 1).copied();
+
+    match c {
+      _ if c.is_whitespace() => i += 1,
+      '(' => {
+        token_vec.push(Token::LParen);
+        i += 1;
+      }
+      ')' => {
+        token_vec.push(Token::RParen);
+        i += 1;
+      }
+      '~' => {
+        token_vec.push(Token::Op(CompareOp::Match));
+        i += 1;
+      }
+      '=' | '!' | '<' | '>' => {
+        let (op, len) = match (c, next_opt) {
+          ('=', Some('=')) => (CompareOp::Eq, 2),
+          ('!', Some('=')) => (CompareOp::Ne, 2),
+          ('!', Some('~')) => (CompareOp::NotMatch, 2),
+          ('<', Some('=')) => (CompareOp::Le, 2),
+          ('>', Some('=')) => (CompareOp::Ge, 2),
+          ('<', _) => (CompareOp::Lt, 1),
+          ('>', _) => (CompareOp::Gt, 1),
+          _ => {
+            return Err(Error::Message(format!(
+              "Filter expression not valid, unknown operator at position {}",
+              i
+            )))
+          }
+        };
+
+        token_vec.push(Token::Op(op));
+        i += len;
+      }
+      '"' | '\'' => {
+        let mut text = String::new();
+        i += 1;
+
+        loop {
+          match char_vec.get(i) {
+            Some('\\') if char_vec.get(i + 1).is_some() => {
+              text.push(char_vec[i + 1]);
+              i += 2;
+            }
+            Some(quote) if *quote == c => {
+              i += 1;
+              break;
+            }
+            Some(text_char) => {
+              text.push(*text_char);
+              i += 1;
+            }
+            None => {
+              return Err(Error::Message(
+                "Filter expression not valid, missing closing quote"
+                  .to_string(),
+              ))
+            }
+          }
+        }
+
+        token_vec.push(Token::Text(text));
+      }
+      _ if c.is_ascii_digit() => {
+        let start = i;
+
+        while char_vec
+          .get(i)
+          .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+        {
+          i += 1;
+        }
+
+        let number_str: String = char_vec[start..i].iter().collect();
+
+        let number = number_str.parse::<f64>().map_err(|_| {
+          Error::Message(format!(
+            "Filter expression not valid, '{}' is not a number",
+            number_str
+          ))
+        })?;
+
+        let unit_start = i;
+
+        while char_vec.get(i).is_some_and(|c| c.is_ascii_alphabetic()) {
+          i += 1;
+        }
+
+        let unit: String = char_vec[unit_start..i].iter().collect();
+
+        let multiplier: f64 = match unit.as_str() {
+          "" => 1.0,
+          "B" => 1.0,
+          "KiB" => 1024.0,
+          "MiB" => 1024.0 * 1024.0,
+          "GiB" => 1024.0 * 1024.0 * 1024.0,
+          "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+          _ => {
+            return Err(Error::Message(format!(
+              "Filter expression not valid, unit '{}' unknown. Valid units are B, KiB, MiB, GiB and TiB",
+              unit
+            )))
+          }
+        };
+
+        token_vec.push(Token::Number(number * multiplier, !unit.is_empty()));
+      }
+      _ if c.is_ascii_alphabetic() || c == '_' => {
+        let start = i;
+
+        while char_vec
+          .get(i)
+          .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.')
+        {
+          i += 1;
+        }
+
+        let ident: String = char_vec[start..i].iter().collect();
+
+        let token = match ident.to_lowercase().as_str() {
+          "and" => Token::And,
+          "or" => Token::Or,
+          "not" => Token::Not,
+          _ => Token::Ident(ident),
+        };
+
+        token_vec.push(token);
+      }
+      _ => {
+        return Err(Error::Message(format!(
+          "Filter expression not valid, unexpected '{}' at position {}",
+          c, i
+        )))
+      }
+    }
+  }
+
+  Ok(token_vec)
+}
+
+/// Recursive descent parser, 'not' binds tighter than 'and', and 'and' tighter than 'or'
+struct Parser {
+  token_vec: Vec<Token>,
+  position: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.token_vec.get(self.position)
+  }
+
+  fn next_token(&mut self) -> Option<Token> {
+    let token_opt = self.token_vec.get(self.position).cloned();
+    self.position += 1;
+    token_opt
+  }
+
+  fn parse_or(&mut self) -> Result<Expr, Error> {
+    let mut expr = self.parse_and()?;
+
+    while self.peek() == Some(&Token::Or) {
+      self.position += 1;
+      expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+    }
+
+    Ok(expr)
+  }
+
+  fn parse_and(&mut self) -> Result<Expr, Error> {
+    let mut expr = self.parse_not()?;
+
+    while self.peek() == Some(&Token::And) {
+      self.position += 1;
+      expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+    }
+
+    Ok(expr)
+  }
+
+  fn parse_not(&mut self) -> Result<Expr, Error> {
+    if self.peek() == Some(&Token::Not) {
+      self.position += 1;
+      return Ok(Expr::Not(Box::new(self.parse_not()?)));
+    }
+
+    self.parse_primary()
+  }
+
+  fn parse_primary(&mut self) -> Result<Expr, Error> {
+    match self.next_token() {
+      Some(Token::LParen) => {
+        let expr = self.parse_or()?;
+
+        match self.next_token() {
+          Some(Token::RParen) => Ok(expr),
+          _ => Err(Error::Message(
+            "Filter expression not valid, missing closing parenthesis"
+              .to_string(),
+          )),
+        }
+      }
+      Some(Token::Ident(ident)) => self.parse_comparison(&ident),
+      Some(token) => Err(Error::Message(format!(
+        "Filter expression not valid, expected a field or '(' but found {:?}",
+        token
+      ))),
+      None => Err(Error::Message(
+        "Filter expression not valid, unexpected end of the expression"
+          .to_string(),
+      )),
+    }
+  }
+
+  fn parse_comparison(&mut self, ident: &str) -> Result<Expr, Error> {
+    let field = ident.parse::<Field>()?;
+
+    let op = match self.next_token() {
+      Some(Token::Op(op)) => op,
+      _ => {
+        return Err(Error::Message(format!(
+          "Filter expression not valid, expected an operator after '{}'",
+          field
+        )))
+      }
+    };
+
+    let operand = match (self.next_token(), field.is_numeric()) {
+      (Some(Token::Number(number, has_unit)), true) => {
+        if has_unit && field != Field::MemoryTotal {
+          return Err(Error::Message(format!(
+            "Filter expression not valid, units only apply to '{}'",
+            Field::MemoryTotal
+          )));
+        }
+
+        if matches!(op, CompareOp::Match | CompareOp::NotMatch) {
+          return Err(Error::Message(format!(
+            "Filter expression not valid, '{}' can't be used with numeric field '{}'",
+            op, field
+          )));
+        }
+
+        Operand::Number(number)
+      }
+      (Some(Token::Text(text)), false) => match op {
+        CompareOp::Eq | CompareOp::Ne => Operand::Text(text),
+        CompareOp::Match | CompareOp::NotMatch => {
+          Operand::Regex(Regex::new(&text).map_err(|e| {
+            Error::Message(format!(
+              "Filter expression not valid, regex '{}' not valid. Reason:\n{}",
+              text, e
+            ))
+          })?)
+        }
+        _ => {
+          return Err(Error::Message(format!(
+            "Filter expression not valid, '{}' can't be used with text field '{}'",
+            op, field
+          )))
+        }
+      },
+      (_, true) => {
+        return Err(Error::Message(format!(
+          "Filter expression not valid, '{}' must be compared with a number",
+          field
+        )))
+      }
+      (_, false) => {
+        return Err(Error::Message(format!(
+          "Filter expression not valid, '{}' must be compared with a quoted text",
+          field
+        )))
+      }
+    };
+
+    Ok(Expr::Compare { field, op, operand })
+  }
+}
+
+#[cfg(test)]
+mod tests;
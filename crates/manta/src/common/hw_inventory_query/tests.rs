@@ -0,0 +1,196 @@
+use manta_backend_dispatcher::types::{
+  ArtifactSummary, ArtifactType, NodeSummary,
+};
+
+use super::HwInventoryQuery;
+
+fn artifact(xname: &str, r#type: ArtifactType, info: &str) -> ArtifactSummary {
+  ArtifactSummary {
+    xname: xname.to_string(),
+    r#type,
+    info: Some(info.to_string()),
+    fru_id: None,
+    serial_number: None,
+  }
+}
+
+/// Node with 2 'AMD EPYC 7742' processors, 4 'NVIDIA A100' accelerators and 4 DIMMs of
+/// 16GiB
+fn node_summary() -> NodeSummary {
+  NodeSummary {
+    xname: "x1000c0s0b0n0".to_string(),
+    r#type: "Node".to_string(),
+    processors: (0..2)
+      .map(|i| {
+        artifact(
+          &format!("x1000c0s0b0n0p{}", i),
+          ArtifactType::Processor,
+          "AMD EPYC 7742",
+        )
+      })
+      .collect(),
+    memory: (0..4)
+      .map(|i| {
+        artifact(
+          &format!("x1000c0s0b0n0d{}", i),
+          ArtifactType::Memory,
+          "16384 MiB",
+        )
+      })
+      .collect(),
+    node_accels: (0..4)
+      .map(|i| {
+        artifact(
+          &format!("x1000c0s0b0n0a{}", i),
+          ArtifactType::NodeAccel,
+          "NVIDIA A100",
+        )
+      })
+      .collect(),
+    node_hsn_nics: Vec::new(),
+  }
+}
+
+fn matches(expression: &str) -> bool {
+  expression
+    .parse::<HwInventoryQuery>()
+    .unwrap()
+    .matches(&node_summary())
+}
+
+#[test]
+fn test_numeric_compare_ops() {
+  for expression in [
+    "processor.count == 2",
+    "processor.count != 3",
+    "processor.count < 3",
+    "processor.count <= 2",
+    "processor.count > 1",
+    "processor.count >= 2",
+  ] {
+    assert!(matches(expression), "'{}' should match", expression);
+  }
+
+  for expression in [
+    "processor.count == 3",
+    "processor.count != 2",
+    "processor.count < 2",
+    "processor.count <= 1",
+    "processor.count > 2",
+    "processor.count >= 3",
+  ] {
+    assert!(!matches(expression), "'{}' should not match", expression);
+  }
+}
+
+#[test]
+fn test_text_compare_ops() {
+  for expression in [
+    "processor.model == \"AMD EPYC 7742\"",
+    "processor.model != \"AMD EPYC 7763\"",
+    "accelerator.model ~ \"A100\"",
+    "accelerator.model !~ \"H100\"",
+    "xname ~ '^x1000c0'",
+  ] {
+    assert!(matches(expression), "'{}' should match", expression);
+  }
+
+  for expression in [
+    "processor.model == \"AMD EPYC\"",
+    "processor.model != \"AMD EPYC 7742\"",
+    "accelerator.model ~ \"H100\"",
+    "accelerator.model !~ \"A100\"",
+    "hsn_nic.model ~ \".*\"",
+  ] {
+    assert!(!matches(expression), "'{}' should not match", expression);
+  }
+}
+
+#[test]
+fn test_memory_total_with_and_without_units() {
+  // 4 DIMMs of 16GiB
+  for expression in [
+    "memory.total == 64GiB",
+    "memory.total == 65536MiB",
+    "memory.total == 67108864KiB",
+    "memory.total == 68719476736B",
+    "memory.total == 68719476736",
+    "memory.total < 1TiB",
+    "memory.total >= 0.0625TiB",
+    "memory.count == 4",
+  ] {
+    assert!(matches(expression), "'{}' should match", expression);
+  }
+
+  assert!(!matches("memory.total > 64GiB"));
+  assert!(!matches("memory.total == 64"));
+}
+
+#[test]
+fn test_and_binds_tighter_than_or() {
+  // true or (false and false)
+  assert!(matches(
+    "processor.count == 2 or accelerator.count == 8 and memory.count == 8"
+  ));
+
+  // (true or false) and false
+  assert!(!matches(
+    "(processor.count == 2 or accelerator.count == 8) and memory.count == 8"
+  ));
+}
+
+#[test]
+fn test_not_binds_tighter_than_and() {
+  // (not true) and true
+  assert!(!matches("not processor.count == 2 and memory.count == 4"));
+
+  // not (false and true)
+  assert!(matches("not (processor.count == 3 and memory.count == 4)"));
+
+  assert!(matches("not not processor.count == 2"));
+}
+
+#[test]
+fn test_nested_parentheses() {
+  assert!(matches(
+    "((xname == 'x1000c0s0b0n1' or xname == 'x1000c0s0b0n0') and (accelerator.count >= 4))"
+  ));
+}
+
+#[test]
+fn test_keywords_and_fields_case_insensitive() {
+  assert!(matches(
+    "Processor.Count == 2 AND NOT accelerator.count == 0"
+  ));
+}
+
+#[test]
+fn test_syntax_errors() {
+  for expression in [
+    "",
+    "xname",
+    "xname ==",
+    "xname = 'x1000c0s0b0n0'",
+    "xname == x1000c0s0b0n0",
+    "xname == 'x1000c0s0b0n0",
+    "foo == 1",
+    "processor.count == '2'",
+    "processor.count ~ 2",
+    "processor.model < 'AMD'",
+    "processor.count == 2GiB",
+    "memory.total == 2PiB",
+    "accelerator.model ~ '['",
+    "(processor.count == 2",
+    "processor.count == 2)",
+    "processor.count == 2 and",
+    "processor.count == 2 memory.count == 4",
+    "not",
+    "processor.count == 2 # comment",
+  ] {
+    assert!(
+      expression.parse::<HwInventoryQuery>().is_err(),
+      "'{}' should not be valid",
+      expression
+    );
+  }
+}
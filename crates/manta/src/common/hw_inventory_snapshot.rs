@@ -0,0 +1,184 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  fs::File,
+  io::BufReader,
+  path::Path,
+  sync::Arc,
+};
+
+use chrono::Local;
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::hsm::{group::GroupTrait, hardware_inventory::HardwareInventory},
+  types::NodeSummary,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Semaphore;
+
+use crate::{
+  common::node_ops::get_node_flag_map,
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Hardware inventory of the members of a list of groups saved to a file, so hardware
+/// changes can be planned without access to the system and hardware interventions can be
+/// compared across time with 'diff hardware'
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct HwInventorySnapshot {
+  pub site: String,
+  pub created: String,
+  /// Members of each group
+  pub groups: BTreeMap<String, Vec<String>>,
+  /// HSM hardware inventory of each node, as returned by the backend
+  pub nodes: BTreeMap<String, Value>,
+  /// HSM flag of each node, eg 'OK' or 'Alert'
+  #[serde(default)]
+  pub flags: BTreeMap<String, String>,
+}
+
+impl HwInventorySnapshot {
+  /// Gets the members of the groups and the hardware inventory of each of them, plus the
+  /// nodes in 'xname_vec', from the backend
+  pub async fn fetch(
+    backend: &StaticBackendDispatcher,
+    shasta_token: &str,
+    site: &str,
+    group_name_vec: &[String],
+    xname_vec: &[String],
+  ) -> Result<Self, Error> {
+    let mut groups = BTreeMap::new();
+
+    for group_name in group_name_vec {
+      let member_vec = backend
+        .get_member_vec_from_group_name_vec(
+          shasta_token,
+          vec![group_name.to_string()],
+        )
+        .await?;
+
+      groups.insert(group_name.to_string(), member_vec);
+    }
+
+    let xname_set: BTreeSet<String> = groups
+      .values()
+      .flatten()
+      .chain(xname_vec)
+      .cloned()
+      .collect();
+
+    let mut tasks = tokio::task::JoinSet::new();
+
+    let sem = Arc::new(Semaphore::new(5));
+
+    for xname in xname_set {
+      let backend = backend.clone();
+      let shasta_token = shasta_token.to_string();
+
+      let permit = Arc::clone(&sem).acquire_owned().await;
+
+      tasks.spawn(async move {
+        let _permit = permit;
+
+        let node_hw_inventory_rslt = backend
+          .get_inventory_hardware_query(
+            &shasta_token,
+            &xname,
+            None,
+            None,
+            None,
+            None,
+            None,
+          )
+          .await;
+
+        (xname, node_hw_inventory_rslt)
+      });
+    }
+
+    let mut nodes = BTreeMap::new();
+
+    while let Some(message) = tasks.join_next().await {
+      let (xname, node_hw_inventory_rslt) = message.map_err(|e| {
+        Error::Message(format!(
+          "Failed fetching node hardware information: {e}"
+        ))
+      })?;
+
+      nodes.insert(xname, node_hw_inventory_rslt?);
+    }
+
+    // Not all backends provide the flags
+    let flags = match get_node_flag_map(backend, shasta_token).await {
+      Ok(node_flag_map) => node_flag_map
+        .into_iter()
+        .filter(|(xname, _)| nodes.contains_key(xname))
+        .collect(),
+      Err(e) if e.is_not_implemented() => BTreeMap::new(),
+      Err(e) => return Err(e),
+    };
+
+    Ok(Self {
+      site: site.to_string(),
+      created: Local::now().to_rfc3339(),
+      groups,
+      nodes,
+      flags,
+    })
+  }
+
+  pub fn load(path: &Path) -> Result<Self, Error> {
+    let file = File::open(path).map_err(|e| {
+      Error::Message(format!(
+        "Could not open hardware snapshot '{}'. Reason:\n{}",
+        path.display(),
+        e
+      ))
+    })?;
+
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+      Error::Message(format!(
+        "Hardware snapshot '{}' not valid. Reason:\n{}",
+        path.display(),
+        e
+      ))
+    })
+  }
+
+  pub fn save(&self, path: &Path) -> Result<(), Error> {
+    let file = File::create(path)?;
+
+    serde_json::to_writer_pretty(file, self)?;
+
+    Ok(())
+  }
+
+  /// Hardware components of each node, nodes without hardware inventory have no
+  /// components
+  pub fn get_node_summary_vec(&self) -> Vec<NodeSummary> {
+    self
+      .nodes
+      .iter()
+      .map(|(xname, node_hw_inventory)| {
+        match node_hw_inventory.pointer("/Nodes/0") {
+          Some(node_hw_inventory) => {
+            NodeSummary::from_csm_value(node_hw_inventory.clone())
+          }
+          None => NodeSummary {
+            xname: xname.clone(),
+            ..Default::default()
+          },
+        }
+      })
+      .collect()
+  }
+
+  /// Members of a group, empty if the group is not in the snapshot
+  pub fn get_members(&self, group_name: &str) -> &[String] {
+    self
+      .groups
+      .get(group_name)
+      .map(Vec::as_slice)
+      .unwrap_or_default()
+  }
+}
@@ -30,7 +30,7 @@ fn get_claims_from_jwt_token(token: &str) -> Result<Value, Error> {
 }
 
 pub fn get_name(token: &str) -> Result<String, Error> {
-  let jwt_claims = get_claims_from_jwt_token(token).unwrap();
+  let jwt_claims = get_claims_from_jwt_token(token)?;
 
   let jwt_name = jwt_claims["name"].as_str();
 
@@ -41,7 +41,7 @@ pub fn get_name(token: &str) -> Result<String, Error> {
 }
 
 pub fn get_preferred_username(token: &str) -> Result<String, Error> {
-  let jwt_claims = get_claims_from_jwt_token(token).unwrap();
+  let jwt_claims = get_claims_from_jwt_token(token)?;
 
   let jwt_preferred_username = jwt_claims["preferred_username"].as_str();
 
@@ -1,19 +1,25 @@
 pub mod audit;
 pub mod authorization;
-pub mod bos_sessiontemplate_utils;
+pub mod cache;
 pub mod cfs_configuration_utils;
 pub mod cfs_session_utils;
 pub mod check_network_connectivity;
 pub mod config;
+pub mod error;
 pub mod hooks;
+pub mod hw_inventory_query;
+pub mod hw_inventory_snapshot;
 pub mod hw_inventory_utils;
 pub mod ims_ops;
 pub mod jwt_ops;
-pub mod kafka;
 pub mod kernel_parameters_ops;
 pub mod local_git_repo;
 pub mod log_ops;
 pub mod node_ops;
+pub mod output;
 pub mod pcs_utils;
+pub mod redfish_endpoint_file;
+pub mod rolling_reboot;
 pub mod terminal_ops;
 pub mod vault;
+pub mod watch;
@@ -1,10 +1,15 @@
-use std::collections::HashMap;
+use std::{
+  collections::{BTreeMap, HashMap},
+  sync::LazyLock,
+};
 
 use comfy_table::{Cell, Table};
 use csm_rs::node::types::NodeDetails;
 use hostlist_parser::parse;
 use manta_backend_dispatcher::{
-  error::Error, interfaces::hsm::group::GroupTrait, types::Component,
+  error::Error,
+  interfaces::hsm::{component::ComponentTrait, group::GroupTrait},
+  types::Component,
 };
 use regex::Regex;
 
@@ -300,112 +305,99 @@ pub fn validate_xname_format(xname: &str) -> bool {
   xname_re.is_match(xname)
 }
 
-pub fn print_table(nodes_status: Vec<NodeDetails>) {
-  let mut table = Table::new();
-
-  table.set_header(vec![
-    "XNAME",
-    "NID",
-    "HSM",
-    "Power Status",
-    "Runtime Configuration",
-    "Configuration Status",
-    "Enabled",
-    "Error Count",
-    "Image Configuration",
-    "Image ID",
-  ]);
-
-  for node_status in nodes_status {
-    let mut node_vec: Vec<String> = node_status
-      .hsm
-      .split(",")
-      .map(|xname_str| xname_str.trim().to_string())
-      .collect();
-    node_vec.sort();
-
-    table.add_row(vec![
-      Cell::new(node_status.xname),
-      Cell::new(node_status.nid),
-      Cell::new(nodes_to_string_format_discrete_columns(Some(&node_vec), 1)),
-      Cell::new(node_status.power_status),
-      Cell::new(node_status.desired_configuration),
-      Cell::new(node_status.configuration_status),
-      Cell::new(node_status.enabled),
-      Cell::new(node_status.error_count),
-      Cell::new(node_status.boot_configuration),
-      Cell::new(node_status.boot_image_id),
-    ]);
-  }
-
-  println!("{table}");
+static BMC_XNAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(
+    r"^x\d{4}(c[0-7](s([0-9]|[1-5][0-9]|6[0-4])b[0-1]|r[0-7]b0|b0)|m[0-3])$",
+  )
+  .unwrap()
+});
+
+/// Validate xname is a controller with a redfish endpoint: node BMC (x1000c0s0b0), router
+/// BMC (x1000c0r0b0), chassis BMC (x1000c0b0) or cabinet PDU controller (x1000m0)
+pub fn validate_bmc_xname_format(xname: &str) -> bool {
+  BMC_XNAME_RE.is_match(xname)
 }
 
-pub fn print_table_wide(nodes_status: Vec<NodeDetails>) {
-  let mut table = Table::new();
-
-  table.set_header(vec![
-    "XNAME",
-    "NID",
-    "HSM",
-    "Power Status",
-    "Runtime Configuration",
-    "Configuration Status",
-    "Enabled",
-    "Error Count",
-    "Image Configuration",
-    "Image ID",
-    "Kernel Params",
-  ]);
-
-  for node_status in nodes_status {
-    let kernel_params_vec: Vec<&str> =
-      node_status.kernel_params.split_whitespace().collect();
-    let cell_max_width = kernel_params_vec
-      .iter()
-      .map(|value| value.len())
-      .max()
-      .unwrap_or(0);
+static NODE_LOCATION_RE: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"^x(\d+)c(\d+)s(\d+)b(\d+)n(\d+)$").unwrap());
+
+/// Location of a node in the system, parsed from its xname
+/// (x<cabinet>c<chassis>s<slot>b<bmc>n<node>)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeLocation {
+  pub cabinet: u32,
+  pub chassis: u32,
+  pub slot: u32,
+  pub bmc: u32,
+  pub node: u32,
+}
 
-    let mut kernel_params_string: String = kernel_params_vec[0].to_string();
-    let mut cell_width = kernel_params_string.len();
+impl NodeLocation {
+  /// Returns None if the xname is not a node xname
+  pub fn from_xname(xname: &str) -> Option<Self> {
+    let captures = NODE_LOCATION_RE.captures(xname)?;
 
-    for kernel_param in kernel_params_vec.iter().skip(1) {
-      cell_width += kernel_param.len();
+    let get_number = |index: usize| captures[index].parse::<u32>().ok();
 
-      if cell_width + kernel_param.len() >= cell_max_width {
-        kernel_params_string.push_str("\n");
-        cell_width = 0;
-      } else {
-        kernel_params_string.push_str(" ");
-      }
+    Some(Self {
+      cabinet: get_number(1)?,
+      chassis: get_number(2)?,
+      slot: get_number(3)?,
+      bmc: get_number(4)?,
+      node: get_number(5)?,
+    })
+  }
 
-      kernel_params_string.push_str(kernel_param);
-    }
+  /// Cabinet xname, eg 'x1000'
+  pub fn cabinet_xname(&self) -> String {
+    format!("x{}", self.cabinet)
+  }
 
-    let mut node_vec: Vec<String> = node_status
-      .hsm
-      .split(",")
-      .map(|xname_str| xname_str.trim().to_string())
-      .collect();
-    node_vec.sort();
-
-    table.add_row(vec![
-      Cell::new(node_status.xname),
-      Cell::new(node_status.nid),
-      Cell::new(nodes_to_string_format_discrete_columns(Some(&node_vec), 1)),
-      Cell::new(node_status.power_status),
-      Cell::new(node_status.desired_configuration),
-      Cell::new(node_status.configuration_status),
-      Cell::new(node_status.enabled),
-      Cell::new(node_status.error_count),
-      Cell::new(node_status.boot_configuration),
-      Cell::new(node_status.boot_image_id),
-      Cell::new(kernel_params_string),
-    ]);
+  /// Chassis xname, eg 'x1000c1'
+  pub fn chassis_xname(&self) -> String {
+    format!("x{}c{}", self.cabinet, self.chassis)
   }
+}
 
-  println!("{table}");
+/// HSM flag of every node in the system
+pub async fn get_node_flag_map(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+) -> Result<BTreeMap<String, String>, Error> {
+  let node_metadata = backend
+    .get(
+      shasta_token,
+      None,
+      Some("Node"),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await?;
+
+  Ok(
+    node_metadata
+      .components
+      .unwrap_or_default()
+      .into_iter()
+      .filter_map(|component| Some((component.id?, component.flag?)))
+      .collect(),
+  )
 }
 
 pub fn print_summary(node_details_list: Vec<NodeDetails>) {
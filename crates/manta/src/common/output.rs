@@ -0,0 +1,733 @@
+use chrono::{DateTime, Local, NaiveDateTime};
+use comfy_table::Table;
+use csm_rs::node::types::NodeDetails;
+use jmespath::Expression;
+use manta_backend_dispatcher::{
+  error::Error,
+  types::{
+    bos::session_template::BosSessionTemplate,
+    cfs::{
+      cfs_configuration_response::CfsConfigurationResponse,
+      session::CfsSessionGetResponse,
+    },
+    hsm::inventory::RedfishEndpoint,
+    ims::Image,
+    pcs::power_cap::types::{PowerCapComponent, PowerCapLimit},
+    BootParameters, Group,
+  },
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use strum_macros::{AsRefStr, Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::common::{self, pcs_utils::TransitionTask};
+
+/// Output formats shared by all `manta get` subcommands
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  EnumIter,
+  EnumString,
+  IntoStaticStr,
+  AsRefStr,
+  Display,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum OutputFormat {
+  Table,
+  TableWide,
+  Json,
+  Yaml,
+  Csv,
+  Ndjson,
+}
+
+/// A record `manta get` knows how to print. `wide` adds the extra columns shown by
+/// `--output table-wide`. CSV output always uses the wide columns so scripts get all the
+/// data available
+pub trait OutputRecord: Serialize {
+  fn header(wide: bool) -> Vec<&'static str>;
+
+  fn row(&self, wide: bool) -> Vec<String>;
+}
+
+/// Custom output requested by the user on top of the output format. `query` is a JMESPath
+/// expression applied to the list of records, `columns` is a comma separated list of
+/// `[HEADER=]EXPRESSION` where each JMESPath expression is evaluated against every record
+/// (eg: 'xname,power_status,IMAGE=boot_image_id')
+#[derive(Debug, Clone, Default)]
+pub struct OutputSelection {
+  query_opt: Option<String>,
+  column_vec: Vec<(String, String)>,
+}
+
+impl OutputSelection {
+  pub fn new(
+    query_opt: Option<&str>,
+    columns_opt: Option<&str>,
+  ) -> Result<Self, Error> {
+    let column_vec: Vec<(String, String)> = columns_opt
+      .unwrap_or_default()
+      .split(',')
+      .map(str::trim)
+      .filter(|column| !column.is_empty())
+      .map(|column| match column.split_once('=') {
+        Some((header, expression)) => {
+          (header.trim().to_string(), expression.trim().to_string())
+        }
+        None => (column.to_string(), column.to_string()),
+      })
+      .collect();
+
+    // Validate expressions before fetching any data from the backend
+    for expression in query_opt
+      .into_iter()
+      .chain(column_vec.iter().map(|(_, expression)| expression.as_str()))
+    {
+      compile(expression)?;
+    }
+
+    Ok(OutputSelection {
+      query_opt: query_opt.map(str::to_string),
+      column_vec,
+    })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.query_opt.is_none() && self.column_vec.is_empty()
+  }
+}
+
+/// Renders a list of records in the format requested by the user
+pub fn render<T: OutputRecord>(
+  record_vec: &[T],
+  format: OutputFormat,
+) -> Result<String, Error> {
+  match format {
+    OutputFormat::Table => Ok(render_table(
+      T::header(false),
+      record_vec.iter().map(|record| record.row(false)).collect(),
+    )),
+    OutputFormat::TableWide => Ok(render_table(
+      T::header(true),
+      record_vec.iter().map(|record| record.row(true)).collect(),
+    )),
+    OutputFormat::Json => Ok(serde_json::to_string_pretty(record_vec)?),
+    OutputFormat::Yaml => serde_yaml::to_string(record_vec)
+      .map_err(|e| Error::Message(format!("Could not render YAML: {e}"))),
+    OutputFormat::Csv => render_csv(
+      T::header(true),
+      record_vec.iter().map(|record| record.row(true)).collect(),
+    ),
+    OutputFormat::Ndjson => record_vec
+      .iter()
+      .map(|record| serde_json::to_string(record).map_err(Error::from))
+      .collect::<Result<Vec<String>, Error>>()
+      .map(|line_vec| line_vec.join("\n")),
+  }
+}
+
+/// Renders a list of records after applying the query and custom columns requested by the
+/// user. Records are converted to JSON first, so JMESPath expressions use the field names
+/// shown by `--output json`
+pub fn render_selection<T: Serialize>(
+  record_vec: &[T],
+  format: OutputFormat,
+  selection: &OutputSelection,
+) -> Result<String, Error> {
+  let mut value = serde_json::to_value(record_vec)?;
+
+  if let Some(query) = &selection.query_opt {
+    value = search(&compile(query)?, value)?;
+  }
+
+  let header_vec: Vec<String> = if selection.column_vec.is_empty() {
+    // Use object keys as columns, if the query returns a list of plain values then the
+    // query itself is the column name
+    let mut header_vec: Vec<String> = Vec::new();
+
+    for element in value_to_vec(&value) {
+      match element {
+        Value::Object(map) => {
+          for key in map.keys() {
+            if !header_vec.contains(key) {
+              header_vec.push(key.clone());
+            }
+          }
+        }
+        _ if header_vec.is_empty() => header_vec
+          .push(selection.query_opt.clone().unwrap_or("Value".to_string())),
+        _ => {}
+      }
+    }
+
+    header_vec
+  } else {
+    let column_expression_vec = selection
+      .column_vec
+      .iter()
+      .map(|(header, expression)| Ok((header.clone(), compile(expression)?)))
+      .collect::<Result<Vec<_>, Error>>()?;
+
+    let record_value_vec = value_to_vec(&value)
+      .into_iter()
+      .map(|element| {
+        column_expression_vec
+          .iter()
+          .map(|(header, expression)| {
+            Ok((header.clone(), search(expression, element.clone())?))
+          })
+          .collect::<Result<Map<String, Value>, Error>>()
+          .map(Value::Object)
+      })
+      .collect::<Result<Vec<Value>, Error>>()?;
+
+    value = Value::Array(record_value_vec);
+
+    selection
+      .column_vec
+      .iter()
+      .map(|(header, _)| header.clone())
+      .collect()
+  };
+
+  let row_vec = || -> Vec<Vec<String>> {
+    value_to_vec(&value)
+      .into_iter()
+      .map(|element| match element {
+        Value::Object(map) => header_vec
+          .iter()
+          .map(|header| map.get(header).map(value_to_cell).unwrap_or_default())
+          .collect(),
+        _ => vec![value_to_cell(element)],
+      })
+      .collect()
+  };
+
+  match format {
+    OutputFormat::Table | OutputFormat::TableWide => {
+      Ok(render_table(header_vec.clone(), row_vec()))
+    }
+    OutputFormat::Csv => render_csv(header_vec.clone(), row_vec()),
+    OutputFormat::Json => Ok(serde_json::to_string_pretty(&value)?),
+    OutputFormat::Yaml => serde_yaml::to_string(&value)
+      .map_err(|e| Error::Message(format!("Could not render YAML: {e}"))),
+    OutputFormat::Ndjson => value_to_vec(&value)
+      .into_iter()
+      .map(|element| serde_json::to_string(element).map_err(Error::from))
+      .collect::<Result<Vec<String>, Error>>()
+      .map(|line_vec| line_vec.join("\n")),
+  }
+}
+
+pub fn print<T: OutputRecord>(
+  record_vec: &[T],
+  format: OutputFormat,
+) -> Result<(), Error> {
+  let output = render(record_vec, format)?;
+
+  if !output.is_empty() {
+    println!("{output}");
+  }
+
+  Ok(())
+}
+
+/// Same as `print` but honours the query and custom columns requested by the user
+pub fn print_selection<T: OutputRecord>(
+  record_vec: &[T],
+  format: OutputFormat,
+  selection: &OutputSelection,
+) -> Result<(), Error> {
+  if selection.is_empty() {
+    return print(record_vec, format);
+  }
+
+  let output = render_selection(record_vec, format, selection)?;
+
+  if !output.is_empty() {
+    println!("{output}");
+  }
+
+  Ok(())
+}
+
+fn compile(expression: &str) -> Result<Expression<'static>, Error> {
+  jmespath::compile(expression).map_err(|e| {
+    Error::Message(format!("JMESPath expression '{expression}' not valid: {e}"))
+  })
+}
+
+fn search(expression: &Expression, value: Value) -> Result<Value, Error> {
+  let result = expression.search(value).map_err(|e| {
+    Error::Message(format!("Could not evaluate JMESPath expression: {e}"))
+  })?;
+
+  Ok(serde_json::to_value(&*result)?)
+}
+
+fn value_to_vec(value: &Value) -> Vec<&Value> {
+  match value {
+    Value::Array(value_vec) => value_vec.iter().collect(),
+    Value::Null => Vec::new(),
+    _ => vec![value],
+  }
+}
+
+fn value_to_cell(value: &Value) -> String {
+  match value {
+    Value::Null => String::new(),
+    Value::String(value) => value.clone(),
+    Value::Array(value_vec) => value_vec
+      .iter()
+      .map(value_to_cell)
+      .collect::<Vec<String>>()
+      .join("\n"),
+    _ => value.to_string(),
+  }
+}
+
+fn render_table(
+  header: Vec<impl ToString>,
+  row_vec: Vec<Vec<String>>,
+) -> String {
+  let mut table = Table::new();
+
+  table.set_header(
+    header
+      .iter()
+      .map(|column| column.to_string())
+      .collect::<Vec<String>>(),
+  );
+
+  for row in row_vec {
+    table.add_row(row);
+  }
+
+  table.to_string()
+}
+
+fn render_csv(
+  header: Vec<impl ToString>,
+  row_vec: Vec<Vec<String>>,
+) -> Result<String, Error> {
+  let mut writer = csv::Writer::from_writer(Vec::new());
+
+  let csv_error =
+    |e: csv::Error| Error::Message(format!("Could not render CSV: {e}"));
+
+  writer
+    .write_record(header.iter().map(|column| column.to_string()))
+    .map_err(csv_error)?;
+
+  for row in row_vec {
+    // Multi line cells are only meant for humans, flatten them so each record is a single
+    // line
+    writer
+      .write_record(
+        row
+          .iter()
+          .map(|cell| cell.replace(",\n", ",").replace('\n', " ")),
+      )
+      .map_err(csv_error)?;
+  }
+
+  let bytes = writer
+    .into_inner()
+    .map_err(|e| Error::Message(format!("Could not render CSV: {e}")))?;
+
+  String::from_utf8(bytes)
+    .map(|csv| csv.trim_end().to_string())
+    .map_err(|e| Error::Message(format!("Could not render CSV: {e}")))
+}
+
+impl OutputRecord for NodeDetails {
+  fn header(wide: bool) -> Vec<&'static str> {
+    let mut header = vec![
+      "XNAME",
+      "NID",
+      "HSM",
+      "Power Status",
+      "Runtime Configuration",
+      "Configuration Status",
+      "Enabled",
+      "Error Count",
+      "Image Configuration",
+      "Image ID",
+    ];
+
+    if wide {
+      header.push("Kernel Params");
+    }
+
+    header
+  }
+
+  fn row(&self, wide: bool) -> Vec<String> {
+    let mut hsm_vec: Vec<String> = self
+      .hsm
+      .split(',')
+      .map(|hsm| hsm.trim().to_string())
+      .collect();
+    hsm_vec.sort();
+
+    let mut row = vec![
+      self.xname.clone(),
+      self.nid.clone(),
+      common::node_ops::nodes_to_string_format_discrete_columns(
+        Some(&hsm_vec),
+        1,
+      ),
+      self.power_status.clone(),
+      self.desired_configuration.clone(),
+      self.configuration_status.clone(),
+      self.enabled.clone(),
+      self.error_count.clone(),
+      self.boot_configuration.clone(),
+      self.boot_image_id.clone(),
+    ];
+
+    if wide {
+      row.push(
+        self
+          .kernel_params
+          .split_whitespace()
+          .collect::<Vec<_>>()
+          .join("\n"),
+      );
+    }
+
+    row
+  }
+}
+
+impl OutputRecord for CfsSessionGetResponse {
+  fn header(_wide: bool) -> Vec<&'static str> {
+    vec![
+      "Session Name",
+      "Configuration Name",
+      "Start",
+      "Status",
+      "Succeeded",
+      "Target Def",
+      "Target",
+      "Image ID",
+    ]
+  }
+
+  fn row(&self, _wide: bool) -> Vec<String> {
+    common::cfs_session_utils::cfs_session_struct_to_vec(self.clone())
+  }
+}
+
+impl OutputRecord for CfsConfigurationResponse {
+  fn header(_wide: bool) -> Vec<&'static str> {
+    vec!["Config Name", "Last updated", "Layers"]
+  }
+
+  fn row(&self, _wide: bool) -> Vec<String> {
+    let layer_vec: Vec<String> = self
+      .layers
+      .iter()
+      .map(|layer| {
+        format!(
+          "{} ({} @ {})",
+          layer.name,
+          layer.playbook,
+          layer.commit.as_deref().unwrap_or("Not defined")
+        )
+      })
+      .collect();
+
+    vec![
+      self.name.clone(),
+      format_creation_date(&self.last_updated),
+      layer_vec.join("\n"),
+    ]
+  }
+}
+
+impl OutputRecord for BosSessionTemplate {
+  fn header(_wide: bool) -> Vec<&'static str> {
+    vec![
+      "Name",
+      "Image ID",
+      "Runtime Configuration",
+      "Cfs Enabled",
+      "Target",
+      "Compute Etag",
+    ]
+  }
+
+  // NOTE: a BOS sessiontemplate may have more than one boot set, values from all boot sets
+  // are merged so each BOS sessiontemplate is printed in a single row
+  fn row(&self, _wide: bool) -> Vec<String> {
+    let boot_set_vec: Vec<_> = self
+      .boot_sets
+      .as_ref()
+      .map(|boot_sets| boot_sets.values().collect())
+      .unwrap_or_default();
+
+    let image_id_vec: Vec<String> = boot_set_vec
+      .iter()
+      .filter_map(|boot_set| boot_set.path.as_ref())
+      .map(|path| {
+        path
+          .trim_start_matches("s3://boot-images/")
+          .trim_end_matches("/manifest.json")
+          .to_string()
+      })
+      .collect();
+
+    let target_vec: Vec<String> = boot_set_vec
+      .iter()
+      .flat_map(|boot_set| {
+        boot_set
+          .node_groups
+          .clone()
+          .or(boot_set.node_list.clone())
+          .unwrap_or_default()
+      })
+      .collect();
+
+    let etag_vec: Vec<String> = boot_set_vec
+      .iter()
+      .filter_map(|boot_set| boot_set.etag.clone())
+      .collect();
+
+    vec![
+      self.name.clone().unwrap_or_default(),
+      image_id_vec.join("\n"),
+      self
+        .cfs
+        .as_ref()
+        .and_then(|cfs| cfs.configuration.clone())
+        .unwrap_or_default(),
+      self
+        .enable_cfs
+        .map(|value| value.to_string())
+        .unwrap_or("N/A".to_string()),
+      common::node_ops::string_vec_to_multi_line_string(Some(&target_vec), 2),
+      etag_vec.join("\n"),
+    ]
+  }
+}
+
+impl OutputRecord for Image {
+  fn header(wide: bool) -> Vec<&'static str> {
+    let mut header = vec!["Image ID", "Name", "Creation time"];
+
+    if wide {
+      header.extend(["Arch", "Manifest"]);
+    }
+
+    header
+  }
+
+  fn row(&self, wide: bool) -> Vec<String> {
+    let mut row = vec![
+      self.id.clone().unwrap_or_default(),
+      self.name.clone(),
+      self
+        .created
+        .as_deref()
+        .map(format_creation_date)
+        .unwrap_or_default(),
+    ];
+
+    if wide {
+      row.push(self.arch.clone().unwrap_or_default());
+      row.push(
+        self
+          .link
+          .as_ref()
+          .map(|link| link.path.clone())
+          .unwrap_or_default(),
+      );
+    }
+
+    row
+  }
+}
+
+impl OutputRecord for BootParameters {
+  fn header(wide: bool) -> Vec<&'static str> {
+    let mut header = vec!["Hosts", "Image ID", "Kernel", "Initrd"];
+
+    if wide {
+      header.push("Kernel Params");
+    }
+
+    header
+  }
+
+  fn row(&self, wide: bool) -> Vec<String> {
+    let mut row = vec![
+      common::node_ops::string_vec_to_multi_line_string(Some(&self.hosts), 2),
+      self.get_boot_image(),
+      self.kernel.clone(),
+      self.initrd.clone(),
+    ];
+
+    if wide {
+      row.push(
+        self
+          .params
+          .split_whitespace()
+          .collect::<Vec<_>>()
+          .join("\n"),
+      );
+    }
+
+    row
+  }
+}
+
+impl OutputRecord for Group {
+  fn header(_wide: bool) -> Vec<&'static str> {
+    vec!["Group Name", "Description", "# members", "Members", "Tags"]
+  }
+
+  fn row(&self, _wide: bool) -> Vec<String> {
+    let mut member_vec = self.get_members();
+    member_vec.sort();
+
+    vec![
+      self.label.clone(),
+      self.description.clone().unwrap_or_default(),
+      member_vec.len().to_string(),
+      member_vec.join("\n"),
+      self.tags.clone().unwrap_or_default().join("\n"),
+    ]
+  }
+}
+
+impl OutputRecord for RedfishEndpoint {
+  fn header(wide: bool) -> Vec<&'static str> {
+    let mut header = vec!["ID", "Type", "FQDN", "IP Address", "Enabled"];
+
+    if wide {
+      header.extend(["Hostname", "Domain", "MAC Address", "UUID", "User"]);
+    }
+
+    header
+  }
+
+  fn row(&self, wide: bool) -> Vec<String> {
+    let mut row = vec![
+      self.id.clone(),
+      self.r#type.clone().unwrap_or_default(),
+      self.fqdn.clone().unwrap_or_default(),
+      self.ip_address.clone().unwrap_or_default(),
+      self.enabled.map(|v| v.to_string()).unwrap_or_default(),
+    ];
+
+    // NOTE: password is never printed
+    if wide {
+      row.extend([
+        self.hostname.clone().unwrap_or_default(),
+        self.domain.clone().unwrap_or_default(),
+        self.mac_addr.clone().unwrap_or_default(),
+        self.uuid.clone().unwrap_or_default(),
+        self.user.clone().unwrap_or_default(),
+      ]);
+    }
+
+    row
+  }
+}
+
+impl OutputRecord for PowerCapComponent {
+  fn header(wide: bool) -> Vec<&'static str> {
+    let mut header = vec![
+      "XNAME",
+      "CONTROL",
+      "CURRENT (W)",
+      "MIN (W)",
+      "MAX (W)",
+      "ERROR",
+    ];
+
+    if wide {
+      header.extend(["HOST MIN (W)", "HOST MAX (W)", "POWERUP (W)"]);
+    }
+
+    header
+  }
+
+  fn row(&self, wide: bool) -> Vec<String> {
+    let power_cap_limit_vec =
+      self.power_cap_limits.as_deref().unwrap_or_default();
+
+    // One line per control, a node may have more than one (eg: node and accelerators)
+    let join = |f: fn(&PowerCapLimit) -> Option<String>| {
+      power_cap_limit_vec
+        .iter()
+        .map(|limit| f(limit).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+    };
+
+    let mut row = vec![
+      self.xname.clone().unwrap_or_default(),
+      join(|limit| limit.name.clone()),
+      join(|limit| limit.current_value.map(|v| v.to_string())),
+      join(|limit| limit.minimum_value.map(|v| v.to_string())),
+      join(|limit| limit.maximum_value.map(|v| v.to_string())),
+      self.error.clone().unwrap_or_default(),
+    ];
+
+    if wide {
+      let limits = self.limits.as_ref();
+
+      row.extend([
+        limits
+          .and_then(|limits| limits.host_limit_min)
+          .map(|v| v.to_string())
+          .unwrap_or_default(),
+        limits
+          .and_then(|limits| limits.host_limit_max)
+          .map(|v| v.to_string())
+          .unwrap_or_default(),
+        limits
+          .and_then(|limits| limits.powerup_power)
+          .map(|v| v.to_string())
+          .unwrap_or_default(),
+      ]);
+    }
+
+    row
+  }
+}
+
+impl OutputRecord for TransitionTask {
+  fn header(_wide: bool) -> Vec<&'static str> {
+    vec!["XNAME", "Status", "Description"]
+  }
+
+  fn row(&self, _wide: bool) -> Vec<String> {
+    vec![
+      self.xname.clone(),
+      self.task_status.clone(),
+      self.task_status_description.clone(),
+    ]
+  }
+}
+
+// NOTE: CSM can have different date formats, so we need to try to parse it in different
+// ways
+pub fn format_creation_date(creation_date: &str) -> String {
+  if let Ok(v) = creation_date.parse::<NaiveDateTime>() {
+    v.format("%d/%m/%Y %H:%M:%S").to_string()
+  } else if let Ok(v) = creation_date.parse::<DateTime<Local>>() {
+    v.naive_local().format("%d/%m/%Y %H:%M:%S").to_string()
+  } else {
+    creation_date.to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests;
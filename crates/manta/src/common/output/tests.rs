@@ -0,0 +1,209 @@
+use serde::Serialize;
+use serde_json::json;
+
+use super::{
+  render, render_selection, OutputFormat, OutputRecord, OutputSelection,
+};
+
+#[derive(Serialize)]
+struct TestRecord {
+  xname: String,
+  power: String,
+  groups: Vec<String>,
+}
+
+impl OutputRecord for TestRecord {
+  fn header(wide: bool) -> Vec<&'static str> {
+    let mut header = vec!["XNAME", "POWER"];
+
+    if wide {
+      header.push("GROUPS");
+    }
+
+    header
+  }
+
+  fn row(&self, wide: bool) -> Vec<String> {
+    let mut row = vec![self.xname.clone(), self.power.clone()];
+
+    if wide {
+      row.push(self.groups.join("\n"));
+    }
+
+    row
+  }
+}
+
+fn record_vec() -> Vec<TestRecord> {
+  vec![
+    TestRecord {
+      xname: "x1000c0s0b0n0".to_string(),
+      power: "ON".to_string(),
+      groups: vec!["zinal".to_string(), "compute".to_string()],
+    },
+    TestRecord {
+      xname: "x1000c0s0b0n1".to_string(),
+      power: "OFF".to_string(),
+      groups: vec!["zinal".to_string()],
+    },
+  ]
+}
+
+#[test]
+fn test_output_format_from_str() {
+  assert_eq!(
+    "table-wide".parse::<OutputFormat>().unwrap(),
+    OutputFormat::TableWide
+  );
+  assert_eq!(
+    "ndjson".parse::<OutputFormat>().unwrap(),
+    OutputFormat::Ndjson
+  );
+  assert!("xml".parse::<OutputFormat>().is_err());
+}
+
+#[test]
+fn test_render_table() {
+  let output = render(&record_vec(), OutputFormat::Table).unwrap();
+
+  assert!(output.contains("XNAME"));
+  assert!(output.contains("x1000c0s0b0n1"));
+  assert!(!output.contains("GROUPS"));
+
+  let output = render(&record_vec(), OutputFormat::TableWide).unwrap();
+
+  assert!(output.contains("GROUPS"));
+  assert!(output.contains("compute"));
+}
+
+#[test]
+fn test_render_json() {
+  let output = render(&record_vec(), OutputFormat::Json).unwrap();
+
+  let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+  assert_eq!(value[0]["xname"], "x1000c0s0b0n0");
+  assert_eq!(value[1]["groups"][0], "zinal");
+}
+
+#[test]
+fn test_render_yaml() {
+  let output = render(&record_vec(), OutputFormat::Yaml).unwrap();
+
+  let value: serde_json::Value = serde_yaml::from_str(&output).unwrap();
+
+  assert_eq!(value[1]["power"], "OFF");
+}
+
+#[test]
+fn test_render_csv_uses_wide_columns_and_flattens_cells() {
+  let output = render(&record_vec(), OutputFormat::Csv).unwrap();
+
+  assert_eq!(
+    output,
+    "XNAME,POWER,GROUPS\n\
+     x1000c0s0b0n0,ON,zinal compute\n\
+     x1000c0s0b0n1,OFF,zinal"
+  );
+}
+
+#[test]
+fn test_render_ndjson() {
+  let output = render(&record_vec(), OutputFormat::Ndjson).unwrap();
+
+  let line_vec: Vec<&str> = output.lines().collect();
+
+  assert_eq!(line_vec.len(), 2);
+  assert_eq!(
+    line_vec[1],
+    r#"{"xname":"x1000c0s0b0n1","power":"OFF","groups":["zinal"]}"#
+  );
+}
+
+#[test]
+fn test_render_empty() {
+  let record_vec: Vec<TestRecord> = Vec::new();
+
+  assert_eq!(render(&record_vec, OutputFormat::Json).unwrap(), "[]");
+  assert_eq!(render(&record_vec, OutputFormat::Ndjson).unwrap(), "");
+  assert_eq!(
+    render(&record_vec, OutputFormat::Csv).unwrap(),
+    "XNAME,POWER,GROUPS"
+  );
+}
+
+#[test]
+fn test_selection_columns() {
+  let selection =
+    OutputSelection::new(None, Some("xname, STATE=power")).unwrap();
+
+  let output =
+    render_selection(&record_vec(), OutputFormat::Csv, &selection).unwrap();
+
+  assert_eq!(output, "xname,STATE\nx1000c0s0b0n0,ON\nx1000c0s0b0n1,OFF");
+
+  let output =
+    render_selection(&record_vec(), OutputFormat::Ndjson, &selection).unwrap();
+
+  let value: serde_json::Value =
+    serde_json::from_str(output.lines().next().unwrap()).unwrap();
+
+  assert_eq!(value, json!({ "xname": "x1000c0s0b0n0", "STATE": "ON" }));
+}
+
+#[test]
+fn test_selection_query() {
+  let selection =
+    OutputSelection::new(Some("[?power=='OFF'].{node: xname}"), None).unwrap();
+
+  let output =
+    render_selection(&record_vec(), OutputFormat::Csv, &selection).unwrap();
+
+  assert_eq!(output, "node\nx1000c0s0b0n1");
+
+  // A query returning plain values uses the query as column name
+  let selection = OutputSelection::new(Some("[].xname"), None).unwrap();
+
+  let output =
+    render_selection(&record_vec(), OutputFormat::Csv, &selection).unwrap();
+
+  assert_eq!(output, "[].xname\nx1000c0s0b0n0\nx1000c0s0b0n1");
+}
+
+#[test]
+fn test_selection_query_and_columns() {
+  let selection =
+    OutputSelection::new(Some("[?power=='ON']"), Some("xname,groups")).unwrap();
+
+  let output =
+    render_selection(&record_vec(), OutputFormat::Csv, &selection).unwrap();
+
+  assert_eq!(output, "xname,groups\nx1000c0s0b0n0,zinal compute");
+}
+
+#[test]
+fn test_selection_query_matches_nothing() {
+  let selection =
+    OutputSelection::new(Some("[?power=='UNKNOWN']"), None).unwrap();
+
+  assert_eq!(
+    render_selection(&record_vec(), OutputFormat::Json, &selection).unwrap(),
+    "[]"
+  );
+  assert_eq!(
+    render_selection(&record_vec(), OutputFormat::Ndjson, &selection).unwrap(),
+    ""
+  );
+
+  let output =
+    render_selection(&record_vec(), OutputFormat::Table, &selection).unwrap();
+
+  assert!(!output.contains("x1000c0s0b0n"));
+}
+
+#[test]
+fn test_selection_not_valid() {
+  assert!(OutputSelection::new(Some("[?power=="), None).is_err());
+  assert!(OutputSelection::new(None, Some("xname,STATE=[")).is_err());
+  assert!(OutputSelection::new(None, Some(" , ")).unwrap().is_empty());
+}
@@ -1,6 +1,236 @@
+use std::{collections::HashMap, time::Duration};
+
 use comfy_table::Table;
+use indicatif::{ProgressBar, ProgressStyle};
+use manta_backend_dispatcher::{
+  error::Error,
+  interfaces::{
+    hsm::{component::ComponentTrait, group::GroupTrait},
+    pcs::PCSTrait,
+  },
+};
+use serde::Serialize;
 use serde_json::Value;
 
+use crate::{common, manta_backend_dispatcher::StaticBackendDispatcher};
+
+/// Max number of times a power transition status is checked before giving up
+const MAX_ATTEMPTS: usize = 300;
+
+/// Power transition requested by the user
+#[derive(Debug, Clone, Copy)]
+pub enum PowerAction {
+  On,
+  Off { force: bool },
+  Reset { force: bool },
+}
+
+impl PowerAction {
+  /// Creates the power transition and returns its ID
+  async fn start(
+    &self,
+    backend: &StaticBackendDispatcher,
+    shasta_token: &str,
+    xname_vec: &[String],
+  ) -> Result<String, Error> {
+    match *self {
+      PowerAction::On => backend.power_on(shasta_token, xname_vec).await,
+      PowerAction::Off { force } => {
+        backend.power_off(shasta_token, xname_vec, force).await
+      }
+      PowerAction::Reset { force } => {
+        backend.power_reset(shasta_token, xname_vec, force).await
+      }
+    }
+  }
+}
+
+/// Creates a power transition on the nodes and shows its progress until it completes. Nodes
+/// whose task failed get a new transition, up to `retry_failed` times. Returns all the
+/// transitions created, the last one has the final status of the nodes retried. If
+/// `no_wait`, only the ID of the transition is printed and nothing is returned
+pub async fn exec_power_transition(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  power_action: PowerAction,
+  xname_vec: &[String],
+  no_wait: bool,
+  retry_failed: usize,
+) -> Result<Vec<Value>, Error> {
+  let transition_id =
+    power_action.start(backend, shasta_token, xname_vec).await?;
+
+  log::info!("PCS transition ID: {}", transition_id);
+
+  if no_wait {
+    println!("{}", transition_id);
+    return Ok(Vec::new());
+  }
+
+  let mut transition_vec =
+    vec![wait_to_complete(backend, shasta_token, &transition_id).await?];
+
+  for retry in 1..=retry_failed {
+    let failed_xname_vec = get_failed_xname_vec(transition_vec.last().unwrap());
+
+    if failed_xname_vec.is_empty() {
+      break;
+    }
+
+    eprintln!(
+      "Retry {} of {} for nodes whose task failed: {}",
+      retry,
+      retry_failed,
+      failed_xname_vec.join(", ")
+    );
+
+    let transition_id = power_action
+      .start(backend, shasta_token, &failed_xname_vec)
+      .await?;
+
+    transition_vec
+      .push(wait_to_complete(backend, shasta_token, &transition_id).await?);
+  }
+
+  Ok(transition_vec)
+}
+
+/// Polls a power transition until it completes. Shows a progress bar with the number of
+/// nodes whose task finished and prints a line each time the task of a node finishes
+pub async fn wait_to_complete(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  transition_id: &str,
+) -> Result<Value, Error> {
+  let progress_bar = ProgressBar::new(0).with_style(
+    ProgressStyle::with_template(
+      "{spinner} Power {msg} [{bar:40}] {pos}/{len} nodes ({elapsed})",
+    )
+    .unwrap()
+    .progress_chars("=> "),
+  );
+
+  progress_bar.enable_steady_tick(Duration::from_millis(200));
+
+  let mut task_status_map: HashMap<String, String> = HashMap::new();
+
+  for _ in 1..=MAX_ATTEMPTS {
+    let transition = backend
+      .get_power_transition(shasta_token, transition_id)
+      .await?;
+
+    let task_vec = transition["tasks"].as_array().cloned().unwrap_or_default();
+
+    progress_bar.set_length(
+      transition
+        .pointer("/taskCounts/total")
+        .and_then(Value::as_u64)
+        .unwrap_or(task_vec.len() as u64),
+    );
+
+    for task in &task_vec {
+      let xname = task["xname"].as_str().unwrap_or_default();
+      let task_status = task["taskStatus"].as_str().unwrap_or_default();
+
+      if is_task_finished(task_status)
+        && task_status_map.get(xname).map(String::as_str) != Some(task_status)
+      {
+        progress_bar.println(format!(
+          "{}: {} {}",
+          xname,
+          task_status,
+          task["taskStatusDescription"].as_str().unwrap_or_default()
+        ));
+
+        task_status_map.insert(xname.to_string(), task_status.to_string());
+      }
+    }
+
+    progress_bar.set_position(task_status_map.len() as u64);
+
+    let transition_status =
+      transition["transitionStatus"].as_str().unwrap_or_default();
+
+    progress_bar.set_message(format!(
+      "'{}' {}",
+      transition["operation"].as_str().unwrap_or_default(),
+      transition_status
+    ));
+
+    match transition_status {
+      "completed" => {
+        progress_bar.finish_and_clear();
+        return Ok(transition);
+      }
+      "aborted" => {
+        progress_bar.abandon();
+        return Err(Error::Message(format!(
+          "PCS transition '{}' aborted",
+          transition_id
+        )));
+      }
+      _ => tokio::time::sleep(Duration::from_secs(3)).await,
+    }
+  }
+
+  progress_bar.abandon();
+
+  Err(Error::Message(format!(
+    "PCS transition '{}' did not complete after {} attempts",
+    transition_id, MAX_ATTEMPTS
+  )))
+}
+
+/// Returns the xnames of the nodes whose task failed in a power transition
+pub fn get_failed_xname_vec(transition: &Value) -> Vec<String> {
+  transition["tasks"]
+    .as_array()
+    .map(|task_vec| {
+      task_vec
+        .iter()
+        .filter(|task| task["taskStatus"] == "failed")
+        .filter_map(|task| task["xname"].as_str().map(str::to_string))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn is_task_finished(task_status: &str) -> bool {
+  matches!(task_status, "succeeded" | "failed" | "unsupported")
+}
+
+/// Task of a power transition on a node, as printed by 'get power-transition'
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitionTask {
+  pub xname: String,
+  pub task_status: String,
+  pub task_status_description: String,
+}
+
+impl TransitionTask {
+  /// Tasks in a PCS power transition
+  pub fn from_transition(transition: &Value) -> Vec<Self> {
+    let field = |task: &Value, name: &str| {
+      task[name].as_str().unwrap_or_default().to_string()
+    };
+
+    transition["tasks"]
+      .as_array()
+      .map(|task_vec| {
+        task_vec
+          .iter()
+          .map(|task| TransitionTask {
+            xname: field(task, "xname"),
+            task_status: field(task, "taskStatus"),
+            task_status_description: field(task, "taskStatusDescription"),
+          })
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+}
+
 pub fn print_summary_table(transition: Value, output: &str) {
   if output == "table" {
     let tasks: Vec<Value> = transition["tasks"].as_array().unwrap().to_vec();
@@ -33,3 +263,41 @@ pub fn print_summary_table(transition: Value, output: &str) {
     println!("{}", serde_json::to_string_pretty(&transition).unwrap());
   }
 }
+
+/// Returns the sorted list of xnames of the nodes in a group or matching a hosts expression
+/// (xnames or nids, hostlist or regex). Nodes the user has no access to are ignored
+pub async fn get_target_xname_vec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  hosts_expression_opt: Option<&str>,
+  hsm_group_name_opt: Option<&str>,
+) -> Result<Vec<String>, Error> {
+  let mut xname_vec = if let Some(hsm_group_name) = hsm_group_name_opt {
+    backend
+      .get_member_vec_from_group_name_vec(
+        shasta_token,
+        vec![hsm_group_name.to_string()],
+      )
+      .await?
+  } else if let Some(hosts_expression) = hosts_expression_opt {
+    let node_metadata_available_vec =
+      backend.get_node_metadata_available(shasta_token).await?;
+
+    common::node_ops::from_hosts_expression_to_xname_vec(
+      hosts_expression,
+      false,
+      node_metadata_available_vec,
+    )
+    .await?
+  } else {
+    Vec::new()
+  };
+
+  xname_vec.sort();
+  xname_vec.dedup();
+
+  Ok(xname_vec)
+}
+
+#[cfg(test)]
+mod tests;
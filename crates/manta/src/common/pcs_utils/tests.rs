@@ -0,0 +1,159 @@
+use manta_backend_dispatcher::{
+  mock::backend_connector::MockBackend, mock::types::MockState,
+};
+use serde_json::json;
+
+use crate::manta_backend_dispatcher::StaticBackendDispatcher;
+
+use super::{
+  exec_power_transition, get_failed_xname_vec, get_target_xname_vec,
+  PowerAction,
+};
+
+const SHASTA_TOKEN: &str = "mock-token";
+
+/// Group 'zinal' with two nodes and group 'nodes_free' with one node. The user only has
+/// access to 'zinal'
+fn backend() -> StaticBackendDispatcher {
+  let state: MockState = serde_json::from_value(json!({
+    "group_available": ["zinal"],
+    "groups": [
+      { "label": "zinal", "members": { "ids": ["x1000c0s0b0n1", "x1000c0s0b0n0"] } },
+      { "label": "nodes_free", "members": { "ids": ["x1000c0s1b0n0"] } },
+    ],
+    "components": [
+      { "ID": "x1000c0s0b0n0", "Type": "Node", "NID": 1 },
+      { "ID": "x1000c0s0b0n1", "Type": "Node", "NID": 2 },
+      { "ID": "x1000c0s1b0n0", "Type": "Node", "NID": 3 },
+    ],
+    "power_state": { "x1000c0s0b0n0": "off", "x1000c0s0b0n1": "off", "x1000c0s1b0n0": "off" },
+  }))
+  .unwrap();
+
+  StaticBackendDispatcher::MOCK(MockBackend::from_state(state))
+}
+
+fn xname_vec(xname_vec: &[&str]) -> Vec<String> {
+  xname_vec.iter().map(|xname| xname.to_string()).collect()
+}
+
+#[test]
+fn test_get_failed_xname_vec() {
+  let transition = json!({
+    "tasks": [
+      { "xname": "x1000c0s0b0n0", "taskStatus": "succeeded" },
+      { "xname": "x1000c0s0b0n1", "taskStatus": "failed" },
+      { "xname": "x1000c0s1b0n0", "taskStatus": "in-progress" },
+      { "xname": "x1000c0s1b0n1", "taskStatus": "failed" },
+    ],
+  });
+
+  assert_eq!(
+    get_failed_xname_vec(&transition),
+    xname_vec(&["x1000c0s0b0n1", "x1000c0s1b0n1"])
+  );
+
+  assert!(get_failed_xname_vec(&json!({})).is_empty());
+}
+
+#[tokio::test]
+async fn test_exec_power_transition() {
+  let transition_vec = exec_power_transition(
+    &backend(),
+    SHASTA_TOKEN,
+    PowerAction::On,
+    &xname_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1"]),
+    false,
+    2,
+  )
+  .await
+  .unwrap();
+
+  // No retry if no task failed
+  assert_eq!(transition_vec.len(), 1);
+  assert!(get_failed_xname_vec(&transition_vec[0]).is_empty());
+}
+
+#[tokio::test]
+async fn test_exec_power_transition_retry_failed() {
+  // The mock fails the task of nodes missing in HSM
+  let transition_vec = exec_power_transition(
+    &backend(),
+    SHASTA_TOKEN,
+    PowerAction::Reset { force: false },
+    &xname_vec(&["x1000c0s0b0n0", "x1000c0s9b0n0"]),
+    false,
+    2,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(transition_vec.len(), 3);
+  assert_eq!(transition_vec[0]["tasks"].as_array().unwrap().len(), 2);
+
+  // Retries only target the nodes that failed
+  for transition in &transition_vec[1..] {
+    assert_eq!(transition["tasks"].as_array().unwrap().len(), 1);
+    assert_eq!(transition["operation"], "soft-restart");
+  }
+
+  assert_eq!(
+    get_failed_xname_vec(transition_vec.last().unwrap()),
+    xname_vec(&["x1000c0s9b0n0"])
+  );
+}
+
+#[tokio::test]
+async fn test_exec_power_transition_no_wait() {
+  let transition_vec = exec_power_transition(
+    &backend(),
+    SHASTA_TOKEN,
+    PowerAction::Off { force: true },
+    &xname_vec(&["x1000c0s9b0n0"]),
+    true,
+    2,
+  )
+  .await
+  .unwrap();
+
+  assert!(transition_vec.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_target_xname_vec() {
+  let backend = backend();
+
+  // Group members are sorted
+  assert_eq!(
+    get_target_xname_vec(&backend, SHASTA_TOKEN, None, Some("zinal"))
+      .await
+      .unwrap(),
+    xname_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1"])
+  );
+
+  // Hosts expression of xnames and nids, nodes the user has no access to are ignored
+  assert_eq!(
+    get_target_xname_vec(
+      &backend,
+      SHASTA_TOKEN,
+      Some("x1000c0s0b0n1,x1000c0s0b0n0,x1000c0s1b0n0"),
+      None
+    )
+    .await
+    .unwrap(),
+    xname_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1"])
+  );
+
+  assert_eq!(
+    get_target_xname_vec(&backend, SHASTA_TOKEN, Some("nid00000[2-3]"), None)
+      .await
+      .unwrap(),
+    xname_vec(&["x1000c0s0b0n1"])
+  );
+
+  // Nothing to target
+  assert!(get_target_xname_vec(&backend, SHASTA_TOKEN, None, None)
+    .await
+    .unwrap()
+    .is_empty());
+}
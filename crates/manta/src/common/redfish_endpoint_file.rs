@@ -0,0 +1,233 @@
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
+
+use manta_backend_dispatcher::{
+  error::Error, types::hsm::inventory::RedfishEndpoint,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::common::node_ops::validate_bmc_xname_format;
+
+/// Formats of the files used by 'import redfish-endpoints' and 'export redfish-endpoints'
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedfishEndpointFileFormat {
+  Csv,
+  Yaml,
+}
+
+impl RedfishEndpointFileFormat {
+  /// Format from the file extension ('.csv', '.yaml' or '.yml')
+  pub fn from_path(path: &Path) -> Result<Self, Error> {
+    match path
+      .extension()
+      .and_then(|extension| extension.to_str())
+      .map(str::to_lowercase)
+      .as_deref()
+    {
+      Some("csv") => Ok(Self::Csv),
+      Some("yaml") | Some("yml") => Ok(Self::Yaml),
+      _ => Err(Error::Message(format!(
+        "Format of file '{}' unknown, use a '.csv', '.yaml' or '.yml' file",
+        path.display()
+      ))),
+    }
+  }
+}
+
+/// Redfish endpoint as a row of a CSV file or an item of a YAML list. Read only fields
+/// managed by the backend (eg 'UUID' or 'DiscoveryInfo') are left out
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RedfishEndpointRecord {
+  pub id: String,
+  #[serde(default)]
+  pub name: Option<String>,
+  #[serde(default)]
+  pub hostname: Option<String>,
+  #[serde(default)]
+  pub domain: Option<String>,
+  #[serde(default)]
+  pub fqdn: Option<String>,
+  #[serde(default)]
+  pub enabled: Option<bool>,
+  #[serde(default)]
+  pub user: Option<String>,
+  /// The backend never returns passwords, exported files leave it empty
+  #[serde(default)]
+  pub password: Option<String>,
+  #[serde(default)]
+  pub use_ssdp: Option<bool>,
+  #[serde(default)]
+  pub mac_required: Option<bool>,
+  #[serde(default)]
+  pub mac_addr: Option<String>,
+  #[serde(default)]
+  pub ip_address: Option<String>,
+  #[serde(default)]
+  pub rediscover_on_update: Option<bool>,
+  #[serde(default)]
+  pub template_id: Option<String>,
+}
+
+impl From<RedfishEndpoint> for RedfishEndpointRecord {
+  fn from(value: RedfishEndpoint) -> Self {
+    Self {
+      id: value.id,
+      name: value.name,
+      hostname: value.hostname,
+      domain: value.domain,
+      fqdn: value.fqdn,
+      enabled: value.enabled,
+      user: value.user,
+      password: None,
+      use_ssdp: value.use_ssdp,
+      mac_required: value.mac_required,
+      mac_addr: value.mac_addr,
+      ip_address: value.ip_address,
+      rediscover_on_update: value.rediscover_on_update,
+      template_id: value.template_id,
+    }
+  }
+}
+
+impl From<RedfishEndpointRecord> for RedfishEndpoint {
+  fn from(value: RedfishEndpointRecord) -> Self {
+    Self {
+      id: value.id,
+      r#type: None,
+      name: value.name,
+      hostname: value.hostname,
+      domain: value.domain,
+      fqdn: value.fqdn,
+      enabled: value.enabled,
+      uuid: None,
+      user: value.user,
+      password: value.password,
+      use_ssdp: value.use_ssdp,
+      mac_required: value.mac_required,
+      mac_addr: value.mac_addr,
+      ip_address: value.ip_address,
+      rediscover_on_update: value.rediscover_on_update,
+      template_id: value.template_id,
+      discovery_info: None,
+    }
+  }
+}
+
+/// Reads the redfish endpoints in a CSV or YAML file, the format is taken from the file
+/// extension
+pub fn read(path: &Path) -> Result<Vec<RedfishEndpointRecord>, Error> {
+  let format = RedfishEndpointFileFormat::from_path(path)?;
+
+  let file = File::open(path).map_err(|e| {
+    Error::Message(format!(
+      "Could not open redfish endpoints file '{}'. Reason:\n{}",
+      path.display(),
+      e
+    ))
+  })?;
+
+  let file_error = |e: String| {
+    Error::Message(format!(
+      "Redfish endpoints file '{}' not valid. Reason:\n{}",
+      path.display(),
+      e
+    ))
+  };
+
+  match format {
+    RedfishEndpointFileFormat::Csv => csv::Reader::from_reader(file)
+      .deserialize()
+      .collect::<Result<Vec<RedfishEndpointRecord>, csv::Error>>()
+      .map_err(|e| file_error(e.to_string())),
+    RedfishEndpointFileFormat::Yaml => {
+      serde_yaml::from_reader(BufReader::new(file))
+        .map_err(|e| file_error(e.to_string()))
+    }
+  }
+}
+
+/// Renders the redfish endpoints in the format 'read' expects
+pub fn to_string(
+  record_vec: &[RedfishEndpointRecord],
+  format: RedfishEndpointFileFormat,
+) -> Result<String, Error> {
+  match format {
+    RedfishEndpointFileFormat::Csv => {
+      let csv_error =
+        |e: String| Error::Message(format!("Could not render CSV: {e}"));
+
+      let mut writer = csv::Writer::from_writer(Vec::new());
+
+      for record in record_vec {
+        writer
+          .serialize(record)
+          .map_err(|e| csv_error(e.to_string()))?;
+      }
+
+      let bytes = writer.into_inner().map_err(|e| csv_error(e.to_string()))?;
+
+      String::from_utf8(bytes).map_err(|e| csv_error(e.to_string()))
+    }
+    RedfishEndpointFileFormat::Yaml => serde_yaml::to_string(record_vec)
+      .map_err(|e| Error::Message(format!("Could not render YAML: {e}"))),
+  }
+}
+
+/// Checks the ID of each redfish endpoint is a BMC xname and no ID, FQDN, MAC or IP
+/// address is used by more than one endpoint. Returns every problem found
+pub fn validate(record_vec: &[RedfishEndpointRecord]) -> Result<(), Error> {
+  let mut error_vec = Vec::new();
+
+  for record in record_vec {
+    if !validate_bmc_xname_format(&record.id) {
+      error_vec.push(format!("'{}' is not a BMC xname", record.id));
+    }
+  }
+
+  let field_vec: [(&str, fn(&RedfishEndpointRecord) -> Option<&str>); 4] = [
+    ("ID", |record| Some(record.id.as_str())),
+    ("FQDN", |record| record.fqdn.as_deref()),
+    ("MAC address", |record| record.mac_addr.as_deref()),
+    ("IP address", |record| record.ip_address.as_deref()),
+  ];
+
+  for (field_name, get_field) in field_vec {
+    let mut id_per_value: HashMap<String, Vec<&str>> = HashMap::new();
+
+    for record in record_vec {
+      if let Some(value) = get_field(record).filter(|value| !value.is_empty()) {
+        id_per_value
+          .entry(value.to_lowercase())
+          .or_default()
+          .push(&record.id);
+      }
+    }
+
+    let mut duplicate_vec: Vec<(String, Vec<&str>)> = id_per_value
+      .into_iter()
+      .filter(|(_, id_vec)| id_vec.len() > 1)
+      .collect();
+
+    duplicate_vec.sort();
+
+    for (value, id_vec) in duplicate_vec {
+      error_vec.push(format!(
+        "{} '{}' used by more than one endpoint: {}",
+        field_name,
+        value,
+        id_vec.join(", ")
+      ));
+    }
+  }
+
+  if error_vec.is_empty() {
+    Ok(())
+  } else {
+    Err(Error::Message(format!(
+      "Redfish endpoints not valid:\n - {}",
+      error_vec.join("\n - ")
+    )))
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+use super::{validate, RedfishEndpointRecord};
+
+fn record(id: &str, fqdn: &str, mac_addr: &str) -> RedfishEndpointRecord {
+  RedfishEndpointRecord {
+    id: id.to_string(),
+    fqdn: Some(fqdn.to_string()),
+    mac_addr: Some(mac_addr.to_string()),
+    ..Default::default()
+  }
+}
+
+fn get_error_message(record_vec: &[RedfishEndpointRecord]) -> String {
+  validate(record_vec).unwrap_err().to_string()
+}
+
+#[test]
+fn test_validate_bmc_xnames() {
+  let record_vec = vec![
+    record("x1000c0s0b0", "x1000c0s0b0.hmn", "a4:bf:01:00:00:01"),
+    record("x1000c0r1b0", "x1000c0r1b0.hmn", "a4:bf:01:00:00:02"),
+    record("x1000c0b0", "x1000c0b0.hmn", "a4:bf:01:00:00:03"),
+    record("x1000m0", "x1000m0.hmn", "a4:bf:01:00:00:04"),
+  ];
+
+  assert!(validate(&record_vec).is_ok());
+}
+
+#[test]
+fn test_validate_not_bmc_xname() {
+  let record_vec = vec![
+    record("x1000c0s0b0n0", "x1000c0s0b0n0.hmn", "a4:bf:01:00:00:01"),
+    record("nid000001", "nid000001.hmn", "a4:bf:01:00:00:02"),
+  ];
+
+  let error_message = get_error_message(&record_vec);
+
+  assert!(error_message.contains("'x1000c0s0b0n0' is not a BMC xname"));
+  assert!(error_message.contains("'nid000001' is not a BMC xname"));
+}
+
+#[test]
+fn test_validate_duplicates() {
+  let record_vec = vec![
+    record("x1000c0s0b0", "x1000c0s0b0.hmn", "a4:bf:01:00:00:01"),
+    record("x1000c0s0b0", "X1000C0S0B0.hmn", "a4:bf:01:00:00:02"),
+    record("x1000c0s1b0", "x1000c0s1b0.hmn", "A4:BF:01:00:00:02"),
+  ];
+
+  let error_message = get_error_message(&record_vec);
+
+  assert!(error_message.contains(
+    "ID 'x1000c0s0b0' used by more than one endpoint: x1000c0s0b0, x1000c0s0b0"
+  ));
+  assert!(error_message.contains(
+    "FQDN 'x1000c0s0b0.hmn' used by more than one endpoint: x1000c0s0b0, x1000c0s0b0"
+  ));
+  assert!(error_message.contains(
+    "MAC address 'a4:bf:01:00:00:02' used by more than one endpoint: x1000c0s0b0, x1000c0s1b0"
+  ));
+}
+
+#[test]
+fn test_validate_empty_values_not_duplicates() {
+  let record_vec = vec![
+    record("x1000c0s0b0", "", ""),
+    record("x1000c0s1b0", "", ""),
+    RedfishEndpointRecord {
+      id: "x1000c0s2b0".to_string(),
+      ..Default::default()
+    },
+  ];
+
+  assert!(validate(&record_vec).is_ok());
+}
@@ -0,0 +1,355 @@
+use std::{
+  collections::{HashMap, HashSet},
+  time::Duration,
+};
+
+use clap::ArgMatches;
+use indicatif::{ProgressBar, ProgressStyle};
+use manta_backend_dispatcher::{
+  audit::AuditOutcome,
+  error::Error,
+  interfaces::{cfs::CfsTrait, hsm::component::ComponentTrait},
+};
+
+use crate::{
+  common::pcs_utils::{self, PowerAction},
+  manta_backend_dispatcher::StaticBackendDispatcher,
+};
+
+/// Seconds between two checks of the health of the nodes in a batch
+const HEALTH_CHECK_INTERVAL: u64 = 10;
+
+/// Seconds after the power reset after which a node in 'Ready' state is considered rebooted
+/// even if it was never seen down (HSM state changes may happen between two checks). Capped
+/// to half the health timeout so nodes never seen down can still be healthy before it
+const GRACE_PERIOD: u64 = 300;
+
+/// Reboots nodes in batches. A batch is rebooted only once the nodes in the previous batch are
+/// healthy again ('Ready' in HSM and CFS configuration status 'configured')
+#[derive(Debug, Clone, Copy)]
+pub struct RollingReboot {
+  /// Max number of nodes rebooted at the same time
+  pub batch_size: usize,
+  /// Max number of nodes down at the same time, this includes the nodes that failed
+  pub max_unavailable: usize,
+  /// Max number of nodes that can fail before the remaining batches are aborted
+  pub max_failed: usize,
+  /// Seconds to wait for the nodes in a batch to be healthy
+  pub health_timeout: u64,
+}
+
+/// Result of a rolling reboot
+#[derive(Debug, Default)]
+pub struct RollingRebootSummary {
+  /// Nodes rebooted and healthy
+  pub healthy: Vec<String>,
+  /// Nodes whose power reset failed or that did not get healthy on time
+  pub failed: Vec<String>,
+  /// Nodes not rebooted because the rolling reboot was aborted
+  pub skipped: Vec<String>,
+}
+
+impl RollingRebootSummary {
+  /// Audit outcome of the rolling reboot, failure if any node failed or was skipped
+  pub fn audit_outcome(&self) -> AuditOutcome {
+    if self.failed.is_empty() && self.skipped.is_empty() {
+      AuditOutcome::Success
+    } else {
+      AuditOutcome::Failure
+    }
+  }
+
+  /// Audit message with the rolling reboot settings and the nodes failed or skipped
+  pub fn audit_message(&self, rolling_reboot: &RollingReboot) -> String {
+    let mut message = format!(
+      "rolling reboot, batch size {}, max unavailable {}",
+      rolling_reboot.batch_size, rolling_reboot.max_unavailable
+    );
+
+    if !self.failed.is_empty() {
+      message.push_str(&format!(", failed: {}", self.failed.join(", ")));
+    }
+
+    if !self.skipped.is_empty() {
+      message.push_str(&format!(", skipped: {}", self.skipped.join(", ")));
+    }
+
+    message
+  }
+}
+
+impl RollingReboot {
+  /// Reads the rolling reboot arguments of a command. Returns `None` if neither
+  /// '--batch-size' nor '--max-unavailable' is set
+  pub fn from_arg_matches(arg_matches: &ArgMatches) -> Option<Self> {
+    let batch_size_opt = arg_matches
+      .get_one::<u64>("batch-size")
+      .map(|batch_size| *batch_size as usize);
+    let max_unavailable_opt = arg_matches
+      .get_one::<u64>("max-unavailable")
+      .map(|max_unavailable| *max_unavailable as usize);
+
+    if batch_size_opt.is_none() && max_unavailable_opt.is_none() {
+      return None;
+    }
+
+    Some(RollingReboot {
+      batch_size: batch_size_opt.or(max_unavailable_opt)?,
+      max_unavailable: max_unavailable_opt.or(batch_size_opt)?,
+      max_failed: *arg_matches
+        .get_one::<usize>("max-failed")
+        .expect("The 'max-failed' argument must have a value"),
+      health_timeout: *arg_matches
+        .get_one::<u64>("health-timeout")
+        .expect("The 'health-timeout' argument must have a value"),
+    })
+  }
+
+  /// Number of nodes to reboot in the next batch, considering the nodes already failed
+  pub fn next_batch_size(&self, failed: usize) -> usize {
+    self
+      .batch_size
+      .min(self.max_unavailable.saturating_sub(failed))
+  }
+}
+
+/// Power resets the nodes in batches and waits for each batch to be healthy before moving to
+/// the next one. Aborts the remaining batches once more than `max_failed` nodes failed.
+/// Backend errors fail the nodes of the batch instead of aborting, so the summary is always
+/// returned
+pub async fn exec(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  shasta_base_url: &str,
+  shasta_root_cert: &[u8],
+  xname_vec: &[String],
+  force: bool,
+  rolling_reboot: &RollingReboot,
+) -> RollingRebootSummary {
+  let mut summary = RollingRebootSummary::default();
+
+  let mut pending_xname_vec = xname_vec.to_vec();
+  let mut batch_num = 0;
+
+  while !pending_xname_vec.is_empty() {
+    let batch_size = rolling_reboot.next_batch_size(summary.failed.len());
+
+    if summary.failed.len() > rolling_reboot.max_failed || batch_size == 0 {
+      eprintln!(
+        "ERROR - {} node/s failed (max failed {}, max unavailable {}). Aborting rolling reboot",
+        summary.failed.len(),
+        rolling_reboot.max_failed,
+        rolling_reboot.max_unavailable
+      );
+      summary.skipped = pending_xname_vec;
+      break;
+    }
+
+    let batch_size = batch_size.min(pending_xname_vec.len());
+    let batch: Vec<String> = pending_xname_vec.drain(..batch_size).collect();
+
+    batch_num += 1;
+
+    println!(
+      "Batch {}: rebooting node/s {} ({} remaining)",
+      batch_num,
+      batch.join(", "),
+      pending_xname_vec.len()
+    );
+
+    let power_failed_xname_vec = match pcs_utils::exec_power_transition(
+      backend,
+      shasta_token,
+      PowerAction::Reset { force },
+      &batch,
+      false,
+      0,
+    )
+    .await
+    {
+      Ok(transition_vec) => transition_vec
+        .last()
+        .map(pcs_utils::get_failed_xname_vec)
+        .unwrap_or_default(),
+      Err(e) => {
+        eprintln!(
+          "ERROR - Could not power reset batch {}. Reason:\n{}",
+          batch_num, e
+        );
+        batch.clone()
+      }
+    };
+
+    let rebooted_xname_vec: Vec<String> = batch
+      .iter()
+      .filter(|xname| !power_failed_xname_vec.contains(xname))
+      .cloned()
+      .collect();
+
+    let unhealthy_xname_vec = wait_until_healthy(
+      backend,
+      shasta_token,
+      shasta_base_url,
+      shasta_root_cert,
+      &rebooted_xname_vec,
+      rolling_reboot.health_timeout,
+    )
+    .await;
+
+    for xname in batch {
+      if power_failed_xname_vec.contains(&xname)
+        || unhealthy_xname_vec.contains(&xname)
+      {
+        summary.failed.push(xname);
+      } else {
+        summary.healthy.push(xname);
+      }
+    }
+  }
+
+  summary
+}
+
+/// Waits for the nodes to go down and come back 'Ready' in HSM with CFS configuration status
+/// 'configured'. Returns the nodes whose CFS configuration failed or that were not healthy
+/// before the timeout. Backend errors are retried until the timeout
+pub async fn wait_until_healthy(
+  backend: &StaticBackendDispatcher,
+  shasta_token: &str,
+  shasta_base_url: &str,
+  shasta_root_cert: &[u8],
+  xname_vec: &[String],
+  health_timeout: u64,
+) -> Vec<String> {
+  let progress_bar = ProgressBar::new(xname_vec.len() as u64).with_style(
+    ProgressStyle::with_template(
+      "{spinner} Waiting for nodes to be healthy [{bar:40}] {pos}/{len} nodes ({elapsed})",
+    )
+    .unwrap()
+    .progress_chars("=> "),
+  );
+
+  progress_bar.enable_steady_tick(Duration::from_millis(200));
+
+  let start = tokio::time::Instant::now();
+  let grace_period = GRACE_PERIOD.min(health_timeout / 2);
+
+  let mut pending_xname_vec = xname_vec.to_vec();
+  let mut seen_down_xname_set: HashSet<String> = HashSet::new();
+  let mut unhealthy_xname_vec = Vec::new();
+
+  while !pending_xname_vec.is_empty()
+    && start.elapsed().as_secs() < health_timeout
+  {
+    let hsm_state_map: HashMap<String, String> =
+      match backend.get_all_nodes(shasta_token, None).await {
+        Ok(node_metadata_array) => node_metadata_array
+          .components
+          .unwrap_or_default()
+          .into_iter()
+          .filter_map(|component| component.id.zip(component.state))
+          .collect(),
+        Err(e) => {
+          progress_bar
+            .println(format!("WARNING - Could not get HSM state: {}", e));
+          tokio::time::sleep(Duration::from_secs(HEALTH_CHECK_INTERVAL)).await;
+          continue;
+        }
+      };
+
+    let cfs_status_map_opt: Option<HashMap<String, String>> = match backend
+      .get_cfs_components(
+        shasta_token,
+        shasta_base_url,
+        shasta_root_cert,
+        None,
+        Some(&pending_xname_vec.join(",")),
+        None,
+      )
+      .await
+    {
+      Ok(component_vec) => Some(
+        component_vec
+          .into_iter()
+          .filter_map(|component| {
+            component.id.zip(component.configuration_status)
+          })
+          .collect(),
+      ),
+      Err(Error::NotImplemented(_)) => {
+        log::warn!("CFS not available, skipping configuration status check");
+        None
+      }
+      Err(e) => {
+        progress_bar.println(format!(
+          "WARNING - Could not get CFS configuration status: {}",
+          e
+        ));
+        tokio::time::sleep(Duration::from_secs(HEALTH_CHECK_INTERVAL)).await;
+        continue;
+      }
+    };
+
+    let grace_period_over = start.elapsed().as_secs() >= grace_period;
+
+    let mut still_pending_xname_vec = Vec::new();
+
+    for xname in pending_xname_vec {
+      let hsm_state = hsm_state_map
+        .get(&xname)
+        .map(String::as_str)
+        .unwrap_or_default();
+
+      let configuration_status = cfs_status_map_opt
+        .as_ref()
+        .map(|cfs_status_map| {
+          cfs_status_map
+            .get(&xname)
+            .map(String::as_str)
+            .unwrap_or_default()
+        })
+        .unwrap_or("configured");
+
+      if hsm_state != "Ready" {
+        seen_down_xname_set.insert(xname.clone());
+      }
+
+      // Status from before the reboot is not taken into account
+      let rebooted = seen_down_xname_set.contains(&xname) || grace_period_over;
+
+      if rebooted && configuration_status == "failed" {
+        progress_bar
+          .println(format!("{}: CFS configuration status failed", xname));
+        progress_bar.inc(1);
+        unhealthy_xname_vec.push(xname);
+      } else if rebooted
+        && hsm_state == "Ready"
+        && configuration_status == "configured"
+      {
+        progress_bar.println(format!("{}: healthy", xname));
+        progress_bar.inc(1);
+      } else {
+        still_pending_xname_vec.push(xname);
+      }
+    }
+
+    pending_xname_vec = still_pending_xname_vec;
+
+    if !pending_xname_vec.is_empty() {
+      tokio::time::sleep(Duration::from_secs(HEALTH_CHECK_INTERVAL)).await;
+    }
+  }
+
+  progress_bar.finish_and_clear();
+
+  for xname in &pending_xname_vec {
+    eprintln!("{}: not healthy after {} seconds", xname, health_timeout);
+  }
+
+  unhealthy_xname_vec.extend(pending_xname_vec);
+
+  unhealthy_xname_vec
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,227 @@
+use manta_backend_dispatcher::{
+  audit::AuditOutcome, mock::backend_connector::MockBackend,
+  mock::types::MockState,
+};
+use serde_json::{json, Value};
+
+use crate::manta_backend_dispatcher::StaticBackendDispatcher;
+
+use super::{RollingReboot, RollingRebootSummary};
+
+const SHASTA_TOKEN: &str = "mock-token";
+
+/// Mock backend with the nodes given as (xname, HSM state, CFS configuration status). Nodes
+/// with HSM state 'Ready' stay 'Ready' after the reboot since the mock does not change HSM
+/// states
+fn mock(node_vec: &[(&str, &str, &str)]) -> MockBackend {
+  let state: MockState = serde_json::from_value(json!({
+    "components": node_vec
+      .iter()
+      .map(|(xname, state, _)| json!({ "ID": xname, "Type": "Node", "State": state }))
+      .collect::<Vec<Value>>(),
+    "cfs_components": node_vec
+      .iter()
+      .map(|(xname, _, configuration_status)| {
+        json!({ "id": xname, "configuration_status": configuration_status })
+      })
+      .collect::<Vec<Value>>(),
+  }))
+  .unwrap();
+
+  MockBackend::from_state(state)
+}
+
+/// Nodes targeted by each power transition created, in order
+fn get_batch_vec(mock: &MockBackend) -> Vec<Vec<String>> {
+  mock
+    .get_state()
+    .power_transitions
+    .iter()
+    .map(|transition| {
+      transition["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| task["xname"].as_str().unwrap().to_string())
+        .collect()
+    })
+    .collect()
+}
+
+fn xname_vec(xname_vec: &[&str]) -> Vec<String> {
+  xname_vec.iter().map(|xname| xname.to_string()).collect()
+}
+
+async fn exec(
+  mock: &MockBackend,
+  xname_vec: &[String],
+  rolling_reboot: &RollingReboot,
+) -> RollingRebootSummary {
+  super::exec(
+    &StaticBackendDispatcher::MOCK(mock.clone()),
+    SHASTA_TOKEN,
+    "https://api.test",
+    &[],
+    xname_vec,
+    false,
+    rolling_reboot,
+  )
+  .await
+}
+
+#[test]
+fn test_next_batch_size() {
+  let rolling_reboot = RollingReboot {
+    batch_size: 3,
+    max_unavailable: 5,
+    max_failed: 10,
+    health_timeout: 600,
+  };
+
+  assert_eq!(rolling_reboot.next_batch_size(0), 3);
+  assert_eq!(rolling_reboot.next_batch_size(3), 2);
+  assert_eq!(rolling_reboot.next_batch_size(5), 0);
+  assert_eq!(rolling_reboot.next_batch_size(7), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_exec_batches() {
+  let mock = mock(&[
+    ("x1000c0s0b0n0", "Ready", "configured"),
+    ("x1000c0s0b0n1", "Ready", "configured"),
+    ("x1000c0s0b1n0", "Ready", "configured"),
+    ("x1000c0s0b1n1", "Ready", "configured"),
+    ("x1000c0s1b0n0", "Ready", "configured"),
+  ]);
+
+  let xname_vec = xname_vec(&[
+    "x1000c0s0b0n0",
+    "x1000c0s0b0n1",
+    "x1000c0s0b1n0",
+    "x1000c0s0b1n1",
+    "x1000c0s1b0n0",
+  ]);
+
+  let rolling_reboot = RollingReboot {
+    batch_size: 2,
+    max_unavailable: 2,
+    max_failed: 0,
+    health_timeout: 600,
+  };
+
+  let summary = exec(&mock, &xname_vec, &rolling_reboot).await;
+
+  assert_eq!(summary.healthy, xname_vec);
+  assert!(summary.failed.is_empty());
+  assert!(summary.skipped.is_empty());
+  assert_eq!(summary.audit_outcome(), AuditOutcome::Success);
+
+  assert_eq!(
+    get_batch_vec(&mock),
+    vec![
+      xname_vec[0..2].to_vec(),
+      xname_vec[2..4].to_vec(),
+      xname_vec[4..].to_vec()
+    ]
+  );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_exec_failed_nodes_count_as_unavailable() {
+  // First node never comes back 'Ready'
+  let mock = mock(&[
+    ("x1000c0s0b0n0", "Off", "configured"),
+    ("x1000c0s0b0n1", "Ready", "configured"),
+    ("x1000c0s0b1n0", "Ready", "configured"),
+    ("x1000c0s0b1n1", "Ready", "configured"),
+  ]);
+
+  let xname_vec = xname_vec(&[
+    "x1000c0s0b0n0",
+    "x1000c0s0b0n1",
+    "x1000c0s0b1n0",
+    "x1000c0s0b1n1",
+  ]);
+
+  let rolling_reboot = RollingReboot {
+    batch_size: 2,
+    max_unavailable: 2,
+    max_failed: 3,
+    health_timeout: 600,
+  };
+
+  let summary = exec(&mock, &xname_vec, &rolling_reboot).await;
+
+  assert_eq!(summary.healthy, xname_vec[1..].to_vec());
+  assert_eq!(summary.failed, xname_vec[..1].to_vec());
+  assert!(summary.skipped.is_empty());
+
+  // Once a node failed, only one node can be down at a time
+  assert_eq!(
+    get_batch_vec(&mock),
+    vec![
+      xname_vec[0..2].to_vec(),
+      xname_vec[2..3].to_vec(),
+      xname_vec[3..].to_vec()
+    ]
+  );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_exec_aborts_after_max_failed() {
+  // First node is not in HSM, its power reset fails
+  let mock = mock(&[
+    ("x1000c0s0b0n1", "Ready", "configured"),
+    ("x1000c0s0b1n0", "Ready", "configured"),
+  ]);
+
+  let xname_vec =
+    xname_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s0b1n0"]);
+
+  let rolling_reboot = RollingReboot {
+    batch_size: 1,
+    max_unavailable: 3,
+    max_failed: 0,
+    health_timeout: 600,
+  };
+
+  let summary = exec(&mock, &xname_vec, &rolling_reboot).await;
+
+  assert!(summary.healthy.is_empty());
+  assert_eq!(summary.failed, xname_vec[..1].to_vec());
+  assert_eq!(summary.skipped, xname_vec[1..].to_vec());
+  assert_eq!(get_batch_vec(&mock), vec![xname_vec[..1].to_vec()]);
+
+  assert_eq!(summary.audit_outcome(), AuditOutcome::Failure);
+  assert_eq!(
+    summary.audit_message(&rolling_reboot),
+    "rolling reboot, batch size 1, max unavailable 3, failed: x1000c0s0b0n0, skipped: x1000c0s0b0n1, x1000c0s0b1n0"
+  );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_wait_until_healthy() {
+  let mock = mock(&[
+    ("x1000c0s0b0n0", "Ready", "configured"),
+    ("x1000c0s0b0n1", "Off", "configured"),
+    ("x1000c0s0b1n0", "Off", "failed"),
+  ]);
+
+  let xname_vec =
+    xname_vec(&["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s0b1n0"]);
+
+  // Health timeout shorter than the grace period, nodes never seen down are still healthy
+  let mut unhealthy_xname_vec = super::wait_until_healthy(
+    &StaticBackendDispatcher::MOCK(mock),
+    SHASTA_TOKEN,
+    "https://api.test",
+    &[],
+    &xname_vec,
+    60,
+  )
+  .await;
+
+  unhealthy_xname_vec.sort();
+
+  assert_eq!(unhealthy_xname_vec, xname_vec[1..].to_vec());
+}
@@ -0,0 +1,197 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fmt::Display,
+  future::Future,
+  io::Write,
+};
+
+use chrono::Local;
+use comfy_table::{Cell, Color, Table};
+use crossterm::{
+  cursor::MoveTo,
+  execute,
+  terminal::{Clear, ClearType},
+};
+use csm_rs::node::types::NodeDetails;
+use manta_backend_dispatcher::{
+  error::Error, types::cfs::session::CfsSessionGetResponse,
+};
+use tokio::time::{sleep, Duration};
+
+use crate::common::output::{
+  self, OutputFormat, OutputRecord, OutputSelection,
+};
+
+/// A record `--watch` knows how to track across polls. `watch_key` identifies the record and
+/// `watch_state` contains the fields whose changes get highlighted
+pub trait WatchRecord: OutputRecord {
+  fn watch_key(&self) -> String;
+
+  fn watch_state(&self) -> String;
+}
+
+/// Polls `fetch` every `interval_secs` seconds and redraws the table in place until the user
+/// presses Ctrl-C. Rows whose state changed since the previous poll are highlighted. Errors
+/// fetching data are shown on screen and the next poll is tried anyway, CSM APIs may not
+/// answer while nodes are rebooting. The query and custom columns requested by the user are
+/// applied to every poll
+pub async fn watch<T, E, F, Fut>(
+  title: &str,
+  interval_secs: u64,
+  wide: bool,
+  selection: &OutputSelection,
+  mut fetch: F,
+) -> Result<(), Error>
+where
+  T: WatchRecord,
+  E: Display,
+  F: FnMut() -> Fut,
+  Fut: Future<Output = Result<Vec<T>, E>>,
+{
+  let mut previous_state_map_opt: Option<HashMap<String, String>> = None;
+
+  let mut stdout = std::io::stdout();
+
+  loop {
+    let screen = match fetch().await {
+      Ok(mut record_vec) => {
+        record_vec.sort_by_key(|record| record.watch_key());
+
+        let changed_key_set =
+          get_changed_key_set(&record_vec, previous_state_map_opt.as_ref());
+
+        previous_state_map_opt = Some(get_state_map(&record_vec));
+
+        get_screen(&record_vec, &changed_key_set, wide, selection)
+          .unwrap_or_else(|e| {
+            format!("ERROR - Could not render data. Reason:\n{e}")
+          })
+      }
+      Err(e) => format!("ERROR - Could not fetch data. Reason:\n{e}"),
+    };
+
+    execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+
+    writeln!(
+      stdout,
+      "Every {}s: {}\t{}\n\n{}",
+      interval_secs,
+      title,
+      Local::now().format("%d/%m/%Y %H:%M:%S"),
+      screen
+    )?;
+
+    tokio::select! {
+      _ = sleep(Duration::from_secs(interval_secs)) => {},
+      _ = tokio::signal::ctrl_c() => break,
+    }
+  }
+
+  Ok(())
+}
+
+/// State of each record, by key
+pub fn get_state_map<T: WatchRecord>(
+  record_vec: &[T],
+) -> HashMap<String, String> {
+  record_vec
+    .iter()
+    .map(|record| (record.watch_key(), record.watch_state()))
+    .collect()
+}
+
+/// Keys of the records new or whose state changed since the previous poll. Nothing changed
+/// on the first poll
+pub fn get_changed_key_set<T: WatchRecord>(
+  record_vec: &[T],
+  previous_state_map_opt: Option<&HashMap<String, String>>,
+) -> HashSet<String> {
+  let Some(previous_state_map) = previous_state_map_opt else {
+    return HashSet::new();
+  };
+
+  record_vec
+    .iter()
+    .filter(|record| {
+      previous_state_map
+        .get(&record.watch_key())
+        .is_none_or(|previous_state| previous_state != &record.watch_state())
+    })
+    .map(|record| record.watch_key())
+    .collect()
+}
+
+/// Renders the table of a poll. Rows are only highlighted without query nor custom columns,
+/// since those may filter, merge or reorder the records
+pub fn get_screen<T: WatchRecord>(
+  record_vec: &[T],
+  changed_key_set: &HashSet<String>,
+  wide: bool,
+  selection: &OutputSelection,
+) -> Result<String, Error> {
+  if selection.is_empty() {
+    return Ok(get_table(record_vec, changed_key_set, wide).to_string());
+  }
+
+  let format = if wide {
+    OutputFormat::TableWide
+  } else {
+    OutputFormat::Table
+  };
+
+  output::render_selection(record_vec, format, selection)
+}
+
+pub fn get_table<T: WatchRecord>(
+  record_vec: &[T],
+  changed_key_set: &HashSet<String>,
+  wide: bool,
+) -> Table {
+  let mut table = Table::new();
+
+  table.set_header(T::header(wide));
+
+  for record in record_vec {
+    let changed = changed_key_set.contains(&record.watch_key());
+
+    table.add_row(record.row(wide).into_iter().map(|value| {
+      if changed {
+        Cell::new(value).fg(Color::Yellow)
+      } else {
+        Cell::new(value)
+      }
+    }));
+  }
+
+  table
+}
+
+impl WatchRecord for NodeDetails {
+  fn watch_key(&self) -> String {
+    self.xname.clone()
+  }
+
+  fn watch_state(&self) -> String {
+    format!("{}/{}", self.power_status, self.configuration_status)
+  }
+}
+
+impl WatchRecord for CfsSessionGetResponse {
+  fn watch_key(&self) -> String {
+    self.name.clone().unwrap_or_default()
+  }
+
+  fn watch_state(&self) -> String {
+    let status = self
+      .status
+      .as_ref()
+      .and_then(|status| status.session.as_ref())
+      .and_then(|session| session.status.clone())
+      .unwrap_or_default();
+
+    format!("{}/{}", status, self.is_success())
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+
+use csm_rs::node::types::NodeDetails;
+
+use crate::common::output::{self, OutputFormat, OutputSelection};
+
+use super::{get_changed_key_set, get_screen, get_state_map, get_table};
+
+fn node(
+  xname: &str,
+  power_status: &str,
+  configuration_status: &str,
+) -> NodeDetails {
+  NodeDetails {
+    xname: xname.to_string(),
+    nid: "nid000001".to_string(),
+    hsm: "zinal".to_string(),
+    power_status: power_status.to_string(),
+    desired_configuration: "zinal-cos".to_string(),
+    configuration_status: configuration_status.to_string(),
+    enabled: "true".to_string(),
+    error_count: "0".to_string(),
+    boot_image_id: "3b2f4c2e".to_string(),
+    boot_configuration: "zinal-cos".to_string(),
+    kernel_params: "console=ttyS0 quiet".to_string(),
+  }
+}
+
+fn key_set(key_vec: &[&str]) -> HashSet<String> {
+  key_vec.iter().map(|key| key.to_string()).collect()
+}
+
+#[test]
+fn test_get_changed_key_set_first_poll() {
+  let node_vec = vec![node("x1000c0s0b0n0", "ON", "configured")];
+
+  assert!(get_changed_key_set(&node_vec, None).is_empty());
+}
+
+#[test]
+fn test_get_changed_key_set() {
+  let previous_state_map = get_state_map(&[
+    node("x1000c0s0b0n0", "ON", "configured"),
+    node("x1000c0s0b0n1", "ON", "pending"),
+    node("x1000c0s0b1n0", "OFF", "configured"),
+    node("x1000c0s0b1n1", "ON", "configured"),
+  ]);
+
+  // Power status changed, configuration status changed, new node, same state. The node
+  // removed is not reported
+  let node_vec = vec![
+    node("x1000c0s0b0n0", "OFF", "configured"),
+    node("x1000c0s0b0n1", "ON", "configured"),
+    node("x1000c0s0b1n0", "OFF", "configured"),
+    node("x1000c0s1b0n0", "ON", "configured"),
+  ];
+
+  assert_eq!(
+    get_changed_key_set(&node_vec, Some(&previous_state_map)),
+    key_set(&["x1000c0s0b0n0", "x1000c0s0b0n1", "x1000c0s1b0n0"])
+  );
+
+  // Next poll without changes
+  assert!(
+    get_changed_key_set(&node_vec, Some(&get_state_map(&node_vec))).is_empty()
+  );
+}
+
+#[test]
+fn test_get_state_map() {
+  assert_eq!(
+    get_state_map(&[node("x1000c0s0b0n0", "ON", "configured")]),
+    HashMap::from([("x1000c0s0b0n0".to_string(), "ON/configured".to_string())])
+  );
+}
+
+#[test]
+fn test_get_table_highlights_changed_rows() {
+  let node_vec = vec![
+    node("x1000c0s0b0n0", "OFF", "configured"),
+    node("x1000c0s0b0n1", "ON", "configured"),
+  ];
+
+  let mut table = get_table(&node_vec, &key_set(&["x1000c0s0b0n0"]), false);
+  table.enforce_styling();
+
+  let table = table.to_string();
+
+  let line = |xname: &str| {
+    table
+      .lines()
+      .find(|line| line.contains(xname))
+      .unwrap()
+      .to_string()
+  };
+
+  assert!(line("x1000c0s0b0n0").contains('\u{1b}'));
+  assert!(!line("x1000c0s0b0n1").contains('\u{1b}'));
+}
+
+#[test]
+fn test_get_screen() {
+  let node_vec = vec![
+    node("x1000c0s0b0n0", "OFF", "configured"),
+    node("x1000c0s0b0n1", "ON", "configured"),
+  ];
+
+  let changed_key_set = key_set(&["x1000c0s0b0n0"]);
+
+  assert_eq!(
+    get_screen(
+      &node_vec,
+      &changed_key_set,
+      true,
+      &OutputSelection::default()
+    )
+    .unwrap(),
+    get_table(&node_vec, &changed_key_set, true).to_string()
+  );
+
+  let screen = get_screen(
+    &node_vec,
+    &changed_key_set,
+    false,
+    &OutputSelection::default(),
+  )
+  .unwrap();
+
+  assert!(screen.contains("x1000c0s0b0n1"));
+  assert!(!screen.contains("console=ttyS0"));
+}
+
+#[test]
+fn test_get_screen_selection() {
+  let node_vec = vec![
+    node("x1000c0s0b0n0", "OFF", "configured"),
+    node("x1000c0s0b0n1", "ON", "configured"),
+  ];
+
+  let selection = OutputSelection::new(
+    Some("[?power_status=='ON']"),
+    Some("xname,POWER=power_status"),
+  )
+  .unwrap();
+
+  let screen =
+    get_screen(&node_vec, &key_set(&["x1000c0s0b0n0"]), false, &selection)
+      .unwrap();
+
+  assert_eq!(
+    screen,
+    output::render_selection(&node_vec, OutputFormat::Table, &selection)
+      .unwrap()
+  );
+  assert!(screen.contains("x1000c0s0b0n1"));
+  assert!(!screen.contains("x1000c0s0b0n0"));
+  assert!(!screen.contains("configured"));
+}
@@ -3,9 +3,9 @@ mod common;
 mod manta_backend_dispatcher;
 
 use ::manta_backend_dispatcher::{
-  interfaces::hsm::group::GroupTrait, types::K8sAuth,
+  contracts::BackendTrait, interfaces::hsm::group::GroupTrait, types::K8sAuth,
 };
-use common::{config::types::MantaConfiguration, kafka::Kafka};
+use common::config::types::MantaConfiguration;
 use manta_backend_dispatcher::StaticBackendDispatcher;
 
 use crate::common::log_ops;
@@ -43,6 +43,8 @@ async fn main() -> core::result::Result<(), Box<dyn std::error::Error>> {
   let shasta_api_url = match backend_tech.as_str() {
     "csm" => shasta_barebone_url.to_owned() + "/apis",
     "ochami" => shasta_barebone_url.to_owned(),
+    // Mock backend does not call any API, its state comes from 'mock_fixture_file'
+    "mock" => shasta_base_url.to_owned(),
     _ => {
       eprintln!("ERROR - Invalid backend technology: {}", backend_tech);
       std::process::exit(1);
@@ -66,18 +68,16 @@ async fn main() -> core::result::Result<(), Box<dyn std::error::Error>> {
       });
   log::debug!("config - vault_base_url:  {vault_base_url:?}");
 
-  // let audit_detail = settings.get_table("audit").unwrap();
-  let audit_detail = configuration.auditor.clone();
-  let audit_kafka_opt: Option<Kafka> = if audit_detail.is_some() {
-    Some(audit_detail.unwrap().kafka)
-  } else {
-    None
-  };
+  let auditor_opt = configuration
+    .auditor
+    .clone()
+    .filter(|auditor| !auditor.is_empty())
+    .map(|auditor| auditor.with_site(&site_name));
 
   let log_level = settings.get_string("log").unwrap_or("error".to_string());
   log::debug!("config - log_level:  {log_level}");
 
-  if audit_kafka_opt.is_none() {
+  if auditor_opt.is_none() {
     log::warn!("config - Auditor not defined");
   }
 
@@ -113,6 +113,9 @@ async fn main() -> core::result::Result<(), Box<dyn std::error::Error>> {
 
   let shasta_root_cert = if let Ok(shasta_root_cert) = shasta_root_cert_rslt {
     shasta_root_cert
+  } else if backend_tech == "mock" {
+    // Mock backend does not open any connection, CA root file is not needed
+    Vec::new()
   } else {
     eprintln!(
       "ERROR - CA public root file '{}' not found. Exit",
@@ -125,10 +128,14 @@ async fn main() -> core::result::Result<(), Box<dyn std::error::Error>> {
     &backend_tech,
     &shasta_api_url,
     &shasta_root_cert,
+    site_detail_value.mock_fixture_file.as_deref(),
   );
 
   // Process input params
-  let cli = crate::cli::build::build_cli();
+  let cli = crate::cli::build::hide_unsupported_subcommands(
+    crate::cli::build::build_cli(),
+    &backend.capabilities(),
+  );
 
   let cli_result = crate::cli::process::process_cli(
     cli,
@@ -139,7 +146,7 @@ async fn main() -> core::result::Result<(), Box<dyn std::error::Error>> {
     &gitea_base_url,
     settings_hsm_group_name_opt.as_ref(),
     k8s_api_url,
-    audit_kafka_opt.as_ref(),
+    auditor_opt.as_ref(),
     &settings,
     &configuration,
   )
@@ -147,6 +154,9 @@ async fn main() -> core::result::Result<(), Box<dyn std::error::Error>> {
 
   match cli_result {
     Ok(_) => Ok(()),
-    Err(e) => panic!("{}", e),
+    Err(e) => {
+      common::error::print_error(e.as_ref());
+      std::process::exit(1);
+    }
   }
 }
@@ -35,6 +35,7 @@ use manta_backend_dispatcher::{
   types::{
     self,
     bos::session_template::BosSessionTemplate,
+    capability::Capabilities,
     cfs::{
       cfs_configuration_details::LayerDetails,
       cfs_configuration_request::CfsConfigurationRequest,
@@ -43,6 +44,7 @@ use manta_backend_dispatcher::{
     },
     hsm::inventory::{RedfishEndpoint, RedfishEndpointArray},
     ims::Image,
+    pcs::power_cap::types::PowerCapTaskInfo,
     BootParameters, Component, ComponentArrayPostArray, Group,
     HWInventoryByLocationList, K8sDetails, NodeMetadataArray,
   },
@@ -53,7 +55,9 @@ use futures::AsyncBufRead;
 use tokio::io::{AsyncRead, AsyncWrite};
 use StaticBackendDispatcher::*;
 
+use crate::common::cache;
 use csm_rs::backend_connector::Csm;
+use manta_backend_dispatcher::mock::backend_connector::MockBackend;
 use ochami_rs::backend_connector::Ochami;
 use serde_json::Value;
 
@@ -61,16 +65,41 @@ use serde_json::Value;
 pub enum StaticBackendDispatcher {
   CSM(Csm),
   OCHAMI(Ochami),
+  MOCK(MockBackend),
 }
 
 impl StaticBackendDispatcher {
-  pub fn new(backend_type: &str, base_url: &str, root_cert: &[u8]) -> Self {
+  pub fn new(
+    backend_type: &str,
+    base_url: &str,
+    root_cert: &[u8],
+    mock_fixture_file_opt: Option<&str>,
+  ) -> Self {
     let csm = Csm::new(base_url, root_cert);
     let ochami = Ochami::new(base_url, root_cert);
 
     match backend_type {
       "csm" => Self::CSM(csm).into(),
       "ochami" => Self::OCHAMI(ochami).into(),
+      "mock" => {
+        let Some(mock_fixture_file) = mock_fixture_file_opt else {
+          eprintln!(
+            "ERROR - Backend 'mock' requires 'mock_fixture_file' in the site configuration"
+          );
+          std::process::exit(1);
+        };
+
+        match MockBackend::new(mock_fixture_file) {
+          Ok(mock) => Self::MOCK(mock),
+          Err(e) => {
+            eprintln!(
+              "ERROR - Could not load mock backend fixture '{}'. Reason:\n{}",
+              mock_fixture_file, e
+            );
+            std::process::exit(1);
+          }
+        }
+      }
       _ => {
         eprintln!("ERROR - Backend '{}' not supported", backend_type);
         std::process::exit(1);
@@ -84,10 +113,14 @@ impl GroupTrait for StaticBackendDispatcher {
     &self,
     auth_token: &str,
   ) -> Result<Vec<Group>, Error> {
-    match self {
-      CSM(b) => b.get_group_available(auth_token).await,
-      OCHAMI(b) => b.get_group_available(auth_token).await,
-    }
+    cache::get_or_fetch("hsm/groups/available", auth_token, &(), || async {
+      match self {
+        CSM(b) => b.get_group_available(auth_token).await,
+        OCHAMI(b) => b.get_group_available(auth_token).await,
+        MOCK(b) => b.get_group_available(auth_token).await,
+      }
+    })
+    .await
   }
 
   async fn get_group_name_available(
@@ -97,6 +130,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_group_name_available(jwt_token).await,
       OCHAMI(b) => b.get_group_name_available(jwt_token).await,
+      MOCK(b) => b.get_group_name_available(jwt_token).await,
     }
   }
 
@@ -108,6 +142,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.add_group(auth_token, hsm_group).await,
       OCHAMI(b) => b.add_group(auth_token, hsm_group).await,
+      MOCK(b) => b.add_group(auth_token, hsm_group).await,
     }
   }
 
@@ -126,6 +161,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_member_vec_from_group_name_vec(auth_token, hsm_group_name_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_member_vec_from_group_name_vec(auth_token, hsm_group_name_vec)
+          .await
+      }
     }
   }
 
@@ -143,6 +182,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_group_map_and_filter_by_group_vec(auth_token, hsm_name_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_group_map_and_filter_by_group_vec(auth_token, hsm_name_vec)
+          .await
+      }
     }
   }
 
@@ -160,6 +203,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_group_map_and_filter_by_member_vec(auth_token, member_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_group_map_and_filter_by_member_vec(auth_token, member_vec)
+          .await
+      }
     }
   }
 
@@ -170,6 +217,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_all_groups(auth_token).await,
       OCHAMI(b) => b.get_all_groups(auth_token).await,
+      MOCK(b) => b.get_all_groups(auth_token).await,
     }
   }
 
@@ -181,6 +229,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_group(auth_token, hsm_name).await,
       OCHAMI(b) => b.get_group(auth_token, hsm_name).await,
+      MOCK(b) => b.get_group(auth_token, hsm_name).await,
     }
   }
 
@@ -192,6 +241,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_groups(auth_token, hsm_name_vec).await,
       OCHAMI(b) => b.get_groups(auth_token, hsm_name_vec).await,
+      MOCK(b) => b.get_groups(auth_token, hsm_name_vec).await,
     }
   }
 
@@ -203,6 +253,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_group(auth_token, hsm_group_label).await,
       OCHAMI(b) => b.delete_group(auth_token, hsm_group_label).await,
+      MOCK(b) => b.delete_group(auth_token, hsm_group_label).await,
     }
   }
 
@@ -220,6 +271,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.get_hsm_map_and_filter_by_hsm_name_vec(auth_token, hsm_name_vec)
           .await
       }
+      MOCK(b) => {
+        b.get_hsm_map_and_filter_by_hsm_name_vec(auth_token, hsm_name_vec)
+          .await
+      }
     }
   }
 
@@ -232,6 +287,7 @@ impl GroupTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.post_member(auth_token, group_label, xname).await,
       OCHAMI(b) => b.post_member(auth_token, group_label, xname).await,
+      MOCK(b) => b.post_member(auth_token, group_label, xname).await,
     }
   }
 
@@ -252,6 +308,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.add_members_to_group(auth_token, group_label, xnames)
           .await
       }
+      MOCK(b) => {
+        b.add_members_to_group(auth_token, group_label, xnames)
+          .await
+      }
     }
   }
 
@@ -270,6 +330,10 @@ impl GroupTrait for StaticBackendDispatcher {
         b.delete_member_from_group(auth_token, group_label, xname)
           .await
       }
+      MOCK(b) => {
+        b.delete_member_from_group(auth_token, group_label, xname)
+          .await
+      }
     }
   }
 
@@ -300,6 +364,15 @@ impl GroupTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.migrate_group_members(
+          auth_token,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          new_target_hsm_members,
+        )
+        .await
+      }
     }
   }
 
@@ -330,6 +403,15 @@ impl GroupTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.update_group_members(
+          auth_token,
+          group_name,
+          members_to_remove,
+          members_to_add,
+        )
+        .await
+      }
     }
   }
 }
@@ -343,6 +425,7 @@ impl HardwareInventory for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_inventory_hardware(auth_token, xname).await,
       OCHAMI(b) => b.get_inventory_hardware(auth_token, xname).await,
+      MOCK(b) => b.get_inventory_hardware(auth_token, xname).await,
     }
   }
 
@@ -369,6 +452,12 @@ impl HardwareInventory for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_inventory_hardware_query(
+          auth_token, xname, r#type, children, parents, partition, format,
+        )
+        .await
+      }
     }
   }
 
@@ -380,6 +469,7 @@ impl HardwareInventory for StaticBackendDispatcher {
     match self {
       CSM(b) => b.post_inventory_hardware(auth_token, hardware).await,
       OCHAMI(b) => b.post_inventory_hardware(auth_token, hardware).await,
+      MOCK(b) => b.post_inventory_hardware(auth_token, hardware).await,
     }
   }
 }
@@ -393,6 +483,7 @@ impl ComponentTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_all_nodes(auth_token, nid_only).await,
       OCHAMI(b) => b.get_all_nodes(auth_token, nid_only).await,
+      MOCK(b) => b.get_all_nodes(auth_token, nid_only).await,
     }
   }
 
@@ -400,10 +491,14 @@ impl ComponentTrait for StaticBackendDispatcher {
     &self,
     auth_token: &str,
   ) -> Result<Vec<Component>, Error> {
-    match self {
-      CSM(b) => b.get_node_metadata_available(auth_token).await,
-      OCHAMI(b) => b.get_node_metadata_available(auth_token).await,
-    }
+    cache::get_or_fetch("hsm/components/available", auth_token, &(), || async {
+      match self {
+        CSM(b) => b.get_node_metadata_available(auth_token).await,
+        OCHAMI(b) => b.get_node_metadata_available(auth_token).await,
+        MOCK(b) => b.get_node_metadata_available(auth_token).await,
+      }
+    })
+    .await
   }
 
   async fn get(
@@ -483,6 +578,32 @@ impl ComponentTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get(
+          auth_token,
+          id,
+          r#type,
+          state,
+          flag,
+          role,
+          subrole,
+          enabled,
+          software_status,
+          subtype,
+          arch,
+          class,
+          nid,
+          nid_start,
+          nid_end,
+          partition,
+          group,
+          state_only,
+          flag_only,
+          role_only,
+          nid_only,
+        )
+        .await
+      }
     }
   }
 
@@ -494,6 +615,7 @@ impl ComponentTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.post_nodes(auth_token, component).await,
       OCHAMI(b) => b.post_nodes(auth_token, component).await,
+      MOCK(b) => b.post_nodes(auth_token, component).await,
     }
   }
 
@@ -505,6 +627,7 @@ impl ComponentTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_node(auth_token, id).await,
       OCHAMI(b) => b.delete_node(auth_token, id).await,
+      MOCK(b) => b.delete_node(auth_token, id).await,
     }
   }
 }
@@ -518,6 +641,7 @@ impl PCSTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.power_on_sync(auth_token, nodes).await,
       OCHAMI(b) => b.power_on_sync(auth_token, nodes).await,
+      MOCK(b) => b.power_on_sync(auth_token, nodes).await,
     }
   }
 
@@ -530,6 +654,7 @@ impl PCSTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.power_off_sync(auth_token, nodes, force).await,
       OCHAMI(b) => b.power_off_sync(auth_token, nodes, force).await,
+      MOCK(b) => b.power_off_sync(auth_token, nodes, force).await,
     }
   }
 
@@ -542,6 +667,103 @@ impl PCSTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.power_reset_sync(auth_token, nodes, force).await,
       OCHAMI(b) => b.power_reset_sync(auth_token, nodes, force).await,
+      MOCK(b) => b.power_reset_sync(auth_token, nodes, force).await,
+    }
+  }
+
+  async fn power_on(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+  ) -> Result<String, Error> {
+    match self {
+      CSM(b) => b.power_on(auth_token, nodes).await,
+      OCHAMI(b) => b.power_on(auth_token, nodes).await,
+      MOCK(b) => b.power_on(auth_token, nodes).await,
+    }
+  }
+
+  async fn power_off(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    match self {
+      CSM(b) => b.power_off(auth_token, nodes, force).await,
+      OCHAMI(b) => b.power_off(auth_token, nodes, force).await,
+      MOCK(b) => b.power_off(auth_token, nodes, force).await,
+    }
+  }
+
+  async fn power_reset(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    match self {
+      CSM(b) => b.power_reset(auth_token, nodes, force).await,
+      OCHAMI(b) => b.power_reset(auth_token, nodes, force).await,
+      MOCK(b) => b.power_reset(auth_token, nodes, force).await,
+    }
+  }
+
+  async fn get_power_transition(
+    &self,
+    auth_token: &str,
+    transition_id: &str,
+  ) -> Result<Value, Error> {
+    match self {
+      CSM(b) => b.get_power_transition(auth_token, transition_id).await,
+      OCHAMI(b) => b.get_power_transition(auth_token, transition_id).await,
+      MOCK(b) => b.get_power_transition(auth_token, transition_id).await,
+    }
+  }
+
+  async fn get_power_transitions(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Value>, Error> {
+    match self {
+      CSM(b) => b.get_power_transitions(auth_token).await,
+      OCHAMI(b) => b.get_power_transitions(auth_token).await,
+      MOCK(b) => b.get_power_transitions(auth_token).await,
+    }
+  }
+
+  async fn power_cap_snapshot(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+  ) -> Result<PowerCapTaskInfo, Error> {
+    match self {
+      CSM(b) => b.power_cap_snapshot(auth_token, nodes).await,
+      OCHAMI(b) => b.power_cap_snapshot(auth_token, nodes).await,
+      MOCK(b) => b.power_cap_snapshot(auth_token, nodes).await,
+    }
+  }
+
+  async fn power_cap_patch(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    control_name: &str,
+    watts: usize,
+  ) -> Result<PowerCapTaskInfo, Error> {
+    match self {
+      CSM(b) => {
+        b.power_cap_patch(auth_token, nodes, control_name, watts)
+          .await
+      }
+      OCHAMI(b) => {
+        b.power_cap_patch(auth_token, nodes, control_name, watts)
+          .await
+      }
+      MOCK(b) => {
+        b.power_cap_patch(auth_token, nodes, control_name, watts)
+          .await
+      }
     }
   }
 }
@@ -551,10 +773,14 @@ impl BootParametersTrait for StaticBackendDispatcher {
     &self,
     auth_token: &str,
   ) -> Result<Vec<BootParameters>, Error> {
-    match self {
-      CSM(b) => b.get_all_bootparameters(auth_token).await,
-      OCHAMI(b) => b.get_all_bootparameters(auth_token).await,
-    }
+    cache::get_or_fetch("bss/bootparameters", auth_token, &(), || async {
+      match self {
+        CSM(b) => b.get_all_bootparameters(auth_token).await,
+        OCHAMI(b) => b.get_all_bootparameters(auth_token).await,
+        MOCK(b) => b.get_all_bootparameters(auth_token).await,
+      }
+    })
+    .await
   }
 
   async fn get_bootparameters(
@@ -565,6 +791,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_bootparameters(auth_token, nodes).await,
       OCHAMI(b) => b.get_bootparameters(auth_token, nodes).await,
+      MOCK(b) => b.get_bootparameters(auth_token, nodes).await,
     }
   }
 
@@ -576,6 +803,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.add_bootparameters(auth_token, boot_parameters).await,
       OCHAMI(b) => b.add_bootparameters(auth_token, boot_parameters).await,
+      MOCK(b) => b.add_bootparameters(auth_token, boot_parameters).await,
     }
   }
 
@@ -587,6 +815,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.update_bootparameters(auth_token, boot_parameters).await,
       OCHAMI(b) => b.update_bootparameters(auth_token, boot_parameters).await,
+      MOCK(b) => b.update_bootparameters(auth_token, boot_parameters).await,
     }
   }
 
@@ -598,6 +827,7 @@ impl BootParametersTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_bootparameters(auth_token, boot_parameters).await,
       OCHAMI(b) => b.delete_bootparameters(auth_token, boot_parameters).await,
+      MOCK(b) => b.delete_bootparameters(auth_token, boot_parameters).await,
     }
   }
 }
@@ -610,6 +840,7 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.get_all_redfish_endpoints(auth_token).await,
       OCHAMI(b) => b.get_all_redfish_endpoints(auth_token).await,
+      MOCK(b) => b.get_all_redfish_endpoints(auth_token).await,
     }
   }
 
@@ -651,6 +882,19 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_redfish_endpoints(
+          auth_token,
+          id,
+          fqdn,
+          r#type,
+          uuid,
+          macaddr,
+          ip_address,
+          last_status,
+        )
+        .await
+      }
     }
   }
 
@@ -662,6 +906,7 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.add_redfish_endpoint(auth_token, redfish_endpoint).await,
       OCHAMI(b) => b.add_redfish_endpoint(auth_token, redfish_endpoint).await,
+      MOCK(b) => b.add_redfish_endpoint(auth_token, redfish_endpoint).await,
     }
   }
 
@@ -679,6 +924,10 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
         b.update_redfish_endpoint(auth_token, redfish_endpoint)
           .await
       }
+      MOCK(b) => {
+        b.update_redfish_endpoint(auth_token, redfish_endpoint)
+          .await
+      }
     }
   }
 
@@ -690,6 +939,7 @@ impl RedfishEndpointTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.delete_redfish_endpoint(auth_token, id).await,
       OCHAMI(b) => b.delete_redfish_endpoint(auth_token, id).await,
+      MOCK(b) => b.delete_redfish_endpoint(auth_token, id).await,
     }
   }
 }
@@ -700,11 +950,20 @@ impl BackendTrait for StaticBackendDispatcher {
     "in manta backend".to_string()
   }
 
+  fn capabilities(&self) -> Capabilities {
+    match self {
+      CSM(b) => b.capabilities(),
+      OCHAMI(b) => b.capabilities(),
+      MOCK(b) => b.capabilities(),
+    }
+  }
+
   // AUTHENTICATION
   async fn get_api_token(&self, site_name: &str) -> Result<String, Error> {
     match self {
       CSM(b) => b.get_api_token(site_name).await,
       OCHAMI(b) => b.get_api_token(site_name).await,
+      MOCK(b) => b.get_api_token(site_name).await,
     }
   }
 
@@ -717,6 +976,7 @@ impl BackendTrait for StaticBackendDispatcher {
     match self {
       CSM(b) => b.nid_to_xname(auth_token, user_input_nid, is_regex).await,
       OCHAMI(b) => b.nid_to_xname(auth_token, user_input_nid, is_regex).await,
+      MOCK(b) => b.nid_to_xname(auth_token, user_input_nid, is_regex).await,
     }
   }
 }
@@ -753,6 +1013,16 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_session_logs_stream(
+          shasta_token,
+          site_name,
+          cfs_session_name,
+          // k8s_api_url,
+          k8s,
+        )
+        .await
+      }
     }
   }
 
@@ -772,6 +1042,10 @@ impl CfsTrait for StaticBackendDispatcher {
         b.get_session_logs_stream_by_xname(auth_token, site_name, xname, k8s)
           .await
       }
+      MOCK(b) => {
+        b.get_session_logs_stream_by_xname(auth_token, site_name, xname, k8s)
+          .await
+      }
     }
   }
 
@@ -791,6 +1065,10 @@ impl CfsTrait for StaticBackendDispatcher {
         b.post_session(shasta_token, shasta_base_url, shasta_root_cert, session)
           .await
       }
+      MOCK(b) => {
+        b.post_session(shasta_token, shasta_base_url, shasta_root_cert, session)
+          .await
+      }
     }
   }
 
@@ -809,42 +1087,74 @@ impl CfsTrait for StaticBackendDispatcher {
     is_succeded_opt: Option<bool>,
     tags_opt: Option<String>,
   ) -> Result<Vec<CfsSessionGetResponse>, Error> {
-    match self {
-      CSM(b) => {
-        b.get_sessions(
-          auth_token,
-          base_url,
-          root_cert,
-          session_name_opt,
-          limit_opt,
-          after_id_opt,
-          min_age_opt,
-          max_age_opt,
-          status_opt,
-          name_contains_opt,
-          is_succeded_opt,
-          tags_opt,
-        )
-        .await
-      }
-      OCHAMI(b) => {
-        b.get_sessions(
-          auth_token,
-          base_url,
-          root_cert,
-          session_name_opt,
-          limit_opt,
-          after_id_opt,
-          min_age_opt,
-          max_age_opt,
-          status_opt,
-          name_contains_opt,
-          is_succeded_opt,
-          tags_opt,
-        )
-        .await
+    let query = (
+      session_name_opt,
+      limit_opt,
+      after_id_opt.clone(),
+      min_age_opt.clone(),
+      max_age_opt.clone(),
+      status_opt.clone(),
+      name_contains_opt.clone(),
+      is_succeded_opt,
+      tags_opt.clone(),
+    );
+
+    cache::get_or_fetch("cfs/sessions", auth_token, &query, || async {
+      match self {
+        CSM(b) => {
+          b.get_sessions(
+            auth_token,
+            base_url,
+            root_cert,
+            session_name_opt,
+            limit_opt,
+            after_id_opt,
+            min_age_opt,
+            max_age_opt,
+            status_opt,
+            name_contains_opt,
+            is_succeded_opt,
+            tags_opt,
+          )
+          .await
+        }
+        OCHAMI(b) => {
+          b.get_sessions(
+            auth_token,
+            base_url,
+            root_cert,
+            session_name_opt,
+            limit_opt,
+            after_id_opt,
+            min_age_opt,
+            max_age_opt,
+            status_opt,
+            name_contains_opt,
+            is_succeded_opt,
+            tags_opt,
+          )
+          .await
+        }
+        MOCK(b) => {
+          b.get_sessions(
+            auth_token,
+            base_url,
+            root_cert,
+            session_name_opt,
+            limit_opt,
+            after_id_opt,
+            min_age_opt,
+            max_age_opt,
+            status_opt,
+            name_contains_opt,
+            is_succeded_opt,
+            tags_opt,
+          )
+          .await
+        }
       }
-    }
+    })
+    .await
   }
 
   async fn get_and_filter_sessions(
@@ -894,6 +1204,22 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_and_filter_sessions(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec_opt,
+          xname_vec_opt,
+          min_age_opt,
+          max_age_opt,
+          status_opt,
+          cfs_session_name_opt,
+          limit_number_opt,
+          is_succeded_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -947,6 +1273,23 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_sessions_by_xname(
+          auth_token,
+          base_url,
+          root_cert,
+          xname_vec,
+          limit_opt,
+          after_id_opt,
+          min_age_opt,
+          max_age_opt,
+          status_opt,
+          name_contains_opt,
+          is_succeded_opt,
+          tags_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -982,6 +1325,17 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.create_configuration_from_repos(
+          gitea_token,
+          gitea_base_url,
+          shasta_root_cert,
+          repo_name_vec,
+          local_git_commit_vec,
+          playbook_file_name_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -992,26 +1346,43 @@ impl CfsTrait for StaticBackendDispatcher {
     root_cert: &[u8],
     cfs_configuration_name_opt: Option<&String>,
   ) -> Result<Vec<CfsConfigurationResponse>, Error> {
-    match self {
-      CSM(b) => {
-        b.get_configuration(
-          auth_token,
-          base_url,
-          root_cert,
-          cfs_configuration_name_opt,
-        )
-        .await
-      }
-      OCHAMI(b) => {
-        b.get_configuration(
-          auth_token,
-          base_url,
-          root_cert,
-          cfs_configuration_name_opt,
-        )
-        .await
-      }
-    }
+    cache::get_or_fetch(
+      "cfs/configurations",
+      auth_token,
+      &cfs_configuration_name_opt,
+      || async {
+        match self {
+          CSM(b) => {
+            b.get_configuration(
+              auth_token,
+              base_url,
+              root_cert,
+              cfs_configuration_name_opt,
+            )
+            .await
+          }
+          OCHAMI(b) => {
+            b.get_configuration(
+              auth_token,
+              base_url,
+              root_cert,
+              cfs_configuration_name_opt,
+            )
+            .await
+          }
+          MOCK(b) => {
+            b.get_configuration(
+              auth_token,
+              base_url,
+              root_cert,
+              cfs_configuration_name_opt,
+            )
+            .await
+          }
+        }
+      },
+    )
+    .await
   }
 
   async fn get_and_filter_configuration(
@@ -1049,6 +1420,18 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_and_filter_configuration(
+          auth_token,
+          base_url,
+          root_cert,
+          configuration_name,
+          configuration_name_pattern,
+          hsm_group_name_vec,
+          limit_number_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -1081,6 +1464,16 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_configuration_layer_details(
+          shasta_root_cert,
+          gitea_base_url,
+          gitea_token,
+          layer,
+          site_name,
+        )
+        .await
+      }
     }
   }
 
@@ -1116,6 +1509,17 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.update_runtime_configuration(
+          auth_token,
+          base_url,
+          root_cert,
+          xnames,
+          desired_configuration,
+          enabled,
+        )
+        .await
+      }
     }
   }
 
@@ -1148,6 +1552,16 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.put_configuration(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          configuration,
+          configuration_name,
+        )
+        .await
+      }
     }
   }
 
@@ -1175,6 +1589,10 @@ impl CfsTrait for StaticBackendDispatcher {
         b.get_derivatives(auth_token, base_url, root_cert, configuration_name)
           .await
       }
+      MOCK(b) => {
+        b.get_derivatives(auth_token, base_url, root_cert, configuration_name)
+          .await
+      }
     }
   }
 
@@ -1213,6 +1631,17 @@ impl CfsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_cfs_components(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          configuration_name,
+          components_ids,
+          status,
+        )
+        .await
+      }
     }
   }
 }
@@ -1283,6 +1712,28 @@ impl SatTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.apply_sat_file(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          vault_base_url,
+          vault_secret_path,
+          k8s_api_url,
+          shasta_k8s_secrets,
+          sat_template_file_yaml,
+          hsm_group_available_vec,
+          ansible_verbosity_opt,
+          ansible_passthrough_opt,
+          gitea_base_url,
+          gitea_token,
+          do_not_reboot,
+          watch_logs,
+          debug_on_failure,
+          dry_run,
+        )
+        .await
+      }
     }
   }
 }
@@ -1329,6 +1780,78 @@ impl ApplyHwClusterPin for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.apply_hw_cluster_pin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
+    }
+  }
+
+  async fn apply_hw_cluster_unpin(
+    &self,
+    shasta_token: &str,
+    shasta_base_url: &str,
+    shasta_root_cert: &[u8],
+    target_hsm_group_name: &str,
+    parent_hsm_group_name: &str,
+    pattern: &str,
+    nodryrun: bool,
+    create_target_hsm_group: bool,
+    delete_empty_parent_hsm_group: bool,
+  ) -> Result<(), Error> {
+    match self {
+      CSM(b) => {
+        b.apply_hw_cluster_unpin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
+      OCHAMI(b) => {
+        b.apply_hw_cluster_unpin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
+      MOCK(b) => {
+        b.apply_hw_cluster_unpin(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          target_hsm_group_name,
+          parent_hsm_group_name,
+          pattern,
+          nodryrun,
+          create_target_hsm_group,
+          delete_empty_parent_hsm_group,
+        )
+        .await
+      }
     }
   }
 }
@@ -1360,6 +1883,15 @@ impl ImsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_images(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          image_id_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -1369,22 +1901,30 @@ impl ImsTrait for StaticBackendDispatcher {
     shasta_base_url: &str,
     shasta_root_cert: &[u8],
   ) -> Result<Vec<Image>, Error> {
-    match self {
-      CSM(b) => {
-        b.get_all_images(shasta_token, shasta_base_url, shasta_root_cert)
-          .await
-      }
-      OCHAMI(b) => {
-        b.get_all_images(shasta_token, shasta_base_url, shasta_root_cert)
-          .await
-      }
-    }
+    cache::get_or_fetch("ims/images", shasta_token, &(), || async {
+      match self {
+        CSM(b) => {
+          b.get_all_images(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+        OCHAMI(b) => {
+          b.get_all_images(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+        MOCK(b) => {
+          b.get_all_images(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+      }
+    })
+    .await
   }
 
   fn filter_images(&self, image_vec: &mut Vec<Image>) -> Result<(), Error> {
     match self {
       CSM(b) => b.filter_images(image_vec),
       OCHAMI(b) => b.filter_images(image_vec),
+      MOCK(b) => b.filter_images(image_vec),
     }
   }
 
@@ -1414,6 +1954,15 @@ impl ImsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.delete_image(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          image_id,
+        )
+        .await
+      }
     }
   }
 }
@@ -1481,6 +2030,27 @@ impl ApplySessionTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.i_apply_session(
+          gitea_token,
+          gitea_base_url,
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          // k8s_api_url,
+          cfs_conf_sess_name,
+          playbook_yaml_file_name_opt,
+          hsm_group,
+          repos_paths,
+          ansible_limit,
+          ansible_verbosity,
+          ansible_passthrough,
+          // watch_logs,
+          /* kafka_audit,
+          k8s, */
+        )
+        .await
+      }
     }
   }
 }
@@ -1524,6 +2094,19 @@ impl MigrateRestoreTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.migrate_restore(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_file,
+          cfs_file,
+          hsm_file,
+          ims_file,
+          image_dir,
+        )
+        .await
+      }
     }
   }
 }
@@ -1558,6 +2141,16 @@ impl MigrateBackupTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.migrate_backup(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos,
+          destination,
+        )
+        .await
+      }
     }
   }
 }
@@ -1595,6 +2188,17 @@ impl GetImagesAndDetailsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_images_and_details(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          id_opt,
+          limit_number,
+        )
+        .await
+      }
     }
   }
 }
@@ -1626,6 +2230,15 @@ impl ClusterSessionTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.post_template_session(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_session,
+        )
+        .await
+      }
     }
   }
 }
@@ -1657,6 +2270,15 @@ impl ClusterTemplateTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_session_template_id_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -1695,6 +2317,18 @@ impl ClusterTemplateTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.get_and_filter_templates(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_name_vec,
+          hsm_member_vec,
+          bos_sessiontemplate_name_opt,
+          limit_number_opt,
+        )
+        .await
+      }
     }
   }
 
@@ -1704,16 +2338,23 @@ impl ClusterTemplateTrait for StaticBackendDispatcher {
     shasta_base_url: &str,
     shasta_root_cert: &[u8],
   ) -> Result<Vec<BosSessionTemplate>, Error> {
-    match self {
-      CSM(b) => {
-        b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
-          .await
-      }
-      OCHAMI(b) => {
-        b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
-          .await
-      }
-    }
+    cache::get_or_fetch("bos/sessiontemplates", shasta_token, &(), || async {
+      match self {
+        CSM(b) => {
+          b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+        OCHAMI(b) => {
+          b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+        MOCK(b) => {
+          b.get_all_templates(shasta_token, shasta_base_url, shasta_root_cert)
+            .await
+        }
+      }
+    })
+    .await
   }
 
   async fn put_template(
@@ -1745,6 +2386,16 @@ impl ClusterTemplateTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.put_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template,
+          bos_template_name,
+        )
+        .await
+      }
     }
   }
 
@@ -1774,6 +2425,15 @@ impl ClusterTemplateTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.delete_template(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          bos_template_id,
+        )
+        .await
+      }
     }
   }
 }
@@ -1814,6 +2474,18 @@ impl CommandsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.i_delete_and_cancel_session(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_group_available_vec,
+          cfs_session_name,
+          dry_run,
+          assume_yes,
+        )
+        .await
+      }
     }
   }
 
@@ -1858,6 +2530,20 @@ impl CommandsTrait for StaticBackendDispatcher {
         )
         .await
       }
+      MOCK(b) => {
+        b.i_delete_data_related_to_cfs_configuration(
+          shasta_token,
+          shasta_base_url,
+          shasta_root_cert,
+          hsm_name_available_vec,
+          configuration_name_opt,
+          configuration_name_pattern,
+          since_opt,
+          until_opt,
+          assume_yes,
+        )
+        .await
+      }
     }
   }
 }
@@ -1885,6 +2571,10 @@ impl ConsoleTrait for StaticBackendDispatcher {
         b.attach_to_console(shasta_token, site_name, xname, width, height, k8s)
           .await
       }
+      MOCK(b) => {
+        b.attach_to_console(shasta_token, site_name, xname, width, height, k8s)
+          .await
+      }
     }
   }
 }
@@ -0,0 +1,980 @@
+use std::{collections::HashMap, pin::Pin};
+
+use futures::AsyncBufRead;
+use hostlist_parser::parse;
+use manta_backend_dispatcher::{
+  contracts::BackendTrait,
+  error::Error,
+  interfaces::{
+    apply_hw_cluster_pin::ApplyHwClusterPin,
+    apply_sat_file::SatTrait,
+    apply_session::ApplySessionTrait,
+    bos::{ClusterSessionTrait, ClusterTemplateTrait},
+    bss::BootParametersTrait,
+    cfs::CfsTrait,
+    commands::CommandsTrait,
+    console::ConsoleTrait,
+    get_images_and_details::GetImagesAndDetailsTrait,
+    hsm::{
+      component::ComponentTrait, group::GroupTrait,
+      hardware_inventory::HardwareInventory,
+      redfish_endpoint::RedfishEndpointTrait,
+    },
+    ims::ImsTrait,
+    migrate_backup::MigrateBackupTrait,
+    migrate_restore::MigrateRestoreTrait,
+    pcs::PCSTrait,
+  },
+  types::{
+    capability::{Capabilities, Capability},
+    hsm::inventory::{RedfishEndpoint, RedfishEndpointArray},
+    pcs::power_status::types::PowerStatusAll as FrontEndPowerStatusAll,
+    BootParameters, Component, ComponentArrayPostArray, Group,
+    HWInventoryByLocationList, NodeMetadataArray,
+  },
+};
+use regex::Regex;
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::{bss, hsm, pcs};
+
+/// Env var with the JWT token to authenticate against OpenCHAMI APIs
+pub const ACCESS_TOKEN_ENV_VAR: &str = "ACCESS_TOKEN";
+
+#[derive(Clone)]
+pub struct Ochami {
+  base_url: String,
+  root_cert: Vec<u8>,
+}
+
+impl Ochami {
+  pub fn new(base_url: &str, root_cert: &[u8]) -> Self {
+    Self {
+      base_url: base_url.to_string(),
+      root_cert: root_cert.to_vec(),
+    }
+  }
+
+  /// Creates a power transition and returns its ID without waiting for it to
+  /// complete
+  async fn create_power_transition(
+    &self,
+    auth_token: &str,
+    operation: &str,
+    nodes: &[String],
+  ) -> Result<String, Error> {
+    let transition = pcs::transitions::http_client::post(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      operation,
+      nodes,
+    )
+    .await
+    .map_err(Error::from)?;
+
+    transition["transitionID"]
+      .as_str()
+      .map(str::to_string)
+      .ok_or_else(|| {
+        Error::Message(format!(
+          "PCS transition ID missing in response:\n{}",
+          transition
+        ))
+      })
+  }
+}
+
+impl BackendTrait for Ochami {
+  fn test_backend_trait(&self) -> String {
+    println!("in ochami backend");
+    "in ochami backend".to_string()
+  }
+
+  fn capabilities(&self) -> Capabilities {
+    // OpenCHAMI only provides SMD, BSS and PCS
+    Capabilities::new(&[
+      Capability::Group,
+      Capability::Component,
+      Capability::HardwareInventory,
+      Capability::RedfishEndpoint,
+      Capability::BootParameters,
+      Capability::Power,
+    ])
+  }
+
+  async fn get_api_token(&self, _site_name: &str) -> Result<String, Error> {
+    // OpenCHAMI tokens are issued outside manta (eg: 'gen_access_token' function in
+    // OpenCHAMI deployment recipes)
+    std::env::var(ACCESS_TOKEN_ENV_VAR).map_err(|_| {
+      Error::Message(format!(
+        "Env var '{}' with OpenCHAMI authentication token missing",
+        ACCESS_TOKEN_ENV_VAR
+      ))
+    })
+  }
+
+  /// Get list of xnames from NIDs
+  /// The list of NIDs can be:
+  ///     - comma separated list of NIDs (eg: nid000001,nid000002,nid000003)
+  ///     - regex (eg: nid00000.*)
+  ///     - hostlist (eg: nid0000[01-15])
+  async fn nid_to_xname(
+    &self,
+    auth_token: &str,
+    user_input_nid: &str,
+    is_regex: bool,
+  ) -> Result<Vec<String>, Error> {
+    let node_vec = hsm::component::http_client::get_all_nodes(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      Some("true"),
+    )
+    .await
+    .map_err(Error::from)?
+    .components
+    .unwrap_or_default();
+
+    let nid_long_vec: Vec<String> = if is_regex {
+      log::debug!("Regex found, getting xnames from NIDs");
+      Vec::new()
+    } else {
+      log::debug!(
+        "No regex found, getting xnames from list of NIDs or NIDs hostlist"
+      );
+      parse(user_input_nid).map_err(|e| {
+        Error::Message(format!(
+          "Could not parse list of nodes as a hostlist. Reason:\n{}Exit",
+          e
+        ))
+      })?
+    };
+
+    let regex_vec: Vec<Regex> = if is_regex {
+      user_input_nid
+        .split(',')
+        .map(|regex_str| Regex::new(regex_str.trim()))
+        .collect::<Result<Vec<Regex>, regex::Error>>()
+        .map_err(|e| Error::Message(e.to_string()))?
+    } else {
+      Vec::new()
+    };
+
+    let xname_vec: Vec<String> = node_vec
+      .into_iter()
+      .filter_map(|node| {
+        let nid_long = format!("nid{:06}", node.nid?);
+
+        let is_requested = if is_regex {
+          regex_vec.iter().any(|regex| regex.is_match(&nid_long))
+        } else {
+          nid_long_vec.contains(&nid_long)
+        };
+
+        if is_requested {
+          node.id
+        } else {
+          None
+        }
+      })
+      .collect();
+
+    log::debug!("xname list:\n{:#?}", xname_vec);
+
+    Ok(xname_vec)
+  }
+}
+
+impl GroupTrait for Ochami {
+  async fn get_group_available(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Group>, Error> {
+    let mut group_vec = self.get_all_groups(auth_token).await?;
+
+    let available_groups_name =
+      self.get_group_name_available(auth_token).await?;
+
+    group_vec.retain(|group| available_groups_name.contains(&group.label));
+
+    Ok(group_vec)
+  }
+
+  /// OpenCHAMI tokens do not carry group roles, users have access to all groups
+  async fn get_group_name_available(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<String>, Error> {
+    self
+      .get_all_groups(auth_token)
+      .await
+      .map(|group_vec| group_vec.into_iter().map(|group| group.label).collect())
+  }
+
+  async fn add_group(
+    &self,
+    auth_token: &str,
+    group: Group,
+  ) -> Result<Group, Error> {
+    let response = hsm::group::http_client::post(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      group.clone(),
+    )
+    .await
+    .map_err(Error::from)?;
+
+    log::info!("Group created: {}", response);
+
+    Ok(group)
+  }
+
+  async fn get_member_vec_from_group_name_vec(
+    &self,
+    auth_token: &str,
+    hsm_group_name_vec: Vec<String>,
+  ) -> Result<Vec<String>, Error> {
+    let hsm_group_name_vec: Vec<&str> =
+      hsm_group_name_vec.iter().map(String::as_str).collect();
+
+    hsm::group::utils::get_member_vec_from_group_name_vec(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      &hsm_group_name_vec,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_group_map_and_filter_by_group_vec(
+    &self,
+    auth_token: &str,
+    hsm_name_vec: Vec<&str>,
+  ) -> Result<HashMap<String, Vec<String>>, Error> {
+    hsm::group::utils::get_group_map_and_filter_by_group_vec(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      &hsm_name_vec,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_group_map_and_filter_by_member_vec(
+    &self,
+    auth_token: &str,
+    member_vec: &[&str],
+  ) -> Result<HashMap<String, Vec<String>>, Error> {
+    hsm::group::utils::get_group_map_and_filter_by_member_vec(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      member_vec,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_all_groups(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Group>, Error> {
+    hsm::group::http_client::get_all(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_group(
+    &self,
+    auth_token: &str,
+    hsm_name: &str,
+  ) -> Result<Group, Error> {
+    hsm::group::http_client::get_one(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      hsm_name,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_groups(
+    &self,
+    auth_token: &str,
+    hsm_name_vec: Option<&[&str]>,
+  ) -> Result<Vec<Group>, Error> {
+    hsm::group::http_client::get(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      hsm_name_vec,
+      None,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn delete_group(
+    &self,
+    auth_token: &str,
+    label: &str,
+  ) -> Result<Value, Error> {
+    hsm::group::http_client::delete(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      label,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_hsm_map_and_filter_by_hsm_name_vec(
+    &self,
+    auth_token: &str,
+    hsm_name_vec: Vec<&str>,
+  ) -> Result<HashMap<String, Vec<String>>, Error> {
+    self
+      .get_group_map_and_filter_by_group_vec(auth_token, hsm_name_vec)
+      .await
+  }
+
+  async fn post_member(
+    &self,
+    auth_token: &str,
+    group_label: &str,
+    xname: &str,
+  ) -> Result<Value, Error> {
+    hsm::group::http_client::post_member(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      group_label,
+      xname,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn add_members_to_group(
+    &self,
+    auth_token: &str,
+    group_label: &str,
+    new_members: Vec<&str>,
+  ) -> Result<Vec<String>, Error> {
+    let mut member_vec: Vec<String> = Vec::new();
+
+    for new_member in new_members {
+      member_vec = hsm::group::utils::add_member(
+        auth_token,
+        &self.base_url,
+        &self.root_cert,
+        group_label,
+        new_member,
+      )
+      .await
+      .map_err(Error::from)?;
+    }
+
+    Ok(member_vec)
+  }
+
+  async fn delete_member_from_group(
+    &self,
+    auth_token: &str,
+    group_label: &str,
+    xname: &str,
+  ) -> Result<(), Error> {
+    hsm::group::http_client::delete_member(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      group_label,
+      xname,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn update_group_members(
+    &self,
+    auth_token: &str,
+    group_name: &str,
+    members_to_remove: &Vec<String>,
+    members_to_add: &Vec<String>,
+  ) -> Result<(), Error> {
+    hsm::group::utils::update_group_members(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      group_name,
+      members_to_remove,
+      members_to_add,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn migrate_group_members(
+    &self,
+    auth_token: &str,
+    target_hsm_group_name: &str,
+    parent_hsm_group_name: &str,
+    new_target_hsm_members: Vec<&str>,
+  ) -> Result<(Vec<String>, Vec<String>), Error> {
+    hsm::group::utils::migrate_group_members(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      target_hsm_group_name,
+      parent_hsm_group_name,
+      &new_target_hsm_members,
+    )
+    .await
+    .map_err(Error::from)
+  }
+}
+
+impl HardwareInventory for Ochami {
+  async fn get_inventory_hardware(
+    &self,
+    auth_token: &str,
+    xname: &str,
+  ) -> Result<Value, Error> {
+    hsm::inventory::hardware::http_client::get(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      xname,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_inventory_hardware_query(
+    &self,
+    auth_token: &str,
+    xname: &str,
+    r#type: Option<&str>,
+    children: Option<bool>,
+    parents: Option<bool>,
+    partition: Option<&str>,
+    format: Option<&str>,
+  ) -> Result<Value, Error> {
+    hsm::inventory::hardware::http_client::get_query(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      xname,
+      r#type,
+      children,
+      parents,
+      partition,
+      format,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn post_inventory_hardware(
+    &self,
+    auth_token: &str,
+    hw_inventory: HWInventoryByLocationList,
+  ) -> Result<Value, Error> {
+    hsm::inventory::hardware::http_client::post(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      &hw_inventory,
+    )
+    .await
+    .map_err(Error::from)
+  }
+}
+
+impl ComponentTrait for Ochami {
+  async fn get_all_nodes(
+    &self,
+    auth_token: &str,
+    nid_only: Option<&str>,
+  ) -> Result<NodeMetadataArray, Error> {
+    hsm::component::http_client::get_all_nodes(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      nid_only,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_node_metadata_available(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Component>, Error> {
+    let xname_available_vec: Vec<String> = self
+      .get_group_available(auth_token)
+      .await?
+      .iter()
+      .flat_map(|group| group.get_members())
+      .collect();
+
+    let node_metadata_vec = self
+      .get_all_nodes(auth_token, None)
+      .await?
+      .components
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|node_metadata| {
+        node_metadata
+          .id
+          .as_ref()
+          .is_some_and(|xname| xname_available_vec.contains(xname))
+      })
+      .collect();
+
+    Ok(node_metadata_vec)
+  }
+
+  async fn get(
+    &self,
+    auth_token: &str,
+    id: Option<&str>,
+    r#type: Option<&str>,
+    state: Option<&str>,
+    flag: Option<&str>,
+    role: Option<&str>,
+    subrole: Option<&str>,
+    enabled: Option<&str>,
+    software_status: Option<&str>,
+    subtype: Option<&str>,
+    arch: Option<&str>,
+    class: Option<&str>,
+    nid: Option<&str>,
+    nid_start: Option<&str>,
+    nid_end: Option<&str>,
+    partition: Option<&str>,
+    group: Option<&str>,
+    state_only: Option<&str>,
+    flag_only: Option<&str>,
+    role_only: Option<&str>,
+    nid_only: Option<&str>,
+  ) -> Result<NodeMetadataArray, Error> {
+    let query: Vec<(&str, &str)> = [
+      ("id", id),
+      ("type", r#type),
+      ("state", state),
+      ("flag", flag),
+      ("role", role),
+      ("subrole", subrole),
+      ("enabled", enabled),
+      ("softwarestatus", software_status),
+      ("subtype", subtype),
+      ("arch", arch),
+      ("class", class),
+      ("nid", nid),
+      ("nidstart", nid_start),
+      ("nidend", nid_end),
+      ("partition", partition),
+      ("group", group),
+      ("stateonly", state_only),
+      ("flagonly", flag_only),
+      ("roleonly", role_only),
+      ("nidonly", nid_only),
+    ]
+    .into_iter()
+    .filter_map(|(key, value_opt)| value_opt.map(|value| (key, value)))
+    .collect();
+
+    hsm::component::http_client::get(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      &query,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn post_nodes(
+    &self,
+    auth_token: &str,
+    component: ComponentArrayPostArray,
+  ) -> Result<(), Error> {
+    hsm::component::http_client::post(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      &component,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn delete_node(
+    &self,
+    auth_token: &str,
+    id: &str,
+  ) -> Result<Value, Error> {
+    hsm::component::http_client::delete_one(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      id,
+    )
+    .await
+    .map_err(Error::from)
+  }
+}
+
+impl PCSTrait for Ochami {
+  async fn power_on_sync(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+  ) -> Result<Value, Error> {
+    pcs::transitions::http_client::post_block(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      "on",
+      nodes,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn power_off_sync(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<Value, Error> {
+    let operation = if force { "force-off" } else { "soft-off" };
+
+    pcs::transitions::http_client::post_block(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      operation,
+      nodes,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn power_reset_sync(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<Value, Error> {
+    let operation = if force {
+      "hard-restart"
+    } else {
+      "soft-restart"
+    };
+
+    pcs::transitions::http_client::post_block(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      operation,
+      nodes,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn power_on(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+  ) -> Result<String, Error> {
+    self.create_power_transition(auth_token, "on", nodes).await
+  }
+
+  async fn power_off(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    let operation = if force { "force-off" } else { "soft-off" };
+
+    self
+      .create_power_transition(auth_token, operation, nodes)
+      .await
+  }
+
+  async fn power_reset(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    force: bool,
+  ) -> Result<String, Error> {
+    let operation = if force {
+      "hard-restart"
+    } else {
+      "soft-restart"
+    };
+
+    self
+      .create_power_transition(auth_token, operation, nodes)
+      .await
+  }
+
+  async fn get_power_transition(
+    &self,
+    auth_token: &str,
+    transition_id: &str,
+  ) -> Result<Value, Error> {
+    pcs::transitions::http_client::get_by_id(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      transition_id,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_power_transitions(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<Value>, Error> {
+    pcs::transitions::http_client::get(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn power_status(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+    power_state_filter: Option<&str>,
+    management_state_filter: Option<&str>,
+  ) -> Result<FrontEndPowerStatusAll, Error> {
+    pcs::power_status::http_client::post(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      nodes,
+      power_state_filter,
+      management_state_filter,
+    )
+    .await
+    .map(FrontEndPowerStatusAll::from)
+    .map_err(Error::from)
+  }
+}
+
+impl BootParametersTrait for Ochami {
+  async fn get_all_bootparameters(
+    &self,
+    auth_token: &str,
+  ) -> Result<Vec<BootParameters>, Error> {
+    bss::http_client::get_all(auth_token, &self.base_url, &self.root_cert)
+      .await
+      .map_err(Error::from)
+  }
+
+  async fn get_bootparameters(
+    &self,
+    auth_token: &str,
+    nodes: &[String],
+  ) -> Result<Vec<BootParameters>, Error> {
+    bss::http_client::get(auth_token, &self.base_url, &self.root_cert, nodes)
+      .await
+      .map_err(Error::from)
+  }
+
+  async fn add_bootparameters(
+    &self,
+    auth_token: &str,
+    boot_parameters: &BootParameters,
+  ) -> Result<(), Error> {
+    bss::http_client::post(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      boot_parameters,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn update_bootparameters(
+    &self,
+    auth_token: &str,
+    boot_parameters: &BootParameters,
+  ) -> Result<(), Error> {
+    bss::http_client::patch(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      boot_parameters,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn delete_bootparameters(
+    &self,
+    auth_token: &str,
+    boot_parameters: &BootParameters,
+  ) -> Result<String, Error> {
+    bss::http_client::delete(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      boot_parameters,
+    )
+    .await
+    .map_err(Error::from)
+  }
+}
+
+impl RedfishEndpointTrait for Ochami {
+  async fn get_all_redfish_endpoints(
+    &self,
+    auth_token: &str,
+  ) -> Result<RedfishEndpointArray, Error> {
+    hsm::inventory::redfish_endpoint::http_client::get(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      &[],
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn get_redfish_endpoints(
+    &self,
+    auth_token: &str,
+    id: Option<&str>,
+    fqdn: Option<&str>,
+    r#type: Option<&str>,
+    uuid: Option<&str>,
+    macaddr: Option<&str>,
+    ip_address: Option<&str>,
+    last_status: Option<&str>,
+  ) -> Result<RedfishEndpointArray, Error> {
+    let query: Vec<(&str, &str)> = [
+      ("id", id),
+      ("fqdn", fqdn),
+      ("type", r#type),
+      ("uuid", uuid),
+      ("macaddr", macaddr),
+      ("ipaddress", ip_address),
+      ("laststatus", last_status),
+    ]
+    .into_iter()
+    .filter_map(|(key, value_opt)| value_opt.map(|value| (key, value)))
+    .collect();
+
+    hsm::inventory::redfish_endpoint::http_client::get(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      &query,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn add_redfish_endpoint(
+    &self,
+    auth_token: &str,
+    redfish_endpoint: &RedfishEndpointArray,
+  ) -> Result<(), Error> {
+    hsm::inventory::redfish_endpoint::http_client::post(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      redfish_endpoint,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn update_redfish_endpoint(
+    &self,
+    auth_token: &str,
+    redfish_endpoint: &RedfishEndpoint,
+  ) -> Result<(), Error> {
+    hsm::inventory::redfish_endpoint::http_client::put(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      redfish_endpoint,
+    )
+    .await
+    .map_err(Error::from)
+  }
+
+  async fn delete_redfish_endpoint(
+    &self,
+    auth_token: &str,
+    id: &str,
+  ) -> Result<Value, Error> {
+    hsm::inventory::redfish_endpoint::http_client::delete(
+      auth_token,
+      &self.base_url,
+      &self.root_cert,
+      id,
+    )
+    .await
+    .map_err(Error::from)
+  }
+}
+
+// Features below are only available in CSM, default trait implementations return a 'not
+// implemented for this backend' error
+
+impl CfsTrait for Ochami {
+  type T = Pin<Box<dyn AsyncBufRead + Send>>;
+}
+
+impl SatTrait for Ochami {}
+
+impl ApplyHwClusterPin for Ochami {}
+
+impl ImsTrait for Ochami {}
+
+impl ApplySessionTrait for Ochami {}
+
+impl MigrateRestoreTrait for Ochami {}
+
+impl MigrateBackupTrait for Ochami {}
+
+impl GetImagesAndDetailsTrait for Ochami {}
+
+impl ClusterSessionTrait for Ochami {}
+
+impl ClusterTemplateTrait for Ochami {}
+
+impl CommandsTrait for Ochami {}
+
+impl ConsoleTrait for Ochami {
+  type T = Box<dyn AsyncWrite + Unpin>;
+  type U = Box<dyn AsyncRead + Unpin>;
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,371 @@
+use csm_rs::stand_in::{CsmStandIn, CsmStore, Fault};
+use manta_backend_dispatcher::{
+  contracts::BackendTrait,
+  error::Error,
+  interfaces::{
+    bss::BootParametersTrait,
+    cfs::CfsTrait,
+    hsm::{
+      component::ComponentTrait, group::GroupTrait,
+      hardware_inventory::HardwareInventory,
+      redfish_endpoint::RedfishEndpointTrait,
+    },
+    pcs::PCSTrait,
+  },
+  types::{
+    capability::Capability, hsm::inventory::RedfishEndpointArray, Group,
+  },
+};
+use serde_json::json;
+
+use super::Ochami;
+
+const AUTH_TOKEN: &str = "ochami-token";
+
+fn store() -> CsmStore {
+  let mut store: CsmStore = serde_json::from_value(json!({
+    "groups": [
+      { "label": "zinal", "members": { "ids": ["x1000c0s0b0n0", "x1000c0s0b0n1"] } },
+      { "label": "nodes_free", "members": { "ids": ["x1000c0s1b0n0"] } },
+    ],
+    "components": [
+      { "ID": "x1000c0s0b0n0", "Type": "Node", "State": "Ready", "NID": 1 },
+      { "ID": "x1000c0s0b0n1", "Type": "Node", "State": "Ready", "NID": 2 },
+      { "ID": "x1000c0s1b0n0", "Type": "Node", "State": "Off", "NID": 3 },
+    ],
+    "hardware_inventory": {
+      "x1000c0s0b0n0": {
+        "XName": "x1000c0s0b0n0",
+        "Format": "NestNodesOnly",
+        "Nodes": [{ "ID": "x1000c0s0b0n0", "Type": "Node" }],
+      },
+    },
+    "redfish_endpoints": [
+      { "ID": "x1000c0s0b0", "FQDN": "x1000c0s0b0.local", "Type": "NodeBMC" },
+    ],
+    "boot_parameters": [
+      { "hosts": ["x1000c0s0b0n0"], "params": "console=ttyS0", "kernel": "http://boot/kernel", "initrd": "http://boot/initrd" },
+    ],
+    "power_state": { "x1000c0s0b0n0": "on", "x1000c0s0b0n1": "on", "x1000c0s1b0n0": "off" },
+  }))
+  .unwrap();
+
+  store.auth_token = Some(AUTH_TOKEN.to_string());
+
+  store
+}
+
+async fn setup() -> (CsmStandIn, Ochami) {
+  let stand_in = CsmStandIn::start(store()).await.unwrap();
+  let ochami = Ochami::new(stand_in.base_url(), &[]);
+
+  (stand_in, ochami)
+}
+
+#[tokio::test]
+async fn test_groups() {
+  let (stand_in, ochami) = setup().await;
+
+  let group = Group::new(
+    "davos",
+    None,
+    Some(vec!["x1000c0s0b0n1".to_string()]),
+    None,
+    None,
+  );
+
+  ochami.add_group(AUTH_TOKEN, group).await.unwrap();
+
+  assert_eq!(
+    ochami
+      .get_group(AUTH_TOKEN, "davos")
+      .await
+      .unwrap()
+      .get_members(),
+    vec!["x1000c0s0b0n1".to_string()]
+  );
+
+  let group_map = ochami
+    .get_group_map_and_filter_by_member_vec(AUTH_TOKEN, &["x1000c0s0b0n1"])
+    .await
+    .unwrap();
+
+  assert_eq!(group_map.len(), 2);
+
+  ochami.delete_group(AUTH_TOKEN, "davos").await.unwrap();
+
+  assert!(stand_in.store().find_group("davos").is_none());
+}
+
+#[tokio::test]
+async fn test_migrate_group_members() {
+  let (stand_in, ochami) = setup().await;
+
+  let (target_member_vec, parent_member_vec) = ochami
+    .migrate_group_members(
+      AUTH_TOKEN,
+      "zinal",
+      "nodes_free",
+      vec!["x1000c0s1b0n0"],
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(target_member_vec.len(), 3);
+  assert!(parent_member_vec.is_empty());
+  assert_eq!(
+    stand_in
+      .store()
+      .get_group_members("nodes_free")
+      .unwrap()
+      .len(),
+    0
+  );
+
+  // Nodes must belong to the parent group
+  assert!(ochami
+    .migrate_group_members(
+      AUTH_TOKEN,
+      "zinal",
+      "nodes_free",
+      vec!["x1000c0s0b0n0"]
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_components() {
+  let (stand_in, ochami) = setup().await;
+
+  let node_vec = ochami
+    .get(
+      AUTH_TOKEN,
+      None,
+      None,
+      Some("Off"),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await
+    .unwrap()
+    .components
+    .unwrap();
+
+  assert_eq!(node_vec.len(), 1);
+  assert_eq!(node_vec[0].id.as_deref(), Some("x1000c0s1b0n0"));
+
+  ochami
+    .delete_node(AUTH_TOKEN, "x1000c0s1b0n0")
+    .await
+    .unwrap();
+
+  assert_eq!(stand_in.store().components.len(), 2);
+}
+
+#[tokio::test]
+async fn test_nid_to_xname() {
+  let (_stand_in, ochami) = setup().await;
+
+  let xname_vec = ochami
+    .nid_to_xname(AUTH_TOKEN, "nid00000[1-2]", false)
+    .await
+    .unwrap();
+
+  assert_eq!(xname_vec, vec!["x1000c0s0b0n0", "x1000c0s0b0n1"]);
+
+  let xname_vec = ochami
+    .nid_to_xname(AUTH_TOKEN, "nid00000[3]", true)
+    .await
+    .unwrap();
+
+  assert_eq!(xname_vec, vec!["x1000c0s1b0n0"]);
+}
+
+#[tokio::test]
+async fn test_get_inventory_hardware_query() {
+  let (_stand_in, ochami) = setup().await;
+
+  let hw_inventory = ochami
+    .get_inventory_hardware_query(
+      AUTH_TOKEN,
+      "x1000c0s0b0n0",
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(hw_inventory["Nodes"][0]["ID"], "x1000c0s0b0n0");
+}
+
+#[tokio::test]
+async fn test_bootparameters() {
+  let (stand_in, ochami) = setup().await;
+
+  let mut boot_parameters = ochami
+    .get_bootparameters(AUTH_TOKEN, &["x1000c0s0b0n0".to_string()])
+    .await
+    .unwrap()
+    .remove(0);
+
+  boot_parameters.params = "console=ttyS0 quiet".to_string();
+
+  ochami
+    .update_bootparameters(AUTH_TOKEN, &boot_parameters)
+    .await
+    .unwrap();
+
+  assert_eq!(
+    ochami.get_all_bootparameters(AUTH_TOKEN).await.unwrap()[0].params,
+    "console=ttyS0 quiet"
+  );
+
+  ochami
+    .delete_bootparameters(AUTH_TOKEN, &boot_parameters)
+    .await
+    .unwrap();
+
+  assert!(stand_in.store().boot_parameters.is_empty());
+}
+
+#[tokio::test]
+async fn test_redfish_endpoints() {
+  let (_stand_in, ochami) = setup().await;
+
+  let redfish_endpoint_vec: RedfishEndpointArray =
+    serde_json::from_value(json!({
+      "RedfishEndpoints": [{ "ID": "x1000c0s1b0", "FQDN": "x1000c0s1b0.local" }],
+    }))
+    .unwrap();
+
+  ochami
+    .add_redfish_endpoint(AUTH_TOKEN, &redfish_endpoint_vec)
+    .await
+    .unwrap();
+
+  let redfish_endpoint_vec = ochami
+    .get_redfish_endpoints(
+      AUTH_TOKEN,
+      None,
+      Some("x1000c0s1b0.local"),
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+    .await
+    .unwrap()
+    .redfish_endpoints
+    .unwrap();
+
+  assert_eq!(redfish_endpoint_vec.len(), 1);
+  assert_eq!(redfish_endpoint_vec[0].id, "x1000c0s1b0");
+
+  ochami
+    .delete_redfish_endpoint(AUTH_TOKEN, "x1000c0s1b0")
+    .await
+    .unwrap();
+
+  let redfish_endpoint_vec = ochami
+    .get_all_redfish_endpoints(AUTH_TOKEN)
+    .await
+    .unwrap()
+    .redfish_endpoints
+    .unwrap();
+
+  assert_eq!(redfish_endpoint_vec.len(), 1);
+}
+
+#[tokio::test]
+async fn test_power() {
+  let (stand_in, ochami) = setup().await;
+
+  ochami
+    .power_on_sync(AUTH_TOKEN, &["x1000c0s1b0n0".to_string()])
+    .await
+    .unwrap();
+
+  assert_eq!(stand_in.store().power_state["x1000c0s1b0n0"], "on");
+
+  let power_status = ochami
+    .power_status(
+      AUTH_TOKEN,
+      &["x1000c0s0b0n0".to_string(), "x1000c0s1b0n0".to_string()],
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(power_status.status.len(), 2);
+  assert!(!power_status.status[0]
+    .supported_power_transitions
+    .is_empty());
+}
+
+#[tokio::test]
+async fn test_error_mapping() {
+  let (stand_in, ochami) = setup().await;
+
+  stand_in.inject_fault(Fault::new(Some("GET"), "/smd/hsm/v2/groups", 500));
+
+  let error = ochami.get_all_groups(AUTH_TOKEN).await.unwrap_err();
+  assert!(
+    matches!(&error, Error::Http { status: 500, service, .. } if service == "SMD"),
+    "{error:?}"
+  );
+
+  let error = ochami.get_all_groups("wrong-token").await.unwrap_err();
+  assert!(matches!(error, Error::Unauthorized { .. }), "{error:?}");
+
+  assert!(ochami.get_all_groups(AUTH_TOKEN).await.is_ok());
+
+  let error = ochami.delete_group(AUTH_TOKEN, "davos").await.unwrap_err();
+  assert!(
+    matches!(&error, Error::NotFound { id, .. } if id == "davos"),
+    "{error:?}"
+  );
+}
+
+#[tokio::test]
+async fn test_cfs_not_implemented() {
+  let (_stand_in, ochami) = setup().await;
+
+  assert!(ochami
+    .get_cfs_health()
+    .await
+    .unwrap_err()
+    .is_not_implemented());
+}
+
+#[tokio::test]
+async fn test_capabilities() {
+  let (_stand_in, ochami) = setup().await;
+
+  let capabilities = ochami.capabilities();
+
+  assert!(capabilities.contains(Capability::Group));
+  assert!(capabilities.contains(Capability::Power));
+  assert!(!capabilities.contains(Capability::Cfs));
+  assert!(capabilities.missing().contains(&Capability::Console));
+}
@@ -0,0 +1,108 @@
+use manta_backend_dispatcher::types::BootParameters;
+
+use crate::{
+  common::http_client::{build_client, check_response, parse_response},
+  error::Error,
+};
+
+/// Get boot parameters --> https://github.com/OpenCHAMI/bss/blob/main/README.md
+/// Returns the boot parameters of all nodes if `xname_vec` is empty
+pub async fn get(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  xname_vec: &[String],
+) -> Result<Vec<BootParameters>, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "boot/v1/bootparameters");
+
+  let query: Vec<(&str, &str)> = xname_vec
+    .iter()
+    .map(|xname| ("name", xname.as_str()))
+    .collect();
+
+  let response = client
+    .get(api_url)
+    .query(&query)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+pub async fn get_all(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+) -> Result<Vec<BootParameters>, Error> {
+  get(auth_token, base_url, root_cert, &[]).await
+}
+
+/// Create boot parameters
+pub async fn post(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  boot_parameters: &BootParameters,
+) -> Result<(), Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "boot/v1/bootparameters");
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(boot_parameters)
+    .send()
+    .await?;
+
+  check_response(response).await.map(|_| ())
+}
+
+/// Update boot parameters, only the fields with values are updated
+pub async fn patch(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  boot_parameters: &BootParameters,
+) -> Result<(), Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "boot/v1/bootparameters");
+
+  let response = client
+    .patch(api_url)
+    .bearer_auth(auth_token)
+    .json(boot_parameters)
+    .send()
+    .await?;
+
+  check_response(response).await.map(|_| ())
+}
+
+/// Delete boot parameters of the hosts in `boot_parameters`
+pub async fn delete(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  boot_parameters: &BootParameters,
+) -> Result<String, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "boot/v1/bootparameters");
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(auth_token)
+    .json(boot_parameters)
+    .send()
+    .await?;
+
+  check_response(response)
+    .await?
+    .text()
+    .await
+    .map_err(Error::NetError)
+}
@@ -0,0 +1,88 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Builds a http client trusting the OpenCHAMI root certificate. The client goes through a
+/// socks5 proxy if env var 'SOCKS5' is set
+pub fn build_client(root_cert: &[u8]) -> Result<reqwest::Client, Error> {
+  let mut client_builder = reqwest::Client::builder();
+
+  // An empty root certificate means the system root certificates are enough (eg: local test
+  // servers or OpenCHAMI behind a proxy with a public certificate)
+  if !root_cert.is_empty() {
+    client_builder = client_builder
+      .add_root_certificate(reqwest::Certificate::from_pem(root_cert)?);
+  }
+
+  // Build client
+  let client = if let Ok(socks5_env) = std::env::var("SOCKS5") {
+    // socks5 proxy
+    log::debug!("SOCKS5 enabled");
+    let socks5proxy = reqwest::Proxy::all(socks5_env)?;
+
+    client_builder.proxy(socks5proxy).build()?
+  } else {
+    client_builder.build()?
+  };
+
+  Ok(client)
+}
+
+/// Returns an error with the service, status and reason given by the server if the http
+/// response is not successful
+pub async fn check_response(
+  response: reqwest::Response,
+) -> Result<reqwest::Response, Error> {
+  if response.status().is_success() {
+    return Ok(response);
+  }
+
+  let status = response.status().as_u16();
+  let path = response.url().path().to_string();
+  let service = get_service_name(&path).to_string();
+  let error_payload = response.text().await?;
+
+  let detail = serde_json::from_str::<Value>(&error_payload)
+    .ok()
+    .and_then(|value| {
+      value
+        .get("detail")
+        .or_else(|| value.get("message"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    })
+    .unwrap_or_else(|| error_payload.trim().to_string());
+
+  Err(Error::HttpError {
+    service,
+    status,
+    path,
+    detail,
+  })
+}
+
+/// Returns the name of the OpenCHAMI service serving an API path
+/// (eg: '/hsm/v2/groups' -> 'SMD')
+pub fn get_service_name(path: &str) -> &'static str {
+  path
+    .split('/')
+    .find_map(|segment| match segment {
+      "smd" | "hsm" => Some("SMD"),
+      "bss" | "boot" => Some("BSS"),
+      "power-control" => Some("PCS"),
+      _ => None,
+    })
+    .unwrap_or("OCHAMI")
+}
+
+/// Checks the http response and parses its JSON payload
+pub async fn parse_response<T: DeserializeOwned>(
+  response: reqwest::Response,
+) -> Result<T, Error> {
+  check_response(response)
+    .await?
+    .json()
+    .await
+    .map_err(Error::NetError)
+}
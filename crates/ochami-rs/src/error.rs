@@ -0,0 +1,75 @@
+use std::io;
+
+use serde_json::Value;
+
+use crate::common::http_client::get_service_name;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+  #[error("OCHAMI-RS: {0}")]
+  Message(String),
+  #[error("OCHAMI-RS > IO: {0}")]
+  IoError(#[from] io::Error),
+  #[error("OCHAMI-RS > Serde: {0}")]
+  SerdeError(#[from] serde_json::Error),
+  #[error("OCHAMI-RS > Net: {0}")]
+  NetError(#[from] reqwest::Error),
+  #[error("http request:\nresponse: {response}\npayload: {payload}")]
+  RequestError {
+    response: reqwest::Error,
+    payload: String, // NOTE: OCHAMI Apis either returns plain text or a json therefore, we
+                     // will just return a String
+  },
+  #[error("OCHAMI-RS > OCHAMI: {0}")]
+  OchamiError(Value),
+  /// OpenCHAMI service answered with an HTTP error status
+  #[error("OCHAMI-RS > {service}: HTTP {status} '{path}'. {detail}")]
+  HttpError {
+    service: String,
+    status: u16,
+    /// Path of the request (eg: '/hsm/v2/groups/zinal')
+    path: String,
+    /// Reason given by the service
+    detail: String,
+  },
+}
+
+impl From<Error> for manta_backend_dispatcher::error::Error {
+  fn from(error: Error) -> Self {
+    use manta_backend_dispatcher::error::Error as BackendError;
+
+    match error {
+      Error::Message(message) => BackendError::Message(message),
+      Error::IoError(e) => BackendError::IoError(e),
+      Error::SerdeError(e) => BackendError::SerdeError(e),
+      Error::NetError(e) if e.is_timeout() => BackendError::Timeout {
+        service: e
+          .url()
+          .map(|url| get_service_name(url.path()))
+          .unwrap_or("OCHAMI")
+          .to_string(),
+        detail: e.to_string(),
+      },
+      Error::NetError(e) => BackendError::NetError(e),
+      Error::RequestError { response, payload } => {
+        BackendError::RequestError { response, payload }
+      }
+      Error::OchamiError(payload) => BackendError::CsmError(payload),
+      Error::HttpError {
+        service,
+        status,
+        path,
+        detail,
+      } => {
+        // Last 2 segments of the path are the resource type and id
+        // (eg: '/hsm/v2/groups/zinal' -> 'groups' and 'zinal')
+        let mut segment_iter =
+          path.split('/').filter(|segment| !segment.is_empty()).rev();
+        let id = segment_iter.next().unwrap_or_default();
+        let resource = segment_iter.next().unwrap_or_default();
+
+        BackendError::from_http_status(&service, resource, id, status, &detail)
+      }
+    }
+  }
+}
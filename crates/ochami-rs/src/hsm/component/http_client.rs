@@ -0,0 +1,89 @@
+use manta_backend_dispatcher::types::{
+  ComponentArrayPostArray, NodeMetadataArray,
+};
+use serde_json::Value;
+
+use crate::{
+  common::http_client::{build_client, check_response, parse_response},
+  error::Error,
+};
+
+/// Get list of SMD components. `query` is the list of filters to apply (eg:
+/// `[("type", "Node"), ("nid", "1,2")]`), see https://github.com/OpenCHAMI/smd/blob/master/api/swagger_v2.yaml
+pub async fn get(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  query: &[(&str, &str)],
+) -> Result<NodeMetadataArray, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "hsm/v2/State/Components");
+
+  let response = client
+    .get(api_url)
+    .query(query)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+/// Get list of SMD components of type 'Node'
+pub async fn get_all_nodes(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  nid_only_opt: Option<&str>,
+) -> Result<NodeMetadataArray, Error> {
+  let mut query = vec![("type", "Node")];
+
+  if let Some(nid_only) = nid_only_opt {
+    query.push(("nidonly", nid_only));
+  }
+
+  get(auth_token, base_url, root_cert, &query).await
+}
+
+/// Create or update SMD components
+pub async fn post(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  component_vec: &ComponentArrayPostArray,
+) -> Result<(), Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "hsm/v2/State/Components");
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(component_vec)
+    .send()
+    .await?;
+
+  check_response(response).await.map(|_| ())
+}
+
+/// Delete a SMD component
+pub async fn delete_one(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  xname: &str,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/hsm/v2/State/Components/{}", base_url, xname);
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
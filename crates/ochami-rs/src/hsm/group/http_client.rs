@@ -0,0 +1,155 @@
+use manta_backend_dispatcher::types::{Group, XnameId};
+use serde_json::Value;
+
+use crate::{
+  common::http_client::{build_client, check_response, parse_response},
+  error::Error,
+};
+
+/// Get list of SMD groups --> https://github.com/OpenCHAMI/smd/blob/master/api/swagger_v2.yaml
+pub async fn get(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  label_vec_opt: Option<&[&str]>,
+  tag_vec_opt: Option<&[&str]>,
+) -> Result<Vec<Group>, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "hsm/v2/groups");
+
+  let mut query = Vec::new();
+
+  if let Some(label_vec) = label_vec_opt {
+    for label in label_vec {
+      query.push(("group", label));
+    }
+  }
+  if let Some(tag_vec) = tag_vec_opt {
+    for tag in tag_vec {
+      query.push(("tag", tag));
+    }
+  }
+
+  let response = client
+    .get(api_url)
+    .query(query.as_slice())
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+pub async fn get_all(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+) -> Result<Vec<Group>, Error> {
+  get(auth_token, base_url, root_cert, None, None).await
+}
+
+/// Get a SMD group by label
+pub async fn get_one(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  label: &str,
+) -> Result<Group, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/hsm/v2/groups/{}", base_url, label);
+
+  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
+
+  parse_response(response).await
+}
+
+/// Create a new SMD group
+pub async fn post(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  group: Group,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "hsm/v2/groups");
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(&group)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+/// Delete a SMD group
+pub async fn delete(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  label: &str,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/hsm/v2/groups/{}", base_url, label);
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+/// Add a member to a SMD group
+pub async fn post_member(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  label: &str,
+  xname: &str,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/hsm/v2/groups/{}/members", base_url, label);
+
+  let member = XnameId {
+    id: Some(xname.to_string()),
+  };
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(&member)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+/// Remove a member from a SMD group
+pub async fn delete_member(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  label: &str,
+  xname: &str,
+) -> Result<(), Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/hsm/v2/groups/{}/members/{}", base_url, label, xname);
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  check_response(response).await.map(|_| ())
+}
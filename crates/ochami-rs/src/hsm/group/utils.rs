@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use crate::{error::Error, hsm::group::http_client};
+
+/// Returns the list of members of a list of SMD groups
+pub async fn get_member_vec_from_group_name_vec(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  group_name_vec: &[&str],
+) -> Result<Vec<String>, Error> {
+  let group_vec = http_client::get(
+    auth_token,
+    base_url,
+    root_cert,
+    Some(group_name_vec),
+    None,
+  )
+  .await?;
+
+  let mut member_vec: Vec<String> = group_vec
+    .iter()
+    .flat_map(|group| group.get_members())
+    .collect();
+
+  member_vec.sort();
+  member_vec.dedup();
+
+  Ok(member_vec)
+}
+
+/// Returns a HashMap with keys being the group names and values the list of members of each
+/// group
+pub async fn get_group_map_and_filter_by_group_vec(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  group_name_vec: &[&str],
+) -> Result<HashMap<String, Vec<String>>, Error> {
+  let group_vec = http_client::get(
+    auth_token,
+    base_url,
+    root_cert,
+    Some(group_name_vec),
+    None,
+  )
+  .await?;
+
+  Ok(
+    group_vec
+      .into_iter()
+      .map(|group| (group.label.clone(), group.get_members()))
+      .collect(),
+  )
+}
+
+/// Returns a HashMap with keys being the group names and values the list of members of each
+/// group. Only groups with any of the members in `member_vec` are returned
+pub async fn get_group_map_and_filter_by_member_vec(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  member_vec: &[&str],
+) -> Result<HashMap<String, Vec<String>>, Error> {
+  let group_vec = http_client::get_all(auth_token, base_url, root_cert).await?;
+
+  Ok(
+    group_vec
+      .into_iter()
+      .map(|group| (group.label.clone(), group.get_members()))
+      .filter(|(_, group_member_vec)| {
+        group_member_vec
+          .iter()
+          .any(|member| member_vec.contains(&member.as_str()))
+      })
+      .collect(),
+  )
+}
+
+/// Adds a member to a group and returns the new list of group members
+pub async fn add_member(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  group_name: &str,
+  new_member: &str,
+) -> Result<Vec<String>, Error> {
+  let group =
+    http_client::get_one(auth_token, base_url, root_cert, group_name).await?;
+
+  let mut member_vec = group.get_members();
+
+  if !member_vec.iter().any(|member| member == new_member) {
+    http_client::post_member(
+      auth_token, base_url, root_cert, group_name, new_member,
+    )
+    .await?;
+
+    member_vec.push(new_member.to_string());
+  }
+
+  member_vec.sort();
+
+  Ok(member_vec)
+}
+
+/// Removes members in `old_member_vec` missing in `new_member_vec` and adds members in
+/// `new_member_vec` missing in `old_member_vec`
+pub async fn update_group_members(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  group_name: &str,
+  old_member_vec: &[String],
+  new_member_vec: &[String],
+) -> Result<(), Error> {
+  // Delete members
+  for old_member in old_member_vec {
+    if !new_member_vec.contains(old_member) {
+      http_client::delete_member(
+        auth_token, base_url, root_cert, group_name, old_member,
+      )
+      .await?;
+    }
+  }
+
+  // Add members
+  for new_member in new_member_vec {
+    if !old_member_vec.contains(new_member) {
+      http_client::post_member(
+        auth_token, base_url, root_cert, group_name, new_member,
+      )
+      .await?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Moves nodes from the parent group to the target group. Returns the new list of members of
+/// the target and parent groups
+pub async fn migrate_group_members(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  target_group_name: &str,
+  parent_group_name: &str,
+  new_target_member_vec: &[&str],
+) -> Result<(Vec<String>, Vec<String>), Error> {
+  let parent_group =
+    http_client::get_one(auth_token, base_url, root_cert, parent_group_name)
+      .await?;
+
+  let mut parent_member_vec = parent_group.get_members();
+
+  // Check nodes belong to parent group
+  let node_not_in_parent_vec: Vec<&str> = new_target_member_vec
+    .iter()
+    .filter(|xname| !parent_member_vec.iter().any(|member| member == *xname))
+    .copied()
+    .collect();
+
+  if !node_not_in_parent_vec.is_empty() {
+    return Err(Error::Message(format!(
+      "Nodes '{}' not valid, they don't belong to group '{}'",
+      node_not_in_parent_vec.join(", "),
+      parent_group_name
+    )));
+  }
+
+  let target_group =
+    http_client::get_one(auth_token, base_url, root_cert, target_group_name)
+      .await?;
+
+  let mut target_member_vec = target_group.get_members();
+
+  for xname in new_target_member_vec {
+    if !target_member_vec.iter().any(|member| member == xname) {
+      http_client::post_member(
+        auth_token,
+        base_url,
+        root_cert,
+        target_group_name,
+        xname,
+      )
+      .await?;
+
+      target_member_vec.push(xname.to_string());
+    }
+
+    http_client::delete_member(
+      auth_token,
+      base_url,
+      root_cert,
+      parent_group_name,
+      xname,
+    )
+    .await?;
+
+    parent_member_vec.retain(|member| member != xname);
+  }
+
+  target_member_vec.sort();
+  parent_member_vec.sort();
+
+  Ok((target_member_vec, parent_member_vec))
+}
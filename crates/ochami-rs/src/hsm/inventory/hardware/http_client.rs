@@ -0,0 +1,86 @@
+use manta_backend_dispatcher::types::HWInventoryByLocationList;
+use serde_json::Value;
+
+use crate::{
+  common::http_client::{build_client, parse_response},
+  error::Error,
+};
+
+/// Get hardware inventory of a component
+pub async fn get(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  xname: &str,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/hsm/v2/Inventory/Hardware/{}", base_url, xname);
+
+  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
+
+  parse_response(response).await
+}
+
+/// Query hardware inventory of a component and its children
+pub async fn get_query(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  xname: &str,
+  r#type: Option<&str>,
+  children: Option<bool>,
+  parents: Option<bool>,
+  partition: Option<&str>,
+  format: Option<&str>,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/hsm/v2/Inventory/Hardware/Query/{}", base_url, xname);
+
+  let children = children.map(|children| children.to_string());
+  let parents = parents.map(|parents| parents.to_string());
+
+  let query: Vec<(&str, &str)> = [
+    ("type", r#type),
+    ("children", children.as_deref()),
+    ("parents", parents.as_deref()),
+    ("partition", partition),
+    ("format", format),
+  ]
+  .into_iter()
+  .filter_map(|(key, value_opt)| value_opt.map(|value| (key, value)))
+  .collect();
+
+  let response = client
+    .get(api_url)
+    .query(&query)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+/// Create or update hardware inventory
+pub async fn post(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  hw_inventory: &HWInventoryByLocationList,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/{}", base_url, "hsm/v2/Inventory/Hardware");
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(hw_inventory)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
@@ -0,0 +1,2 @@
+pub mod hardware;
+pub mod redfish_endpoint;
@@ -0,0 +1,99 @@
+use manta_backend_dispatcher::types::hsm::inventory::{
+  RedfishEndpoint, RedfishEndpointArray,
+};
+use serde_json::Value;
+
+use crate::{
+  common::http_client::{build_client, check_response, parse_response},
+  error::Error,
+};
+
+/// Get list of redfish endpoints. `query` is the list of filters to apply (eg:
+/// `[("fqdn", "x1000c0s0b0.local")]`)
+pub async fn get(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  query: &[(&str, &str)],
+) -> Result<RedfishEndpointArray, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/{}", base_url, "hsm/v2/Inventory/RedfishEndpoints");
+
+  let response = client
+    .get(api_url)
+    .query(query)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+/// Create redfish endpoints
+pub async fn post(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  redfish_endpoint_vec: &RedfishEndpointArray,
+) -> Result<(), Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/{}", base_url, "hsm/v2/Inventory/RedfishEndpoints");
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(redfish_endpoint_vec)
+    .send()
+    .await?;
+
+  check_response(response).await.map(|_| ())
+}
+
+/// Update a redfish endpoint
+pub async fn put(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  redfish_endpoint: &RedfishEndpoint,
+) -> Result<(), Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!(
+    "{}/hsm/v2/Inventory/RedfishEndpoints/{}",
+    base_url, redfish_endpoint.id
+  );
+
+  let response = client
+    .put(api_url)
+    .bearer_auth(auth_token)
+    .json(redfish_endpoint)
+    .send()
+    .await?;
+
+  check_response(response).await.map(|_| ())
+}
+
+/// Delete a redfish endpoint
+pub async fn delete(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  id: &str,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/hsm/v2/Inventory/RedfishEndpoints/{}", base_url, id);
+
+  let response = client
+    .delete(api_url)
+    .bearer_auth(auth_token)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
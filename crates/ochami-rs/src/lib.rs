@@ -0,0 +1,6 @@
+pub mod backend_connector;
+pub mod bss;
+pub mod common;
+pub mod error;
+pub mod hsm;
+pub mod pcs;
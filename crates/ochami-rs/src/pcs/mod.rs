@@ -0,0 +1,2 @@
+pub mod power_status;
+pub mod transitions;
@@ -0,0 +1,40 @@
+use serde_json::json;
+
+use crate::{
+  common::http_client::{build_client, parse_response},
+  error::Error,
+  pcs::power_status::types::PowerStatusAll,
+};
+
+/// Get power status of a list of components --> https://github.com/OpenCHAMI/power-control/blob/main/api/swagger.yaml
+pub async fn post(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  xname_vec: &[String],
+  power_state_filter_opt: Option<&str>,
+  management_state_filter_opt: Option<&str>,
+) -> Result<PowerStatusAll, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/{}", base_url, "power-control/v1/power-status");
+
+  let mut request_payload = json!({ "xname": xname_vec });
+
+  if let Some(power_state_filter) = power_state_filter_opt {
+    request_payload["powerStateFilter"] = json!(power_state_filter);
+  }
+  if let Some(management_state_filter) = management_state_filter_opt {
+    request_payload["managementStateFilter"] = json!(management_state_filter);
+  }
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(&request_payload)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
@@ -0,0 +1,84 @@
+use manta_backend_dispatcher::types::pcs::{
+  power_status::types::{
+    ManagementState as FrontEndManagementState,
+    PowerState as FrontEndPowerState, PowerStatus as FrontEndPowerStatus,
+    PowerStatusAll as FrontEndPowerStatusAll,
+  },
+  transitions::types::Operation as FrontEndOperation,
+};
+use serde::{Deserialize, Serialize};
+
+/// Power status of a component as returned by PCS
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerStatus {
+  pub xname: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "powerState")]
+  pub power_state: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "managementState")]
+  pub management_state: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  #[serde(default)]
+  #[serde(rename = "supportedPowerTransitions")]
+  pub supported_power_transitions: Vec<String>,
+  #[serde(default)]
+  #[serde(rename = "lastUpdated")]
+  pub last_updated: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerStatusAll {
+  pub status: Vec<PowerStatus>,
+}
+
+fn to_frontend_operation(operation: &str) -> Option<FrontEndOperation> {
+  match operation.to_lowercase().as_str() {
+    "on" => Some(FrontEndOperation::On),
+    "off" => Some(FrontEndOperation::Off),
+    "soft-off" => Some(FrontEndOperation::SoftOff),
+    "soft-restart" => Some(FrontEndOperation::SoftRestart),
+    "hard-restart" => Some(FrontEndOperation::HardRestart),
+    "init" => Some(FrontEndOperation::Init),
+    "force-off" => Some(FrontEndOperation::ForceOff),
+    _ => None,
+  }
+}
+
+impl From<PowerStatus> for FrontEndPowerStatus {
+  fn from(value: PowerStatus) -> Self {
+    FrontEndPowerStatus {
+      xname: value.xname,
+      power_state: value.power_state.map(|power_state| {
+        match power_state.to_lowercase().as_str() {
+          "on" => FrontEndPowerState::On,
+          "off" => FrontEndPowerState::Off,
+          _ => FrontEndPowerState::Undefined,
+        }
+      }),
+      management_state: value.management_state.map(|management_state| {
+        if management_state.eq_ignore_ascii_case("available") {
+          FrontEndManagementState::Available
+        } else {
+          FrontEndManagementState::Unavailable
+        }
+      }),
+      error: value.error,
+      supported_power_transitions: value
+        .supported_power_transitions
+        .iter()
+        .filter_map(|operation| to_frontend_operation(operation))
+        .collect(),
+      last_updated: value.last_updated,
+    }
+  }
+}
+
+impl From<PowerStatusAll> for FrontEndPowerStatusAll {
+  fn from(value: PowerStatusAll) -> Self {
+    FrontEndPowerStatusAll {
+      status: value.status.into_iter().map(Into::into).collect(),
+    }
+  }
+}
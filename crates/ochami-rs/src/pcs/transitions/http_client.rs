@@ -0,0 +1,149 @@
+use std::time;
+
+use serde_json::{json, Value};
+
+use crate::{
+  common::http_client::{build_client, parse_response},
+  error::Error,
+};
+
+/// Max number of times a transition status is checked before giving up
+const MAX_ATTEMPTS: usize = 300;
+
+/// Get a power transition --> https://github.com/OpenCHAMI/power-control/blob/main/api/swagger.yaml
+pub async fn get_by_id(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  transition_id: &str,
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!(
+    "{}/power-control/v1/transitions/{}",
+    base_url, transition_id
+  );
+
+  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
+
+  parse_response(response).await
+}
+
+/// Get all power transitions
+pub async fn get(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+) -> Result<Vec<Value>, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String = format!("{}/power-control/v1/transitions", base_url);
+
+  let response = client.get(api_url).bearer_auth(auth_token).send().await?;
+
+  let transitions: Value = parse_response(response).await?;
+
+  Ok(
+    transitions["transitions"]
+      .as_array()
+      .cloned()
+      .unwrap_or_default(),
+  )
+}
+
+/// Create a power transition. Operation is one of 'on', 'off', 'soft-off', 'soft-restart',
+/// 'hard-restart', 'init' or 'force-off'
+pub async fn post(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  operation: &str,
+  xname_vec: &[String],
+) -> Result<Value, Error> {
+  let client = build_client(root_cert)?;
+
+  let api_url: String =
+    format!("{}/{}", base_url, "power-control/v1/transitions");
+
+  let request_payload = json!({
+    "operation": operation,
+    "location": xname_vec
+      .iter()
+      .map(|xname| json!({ "xname": xname }))
+      .collect::<Vec<Value>>(),
+  });
+
+  let response = client
+    .post(api_url)
+    .bearer_auth(auth_token)
+    .json(&request_payload)
+    .send()
+    .await?;
+
+  parse_response(response).await
+}
+
+/// Create a power transition and wait for it to complete
+pub async fn post_block(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  operation: &str,
+  xname_vec: &[String],
+) -> Result<Value, Error> {
+  let transition =
+    post(auth_token, base_url, root_cert, operation, xname_vec).await?;
+
+  let transition_id = transition["transitionID"].as_str().ok_or_else(|| {
+    Error::Message(format!(
+      "PCS transition ID missing in response:\n{}",
+      transition
+    ))
+  })?;
+
+  log::info!("PCS transition ID: {}", transition_id);
+
+  wait_to_complete(auth_token, base_url, root_cert, transition_id).await
+}
+
+/// Polls a power transition until it completes
+pub async fn wait_to_complete(
+  auth_token: &str,
+  base_url: &str,
+  root_cert: &[u8],
+  transition_id: &str,
+) -> Result<Value, Error> {
+  for attempt in 1..=MAX_ATTEMPTS {
+    let transition =
+      get_by_id(auth_token, base_url, root_cert, transition_id).await?;
+
+    let transition_status = transition["transitionStatus"].as_str();
+
+    log::info!(
+      "Power '{}' summary - status: {}, failed: {}, in-progress: {}, succeeded: {}, total: {}. Attempt {} of {}",
+      transition["operation"].as_str().unwrap_or_default(),
+      transition_status.unwrap_or_default(),
+      transition.pointer("/taskCounts/failed").unwrap_or(&Value::Null),
+      transition.pointer("/taskCounts/in-progress").unwrap_or(&Value::Null),
+      transition.pointer("/taskCounts/succeeded").unwrap_or(&Value::Null),
+      transition.pointer("/taskCounts/total").unwrap_or(&Value::Null),
+      attempt,
+      MAX_ATTEMPTS
+    );
+
+    if transition_status == Some("completed") {
+      return Ok(transition);
+    }
+
+    if matches!(transition_status, Some("aborted")) {
+      return Err(Error::OchamiError(transition));
+    }
+
+    tokio::time::sleep(time::Duration::from_secs(3)).await;
+  }
+
+  Err(Error::Message(format!(
+    "PCS transition '{}' did not complete after {} attempts",
+    transition_id, MAX_ATTEMPTS
+  )))
+}